@@ -1,7 +1,8 @@
 use std::path::PathBuf;
 
+use anise::naif::names::body_name_to_id;
 use clap::{Args, Parser, Subcommand};
-use hifitime::Epoch;
+use hifitime::{Duration, Epoch};
 
 #[derive(Parser, Debug)]
 #[clap(name="ANISE", author="Rabotin and ANISE contributors", version, about, long_about = None)]
@@ -10,7 +11,7 @@ pub struct CliArgs {
     pub action: Actions,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Subcommand)]
+#[derive(Debug, PartialEq, Subcommand)]
 pub enum Actions {
     /// Checks the integrity of the file
     Check {
@@ -40,13 +41,41 @@ pub enum Actions {
         /// Output ANISE binary file
         outfile: PathBuf,
     },
+    /// Migrates an ANISE data set of an older minor version of ANISE to the current version
+    Upgrade {
+        /// Path to ANISE file
+        file: PathBuf,
+        /// Output ANISE file path, the input file is overwritten if not set
+        #[clap(long)]
+        output: Option<PathBuf>,
+    },
     /// Truncate the segment of the provided ID of the input NAIF DAF file to the provided start and end epochs
     /// Limitation: this may not work correctly if there are several segments with the same ID.
     /// Only works with Chebyshev Type 2 data types (i.e. planetary ephemerides).
     TruncDAFById(TruncateById),
+    /// Truncate all of the segments of the input NAIF DAF file (SPK or BPC) to the provided start and end epochs, removing the segments outside of these epochs.
+    /// Only the Chebyshev Type 2 and Type 3 segments can be truncated, other segments overlapping these epochs are kept whole.
+    Truncate(Truncate),
     /// Remove the segment of the provided ID of the input NAIF DAF file.
     /// Limitation: this may not work correctly if there are several segments with the same ID.
     RmDAFById(RmById),
+    /// Reports the time coverage of each segment of a NAIF DAF file (SPK or BPC), and the gaps and overlaps between the segments of each ID
+    Coverage(Coverage),
+    /// Compares the ephemerides of the candidate file to those of the reference file, for each object of the reference with respect to its center.
+    Diff(Diff),
+    /// Validates the ephemerides of the provided kernels against SPICE, as an acceptance test of a kernel delivery.
+    /// Requires the `spice` feature, which links to CSPICE.
+    Validate(Validate),
+    /// Bundles all of the supported kernels of a directory tree (SPK, BPC, LSK, SCLK, ANISE data sets, and text PCK and FK,
+    /// which are converted) into a single ANISE bundle with a manifest, which can be loaded like any other file.
+    /// Fails if a frame or a center used by the SPKs or BPCs is not defined by the bundle.
+    Bundle(Bundle),
+    /// Re-fits all of the segments of the input SPK (e.g. dense Hermite or Lagrange segments from an orbit determination) into
+    /// Chebyshev Type 2 segments of the provided degree and record length, and reports the fit residuals of each segment.
+    Refit(Refit),
+    /// Computes the state of the target with respect to the observer at the provided epoch from the provided kernels.
+    /// With `--verbose`, also prints which file and segment answered each link of the query.
+    Query(Query),
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Args)]
@@ -55,7 +84,8 @@ pub(crate) struct RmById {
     pub input: PathBuf,
     /// Output DAF file path
     pub output: PathBuf,
-    /// ID of the segment to truncate
+    /// ID of the segment to remove, or its standard NAIF body name (e.g. `MOON`)
+    #[clap(value_parser = parse_body_id)]
     pub id: i32,
 }
 
@@ -65,10 +95,148 @@ pub(crate) struct TruncateById {
     pub input: PathBuf,
     /// Output DAF file path
     pub output: PathBuf,
-    /// ID of the segment to truncate
+    /// ID of the segment to truncate, or its standard NAIF body name (e.g. `MOON`)
+    #[clap(value_parser = parse_body_id)]
     pub id: i32,
     /// New start epoch of the segment
     pub start: Option<Epoch>,
     /// New end epoch of the segment
     pub end: Option<Epoch>,
 }
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Args)]
+pub(crate) struct Truncate {
+    /// Input DAF file, SPK or BPC
+    pub input: PathBuf,
+    /// Start epoch of the data to keep
+    pub start: Epoch,
+    /// End epoch of the data to keep
+    pub end: Epoch,
+    /// Output DAF file path, the input file is overwritten if not set
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Args)]
+pub(crate) struct Diff {
+    /// Reference file, e.g. the current SPK
+    pub reference: PathBuf,
+    /// Candidate file, e.g. the new SPK
+    pub candidate: PathBuf,
+    /// Start epoch of the comparison
+    pub start: Epoch,
+    /// End epoch of the comparison
+    pub end: Epoch,
+    /// Number of epochs at which each object is compared
+    #[clap(long, default_value_t = 100)]
+    pub num_epochs: usize,
+}
+
+#[derive(Debug, PartialEq, Args)]
+pub(crate) struct Validate {
+    /// Kernels to validate, loaded both in ANISE and in SPICE
+    #[clap(required = true)]
+    pub kernels: Vec<PathBuf>,
+    /// Pair of target and observer to validate as `TARGET:OBSERVER`, by ID or standard NAIF body name (e.g. `MOON:EARTH`),
+    /// may be repeated. Defaults to each object of the kernels with respect to its center.
+    #[clap(long = "pair", value_parser = parse_pair)]
+    pub pairs: Vec<(i32, i32)>,
+    /// Number of epochs at which each pair is compared, evenly spread over the common coverage of the pair
+    #[clap(long, default_value_t = 1_000)]
+    pub num_samples: usize,
+    /// Maximum absolute difference of any component, in km or km/s
+    #[clap(long, default_value_t = 0.1)]
+    pub max_abs_err: f64,
+    /// Maximum 99th percentile of the absolute differences of the components, in km or km/s
+    #[clap(long, default_value_t = 5e-3)]
+    pub max_q99_err: f64,
+    /// Writes each compared component to this Parquet file
+    #[clap(long)]
+    pub parquet: Option<PathBuf>,
+    /// Writes the summary of the validation to this HTML file
+    #[clap(long)]
+    pub html: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Args)]
+pub(crate) struct Coverage {
+    /// Input DAF file, SPK or BPC
+    pub input: PathBuf,
+    /// Only report the coverage of this ID, or of this standard NAIF body name (e.g. `MOON`)
+    #[clap(long, value_parser = parse_body_id)]
+    pub id: Option<i32>,
+    /// Print the report as JSON
+    #[clap(long)]
+    pub json: bool,
+    /// Also report the position and velocity jumps at the boundaries between the segments of each ID (SPK only)
+    #[clap(long)]
+    pub continuity: bool,
+}
+
+/// Parses a NAIF ID from its integer value or from its standard NAIF body name.
+fn parse_body_id(arg: &str) -> Result<i32, String> {
+    body_name_to_id(arg)
+        .ok_or_else(|| format!("`{arg}` is neither a NAIF ID nor a known body name"))
+}
+
+/// Parses a pair of target and observer formatted as `TARGET:OBSERVER`.
+fn parse_pair(arg: &str) -> Result<(i32, i32), String> {
+    let (target, observer) = arg
+        .split_once(':')
+        .ok_or_else(|| format!("`{arg}` is not formatted as TARGET:OBSERVER"))?;
+    Ok((
+        parse_body_id(target.trim())?,
+        parse_body_id(observer.trim())?,
+    ))
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Args)]
+pub(crate) struct Bundle {
+    /// Directory of the kernels, including its subdirectories
+    pub dir: PathBuf,
+    /// Output ANISE bundle file
+    #[clap(short, long)]
+    pub output: PathBuf,
+    /// Overwrite the output file if it exists
+    #[clap(long)]
+    pub overwrite: bool,
+    /// Write the bundle even if some frames or centers used by the SPKs or BPCs are not defined
+    #[clap(long)]
+    pub allow_unresolved: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Args)]
+pub(crate) struct Refit {
+    /// Input SPK file
+    pub input: PathBuf,
+    /// Output SPK file path
+    pub output: PathBuf,
+    /// Degree of the Chebyshev polynomials
+    #[clap(long, default_value_t = 15)]
+    pub degree: usize,
+    /// Maximum length of each Chebyshev record, e.g. `1 day` or `4 h`
+    #[clap(long, default_value = "1 day")]
+    pub interval: Duration,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Args)]
+pub(crate) struct Query {
+    /// Kernels to load, where the kernels loaded last have precedence
+    #[clap(required = true)]
+    pub kernels: Vec<PathBuf>,
+    /// Target, by ID or standard NAIF body name (e.g. `MOON`)
+    #[clap(long, value_parser = parse_body_id)]
+    pub target: i32,
+    /// Observer, by ID or standard NAIF body name (e.g. `EARTH`)
+    #[clap(long, value_parser = parse_body_id)]
+    pub observer: i32,
+    /// Epoch of the query
+    #[clap(long)]
+    pub epoch: Epoch,
+    /// Orientation ID of the state, J2000 by default
+    #[clap(long, default_value_t = 1)]
+    pub orientation: i32,
+    /// Print the file, segment, coverage, and data type which answered each link of the query
+    #[clap(short, long)]
+    pub verbose: bool,
+}
@@ -0,0 +1,245 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anise::almanac::bundle::{KernelBundle, BUNDLE_MAGIC};
+use anise::file2heap;
+use anise::math::rotation::Quaternion;
+use anise::naif::daf::{FileRecord, NAIFRecord};
+use anise::naif::kpl::fk::FKItem;
+use anise::naif::kpl::parser::{convert_fk_items, convert_tpc_items, parse_bytes};
+use anise::naif::kpl::tpc::TPCItem;
+use anise::prelude::*;
+use anise::structure::dataset::{DataSetT, DataSetType};
+use anise::structure::maneuver::ManeuverPlan;
+use anise::structure::metadata::Metadata;
+use anise::structure::planetocentric::PlanetaryData;
+use anise::structure::spacecraft::SpacecraftData;
+use bytes::Bytes;
+use log::{error, info, warn};
+use snafu::prelude::*;
+use zerocopy::FromBytes;
+
+use crate::args::Bundle;
+use crate::{
+    AniseSnafu, ArgumentSnafu, CliAlmanacSnafu, CliDataSetSnafu, CliErrors, FileNotFoundSnafu,
+    FilePersistSnafu,
+};
+
+/// Bundles the supported kernels of a directory tree into a single ANISE bundle.
+///
+/// DAF files (SPK and BPC), leap seconds and clock kernels, and ANISE data sets are copied as is. All of the text
+/// planetary constants kernels are converted into a single planetary data set, and all of the frame kernels into a
+/// single Euler parameter data set, since an Almanac holds at most one data set of each kind.
+pub(crate) fn bundle(args: Bundle) -> Result<(), CliErrors> {
+    ensure!(
+        args.overwrite || !args.output.exists(),
+        ArgumentSnafu {
+            arg: format!(
+                "{} exists, use --overwrite to replace it",
+                args.output.display()
+            )
+        }
+    );
+
+    let mut paths = Vec::new();
+    find_files(&args.dir, &mut paths).context(FileNotFoundSnafu)?;
+    paths.sort();
+
+    let mut bundle = KernelBundle::default();
+    let mut tpc_items: HashMap<i32, TPCItem> = HashMap::new();
+    let mut tpc_names = Vec::new();
+    let mut fk_items: HashMap<i32, FKItem> = HashMap::new();
+    let mut fk_names = Vec::new();
+    let mut datasets: Vec<(DataSetType, String)> = Vec::new();
+
+    for path in paths {
+        if path == args.output {
+            continue;
+        }
+
+        let name = path
+            .strip_prefix(&args.dir)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        let bytes = file2heap!(path).context(AniseSnafu)?;
+
+        if bytes.starts_with(BUNDLE_MAGIC) {
+            warn!("skipping {name}: bundles cannot be nested");
+        } else if bytes.starts_with(b"KPL/PCK") {
+            info!("converting {name} as KPL/PCK");
+            for (id, item) in
+                parse_bytes::<_, TPCItem>(&mut bytes.as_ref(), false).context(CliDataSetSnafu)?
+            {
+                let known = tpc_items.entry(id).or_default();
+                known.body_id = item.body_id.or(known.body_id);
+                known.data.extend(item.data);
+            }
+            tpc_names.push(name);
+        } else if bytes.starts_with(b"KPL/FK") {
+            info!("converting {name} as KPL/FK");
+            for (id, item) in
+                parse_bytes::<_, FKItem>(&mut bytes.as_ref(), false).context(CliDataSetSnafu)?
+            {
+                let known = fk_items.entry(id).or_default();
+                known.body_id = item.body_id.or(known.body_id);
+                known.name = item.name.or(known.name.take());
+                known.data.extend(item.data);
+            }
+            fk_names.push(name);
+        } else if bytes.starts_with(b"KPL/LSK") {
+            info!("adding {name} as KPL/LSK");
+            bundle.push("LSK", &name, bytes);
+        } else if bytes.starts_with(b"KPL/SCLK") {
+            info!("adding {name} as KPL/SCLK");
+            bundle.push("SCLK", &name, bytes);
+        } else if bytes.starts_with(b"KPL/") {
+            warn!("skipping {name}: this text kernel is not supported");
+        } else if let Ok(metadata) = Metadata::decode_header(&bytes) {
+            let kind = dataset_kind(metadata.dataset_type);
+            if let Some((_, other)) = datasets.iter().find(|(t, _)| *t == metadata.dataset_type) {
+                return Err(CliErrors::ArgumentError {
+                    arg: format!(
+                        "{name} and {other} are both {kind} but a bundle may only contain one data set of each kind"
+                    ),
+                });
+            }
+            info!("adding {name} as ANISE {kind}");
+            datasets.push((metadata.dataset_type, name.clone()));
+            bundle.push(kind, &name, bytes);
+        } else if let Some(kind) = daf_kind(&bytes) {
+            info!("adding {name} as DAF/{kind}");
+            bundle.push(kind, &name, bytes);
+        } else {
+            warn!("skipping {name}: not a supported kernel");
+        }
+    }
+
+    if !tpc_items.is_empty() {
+        let name = tpc_names.join("+");
+        if let Some((_, other)) = datasets
+            .iter()
+            .find(|(t, _)| *t == DataSetType::PlanetaryData)
+        {
+            return Err(CliErrors::ArgumentError {
+                arg: format!(
+                    "{name} would be converted to planetary data, which {other} already is"
+                ),
+            });
+        }
+        let dataset = convert_tpc_items(tpc_items, HashMap::new()).context(CliDataSetSnafu)?;
+        info!(
+            "{name} converted into {} planetary constants",
+            dataset.len()
+        );
+        bundle
+            .push_dataset(&name, &dataset)
+            .context(CliDataSetSnafu)?;
+    }
+
+    if !fk_items.is_empty() {
+        let name = fk_names.join("+");
+        if let Some((_, other)) = datasets
+            .iter()
+            .find(|(t, _)| *t == DataSetType::EulerParameterData)
+        {
+            return Err(CliErrors::ArgumentError {
+                arg: format!(
+                    "{name} would be converted to Euler parameters, which {other} already is"
+                ),
+            });
+        }
+        let dataset = convert_fk_items(fk_items).context(CliDataSetSnafu)?;
+        info!("{name} converted into {} frames", dataset.len());
+        bundle
+            .push_dataset(&name, &dataset)
+            .context(CliDataSetSnafu)?;
+    }
+
+    ensure!(
+        !bundle.is_empty(),
+        ArgumentSnafu {
+            arg: format!("no supported kernel found in {}", args.dir.display())
+        }
+    );
+
+    // Load the bundle as any user would, to check that it is usable and self-consistent.
+    let bytes = bundle.to_bytes();
+    let almanac = Almanac::default()
+        .load_from_bytes(Bytes::from(bytes.clone()))
+        .context(CliAlmanacSnafu)?;
+
+    let unresolved = almanac.unresolved_references().context(CliAlmanacSnafu)?;
+    for reference in &unresolved {
+        if args.allow_unresolved {
+            warn!("{reference}");
+        } else {
+            error!("{reference}");
+        }
+    }
+    ensure!(
+        args.allow_unresolved || unresolved.is_empty(),
+        ArgumentSnafu {
+            arg: format!(
+                "{} references cannot be resolved, use --allow-unresolved to bundle anyway",
+                unresolved.len()
+            )
+        }
+    );
+
+    fs::write(&args.output, bytes).context(FilePersistSnafu)?;
+
+    println!("{}", bundle.manifest());
+    info!(
+        "[OK] {} kernels bundled into {}",
+        bundle.len(),
+        args.output.display()
+    );
+
+    Ok(())
+}
+
+/// Recursively finds all of the files in this directory.
+fn find_files(dir: &Path, paths: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_files(&path, paths)?;
+        } else {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the kind of this DAF file, if it is supported.
+fn daf_kind(bytes: &[u8]) -> Option<&'static str> {
+    let file_record = FileRecord::read_from_bytes(bytes.get(..FileRecord::SIZE)?).ok()?;
+    match file_record.identification().ok()? {
+        "SPK" => Some("SPK"),
+        "PCK" => Some("BPC"),
+        _ => None,
+    }
+}
+
+/// Returns the kind of this data set, as named by the bundle when converting kernels.
+fn dataset_kind(dataset_type: DataSetType) -> &'static str {
+    match dataset_type {
+        DataSetType::NotApplicable => "unknown data",
+        DataSetType::SpacecraftData => SpacecraftData::NAME,
+        DataSetType::PlanetaryData => PlanetaryData::NAME,
+        DataSetType::EulerParameterData => Quaternion::NAME,
+        DataSetType::ManeuverData => ManeuverPlan::NAME,
+    }
+}
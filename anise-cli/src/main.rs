@@ -4,15 +4,20 @@ use std::env::{set_var, var};
 use std::io;
 use std::path::PathBuf;
 
+use anise::compare::Comparator;
+use anise::ephemerides::refit::refit_spk;
+use anise::errors::{AlmanacError, EphemerisSnafu};
 use anise::math::interpolation::InterpolationError;
-use anise::naif::daf::datatypes::Type2ChebyshevSet;
+use anise::naif::daf::daf::MutDAF;
+use anise::naif::daf::datatypes::{Type2ChebyshevSet, Type3ChebyshevSet};
 use anise::naif::daf::{DafDataType, NAIFDataSet, DAF};
+use anise::naif::names::body_id_to_name;
 use anise::naif::pck::BPCSummaryRecord;
 use anise::naif::pretty_print::NAIFPrettyPrint;
 use anise::naif::spk::summary::SPKSummaryRecord;
 use bytes::Bytes;
 use clap::Parser;
-use log::info;
+use log::{info, warn};
 use snafu::prelude::*;
 use zerocopy::FromBytes;
 
@@ -20,12 +25,17 @@ use anise::file2heap;
 use anise::naif::daf::{file_record::FileRecordError, DAFError, FileRecord, NAIFRecord};
 use anise::naif::kpl::parser::{convert_fk, convert_tpc};
 use anise::prelude::*;
-use anise::structure::dataset::{DataSetError, DataSetType};
+use anise::structure::dataset::{DataSet, DataSetError, DataSetT, DataSetType};
 use anise::structure::metadata::Metadata;
-use anise::structure::{EulerParameterDataSet, PlanetaryDataSet, SpacecraftDataSet};
+use anise::structure::{
+    EulerParameterDataSet, ManeuverDataSet, PlanetaryDataSet, SpacecraftDataSet,
+};
 
 mod args;
 use args::{Actions, CliArgs};
+mod bundle;
+#[cfg(feature = "spice")]
+mod validate;
 
 const LOG_VAR: &str = "ANISE_LOG";
 
@@ -62,6 +72,15 @@ pub enum CliErrors {
     SegmentInterpolation {
         source: InterpolationError,
     },
+    CliAlmanac {
+        source: AlmanacError,
+    },
+    /// SPICE error encountered when loading a kernel
+    #[snafu(display("SPICE failed to load {path}: {err}"))]
+    Spice {
+        path: String,
+        err: String,
+    },
 }
 
 fn main() -> Result<(), CliErrors> {
@@ -109,6 +128,13 @@ fn main() -> Result<(), CliErrors> {
                         println!("{dataset}");
                         Ok(())
                     }
+                    DataSetType::ManeuverData => {
+                        // Decode as maneuver data
+                        let dataset =
+                            ManeuverDataSet::try_from_bytes(bytes).context(CliDataSetSnafu)?;
+                        println!("{dataset}");
+                        Ok(())
+                    }
                 }
             } else {
                 // Load the header only
@@ -153,12 +179,36 @@ fn main() -> Result<(), CliErrors> {
             Ok(())
         }
         Actions::ConvertFk { fkfile, outfile } => {
-            let dataset = convert_fk(fkfile, false).unwrap();
+            let dataset = convert_fk(fkfile, false).context(CliDataSetSnafu)?;
 
             dataset.save_as(&outfile, false).context(CliDataSetSnafu)?;
 
             Ok(())
         }
+        Actions::Upgrade { file, output } => {
+            let bytes = file2heap!(file).context(AniseSnafu)?;
+            let output = output.unwrap_or(file);
+            let metadata =
+                Metadata::decode_header(&bytes).map_err(|e| CliErrors::ArgumentError {
+                    arg: format!("not an ANISE data set: {e}"),
+                })?;
+
+            match metadata.dataset_type {
+                DataSetType::NotApplicable => unreachable!("no such ANISE data yet"),
+                DataSetType::SpacecraftData => {
+                    upgrade_dataset(SpacecraftDataSet::try_from_bytes(bytes), output)
+                }
+                DataSetType::PlanetaryData => {
+                    upgrade_dataset(PlanetaryDataSet::try_from_bytes(bytes), output)
+                }
+                DataSetType::EulerParameterData => {
+                    upgrade_dataset(EulerParameterDataSet::try_from_bytes(bytes), output)
+                }
+                DataSetType::ManeuverData => {
+                    upgrade_dataset(ManeuverDataSet::try_from_bytes(bytes), output)
+                }
+            }
+        }
         Actions::TruncDAFById(action) => {
             ensure!(
                 action.start.is_some() || action.end.is_some(),
@@ -188,7 +238,470 @@ fn main() -> Result<(), CliErrors> {
                 }),
             }
         }
+        Actions::Truncate(action) => {
+            let (bytes, file_record) = read_and_record(action.input.clone())?;
+
+            match file_record.identification().context(CliFileRecordSnafu)? {
+                "PCK" => truncate_daf::<BPCSummaryRecord>(action, bytes),
+                "SPK" => truncate_daf::<SPKSummaryRecord>(action, bytes),
+                fileid => Err(CliErrors::ArgumentError {
+                    arg: format!("{fileid} is not supported yet"),
+                }),
+            }
+        }
+        Actions::Coverage(action) => {
+            let (bytes, file_record) = read_and_record(action.input.clone())?;
+
+            match file_record.identification().context(CliFileRecordSnafu)? {
+                "PCK" => coverage::<BPCSummaryRecord>(action, bytes, None),
+                "SPK" => {
+                    let almanac = if action.continuity {
+                        let spk = SPK::parse(bytes.clone()).context(CliDAFSnafu)?;
+                        Some(
+                            Almanac::from_spk(spk).map_err(|e| CliErrors::ArgumentError {
+                                arg: format!("{e}"),
+                            })?,
+                        )
+                    } else {
+                        None
+                    };
+                    coverage::<SPKSummaryRecord>(action, bytes, almanac)
+                }
+                fileid => Err(CliErrors::ArgumentError {
+                    arg: format!("{fileid} is not supported yet"),
+                }),
+            }
+        }
+        Actions::Diff(action) => diff(action),
+        #[cfg(feature = "spice")]
+        Actions::Validate(action) => validate::validate(action),
+        #[cfg(not(feature = "spice"))]
+        Actions::Validate(_) => Err(CliErrors::ArgumentError {
+            arg: "validation requires anise-cli to be built with the `spice` feature".to_string(),
+        }),
+        Actions::Bundle(action) => bundle::bundle(action),
+        Actions::Refit(action) => refit(action),
+        Actions::Query(action) => query(action),
+    }
+}
+
+fn upgrade_dataset<T: DataSetT, const ENTRIES: usize>(
+    dataset: Result<DataSet<T, ENTRIES>, DataSetError>,
+    output: PathBuf,
+) -> Result<(), CliErrors> {
+    let mut dataset = dataset.context(CliDataSetSnafu)?;
+    let version = dataset.metadata.anise_version;
+
+    if dataset.upgrade().context(CliDataSetSnafu)? {
+        dataset.save_as(&output, true).context(CliDataSetSnafu)?;
+        info!(
+            "[OK] upgraded {} data set from version {}.{}.{} to {output:?}",
+            T::NAME,
+            version.major,
+            version.minor,
+            version.patch
+        );
+    } else {
+        info!(
+            "{} data set already at the current version {}.{}.{}",
+            T::NAME,
+            version.major,
+            version.minor,
+            version.patch
+        );
+    }
+
+    Ok(())
+}
+
+fn truncate_daf<R>(
+    args::Truncate {
+        input,
+        start,
+        end,
+        output,
+    }: args::Truncate,
+    bytes: Bytes,
+) -> Result<(), CliErrors>
+where
+    R: NAIFSummaryRecord,
+{
+    ensure!(
+        start < end,
+        ArgumentSnafu {
+            arg: format!("start epoch {start} must be before end epoch {end}")
+        }
+    );
+
+    info!("Loading {input:?} as DAF/{}", R::NAME);
+    let daf = DAF::<R>::parse(bytes).context(CliDAFSnafu)?;
+    let summaries = daf.data_summaries().context(CliDAFSnafu)?.to_vec();
+
+    let mut daf_mut = daf.to_mutable();
+    // Process the segments from the last one, such that the indexes of the segments left to process do not change.
+    for (idx, summary) in summaries.iter().enumerate().rev() {
+        if summary.is_empty() {
+            continue;
+        }
+
+        if summary.end_epoch() < start || summary.start_epoch() > end {
+            info!(
+                "Removing segment #{idx} of ID {} from {} to {}",
+                summary.id(),
+                summary.start_epoch(),
+                summary.end_epoch()
+            );
+            daf_mut.delete_nth_data(idx).context(CliDAFSnafu)?;
+            continue;
+        }
+
+        let new_start = (summary.start_epoch() < start).then_some(start);
+        let new_end = (summary.end_epoch() > end).then_some(end);
+        if new_start.is_none() && new_end.is_none() {
+            continue;
+        }
+
+        let data_type = summary.data_type().map_err(|err| CliErrors::CliDataType {
+            error: Box::new(err),
+        })?;
+        match data_type {
+            DafDataType::Type2ChebyshevTriplet => truncate_segment::<R, Type2ChebyshevSet>(
+                &daf,
+                &mut daf_mut,
+                idx,
+                summary,
+                new_start,
+                new_end,
+            )?,
+            DafDataType::Type3ChebyshevSextuplet => truncate_segment::<R, Type3ChebyshevSet>(
+                &daf,
+                &mut daf_mut,
+                idx,
+                summary,
+                new_start,
+                new_end,
+            )?,
+            _ => warn!(
+                "Segment #{idx} of ID {} is of type {data_type:?} which cannot be truncated, keeping it whole",
+                summary.id()
+            ),
+        }
+    }
+
+    let output = output.unwrap_or(input);
+    info!("Saving file to {output:?}");
+    daf_mut.persist(output).context(FilePersistSnafu)?;
+
+    Ok(())
+}
+
+/// Truncates the n-th segment of the original DAF and sets it in the mutable DAF.
+fn truncate_segment<'a, R, S>(
+    daf: &'a DAF<R>,
+    daf_mut: &mut MutDAF<R>,
+    idx: usize,
+    summary: &R,
+    new_start: Option<Epoch>,
+    new_end: Option<Epoch>,
+) -> Result<(), CliErrors>
+where
+    R: NAIFSummaryRecord,
+    S: NAIFDataSet<'a>,
+{
+    info!(
+        "Truncating segment #{idx} of ID {} from {} to {}",
+        summary.id(),
+        new_start.unwrap_or_else(|| summary.start_epoch()),
+        new_end.unwrap_or_else(|| summary.end_epoch())
+    );
+
+    let segment = daf.nth_data::<S>(idx).context(CliDAFSnafu)?;
+    let updated_segment = segment
+        .truncate(summary, new_start, new_end)
+        .context(SegmentInterpolationSnafu)?;
+
+    daf_mut
+        .set_nth_data(
+            idx,
+            updated_segment,
+            new_start.unwrap_or_else(|| summary.start_epoch()),
+            new_end.unwrap_or_else(|| summary.end_epoch()),
+        )
+        .context(CliDAFSnafu)
+}
+
+/// Reports the coverage of the segments of the DAF, and the continuity of the segments of each ID if the Almanac of that SPK is provided.
+fn coverage<R>(
+    args::Coverage {
+        input, id, json, ..
+    }: args::Coverage,
+    bytes: Bytes,
+    continuity_almanac: Option<Almanac>,
+) -> Result<(), CliErrors>
+where
+    R: NAIFSummaryRecord,
+{
+    info!("Loading {input:?} as DAF/{}", R::NAME);
+    let daf = DAF::<R>::parse(bytes).context(CliDAFSnafu)?;
+
+    // Group the segments by ID, in the order of the file.
+    let mut segments_by_id: Vec<(i32, Vec<(usize, String, Epoch, Epoch)>)> = Vec::new();
+    for (idx, summary) in daf
+        .data_summaries()
+        .context(CliDAFSnafu)?
+        .iter()
+        .enumerate()
+    {
+        if summary.is_empty() || id.is_some_and(|id| id != summary.id()) {
+            continue;
+        }
+        let segment = (
+            idx,
+            daf.nth_summary_name(idx).context(CliDAFSnafu)?,
+            summary.start_epoch(),
+            summary.end_epoch(),
+        );
+        match segments_by_id
+            .iter_mut()
+            .find(|(this_id, _)| *this_id == summary.id())
+        {
+            Some((_, segments)) => segments.push(segment),
+            None => segments_by_id.push((summary.id(), vec![segment])),
+        }
+    }
+
+    ensure!(
+        !segments_by_id.is_empty(),
+        ArgumentSnafu {
+            arg: format!("no segment found in {input:?} for ID {id:?}")
+        }
+    );
+
+    let mut report = Vec::with_capacity(segments_by_id.len());
+    for (this_id, mut segments) in segments_by_id {
+        segments.sort_by_key(|(_, _, start, _)| *start);
+
+        // Walk the segments by start epoch, tracking the end of the coverage so far.
+        let mut gaps = Vec::new();
+        let mut overlaps = Vec::new();
+        let mut covered_until = segments[0].3;
+        for (_, _, start, end) in segments.iter().skip(1) {
+            if *start > covered_until {
+                gaps.push((covered_until, *start));
+            } else if *start < covered_until {
+                overlaps.push((*start, covered_until.min(*end)));
+            }
+            covered_until = covered_until.max(*end);
+        }
+
+        let boundaries = match &continuity_almanac {
+            Some(almanac) => {
+                almanac
+                    .continuity_report(this_id)
+                    .map_err(|e| CliErrors::ArgumentError {
+                        arg: format!("continuity of {this_id}: {e}"),
+                    })?
+                    .boundaries
+            }
+            None => Vec::new(),
+        };
+
+        if json {
+            let spans = |spans: &[(Epoch, Epoch)]| {
+                spans
+                    .iter()
+                    .map(|(start, end)| {
+                        serde_json::json!({
+                            "start": start.to_string(),
+                            "end": end.to_string(),
+                            "duration_s": (*end - *start).to_seconds(),
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            };
+
+            report.push(serde_json::json!({
+                "id": this_id,
+                "body": body_id_to_name(this_id),
+                "segments": segments
+                    .iter()
+                    .map(|(idx, name, start, end)| serde_json::json!({
+                        "index": idx,
+                        "name": name,
+                        "start": start.to_string(),
+                        "end": end.to_string(),
+                    }))
+                    .collect::<Vec<_>>(),
+                "gaps": spans(&gaps),
+                "overlaps": spans(&overlaps),
+                "boundaries": boundaries
+                    .iter()
+                    .map(|boundary| serde_json::json!({
+                        "epoch": boundary.epoch.to_string(),
+                        "gap_s": boundary.gap.to_seconds(),
+                        "position_jump_km": boundary.position_jump_km,
+                        "velocity_jump_km_s": boundary.velocity_jump_km_s,
+                    }))
+                    .collect::<Vec<_>>(),
+            }));
+        } else {
+            match body_id_to_name(this_id) {
+                Some(name) => println!("ID {this_id} ({name}): {} segment(s)", segments.len()),
+                None => println!("ID {this_id}: {} segment(s)", segments.len()),
+            }
+            for (idx, name, start, end) in &segments {
+                println!("\t#{idx} `{name}`: {start} to {end} ({})", *end - *start);
+            }
+            for (start, end) in &gaps {
+                println!("\tGAP from {start} to {end} ({})", *end - *start);
+            }
+            for (start, end) in &overlaps {
+                println!("\tOVERLAP from {start} to {end} ({})", *end - *start);
+            }
+            if gaps.is_empty() && overlaps.is_empty() {
+                println!("\tcontinuous coverage");
+            }
+            for boundary in &boundaries {
+                println!("\tBOUNDARY at {boundary}");
+            }
+        }
+    }
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "file": input.to_string_lossy(),
+                "ids": report,
+            })
+        );
+    }
+
+    Ok(())
+}
+
+fn diff(
+    args::Diff {
+        reference,
+        candidate,
+        start,
+        end,
+        num_epochs,
+    }: args::Diff,
+) -> Result<(), CliErrors> {
+    ensure!(
+        num_epochs > 1 && end > start,
+        ArgumentSnafu {
+            arg: "END must be after START and there must be at least two epochs"
+        }
+    );
+
+    let reference = Almanac::new(&reference.to_string_lossy()).context(CliAlmanacSnafu)?;
+    let candidate = Almanac::new(&candidate.to_string_lossy()).context(CliAlmanacSnafu)?;
+
+    let mut ids = reference
+        .spk_domains()
+        .map_err(|e| CliErrors::ArgumentError {
+            arg: format!("reference: {e}"),
+        })?
+        .into_keys()
+        .collect::<Vec<i32>>();
+    ids.sort();
+
+    let step = (end - start) / ((num_epochs - 1) as f64);
+    let mut comparator = Comparator::new(reference.clone(), candidate)
+        .with_epochs(TimeSeries::inclusive(start, end, step));
+
+    for id in ids {
+        if let Ok(summaries) = reference.spk_summaries(id) {
+            comparator = comparator.with_pair(
+                Frame::from_ephem_j2000(id),
+                Frame::from_ephem_j2000(summaries[0].center_id),
+            );
+        }
+    }
+
+    let report = comparator.compare().context(CliAlmanacSnafu)?;
+    println!("{report}");
+
+    if let Some(worst) = report.worst_pair() {
+        info!("Largest difference: {worst}");
+    }
+
+    Ok(())
+}
+
+fn refit(
+    args::Refit {
+        input,
+        output,
+        degree,
+        interval,
+    }: args::Refit,
+) -> Result<(), CliErrors> {
+    let spk = SPK::load(&input.to_string_lossy()).context(CliDAFSnafu)?;
+
+    let (refit, residuals) = refit_spk(&spk, degree, interval)
+        .context(EphemerisSnafu {
+            action: "refitting SPK",
+        })
+        .context(CliAlmanacSnafu)?;
+
+    for segment_residuals in &residuals {
+        println!("{segment_residuals}");
+    }
+
+    refit.persist(&output).context(FilePersistSnafu)?;
+    info!(
+        "[OK] {} segments refit into {}",
+        residuals.len(),
+        output.display()
+    );
+
+    Ok(())
+}
+
+fn query(
+    args::Query {
+        kernels,
+        target,
+        observer,
+        epoch,
+        orientation,
+        verbose,
+    }: args::Query,
+) -> Result<(), CliErrors> {
+    let paths = kernels
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect::<Vec<String>>();
+    let almanac = Almanac::default()
+        .load_parallel(
+            &paths
+                .iter()
+                .map(|path| path.as_str())
+                .collect::<Vec<&str>>(),
+        )
+        .context(CliAlmanacSnafu)?;
+
+    let target_frame = Frame::new(target, orientation);
+    let observer_frame = Frame::new(observer, orientation);
+
+    let state = almanac
+        .transform(target_frame, observer_frame, epoch, None)
+        .context(CliAlmanacSnafu)?;
+    println!("{state}");
+
+    if verbose {
+        for segment in almanac
+            .query_provenance(target_frame, observer_frame, epoch)
+            .context(CliAlmanacSnafu)?
+        {
+            println!("\t{segment}");
+        }
     }
+
+    Ok(())
 }
 
 fn read_and_record(path_str: PathBuf) -> Result<(bytes::Bytes, FileRecord), CliErrors> {
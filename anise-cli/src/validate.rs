@@ -0,0 +1,377 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::ffi::{c_char, CStr, CString};
+use std::fs::{self, File};
+use std::path::Path;
+use std::sync::Arc;
+
+use anise::naif::names::body_id_to_name;
+use anise::prelude::*;
+use arrow::array::{ArrayRef, Float64Array, StringArray};
+use arrow::record_batch::RecordBatch;
+use log::{error, info, warn};
+use parquet::arrow::ArrowWriter;
+use snafu::prelude::*;
+
+use crate::args::Validate;
+use crate::{ArgumentSnafu, CliAlmanacSnafu, CliErrors, FilePersistSnafu};
+
+const COMPONENTS: [&str; 6] = ["X", "Y", "Z", "VX", "VY", "VZ"];
+
+/// Results of the validation of a pair of target and observer against SPICE.
+struct PairValidation {
+    target_id: i32,
+    observer_id: i32,
+    num_samples: usize,
+    /// Number of epochs where ANISE failed to compute the state
+    num_errors: usize,
+    /// Absolute differences of each component, in km or km/s
+    abs_errors: Vec<f64>,
+}
+
+impl PairValidation {
+    fn name(&self) -> String {
+        format!(
+            "{} wrt {}",
+            body_name(self.target_id),
+            body_name(self.observer_id)
+        )
+    }
+
+    /// Returns the quantile of the absolute errors, rounded to the next higher sample.
+    fn quantile(&self, q: f64) -> f64 {
+        let mut errors = self.abs_errors.clone();
+        errors.sort_by(f64::total_cmp);
+        errors
+            .get(((errors.len().saturating_sub(1)) as f64 * q).ceil() as usize)
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    fn max(&self) -> f64 {
+        self.abs_errors.iter().copied().fold(0.0, f64::max)
+    }
+
+    fn passes(&self, args: &Validate) -> bool {
+        self.num_errors == 0
+            && self.max() <= args.max_abs_err
+            && self.quantile(0.99) <= args.max_q99_err
+    }
+}
+
+/// Loads the kernel in SPICE, returning the SPICE error instead of aborting the process.
+fn spice_furnsh(path: &str) -> Result<(), CliErrors> {
+    let set = CString::new("SET").unwrap();
+    let action = CString::new("RETURN").unwrap();
+    let device = CString::new("NONE").unwrap();
+    unsafe {
+        spice::c::erract_c(set.as_ptr(), 0, action.as_ptr() as *mut c_char);
+        spice::c::errprt_c(set.as_ptr(), 0, device.as_ptr() as *mut c_char);
+    }
+
+    spice::furnsh(path);
+
+    match spice_failure() {
+        Some(err) => Err(CliErrors::Spice {
+            path: path.to_string(),
+            err,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Returns the message of the last SPICE error, if any, and resets the error status: in `RETURN` mode, the error status is
+/// sticky and all of the following SPICE calls return immediately until it is reset.
+fn spice_failure() -> Option<String> {
+    if unsafe { spice::c::failed_c() } == 0 {
+        return None;
+    }
+
+    let option = CString::new("LONG").unwrap();
+    let mut msg = [0 as c_char; 1841];
+    Some(unsafe {
+        spice::c::getmsg_c(option.as_ptr(), msg.len() as i32, msg.as_mut_ptr());
+        spice::c::reset_c();
+        CStr::from_ptr(msg.as_ptr()).to_string_lossy().into_owned()
+    })
+}
+
+/// Compares the states of each pair computed by ANISE and SPICE from the same kernels, optionally writing each compared
+/// component to a Parquet file and a summary to an HTML file, and fails if any pair exceeds the tolerances.
+///
+/// All of the states are geometric (no aberration correction) and in the J2000 frame.
+pub(crate) fn validate(args: Validate) -> Result<(), CliErrors> {
+    ensure!(
+        args.num_samples > 1,
+        ArgumentSnafu {
+            arg: "there must be at least two samples"
+        }
+    );
+
+    let mut almanac = Almanac::default();
+    for path in &args.kernels {
+        let path_str = path.to_string_lossy();
+        almanac = almanac.load(&path_str).context(CliAlmanacSnafu)?;
+        spice_furnsh(&path_str)?;
+    }
+
+    let mut pairs = args.pairs.clone();
+    if pairs.is_empty() {
+        let mut ids = almanac
+            .spk_domains()
+            .map_err(|e| CliErrors::ArgumentError {
+                arg: format!("{e}"),
+            })?
+            .into_keys()
+            .collect::<Vec<i32>>();
+        ids.sort();
+
+        for id in ids {
+            if let Ok(summaries) = almanac.spk_summaries(id) {
+                pairs.push((id, summaries[0].center_id));
+            }
+        }
+    }
+
+    let mut writer = match &args.parquet {
+        Some(path) => Some(parquet_writer(path)?),
+        None => None,
+    };
+
+    let mut results = Vec::with_capacity(pairs.len());
+    for (target_id, observer_id) in pairs {
+        let (start, end) = match common_domain(&almanac, target_id, observer_id) {
+            Some(domain) => domain,
+            None => {
+                warn!("{target_id} and {observer_id} have no common coverage, skipping");
+                continue;
+            }
+        };
+
+        let target = Frame::from_ephem_j2000(target_id);
+        let observer = Frame::from_ephem_j2000(observer_id);
+
+        let mut result = PairValidation {
+            target_id,
+            observer_id,
+            num_samples: args.num_samples,
+            num_errors: 0,
+            abs_errors: Vec::with_capacity(args.num_samples * COMPONENTS.len()),
+        };
+
+        let mut epochs_et_s = Vec::with_capacity(args.num_samples * COMPONENTS.len());
+        let mut components = Vec::with_capacity(args.num_samples * COMPONENTS.len());
+        let mut spice_vals = Vec::with_capacity(args.num_samples * COMPONENTS.len());
+        let mut anise_vals = Vec::with_capacity(args.num_samples * COMPONENTS.len());
+
+        let step = (end - start) / ((args.num_samples - 1) as f64);
+        for epoch in TimeSeries::inclusive(start, end, step) {
+            let state = match almanac.translate(target, observer, epoch, None) {
+                Ok(state) => state,
+                Err(e) => {
+                    error!("{} @ {epoch}: {e}", result.name());
+                    result.num_errors += 1;
+                    continue;
+                }
+            };
+
+            let (spice_state, _) = spice::spkezr(
+                &target_id.to_string(),
+                epoch.to_et_seconds(),
+                "J2000",
+                "NONE",
+                &observer_id.to_string(),
+            );
+            if let Some(err) = spice_failure() {
+                error!("{} @ {epoch}: SPICE {err}", result.name());
+                result.num_errors += 1;
+                continue;
+            }
+
+            let anise_state = [
+                state.radius_km.x,
+                state.radius_km.y,
+                state.radius_km.z,
+                state.velocity_km_s.x,
+                state.velocity_km_s.y,
+                state.velocity_km_s.z,
+            ];
+
+            for (i, component) in COMPONENTS.iter().enumerate() {
+                epochs_et_s.push(epoch.to_et_seconds());
+                components.push(component.to_string());
+                spice_vals.push(spice_state[i]);
+                anise_vals.push(anise_state[i]);
+                result
+                    .abs_errors
+                    .push((anise_state[i] - spice_state[i]).abs());
+            }
+        }
+
+        if let Some(writer) = writer.as_mut() {
+            let name = result.name();
+            let batch = RecordBatch::try_from_iter(vec![
+                (
+                    "pair",
+                    Arc::new(StringArray::from(vec![name; components.len()])) as ArrayRef,
+                ),
+                (
+                    "component",
+                    Arc::new(StringArray::from(components)) as ArrayRef,
+                ),
+                (
+                    "ET Epoch (s)",
+                    Arc::new(Float64Array::from(epochs_et_s)) as ArrayRef,
+                ),
+                (
+                    "SPICE value",
+                    Arc::new(Float64Array::from(spice_vals)) as ArrayRef,
+                ),
+                (
+                    "ANISE value",
+                    Arc::new(Float64Array::from(anise_vals)) as ArrayRef,
+                ),
+                (
+                    "Absolute difference",
+                    Arc::new(Float64Array::from(result.abs_errors.clone())) as ArrayRef,
+                ),
+            ])
+            .map_err(|e| CliErrors::CliDataType { error: Box::new(e) })?;
+
+            writer
+                .write(&batch)
+                .map_err(|e| CliErrors::CliDataType { error: Box::new(e) })?;
+        }
+
+        info!(
+            "{}: max {:.3e}, q99 {:.3e}, {} errors -- {}",
+            result.name(),
+            result.max(),
+            result.quantile(0.99),
+            result.num_errors,
+            if result.passes(&args) { "PASS" } else { "FAIL" }
+        );
+        results.push(result);
+    }
+
+    if let Some(writer) = writer {
+        writer
+            .close()
+            .map_err(|e| CliErrors::CliDataType { error: Box::new(e) })?;
+    }
+
+    if let Some(path) = &args.html {
+        fs::write(path, html_report(&args, &results)).context(FilePersistSnafu)?;
+        info!("report written to {}", path.display());
+    }
+
+    let num_failed = results.iter().filter(|r| !r.passes(&args)).count();
+    ensure!(
+        num_failed == 0,
+        ArgumentSnafu {
+            arg: format!(
+                "{num_failed} of {} pairs failed the validation",
+                results.len()
+            )
+        }
+    );
+
+    info!("all {} pairs validated", results.len());
+    Ok(())
+}
+
+/// Returns the time span where both the target and the observer are defined, if any.
+fn common_domain(almanac: &Almanac, target_id: i32, observer_id: i32) -> Option<(Epoch, Epoch)> {
+    let (mut start, mut end) = almanac.spk_domain(target_id).ok()?;
+    // The observer may be the root of the ephemeris tree, e.g. the SSB, which has no domain of its own.
+    if let Ok((obs_start, obs_end)) = almanac.spk_domain(observer_id) {
+        start = start.max(obs_start);
+        end = end.min(obs_end);
+    }
+    (end > start).then_some((start, end))
+}
+
+fn parquet_writer(path: &Path) -> Result<ArrowWriter<File>, CliErrors> {
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    let schema = Schema::new(vec![
+        Field::new("pair", DataType::Utf8, false),
+        Field::new("component", DataType::Utf8, false),
+        Field::new("ET Epoch (s)", DataType::Float64, false),
+        Field::new("SPICE value", DataType::Float64, false),
+        Field::new("ANISE value", DataType::Float64, false),
+        Field::new("Absolute difference", DataType::Float64, false),
+    ]);
+
+    let file = File::create(path).context(FilePersistSnafu)?;
+    ArrowWriter::try_new(file, Arc::new(schema), None)
+        .map_err(|e| CliErrors::CliDataType { error: Box::new(e) })
+}
+
+fn body_name(id: i32) -> String {
+    match body_id_to_name(id) {
+        Some(name) => format!("{name} ({id})"),
+        None => format!("{id}"),
+    }
+}
+
+/// Builds a standalone HTML page summarizing the validation of each pair.
+fn html_report(args: &Validate, results: &[PairValidation]) -> String {
+    let kernels = args
+        .kernels
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let mut rows = String::new();
+    for result in results {
+        let passes = result.passes(args);
+        rows.push_str(&format!(
+            "<tr class=\"{}\"><td>{}</td><td>{}</td><td>{}</td><td>{:.3e}</td><td>{:.3e}</td><td>{:.3e}</td><td>{}</td></tr>\n",
+            if passes { "pass" } else { "fail" },
+            result.name(),
+            result.num_samples,
+            result.num_errors,
+            result.quantile(0.75),
+            result.quantile(0.99),
+            result.max(),
+            if passes { "PASS" } else { "FAIL" }
+        ));
+    }
+
+    format!(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>ANISE validation against SPICE</title>
+<style>
+body {{ font-family: sans-serif; }}
+table {{ border-collapse: collapse; }}
+td, th {{ border: 1px solid #ccc; padding: 4px 8px; text-align: right; }}
+tr.pass td:last-child {{ color: green; }}
+tr.fail td:last-child {{ color: red; font-weight: bold; }}
+</style>
+</head>
+<body>
+<h1>ANISE validation against SPICE</h1>
+<p>Kernels: {kernels}</p>
+<p>Geometric states in J2000; absolute differences of each component in km and km/s. Tolerances: maximum {:e}, 99th percentile {:e}.</p>
+<table>
+<tr><th>Pair</th><th>Samples</th><th>ANISE errors</th><th>q75</th><th>q99</th><th>Max</th><th>Result</th></tr>
+{rows}</table>
+</body>
+</html>
+",
+        args.max_abs_err, args.max_q99_err
+    )
+}
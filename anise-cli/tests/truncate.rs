@@ -0,0 +1,96 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::process::Command;
+
+use anise::constants::frames::{EARTH_J2000, MOON_J2000};
+use anise::prelude::*;
+
+const CLI: &str = env!("CARGO_BIN_EXE_anise-cli");
+
+#[test]
+fn truncate_spk() {
+    let input = "../data/de440s.bsp";
+    let output = "../target/cli-truncated-de440s.bsp";
+    let start = Epoch::from_str("2023-01-01 00:00:00 TDB").unwrap();
+    let end = Epoch::from_str("2023-02-01 00:00:00 TDB").unwrap();
+
+    let status = Command::new(CLI)
+        .args([
+            "truncate",
+            input,
+            &start.to_string(),
+            &end.to_string(),
+            "--output",
+            output,
+        ])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    // All of the segments are truncated to the requested epochs
+    let truncated = SPK::load(output).unwrap();
+    let summaries = truncated.data_summaries().unwrap();
+    assert_eq!(
+        summaries
+            .iter()
+            .filter(|summary| !summary.is_empty())
+            .count(),
+        SPK::load(input)
+            .unwrap()
+            .data_summaries()
+            .unwrap()
+            .iter()
+            .filter(|summary| !summary.is_empty())
+            .count()
+    );
+    for summary in summaries.iter().filter(|summary| !summary.is_empty()) {
+        // The epochs are stored as ET seconds in the summaries
+        assert!((summary.start_epoch() - start).abs() < Unit::Microsecond * 1);
+        assert!((summary.end_epoch() - end).abs() < Unit::Microsecond * 1);
+    }
+
+    // The ephemerides within the epochs are unchanged, and those outside of them are removed
+    let original = Almanac::new(input).unwrap();
+    let almanac = Almanac::default().with_spk(truncated).unwrap();
+    let epoch = Epoch::from_str("2023-01-15 12:34:56 TDB").unwrap();
+    let expected = original
+        .translate(MOON_J2000, EARTH_J2000, epoch, None)
+        .unwrap();
+    let state = almanac
+        .translate(MOON_J2000, EARTH_J2000, epoch, None)
+        .unwrap();
+    assert!((state.radius_km - expected.radius_km).norm() < 1e-9);
+    assert!((state.velocity_km_s - expected.velocity_km_s).norm() < 1e-12);
+
+    assert!(almanac
+        .translate(MOON_J2000, EARTH_J2000, end + Unit::Day * 1, None)
+        .is_err());
+}
+
+#[test]
+fn truncate_invalid_epochs() {
+    let output = "../target/cli-truncated-invalid-de440s.bsp";
+
+    // The start epoch must be before the end epoch
+    let status = Command::new(CLI)
+        .args([
+            "truncate",
+            "../data/de440s.bsp",
+            "2023-02-01 00:00:00 TDB",
+            "2023-01-01 00:00:00 TDB",
+            "--output",
+            output,
+        ])
+        .status()
+        .unwrap();
+    assert!(!status.success());
+    assert!(!std::path::Path::new(output).exists());
+}
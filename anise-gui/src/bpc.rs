@@ -47,15 +47,14 @@ pub fn bpc_ui(
             let pck = almanac.bpc_data[0].as_ref().unwrap();
 
             for (sno, summary) in pck.data_summaries().unwrap().iter().enumerate() {
-                let name_rcrd = pck.name_record().unwrap();
-                let name = name_rcrd.nth_name(sno, pck.file_record().unwrap().summary_size());
                 if summary.is_empty() {
                     continue;
                 }
+                let name = pck.nth_summary_name(sno).unwrap();
 
                 body.row(30.0, |mut row| {
                     row.col(|ui| {
-                        ui.label(name);
+                        ui.label(name.as_str());
                     });
 
                     row.col(|ui| match orientation_name_from_id(summary.frame_id) {
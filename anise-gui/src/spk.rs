@@ -43,11 +43,10 @@ pub fn spk_ui(
             let spk = almanac.spk_data[0].as_ref().unwrap();
 
             for (sno, summary) in spk.data_summaries().unwrap().iter().enumerate() {
-                let name_rcrd = spk.name_record().unwrap();
-                let name = name_rcrd.nth_name(sno, spk.file_record().unwrap().summary_size());
                 if summary.is_empty() {
                     continue;
                 }
+                let name = spk.nth_summary_name(sno).unwrap();
 
                 body.row(30.0, |mut row| {
                     row.col(|ui| {
@@ -58,7 +57,7 @@ pub fn spk_ui(
                         ));
                     });
                     row.col(|ui| {
-                        ui.label(name);
+                        ui.label(name.as_str());
                     });
 
                     row.col(|ui| {
@@ -16,6 +16,7 @@ use pyo3::py_run;
 
 use anise::astro::orbit::Orbit;
 use anise::frames::Frame;
+use anise::math::cartesian::CovariantState;
 
 use super::constants::register_constants;
 
@@ -24,6 +25,7 @@ pub(crate) fn register_astro(parent_module: &Bound<'_, PyModule>) -> PyResult<()
     sm.add_class::<Ellipsoid>()?;
     sm.add_class::<Frame>()?;
     sm.add_class::<Orbit>()?;
+    sm.add_class::<CovariantState>()?;
     sm.add_class::<AzElRange>()?;
     sm.add_class::<Occultation>()?;
 
@@ -12,7 +12,7 @@ use anise::constants::celestial_objects::*;
 use anise::constants::orientations::*;
 use anise::constants::usual_planetary_constants::MEAN_EARTH_ANGULAR_VELOCITY_DEG_S;
 use anise::constants::usual_planetary_constants::MEAN_MOON_ANGULAR_VELOCITY_DEG_S;
-use anise::constants::SPEED_OF_LIGHT_KM_S;
+use anise::constants::{AU_KM, SPEED_OF_LIGHT_KM_S};
 use pyo3::prelude::*;
 use pyo3::py_run;
 
@@ -199,6 +199,9 @@ impl UsualConstants {
     #[classattr]
     /// Speed of light in kilometers per second (km/s)
     const SPEED_OF_LIGHT_KM_S: f64 = SPEED_OF_LIGHT_KM_S;
+    #[classattr]
+    /// Astronomical unit in kilometers (km)
+    const AU_KM: f64 = AU_KM;
 }
 
 pub(crate) fn register_constants(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
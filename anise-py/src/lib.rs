@@ -8,9 +8,12 @@
  * Documentation: https://nyxspace.com/
  */
 
+use ::anise::almanac::compiled::CompiledQuery;
 use ::anise::almanac::metaload::{MetaAlmanac, MetaFile};
+use ::anise::almanac::named::NamedState;
 use ::anise::almanac::Almanac;
 use ::anise::astro::Aberration;
+use ::anise::python_config::{get_config, set_config, AngleUnit, Config};
 use hifitime::leap_seconds::{LatestLeapSeconds, LeapSecondsFile};
 use hifitime::prelude::*;
 use hifitime::ut1::Ut1Provider;
@@ -33,8 +36,14 @@ fn anise(m: &Bound<'_, PyModule>) -> PyResult<()> {
     rotation::register_rotation(m)?;
     m.add_class::<Almanac>()?;
     m.add_class::<Aberration>()?;
+    m.add_class::<NamedState>()?;
+    m.add_class::<CompiledQuery>()?;
     m.add_class::<MetaAlmanac>()?;
     m.add_class::<MetaFile>()?;
+    m.add_class::<Config>()?;
+    m.add_class::<AngleUnit>()?;
+    m.add_function(wrap_pyfunction!(get_config, m)?)?;
+    m.add_function(wrap_pyfunction!(set_config, m)?)?;
     Ok(())
 }
 
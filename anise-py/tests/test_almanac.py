@@ -45,6 +45,8 @@ def test_state_transformation():
     assert eme2k.mu_km3_s2() == 398600.435436096
     assert eme2k.shape.polar_radius_km == 6356.75
     assert abs(eme2k.shape.flattening() - 0.0033536422844278) < 2e-16
+    assert ctx.gm(Frames.EME2000) == eme2k.mu_km3_s2()
+    assert ctx.body_radii(Frames.EME2000).polar_radius_km == 6356.75
 
     epoch = Epoch("2021-10-29 12:34:56 TDB")
 
@@ -182,6 +184,207 @@ def test_frame_defs():
     assert Frames.EME2000 != Frames.SSB_J2000
 
 
+def test_pickle_and_context_manager():
+    data_path = Path(__file__).parent.joinpath("..", "..", "data")
+
+    with Almanac(str(data_path.joinpath("de440s.bsp"))) as almanac:
+        almanac = almanac.load(str(data_path.joinpath("pck08.pca")))
+
+    epoch = Epoch("2021-10-29 12:34:56 TDB")
+    # Pickling copies all of the loaded data, so the workers need not access the files
+    unpickled = pickle.loads(pickle.dumps(almanac))
+    assert str(unpickled) == str(almanac)
+
+    state = almanac.translate(Frames.MOON_J2000, Frames.EARTH_J2000, epoch, None)
+    unpickled_state = unpickled.translate(
+        Frames.MOON_J2000, Frames.EARTH_J2000, epoch, None
+    )
+    assert state == unpickled_state
+    assert unpickled.frame_info(Frames.EME2000) == almanac.frame_info(Frames.EME2000)
+
+    # The in-memory state is pickled too, e.g. the overridden planetary constants
+    overridden = almanac.override_gm(399, 398_600.0)
+    unpickled = pickle.loads(pickle.dumps(overridden))
+    assert unpickled.gm(Frames.EARTH_J2000) == 398_600.0
+
+
+def test_body_names():
+    data_path = Path(__file__).parent.joinpath("..", "..", "data")
+    almanac = Almanac(str(data_path.joinpath("de440s.bsp")))
+
+    almanac = almanac.register_body_name("My SC", -123456)
+    assert almanac.body_id("MY SC") == -123456
+    assert almanac.body_name(-123456) == "MY SC"
+    assert almanac.body_id("Moon") == 301
+    assert almanac.body_name(399) == "EARTH"
+
+    # Registered names are pickled
+    unpickled = pickle.loads(pickle.dumps(almanac))
+    assert unpickled.body_id("my sc") == -123456
+
+
+def test_introspection():
+    data_path = Path(__file__).parent.joinpath("..", "..", "data")
+    almanac = Almanac(str(data_path.joinpath("de440s.bsp"))).load(
+        str(data_path.joinpath("pck08.pca"))
+    )
+
+    frames = almanac.list_frames()
+    earth = [row for row in frames if row["id"] == 399][0]
+    assert earth["kind"] == "planetary"
+    assert 398600 < earth["mu_km3_s2"] < 398601
+    assert earth["equatorial_radius_km"] > earth["polar_radius_km"]
+
+    objects = almanac.list_objects()
+    assert all(row["kind"] == "SPK" for row in objects)
+    moon = [row for row in objects if row["id"] == 301][0]
+    assert moon["center_id"] == 3
+    assert moon["name"] == "MOON"
+    assert moon["start_epoch"] < moon["end_epoch"]
+
+    coverage = almanac.coverage()
+    assert len(coverage) >= len({row["id"] for row in objects})
+    for row in coverage:
+        assert row["end_epoch"] - row["start_epoch"] == row["duration"]
+
+
+def test_config():
+    from math import radians
+
+    from anise import AngleUnit, Config, get_config, set_config
+    from anise.time import TimeScale
+
+    data_path = Path(__file__).parent.joinpath("..", "..", "data")
+    almanac = Almanac(str(data_path.joinpath("pck08.pca")))
+    eme2k = almanac.frame_info(Frames.EME2000)
+
+    epoch = Epoch("2021-10-29 12:34:56 UTC")
+    orbit = Orbit(-2436.45, -2436.45, 6891.037, 5.088611, -5.088611, 0.0, epoch, eme2k)
+
+    assert get_config() == Config()
+    assert orbit.inc() == orbit.inc_deg()
+
+    try:
+        set_config(Config(angle_unit=AngleUnit.Radian, time_scale=TimeScale.TDB))
+        assert abs(orbit.inc() - radians(orbit.inc_deg())) < 1e-12
+        lat, long, _ = orbit.geodetic()
+        assert abs(lat - radians(orbit.latitude_deg())) < 1e-12
+        # Epochs without a time scale are in the configured one
+        config = get_config()
+        assert config.parse_epoch("2021-10-29 12:34:56") == Epoch(
+            "2021-10-29 12:34:56 TDB"
+        )
+        assert config.parse_epoch("2021-10-29 12:34:56 UTC") == epoch
+        # All of the functions taking angles or epochs use the configuration
+        assert abs(orbit.with_inc(radians(45.0)).inc_deg() - 45.0) < 1e-9
+        tdb_orbit = Orbit(
+            -2436.45, -2436.45, 6891.037, 5.088611, -5.088611, 0.0, "2021-10-29 12:34:56", eme2k
+        )
+        assert tdb_orbit.epoch == Epoch("2021-10-29 12:34:56 TDB")
+        aer = AzElRange(epoch, 90.0, 45.0, 1000.0, 1.0)
+        assert abs(aer.azimuth - radians(90.0)) < 1e-12
+    finally:
+        set_config(Config())
+
+
+def test_state_arithmetic():
+    data_path = Path(__file__).parent.joinpath("..", "..", "data")
+    almanac = Almanac(str(data_path.joinpath("pck08.pca")))
+    eme2k = almanac.frame_info(Frames.EME2000)
+
+    epoch = Epoch("2021-10-29 12:34:56 UTC")
+    orbit = Orbit(-2436.45, -2436.45, 6891.037, 5.088611, -5.088611, 0.0, epoch, eme2k)
+    offset = Orbit(1.0, 2.0, 3.0, 0.1, 0.2, 0.3, epoch, eme2k)
+
+    total = orbit + offset
+    assert total.epoch == epoch
+    assert total.frame == eme2k
+    assert abs(total.x_km - (orbit.x_km + 1.0)) < 1e-12
+    assert abs(total.vz_km_s - 0.3) < 1e-12
+    assert total - offset == orbit
+    assert -(-orbit) == orbit
+    assert abs((-orbit).y_km - 2436.45) < 1e-12
+
+    # Combining states at different epochs or in different frames raises an informative exception
+    later = Orbit(1.0, 2.0, 3.0, 0.1, 0.2, 0.3, Epoch("2021-10-29 12:34:57 UTC"), eme2k)
+    try:
+        orbit + later
+        assert False, "adding states at different epochs must fail"
+    except ValueError as e:
+        assert "epochs" in str(e)
+
+    moon = Orbit(1.0, 2.0, 3.0, 0.1, 0.2, 0.3, epoch, Frames.MOON_J2000)
+    try:
+        orbit - moon
+        assert False, "subtracting states with different centers must fail"
+    except ValueError as e:
+        assert "frames" in str(e)
+
+    itrf93 = Orbit(1.0, 2.0, 3.0, 0.1, 0.2, 0.3, epoch, Frames.EARTH_ITRF93)
+    try:
+        orbit + itrf93
+        assert False, "adding states with different orientations must fail"
+    except ValueError as e:
+        assert "frames" in str(e)
+
+
+def test_compiled_query():
+    data_path = Path(__file__).parent.joinpath("..", "..", "data")
+    almanac = Almanac(str(data_path.joinpath("de440s.bsp")))
+
+    start = Epoch("2025-01-01 00:00:00 UTC")
+    end = Epoch("2025-01-31 00:00:00 UTC")
+    query = almanac.compile(
+        Frames.MOON_J2000, Frames.EARTH_J2000, Frames.EARTH_J2000, start, end
+    )
+    assert query.num_segments() > 0
+
+    epoch = Epoch("2025-01-17 12:34:56 UTC")
+    state = query.evaluate(epoch)
+    expected = almanac.translate(Frames.MOON_J2000, Frames.EARTH_J2000, epoch, None)
+    assert abs(state.x_km - expected.x_km) < 1e-6
+    assert abs(state.vz_km_s - expected.vz_km_s) < 1e-9
+    # Outside of the span, there is no state
+    assert query.evaluate(Epoch("2025-02-01 00:00:00 UTC")) is None
+
+
+def test_diff():
+    data_path = Path(__file__).parent.joinpath("..", "..", "data")
+    pck08 = Almanac(str(data_path.joinpath("pck08.pca")))
+    pck11 = Almanac(str(data_path.joinpath("pck11.pca")))
+
+    assert pck08.diff_planetary_data(pck08) == []
+    rows = pck08.diff_planetary_data(pck11)
+    assert len(rows) > 0
+    for row in rows:
+        assert row["reference"] != row["candidate"]
+        if row["reference"] is not None and row["candidate"] is not None:
+            assert abs(row["difference"] - (row["candidate"] - row["reference"])) < 1e-12
+
+    almanac = Almanac(str(data_path.joinpath("de440s.bsp")))
+    epochs = [Epoch("2025-01-01 00:00:00 UTC"), Epoch("2025-01-02 00:00:00 UTC")]
+    rows = almanac.diff_states(
+        almanac, [(Frames.MOON_J2000, Frames.EARTH_J2000)], epochs
+    )
+    assert len(rows) == 2
+    assert rows[0]["epoch"] == epochs[0]
+    assert rows[1]["position_km"] == 0.0
+    assert rows[1]["velocity_km_s"] == 0.0
+
+
+def test_query_provenance():
+    data_path = Path(__file__).parent.joinpath("..", "..", "data")
+    almanac = Almanac(str(data_path.joinpath("de440s.bsp")))
+    epoch = Epoch("2025-01-01 00:00:00 UTC")
+
+    rows = almanac.query_provenance(Frames.MOON_J2000, Frames.EARTH_J2000, epoch)
+    assert [row["id"] for row in rows] == [301, 3, 399]
+    for row in rows:
+        assert row["kind"] == "SPK"
+        assert row["path"].endswith("de440s.bsp")
+        assert row["start_epoch"] <= epoch <= row["end_epoch"]
+
+
 if __name__ == "__main__":
     # test_meta_load()
     # test_exports()
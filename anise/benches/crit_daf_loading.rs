@@ -0,0 +1,18 @@
+use anise::prelude::*;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    // The lazy load time does not depend on the size of the file, unlike the eager load which copies and hashes all of it.
+    for path in ["../data/de440s.bsp", "../data/de440.bsp"] {
+        c.bench_function(&format!("Eager load of {path}"), |b| {
+            b.iter(|| black_box(Almanac::default().load(path).unwrap()))
+        });
+
+        c.bench_function(&format!("Lazy load of {path}"), |b| {
+            b.iter(|| black_box(Almanac::default().load_lazy(path).unwrap()))
+        });
+    }
+}
+
+criterion_group!(daf_loading, criterion_benchmark);
+criterion_main!(daf_loading);
@@ -1,12 +1,35 @@
 use std::path::PathBuf;
 
-use anise::{constants::frames::EARTH_ITRF93, naif::kpl::parser::convert_tpc, prelude::*};
+use anise::{
+    constants::{
+        frames::{EARTH_ITRF93, IAU_EARTH_FRAME},
+        orientations::IAU_EARTH,
+    },
+    naif::kpl::parser::convert_tpc,
+    prelude::*,
+};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
+const NUM_QUERIES: f64 = 100.0;
+
 fn benchmark_fetch(almanac: &Almanac, frame: Frame) {
     black_box(almanac.frame_from_uid(frame).unwrap());
 }
 
+fn benchmark_get_by_id(almanac: &Almanac) {
+    black_box(almanac.planetary_data.get_by_id(IAU_EARTH).unwrap());
+}
+
+fn benchmark_get_ref_by_id(almanac: &Almanac) {
+    black_box(almanac.planetary_data.get_ref_by_id(IAU_EARTH).unwrap());
+}
+
+fn benchmark_iau_rotation(almanac: &Almanac, time_it: TimeSeries) {
+    for epoch in time_it {
+        black_box(almanac.rotation_to_parent(IAU_EARTH_FRAME, epoch).unwrap());
+    }
+}
+
 pub fn criterion_benchmark(c: &mut Criterion) {
     let pca = PathBuf::from_str("pck11.pca").unwrap();
     let planetary_data = convert_tpc("../data/pck00011.tpc", "../data/gm_de431.tpc").unwrap();
@@ -17,6 +40,24 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("Frame fetch from planetary dataset", |b| {
         b.iter(|| benchmark_fetch(&almanac, EARTH_ITRF93))
     });
+
+    // Copying an entry of the planetary data, as done before the rotations used references to the decoded entries
+    c.bench_function("Planetary data copy by ID", |b| {
+        b.iter(|| benchmark_get_by_id(&almanac))
+    });
+
+    c.bench_function("Planetary data reference by ID", |b| {
+        b.iter(|| benchmark_get_ref_by_id(&almanac))
+    });
+
+    let start_epoch = Epoch::from_gregorian_at_noon(2012, 1, 1, TimeScale::ET);
+    let end_epoch = Epoch::from_gregorian_at_noon(2021, 1, 1, TimeScale::ET);
+    let time_step = ((end_epoch - start_epoch).to_seconds() / NUM_QUERIES).seconds();
+    let time_it = TimeSeries::exclusive(start_epoch, end_epoch - time_step, time_step);
+
+    c.bench_function("IAU Earth rotation to parent", |b| {
+        b.iter(|| benchmark_iau_rotation(&almanac, time_it.clone()))
+    });
 }
 
 criterion_group!(pca, criterion_benchmark);
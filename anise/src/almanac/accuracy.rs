@@ -0,0 +1,289 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::Epoch;
+use snafu::ResultExt;
+
+use crate::ephemerides::paths::MAX_TREE_DEPTH;
+use crate::errors::{AlmanacResult, OrientationSnafu};
+use crate::orientations::RotationSource;
+use crate::prelude::Frame;
+use crate::structure::lookuptable::LookUpTable;
+use crate::NaifId;
+
+use super::provenance::SegmentProvenance;
+use super::transcript::segment_source;
+use super::Almanac;
+
+/// Accuracy class of a data source, sorted from the most to the least accurate.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AccuracyClass {
+    /// Exact by definition, e.g. the rotation from J2000 to the ecliptic
+    Exact,
+    /// Interpolated from high precision data, i.e. SPK ephemerides and BPC orientations (e.g. from Earth orientation parameters)
+    HighPrecision,
+    /// Constant rotation of the Euler parameter data, e.g. an instrument alignment, whose accuracy is that of its measurement
+    Constant,
+    /// Computed by a custom provider, whose accuracy is that of its own data
+    Provided,
+    /// Computed from an analytic model, i.e. the IAU rotation models of the planetary data, the fast Earth orientation, and
+    /// the approximate ephemeris providers such as the J2 propagator
+    Analytic,
+}
+
+/// A data source used to compute one link of the ephemeris or orientation path of a query, cf. [AccuracyReport].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataSource {
+    /// Ephemeris ID (for a translation) or orientation ID (for a rotation) computed with this source, with respect to its parent
+    pub id: NaifId,
+    /// Description of the source, e.g. the CRC32 of the SPK file and the index and name of the segment
+    pub description: String,
+    pub accuracy: AccuracyClass,
+    /// Segment of the SPK or BPC kernel used for this link, if any
+    pub segment: Option<SegmentProvenance>,
+}
+
+impl fmt::Display for DataSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} from {} ({:?})",
+            self.id, self.description, self.accuracy
+        )
+    }
+}
+
+/// Data sources used by a query of the Almanac, to audit whether it silently relied on a low accuracy model, cf.
+/// [Almanac::accuracy_report].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccuracyReport {
+    pub target_frame: Frame,
+    pub observer_frame: Frame,
+    pub epoch: Epoch,
+    /// Sources of the translations, from each frame up to the root of the ephemeris tree
+    pub translation_sources: Vec<DataSource>,
+    /// Sources of the rotations, along the orientation path from each frame up to the root of the orientation tree
+    pub rotation_sources: Vec<DataSource>,
+}
+
+impl AccuracyReport {
+    /// Returns the accuracy class of the query, i.e. that of its least accurate source.
+    pub fn accuracy(&self) -> AccuracyClass {
+        self.translation_sources
+            .iter()
+            .chain(self.rotation_sources.iter())
+            .map(|source| source.accuracy)
+            .max()
+            .unwrap_or(AccuracyClass::Exact)
+    }
+
+    /// Returns the sources computed from an analytic model.
+    pub fn analytic_sources(&self) -> Vec<&DataSource> {
+        self.translation_sources
+            .iter()
+            .chain(self.rotation_sources.iter())
+            .filter(|source| source.accuracy == AccuracyClass::Analytic)
+            .collect()
+    }
+}
+
+impl fmt::Display for AccuracyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} wrt {} @ {}: {:?}",
+            self.target_frame,
+            self.observer_frame,
+            self.epoch,
+            self.accuracy()
+        )?;
+        for source in &self.translation_sources {
+            writeln!(f, "\ttranslation of {source}")?;
+        }
+        for source in &self.rotation_sources {
+            writeln!(f, "\trotation of {source}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Almanac {
+    /// Returns the data sources used to transform the target frame into the observer frame at this epoch, with their accuracy
+    /// class, e.g. to check that the orientation of the Earth comes from a high precision BPC and not from the IAU model.
+    ///
+    /// The query is computed first, so this returns its error if it fails.
+    pub fn accuracy_report(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        epoch: Epoch,
+    ) -> AlmanacResult<AccuracyReport> {
+        self.transform_unrecorded(target_frame, observer_frame, epoch, None)?;
+
+        let mut translation_sources = Vec::new();
+        let mut rotation_sources = Vec::new();
+        for frame in [target_frame, observer_frame] {
+            for source in self.translation_sources(frame.ephemeris_id, epoch) {
+                if !translation_sources.contains(&source) {
+                    translation_sources.push(source);
+                }
+            }
+            for source in self.rotation_sources(frame, epoch)? {
+                if !rotation_sources.contains(&source) {
+                    rotation_sources.push(source);
+                }
+            }
+        }
+
+        Ok(AccuracyReport {
+            target_frame,
+            observer_frame,
+            epoch,
+            translation_sources,
+            rotation_sources,
+        })
+    }
+
+    /// Returns the sources of the translations from this object up to the root of the ephemeris tree, in the same order of
+    /// precedence as the translations.
+    fn translation_sources(&self, mut id: NaifId, epoch: Epoch) -> Vec<DataSource> {
+        let root = self.try_find_ephemeris_root().ok();
+        let mut sources = Vec::new();
+        for _ in 0..MAX_TREE_DEPTH {
+            if Some(id) == root {
+                break;
+            }
+            if let Some(provider) = self.ephemeris_provider_at_epoch(id, epoch) {
+                sources.push(DataSource {
+                    id,
+                    description: "ephemeris provider".to_string(),
+                    accuracy: if provider.is_approximate() {
+                        AccuracyClass::Analytic
+                    } else {
+                        AccuracyClass::Provided
+                    },
+                    segment: None,
+                });
+                id = provider.center_id();
+            } else if let Ok((summary, spk_no, idx_in_spk)) = self.spk_summary_at_epoch(id, epoch) {
+                let spk = self.spk_data[spk_no].as_ref().unwrap();
+                sources.push(DataSource {
+                    id,
+                    description: segment_source("SPK", spk, idx_in_spk),
+                    accuracy: AccuracyClass::HighPrecision,
+                    segment: Some(SegmentProvenance::new(
+                        self, "SPK", spk, idx_in_spk, summary,
+                    )),
+                });
+                id = summary.center_id;
+            } else {
+                break;
+            }
+        }
+        sources
+    }
+
+    /// Returns the sources of the rotations along the orientation path of this frame, from the frame up to the root of the
+    /// orientation tree, as computed by [Almanac::rotation_to_parent].
+    fn rotation_sources(&self, frame: Frame, epoch: Epoch) -> AlmanacResult<Vec<DataSource>> {
+        let (path_len, path) =
+            self.orientation_path_to_root(frame, epoch)
+                .context(OrientationSnafu {
+                    action: "building accuracy report",
+                })?;
+
+        // Each orientation of the path is rotated to the next one, so the root itself needs no source.
+        let mut sources = Vec::new();
+        let rotated_ids = core::iter::once(frame.orientation_id)
+            .chain(path.iter().flatten().copied())
+            .take(path_len);
+        for id in rotated_ids {
+            let source = self.rotation_source(id, epoch).context(OrientationSnafu {
+                action: "building accuracy report",
+            })?;
+
+            let (description, accuracy, segment) = match source {
+                RotationSource::Root => continue,
+                RotationSource::Builtin(_) => {
+                    ("built-in rotation".to_string(), AccuracyClass::Exact, None)
+                }
+                RotationSource::Icrf => ("ICRF frame bias".to_string(), AccuracyClass::Exact, None),
+                RotationSource::EarthFast => (
+                    "fast Earth orientation model".to_string(),
+                    AccuracyClass::Analytic,
+                    None,
+                ),
+                RotationSource::TwoVector(_) => (
+                    "two-vector frame from the ephemerides".to_string(),
+                    AccuracyClass::HighPrecision,
+                    None,
+                ),
+                RotationSource::Provider(_) => (
+                    "orientation provider".to_string(),
+                    AccuracyClass::Provided,
+                    None,
+                ),
+                RotationSource::Bpc {
+                    summary,
+                    bpc_no,
+                    idx_in_bpc,
+                } => {
+                    let bpc = self.bpc_data[bpc_no].as_ref().unwrap();
+                    (
+                        segment_source("BPC", bpc, idx_in_bpc),
+                        AccuracyClass::HighPrecision,
+                        Some(SegmentProvenance::new(
+                            self, "BPC", bpc, idx_in_bpc, summary,
+                        )),
+                    )
+                }
+                RotationSource::Planetary(_) => (
+                    format!(
+                        "IAU rotation model of planetary data {}",
+                        record_name(&self.planetary_data.lut, id)
+                    ),
+                    AccuracyClass::Analytic,
+                    None,
+                ),
+                RotationSource::EulerParameters(_) => (
+                    format!(
+                        "Euler parameters {}",
+                        record_name(&self.euler_param_data.lut, id)
+                    ),
+                    AccuracyClass::Constant,
+                    None,
+                ),
+            };
+
+            sources.push(DataSource {
+                id,
+                description,
+                accuracy,
+                segment,
+            });
+        }
+        Ok(sources)
+    }
+}
+
+/// Returns the name of the data set record of this ID with its ID, e.g. `IAU_EARTH (399)`, or only its ID if it has no name.
+fn record_name<const ENTRIES: usize>(lut: &LookUpTable<ENTRIES>, id: NaifId) -> String {
+    lut.by_id
+        .get(&id)
+        .and_then(|index| {
+            lut.by_name
+                .iter()
+                .find(|(_, name_index)| *name_index == index)
+        })
+        .map(|(name, _)| format!("{name} ({id})"))
+        .unwrap_or_else(|| format!("#{id}"))
+}
@@ -10,12 +10,12 @@
 
 use crate::{
     astro::{Aberration, AzElRange},
-    constants::SPEED_OF_LIGHT_KM_S,
     ephemerides::{EphemerisError, EphemerisPhysicsSnafu},
     errors::{AlmanacError, EphemerisSnafu, PhysicsError},
-    frames::Frame,
-    math::angles::{between_0_360, between_pm_180},
+    frames::{Frame, TopocentricAxes},
+    math::angles::azimuth_elevation_deg,
     prelude::Orbit,
+    NaifId,
 };
 
 use super::Almanac;
@@ -29,6 +29,9 @@ use snafu::ResultExt;
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
 
+/// Orientation ID of the SEZ frame of the transmitter, which only exists during the AER computation
+const TX_SEZ_ID: NaifId = -1;
+
 #[cfg_attr(feature = "python", pymethods)]
 impl Almanac {
     /// Computes the azimuth (in degrees), elevation (in degrees), and range (in kilometers) of the
@@ -79,14 +82,23 @@ impl Almanac {
             }
         }
 
-        // Compute the SEZ DCM
-        // SEZ DCM is topo to fixed
-        let sez_dcm = tx
-            .dcm_from_topocentric_to_body_fixed(-1)
+        // Compute the SEZ DCM, from the topocentric frame of the transmitter to its body fixed frame
+        let (latitude_deg, longitude_deg, height_km) = tx
+            .latlongalt()
             .context(EphemerisPhysicsSnafu { action: "" })
             .context(EphemerisSnafu {
                 action: "computing SEZ DCM for AER",
             })?;
+        let sez_dcm = Frame::topocentric(
+            "SEZ",
+            TX_SEZ_ID,
+            latitude_deg,
+            longitude_deg,
+            height_km,
+            tx.frame,
+        )
+        .with_axes(TopocentricAxes::SEZ)
+        .dcm_to_body_fixed();
 
         let tx_sez = (sez_dcm.transpose() * tx)
             .context(EphemerisPhysicsSnafu { action: "" })
@@ -115,16 +127,14 @@ impl Almanac {
         // Compute the range-rate \dot ρ. Note that rx_in_tx_frame is already the relative velocity of rx wrt tx!
         let range_rate_km_s = rho_tx_frame.dot(&rx_in_tx_frame.velocity_km_s) / rho_tx_frame.norm();
 
-        // Finally, compute the elevation (math is the same as declination)
+        // Finally, compute the azimuth and elevation (math is the same as declination)
         // Source: Vallado, section 4.4.3
-        // Only the sine is needed as per Vallado, and the formula is the same as the declination
+        // Only the sine is needed for the elevation as per Vallado, and the formula is the same as the declination
         // because we're in the SEZ frame.
-        let elevation_deg = between_pm_180((rho_sez.z / rho_sez.norm()).asin().to_degrees());
+        let (azimuth_deg, elevation_deg) = azimuth_elevation_deg(&rho_sez);
         if (elevation_deg - 90.0).abs() < 1e-6 {
             warn!("object nearly overhead (el = {elevation_deg:.6} deg), azimuth may be incorrect");
         }
-        // For the elevation, we need to perform a quadrant check because it's measured from 0 to 360 degrees.
-        let azimuth_deg = between_0_360((rho_sez.y.atan2(-rho_sez.x)).to_degrees());
 
         Ok(AzElRange {
             epoch: tx.epoch,
@@ -133,7 +143,7 @@ impl Almanac {
             range_km: rho_sez.norm(),
             range_rate_km_s,
             obstructed_by,
-            light_time: (rho_sez.norm() / SPEED_OF_LIGHT_KM_S).seconds(),
+            light_time: (rho_sez.norm() / self.speed_of_light_km_s()).seconds(),
         })
     }
 }
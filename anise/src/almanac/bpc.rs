@@ -19,10 +19,11 @@ use snafu::ensure;
 use crate::naif::daf::NAIFSummaryRecord;
 use crate::naif::pck::BPCSummaryRecord;
 use crate::naif::BPC;
+use crate::orientations::stk::convert_stk_attitude;
 use crate::orientations::{NoOrientationsLoadedSnafu, OrientationError};
 use crate::{naif::daf::DAFError, NaifId};
 
-use super::{Almanac, MAX_LOADED_BPCS};
+use super::{log_integrity, Almanac, MAX_LOADED_BPCS};
 
 impl Almanac {
     pub fn from_bpc(bpc: BPC) -> Result<Almanac, OrientationError> {
@@ -32,6 +33,14 @@ impl Almanac {
 
     /// Loads a Binary Planetary Constants kernel.
     pub fn with_bpc(&self, bpc: BPC) -> Result<Self, OrientationError> {
+        if self.load_checks {
+            log_integrity("BPC", bpc.integrity_report());
+        }
+        self.add_bpc(bpc)
+    }
+
+    /// Adds the BPC to a new context without checking its integrity, e.g. because it was checked when it was parsed.
+    pub(crate) fn add_bpc(&self, bpc: BPC) -> Result<Self, OrientationError> {
         // This is just a bunch of pointers so it doesn't use much memory.
         let mut me = self.clone();
         let mut data_idx = MAX_LOADED_BPCS;
@@ -50,6 +59,17 @@ impl Almanac {
         Ok(me)
     }
 
+    /// Loads an STK attitude file (`.a`) as a BPC where the orientation is that of `frame_id`.
+    ///
+    /// Refer to [convert_stk_attitude] for the supported subset of the format.
+    pub fn load_stk_attitude(
+        &self,
+        path: &str,
+        frame_id: NaifId,
+    ) -> Result<Self, OrientationError> {
+        self.with_bpc(convert_stk_attitude(path, frame_id)?)
+    }
+
     pub fn num_loaded_bpc(&self) -> usize {
         let mut count = 0;
         for maybe in &self.bpc_data {
@@ -107,7 +127,12 @@ impl Almanac {
             .enumerate()
         {
             let bpc = maybe_bpc.as_ref().unwrap();
-            if let Ok((summary, idx_in_bpc)) = bpc.summary_from_id_at_epoch(id, epoch) {
+            let found = if self.spice_compat {
+                bpc.last_summary_from_id_at_epoch(id, epoch)
+            } else {
+                bpc.summary_from_id_at_epoch(id, epoch)
+            };
+            if let Ok((summary, idx_in_bpc)) = found {
                 // NOTE: We're iterating backward, so the correct BPC number is "total loaded" minus "current iteration".
                 return Ok((summary, self.num_loaded_bpc() - no - 1, idx_in_bpc));
             }
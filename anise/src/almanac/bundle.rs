@@ -0,0 +1,401 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use bytes::Bytes;
+use der::Encode;
+use hifitime::Epoch;
+
+use crate::constants::orientations::J2000;
+use crate::errors::{AlmanacError, AlmanacResult, DecodingError};
+use crate::naif::daf::NAIFSummaryRecord;
+use crate::orientations::builtin_rotation;
+use crate::prelude::Frame;
+use crate::structure::dataset::{DataSet, DataSetError, DataSetT};
+use crate::NaifId;
+
+use super::Almanac;
+
+/// Header of an ANISE bundle, followed by the length in bytes of its manifest as a little endian u64, cf. [KernelBundle].
+pub const BUNDLE_MAGIC: &[u8; 16] = b"ANISE/BUNDLE/v1\n";
+
+/// Entries of a bundle start on a multiple of this many bytes, such that the doubles of DAF files are aligned.
+const BUNDLE_ALIGNMENT: usize = 8;
+
+/// An entry of the manifest of a [KernelBundle].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BundleEntry {
+    /// Kind of the kernel, e.g. `SPK`, `BPC`, `LSK`, or the kind of an ANISE data set like `planetary data`
+    pub kind: String,
+    /// Name of the entry, typically the path of its source file relative to the bundled directory
+    pub name: String,
+    /// Offset of the entry from the start of the data, which follows the manifest
+    pub offset: usize,
+    /// Length of the entry in bytes
+    pub length: usize,
+    /// CRC32 of the bytes of the entry
+    pub crc32: u32,
+}
+
+impl fmt::Display for BundleEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t0x{:08x}\t{}",
+            self.kind, self.offset, self.length, self.crc32, self.name
+        )
+    }
+}
+
+/// A single file containing several kernels (SPK, BPC, LSK, SCLK, and ANISE data sets) and a manifest of its entries,
+/// which can be loaded at once with `Almanac::load`, e.g. to distribute all of the data of a mission.
+///
+/// # Format
+/// The file starts with [BUNDLE_MAGIC] and the length of the manifest, followed by the manifest (one line per entry, cf.
+/// the Display of [BundleEntry]) and the entries, each padded to eight bytes.
+#[derive(Clone, Debug, Default)]
+pub struct KernelBundle {
+    entries: Vec<(BundleEntry, Bytes)>,
+}
+
+impl KernelBundle {
+    /// Adds the provided kernel to this bundle.
+    pub fn push(&mut self, kind: &str, name: &str, bytes: Bytes) {
+        let offset = self
+            .entries
+            .last()
+            .map(|(entry, _)| aligned(entry.offset + entry.length))
+            .unwrap_or(0);
+
+        self.entries.push((
+            BundleEntry {
+                kind: kind.to_string(),
+                name: name.to_string(),
+                offset,
+                length: bytes.len(),
+                crc32: crc32fast::hash(&bytes),
+            },
+            bytes,
+        ));
+    }
+
+    /// Encodes the provided data set and adds it to this bundle.
+    pub fn push_dataset<T: DataSetT, const ENTRIES: usize>(
+        &mut self,
+        name: &str,
+        dataset: &DataSet<T, ENTRIES>,
+    ) -> Result<(), DataSetError> {
+        let mut buf = vec![];
+        dataset
+            .encode_to_vec(&mut buf)
+            .map_err(|err| DataSetError::DataDecoding {
+                action: "encoding data set",
+                source: DecodingError::DecodingDer { err },
+            })?;
+
+        self.push(T::NAME, name, Bytes::from(buf));
+        Ok(())
+    }
+
+    /// Returns the entries of the manifest of this bundle.
+    pub fn entries(&self) -> impl Iterator<Item = &BundleEntry> {
+        self.entries.iter().map(|(entry, _)| entry)
+    }
+
+    /// Returns the bytes of the entry of the provided name, if any.
+    pub fn get(&self, name: &str) -> Option<Bytes> {
+        self.entries
+            .iter()
+            .find(|(entry, _)| entry.name == name)
+            .map(|(_, bytes)| bytes.clone())
+    }
+
+    /// Returns the number of entries of this bundle
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if this bundle has no entry
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the manifest of this bundle, one entry per line.
+    pub fn manifest(&self) -> String {
+        self.entries()
+            .map(|entry| format!("{entry}\n"))
+            .collect::<String>()
+    }
+
+    /// Serializes this bundle, cf. [KernelBundle] for the format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let manifest = self.manifest();
+
+        let mut buf = Vec::new();
+        buf.extend_from_slice(BUNDLE_MAGIC);
+        buf.extend_from_slice(&(manifest.len() as u64).to_le_bytes());
+        buf.extend_from_slice(manifest.as_bytes());
+
+        let data_start = aligned(buf.len());
+        for (entry, bytes) in &self.entries {
+            buf.resize(data_start + entry.offset, 0);
+            buf.extend_from_slice(bytes);
+        }
+
+        buf
+    }
+
+    /// Parses a bundle from its bytes, checking the CRC32 of each entry. The entries are not copied.
+    pub fn parse(bytes: Bytes) -> AlmanacResult<Self> {
+        let malformed = |err: String| AlmanacError::GenericError {
+            err: format!("malformed bundle: {err}"),
+        };
+        let inaccessible = |start: usize, end: usize| AlmanacError::TLDataSet {
+            action: "parsing bundle",
+            source: DataSetError::DataDecoding {
+                action: "computing the span of a bundle entry",
+                source: DecodingError::InaccessibleBytes {
+                    start,
+                    end,
+                    size: bytes.len(),
+                },
+            },
+        };
+
+        if !bytes.starts_with(BUNDLE_MAGIC) {
+            return Err(malformed("missing header".to_string()));
+        }
+
+        let header_len = BUNDLE_MAGIC.len() + 8;
+        let manifest_len = bytes
+            .get(BUNDLE_MAGIC.len()..header_len)
+            .map(|len| u64::from_le_bytes(len.try_into().unwrap()) as usize)
+            .ok_or_else(|| malformed("missing manifest length".to_string()))?;
+
+        let manifest_end = header_len
+            .checked_add(manifest_len)
+            .ok_or_else(|| inaccessible(header_len, usize::MAX))?;
+        let manifest = bytes
+            .get(header_len..manifest_end)
+            .ok_or_else(|| inaccessible(header_len, manifest_end))?;
+        let manifest = core::str::from_utf8(manifest)
+            .map_err(|_| malformed("manifest is not valid UTF-8".to_string()))?;
+
+        let data_start = aligned(manifest_end);
+
+        let mut entries = Vec::new();
+        for (lno, line) in manifest.lines().enumerate() {
+            let fields = line.splitn(5, '\t').collect::<Vec<&str>>();
+            if fields.len() != 5 {
+                return Err(malformed(format!("manifest line {}: {line}", lno + 1)));
+            }
+
+            let parse_usize = |field: &str| {
+                field
+                    .parse::<usize>()
+                    .map_err(|e| malformed(format!("manifest line {}: {e}", lno + 1)))
+            };
+
+            let entry = BundleEntry {
+                kind: fields[0].to_string(),
+                offset: parse_usize(fields[1])?,
+                length: parse_usize(fields[2])?,
+                crc32: u32::from_str_radix(fields[3].trim_start_matches("0x"), 16)
+                    .map_err(|e| malformed(format!("manifest line {}: {e}", lno + 1)))?,
+                name: fields[4].to_string(),
+            };
+
+            let start = data_start
+                .checked_add(entry.offset)
+                .ok_or_else(|| inaccessible(data_start, usize::MAX))?;
+            let end = start
+                .checked_add(entry.length)
+                .ok_or_else(|| inaccessible(start, usize::MAX))?;
+            if end > bytes.len() {
+                return Err(malformed(format!(
+                    "{} spans bytes {start} to {end} but the bundle has {} bytes",
+                    entry.name,
+                    bytes.len()
+                )));
+            }
+
+            let data = bytes.slice(start..end);
+            let crc32 = crc32fast::hash(&data);
+            if crc32 != entry.crc32 {
+                return Err(AlmanacError::GenericError {
+                    err: format!(
+                        "integrity error in bundle: CRC32 of {} is 0x{crc32:08x} but manifest expects 0x{:08x}",
+                        entry.name, entry.crc32
+                    ),
+                });
+            }
+
+            entries.push((entry, data));
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Returns the bytes of each entry with its name.
+    pub(crate) fn data(&self) -> impl Iterator<Item = (&str, Bytes)> {
+        self.entries
+            .iter()
+            .map(|(entry, bytes)| (entry.name.as_str(), bytes.clone()))
+    }
+}
+
+impl Almanac {
+    /// Returns a description of each reference of the loaded SPK and BPC segments which this Almanac cannot resolve, at the
+    /// start of each segment: the center of each SPK segment must be reachable from the root of the ephemerides, and the
+    /// frame of each SPK segment and the inertial frame of each BPC segment must be reachable from the root of the
+    /// orientations.
+    ///
+    /// This is empty if the loaded data is self-consistent, e.g. before distributing a [KernelBundle].
+    pub fn unresolved_references(&self) -> AlmanacResult<Vec<String>> {
+        let mut unresolved = Vec::new();
+
+        for spk in self.spk_data.iter().take(self.num_loaded_spk()).flatten() {
+            let summaries = spk
+                .data_summaries()
+                .map_err(|e| AlmanacError::GenericError {
+                    err: format!("reading SPK summaries: {e}"),
+                })?;
+
+            for summary in summaries.iter().filter(|summary| !summary.is_empty()) {
+                let epoch = summary.start_epoch();
+
+                if self
+                    .ephemeris_path_to_root(Frame::from_ephem_j2000(summary.center_id), epoch)
+                    .is_err()
+                {
+                    unresolved.push(format!(
+                        "SPK segment of {} @ {epoch}: center {} is not defined",
+                        summary.target_id, summary.center_id
+                    ));
+                }
+
+                if !self.orientation_resolves(summary.frame_id, epoch) {
+                    unresolved.push(format!(
+                        "SPK segment of {} @ {epoch}: frame {} is not defined",
+                        summary.target_id, summary.frame_id
+                    ));
+                }
+            }
+        }
+
+        for bpc in self.bpc_data.iter().take(self.num_loaded_bpc()).flatten() {
+            let summaries = bpc
+                .data_summaries()
+                .map_err(|e| AlmanacError::GenericError {
+                    err: format!("reading BPC summaries: {e}"),
+                })?;
+
+            for summary in summaries.iter().filter(|summary| !summary.is_empty()) {
+                let epoch = summary.start_epoch();
+                if !self.orientation_resolves(summary.inertial_frame_id, epoch) {
+                    unresolved.push(format!(
+                        "BPC segment of {} @ {epoch}: inertial frame {} is not defined",
+                        summary.frame_id, summary.inertial_frame_id
+                    ));
+                }
+            }
+        }
+
+        Ok(unresolved)
+    }
+
+    /// Returns true if this orientation is built in or can be rotated to the root of the orientations.
+    fn orientation_resolves(&self, id: NaifId, epoch: Epoch) -> bool {
+        id == J2000
+            || builtin_rotation(id).is_some()
+            || self
+                .orientation_path_to_root(Frame::from_orient_ssb(id), epoch)
+                .is_ok()
+    }
+}
+
+fn aligned(offset: usize) -> usize {
+    offset.div_ceil(BUNDLE_ALIGNMENT) * BUNDLE_ALIGNMENT
+}
+
+#[cfg(test)]
+mod ut_bundle {
+    use super::{AlmanacError, DataSetError, DecodingError, KernelBundle, BUNDLE_MAGIC};
+    use crate::prelude::Almanac;
+    use bytes::Bytes;
+
+    #[test]
+    fn bundle_round_trip() {
+        let spk = std::fs::read("../data/de440s.bsp").unwrap();
+        let pca = std::fs::read("../data/pck08.pca").unwrap();
+
+        let mut bundle = KernelBundle::default();
+        bundle.push("SPK", "de440s.bsp", Bytes::from(spk.clone()));
+        bundle.push("planetary data", "pck08.pca", Bytes::from(pca));
+        assert_eq!(bundle.len(), 2);
+
+        let bytes = bundle.to_bytes();
+        assert!(bytes.starts_with(BUNDLE_MAGIC));
+
+        let parsed = KernelBundle::parse(Bytes::from(bytes.clone())).unwrap();
+        assert_eq!(
+            parsed.entries().collect::<Vec<_>>(),
+            bundle.entries().collect::<Vec<_>>()
+        );
+        assert_eq!(parsed.get("de440s.bsp").unwrap(), Bytes::from(spk));
+        assert_eq!(parsed.entries().nth(1).unwrap().offset % 8, 0);
+
+        // The bundle is loaded like any other kernel
+        let almanac = Almanac::default()
+            .load_from_bytes(Bytes::from(bytes.clone()))
+            .unwrap();
+        assert_eq!(almanac.num_loaded_spk(), 1);
+        assert!(!almanac.planetary_data.is_empty());
+        assert!(almanac.unresolved_references().unwrap().is_empty());
+
+        // Corrupting an entry is detected
+        let mut corrupted = bytes;
+        let last = corrupted.len() - 1;
+        corrupted[last] ^= 0xff;
+        assert!(KernelBundle::parse(Bytes::from(corrupted)).is_err());
+    }
+
+    #[test]
+    fn bundle_overflowing_spans() {
+        let bundle_with_manifest = |manifest: &str, manifest_len: u64| {
+            let mut buf = Vec::new();
+            buf.extend_from_slice(BUNDLE_MAGIC);
+            buf.extend_from_slice(&manifest_len.to_le_bytes());
+            buf.extend_from_slice(manifest.as_bytes());
+            Bytes::from(buf)
+        };
+
+        // Manifest length overflowing the header
+        assert!(KernelBundle::parse(bundle_with_manifest("", u64::MAX)).is_err());
+
+        // Offset and length overflowing the start of the data
+        for manifest in [
+            format!("SPK\t{}\t1\t0x0\tde440s.bsp", usize::MAX),
+            format!("SPK\t0\t{}\t0x0\tde440s.bsp", usize::MAX),
+        ] {
+            assert!(matches!(
+                KernelBundle::parse(bundle_with_manifest(&manifest, manifest.len() as u64)),
+                Err(AlmanacError::TLDataSet {
+                    source: DataSetError::DataDecoding {
+                        source: DecodingError::InaccessibleBytes { .. },
+                        ..
+                    },
+                    ..
+                })
+            ));
+        }
+    }
+}
@@ -0,0 +1,227 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use hifitime::{Duration, Epoch};
+
+use crate::astro::Aberration;
+use crate::math::cartesian::CartesianState;
+use crate::math::rotation::DCM;
+use crate::prelude::Frame;
+
+use super::Almanac;
+
+/// Default number of states and of DCMs kept in a [QueryCache].
+pub const DEFAULT_CACHE_CAPACITY: usize = 64;
+
+#[derive(Copy, Clone, Debug)]
+struct CachedState {
+    target_frame: Frame,
+    observer_frame: Frame,
+    ab_corr: Option<Aberration>,
+    state: CartesianState,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct CachedRotation {
+    from_frame: Frame,
+    to_frame: Frame,
+    epoch: Epoch,
+    dcm: DCM,
+}
+
+#[derive(Debug, Default)]
+struct CacheEntries {
+    states: VecDeque<CachedState>,
+    rotations: VecDeque<CachedRotation>,
+    hits: usize,
+    misses: usize,
+}
+
+/// A cache of the results of the `transform` and `rotate` queries of an Almanac, where a query within the tolerance of
+/// the epoch of a cached query of the same frames returns the cached result.
+///
+/// This is useful when the same epoch is queried many times, e.g. by several widgets of a real-time display. Only
+/// successful queries are cached, and the least recent results are evicted once the capacity is reached.
+///
+/// # Note
+/// The cached state is returned as is, so its epoch may differ from the requested epoch by up to the tolerance.
+///
+/// The cache is shared by references to the same Almanac (including across threads), but clones of a cache are empty:
+/// the Almanacs derived from an Almanac with a cache (e.g. via `load` or `with_spk`) have their own cache, so they never
+/// return results computed with other kernels.
+#[derive(Debug)]
+pub struct QueryCache {
+    /// Maximum difference between the requested epoch and the epoch of a cached result
+    pub tolerance: Duration,
+    /// Maximum number of states and of DCMs kept in the cache
+    pub capacity: usize,
+    entries: Mutex<CacheEntries>,
+}
+
+impl QueryCache {
+    /// Initializes a new empty cache with the provided epoch tolerance and capacity.
+    pub fn new(tolerance: Duration, capacity: usize) -> Self {
+        Self {
+            tolerance,
+            capacity,
+            entries: Mutex::new(CacheEntries::default()),
+        }
+    }
+
+    /// Returns the number of queries answered from this cache
+    pub fn hits(&self) -> usize {
+        self.entries().hits
+    }
+
+    /// Returns the number of queries which were not in this cache
+    pub fn misses(&self) -> usize {
+        self.entries().misses
+    }
+
+    /// Returns the number of states and DCMs in this cache
+    pub fn len(&self) -> usize {
+        let entries = self.entries();
+        entries.states.len() + entries.rotations.len()
+    }
+
+    /// Returns true if this cache holds nothing
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all of the cached results, and resets the hit and miss counters
+    pub fn clear(&self) {
+        *self.entries() = CacheEntries::default();
+    }
+
+    /// Locks the entries of this cache. A panic of another thread while it held the lock cannot leave the entries in an
+    /// invalid state, so the lock is recovered instead of propagating the panic to all of the threads sharing this cache.
+    fn entries(&self) -> MutexGuard<'_, CacheEntries> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn within_tolerance(&self, cached: Epoch, epoch: Epoch) -> bool {
+        (cached - epoch).abs() <= self.tolerance
+    }
+
+    pub(crate) fn state(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+    ) -> Option<CartesianState> {
+        let mut entries = self.entries();
+        let found = entries
+            .states
+            .iter()
+            .rev()
+            .find(|cached| {
+                cached.target_frame == target_frame
+                    && cached.observer_frame == observer_frame
+                    && cached.ab_corr == ab_corr
+                    && self.within_tolerance(cached.state.epoch, epoch)
+            })
+            .map(|cached| cached.state);
+
+        if found.is_some() {
+            entries.hits += 1;
+        } else {
+            entries.misses += 1;
+        }
+        found
+    }
+
+    pub(crate) fn insert_state(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        ab_corr: Option<Aberration>,
+        state: CartesianState,
+    ) {
+        let mut entries = self.entries();
+        if entries.states.len() >= self.capacity {
+            entries.states.pop_front();
+        }
+        entries.states.push_back(CachedState {
+            target_frame,
+            observer_frame,
+            ab_corr,
+            state,
+        });
+    }
+
+    pub(crate) fn rotation(&self, from_frame: Frame, to_frame: Frame, epoch: Epoch) -> Option<DCM> {
+        let mut entries = self.entries();
+        let found = entries
+            .rotations
+            .iter()
+            .rev()
+            .find(|cached| {
+                cached.from_frame == from_frame
+                    && cached.to_frame == to_frame
+                    && self.within_tolerance(cached.epoch, epoch)
+            })
+            .map(|cached| cached.dcm);
+
+        if found.is_some() {
+            entries.hits += 1;
+        } else {
+            entries.misses += 1;
+        }
+        found
+    }
+
+    pub(crate) fn insert_rotation(
+        &self,
+        from_frame: Frame,
+        to_frame: Frame,
+        epoch: Epoch,
+        dcm: DCM,
+    ) {
+        let mut entries = self.entries();
+        if entries.rotations.len() >= self.capacity {
+            entries.rotations.pop_front();
+        }
+        entries.rotations.push_back(CachedRotation {
+            from_frame,
+            to_frame,
+            epoch,
+            dcm,
+        });
+    }
+}
+
+impl Clone for QueryCache {
+    /// Returns a new empty cache with the same tolerance and capacity.
+    fn clone(&self) -> Self {
+        Self::new(self.tolerance, self.capacity)
+    }
+}
+
+impl Almanac {
+    /// Returns a copy of this Almanac which caches the results of its `transform` and `rotate` queries, where queries
+    /// within the provided tolerance (e.g. 1 microsecond) of a cached epoch return the cached result, cf. [QueryCache].
+    pub fn with_cache(&self, tolerance: Duration) -> Self {
+        let mut me = self.clone();
+        me.cache = Some(QueryCache::new(tolerance, DEFAULT_CACHE_CAPACITY));
+        me
+    }
+
+    /// Returns a copy of this Almanac without a query cache.
+    pub fn without_cache(&self) -> Self {
+        let mut me = self.clone();
+        me.cache = None;
+        me
+    }
+}
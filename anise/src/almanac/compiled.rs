@@ -0,0 +1,801 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::{Epoch, Unit};
+use snafu::ResultExt;
+
+use super::Almanac;
+use crate::constants::orientations::{ICRF, ITRF93_FAST, J2000};
+use crate::constants::registry::UT1_MINUS_UTC;
+use crate::ephemerides::paths::MAX_TREE_DEPTH;
+use crate::ephemerides::EphemerisError;
+use crate::errors::{AlmanacError, AlmanacResult, EphemerisSnafu, OrientationSnafu};
+use crate::math::cartesian::CartesianState;
+use crate::math::interpolation::chebyshev_eval;
+use crate::math::rotation::{r1, r1_dot, r3, r3_dot, DCM};
+use crate::math::{Matrix3, Vector3};
+use crate::naif::daf::{DAFError, DafDataType, NAIFSummaryRecord, SegmentRecord, DAF};
+use crate::orientations::{
+    builtin_rotation, earth_fast_rotation_truncated, icrf_frame_bias, NutationTruncation,
+    OrientationError,
+};
+use crate::prelude::Frame;
+use crate::structure::planetocentric::PlanetaryData;
+use crate::NaifId;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Chebyshev records of an SPK or BPC segment, decoded once such that they can be evaluated without any look up.
+#[derive(Clone, Debug, PartialEq)]
+struct CompiledSegment {
+    /// Start of the interval where this segment has precedence, in ET seconds
+    start_et_s: f64,
+    /// End of the interval where this segment has precedence, in ET seconds
+    end_et_s: f64,
+    /// Start of the first record, in ET seconds
+    init_et_s: f64,
+    /// Duration of each record, in seconds
+    interval_s: f64,
+    /// Coefficients of each record, per component: three for the position or angles (type 2), and three more for the velocity (type 3)
+    records: Vec<Vec<Vec<f64>>>,
+}
+
+impl CompiledSegment {
+    /// Returns the three components and their time derivatives at the provided epoch.
+    fn evaluate(&self, et_s: f64, epoch: Epoch) -> Option<(Vector3, Vector3)> {
+        let idx = (((et_s - self.init_et_s) / self.interval_s).max(0.0) as usize)
+            .min(self.records.len() - 1);
+        let radius_s = self.interval_s / 2.0;
+        let midpoint_et_s = self.init_et_s + (idx as f64 + 0.5) * self.interval_s;
+        let normalized_time = (et_s - midpoint_et_s) / radius_s;
+
+        let eval = |coeffs: &[f64]| {
+            chebyshev_eval(
+                normalized_time,
+                coeffs,
+                radius_s,
+                epoch,
+                coeffs.len().saturating_sub(1),
+            )
+            .ok()
+        };
+
+        let coefficients = &self.records[idx];
+        let mut val = Vector3::zeros();
+        let mut deriv = Vector3::zeros();
+        for (cno, coeffs) in coefficients.iter().take(3).enumerate() {
+            let (cval, cderiv) = eval(coeffs)?;
+            val[cno] = cval;
+            deriv[cno] = match coefficients.get(cno + 3) {
+                Some(deriv_coeffs) => eval(deriv_coeffs)?.0,
+                None => cderiv,
+            };
+        }
+        Some((val, deriv))
+    }
+
+    /// Returns the segment which has precedence at this epoch, from segments in chronological order.
+    fn select(segments: &[Self], et_s: f64) -> &Self {
+        segments
+            .iter()
+            .find(|segment| et_s < segment.end_et_s)
+            .unwrap_or(&segments[segments.len() - 1])
+    }
+}
+
+/// Translation of a body with respect to its center over the span of a compiled query.
+#[derive(Clone, Debug, PartialEq)]
+struct CompiledLeg {
+    /// +1.0 if this leg is on the path of the target, -1.0 if it is on the path of the observer
+    sign: f64,
+    /// Chronological segments covering the whole span
+    segments: Vec<CompiledSegment>,
+}
+
+/// Rotation of an orientation from its parent over the span of a compiled query, resolved from the same sources as
+/// [Almanac::rotation_to_parent].
+#[derive(Clone, Debug, PartialEq)]
+enum CompiledRotation {
+    /// Constant rotation, e.g. of the built-in inertial frames or of Euler parameters
+    Constant(Matrix3),
+    /// Fast Earth orientation model, with the UT1-UTC offset and the nutation truncation of the Almanac
+    EarthFast {
+        ut1_minus_utc_s: f64,
+        truncation: Option<NutationTruncation>,
+    },
+    /// Planetary constants, with the planetary data of their system and the nutation truncation of the Almanac
+    Planetary {
+        data: PlanetaryData,
+        system: PlanetaryData,
+        truncation: Option<NutationTruncation>,
+    },
+    /// Chronological BPC segments of the right ascension, declination, and twist angles, covering the whole span
+    Chebyshev(Vec<CompiledSegment>),
+}
+
+impl CompiledRotation {
+    /// Returns the rotation matrix from the parent orientation and its time derivative.
+    fn evaluate(&self, et_s: f64, epoch: Epoch) -> Option<(Matrix3, Matrix3)> {
+        let dcm = match self {
+            Self::Constant(rot_mat) => return Some((*rot_mat, Matrix3::zeros())),
+            Self::EarthFast {
+                ut1_minus_utc_s,
+                truncation,
+            } => earth_fast_rotation_truncated(
+                epoch,
+                *ut1_minus_utc_s,
+                truncation.unwrap_or_default(),
+            ),
+            Self::Planetary {
+                data,
+                system,
+                truncation: Some(truncation),
+            } => data
+                .rotation_to_parent_truncated(epoch, system, *truncation)
+                .ok()?,
+            Self::Planetary {
+                data,
+                system,
+                truncation: None,
+            } => data.rotation_to_parent(epoch, system).ok()?,
+            Self::Chebyshev(segments) => {
+                let (ra_dec_w, d_ra_dec_w) =
+                    CompiledSegment::select(segments, et_s).evaluate(et_s, epoch)?;
+                let (ra_rad, dec_rad, twist_rad) = (ra_dec_w[0], ra_dec_w[1], ra_dec_w[2]);
+
+                let rot_mat = r3(twist_rad) * r1(dec_rad) * r3(ra_rad);
+                let rot_mat_dt = d_ra_dec_w[2] * r3_dot(twist_rad) * r1(dec_rad) * r3(ra_rad)
+                    + d_ra_dec_w[1] * r3(twist_rad) * r1_dot(dec_rad) * r3(ra_rad)
+                    + d_ra_dec_w[0] * r3(twist_rad) * r1(dec_rad) * r3_dot(ra_rad);
+                return Some((rot_mat, rot_mat_dt));
+            }
+        };
+        Some((dcm.rot_mat, dcm.rot_mat_dt.unwrap_or_else(Matrix3::zeros)))
+    }
+
+    /// Returns the rotation matrix from the root of the chain to its first orientation, and its time derivative, where
+    /// the chain lists the rotations from each orientation to its parent.
+    fn evaluate_chain(chain: &[Self], et_s: f64, epoch: Epoch) -> Option<(Matrix3, Matrix3)> {
+        let mut rot_mat = Matrix3::identity();
+        let mut rot_mat_dt = Matrix3::zeros();
+        for rotation in chain {
+            let (leg_rot_mat, leg_rot_mat_dt) = rotation.evaluate(et_s, epoch)?;
+            rot_mat_dt = rot_mat_dt * leg_rot_mat + rot_mat * leg_rot_mat_dt;
+            rot_mat *= leg_rot_mat;
+        }
+        Some((rot_mat, rot_mat_dt))
+    }
+}
+
+/// A transformation query compiled over a time span by [Almanac::compile], for high rate evaluations (e.g. in a real time loop).
+///
+/// The path from the target to the observer and the rotation path to the requested frame are resolved, and all of the
+/// Chebyshev records of the SPK and BPC segments used over the span are decoded, when the query is compiled. Hence, evaluating
+/// the query does not perform any look up in the Almanac and does not decode any data.
+///
+/// # Limitations
+/// + Only the Chebyshev SPK and BPC segments (types 2 and 3) are supported.
+/// + The ephemeris and orientation providers, the two-vector frames, and the boundary smoothing of the Almanac are not supported.
+/// + As for [Almanac::transform] without aberration correction, this is a geometric state.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise"))]
+pub struct CompiledQuery {
+    /// Frame of the returned states
+    pub frame: Frame,
+    pub start: Epoch,
+    pub end: Epoch,
+    legs: Vec<CompiledLeg>,
+    /// Rotations from the orientation of the target up to the common orientation
+    from_rotations: Vec<CompiledRotation>,
+    /// Rotations from the requested orientation up to the common orientation
+    to_rotations: Vec<CompiledRotation>,
+}
+
+impl CompiledQuery {
+    /// Returns the number of SPK and BPC segments decoded in this query.
+    pub fn num_segments(&self) -> usize {
+        let num_bpc_segments = self
+            .from_rotations
+            .iter()
+            .chain(&self.to_rotations)
+            .map(|rotation| match rotation {
+                CompiledRotation::Chebyshev(segments) => segments.len(),
+                _ => 0,
+            })
+            .sum::<usize>();
+        self.legs
+            .iter()
+            .map(|leg| leg.segments.len())
+            .sum::<usize>()
+            + num_bpc_segments
+    }
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl CompiledQuery {
+    /// Returns the geometric state of the target with respect to the observer at the provided epoch, or None if the epoch
+    /// is outside of the span of this query.
+    ///
+    /// :type epoch: Epoch
+    /// :rtype: Orbit
+    pub fn evaluate(&self, epoch: Epoch) -> Option<CartesianState> {
+        if epoch < self.start || epoch > self.end {
+            return None;
+        }
+
+        let et_s = epoch.to_et_seconds();
+        let mut radius_km = Vector3::zeros();
+        let mut velocity_km_s = Vector3::zeros();
+
+        for leg in &self.legs {
+            let (pos_km, vel_km_s) =
+                CompiledSegment::select(&leg.segments, et_s).evaluate(et_s, epoch)?;
+            radius_km += leg.sign * pos_km;
+            velocity_km_s += leg.sign * vel_km_s;
+        }
+
+        // Rotate from the orientation of the target into the requested orientation, via their common orientation.
+        let (from_rot_mat, from_rot_mat_dt) =
+            CompiledRotation::evaluate_chain(&self.from_rotations, et_s, epoch)?;
+        let (to_rot_mat, to_rot_mat_dt) =
+            CompiledRotation::evaluate_chain(&self.to_rotations, et_s, epoch)?;
+        let rot_mat = to_rot_mat * from_rot_mat.transpose();
+        let rot_mat_dt =
+            to_rot_mat_dt * from_rot_mat.transpose() + to_rot_mat * from_rot_mat_dt.transpose();
+
+        Some(CartesianState {
+            radius_km: rot_mat * radius_km,
+            velocity_km_s: rot_mat * velocity_km_s + rot_mat_dt * radius_km,
+            acceleration_km_s2: None,
+            epoch,
+            frame: self.frame,
+        })
+    }
+
+    /// Returns the number of SPK and BPC segments decoded in this query.
+    ///
+    /// :rtype: int
+    #[cfg(feature = "python")]
+    #[pyo3(name = "num_segments")]
+    fn py_num_segments(&self) -> usize {
+        self.num_segments()
+    }
+
+    #[cfg(feature = "python")]
+    fn __str__(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Compiles the geometric state of the target with respect to the observer, expressed in the orientation of the
+    /// provided frame, over the provided span into a [CompiledQuery], which evaluates pre-decoded Chebyshev records
+    /// without any look up, cf. its limitations.
+    ///
+    /// The returned states match those of `transform` to `observer_frame.with_orient(frame.orientation_id)`: the SPK and BPC
+    /// segments are selected with the same precedence as the other queries, including segments which start within the span.
+    ///
+    /// :type target_frame: Frame
+    /// :type observer_frame: Frame
+    /// :type frame: Frame
+    /// :type start: Epoch
+    /// :type end: Epoch
+    /// :rtype: CompiledQuery
+    pub fn compile(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        frame: Frame,
+        start: Epoch,
+        end: Epoch,
+    ) -> AlmanacResult<CompiledQuery> {
+        if end < start {
+            return Err(cannot_compile(format!(
+                "span ends ({end}) before it starts ({start})"
+            )));
+        }
+
+        // Use the frame info of the observer if it is loaded, as `translate` does.
+        let observer_frame = self
+            .frame_from_uid(observer_frame)
+            .unwrap_or(observer_frame);
+
+        let root = self.try_find_ephemeris_root().context(EphemerisSnafu {
+            action: "compiling query",
+        })?;
+        let target_chain = self.compiled_chain(target_frame.ephemeris_id, root, start)?;
+        let observer_chain = self.compiled_chain(observer_frame.ephemeris_id, root, start)?;
+
+        // The common node is the first node of the target chain which is also on the observer chain.
+        let common_node = *target_chain
+            .iter()
+            .find(|id| observer_chain.contains(id))
+            .ok_or(AlmanacError::Ephemeris {
+                action: "compiling query",
+                source: Box::new(EphemerisError::TranslationOrigin {
+                    from: target_frame.into(),
+                    to: observer_frame.into(),
+                    epoch: start,
+                }),
+            })?;
+
+        let mut legs = Vec::new();
+        for (chain, sign) in [(&target_chain, 1.0), (&observer_chain, -1.0)] {
+            for id in chain.iter().take_while(|id| **id != common_node) {
+                legs.push(CompiledLeg {
+                    sign,
+                    segments: self.compiled_segments(*id, start, end)?,
+                });
+            }
+        }
+
+        let (from_rotations, to_rotations) = if target_frame.orientation_id == frame.orientation_id
+        {
+            (Vec::new(), Vec::new())
+        } else {
+            let from_chain = self.compiled_orientation_chain(target_frame, start)?;
+            let to_chain = self.compiled_orientation_chain(frame, start)?;
+
+            let common_orientation = *from_chain.iter().find(|id| to_chain.contains(id)).ok_or(
+                AlmanacError::Orientation {
+                    action: "compiling query",
+                    source: Box::new(OrientationError::RotationOrigin {
+                        from: target_frame.into(),
+                        to: frame.into(),
+                        epoch: start,
+                    }),
+                },
+            )?;
+
+            let mut rotations = [Vec::new(), Vec::new()];
+            for (chain, rotations) in [&from_chain, &to_chain].into_iter().zip(&mut rotations) {
+                for id in chain.iter().take_while(|id| **id != common_orientation) {
+                    rotations.push(self.compiled_rotation(*id, start, end)?);
+                }
+            }
+            let [from_rotations, to_rotations] = rotations;
+            (from_rotations, to_rotations)
+        };
+
+        Ok(CompiledQuery {
+            frame: observer_frame.with_orient(frame.orientation_id),
+            start,
+            end,
+            legs,
+            from_rotations,
+            to_rotations,
+        })
+    }
+}
+
+/// Returns the error of a query which cannot be compiled.
+fn cannot_compile(reason: String) -> AlmanacError {
+    AlmanacError::Ephemeris {
+        action: "compiling query",
+        source: Box::new(EphemerisError::CompiledQuery { reason }),
+    }
+}
+
+impl Almanac {
+    /// Returns the ephemeris IDs from this ID up to the root of the ephemeris tree, both included.
+    fn compiled_chain(&self, id: NaifId, root: NaifId, epoch: Epoch) -> AlmanacResult<Vec<NaifId>> {
+        let mut chain = vec![id];
+        let mut id = id;
+        for _ in 0..MAX_TREE_DEPTH {
+            if id == root {
+                return Ok(chain);
+            }
+            id = self
+                .spk_summary_at_epoch(id, epoch)
+                .context(EphemerisSnafu {
+                    action: "compiling path to root",
+                })?
+                .0
+                .center_id;
+            chain.push(id);
+        }
+
+        Err(AlmanacError::Ephemeris {
+            action: "compiling path to root",
+            source: Box::new(EphemerisError::SPK {
+                action: "compiling path to root",
+                source: DAFError::MaxRecursionDepth,
+            }),
+        })
+    }
+
+    /// Returns the orientation IDs from the orientation of this frame up to the root of the orientation tree, both included.
+    fn compiled_orientation_chain(&self, frame: Frame, epoch: Epoch) -> AlmanacResult<Vec<NaifId>> {
+        let (path_len, path) =
+            self.orientation_path_to_root(frame, epoch)
+                .context(OrientationSnafu {
+                    action: "compiling path to root",
+                })?;
+
+        Ok(core::iter::once(frame.orientation_id)
+            .chain(path.iter().take(path_len).flatten().copied())
+            .collect())
+    }
+
+    /// Decodes the SPK segments of this ID over the provided span, which must all share the same center.
+    fn compiled_segments(
+        &self,
+        id: NaifId,
+        start: Epoch,
+        end: Epoch,
+    ) -> AlmanacResult<Vec<CompiledSegment>> {
+        if self.ephemeris_provider_at_epoch(id, start).is_some() {
+            return Err(cannot_compile(format!(
+                "{id} is computed by an ephemeris provider"
+            )));
+        }
+
+        let summary_at_epoch = |epoch: Epoch| {
+            self.spk_summary_at_epoch(id, epoch)
+                .map(|(summary, spk_no, idx_in_spk)| (*summary, spk_no, idx_in_spk))
+                .context(EphemerisSnafu {
+                    action: "compiling segment",
+                })
+        };
+
+        let center_id = summary_at_epoch(start)?.0.center_id;
+
+        compile_by_precedence(
+            &self.spk_data[..self.num_loaded_spk()],
+            id,
+            start,
+            end,
+            |epoch| {
+                let selected = summary_at_epoch(epoch)?;
+                if selected.0.center_id != center_id {
+                    return Err(cannot_compile(format!(
+                        "center of {id} changes from {center_id} to {} at {epoch}",
+                        selected.0.center_id
+                    )));
+                }
+                Ok(selected)
+            },
+            |source| AlmanacError::Ephemeris {
+                action: "compiling segment",
+                source: Box::new(EphemerisError::SPK {
+                    action: "compiling segment",
+                    source,
+                }),
+            },
+        )
+    }
+
+    /// Resolves the rotation of this orientation from its parent over the provided span, from the same sources and with
+    /// the same precedence as [Almanac::rotation_to_parent].
+    fn compiled_rotation(
+        &self,
+        id: NaifId,
+        start: Epoch,
+        end: Epoch,
+    ) -> AlmanacResult<CompiledRotation> {
+        if id == J2000 {
+            return Ok(CompiledRotation::Constant(Matrix3::identity()));
+        } else if let Some(builtin) = builtin_rotation(id) {
+            return Ok(CompiledRotation::Constant(builtin.dcm().rot_mat));
+        } else if id == ITRF93_FAST {
+            return Ok(CompiledRotation::EarthFast {
+                ut1_minus_utc_s: self
+                    .constant(UT1_MINUS_UTC.name)
+                    .unwrap_or(UT1_MINUS_UTC.value),
+                truncation: self.nutation_truncation,
+            });
+        } else if id == ICRF {
+            return Ok(CompiledRotation::Constant(
+                if self.frame_bias && !self.spice_compat {
+                    icrf_frame_bias().rot_mat
+                } else {
+                    Matrix3::identity()
+                },
+            ));
+        } else if self.two_vector_frame(id).is_some() {
+            return Err(cannot_compile(format!("{id} is a two-vector frame")));
+        } else if self.orientation_provider_at_epoch(id, start).is_some() {
+            return Err(cannot_compile(format!(
+                "{id} is computed by an orientation provider"
+            )));
+        }
+
+        let orientation_err = |source: OrientationError| AlmanacError::Orientation {
+            action: "compiling rotation",
+            source: Box::new(source),
+        };
+
+        if let Ok((summary, _, _)) = self.bpc_summary_at_epoch(id, start) {
+            let parent_id = summary.inertial_frame_id;
+            return compile_by_precedence(
+                &self.bpc_data[..self.num_loaded_bpc()],
+                id,
+                start,
+                end,
+                |epoch| {
+                    let (summary, bpc_no, idx_in_bpc) = self
+                        .bpc_summary_at_epoch(id, epoch)
+                        .map_err(orientation_err)?;
+                    if summary.inertial_frame_id != parent_id {
+                        return Err(cannot_compile(format!(
+                            "parent of {id} changes from {parent_id} to {} at {epoch}",
+                            summary.inertial_frame_id
+                        )));
+                    }
+                    Ok((*summary, bpc_no, idx_in_bpc))
+                },
+                |source| {
+                    orientation_err(OrientationError::BPC {
+                        action: "compiling segment",
+                        source,
+                    })
+                },
+            )
+            .map(CompiledRotation::Chebyshev);
+        }
+
+        if let Ok(data) = self.planetary_data.get_ref_by_id(id) {
+            let system = self
+                .planetary_data
+                .get_ref_by_id(data.parent_id)
+                .unwrap_or(data);
+            return Ok(CompiledRotation::Planetary {
+                data: *data,
+                system: *system,
+                truncation: self.nutation_truncation,
+            });
+        }
+
+        // Euler parameters rotate from the orientation to its parent, so the rotation from the parent is the transpose.
+        let dcm: DCM = self
+            .euler_parameters_to_parent(id)
+            .map_err(orientation_err)?
+            .into();
+        Ok(CompiledRotation::Constant(if dcm.to == id {
+            dcm.rot_mat
+        } else {
+            dcm.rot_mat.transpose()
+        }))
+    }
+}
+
+/// Decodes the segments of this ID over the provided span, switching segments wherever the selection function, which
+/// implements the load precedence of the Almanac, selects a different segment.
+///
+/// The selected segment may only change where a segment of this ID starts or ends, so the selection is evaluated at the
+/// start of the span and at each of these epochs within the span.
+fn compile_by_precedence<R: NAIFSummaryRecord>(
+    dafs: &[Option<DAF<R>>],
+    id: NaifId,
+    start: Epoch,
+    end: Epoch,
+    summary_at_epoch: impl Fn(Epoch) -> AlmanacResult<(R, usize, usize)>,
+    daf_err: impl Fn(DAFError) -> AlmanacError,
+) -> AlmanacResult<Vec<CompiledSegment>> {
+    let mut epochs = vec![start];
+    for daf in dafs.iter().flatten() {
+        for summary in daf.data_summaries().map_err(&daf_err)?.iter() {
+            if summary.id() != id || summary.is_empty() {
+                continue;
+            }
+            for epoch in [
+                summary.start_epoch(),
+                summary.end_epoch() + Unit::Nanosecond * 1,
+            ] {
+                if epoch > start && epoch <= end {
+                    epochs.push(epoch);
+                }
+            }
+        }
+    }
+    epochs.sort();
+    epochs.dedup();
+
+    let mut segments: Vec<CompiledSegment> = Vec::new();
+    let mut selected = None;
+    for (eno, epoch) in epochs.iter().enumerate() {
+        let (summary, daf_no, idx) = summary_at_epoch(*epoch)?;
+        let until_et_s = epochs.get(eno + 1).unwrap_or(&end).to_et_seconds();
+
+        if selected == Some((daf_no, idx)) {
+            // Same segment as before this epoch, so extend its interval.
+            if let Some(segment) = segments.last_mut() {
+                segment.end_et_s = until_et_s;
+            }
+            continue;
+        }
+        selected = Some((daf_no, idx));
+
+        let daf = dafs
+            .get(daf_no)
+            .and_then(|daf| daf.as_ref())
+            .ok_or_else(|| {
+                daf_err(DAFError::InvalidIndex {
+                    idx: daf_no,
+                    kind: R::NAME,
+                })
+            })?;
+
+        segments.push(
+            compile_segment(daf, idx, &summary, epoch.to_et_seconds(), until_et_s)
+                .map_err(&daf_err)?,
+        );
+    }
+
+    Ok(segments)
+}
+
+/// Decodes the Chebyshev records of the n-th segment of this DAF, which has precedence over the provided interval.
+fn compile_segment<R: NAIFSummaryRecord>(
+    daf: &DAF<R>,
+    idx: usize,
+    summary: &R,
+    start_et_s: f64,
+    end_et_s: f64,
+) -> Result<CompiledSegment, DAFError> {
+    let segment = daf.nth_segment(idx)?;
+
+    match segment.data_type {
+        DafDataType::Type2ChebyshevTriplet | DafDataType::Type3ChebyshevSextuplet => {}
+        dtype => {
+            return Err(DAFError::UnsupportedDatatype {
+                dtype,
+                kind: "compiled queries",
+            })
+        }
+    }
+
+    let mut init_et_s = summary.start_epoch_et_s();
+    let mut interval_s = summary.end_epoch_et_s() - summary.start_epoch_et_s();
+    let mut records = Vec::new();
+    for (rno, record) in segment.records()?.into_iter().enumerate() {
+        if let SegmentRecord::Chebyshev {
+            midpoint,
+            radius,
+            coefficients,
+        } = record
+        {
+            if rno == 0 {
+                interval_s = 2.0 * radius.to_seconds();
+                init_et_s = midpoint.to_et_seconds() - radius.to_seconds();
+            }
+            records.push(coefficients);
+        }
+    }
+
+    if records.is_empty() || interval_s <= 0.0 {
+        return Err(DAFError::EmptySummary { kind: R::NAME, idx });
+    }
+
+    Ok(CompiledSegment {
+        start_et_s,
+        end_et_s,
+        init_et_s,
+        interval_s,
+        records,
+    })
+}
+
+#[cfg(test)]
+mod ut_compiled {
+    use crate::constants::frames::{
+        EARTH_ECLIPJ2000, EARTH_ITRF93, EARTH_J2000, IAU_EARTH_FRAME, MOON_J2000, SUN_J2000,
+    };
+    use crate::naif::daf::datatypes::Type2ChebyshevSet;
+    use crate::naif::daf::NAIFDataSet;
+    use crate::prelude::{Almanac, Epoch, Frame, SPK};
+    use hifitime::{TimeSeries, Unit};
+
+    fn assert_matches_transform(
+        almanac: &Almanac,
+        target: Frame,
+        observer: Frame,
+        frame: Frame,
+        start: Epoch,
+        end: Epoch,
+    ) {
+        let query = almanac
+            .compile(target, observer, frame, start, end)
+            .unwrap();
+        assert!(query.num_segments() > 0);
+
+        let observer = observer.with_orient(frame.orientation_id);
+        for epoch in TimeSeries::inclusive(start, end, Unit::Hour * 7.3) {
+            let expected = almanac.transform(target, observer, epoch, None).unwrap();
+            let state = query.evaluate(epoch).unwrap();
+            assert_eq!(state.frame, expected.frame);
+            assert!(
+                (state.radius_km - expected.radius_km).norm() < 1e-6,
+                "{target} wrt {observer} @ {epoch}"
+            );
+            assert!(
+                (state.velocity_km_s - expected.velocity_km_s).norm() < 1e-9,
+                "{target} wrt {observer} @ {epoch}"
+            );
+        }
+
+        assert!(query.evaluate(end + Unit::Second * 1).is_none());
+        assert!(query.evaluate(start - Unit::Second * 1).is_none());
+    }
+
+    #[test]
+    fn compiled_matches_transform() {
+        let almanac = Almanac::default()
+            .load("../data/de440s.bsp")
+            .unwrap()
+            .load("../data/pck08.pca")
+            .unwrap()
+            .load("../data/earth_latest_high_prec.bpc")
+            .unwrap();
+        let start = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+        let end = start + Unit::Day * 60;
+
+        for (target, observer, frame) in [
+            (MOON_J2000, EARTH_J2000, EARTH_J2000),
+            (EARTH_J2000, SUN_J2000, SUN_J2000),
+            (MOON_J2000, EARTH_J2000, EARTH_ECLIPJ2000),
+            (MOON_J2000, EARTH_J2000, IAU_EARTH_FRAME),
+            (SUN_J2000, EARTH_J2000, EARTH_ITRF93),
+        ] {
+            assert_matches_transform(&almanac, target, observer, frame, start, end);
+        }
+
+        assert!(almanac
+            .compile(MOON_J2000, EARTH_J2000, EARTH_J2000, end, start)
+            .is_err());
+    }
+
+    #[test]
+    fn compiled_follows_load_precedence() {
+        let start = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+        let mid = start + Unit::Day * 10;
+        let end = start + Unit::Day * 20;
+
+        // Build a DE421 kernel whose Moon segment only starts within the span.
+        let de421 = SPK::load("../data/de421.bsp").unwrap();
+        let idx = de421
+            .data_summaries()
+            .unwrap()
+            .iter()
+            .position(|summary| summary.target_id == 301)
+            .unwrap();
+        let summary = de421.data_summaries().unwrap()[idx];
+        let moon = de421
+            .nth_data::<Type2ChebyshevSet>(idx)
+            .unwrap()
+            .truncate(&summary, Some(mid), None)
+            .unwrap();
+        let mut patched = de421.to_mutable();
+        patched
+            .set_nth_data(idx, moon, mid, summary.end_epoch())
+            .unwrap();
+        let path = "../target/compiled-moon-de421.bsp";
+        patched.persist(path).unwrap();
+
+        let de440s = Almanac::default().load("../data/de440s.bsp").unwrap();
+        // The patched kernel is loaded last, so its Moon segment has precedence from the middle of the span onward.
+        let almanac = de440s.load(path).unwrap();
+
+        assert_matches_transform(&almanac, MOON_J2000, EARTH_J2000, EARTH_J2000, start, end);
+
+        // Ensure that the two ephemerides differ, otherwise the above does not check anything.
+        let epoch = mid + Unit::Day * 5;
+        let orig = de440s
+            .translate(MOON_J2000, EARTH_J2000, epoch, None)
+            .unwrap();
+        let patched = almanac
+            .translate(MOON_J2000, EARTH_J2000, epoch, None)
+            .unwrap();
+        assert!((orig.radius_km - patched.radius_km).norm() > 1e-6);
+    }
+}
@@ -0,0 +1,330 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::{Duration, Epoch};
+use log::warn;
+use snafu::ResultExt;
+
+use crate::ephemerides::{EphemInterpolationSnafu, EphemerisError};
+use crate::math::interpolation::hermite_eval;
+use crate::math::Vector3;
+use crate::naif::daf::{DAFError, NAIFSummaryRecord};
+use crate::naif::spk::summary::SPKSummaryRecord;
+use crate::prelude::Frame;
+use crate::NaifId;
+
+use super::Almanac;
+
+/// Jump of the ephemeris of an object at the boundary between two of its SPK segments, cf. [Almanac::continuity_report].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SegmentBoundary {
+    /// Epoch of the boundary, i.e. the start of the second segment, or the end of the first segment if there is a gap between them
+    pub epoch: Epoch,
+    /// Duration without data between the end of the first segment and the start of the second one, zero if they are contiguous
+    pub gap: Duration,
+    /// Norm of the difference of the positions of both segments at the boundary, in kilometers
+    pub position_jump_km: f64,
+    /// Norm of the difference of the velocities of both segments at the boundary, in kilometers per second
+    pub velocity_jump_km_s: f64,
+}
+
+impl fmt::Display for SegmentBoundary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: Δr = {:.6} km, Δv = {:.9} km/s",
+            self.epoch, self.position_jump_km, self.velocity_jump_km_s
+        )?;
+        if self.gap > Duration::ZERO {
+            write!(f, " after a gap of {}", self.gap)?;
+        }
+        Ok(())
+    }
+}
+
+/// Continuity of the ephemeris of an object across the boundaries of its SPK segments, e.g. between the segments before and after
+/// a maneuver.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ContinuityReport {
+    /// NAIF ID of the object
+    pub id: NaifId,
+    /// Boundaries between the consecutive segments of the object, sorted by epoch
+    pub boundaries: Vec<SegmentBoundary>,
+}
+
+impl ContinuityReport {
+    /// Returns the largest position jump at the boundaries, in kilometers
+    pub fn max_position_jump_km(&self) -> f64 {
+        self.boundaries
+            .iter()
+            .map(|boundary| boundary.position_jump_km)
+            .fold(0.0, f64::max)
+    }
+
+    /// Returns the largest velocity jump at the boundaries, in kilometers per second
+    pub fn max_velocity_jump_km_s(&self) -> f64 {
+        self.boundaries
+            .iter()
+            .map(|boundary| boundary.velocity_jump_km_s)
+            .fold(0.0, f64::max)
+    }
+
+    /// Returns true if there is no gap and all of the jumps are within the tolerances.
+    pub fn is_continuous(&self, position_tol_km: f64, velocity_tol_km_s: f64) -> bool {
+        self.boundaries.iter().all(|boundary| {
+            boundary.gap == Duration::ZERO
+                && boundary.position_jump_km <= position_tol_km
+                && boundary.velocity_jump_km_s <= velocity_tol_km_s
+        })
+    }
+}
+
+impl fmt::Display for ContinuityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} segment boundaries of {}: max Δr = {:.6} km, max Δv = {:.9} km/s",
+            self.boundaries.len(),
+            self.id,
+            self.max_position_jump_km(),
+            self.max_velocity_jump_km_s()
+        )
+    }
+}
+
+impl Almanac {
+    /// Returns the jumps of the position and velocity of the object at the boundaries between its consecutive SPK segments,
+    /// across all of the loaded SPK files.
+    ///
+    /// Both segments are evaluated at the start of the second one if they overlap or are contiguous, or each at its own
+    /// bound if there is a gap. Consecutive segments relative to another center or frame are not compared.
+    pub fn continuity_report(&self, id: NaifId) -> Result<ContinuityReport, EphemerisError> {
+        let mut segments: Vec<(&SPKSummaryRecord, usize, usize)> = Vec::new();
+        for (spk_no, maybe_spk) in self.spk_data.iter().take(self.num_loaded_spk()).enumerate() {
+            let spk = maybe_spk.as_ref().ok_or(EphemerisError::Unreachable)?;
+            if let Ok(summaries) = spk.data_summaries() {
+                for (idx_in_spk, summary) in summaries.iter().enumerate() {
+                    if summary.id() == id {
+                        segments.push((summary, spk_no, idx_in_spk));
+                    }
+                }
+            }
+        }
+
+        if segments.is_empty() {
+            return Err(EphemerisError::SPK {
+                action: "building continuity report",
+                source: DAFError::SummaryIdError { kind: "SPK", id },
+            });
+        }
+
+        segments.sort_by_key(|(summary, _, _)| summary.start_epoch());
+
+        let mut boundaries = Vec::new();
+        for pair in segments.windows(2) {
+            let (first, first_spk, first_idx) = pair[0];
+            let (second, second_spk, second_idx) = pair[1];
+
+            if first.center_id != second.center_id || first.frame_id != second.frame_id {
+                warn!(
+                    "segments of {id} at {} are not relative to the same center and frame, skipping",
+                    second.start_epoch()
+                );
+                continue;
+            }
+
+            let (first_epoch, second_epoch) = if second.start_epoch() <= first.end_epoch() {
+                (second.start_epoch(), second.start_epoch())
+            } else {
+                (first.end_epoch(), second.start_epoch())
+            };
+
+            let (first_pos_km, first_vel_km_s) =
+                self.evaluate_spk_segment(first, first_spk, first_idx, first_epoch)?;
+            let (second_pos_km, second_vel_km_s) =
+                self.evaluate_spk_segment(second, second_spk, second_idx, second_epoch)?;
+
+            boundaries.push(SegmentBoundary {
+                epoch: first_epoch,
+                gap: second_epoch - first_epoch,
+                position_jump_km: (second_pos_km - first_pos_km).norm(),
+                velocity_jump_km_s: (second_vel_km_s - first_vel_km_s).norm(),
+            });
+        }
+
+        Ok(ContinuityReport { id, boundaries })
+    }
+
+    /// Returns the translation of the source to its parent smoothed across the boundary between two of its segments, if the epoch
+    /// is within the half width of such a boundary, cf. `with_boundary_smoothing`.
+    pub(crate) fn smoothed_translation_parts(
+        &self,
+        source: Frame,
+        epoch: Epoch,
+        half_width: Duration,
+    ) -> Result<Option<(Vector3, Vector3, Frame)>, EphemerisError> {
+        let id = source.ephemeris_id;
+        let (before, before_spk, before_idx) =
+            match self.spk_summary_at_epoch(id, epoch - half_width) {
+                Ok(found) => found,
+                Err(_) => return Ok(None),
+            };
+        let (after, after_spk, after_idx) = match self.spk_summary_at_epoch(id, epoch + half_width)
+        {
+            Ok(found) => found,
+            Err(_) => return Ok(None),
+        };
+
+        if (before_spk, before_idx) == (after_spk, after_idx)
+            || before.center_id != after.center_id
+            || before.frame_id != after.frame_id
+        {
+            return Ok(None);
+        }
+
+        // The segment used after the boundary starts within the window, or the one used before it ends within the window.
+        let boundary = if after.start_epoch() > epoch - half_width {
+            after.start_epoch()
+        } else {
+            before.end_epoch()
+        };
+        if (epoch - boundary).abs() >= half_width {
+            return Ok(None);
+        }
+
+        let (start, end) = (boundary - half_width, boundary + half_width);
+        let (start_pos_km, start_vel_km_s, frame) = self.spk_translation_parts(source, start)?;
+        let (end_pos_km, end_vel_km_s, _) = self.spk_translation_parts(source, end)?;
+
+        // Cubic Hermite interpolation of each component, in seconds since the start of the window
+        let xs = [0.0, (end - start).to_seconds()];
+        let x_eval = (epoch - start).to_seconds();
+        let mut pos_km = Vector3::zeros();
+        let mut vel_km_s = Vector3::zeros();
+        for i in 0..3 {
+            let (pos, vel) = hermite_eval(
+                &xs,
+                &[start_pos_km[i], end_pos_km[i]],
+                &[start_vel_km_s[i], end_vel_km_s[i]],
+                x_eval,
+            )
+            .context(EphemInterpolationSnafu)?;
+            pos_km[i] = pos;
+            vel_km_s[i] = vel;
+        }
+
+        Ok(Some((pos_km, vel_km_s, frame)))
+    }
+}
+
+#[cfg(test)]
+mod ut_continuity {
+    use crate::naif::daf::datatypes::HermiteSetType13;
+    use crate::naif::daf::{DafDataType, NAIFDataSet};
+    use crate::naif::spk::summary::SPKSummaryRecord;
+    use crate::naif::{SPKSegment, SPK};
+    use crate::prelude::{Almanac, Epoch, Frame};
+    use hifitime::Unit;
+
+    /// Object moving at 1 km/s along X, whose second segment is 0.5 km ahead of the first one
+    fn jumping_almanac() -> Almanac {
+        let segments = [(0.0, 0.0), (10.0, 0.5)].map(|(start_et_s, offset_km)| {
+            let state_data = [
+                start_et_s + offset_km,
+                0.0,
+                0.0,
+                1.0,
+                0.0,
+                0.0,
+                start_et_s + offset_km + 10.0,
+                0.0,
+                0.0,
+                1.0,
+                0.0,
+                0.0,
+            ];
+            let epoch_data = [start_et_s, start_et_s + 10.0];
+            SPKSegment {
+                name: format!("from {start_et_s}"),
+                summary: SPKSummaryRecord {
+                    start_epoch_et_s: start_et_s,
+                    end_epoch_et_s: start_et_s + 10.0,
+                    target_id: -10,
+                    center_id: 399,
+                    frame_id: 1,
+                    data_type_i: DafDataType::Type13HermiteUnequalStep as i32,
+                    ..Default::default()
+                },
+                data: HermiteSetType13 {
+                    samples: 2,
+                    num_records: 2,
+                    state_data: &state_data,
+                    epoch_data: &epoch_data,
+                    epoch_registry: &[],
+                }
+                .to_f64_daf_vec()
+                .unwrap(),
+            }
+        });
+
+        Almanac::from_spk(SPK::from_segments("ANISE TEST", &segments).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn continuity_report() {
+        let almanac = jumping_almanac();
+
+        let report = almanac.continuity_report(-10).unwrap();
+        assert_eq!(report.boundaries.len(), 1);
+        let boundary = report.boundaries[0];
+        assert_eq!(boundary.epoch, Epoch::from_et_seconds(10.0));
+        assert_eq!(boundary.gap, Unit::Second * 0);
+        assert!((boundary.position_jump_km - 0.5).abs() < 1e-12);
+        assert!(boundary.velocity_jump_km_s < 1e-12);
+        assert!(!report.is_continuous(0.1, 1e-9));
+        assert!(report.is_continuous(1.0, 1e-9));
+
+        assert!(almanac.continuity_report(-20).is_err());
+    }
+
+    #[test]
+    fn boundary_smoothing() {
+        let object = Frame::new(-10, 1);
+        let almanac = jumping_almanac();
+        let smoothed = almanac.with_boundary_smoothing(Some(Unit::Second * 2));
+
+        let epoch = Epoch::from_et_seconds(10.0);
+        // Without smoothing, the position jumps right after the boundary.
+        let state = almanac.translate_to_parent(object, epoch).unwrap();
+        assert!((state.radius_km.x - 10.0).abs() < 1e-12);
+        let state = almanac
+            .translate_to_parent(object, epoch + Unit::Millisecond)
+            .unwrap();
+        assert!((state.radius_km.x - 10.501).abs() < 1e-9);
+
+        // With smoothing, the cubic Hermite interpolation between 8 s and 12 s is used.
+        let state = smoothed.translate_to_parent(object, epoch).unwrap();
+        assert!((state.radius_km.x - 10.25).abs() < 1e-12);
+        assert!((state.velocity_km_s.x - 1.1875).abs() < 1e-12);
+
+        // The smoothed position is continuous at the edges of the window, and unchanged outside of it.
+        for et_s in [7.0, 8.0, 8.0 - 1e-6, 12.0, 12.0 + 1e-6, 15.0] {
+            let epoch = Epoch::from_et_seconds(et_s);
+            let expected = almanac.translate_to_parent(object, epoch).unwrap();
+            let state = smoothed.translate_to_parent(object, epoch).unwrap();
+            assert!(
+                (state.radius_km - expected.radius_km).norm() < 1e-6,
+                "{et_s}"
+            );
+        }
+    }
+}
@@ -14,9 +14,11 @@ use crate::{
     astro::{Aberration, Occultation},
     constants::{frames::SUN_J2000, orientations::J2000},
     ephemerides::EphemerisPhysicsSnafu,
-    errors::{AlmanacError, EphemerisSnafu, OrientationSnafu},
+    errors::{AlmanacError, EphemerisSnafu, OrientationSnafu, PhysicsError},
     frames::Frame,
+    math::{Matrix3, Vector3},
     prelude::Orbit,
+    structure::planetocentric::ellipsoid::Ellipsoid,
 };
 
 use super::Almanac;
@@ -27,6 +29,9 @@ use snafu::ResultExt;
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
 
+/// Number of samples along each axis of the grid of directions covering the back object in [Almanac::occultation_triaxial].
+const TRIAXIAL_OCCULTATION_SAMPLES: usize = 101;
+
 #[cfg_attr(feature = "python", pymethods)]
 impl Almanac {
     /// Computes whether the line of sight between an observer and an observed Cartesian state is obstructed by the obstructing body.
@@ -288,6 +293,95 @@ impl Almanac {
         }
     }
 
+    /// Computes the occultation percentage of the `back_frame` object by the `front_frame` object as seen from the
+    /// observer like [Almanac::occultation], but modeling both objects as triaxial ellipsoids oriented per their frames
+    /// at the epoch of the observer, instead of spheres of their mean equatorial radii.
+    ///
+    /// This matters for irregular bodies, e.g. Phobos whose radii are about 13.0, 11.4, and 9.1 km: provide their body
+    /// fixed frames (e.g. `Frame::new(401, 401)` for IAU Phobos) such that their shapes are oriented correctly.
+    ///
+    /// # Algorithm
+    /// The directions from the observer covering the back object are sampled on a regular grid of 101 by 101 points of
+    /// its tangent plane, each weighted by its solid angle. The percentage is the weighted fraction of the directions
+    /// intersecting the back ellipsoid which first intersect the front ellipsoid, so it is accurate to about one
+    /// percent in penumbra, and exact when the back object is fully visible or fully hidden.
+    ///
+    /// :type back_frame: Frame
+    /// :type front_frame: Frame
+    /// :type observer: Orbit
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: Occultation
+    pub fn occultation_triaxial(
+        &self,
+        mut back_frame: Frame,
+        mut front_frame: Frame,
+        observer: Orbit,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<Occultation> {
+        if back_frame.shape.is_none() {
+            back_frame =
+                self.frame_from_uid(back_frame)
+                    .map_err(|e| AlmanacError::GenericError {
+                        err: format!("{e} when fetching {back_frame:e} frame data"),
+                    })?;
+        }
+
+        if front_frame.shape.is_none() {
+            front_frame =
+                self.frame_from_uid(front_frame)
+                    .map_err(|e| AlmanacError::GenericError {
+                        err: format!("{e} when fetching {front_frame:e} frame data"),
+                    })?;
+        }
+
+        let shape_of = |frame: Frame| {
+            frame
+                .shape
+                .ok_or(PhysicsError::MissingFrameData {
+                    action: "computing triaxial occultation",
+                    data: "shape",
+                    frame: frame.into(),
+                })
+                .context(EphemerisPhysicsSnafu {
+                    action: "fetching shape of occultation object",
+                })
+                .context(EphemerisSnafu {
+                    action: "computing triaxial occultation",
+                })
+        };
+        let back_shape = shape_of(back_frame)?;
+        let front_shape = shape_of(front_frame)?;
+
+        let epoch = observer.epoch;
+
+        // Position of the observer with respect to each object, in the axes of that object
+        let obs_back_km = self.transform_to(observer, back_frame, ab_corr)?.radius_km;
+        let obs_front_km = self.transform_to(observer, front_frame, ab_corr)?.radius_km;
+
+        let back_to_front = self
+            .rotate(back_frame, front_frame, epoch)
+            .context(OrientationSnafu {
+                action: "computing triaxial occultation",
+            })?
+            .rot_mat;
+
+        let percentage = 100.0
+            * triaxial_hidden_fraction(
+                obs_back_km,
+                back_shape,
+                obs_front_km,
+                front_shape,
+                back_to_front,
+            );
+
+        Ok(Occultation {
+            epoch,
+            percentage,
+            back_frame,
+            front_frame,
+        })
+    }
+
     /// Computes the solar eclipsing of the observer due to the eclipsing_frame.
     ///
     /// This function calls `occultation` where the back object is the Sun in the J2000 frame, and the front object
@@ -312,6 +406,113 @@ fn circ_seg_area(r: f64, d: f64) -> f64 {
     r.powi(2) * (d / r).acos() - d * (r.powi(2) - d.powi(2)).sqrt()
 }
 
+/// Returns the fraction of the apparent area of the back ellipsoid which is hidden by the front ellipsoid, given the
+/// position of the observer with respect to the center of each ellipsoid in its axes, and the rotation from the axes
+/// of the back ellipsoid to those of the front ellipsoid.
+fn triaxial_hidden_fraction(
+    obs_back_km: Vector3,
+    back_shape: Ellipsoid,
+    obs_front_km: Vector3,
+    front_shape: Ellipsoid,
+    back_to_front: Matrix3,
+) -> f64 {
+    // Direction to the center of the back object, in the axes of the front object
+    let to_back_km = -(back_to_front * obs_back_km);
+    let distance_km = to_back_km.norm();
+    if distance_km < f64::EPSILON {
+        return 0.0;
+    }
+    let axis = to_back_km / distance_km;
+
+    // Cone covering the back object, limited to a half angle of 85 degrees if the observer is very close to it.
+    let max_radius_km = back_shape
+        .semi_major_equatorial_radius_km
+        .max(back_shape.semi_minor_equatorial_radius_km)
+        .max(back_shape.polar_radius_km);
+    let half_angle = if max_radius_km < distance_km {
+        (max_radius_km / distance_km)
+            .asin()
+            .min(85.0_f64.to_radians())
+    } else {
+        85.0_f64.to_radians()
+    };
+    let tan_half = half_angle.tan();
+
+    // Orthonormal basis of the tangent plane
+    let helper = if axis.x.abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    let e1 = axis.cross(&helper).normalize();
+    let e2 = axis.cross(&e1);
+
+    let front_to_back = back_to_front.transpose();
+    let n = TRIAXIAL_OCCULTATION_SAMPLES;
+    let (mut back_area, mut hidden_area) = (0.0, 0.0);
+
+    for i in 0..n {
+        let x = tan_half * (2.0 * (i as f64 + 0.5) / n as f64 - 1.0);
+        for j in 0..n {
+            let y = tan_half * (2.0 * (j as f64 + 0.5) / n as f64 - 1.0);
+            let direction = axis + x * e1 + y * e2;
+
+            if let Some(t_back) =
+                ray_ellipsoid_intersection(obs_back_km, front_to_back * direction, back_shape)
+            {
+                // Solid angle of this sample of the tangent plane
+                let weight = (1.0 + x * x + y * y).powf(-1.5);
+                back_area += weight;
+
+                if ray_ellipsoid_intersection(obs_front_km, direction, front_shape)
+                    .is_some_and(|t_front| t_front < t_back)
+                {
+                    hidden_area += weight;
+                }
+            }
+        }
+    }
+
+    if back_area > 0.0 {
+        hidden_area / back_area
+    } else {
+        0.0
+    }
+}
+
+/// Returns the smallest non-negative parameter `t` such that `origin + t * direction` is on or in the ellipsoid, whose
+/// semi major equatorial radius is along the X axis and polar radius along the Z axis, or None if the ray misses it.
+fn ray_ellipsoid_intersection(
+    origin: Vector3,
+    direction: Vector3,
+    shape: Ellipsoid,
+) -> Option<f64> {
+    let scale = Vector3::new(
+        1.0 / shape.semi_major_equatorial_radius_km,
+        1.0 / shape.semi_minor_equatorial_radius_km,
+        1.0 / shape.polar_radius_km,
+    );
+    let origin = origin.component_mul(&scale);
+    let direction = direction.component_mul(&scale);
+
+    let a = direction.dot(&direction);
+    let b = 2.0 * origin.dot(&direction);
+    let c = origin.dot(&origin) - 1.0;
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 || a < f64::EPSILON {
+        return None;
+    }
+
+    let t_far = (-b + discriminant.sqrt()) / (2.0 * a);
+    if t_far < 0.0 {
+        // The ellipsoid is behind the origin
+        None
+    } else {
+        Some(((-b - discriminant.sqrt()) / (2.0 * a)).max(0.0))
+    }
+}
+
 #[cfg(test)]
 mod ut_los {
     use crate::constants::frames::{EARTH_J2000, MOON_J2000};
@@ -470,4 +671,74 @@ mod ut_los {
             Ok(false)
         );
     }
+
+    #[test]
+    fn triaxial_hidden_fraction_orientation() {
+        use super::{triaxial_hidden_fraction, Ellipsoid, Matrix3, Vector3};
+
+        let front = Ellipsoid {
+            semi_major_equatorial_radius_km: 2.0,
+            semi_minor_equatorial_radius_km: 1.0,
+            polar_radius_km: 1.0,
+        };
+        let back = Ellipsoid::from_sphere(20.0);
+
+        // The back sphere is 1000 km away and the front ellipsoid 100 km away, so their apparent areas are in the ratio
+        // of the cross section of the ellipsoid seen along that axis to 4 km^2.
+        for (axis, expected) in [(Vector3::z(), 0.5), (Vector3::x(), 0.25)] {
+            let fraction = triaxial_hidden_fraction(
+                -1000.0 * axis,
+                back,
+                -100.0 * axis,
+                front,
+                Matrix3::identity(),
+            );
+            assert!(
+                (fraction - expected).abs() < 0.01,
+                "looking along {axis}: {fraction} != {expected}"
+            );
+        }
+
+        // Hidden behind the back object
+        assert_eq!(
+            triaxial_hidden_fraction(
+                -1000.0 * Vector3::z(),
+                back,
+                -2000.0 * Vector3::z(),
+                front,
+                Matrix3::identity()
+            ),
+            0.0
+        );
+    }
+
+    #[rstest]
+    fn triaxial_earth_eclipse(almanac: Almanac) {
+        use crate::constants::frames::{IAU_EARTH_FRAME, SUN_J2000};
+
+        let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+        let epoch = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+
+        // Directly behind and in front of the Earth as seen from the Sun
+        let sun_dir = almanac
+            .translate(SUN_J2000, EARTH_J2000, epoch, None)
+            .unwrap()
+            .radius_km
+            .normalize();
+
+        for (sign, expected) in [(-1.0, 100.0), (1.0, 0.0)] {
+            let pos = sign * 7000.0 * sun_dir;
+            let observer = Orbit::new(pos.x, pos.y, pos.z, 0.0, 0.0, 0.0, epoch, eme2k);
+
+            let sphere = almanac
+                .occultation(SUN_J2000, IAU_EARTH_FRAME, observer, None)
+                .unwrap();
+            let triaxial = almanac
+                .occultation_triaxial(SUN_J2000, IAU_EARTH_FRAME, observer, None)
+                .unwrap();
+
+            assert_eq!(sphere.percentage, expected);
+            assert_eq!(triaxial.percentage, expected);
+        }
+    }
 }
@@ -0,0 +1,92 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::io::Cursor;
+
+use snafu::ResultExt;
+
+use crate::{
+    almanac::Almanac,
+    constants::celestial_objects::MAJOR_BODIES,
+    errors::{AlmanacResult, TLDataSetSnafu},
+    naif::kpl::{
+        parser::{convert_tpc_items, parse_bytes},
+        tpc::TPCItem,
+    },
+    structure::{dataset::DataSetError, PlanetaryDataSet},
+};
+
+/// Planetary constants kernel from which the constants of the major bodies are converted.
+const PCK00011_TPC: &str =
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/pck00011.tpc"));
+
+/// Gravity parameters kernel from which the GMs of the major bodies are converted.
+const GM_DE440_TPC: &str =
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/gm_de440.tpc"));
+
+impl Almanac {
+    /// Provides the planetary constants (gravitational parameter, shape, and IAU rotation) of the Sun, the planets, the Moon, and
+    /// the planetary system barycenters, cf. [MAJOR_BODIES](crate::constants::celestial_objects::MAJOR_BODIES).
+    ///
+    /// These are converted from the PCK11 and DE440 GM text kernels, which are embedded at compile time, so this requires no file
+    /// and is meant for quick calculations: load a PCA file for any other body.
+    pub fn embedded_constants() -> AlmanacResult<Self> {
+        Ok(Almanac {
+            planetary_data: major_bodies().context(TLDataSetSnafu {
+                action: "loading the embedded planetary constants",
+            })?,
+            ..Default::default()
+        })
+    }
+}
+
+/// Converts the embedded text kernels and keeps the major bodies only.
+fn major_bodies() -> Result<PlanetaryDataSet, DataSetError> {
+    let gravity_data = parse_bytes::<_, TPCItem>(&mut Cursor::new(GM_DE440_TPC), false)?;
+    let planetary_data = parse_bytes::<_, TPCItem>(&mut Cursor::new(PCK00011_TPC), false)?;
+    let all_bodies = convert_tpc_items(planetary_data, gravity_data)?;
+    let entries = all_bodies.lut.entries();
+
+    let mut major_bodies = PlanetaryDataSet::default();
+    for id in MAJOR_BODIES {
+        let name = entries
+            .values()
+            .find_map(|(entry_id, name)| (*entry_id == Some(id)).then_some(name.clone()))
+            .flatten();
+        major_bodies.push(all_bodies.get_by_id(id)?, Some(id), name.as_deref())?;
+    }
+    major_bodies.set_crc32();
+
+    Ok(major_bodies)
+}
+
+#[cfg(test)]
+mod ut_embed_constants {
+    use super::Almanac;
+    use crate::constants::celestial_objects::MAJOR_BODIES;
+    use crate::constants::frames::{EARTH_J2000, IAU_EARTH_FRAME, IAU_MOON_FRAME, MOON_J2000};
+    use hifitime::Epoch;
+
+    #[test]
+    fn test_embedded_constants() {
+        let almanac = Almanac::embedded_constants().unwrap();
+        for id in MAJOR_BODIES {
+            assert!(almanac.planetary_data.get_by_id(id).is_ok(), "{id}");
+        }
+
+        let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+        assert!((eme2k.mu_km3_s2().unwrap() - 398_600.435_507).abs() < 1e-3);
+        assert!(eme2k.mean_equatorial_radius_km().is_ok());
+
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+        almanac.rotate(IAU_EARTH_FRAME, EARTH_J2000, epoch).unwrap();
+        almanac.rotate(IAU_MOON_FRAME, MOON_J2000, epoch).unwrap();
+    }
+}
@@ -0,0 +1,97 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::{Duration, Epoch, TimeSeries};
+use snafu::ResultExt;
+
+use crate::errors::{AlmanacResult, OrientationSnafu};
+use crate::math::rotation::EulerSequence;
+use crate::math::Vector3;
+use crate::prelude::Frame;
+
+use super::Almanac;
+
+/// Euler angles (and their rates) of the rotation between two frames at an epoch.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct EulerAngles {
+    pub epoch: Epoch,
+    pub sequence: EulerSequence,
+    /// Angles in degrees, in the order of the rotations of the sequence
+    pub angles_deg: Vector3,
+    /// Rates of the angles in degrees per second, unset if the rotation has no time derivative or is in gimbal lock
+    pub rates_deg_s: Option<Vector3>,
+}
+
+impl Almanac {
+    /// Returns the Euler angles of the rotation from the `from_frame` to the `to_frame` in the provided sequence, sampled
+    /// every `step` from `start` until `end` (inclusive), e.g. to export an attitude profile to control design tools.
+    ///
+    /// The first and last angles are unwrapped: a multiple of 360 degrees is added to each sample such that it is within
+    /// 180 degrees of the previous one, so the series is continuous instead of jumping at +/- 180 degrees.
+    pub fn euler_angles_series(
+        &self,
+        from_frame: Frame,
+        to_frame: Frame,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+        sequence: EulerSequence,
+    ) -> AlmanacResult<Vec<EulerAngles>> {
+        let mut series: Vec<EulerAngles> = Vec::new();
+
+        for epoch in TimeSeries::inclusive(start, end, step) {
+            let dcm = self
+                .rotate(from_frame, to_frame, epoch)
+                .context(OrientationSnafu {
+                    action: "computing Euler angles series",
+                })?;
+
+            let angles_rad = dcm.euler_angles_rad(sequence);
+            let rates_deg_s = dcm
+                .rot_mat_dt
+                .and_then(|rot_mat_dt| sequence.rates_rad_s(angles_rad, &rot_mat_dt))
+                .map(|rates_rad_s| rates_rad_s.map(|rate| rate.to_degrees()));
+
+            let mut angles_deg = angles_rad.map(|angle| angle.to_degrees());
+            if let Some(prev) = series.last() {
+                for idx in [0, 2] {
+                    angles_deg[idx] = unwrap_deg(prev.angles_deg[idx], angles_deg[idx]);
+                }
+            }
+
+            series.push(EulerAngles {
+                epoch,
+                sequence,
+                angles_deg,
+                rates_deg_s,
+            });
+        }
+
+        Ok(series)
+    }
+}
+
+/// Returns the angle plus the multiple of 360 degrees which is closest to the previous angle.
+fn unwrap_deg(prev_deg: f64, angle_deg: f64) -> f64 {
+    angle_deg + 360.0 * ((prev_deg - angle_deg) / 360.0).round()
+}
+
+#[cfg(test)]
+mod ut_euler_angles {
+    use super::unwrap_deg;
+
+    #[test]
+    fn unwrap_angles() {
+        assert_eq!(unwrap_deg(179.0, -179.0), 181.0);
+        assert_eq!(unwrap_deg(-179.0, 179.0), -181.0);
+        assert_eq!(unwrap_deg(721.0, 2.0), 722.0);
+        assert_eq!(unwrap_deg(10.0, 20.0), 20.0);
+    }
+}
@@ -0,0 +1,375 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::{Duration, Epoch, Unit as TimeUnit};
+use snafu::ensure;
+
+use crate::astro::Aberration;
+use crate::errors::{AlmanacResult, GenericSnafu};
+use crate::prelude::Frame;
+
+use super::Almanac;
+
+/// Precision of the epochs of the events found by the helpers of the Almanac, in seconds.
+pub const EVENT_PRECISION_S: f64 = 1e-3;
+
+/// Direction in which an event function crosses zero.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CrossingDirection {
+    /// The event function goes from negative to positive, e.g. an ascending node
+    Rising,
+    /// The event function goes from positive to negative, e.g. a descending node
+    Falling,
+}
+
+/// An epoch at which an event function crosses zero.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct EventCrossing {
+    pub epoch: Epoch,
+    pub direction: CrossingDirection,
+}
+
+impl fmt::Display for EventCrossing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} @ {}", self.direction, self.epoch)
+    }
+}
+
+impl Almanac {
+    /// Finds all of the epochs between `start` and `end` where the provided event function crosses zero.
+    ///
+    /// The event function is sampled every `step`, and each change of sign between two samples is refined by bisection
+    /// until the crossing is known to within the provided precision.
+    ///
+    /// # Limitation
+    /// Two crossings within the same step cancel out and are not reported, so the step must be shorter than the
+    /// shortest time between two crossings.
+    pub fn find_crossings<F>(
+        &self,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+        precision: Duration,
+        event: F,
+    ) -> AlmanacResult<Vec<EventCrossing>>
+    where
+        F: Fn(&Self, Epoch) -> AlmanacResult<f64>,
+    {
+        self.find_crossings_with_rate(start, end, step, precision, |almanac, epoch| {
+            Ok((event(almanac, epoch)?, f64::NAN))
+        })
+    }
+
+    /// Finds all of the epochs between `start` and `end` where the provided event function crosses zero, given the
+    /// value of the event function and its time derivative (per second).
+    ///
+    /// The event function is sampled every `step`, and each change of sign between two samples is refined with Newton
+    /// steps from the derivative, which converge much faster than a bisection. The refinement falls back to a bisection
+    /// whenever a Newton step would leave the bracket of the crossing, e.g. if the derivative is not finite.
+    ///
+    /// # Limitation
+    /// Two crossings within the same step cancel out and are not reported, so the step must be shorter than the
+    /// shortest time between two crossings.
+    pub fn find_crossings_with_rate<F>(
+        &self,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+        precision: Duration,
+        event: F,
+    ) -> AlmanacResult<Vec<EventCrossing>>
+    where
+        F: Fn(&Self, Epoch) -> AlmanacResult<(f64, f64)>,
+    {
+        ensure!(
+            start < end && step > Duration::ZERO && precision > Duration::ZERO,
+            GenericSnafu {
+                err: "event search requires start < end and a positive step and precision"
+            }
+        );
+
+        let precision_s = precision.to_seconds();
+        let mut crossings = Vec::new();
+
+        let (mut prev_epoch, (mut prev_value, mut prev_rate)) = (start, event(self, start)?);
+        while prev_epoch < end {
+            let epoch = if prev_epoch + step > end {
+                end
+            } else {
+                prev_epoch + step
+            };
+            let (value, rate) = event(self, epoch)?;
+
+            let direction = if prev_value < 0.0 && value >= 0.0 {
+                Some(CrossingDirection::Rising)
+            } else if prev_value > 0.0 && value <= 0.0 {
+                Some(CrossingDirection::Falling)
+            } else {
+                None
+            };
+
+            if let Some(direction) = direction {
+                // Refine within the bracket, whose lower bound remains on the same side of zero as the previous sample.
+                let (mut lower, mut upper) = (prev_epoch, epoch);
+                let (mut guess, mut guess_value, mut guess_rate) =
+                    (prev_epoch, prev_value, prev_rate);
+                let mut bisect = false;
+                loop {
+                    let newton_s = -guess_value / guess_rate;
+                    let mut next = lower + (upper - lower) * 0.5;
+                    let mut converged = false;
+                    if !bisect && newton_s.is_finite() {
+                        let newton = guess + newton_s * TimeUnit::Second;
+                        if newton > lower && newton < upper {
+                            next = newton;
+                            converged = newton_s.abs() <= precision_s;
+                        }
+                    }
+
+                    let width = upper - lower;
+                    let (next_value, next_rate) = event(self, next)?;
+                    if next_value == 0.0 {
+                        (lower, upper) = (next, next);
+                    } else if (next_value < 0.0) == (prev_value < 0.0) {
+                        lower = next;
+                    } else {
+                        upper = next;
+                    }
+                    // Bisect next if the Newton step did not at least halve the bracket, which guarantees the convergence.
+                    bisect = upper - lower > width * 0.5;
+
+                    (guess, guess_value, guess_rate) = (next, next_value, next_rate);
+
+                    if converged || upper - lower <= precision {
+                        break;
+                    }
+                }
+
+                crossings.push(EventCrossing {
+                    epoch: if upper - lower <= precision {
+                        lower + (upper - lower) * 0.5
+                    } else {
+                        guess
+                    },
+                    direction,
+                });
+            }
+
+            (prev_epoch, prev_value, prev_rate) = (epoch, value, rate);
+        }
+
+        Ok(crossings)
+    }
+
+    /// Returns the elevation (in degrees) of the target above the equator plane of the body frame, e.g. the latitude of
+    /// a spacecraft, or the opening angle of the rings of Saturn as seen from the Earth or the Sun when the body frame is `IAU_SATURN`.
+    ///
+    /// The body frame must be a body fixed frame, like the IAU frames built from the pole orientations of the planetary constants.
+    pub fn equator_plane_elevation_deg(
+        &self,
+        target_frame: Frame,
+        body_frame: Frame,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<f64> {
+        let state = self.transform(target_frame, body_frame, epoch, ab_corr)?;
+        Ok((state.radius_km.z / state.rmag_km()).asin().to_degrees())
+    }
+
+    /// Finds the epochs where the target crosses the equator plane of the body frame between `start` and `end`.
+    ///
+    /// A crossing from south to north (an ascending node) is reported as [CrossingDirection::Rising].
+    /// The equator plane is searched every `step`, and each crossing is refined to [EVENT_PRECISION_S].
+    pub fn equator_crossings(
+        &self,
+        target_frame: Frame,
+        body_frame: Frame,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<Vec<EventCrossing>> {
+        self.find_crossings(
+            start,
+            end,
+            step,
+            EVENT_PRECISION_S * TimeUnit::Second,
+            |almanac, epoch| {
+                almanac.equator_plane_elevation_deg(target_frame, body_frame, epoch, ab_corr)
+            },
+        )
+    }
+
+    /// Returns the apsis event function of the target with respect to the observer, i.e. the dot product of the position
+    /// and velocity (in km^2/s), which is zero at the periapsis and apoapsis, and its time derivative.
+    ///
+    /// The derivative is computed assuming that the acceleration is that of a point mass using the gravitational parameter
+    /// of the observer frame, and is `NaN` if that gravitational parameter is not loaded in the Almanac.
+    pub fn apsis_event(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<(f64, f64)> {
+        let state = self.transform(target_frame, observer_frame, epoch, ab_corr)?;
+        let mu_km3_s2 = self
+            .frame_from_uid(observer_frame)
+            .ok()
+            .and_then(|frame| frame.mu_km3_s2().ok())
+            .unwrap_or(f64::NAN);
+
+        Ok((
+            state.radius_km.dot(&state.velocity_km_s),
+            state.vmag_km_s().powi(2) - mu_km3_s2 / state.rmag_km(),
+        ))
+    }
+
+    /// Returns the node event function of the target with respect to the observer, i.e. the height (in km) of the target
+    /// above the XY plane of the observer frame, which is zero at the ascending and descending nodes, and its time derivative.
+    pub fn node_event(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<(f64, f64)> {
+        let state = self.transform(target_frame, observer_frame, epoch, ab_corr)?;
+        Ok((state.radius_km.z, state.velocity_km_s.z))
+    }
+
+    /// Finds the periapsis ([CrossingDirection::Rising]) and apoapsis ([CrossingDirection::Falling]) epochs of the target
+    /// with respect to the observer between `start` and `end`, searched every `step` and refined to [EVENT_PRECISION_S].
+    pub fn apsis_crossings(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<Vec<EventCrossing>> {
+        self.find_crossings_with_rate(
+            start,
+            end,
+            step,
+            EVENT_PRECISION_S * TimeUnit::Second,
+            |almanac, epoch| almanac.apsis_event(target_frame, observer_frame, epoch, ab_corr),
+        )
+    }
+
+    /// Finds the ascending ([CrossingDirection::Rising]) and descending ([CrossingDirection::Falling]) node epochs of the
+    /// target with respect to the XY plane of the observer frame between `start` and `end`, searched every `step` and
+    /// refined to [EVENT_PRECISION_S].
+    ///
+    /// The orientation of the observer frame defines the reference plane, e.g. `EARTH_J2000` for the nodes on the
+    /// Earth mean equator, `EARTH_ECLIPJ2000` for the nodes on the ecliptic, or a body fixed frame.
+    pub fn node_crossings(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<Vec<EventCrossing>> {
+        self.find_crossings_with_rate(
+            start,
+            end,
+            step,
+            EVENT_PRECISION_S * TimeUnit::Second,
+            |almanac, epoch| almanac.node_event(target_frame, observer_frame, epoch, ab_corr),
+        )
+    }
+
+    /// Finds the epochs where the observer crosses the ring plane of a planet between `start` and `end`, e.g. the
+    /// Earth or the Sun (i.e. the equinoxes of the planet) crossing the ring plane of Saturn.
+    ///
+    /// The ring plane is the equator plane of the body fixed frame of the planet, as defined by its IAU pole orientation.
+    /// Use a light time correction to find the epochs where the observer _sees_ the rings edge on.
+    pub fn ring_plane_crossings(
+        &self,
+        observer_frame: Frame,
+        planet_frame: Frame,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<Vec<EventCrossing>> {
+        self.equator_crossings(observer_frame, planet_frame, start, end, step, ab_corr)
+    }
+}
+
+#[cfg(test)]
+mod ut_events {
+    use super::CrossingDirection;
+    use crate::almanac::Almanac;
+    use hifitime::{Epoch, TimeUnits};
+
+    #[test]
+    fn find_crossings_sine() {
+        let start = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+        let period_s = 3600.0;
+        let crossings = Almanac::default()
+            .find_crossings(
+                start,
+                start + 2.hours(),
+                5.minutes(),
+                1.microseconds(),
+                |_, epoch| {
+                    Ok((core::f64::consts::TAU * (epoch - start).to_seconds() / period_s).sin())
+                },
+            )
+            .unwrap();
+
+        // The crossing at the start is not reported because there is no sign change before it.
+        assert_eq!(crossings.len(), 3);
+        assert_eq!(crossings[0].direction, CrossingDirection::Falling);
+        assert_eq!(crossings[1].direction, CrossingDirection::Rising);
+        assert_eq!(crossings[2].direction, CrossingDirection::Falling);
+        for (crossing, expected_s) in crossings.iter().zip([1800.0, 3600.0, 5400.0]) {
+            assert!(((crossing.epoch - start).to_seconds() - expected_s).abs() < 1e-6);
+        }
+
+        assert!(Almanac::default()
+            .find_crossings(start, start, 5.minutes(), 1.seconds(), |_, _| Ok(0.0))
+            .is_err());
+    }
+
+    #[test]
+    fn find_crossings_with_rate_sine() {
+        let start = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+        let omega = core::f64::consts::TAU / 3600.0;
+        let evaluations = core::cell::Cell::new(0);
+
+        let crossings = Almanac::default()
+            .find_crossings_with_rate(
+                start + 1.seconds(),
+                start + 2.hours(),
+                5.minutes(),
+                1.nanoseconds(),
+                |_, epoch| {
+                    evaluations.set(evaluations.get() + 1);
+                    let t_s = (epoch - start).to_seconds();
+                    Ok(((omega * t_s).sin(), omega * (omega * t_s).cos()))
+                },
+            )
+            .unwrap();
+
+        assert_eq!(crossings.len(), 3);
+        for (crossing, expected_s) in crossings.iter().zip([1800.0, 3600.0, 5400.0]) {
+            assert!(((crossing.epoch - start).to_seconds() - expected_s).abs() < 1e-8);
+        }
+        // 25 samples and a handful of Newton steps per crossing, where a bisection would need about 40 steps per crossing
+        assert!(evaluations.get() < 60, "{} evaluations", evaluations.get());
+    }
+}
@@ -0,0 +1,258 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use arrow::{
+    array::{ArrayRef, Float64Array, StringArray},
+    record_batch::RecordBatch,
+};
+use hifitime::{Duration, Epoch, TimeSeries};
+use log::info;
+use parquet::{arrow::ArrowWriter, file::properties::WriterProperties};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::errors::{AlmanacError, AlmanacResult};
+use crate::prelude::{Aberration, Frame};
+
+use super::euler_angles::EulerAngles;
+use super::Almanac;
+
+/// Column names of the exported trajectory, identical to those of the ephemeris validation framework.
+pub const TRAJECTORY_COLUMNS: [&str; 9] = [
+    "source frame",
+    "destination frame",
+    "ET Epoch (s)",
+    "X",
+    "Y",
+    "Z",
+    "VX",
+    "VY",
+    "VZ",
+];
+
+/// File format of an exported trajectory.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[default]
+    Parquet,
+    Csv,
+}
+
+impl Almanac {
+    /// Exports the state of the target frame as seen from the observer frame, sampled every `step` from `start` until `end` (inclusive),
+    /// to a Parquet or CSV file. Returns the number of states written.
+    ///
+    /// The states are expressed in the orientation of the observer frame, exactly like [Almanac::transform].
+    /// The columns are those of [TRAJECTORY_COLUMNS]: the position components are in kilometers and the velocity components in kilometers per second.
+    #[allow(clippy::too_many_arguments)]
+    pub fn export_trajectory<P: AsRef<Path>>(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+        ab_corr: Option<Aberration>,
+        path: P,
+        format: ExportFormat,
+    ) -> AlmanacResult<usize> {
+        let path = path.as_ref();
+
+        let src_frame = format!("{target_frame:e}");
+        let dst_frame = format!("{observer_frame:e}");
+
+        let mut epochs_et_s = Vec::new();
+        let mut components: [Vec<f64>; 6] = Default::default();
+
+        for epoch in TimeSeries::inclusive(start, end, step) {
+            let state = self.transform(target_frame, observer_frame, epoch, ab_corr)?;
+
+            epochs_et_s.push(epoch.to_et_seconds());
+            for (i, val) in state.to_cartesian_pos_vel().iter().enumerate() {
+                components[i].push(*val);
+            }
+        }
+
+        if epochs_et_s.is_empty() {
+            return Err(AlmanacError::Export {
+                path: path.display().to_string(),
+                err: format!("no epochs between {start} and {end} with a step of {step}"),
+            });
+        }
+
+        match format {
+            ExportFormat::Parquet => {
+                write_parquet(path, &src_frame, &dst_frame, &epochs_et_s, &components)
+            }
+            ExportFormat::Csv => write_csv(path, &src_frame, &dst_frame, &epochs_et_s, &components),
+        }
+        .map_err(|err| AlmanacError::Export {
+            path: path.display().to_string(),
+            err,
+        })?;
+
+        info!(
+            "[export_trajectory] {} states of {src_frame} wrt {dst_frame} written to {}",
+            epochs_et_s.len(),
+            path.display()
+        );
+
+        Ok(epochs_et_s.len())
+    }
+}
+
+/// Column names of the exported Euler angles series.
+pub const EULER_ANGLES_COLUMNS: [&str; 8] = [
+    "sequence",
+    "ET Epoch (s)",
+    "angle 1 (deg)",
+    "angle 2 (deg)",
+    "angle 3 (deg)",
+    "rate 1 (deg/s)",
+    "rate 2 (deg/s)",
+    "rate 3 (deg/s)",
+];
+
+/// Exports the provided Euler angles series (cf. [Almanac::euler_angles_series]) to a Parquet file, where the rates are
+/// null if they are not defined. Returns the number of rows written.
+pub fn export_euler_angles<P: AsRef<Path>>(
+    series: &[EulerAngles],
+    path: P,
+) -> AlmanacResult<usize> {
+    let path = path.as_ref();
+
+    let mut columns: Vec<(&str, ArrayRef)> = vec![
+        (
+            EULER_ANGLES_COLUMNS[0],
+            Arc::new(StringArray::from(
+                series
+                    .iter()
+                    .map(|row| row.sequence.to_string())
+                    .collect::<Vec<String>>(),
+            )),
+        ),
+        (
+            EULER_ANGLES_COLUMNS[1],
+            Arc::new(Float64Array::from(
+                series
+                    .iter()
+                    .map(|row| row.epoch.to_et_seconds())
+                    .collect::<Vec<f64>>(),
+            )),
+        ),
+    ];
+
+    for idx in 0..3 {
+        columns.push((
+            EULER_ANGLES_COLUMNS[2 + idx],
+            Arc::new(Float64Array::from(
+                series
+                    .iter()
+                    .map(|row| row.angles_deg[idx])
+                    .collect::<Vec<f64>>(),
+            )),
+        ));
+    }
+
+    for idx in 0..3 {
+        columns.push((
+            EULER_ANGLES_COLUMNS[5 + idx],
+            Arc::new(Float64Array::from(
+                series
+                    .iter()
+                    .map(|row| row.rates_deg_s.map(|rates| rates[idx]))
+                    .collect::<Vec<Option<f64>>>(),
+            )),
+        ));
+    }
+
+    write_batch(path, columns).map_err(|err| AlmanacError::Export {
+        path: path.display().to_string(),
+        err,
+    })?;
+
+    info!(
+        "[export_euler_angles] {} rows written to {}",
+        series.len(),
+        path.display()
+    );
+
+    Ok(series.len())
+}
+
+fn write_parquet(
+    path: &Path,
+    src_frame: &str,
+    dst_frame: &str,
+    epochs_et_s: &[f64],
+    components: &[Vec<f64>; 6],
+) -> Result<(), String> {
+    let num_rows = epochs_et_s.len();
+
+    let mut columns: Vec<(&str, ArrayRef)> = vec![
+        (
+            TRAJECTORY_COLUMNS[0],
+            Arc::new(StringArray::from(vec![src_frame; num_rows])),
+        ),
+        (
+            TRAJECTORY_COLUMNS[1],
+            Arc::new(StringArray::from(vec![dst_frame; num_rows])),
+        ),
+        (
+            TRAJECTORY_COLUMNS[2],
+            Arc::new(Float64Array::from(epochs_et_s.to_vec())),
+        ),
+    ];
+
+    for (name, data) in TRAJECTORY_COLUMNS[3..].iter().zip(components.iter()) {
+        columns.push((*name, Arc::new(Float64Array::from(data.clone()))));
+    }
+
+    write_batch(path, columns)
+}
+
+fn write_batch(path: &Path, columns: Vec<(&str, ArrayRef)>) -> Result<(), String> {
+    let batch = RecordBatch::try_from_iter(columns).map_err(|e| e.to_string())?;
+
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    let props = WriterProperties::builder().build();
+    let mut writer =
+        ArrowWriter::try_new(file, batch.schema(), Some(props)).map_err(|e| e.to_string())?;
+
+    writer.write(&batch).map_err(|e| e.to_string())?;
+    writer.close().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+fn write_csv(
+    path: &Path,
+    src_frame: &str,
+    dst_frame: &str,
+    epochs_et_s: &[f64],
+    components: &[Vec<f64>; 6],
+) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    let mut writer = BufWriter::new(file);
+
+    writeln!(writer, "{}", TRAJECTORY_COLUMNS.join(",")).map_err(|e| e.to_string())?;
+
+    for (i, epoch_et_s) in epochs_et_s.iter().enumerate() {
+        write!(writer, "{src_frame},{dst_frame},{epoch_et_s}").map_err(|e| e.to_string())?;
+        for data in components {
+            write!(writer, ",{}", data[i]).map_err(|e| e.to_string())?;
+        }
+        writeln!(writer).map_err(|e| e.to_string())?;
+    }
+
+    writer.flush().map_err(|e| e.to_string())
+}
@@ -0,0 +1,138 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::{Duration, Epoch, Unit as TimeUnit};
+
+use crate::astro::Aberration;
+use crate::errors::{AlmanacError, AlmanacResult};
+use crate::math::cartesian::CartesianState;
+use crate::prelude::Frame;
+
+use super::Almanac;
+
+/// Time step used to estimate the acceleration at the bounds of the ephemeris data, in seconds.
+const ACCELERATION_STEP_S: f64 = 1.0;
+
+/// A state which may have been extrapolated beyond the bounds of the loaded ephemeris data.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ExtrapolatedState {
+    pub state: CartesianState,
+    /// Time between the epoch of the state and the closest epoch with ephemeris data: zero if the state was not
+    /// extrapolated, positive past the end of the data, and negative before its start.
+    pub extrapolation: Duration,
+}
+
+impl ExtrapolatedState {
+    /// Returns true if this state was extrapolated
+    pub fn is_extrapolated(&self) -> bool {
+        self.extrapolation != Duration::ZERO
+    }
+}
+
+impl fmt::Display for ExtrapolatedState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_extrapolated() {
+            write!(f, "{} (extrapolated by {})", self.state, self.extrapolation)
+        } else {
+            write!(f, "{}", self.state)
+        }
+    }
+}
+
+impl Almanac {
+    /// Returns the state of the target with respect to the observer like `transform`, but extrapolates it by at most
+    /// `max_extrapolation` beyond the bounds of the loaded ephemeris data of the target and observer.
+    ///
+    /// This is useful for real-time applications where the ephemeris kernels lag behind the current time by a few minutes.
+    ///
+    /// # Algorithm
+    /// The state is continued from the closest bound of the data with a second order Taylor expansion, where the
+    /// acceleration is estimated by finite differencing the velocity over one second at that bound. The extrapolation
+    /// happens in the observer frame, so the observer frame should be inertial.
+    ///
+    /// # Limitations
+    /// + Only the ephemeris data of the target and observer themselves are considered (not that of their centers).
+    /// + A state within the bounds of the data is never extrapolated, e.g. in a gap between two segments.
+    /// + The error of the extrapolation grows with the cube of the extrapolation time, so it should be kept short.
+    pub fn transform_extrapolated(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+        max_extrapolation: Duration,
+    ) -> AlmanacResult<ExtrapolatedState> {
+        let err = match self.transform(target_frame, observer_frame, epoch, ab_corr) {
+            Ok(state) => {
+                return Ok(ExtrapolatedState {
+                    state,
+                    extrapolation: Duration::ZERO,
+                })
+            }
+            Err(err) => err,
+        };
+
+        // Find the common domain of the data of the target and the observer.
+        let mut domain: Option<(Epoch, Epoch)> = None;
+        for id in [target_frame.ephemeris_id, observer_frame.ephemeris_id] {
+            if let Ok((start, end)) = self.spk_domain(id) {
+                domain = Some(match domain {
+                    Some((prev_start, prev_end)) => (
+                        if start > prev_start {
+                            start
+                        } else {
+                            prev_start
+                        },
+                        if end < prev_end { end } else { prev_end },
+                    ),
+                    None => (start, end),
+                });
+            }
+        }
+
+        let (anchor, fd_step) = match domain {
+            Some((_, end)) if epoch > end && epoch - end <= max_extrapolation => {
+                (end, -ACCELERATION_STEP_S * TimeUnit::Second)
+            }
+            Some((start, _)) if epoch < start && start - epoch <= max_extrapolation => {
+                (start, ACCELERATION_STEP_S * TimeUnit::Second)
+            }
+            Some((start, end)) if epoch < start || epoch > end => {
+                return Err(AlmanacError::GenericError {
+                    err: format!(
+                        "{epoch} is beyond the maximum extrapolation of {max_extrapolation} from the data between {start} and {end}"
+                    ),
+                })
+            }
+            // Within the domain (or no domain at all), so this is not an extrapolation problem.
+            _ => return Err(err),
+        };
+
+        let bound = self.transform(target_frame, observer_frame, anchor, ab_corr)?;
+        let inner = self.transform(target_frame, observer_frame, anchor + fd_step, ab_corr)?;
+        let accel_km_s2 = (inner.velocity_km_s - bound.velocity_km_s) / fd_step.to_seconds();
+
+        let extrapolation = epoch - anchor;
+        let dt_s = extrapolation.to_seconds();
+
+        let mut state = bound;
+        state.epoch = epoch;
+        state.radius_km =
+            bound.radius_km + bound.velocity_km_s * dt_s + accel_km_s2 * (0.5 * dt_s.powi(2));
+        state.velocity_km_s = bound.velocity_km_s + accel_km_s2 * dt_s;
+
+        Ok(ExtrapolatedState {
+            state,
+            extrapolation,
+        })
+    }
+}
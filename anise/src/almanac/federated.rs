@@ -0,0 +1,267 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::sync::OnceLock;
+
+use hifitime::Epoch;
+use snafu::ResultExt;
+
+use crate::astro::Aberration;
+use crate::ephemerides::EphemerisError;
+use crate::errors::{AlmanacResult, EphemerisSnafu, OrientationSnafu, TLDataSetSnafu};
+use crate::math::cartesian::CartesianState;
+use crate::math::rotation::DCM;
+use crate::orientations::OrientationError;
+use crate::prelude::Frame;
+use crate::NaifId;
+
+use super::{planetary::PlanetaryDataError, Almanac};
+
+/// An ordered list of Almanacs queried as a fallback chain, e.g. mission specific kernels first, then a base planetary set.
+///
+/// This is useful when teams maintain separate kernel bundles that should not be merged on disk.
+///
+/// Each query is tried against each Almanac in order, and the first successful result is returned. If all of them fail,
+/// e.g. because the target is only in the first Almanac and the observer only in the second one, the query is tried once
+/// more against the merge of all of the Almanacs (cf. [FederatedAlmanac::merged]). If that fails too, the error of the
+/// first Almanac is returned. The merge is only built once, on the first query which needs it.
+#[derive(Clone, Default)]
+pub struct FederatedAlmanac {
+    almanacs: Vec<Almanac>,
+    /// Merge of the Almanacs used by the queries, or None if they cannot be merged
+    merged_cache: OnceLock<Option<Almanac>>,
+}
+
+impl FederatedAlmanac {
+    /// Initializes a new federation from the provided Almanacs, in decreasing order of precedence.
+    pub fn new(almanacs: Vec<Almanac>) -> Self {
+        Self {
+            almanacs,
+            merged_cache: OnceLock::new(),
+        }
+    }
+
+    /// Returns a copy of this federation where the provided Almanac is queried after all of the others.
+    pub fn with_fallback(&self, almanac: Almanac) -> Self {
+        let mut almanacs = self.almanacs.clone();
+        almanacs.push(almanac);
+        Self::new(almanacs)
+    }
+
+    /// Returns the Almanacs of this federation, in decreasing order of precedence.
+    pub fn almanacs(&self) -> &[Almanac] {
+        &self.almanacs
+    }
+
+    /// Merges all of the Almanacs into one, where the kernels of the first Almanac have precedence over those of the
+    /// following ones. Likewise, the entries of the data sets, the constant overrides, and the spacecraft clocks of the
+    /// first Almanac replace those with the same ID (or name) in the following ones, and the settings (e.g. the SPICE
+    /// compatibility mode or the load checks) are those of the first Almanac.
+    ///
+    /// This only copies pointers to the loaded data, but fails if the total number of kernels exceeds the capacity of an Almanac.
+    pub fn merged(&self) -> AlmanacResult<Almanac> {
+        let mut members = self.almanacs.iter().rev();
+        let mut merged = match members.next() {
+            Some(last) => last.clone(),
+            None => return Ok(Almanac::default()),
+        };
+
+        for almanac in members {
+            // Kernels loaded last have precedence.
+            for spk in almanac
+                .spk_data
+                .iter()
+                .take(almanac.num_loaded_spk())
+                .flatten()
+            {
+                merged = merged.add_spk(spk.clone()).context(EphemerisSnafu {
+                    action: "merging federated Almanacs",
+                })?;
+            }
+            for bpc in almanac
+                .bpc_data
+                .iter()
+                .take(almanac.num_loaded_bpc())
+                .flatten()
+            {
+                merged = merged.add_bpc(bpc.clone()).context(OrientationSnafu {
+                    action: "merging federated Almanacs",
+                })?;
+            }
+
+            merged
+                .planetary_data
+                .merge(&almanac.planetary_data)
+                .context(TLDataSetSnafu {
+                    action: "merging federated planetary data",
+                })?;
+            merged
+                .spacecraft_data
+                .merge(&almanac.spacecraft_data)
+                .context(TLDataSetSnafu {
+                    action: "merging federated spacecraft data",
+                })?;
+            merged
+                .euler_param_data
+                .merge(&almanac.euler_param_data)
+                .context(TLDataSetSnafu {
+                    action: "merging federated Euler parameters",
+                })?;
+            merged
+                .maneuver_data
+                .merge(&almanac.maneuver_data)
+                .context(TLDataSetSnafu {
+                    action: "merging federated maneuver data",
+                })?;
+            for constant in &almanac.constant_overrides {
+                merged
+                    .constant_overrides
+                    .retain(|other| other.name != constant.name);
+                merged.constant_overrides.push(*constant);
+            }
+            for planetary_override in &almanac.planetary_overrides {
+                merged.planetary_overrides.retain(|other| {
+                    other.id != planetary_override.id
+                        || core::mem::discriminant(&other.value)
+                            != core::mem::discriminant(&planetary_override.value)
+                });
+                merged.planetary_overrides.push(*planetary_override);
+            }
+            for clock in &almanac.sclk_data {
+                merged = merged.with_sclk(clock.clone());
+            }
+            for (name, id) in &almanac.body_names {
+                merged = merged.register_body_name(name, *id);
+            }
+            merged
+                .ephemeris_providers
+                .extend(almanac.ephemeris_providers.iter().cloned());
+            merged
+                .orientation_providers
+                .extend(almanac.orientation_providers.iter().cloned());
+            for frame in &almanac.two_vector_frames {
+                merged = merged.with_two_vector_frame(frame.clone());
+            }
+            merged.kernel_pool.extend(&almanac.kernel_pool);
+            merged
+                .load_timings
+                .extend(almanac.load_timings.iter().cloned());
+            if almanac.leap_seconds.is_some() {
+                merged.leap_seconds = almanac.leap_seconds.clone();
+            }
+            merged.spice_compat = almanac.spice_compat;
+            merged.load_checks = almanac.load_checks;
+            merged.frame_bias = almanac.frame_bias;
+            merged.boundary_smoothing = almanac.boundary_smoothing;
+            merged.nutation_truncation = almanac.nutation_truncation;
+        }
+
+        Ok(merged)
+    }
+
+    /// Queries each Almanac in order, then their merge, and returns the first success or the first error.
+    fn query<T, E, F: Fn(&Almanac) -> Result<T, E>>(&self, query: F) -> Result<T, E> {
+        let mut first_err = None;
+        for almanac in &self.almanacs {
+            match query(almanac) {
+                Ok(result) => return Ok(result),
+                Err(e) => {
+                    first_err.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_err {
+            Some(e) => {
+                if self.almanacs.len() > 1 {
+                    let merged = self.merged_cache.get_or_init(|| self.merged().ok());
+                    if let Some(Ok(result)) = merged.as_ref().map(query) {
+                        return Ok(result);
+                    }
+                }
+                Err(e)
+            }
+            // No Almanac at all, so use an empty one to get the appropriate error.
+            None => query(&Almanac::default()),
+        }
+    }
+
+    /// Returns the Cartesian state of the target frame as seen from the observer frame, cf. [Almanac::transform].
+    pub fn transform(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<CartesianState> {
+        self.query(|almanac| almanac.transform(target_frame, observer_frame, epoch, ab_corr))
+    }
+
+    /// Returns the translation of the target frame as seen from the observer frame, cf. [Almanac::translate].
+    pub fn translate(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+    ) -> Result<CartesianState, EphemerisError> {
+        self.query(|almanac| almanac.translate(target_frame, observer_frame, epoch, ab_corr))
+    }
+
+    /// Returns the DCM from the `from_frame` to the `to_frame`, cf. [Almanac::rotate].
+    pub fn rotate(
+        &self,
+        from_frame: Frame,
+        to_frame: Frame,
+        epoch: Epoch,
+    ) -> Result<DCM, OrientationError> {
+        self.query(|almanac| almanac.rotate(from_frame, to_frame, epoch))
+    }
+
+    /// Returns the frame information of the provided frame, cf. [Almanac::frame_from_uid].
+    pub fn frame_from_uid(&self, uid: Frame) -> Result<Frame, PlanetaryDataError> {
+        self.query(|almanac| almanac.frame_from_uid(uid))
+    }
+
+    /// Returns the union of the domains of the provided ID across all of the Almanacs, cf. [Almanac::spk_domain].
+    ///
+    /// The union may include gaps where none of the Almanacs has data.
+    pub fn spk_domain(&self, id: NaifId) -> Result<(Epoch, Epoch), EphemerisError> {
+        let mut domain: Option<(Epoch, Epoch)> = None;
+        let mut first_err = None;
+
+        for almanac in &self.almanacs {
+            match almanac.spk_domain(id) {
+                Ok((start, end)) => {
+                    domain = Some(match domain {
+                        Some((prev_start, prev_end)) => (
+                            if start < prev_start {
+                                start
+                            } else {
+                                prev_start
+                            },
+                            if end > prev_end { end } else { prev_end },
+                        ),
+                        None => (start, end),
+                    });
+                }
+                Err(e) => {
+                    first_err.get_or_insert(e);
+                }
+            }
+        }
+
+        match (domain, first_err) {
+            (Some(domain), _) => Ok(domain),
+            (None, Some(e)) => Err(e),
+            (None, None) => Almanac::default().spk_domain(id),
+        }
+    }
+}
@@ -0,0 +1,306 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::Epoch;
+use snafu::ResultExt;
+
+use super::Almanac;
+use crate::astro::Aberration;
+use crate::constants::frames::SUN_J2000;
+use crate::ephemerides::EphemerisPhysicsSnafu;
+use crate::errors::{AlmanacError, AlmanacResult, EphemerisSnafu, OrientationSnafu, PhysicsError};
+use crate::frames::Frame;
+use crate::math::Vector3;
+use crate::prelude::Orbit;
+use crate::structure::planetocentric::ellipsoid::Ellipsoid;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Ellipse on the surface of a body, e.g. its limb as seen from an observer or its terminator, cf. [Almanac::limb] and
+/// [Almanac::terminator]. The points of the ellipse are `center + cos(θ) semi_major + sin(θ) semi_minor`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SurfaceEllipse {
+    pub epoch: Epoch,
+    /// Frame of the vectors, centered on the body
+    pub frame: Frame,
+    pub center_km: Vector3,
+    pub semi_major_axis_km: Vector3,
+    pub semi_minor_axis_km: Vector3,
+}
+
+impl SurfaceEllipse {
+    /// Returns the point of this ellipse at the provided angle from its semi major axis, in the frame of this ellipse.
+    pub fn point_km(&self, angle_rad: f64) -> Vector3 {
+        self.center_km
+            + angle_rad.cos() * self.semi_major_axis_km
+            + angle_rad.sin() * self.semi_minor_axis_km
+    }
+
+    /// Returns the unit normal of the plane of this ellipse.
+    pub fn normal(&self) -> Vector3 {
+        self.semi_major_axis_km
+            .cross(&self.semi_minor_axis_km)
+            .normalize()
+    }
+}
+
+impl fmt::Display for SurfaceEllipse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ellipse in {} @ {}: center = {:.3} km, semi axes = {:.3} km and {:.3} km",
+            self.frame,
+            self.epoch,
+            self.center_km,
+            self.semi_major_axis_km.norm(),
+            self.semi_minor_axis_km.norm()
+        )
+    }
+}
+
+impl Almanac {
+    /// Returns the limb of the body as seen from the observer, i.e. the ellipse where the lines of sight from the observer are
+    /// tangent to the ellipsoid of the body, expressed in the orientation of the observer and centered on the body.
+    ///
+    /// The body frame should be its body fixed frame (e.g. `IAU_MOON_FRAME`) such that its triaxial shape is oriented correctly;
+    /// its shape is fetched from the planetary data if it is not set.
+    ///
+    /// # Algorithm
+    /// The limb is the intersection of the ellipsoid and of the polar plane of the observer. Scaling the axes of the ellipsoid to
+    /// a unit sphere makes it a circle, whose generating vectors are scaled back and converted into the semi axes of the ellipse
+    /// (as in SPICE's `EDLIMB`).
+    pub fn limb(
+        &self,
+        body: Frame,
+        observer: Orbit,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<SurfaceEllipse> {
+        self.limb_geometry(body, observer, ab_corr)
+            .map(|(limb, _)| limb)
+    }
+
+    /// Returns the terminator of the body, i.e. the ellipse separating its day and night sides, expressed in the body frame.
+    ///
+    /// The Sun is modeled as a point source, so this is the limb of the body as seen from the center of the Sun: the
+    /// penumbral band around it spans about a quarter of a degree of the body as seen from it at 1 AU.
+    pub fn terminator(
+        &self,
+        mut body: Frame,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<SurfaceEllipse> {
+        let shape = self.body_shape(&mut body, "computing terminator")?;
+
+        let sun_km = self.transform(SUN_J2000, body, epoch, ab_corr)?.radius_km;
+
+        limb_ellipse(sun_km, shape, "computing terminator").map(
+            |(center_km, semi_major_axis_km, semi_minor_axis_km)| SurfaceEllipse {
+                epoch,
+                frame: body,
+                center_km,
+                semi_major_axis_km,
+                semi_minor_axis_km,
+            },
+        )
+    }
+
+    /// Returns the ellipse of the limb and the position of the observer in its frame.
+    fn limb_geometry(
+        &self,
+        mut body: Frame,
+        observer: Orbit,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<(SurfaceEllipse, Vector3)> {
+        let shape = self.body_shape(&mut body, "computing limb")?;
+
+        // Position of the observer with respect to the body, in the axes of its shape
+        let obs_body_km = self.transform_to(observer, body, ab_corr)?.radius_km;
+
+        let (center_km, semi_major_km, semi_minor_km) =
+            limb_ellipse(obs_body_km, shape, "computing limb")?;
+
+        let frame = Frame::new(body.ephemeris_id, observer.frame.orientation_id);
+        let body_to_obs = self
+            .rotate(body, frame, observer.epoch)
+            .context(OrientationSnafu {
+                action: "computing limb",
+            })?
+            .rot_mat;
+
+        Ok((
+            SurfaceEllipse {
+                epoch: observer.epoch,
+                frame,
+                center_km: body_to_obs * center_km,
+                semi_major_axis_km: body_to_obs * semi_major_km,
+                semi_minor_axis_km: body_to_obs * semi_minor_km,
+            },
+            body_to_obs * obs_body_km,
+        ))
+    }
+
+    /// Sets the frame data of the body if needed, and returns its shape.
+    fn body_shape(&self, body: &mut Frame, action: &'static str) -> AlmanacResult<Ellipsoid> {
+        if body.shape.is_none() {
+            *body = self
+                .frame_from_uid(*body)
+                .map_err(|e| AlmanacError::GenericError {
+                    err: format!("{e} when fetching {body:e} frame data"),
+                })?;
+        }
+
+        body.shape
+            .ok_or(PhysicsError::MissingFrameData {
+                action,
+                data: "shape",
+                frame: (*body).into(),
+            })
+            .context(EphemerisPhysicsSnafu {
+                action: "fetching shape of body",
+            })
+            .context(EphemerisSnafu { action })
+    }
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Returns the apparent angular diameter in degrees of the body as seen from the observer, i.e. the angle subtended by the
+    /// major axis of its limb, cf. `limb`. For a spherical body of radius R at a distance d, this is 2 asin(R/d).
+    ///
+    /// :type body: Frame
+    /// :type observer: Orbit
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: float
+    pub fn angular_diameter_deg(
+        &self,
+        body: Frame,
+        observer: Orbit,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<f64> {
+        let (limb, obs_km) = self.limb_geometry(body, observer, ab_corr)?;
+        let range_km = (limb.center_km - obs_km).norm();
+        Ok(2.0 * limb.semi_major_axis_km.norm().atan2(range_km).to_degrees())
+    }
+}
+
+/// Returns the center and the semi major and semi minor axes of the limb of the ellipsoid as seen from the provided point,
+/// all in the axes of the ellipsoid.
+fn limb_ellipse(
+    viewpoint_km: Vector3,
+    shape: Ellipsoid,
+    action: &'static str,
+) -> AlmanacResult<(Vector3, Vector3, Vector3)> {
+    let radii_km = Vector3::new(
+        shape.semi_major_equatorial_radius_km,
+        shape.semi_minor_equatorial_radius_km,
+        shape.polar_radius_km,
+    );
+
+    // In the scaled space, the ellipsoid is the unit sphere.
+    let scaled_view = viewpoint_km.component_div(&radii_km);
+    let scaled_dist_sq = scaled_view.norm_squared();
+    if scaled_dist_sq <= 1.0 {
+        return Err(AlmanacError::GenericError {
+            err: format!(
+                "{action}: viewpoint {viewpoint_km} km is inside of the ellipsoid {shape}"
+            ),
+        });
+    }
+
+    let normal = scaled_view.normalize();
+    let scaled_center = scaled_view / scaled_dist_sq;
+    let scaled_radius = (1.0 - 1.0 / scaled_dist_sq).sqrt();
+
+    // Any orthonormal basis of the plane of the circle.
+    let helper = if normal.x.abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    let u = normal.cross(&helper).normalize();
+    let w = normal.cross(&u);
+
+    // Generating vectors of the ellipse
+    let center_km = scaled_center.component_mul(&radii_km);
+    let gen1 = (scaled_radius * u).component_mul(&radii_km);
+    let gen2 = (scaled_radius * w).component_mul(&radii_km);
+
+    // Rotate the generating vectors such that they are orthogonal, i.e. the semi axes.
+    let theta = 0.5 * (2.0 * gen1.dot(&gen2)).atan2(gen1.norm_squared() - gen2.norm_squared());
+    let axis1 = theta.cos() * gen1 + theta.sin() * gen2;
+    let axis2 = -theta.sin() * gen1 + theta.cos() * gen2;
+
+    if axis1.norm() >= axis2.norm() {
+        Ok((center_km, axis1, axis2))
+    } else {
+        Ok((center_km, axis2, -axis1))
+    }
+}
+
+#[cfg(test)]
+mod ut_limb {
+    use super::limb_ellipse;
+    use crate::math::Vector3;
+    use crate::structure::planetocentric::ellipsoid::Ellipsoid;
+
+    #[test]
+    fn sphere_limb() {
+        let radius_km = 1_000.0;
+        let dist_km = 5_000.0;
+        let (center, major, minor) = limb_ellipse(
+            Vector3::new(0.0, 0.0, dist_km),
+            Ellipsoid::from_sphere(radius_km),
+            "test",
+        )
+        .unwrap();
+
+        assert!((center - Vector3::new(0.0, 0.0, radius_km.powi(2) / dist_km)).norm() < 1e-9);
+        let expected_km = radius_km * (1.0 - (radius_km / dist_km).powi(2)).sqrt();
+        assert!((major.norm() - expected_km).abs() < 1e-9);
+        assert!((minor.norm() - expected_km).abs() < 1e-9);
+
+        assert!(limb_ellipse(
+            Vector3::new(10.0, 0.0, 0.0),
+            Ellipsoid::from_sphere(radius_km),
+            "test"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn triaxial_limb() {
+        // Phobos like ellipsoid
+        let shape = Ellipsoid {
+            semi_major_equatorial_radius_km: 13.0,
+            semi_minor_equatorial_radius_km: 11.4,
+            polar_radius_km: 9.1,
+        };
+        let radii = Vector3::new(13.0, 11.4, 9.1);
+        let view = Vector3::new(40.0, -25.0, 18.0);
+        let (center, major, minor) = limb_ellipse(view, shape, "test").unwrap();
+
+        assert!(major.dot(&minor).abs() < 1e-9);
+        assert!(major.norm() >= minor.norm());
+
+        for i in 0..36 {
+            let angle = (i as f64 * 10.0).to_radians();
+            let point = center + angle.cos() * major + angle.sin() * minor;
+            // On the ellipsoid
+            let scaled = point.component_div(&radii);
+            assert!((scaled.norm() - 1.0).abs() < 1e-12);
+            // And the line of sight is tangent to it, i.e. orthogonal to its normal.
+            let normal = point.component_div(&radii.component_mul(&radii));
+            assert!(normal.dot(&(point - view)).abs() < 1e-9);
+        }
+    }
+}
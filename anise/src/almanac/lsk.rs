@@ -0,0 +1,66 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::Epoch;
+
+use crate::naif::kpl::lsk::LeapSecondsKernel;
+
+use super::Almanac;
+
+impl Almanac {
+    /// Returns a copy of this Almanac with the provided leap second kernel, replacing the previous one if any.
+    ///
+    /// The kernel is only used for the UTC conversions in SPICE compatibility mode, cf. `with_spice_compat`.
+    pub fn with_lsk(&self, lsk: LeapSecondsKernel) -> Self {
+        let mut me = self.clone();
+        me.leap_seconds = Some(lsk);
+        me
+    }
+
+    /// Returns the leap second kernel used for the UTC conversions, which is only the loaded one in SPICE compatibility mode.
+    pub fn strict_lsk(&self) -> Option<&LeapSecondsKernel> {
+        if self.spice_compat {
+            self.leap_seconds.as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the epoch of the provided UTC calendar date.
+    ///
+    /// In SPICE compatibility mode with a loaded LSK, the leap seconds of that kernel are used, so that the ET seconds of the epoch
+    /// match those of SPICE exactly. Otherwise, the leap seconds of hifitime are used.
+    #[allow(clippy::too_many_arguments)]
+    pub fn utc_to_epoch(
+        &self,
+        year: i32,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        nanos: u32,
+    ) -> Epoch {
+        match self.strict_lsk() {
+            Some(lsk) => {
+                lsk.epoch_from_gregorian_utc(year, month, day, hour, minute, second, nanos)
+            }
+            None => Epoch::from_gregorian_utc(year, month, day, hour, minute, second, nanos),
+        }
+    }
+
+    /// Returns the UTC calendar date of the provided epoch, with the leap seconds of the loaded LSK in SPICE compatibility mode, cf. `utc_to_epoch`.
+    pub fn epoch_to_utc(&self, epoch: Epoch) -> (i32, u8, u8, u8, u8, u8, u32) {
+        match self.strict_lsk() {
+            Some(lsk) => lsk.to_gregorian_utc(epoch),
+            None => epoch.to_gregorian_utc(),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::Epoch;
+use snafu::ResultExt;
+
+use crate::errors::{AlmanacError, AlmanacResult, TLDataSetSnafu};
+use crate::structure::maneuver::Maneuver;
+use crate::structure::spacecraft::Mass;
+use crate::NaifId;
+
+use super::Almanac;
+
+impl Almanac {
+    /// Returns the maneuvers of the spacecraft with the provided ID which happen at least partially between the start and end
+    /// epochs (inclusive), sorted by start epoch, from the loaded maneuver data.
+    ///
+    /// A finite burn in progress at the start epoch is included, such that a trajectory segment can be propagated from there.
+    pub fn maneuvers(
+        &self,
+        spacecraft_id: NaifId,
+        start: Epoch,
+        end: Epoch,
+    ) -> AlmanacResult<Vec<Maneuver>> {
+        let plan = self
+            .maneuver_data
+            .get_by_id(spacecraft_id)
+            .context(TLDataSetSnafu {
+                action: "fetching maneuvers",
+            })?;
+
+        Ok(plan.between(start, end))
+    }
+
+    /// Returns the mass of the spacecraft with the provided ID at the provided epoch, from the mass history of the loaded
+    /// spacecraft data, or from its constant mass if it has no history, cf. `SpacecraftData::mass_at`.
+    pub fn spacecraft_mass(&self, spacecraft_id: NaifId, epoch: Epoch) -> AlmanacResult<Mass> {
+        self.spacecraft_data
+            .get_ref_by_id(spacecraft_id)
+            .context(TLDataSetSnafu {
+                action: "fetching spacecraft mass",
+            })?
+            .mass_at(epoch)
+            .ok_or(AlmanacError::GenericError {
+                err: format!("no mass data for spacecraft {spacecraft_id}"),
+            })
+    }
+}
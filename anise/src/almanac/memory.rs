@@ -0,0 +1,194 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use core::mem::size_of;
+
+use snafu::ResultExt;
+
+use crate::errors::{AlmanacError, AlmanacResult, LoadingSnafu};
+use crate::file2heap;
+use crate::naif::daf::{NAIFSummaryRecord, DAF};
+use crate::structure::dataset::{DataSet, DataSetT};
+
+use super::Almanac;
+
+/// Memory held by one of the kernels or data sets loaded in an Almanac.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoadedData {
+    /// Kind of data, e.g. `SPK` or `planetary data`
+    pub kind: &'static str,
+    /// Handle of this data: the CRC32 of the file for SPK and BPC kernels, or the checksum of the data for data sets.
+    pub crc32: u32,
+    /// Number of bytes held in memory, or mapped in memory if `memory_mapped` is set
+    pub bytes: usize,
+    /// Set for the SPK and BPC kernels loaded lazily with `load_lazy`, whose bytes are memory mapped: only the pages which
+    /// were read are resident in memory.
+    pub memory_mapped: bool,
+}
+
+impl fmt::Display for LoadedData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} 0x{:08x}: {} bytes",
+            self.kind, self.crc32, self.bytes
+        )?;
+        if self.memory_mapped {
+            write!(f, " (memory mapped)")?;
+        }
+        Ok(())
+    }
+}
+
+impl Almanac {
+    /// Returns the memory held by each of the kernels and data sets loaded in this Almanac, in the order they were loaded.
+    ///
+    /// The size of SPK and BPC kernels is that of the whole file, which is held in memory unless the kernel was loaded
+    /// with `load_lazy`: such kernels are memory mapped and listed with `memory_mapped` set. The size of the data sets is
+    /// the size of their decoded structure and data.
+    pub fn memory_usage(&self) -> Vec<LoadedData> {
+        let mut usage = Vec::new();
+
+        for spk in self.spk_data.iter().take(self.num_loaded_spk()).flatten() {
+            usage.push(daf_usage("SPK", spk));
+        }
+        for bpc in self.bpc_data.iter().take(self.num_loaded_bpc()).flatten() {
+            usage.push(daf_usage("BPC", bpc));
+        }
+
+        for (kind, maybe_usage) in [
+            ("planetary data", dataset_usage(&self.planetary_data)),
+            ("spacecraft data", dataset_usage(&self.spacecraft_data)),
+            (
+                "Euler parameter data",
+                dataset_usage(&self.euler_param_data),
+            ),
+            ("maneuver data", dataset_usage(&self.maneuver_data)),
+        ] {
+            if let Some((crc32, bytes)) = maybe_usage {
+                usage.push(LoadedData {
+                    kind,
+                    crc32,
+                    bytes,
+                    memory_mapped: false,
+                });
+            }
+        }
+
+        usage
+    }
+
+    /// Returns the total number of bytes held by the kernels and data sets loaded in this Almanac, cf. [Almanac::memory_usage].
+    ///
+    /// The memory mapped kernels are not included, since their bytes are only resident in memory once read.
+    pub fn total_memory_usage(&self) -> usize {
+        self.memory_usage()
+            .iter()
+            .filter(|loaded| !loaded.memory_mapped)
+            .map(|loaded| loaded.bytes)
+            .sum()
+    }
+
+    /// Returns a copy of this Almanac without the SPK or BPC kernel whose CRC32 is provided, as listed by [Almanac::memory_usage].
+    ///
+    /// The other kernels remain loaded in the same order, so the precedence of their segments is unchanged.
+    pub fn unload_by_crc32(&self, crc32: u32) -> AlmanacResult<Self> {
+        let key = DafKey::Crc32(crc32);
+        let mut me = self.clone();
+        if unload_daf(&mut me.spk_data, key) || unload_daf(&mut me.bpc_data, key) {
+            Ok(me)
+        } else {
+            Err(AlmanacError::GenericError {
+                err: format!("no SPK or BPC with CRC32 0x{crc32:08x} is loaded"),
+            })
+        }
+    }
+
+    /// Returns a copy of this Almanac without the SPK or BPC kernel loaded from the provided path.
+    ///
+    /// The kernel is identified by the path it was loaded from, without reading the file. Kernels loaded from bytes have no
+    /// path, so they are identified by the CRC32 of the file instead, cf. [Almanac::unload_by_crc32].
+    pub fn unload(&self, path: &str) -> AlmanacResult<Self> {
+        let key = DafKey::Path(path);
+        let mut me = self.clone();
+        if unload_daf(&mut me.spk_data, key) || unload_daf(&mut me.bpc_data, key) {
+            return Ok(me);
+        }
+
+        let bytes = file2heap!(path).context(LoadingSnafu {
+            path: path.to_string(),
+        })?;
+
+        self.unload_by_crc32(crc32fast::hash(&bytes))
+            .map_err(|e| AlmanacError::GenericError {
+                err: format!("with {path}: {e}"),
+            })
+    }
+}
+
+fn daf_usage<R: NAIFSummaryRecord>(kind: &'static str, daf: &DAF<R>) -> LoadedData {
+    LoadedData {
+        kind,
+        crc32: daf.checksum(),
+        bytes: daf.bytes.len(),
+        memory_mapped: daf.lazy_crc32.is_some(),
+    }
+}
+
+/// Returns the checksum and size of the data set, if it contains any data.
+fn dataset_usage<T: DataSetT, const ENTRIES: usize>(
+    dataset: &DataSet<T, ENTRIES>,
+) -> Option<(u32, usize)> {
+    if dataset.data.is_empty() {
+        None
+    } else {
+        Some((
+            dataset.data_checksum,
+            size_of::<DataSet<T, ENTRIES>>() + dataset.data.capacity() * size_of::<T>(),
+        ))
+    }
+}
+
+/// Identifies a loaded DAF to unload.
+#[derive(Copy, Clone)]
+enum DafKey<'a> {
+    Crc32(u32),
+    /// Path the DAF was loaded from, which does not require computing the checksum of lazily loaded DAFs
+    Path(&'a str),
+}
+
+impl DafKey<'_> {
+    fn matches<R: NAIFSummaryRecord>(&self, daf: &DAF<R>) -> bool {
+        match self {
+            Self::Crc32(crc32) => daf.checksum() == *crc32,
+            Self::Path(path) => daf.source_path() == Some(*path),
+        }
+    }
+}
+
+/// Removes the first DAF matching the key from the slots, shifting the following ones such that the loaded DAFs remain
+/// contiguous.
+fn unload_daf<R: NAIFSummaryRecord, const N: usize>(
+    slots: &mut [Option<DAF<R>>; N],
+    key: DafKey,
+) -> bool {
+    match slots
+        .iter()
+        .position(|slot| slot.as_ref().is_some_and(|daf| key.matches(daf)))
+    {
+        Some(idx) => {
+            slots[idx] = None;
+            slots[idx..].rotate_left(1);
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,210 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use log::warn;
+use snafu::ResultExt;
+
+use crate::errors::{AlmanacError, AlmanacResult, LoadingSnafu, MetaSnafu};
+use crate::prelude::InputOutputError;
+
+use super::{Almanac, DownloadProgress, MetaAlmanac, MetaAlmanacError, MetaFile};
+
+/// Function called with the progress of each download, from the blocking download threads.
+pub type ProgressCallback = Arc<dyn Fn(&DownloadProgress) + Send + Sync>;
+
+/// Retry policy of the asynchronous downloads, with an exponential backoff.
+///
+/// Only the connection and fetching errors are retried: IO errors on the local storage fail immediately.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts of each download, including the first one
+    pub max_attempts: u32,
+    /// Delay before the second attempt, doubled after each failed attempt
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between two attempts
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, waiting one second then two seconds.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns a policy which never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+}
+
+impl MetaFile {
+    /// Processes this MetaFile like `process` without blocking the async runtime, retrying failed downloads per the
+    /// provided policy, and returns the processed MetaFile.
+    pub async fn process_async(
+        &self,
+        autodelete: bool,
+        retry: RetryPolicy,
+        progress: Option<ProgressCallback>,
+    ) -> Result<MetaFile, MetaAlmanacError> {
+        let mut backoff = retry.initial_backoff;
+        let mut attempt = 1;
+
+        loop {
+            let mut file = self.clone();
+            let progress = progress.clone();
+
+            let result = tokio::task::spawn_blocking(move || {
+                match progress {
+                    Some(progress) => file.process_with_progress(autodelete, progress.as_ref()),
+                    None => file.process(autodelete),
+                }
+                .map(|_| file)
+            })
+            .await
+            .unwrap_or_else(|e| {
+                Err(MetaAlmanacError::FetchError {
+                    error: format!("download task failed: {e}"),
+                    uri: self.uri.clone(),
+                })
+            });
+
+            match result {
+                Err(
+                    e @ (MetaAlmanacError::FetchError { .. } | MetaAlmanacError::CnxError { .. }),
+                ) if attempt < retry.max_attempts => {
+                    warn!("{e} (attempt {attempt}) -- retrying in {backoff:?}");
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(retry.max_backoff);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl MetaAlmanac {
+    /// Fetches all of the URIs like `process` without blocking the async runtime, and returns a loaded Almanac.
+    ///
+    /// The files are downloaded concurrently, each with the provided retry policy, and then loaded in order.
+    pub async fn process_async(
+        &mut self,
+        autodelete: bool,
+        retry: RetryPolicy,
+        progress: Option<ProgressCallback>,
+    ) -> AlmanacResult<Almanac> {
+        let tasks = self
+            .files
+            .iter()
+            .map(|file| {
+                let file = file.clone();
+                let progress = progress.clone();
+                tokio::spawn(async move { file.process_async(autodelete, retry, progress).await })
+            })
+            .collect::<Vec<_>>();
+
+        for (fno, task) in tasks.into_iter().enumerate() {
+            let file = self.files[fno].clone();
+            let processed = task
+                .await
+                .unwrap_or_else(|e| {
+                    Err(MetaAlmanacError::FetchError {
+                        error: format!("download task failed: {e}"),
+                        uri: file.uri.clone(),
+                    })
+                })
+                .context(MetaSnafu { fno, file })?;
+            self.files[fno] = processed;
+        }
+
+        // At this stage, all of the files are local files, so we can load them as is.
+        let mut ctx = Almanac::default();
+        for file in &self.files {
+            ctx = ctx.load_async(&file.uri).await?;
+        }
+        Ok(ctx)
+    }
+}
+
+impl Almanac {
+    /// Loads the provided path like `load`, reading the file without blocking the async runtime.
+    pub async fn load_async(&self, path: &str) -> AlmanacResult<Self> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| InputOutputError::IOError { kind: e.kind() })
+            .context(LoadingSnafu {
+                path: path.to_string(),
+            })?;
+
+        self._load_from_bytes(Bytes::from(bytes), Some(path))
+            .map_err(|e| match e {
+                AlmanacError::GenericError { err } => AlmanacError::GenericError {
+                    err: format!("with {path}: {err}"),
+                },
+                _ => e,
+            })
+    }
+
+    /// Loads from the provided MetaFile like `load_from_metafile`, downloading it if necessary without blocking the
+    /// async runtime, with the provided retry policy and progress callback.
+    pub async fn load_from_metafile_async(
+        &self,
+        metafile: MetaFile,
+        autodelete: bool,
+        retry: RetryPolicy,
+        progress: Option<ProgressCallback>,
+    ) -> AlmanacResult<Self> {
+        let processed = metafile
+            .process_async(autodelete, retry, progress)
+            .await
+            .context(MetaSnafu {
+                fno: 0_usize,
+                file: metafile.clone(),
+            })?;
+        self.load_async(&processed.uri).await
+    }
+}
+
+#[cfg(test)]
+mod ut_async_load {
+    use super::{Almanac, MetaFile, RetryPolicy};
+
+    #[tokio::test]
+    async fn load_local_async() {
+        let almanac = Almanac::default()
+            .load_from_metafile_async(
+                MetaFile {
+                    uri: "../data/de440s.bsp".to_string(),
+                    crc32: None,
+                },
+                true,
+                RetryPolicy::none(),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(almanac.num_loaded_spk(), 1);
+
+        assert!(almanac.load_async("../data/not_a_file.bsp").await.is_err());
+    }
+}
@@ -68,12 +68,13 @@ impl MetaAlmanac {
                 file: file.clone(),
             })?;
         }
-        // At this stage, all of the files are local files, so we can load them as is.
-        let mut ctx = Almanac::default();
-        for uri in &self.files {
-            ctx = ctx.load(&uri.uri)?;
-        }
-        Ok(ctx)
+        // At this stage, all of the files are local files, so we can load them as is, in parallel.
+        let paths = self
+            .files
+            .iter()
+            .map(|file| file.uri.as_str())
+            .collect::<Vec<_>>();
+        Almanac::default().load_parallel(&paths)
     }
 
     /// Returns an Almanac loaded from the latest NAIF data via the `default` MetaAlmanac.
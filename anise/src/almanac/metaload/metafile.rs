@@ -15,7 +15,7 @@ use serde_derive::{Deserialize, Serialize};
 use serde_dhall::StaticType;
 use std::env;
 use std::fs::{create_dir_all, remove_file, File};
-use std::io::Write;
+use std::io::{Read, Write};
 use std::path::Path;
 use std::thread;
 use std::time::Duration;
@@ -52,11 +52,37 @@ pub struct MetaFile {
     pub crc32: Option<u32>,
 }
 
+/// Progress of the download of a remote file, as reported to the progress callbacks.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DownloadProgress {
+    /// URI of the file being downloaded
+    pub uri: String,
+    /// Number of bytes downloaded so far
+    pub downloaded_bytes: usize,
+    /// Size of the file, if provided by the server
+    pub total_bytes: Option<u64>,
+}
+
+/// Size of the chunks in which a remote file is read, between two progress reports.
+const DOWNLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Maximum size of a remote file.
+const MAX_DOWNLOAD_SIZE: u64 = 1024 * 1024 * 200;
+
 impl MetaFile {
     /// Processes this MetaFile by downloading it if it's a URL and sets this structure's `uri` field to the local path
     ///
     /// This function modified `self` and changes the URI to be the path to the downloaded file.
     pub fn process(&mut self, autodelete: bool) -> Result<(), MetaAlmanacError> {
+        self.process_with_progress(autodelete, &|_| {})
+    }
+
+    /// Processes this MetaFile like `process`, calling the provided function after each chunk of a download.
+    pub fn process_with_progress(
+        &mut self,
+        autodelete: bool,
+        progress: &dyn Fn(&DownloadProgress),
+    ) -> Result<(), MetaAlmanacError> {
         // First, parse environment variables if any.
         self.uri = replace_env_vars(&self.uri);
         match Url::parse(&self.uri) {
@@ -203,17 +229,15 @@ impl MetaFile {
                                                         }
                                                         Ok(mut file) => {
                                                             // Created the file, let's write the bytes.
-                                                            let bytes = resp
-                                                                .body_mut()
-                                                                .with_config()
-                                                                .limit(1024 * 1024 * 200) // 200 MB limit
-                                                                .read_to_vec()
-                                                                .map_err(|e| {
-                                                                    MetaAlmanacError::FetchError {
-                                                                        error: format!("{e:?}"),
-                                                                        uri: self.uri.clone(),
-                                                                    }
-                                                                })?;
+                                                            let bytes = match self
+                                                                .read_body(&mut resp, progress)
+                                                            {
+                                                                Ok(bytes) => bytes,
+                                                                Err(e) => {
+                                                                    del_lock_file();
+                                                                    return Err(e);
+                                                                }
+                                                            };
                                                             let crc32 = crc32fast::hash(&bytes);
                                                             file.write_all(&bytes).unwrap();
 
@@ -273,6 +297,58 @@ impl MetaFile {
     }
 }
 
+impl MetaFile {
+    /// Reads the body of the response in chunks, reporting the progress after each chunk.
+    fn read_body(
+        &self,
+        resp: &mut ureq::http::Response<ureq::Body>,
+        progress: &dyn Fn(&DownloadProgress),
+    ) -> Result<Vec<u8>, MetaAlmanacError> {
+        let mut report = DownloadProgress {
+            uri: self.uri.clone(),
+            downloaded_bytes: 0,
+            total_bytes: resp.body().content_length(),
+        };
+
+        if let Some(total_bytes) = report.total_bytes {
+            if total_bytes > MAX_DOWNLOAD_SIZE {
+                return Err(MetaAlmanacError::FetchError {
+                    error: format!(
+                        "content length of {total_bytes} bytes exceeds the maximum download size of {MAX_DOWNLOAD_SIZE} bytes"
+                    ),
+                    uri: self.uri.clone(),
+                });
+            }
+        }
+
+        let mut reader = resp
+            .body_mut()
+            .with_config()
+            .limit(MAX_DOWNLOAD_SIZE)
+            .reader();
+
+        let mut bytes =
+            Vec::with_capacity(report.total_bytes.unwrap_or(0).min(MAX_DOWNLOAD_SIZE) as usize);
+        let mut chunk = vec![0; DOWNLOAD_CHUNK_SIZE];
+        loop {
+            let count = reader
+                .read(&mut chunk)
+                .map_err(|e| MetaAlmanacError::FetchError {
+                    error: format!("{e:?}"),
+                    uri: self.uri.clone(),
+                })?;
+            if count == 0 {
+                break;
+            }
+            bytes.extend_from_slice(&chunk[..count]);
+            report.downloaded_bytes = bytes.len();
+            progress(&report);
+        }
+
+        Ok(bytes)
+    }
+}
+
 #[cfg(feature = "python")]
 #[cfg_attr(feature = "python", pymethods)]
 impl MetaFile {
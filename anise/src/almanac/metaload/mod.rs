@@ -11,8 +11,15 @@
 mod metaalmanac;
 mod metafile;
 
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+mod async_load;
+
 pub use metaalmanac::MetaAlmanac;
-pub use metafile::MetaFile;
+pub use metafile::{DownloadProgress, MetaFile};
+
+#[cfg(feature = "tokio")]
+pub use async_load::{ProgressCallback, RetryPolicy};
 
 use super::Almanac;
 
@@ -9,38 +9,91 @@
  */
 
 use bytes::Bytes;
-use hifitime::TimeScale;
-use log::info;
+use hifitime::{Duration, TimeScale};
+use log::{info, warn};
 use snafu::ResultExt;
 use zerocopy::FromBytes;
 
+use crate::almanac::bundle::{KernelBundle, BUNDLE_MAGIC};
+use crate::almanac::cache::QueryCache;
+use crate::almanac::overlay::PlanetaryOverride;
+use crate::almanac::parallel::FileLoadTiming;
+use crate::almanac::transcript::QueryTranscript;
+use crate::constants::registry::Constant;
+use crate::ephemerides::provider::EphemerisProvider;
 use crate::ephemerides::SPKSnafu;
+#[cfg(feature = "signing")]
+use crate::errors::SignatureSnafu;
 use crate::errors::{
     AlmanacError, AlmanacResult, EphemerisSnafu, LoadingSnafu, OrientationSnafu, TLDataSetSnafu,
 };
+#[cfg(feature = "signing")]
 use crate::file2heap;
-use crate::naif::daf::{FileRecord, NAIFRecord};
+use crate::file2mmap;
+use crate::naif::daf::{DAFError, FileRecord, IntegrityReport, NAIFRecord};
+use crate::naif::kpl::lsk::LeapSecondsKernel;
+use crate::naif::kpl::pool::KernelPool;
+use crate::naif::kpl::sclk::{parse_sclk, SpacecraftClock};
 use crate::naif::pretty_print::NAIFPrettyPrint;
 use crate::naif::{BPC, SPK};
-use crate::orientations::BPCSnafu;
+use crate::orientations::{BPCSnafu, NutationTruncation, OrientationProvider, TwoVectorFrame};
 use crate::structure::dataset::DataSetType;
 use crate::structure::metadata::Metadata;
-use crate::structure::{EulerParameterDataSet, PlanetaryDataSet, SpacecraftDataSet};
+use crate::structure::{
+    EulerParameterDataSet, ManeuverDataSet, PlanetaryDataSet, SpacecraftDataSet,
+};
+use crate::NaifId;
 use core::fmt;
+use std::sync::Arc;
 
 // TODO: Switch these to build constants so that it's configurable when building the library.
 pub const MAX_LOADED_SPKS: usize = 32;
 pub const MAX_LOADED_BPCS: usize = 8;
-pub const MAX_SPACECRAFT_DATA: usize = 16;
+/// Capacity of the look up tables of the spacecraft and maneuver data sets with the `heapless-lut` feature, large enough for
+/// constellations; this must be a power of two. Otherwise, the look up tables are heap allocated and grow as needed.
+pub const MAX_SPACECRAFT_DATA: usize = 256;
+/// Capacity of the look up tables of the planetary and Euler parameter data sets with the `heapless-lut` feature; this must
+/// be a power of two.
 pub const MAX_PLANETARY_DATA: usize = 128;
 
+pub mod accuracy;
 pub mod aer;
 pub mod bpc;
+pub mod bundle;
+pub mod cache;
+pub mod compiled;
+pub mod continuity;
 pub mod eclipse;
+pub mod euler_angles;
+pub mod events;
+pub mod extrapolate;
+pub mod federated;
+pub mod limb;
+pub mod lsk;
+pub mod maneuver;
+pub mod memory;
+pub mod named;
+pub mod names;
+pub mod opnav;
+pub mod orientation_subset;
+pub mod overlay;
+pub mod parallel;
+pub mod partials;
 pub mod planetary;
+pub mod provenance;
+pub mod sample;
+pub mod sclk;
+pub mod site;
 pub mod solar;
 pub mod spk;
+pub mod timeline;
+pub mod transcript;
 pub mod transform;
+pub mod window;
+
+#[cfg(feature = "export")]
+#[cfg_attr(docsrs, doc(cfg(feature = "export")))]
+pub mod export;
 
 #[cfg(feature = "metaload")]
 pub mod metaload;
@@ -52,11 +105,20 @@ mod python;
 #[cfg_attr(docsrs, doc(cfg(feature = "embed_ephem")))]
 mod embed;
 
+#[cfg(feature = "embed-constants")]
+#[cfg_attr(docsrs, doc(cfg(feature = "embed-constants")))]
+mod embed_constants;
+
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
 
 /// An Almanac contains all of the loaded SPICE and ANISE data. It is the context for all computations.
 ///
+/// # Thread safety
+/// The Almanac is `Send` and `Sync`, which is checked at compile time, so a single Almanac may be shared by reference across
+/// threads (as done by the batch and parallel APIs). Its query cache and transcript are behind locks shared by all of the
+/// queries, and a panic in one thread does not prevent the other threads from using them.
+///
 /// :type path: str
 /// :rtype: Almanac
 #[derive(Clone, Default)]
@@ -73,6 +135,42 @@ pub struct Almanac {
     pub spacecraft_data: SpacecraftDataSet,
     /// Dataset of euler parameters
     pub euler_param_data: EulerParameterDataSet,
+    /// Dataset of the maneuvers of spacecraft, cf. `maneuvers`
+    pub maneuver_data: ManeuverDataSet,
+    /// Transcript of the queries of this Almanac, if recording is enabled with `with_transcript`
+    pub transcript: Option<QueryTranscript>,
+    /// Set to true to mimic the behavior of SPICE, cf. `with_spice_compat`
+    pub spice_compat: bool,
+    /// Values overriding those of the constants registry, cf. `with_constant`
+    pub constant_overrides: Vec<Constant>,
+    /// Spacecraft clocks loaded from SCLK kernels
+    pub sclk_data: Vec<SpacecraftClock>,
+    /// Cache of the query results, if enabled with `with_cache`
+    pub cache: Option<QueryCache>,
+    /// Custom body names registered in addition to the standard NAIF names, cf. `register_body_name`
+    pub body_names: Vec<(String, NaifId)>,
+    /// Set to true to check the integrity of SPK and BPC files when they are loaded, cf. `with_load_checks`
+    pub load_checks: bool,
+    /// Custom ephemeris sources, cf. `with_ephemeris_provider`
+    pub ephemeris_providers: Vec<Arc<dyn EphemerisProvider>>,
+    /// Custom orientation sources, cf. `with_orientation_provider`
+    pub orientation_providers: Vec<Arc<dyn OrientationProvider>>,
+    /// Dynamic frames defined by two directions computed from the ephemerides, cf. `with_two_vector_frame`
+    pub two_vector_frames: Vec<TwoVectorFrame>,
+    /// Variables assigned in the loaded text kernels, e.g. `BODY399_RADII` or mission specific parameters
+    pub kernel_pool: KernelPool,
+    /// Leap second kernel used for the UTC conversions in SPICE compatibility mode, cf. `with_lsk`
+    pub leap_seconds: Option<LeapSecondsKernel>,
+    /// Set to true to apply the frame bias between the ICRF and J2000, cf. `with_frame_bias`
+    pub frame_bias: bool,
+    /// Half width of the window over which the ephemerides are smoothed across the boundaries of SPK segments, cf. `with_boundary_smoothing`
+    pub boundary_smoothing: Option<Duration>,
+    /// Time spent loading each file, cf. `load_timings`
+    pub load_timings: Vec<FileLoadTiming>,
+    /// Planetary constants overriding those of the planetary data, cf. `override_gm`
+    pub planetary_overrides: Vec<PlanetaryOverride>,
+    /// Truncation of the nutation series of the analytic orientation models, if any, cf. `with_nutation_truncation`
+    pub nutation_truncation: Option<NutationTruncation>,
 }
 
 impl fmt::Display for Almanac {
@@ -92,6 +190,9 @@ impl fmt::Display for Almanac {
         if !self.euler_param_data.lut.by_id.is_empty() {
             write!(f, "\t{}", self.euler_param_data)?;
         }
+        if !self.maneuver_data.data.is_empty() {
+            write!(f, "\t{}", self.maneuver_data)?;
+        }
         Ok(())
     }
 }
@@ -116,12 +217,240 @@ impl Almanac {
         me
     }
 
+    /// Loads the provided maneuver data into a clone of this original Almanac.
+    pub fn with_maneuver_data(&self, maneuver_data: ManeuverDataSet) -> Self {
+        let mut me = self.clone();
+        me.maneuver_data = maneuver_data;
+        me
+    }
+
+    /// Returns a copy of this Almanac where the SPICE compatibility mode is enabled or disabled.
+    ///
+    /// This mode mimics the quirks of SPICE such that validation against SPICE matches exactly, whereas the default mode uses ANISE's preferred behavior.
+    /// The differences are:
+    /// + Segment selection: SPICE uses the last segment of a file covering the epoch, whereas ANISE uses the first one.
+    ///   ANISE also accepts epochs within 100 ns of the bounds of a segment, whereas SPICE requires the epoch to be within the bounds.
+    ///   In both modes, the files loaded last have precedence.
+    /// + Converged light time: SPICE iterates up to five times until the light time stops changing, whereas ANISE always iterates three times.
+    /// + Frame tie: the J2000 frame is identical to the ICRF, as in SPICE, even if the frame bias is enabled with `with_frame_bias`.
+    /// + Leap seconds: the UTC conversions of the Almanac (e.g. `utc_to_epoch`) use the loaded LSK, if any, instead of the leap seconds of hifitime.
+    pub fn with_spice_compat(&self, enabled: bool) -> Self {
+        let mut me = self.clone();
+        me.spice_compat = enabled;
+        me
+    }
+
+    /// Returns a copy of this Almanac where the frame bias between the ICRF ([ICRF](crate::constants::orientations::ICRF))
+    /// and the J2000 frame is applied, or not (the default).
+    ///
+    /// The ephemerides and orientations of the SPK and BPC files are in the ICRF although labeled J2000, so this only matters
+    /// for data expressed in the mean equator and dynamical equinox of J2000, e.g. the FK5 star catalog, compared to ICRF data like
+    /// the Gaia star catalog: the difference is about 0.023 arcseconds, cf. [icrf_frame_bias](crate::orientations::icrf_frame_bias).
+    /// The frame bias is never applied in SPICE compatibility mode.
+    pub fn with_frame_bias(&self, enabled: bool) -> Self {
+        let mut me = self.clone();
+        me.frame_bias = enabled;
+        me
+    }
+
+    /// Returns a copy of this Almanac where the nutation series of the analytic orientation models are truncated as requested,
+    /// or fully evaluated (the default) if None, trading accuracy for speed, e.g. on embedded systems.
+    ///
+    /// This applies to the fast Earth orientation ([ITRF93_FAST](crate::constants::orientations::ITRF93_FAST)) and to the
+    /// IAU rotation models of the planetary data, e.g. that of the Moon, cf. [NutationTruncation] for the resulting errors.
+    pub fn with_nutation_truncation(&self, truncation: Option<NutationTruncation>) -> Self {
+        let mut me = self.clone();
+        me.nutation_truncation = truncation;
+        me
+    }
+
+    /// Returns a copy of this Almanac where the translations are smoothed over the provided half width around the boundaries
+    /// between two SPK segments of the same object, or not smoothed (the default) if None.
+    ///
+    /// Within that window, the position and velocity are a cubic Hermite interpolation between the states at both ends of the window,
+    /// such that the velocity is the derivative of the position even if the segments are not continuous, e.g. before and after
+    /// a maneuver. Use [continuity_report](Almanac::continuity_report) to check the jumps at the boundaries.
+    pub fn with_boundary_smoothing(&self, half_width: Option<Duration>) -> Self {
+        let mut me = self.clone();
+        me.boundary_smoothing = half_width;
+        me
+    }
+
+    /// Returns a copy of this Almanac where the SPK and BPC files are checked when they are loaded, or not.
+    ///
+    /// The checks are those of [integrity_report](crate::naif::daf::DAF::integrity_report): the issues found are logged
+    /// as warnings but the files are loaded regardless. Use `integrity_reports` to retrieve them.
+    pub fn with_load_checks(&self, enabled: bool) -> Self {
+        let mut me = self.clone();
+        me.load_checks = enabled;
+        me
+    }
+
+    /// Returns the integrity reports of all of the loaded SPK files, followed by those of all of the loaded BPC files,
+    /// in the order in which they were loaded.
+    pub fn integrity_reports(&self) -> AlmanacResult<Vec<IntegrityReport>> {
+        let mut reports = Vec::new();
+        for spk in self.spk_data.iter().take(self.num_loaded_spk()).flatten() {
+            reports.push(
+                spk.integrity_report()
+                    .context(SPKSnafu {
+                        action: "checking integrity",
+                    })
+                    .context(EphemerisSnafu {
+                        action: "checking integrity",
+                    })?,
+            );
+        }
+        for bpc in self.bpc_data.iter().take(self.num_loaded_bpc()).flatten() {
+            reports.push(
+                bpc.integrity_report()
+                    .context(BPCSnafu {
+                        action: "checking integrity",
+                    })
+                    .context(OrientationSnafu {
+                        action: "checking integrity",
+                    })?,
+            );
+        }
+        Ok(reports)
+    }
+
+    /// Loads the ANISE data set (or bundle of data sets) at the provided path like `load`, after verifying the signature
+    /// stored in each data set (cf. [sign_file](crate::structure::signature::sign_file)) against the trusted public key.
+    /// Nothing is loaded if a signature is missing or does not match, or if the file is not an ANISE data set.
+    #[cfg(feature = "signing")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "signing")))]
+    pub fn load_verified(
+        &self,
+        path: &str,
+        trusted_key: &crate::structure::signature::VerifyingKey,
+    ) -> AlmanacResult<Self> {
+        let bytes = file2heap!(path).context(LoadingSnafu {
+            path: path.to_string(),
+        })?;
+
+        let kernel = Self::parse_kernel(bytes, Some(path))?;
+        kernel.check_integrity(self.load_checks);
+        kernel
+            .verify_signatures(trusted_key)
+            .context(SignatureSnafu {
+                path: path.to_string(),
+            })?;
+
+        self.with_parsed_kernel(kernel)
+    }
+
+    /// Loads the file at the provided path like `load`, except that SPK and BPC files are memory mapped and parsed lazily
+    /// (cf. [parse_lazy](crate::naif::daf::DAF::parse_lazy)), such that the load time does not depend on the size of the file,
+    /// e.g. for the multi-gigabyte DE441 kernels. Their pages are only read from disk when the segments are queried.
+    ///
+    /// The file must not be modified while it is loaded. The checks enabled with `with_load_checks` still read the whole file.
+    pub fn load_lazy(&self, path: &str) -> AlmanacResult<Self> {
+        let bytes = file2mmap!(path).context(LoadingSnafu {
+            path: path.to_string(),
+        })?;
+
+        let fileid = bytes
+            .get(..FileRecord::SIZE)
+            .and_then(|file_record_bytes| FileRecord::read_from_bytes(file_record_bytes).ok())
+            .and_then(|file_record| file_record.identification().ok().map(str::to_string));
+
+        match fileid.as_deref() {
+            Some("SPK") => {
+                info!("Loading {path} lazily as DAF/SPK");
+                let spk = SPK::parse_lazy(bytes)
+                    .context(SPKSnafu {
+                        action: "parsing bytes lazily",
+                    })
+                    .context(EphemerisSnafu {
+                        action: "from lazy loading",
+                    })?
+                    .with_source_path(path);
+                self.with_spk(spk).context(EphemerisSnafu {
+                    action: "adding SPK file to context",
+                })
+            }
+            Some("PCK") => {
+                info!("Loading {path} lazily as DAF/PCK");
+                let bpc = BPC::parse_lazy(bytes)
+                    .context(BPCSnafu {
+                        action: "parsing bytes lazily",
+                    })
+                    .context(OrientationSnafu {
+                        action: "from lazy loading",
+                    })?
+                    .with_source_path(path);
+                self.with_bpc(bpc).context(OrientationSnafu {
+                    action: "adding BPC file to context",
+                })
+            }
+            // Other files are small enough to be loaded as usual.
+            _ => self.load(path),
+        }
+    }
+
     /// Loads the provides bytes as one of the data types supported in ANISE.
     pub fn load_from_bytes(&self, bytes: Bytes) -> AlmanacResult<Self> {
         self._load_from_bytes(bytes, None)
     }
 
     fn _load_from_bytes(&self, bytes: Bytes, path: Option<&str>) -> AlmanacResult<Self> {
+        let kernel = Self::parse_kernel(bytes, path)?;
+        kernel.check_integrity(self.load_checks);
+        self.with_parsed_kernel(kernel)
+    }
+
+    /// Parses the provided bytes as one of the data types supported in ANISE, checking their integrity, but without adding
+    /// them to an Almanac, such that several files can be parsed concurrently.
+    pub(crate) fn parse_kernel(bytes: Bytes, path: Option<&str>) -> AlmanacResult<ParsedKernel> {
+        let parse_pool = || {
+            KernelPool::parse(&mut bytes.as_ref()).map_err(|e| AlmanacError::GenericError {
+                err: format!(
+                    "syntax error at {e} when loading {}",
+                    path.unwrap_or("bytes")
+                ),
+            })
+        };
+
+        if bytes.starts_with(BUNDLE_MAGIC) {
+            info!("Loading {} as ANISE bundle", path.unwrap_or("bytes"));
+            let bundle = KernelBundle::parse(bytes)?;
+            return Ok(ParsedKernel::Bundle(
+                bundle
+                    .data()
+                    .map(|(name, data)| Self::parse_kernel(data, Some(name)))
+                    .collect::<AlmanacResult<Vec<ParsedKernel>>>()?,
+            ));
+        }
+
+        // Text SCLK kernels are identified by their header
+        if bytes.starts_with(b"KPL/SCLK") {
+            info!("Loading {} as KPL/SCLK", path.unwrap_or("bytes"));
+            let clocks =
+                parse_sclk(&mut bytes.as_ref()).map_err(|e| AlmanacError::GenericError {
+                    err: format!("{e} when loading {}", path.unwrap_or("bytes")),
+                })?;
+            return Ok(ParsedKernel::Sclk {
+                clocks,
+                pool: parse_pool()?,
+            });
+        } else if bytes.starts_with(b"KPL/LSK") {
+            info!("Loading {} as KPL/LSK", path.unwrap_or("bytes"));
+            let pool = parse_pool()?;
+            let lsk =
+                LeapSecondsKernel::from_pool(&pool).map_err(|e| AlmanacError::GenericError {
+                    err: format!("{e} when loading {}", path.unwrap_or("bytes")),
+                })?;
+            return Ok(ParsedKernel::Lsk { lsk, pool });
+        } else if bytes.starts_with(b"KPL/") {
+            // Other text kernels must be converted to be used as planetary constants or frames, so only their variables are loaded.
+            info!(
+                "Loading {} into the kernel pool (convert it to use its planetary constants or frames)",
+                path.unwrap_or("bytes")
+            );
+            return Ok(ParsedKernel::Pool(parse_pool()?));
+        }
+
         // Try to load as a SPICE DAF first (likely the most typical use case)
 
         // Load the header only
@@ -131,29 +460,31 @@ impl Almanac {
                 return match fileid {
                     "PCK" => {
                         info!("Loading {} as DAF/PCK", path.unwrap_or("bytes"));
-                        let bpc = BPC::parse(bytes)
+                        let mut bpc = BPC::parse(bytes)
                             .context(BPCSnafu {
                                 action: "parsing bytes",
                             })
                             .context(OrientationSnafu {
                                 action: "from generic loading",
                             })?;
-                        self.with_bpc(bpc).context(OrientationSnafu {
-                            action: "adding BPC file to context",
-                        })
+                        if let Some(path) = path {
+                            bpc = bpc.with_source_path(path);
+                        }
+                        Ok(ParsedKernel::Bpc(bpc))
                     }
                     "SPK" => {
                         info!("Loading {} as DAF/SPK", path.unwrap_or("bytes"));
-                        let spk = SPK::parse(bytes)
+                        let mut spk = SPK::parse(bytes)
                             .context(SPKSnafu {
                                 action: "parsing bytes",
                             })
                             .context(EphemerisSnafu {
                                 action: "from generic loading",
                             })?;
-                        self.with_spk(spk).context(EphemerisSnafu {
-                            action: "adding SPK file to context",
-                        })
+                        if let Some(path) = path {
+                            spk = spk.with_source_path(path);
+                        }
+                        Ok(ParsedKernel::Spk(spk))
                     }
                     fileid => Err(AlmanacError::GenericError {
                         err: format!("DAF/{fileid} is not yet supported"),
@@ -191,7 +522,7 @@ impl Almanac {
                         "Loading {} as ANISE spacecraft data",
                         path.unwrap_or("bytes")
                     );
-                    Ok(self.with_spacecraft_data(dataset))
+                    Ok(ParsedKernel::SpacecraftData(dataset))
                 }
                 DataSetType::PlanetaryData => {
                     // Decode as planetary data
@@ -201,7 +532,7 @@ impl Almanac {
                         }
                     })?;
                     info!("Loading {} as ANISE/PCA", path.unwrap_or("bytes"));
-                    Ok(self.with_planetary_data(dataset))
+                    Ok(ParsedKernel::PlanetaryData(dataset))
                 }
                 DataSetType::EulerParameterData => {
                     // Decode as euler parameter data
@@ -211,7 +542,17 @@ impl Almanac {
                         }
                     })?;
                     info!("Loading {} as ANISE/EPA", path.unwrap_or("bytes"));
-                    Ok(self.with_euler_parameters(dataset))
+                    Ok(ParsedKernel::EulerParameterData(dataset))
+                }
+                DataSetType::ManeuverData => {
+                    // Decode as maneuver data
+                    let dataset = ManeuverDataSet::try_from_bytes(bytes).context({
+                        TLDataSetSnafu {
+                            action: "loading as maneuver data",
+                        }
+                    })?;
+                    info!("Loading {} as ANISE maneuver data", path.unwrap_or("bytes"));
+                    Ok(ParsedKernel::ManeuverData(dataset))
                 }
             }
         } else {
@@ -220,6 +561,121 @@ impl Almanac {
             })
         }
     }
+
+    /// Returns a copy of this Almanac where the parsed kernel is added.
+    /// Adds the parsed kernel to a new context: the integrity of its SPK and BPC files is checked by `check_integrity`.
+    pub(crate) fn with_parsed_kernel(&self, kernel: ParsedKernel) -> AlmanacResult<Self> {
+        match kernel {
+            ParsedKernel::Sclk { clocks, pool } => {
+                let mut me = self.clone();
+                for clock in clocks {
+                    me = me.with_sclk(clock);
+                }
+                me.kernel_pool.extend(&pool);
+                Ok(me)
+            }
+            ParsedKernel::Lsk { lsk, pool } => {
+                let mut me = self.with_lsk(lsk);
+                me.kernel_pool.extend(&pool);
+                Ok(me)
+            }
+            ParsedKernel::Pool(pool) => {
+                let mut me = self.clone();
+                me.kernel_pool.extend(&pool);
+                Ok(me)
+            }
+            ParsedKernel::Bpc(bpc) => self.add_bpc(bpc).context(OrientationSnafu {
+                action: "adding BPC file to context",
+            }),
+            ParsedKernel::Spk(spk) => self.add_spk(spk).context(EphemerisSnafu {
+                action: "adding SPK file to context",
+            }),
+            ParsedKernel::SpacecraftData(dataset) => Ok(self.with_spacecraft_data(dataset)),
+            ParsedKernel::PlanetaryData(dataset) => Ok(self.with_planetary_data(dataset)),
+            ParsedKernel::EulerParameterData(dataset) => Ok(self.with_euler_parameters(dataset)),
+            ParsedKernel::ManeuverData(dataset) => Ok(self.with_maneuver_data(dataset)),
+            ParsedKernel::Bundle(kernels) => {
+                let mut me = self.clone();
+                for kernel in kernels {
+                    me = me.with_parsed_kernel(kernel)?;
+                }
+                Ok(me)
+            }
+        }
+    }
+}
+
+/// A kernel parsed by [Almanac::parse_kernel], which is not yet part of an Almanac.
+pub(crate) enum ParsedKernel {
+    Sclk {
+        clocks: Vec<SpacecraftClock>,
+        pool: KernelPool,
+    },
+    Lsk {
+        lsk: LeapSecondsKernel,
+        pool: KernelPool,
+    },
+    Pool(KernelPool),
+    Bpc(BPC),
+    Spk(SPK),
+    SpacecraftData(SpacecraftDataSet),
+    PlanetaryData(PlanetaryDataSet),
+    EulerParameterData(EulerParameterDataSet),
+    ManeuverData(ManeuverDataSet),
+    /// All of the kernels of a bundle, in the order of its manifest
+    Bundle(Vec<ParsedKernel>),
+}
+
+impl ParsedKernel {
+    /// Logs the issues found in the SPK and BPC files of this kernel, if the load checks are enabled, cf.
+    /// `Almanac::with_load_checks`. This is called where the kernel is parsed, e.g. concurrently in `load_parallel`.
+    pub(crate) fn check_integrity(&self, load_checks: bool) {
+        if !load_checks {
+            return;
+        }
+        match self {
+            Self::Spk(spk) => log_integrity("SPK", spk.integrity_report()),
+            Self::Bpc(bpc) => log_integrity("BPC", bpc.integrity_report()),
+            Self::Bundle(kernels) => kernels
+                .iter()
+                .for_each(|kernel| kernel.check_integrity(load_checks)),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(feature = "signing")]
+impl ParsedKernel {
+    /// Verifies the signature of the data sets of this kernel against the trusted key: other kernels are never signed.
+    fn verify_signatures(
+        &self,
+        trusted_key: &crate::structure::signature::VerifyingKey,
+    ) -> Result<(), crate::structure::signature::SignatureError> {
+        use crate::structure::signature::{verify, SignatureError};
+
+        match self {
+            Self::SpacecraftData(dataset) => verify(dataset, trusted_key),
+            Self::PlanetaryData(dataset) => verify(dataset, trusted_key),
+            Self::EulerParameterData(dataset) => verify(dataset, trusted_key),
+            Self::ManeuverData(dataset) => verify(dataset, trusted_key),
+            Self::Bundle(kernels) => kernels
+                .iter()
+                .try_for_each(|kernel| kernel.verify_signatures(trusted_key)),
+            _ => Err(SignatureError::Unsigned),
+        }
+    }
+}
+
+/// Logs the issues of the integrity report of a DAF file as warnings.
+pub(crate) fn log_integrity(kind: &str, report: Result<IntegrityReport, DAFError>) {
+    match report {
+        Ok(report) => {
+            for warning in &report.warnings {
+                warn!("{kind} {warning}");
+            }
+        }
+        Err(e) => warn!("could not check the integrity of the {kind}: {e}"),
+    }
 }
 
 #[cfg_attr(feature = "python", pymethods)]
@@ -229,21 +685,8 @@ impl Almanac {
     /// :type path: str
     /// :rtype: Almanac
     pub fn load(&self, path: &str) -> AlmanacResult<Self> {
-        // Load the data onto the heap
-        let bytes = file2heap!(path).context(LoadingSnafu {
-            path: path.to_string(),
-        })?;
-
-        self._load_from_bytes(bytes, Some(path))
-            .map_err(|e| match e {
-                AlmanacError::GenericError { err } => {
-                    // Add the path to the error
-                    AlmanacError::GenericError {
-                        err: format!("with {path}: {err}"),
-                    }
-                }
-                _ => e,
-            })
+        // Load the data onto the heap, recording the time spent
+        self.load_timed(path)
     }
 
     /// Initializes a new Almanac from the provided file path, guessing at the file type
@@ -325,5 +768,27 @@ impl Almanac {
                 self.euler_param_data.describe()
             );
         }
+
+        if !print_any && !self.load_timings.is_empty() {
+            println!("=== LOAD TIMINGS ===");
+            for timing in &self.load_timings {
+                println!("{timing}");
+            }
+        }
     }
 }
+
+// The batch and parallel APIs share these by reference across threads: this fails to compile if any of them loses `Send` or
+// `Sync`, e.g. if some interior state is added with a `RefCell` or an `Rc`.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Almanac>();
+    assert_send_sync::<SPK>();
+    assert_send_sync::<BPC>();
+    assert_send_sync::<QueryCache>();
+    assert_send_sync::<QueryTranscript>();
+    assert_send_sync::<TwoVectorFrame>();
+    assert_send_sync::<compiled::CompiledQuery>();
+    assert_send_sync::<federated::FederatedAlmanac>();
+    assert_send_sync::<orientation_subset::OrientationAlmanac>();
+};
@@ -0,0 +1,214 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+#[cfg(not(feature = "python"))]
+use core::str::FromStr;
+
+use hifitime::Epoch;
+use snafu::ResultExt;
+
+use crate::constants::orientations::{id_to_orientation_name, J2000};
+use crate::errors::{AlmanacError, AlmanacResult, EphemerisSnafu, OrientationSnafu};
+use crate::math::cartesian::CartesianState;
+use crate::orientations::OrientationError;
+use crate::prelude::{Aberration, Frame};
+use crate::NaifId;
+
+use super::Almanac;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// State of a target with respect to an observer, along with the names used to query it, as returned by
+/// [Almanac::state_of_named].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise"))]
+pub struct NamedState {
+    pub state: CartesianState,
+    /// Name of the target body, as provided
+    pub target: String,
+    /// Name of the observer body, as provided
+    pub observer: String,
+    /// Name of the orientation of the state, as provided
+    pub orientation: String,
+    pub ab_corr: Option<Aberration>,
+}
+
+impl fmt::Display for NamedState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} as seen from {} in {}",
+            self.target, self.observer, self.orientation
+        )?;
+        if let Some(ab_corr) = self.ab_corr {
+            write!(f, " ({ab_corr})")?;
+        }
+        write!(f, ": {}", self.state)
+    }
+}
+
+#[cfg(feature = "python")]
+#[cfg_attr(feature = "python", pymethods)]
+impl NamedState {
+    /// :rtype: Orbit
+    #[getter]
+    fn get_state(&self) -> CartesianState {
+        self.state
+    }
+
+    /// :rtype: str
+    #[getter]
+    fn get_target(&self) -> String {
+        self.target.clone()
+    }
+
+    /// :rtype: str
+    #[getter]
+    fn get_observer(&self) -> String {
+        self.observer.clone()
+    }
+
+    /// :rtype: str
+    #[getter]
+    fn get_orientation(&self) -> String {
+        self.orientation.clone()
+    }
+
+    /// :rtype: Aberration
+    #[getter]
+    fn get_ab_corr(&self) -> Option<Aberration> {
+        self.ab_corr
+    }
+
+    fn __str__(&self) -> String {
+        format!("{self}")
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self} (@{self:p})")
+    }
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Returns the state of the target as seen from the observer in the provided orientation, where all of the inputs
+    /// are strings, e.g. `state_of_named("MOON", "EARTH", "2023-11-05 12:00:00 UTC", "EME2000", Aberration::LT)`.
+    ///
+    /// This is the scripting entry point of [Almanac::state_of]:
+    /// + the target and observer are body names (case insensitive) or NAIF IDs, cf. `body_id`;
+    /// + the epoch is parsed by Hifitime, so it includes its time scale (in Python, epochs without a time scale are in the time
+    ///   scale of the `Config`, UTC by default);
+    /// + the orientation is a frame name like `EME2000`, `ICRF`, `ECLIPJ2000`, or `IAU_EARTH`, the name of loaded Euler
+    ///   parameters, or a NAIF ID, cf. `orientation_id`.
+    ///
+    /// :type target: str
+    /// :type observer: str
+    /// :type epoch: str
+    /// :type orientation: str
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: NamedState
+    pub fn state_of_named(
+        &self,
+        target: &str,
+        observer: &str,
+        epoch: &str,
+        orientation: &str,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<NamedState> {
+        let action = "resolving names of state query";
+        let target_id = self.body_id(target).context(EphemerisSnafu { action })?;
+        let observer_id = self.body_id(observer).context(EphemerisSnafu { action })?;
+        let orientation_id = self
+            .orientation_id(orientation)
+            .context(OrientationSnafu { action })?;
+        // Python users may configure the time scale of epochs which do not specify one.
+        #[cfg(feature = "python")]
+        let parsed = crate::python_config::current_config().parse_epoch(epoch);
+        #[cfg(not(feature = "python"))]
+        let parsed = Epoch::from_str(epoch);
+
+        let epoch = parsed.map_err(|e| AlmanacError::GenericError {
+            err: format!("{e} when parsing epoch `{epoch}`"),
+        })?;
+
+        let state = self.state_of(
+            target_id,
+            Frame::new(observer_id, orientation_id),
+            epoch,
+            ab_corr,
+        )?;
+
+        Ok(NamedState {
+            state,
+            target: target.to_string(),
+            observer: observer.to_string(),
+            orientation: orientation.to_string(),
+            ab_corr,
+        })
+    }
+
+    /// Returns the NAIF ID of the orientation of the provided name (case insensitive), checking the standard frame
+    /// names first (where `EME2000` is an alias of `J2000`), then the names of the two-vector frames, and those of the loaded
+    /// Euler parameters and planetary data. Integer strings are returned as is.
+    ///
+    /// :type name: str
+    /// :rtype: int
+    pub fn orientation_id(&self, name: &str) -> Result<NaifId, OrientationError> {
+        let normalized = name.trim().to_uppercase();
+        if let Ok(id) = normalized.parse::<NaifId>() {
+            return Ok(id);
+        } else if normalized == "EME2000" {
+            return Ok(J2000);
+        }
+
+        if let Ok(id) =
+            id_to_orientation_name(name.trim()).or_else(|_| id_to_orientation_name(&normalized))
+        {
+            return Ok(id);
+        }
+
+        if let Some(frame) = self
+            .two_vector_frames
+            .iter()
+            .find(|frame| frame.name.eq_ignore_ascii_case(&normalized))
+        {
+            return Ok(frame.orientation_id);
+        }
+
+        self.euler_param_data
+            .lut
+            .entries()
+            .values()
+            .chain(self.planetary_data.lut.entries().values())
+            .find_map(|(id, entry_name)| match (id, entry_name) {
+                (Some(id), Some(entry_name)) if entry_name.eq_ignore_ascii_case(&normalized) => {
+                    Some(*id)
+                }
+                _ => None,
+            })
+            .ok_or(OrientationError::OrientationNameToId {
+                name: name.to_string(),
+            })
+    }
+
+    /// Returns the spacecraft frame of the provided name (case insensitive) or NAIF ID, e.g. the `SC_BUS` frame of a converted
+    /// FK, centered on its spacecraft following the NAIF convention where the frames of the spacecraft -N are numbered from
+    /// -N000 to -N999, such that it can be used directly in `rotate` and `transform` queries.
+    ///
+    /// :type name: str
+    /// :rtype: Frame
+    pub fn spacecraft_frame(&self, name: &str) -> Result<Frame, OrientationError> {
+        let id = self.orientation_id(name)?;
+        Frame::from_spacecraft_frame(id).ok_or(OrientationError::NotSpacecraftFrame { id })
+    }
+}
@@ -0,0 +1,94 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use crate::ephemerides::EphemerisError;
+use crate::naif::names::{body_id_to_name, body_name_to_id, normalize_body_name};
+use crate::NaifId;
+
+use super::Almanac;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Returns a copy of this Almanac where the provided body name (case insensitive) maps to the provided NAIF ID,
+    /// e.g. to name a spacecraft. Registered names have precedence over the standard NAIF names, and registering a
+    /// name again replaces its ID.
+    ///
+    /// :type name: str
+    /// :type id: int
+    /// :rtype: Almanac
+    pub fn register_body_name(&self, name: &str, id: NaifId) -> Self {
+        let name = normalize_body_name(name);
+        let mut me = self.clone();
+        me.body_names.retain(|(other, _)| *other != name);
+        me.body_names.push((name, id));
+        me
+    }
+
+    /// Returns the NAIF ID of the provided body name (case insensitive), from the registered names first and then from
+    /// the standard NAIF names. Integer strings (e.g. `-85`) are returned as is.
+    ///
+    /// :type name: str
+    /// :rtype: int
+    pub fn body_id(&self, name: &str) -> Result<NaifId, EphemerisError> {
+        let normalized = normalize_body_name(name);
+        self.body_names
+            .iter()
+            .find(|(registered, _)| *registered == normalized)
+            .map(|(_, id)| *id)
+            .or_else(|| body_name_to_id(&normalized))
+            .ok_or(EphemerisError::NameToId {
+                name: name.to_string(),
+            })
+    }
+
+    /// Returns the name of the provided body ID, from the names registered last first and then from the standard NAIF
+    /// names.
+    ///
+    /// :type id: int
+    /// :rtype: str
+    pub fn body_name(&self, id: NaifId) -> Result<String, EphemerisError> {
+        self.body_names
+            .iter()
+            .rev()
+            .find(|(_, registered)| *registered == id)
+            .map(|(name, _)| name.clone())
+            .or_else(|| body_id_to_name(id).map(|name| name.to_string()))
+            .ok_or(EphemerisError::IdToName { id })
+    }
+}
+
+#[cfg(test)]
+mod ut_names {
+    use super::Almanac;
+
+    #[test]
+    fn register_body_names() {
+        let almanac = Almanac::default().register_body_name("My_SC", -123_456);
+
+        assert_eq!(almanac.body_id("my_sc").unwrap(), -123_456);
+        assert_eq!(almanac.body_name(-123_456).unwrap(), "MY_SC");
+        assert_eq!(almanac.body_id("Moon").unwrap(), 301);
+        assert_eq!(almanac.body_name(399).unwrap(), "EARTH");
+        assert!(almanac.body_id("other sc").is_err());
+        assert!(Almanac::default().body_name(-123_456).is_err());
+
+        // Registered names have precedence, and are replaced when registered again
+        let almanac = almanac
+            .register_body_name("Moon", -1)
+            .register_body_name("my_sc", -654_321);
+        assert_eq!(almanac.body_id("MOON").unwrap(), -1);
+        assert_eq!(almanac.body_id("MY_SC").unwrap(), -654_321);
+        assert!(almanac.body_name(-123_456).is_err());
+        assert_eq!(almanac.body_names.len(), 2);
+    }
+}
@@ -0,0 +1,187 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use snafu::ResultExt;
+
+use super::Almanac;
+use crate::astro::{gravitational_deflection, stellar_aberration, Aberration};
+use crate::constants::frames::{SSB_J2000, SUN_J2000};
+use crate::constants::orientations::J2000;
+use crate::constants::registry::GM_SUN;
+use crate::ephemerides::EphemerisPhysicsSnafu;
+use crate::errors::{AlmanacError, AlmanacResult, EphemerisSnafu, OrientationSnafu};
+use crate::frames::Frame;
+use crate::math::Vector3;
+use crate::prelude::Orbit;
+
+impl Almanac {
+    /// Returns the apparent unit direction from the observer to the target (e.g. the centroid of a planet in an optical
+    /// navigation image), expressed in the orientation of the observer.
+    ///
+    /// + `ab_corr`: the light time correction and, if its stellar flag is set, the stellar aberration correction;
+    /// + `light_bending`: whether to correct for the gravitational deflection of the light by the Sun.
+    ///
+    /// The light is deflected by the Sun at the light time corrected position of the target, before the stellar aberration
+    /// correction, as in the IERS Conventions. Transmission mode corrections are not defined for the deflection, so they are
+    /// applied as in reception.
+    pub fn apparent_direction(
+        &self,
+        target: Frame,
+        observer: Orbit,
+        ab_corr: Option<Aberration>,
+        light_bending: bool,
+    ) -> AlmanacResult<Vector3> {
+        let observer_ssb = self.transform_to(observer, SSB_J2000, None)?;
+
+        // Light time corrected position of the target, without the stellar aberration which is applied last.
+        let target_km = match ab_corr {
+            Some(ab_corr) => {
+                self.aberrated_state(
+                    target.with_orient(J2000),
+                    observer_ssb,
+                    Aberration {
+                        stellar: false,
+                        ..ab_corr
+                    },
+                )
+                .context(EphemerisSnafu {
+                    action: "computing apparent direction",
+                })?
+                .radius_km
+            }
+            None => {
+                self.transform(target.with_orient(J2000), SSB_J2000, observer.epoch, None)?
+                    .radius_km
+                    - observer_ssb.radius_km
+            }
+        };
+
+        if target_km.norm() <= f64::EPSILON {
+            return Err(AlmanacError::GenericError {
+                err: format!("{target:e} is at the observer, so it has no direction"),
+            });
+        }
+
+        let mut direction = target_km.normalize();
+        if light_bending {
+            let sun_ssb_km = self
+                .transform(SUN_J2000, SSB_J2000, observer.epoch, None)?
+                .radius_km;
+            let observer_from_sun_km = observer_ssb.radius_km - sun_ssb_km;
+            direction = self.deflect_by_sun(
+                direction,
+                observer_from_sun_km + target_km,
+                observer_from_sun_km,
+            )?;
+        }
+
+        if let Some(ab_corr) = ab_corr.filter(|ab_corr| ab_corr.stellar) {
+            direction = self.aberrate(direction, observer_ssb.velocity_km_s, ab_corr)?;
+        }
+
+        self.direction_in(direction, observer)
+    }
+
+    /// Returns the apparent unit direction from the observer to a star of the provided catalog direction, i.e. its unit
+    /// direction from the solar system barycenter in the ICRF (J2000), expressed in the orientation of the observer.
+    ///
+    /// The star is infinitely far, so it is only corrected for the stellar aberration of the observer and, if requested, for
+    /// the gravitational deflection of its light by the Sun. Its proper motion and parallax are not accounted for.
+    pub fn apparent_star_direction(
+        &self,
+        catalog_direction: Vector3,
+        observer: Orbit,
+        stellar: bool,
+        light_bending: bool,
+    ) -> AlmanacResult<Vector3> {
+        if catalog_direction.norm() <= f64::EPSILON {
+            return Err(AlmanacError::GenericError {
+                err: "catalog direction of a star must be a non zero vector".to_string(),
+            });
+        }
+
+        let mut direction = catalog_direction.normalize();
+
+        let observer_ssb = self.transform_to(observer, SSB_J2000, None)?;
+        if light_bending {
+            let sun_ssb_km = self
+                .transform(SUN_J2000, SSB_J2000, observer.epoch, None)?
+                .radius_km;
+            direction =
+                self.deflect_by_sun(direction, direction, observer_ssb.radius_km - sun_ssb_km)?;
+        }
+
+        if stellar {
+            direction = self.aberrate(
+                direction,
+                observer_ssb.velocity_km_s,
+                Aberration::LT_S.unwrap(),
+            )?;
+        }
+
+        self.direction_in(direction, observer)
+    }
+
+    /// Deflects the direction by the gravity of the Sun, whose GM is that of the planetary data if loaded, or of the constants.
+    fn deflect_by_sun(
+        &self,
+        direction: Vector3,
+        source_from_sun: Vector3,
+        observer_from_sun_km: Vector3,
+    ) -> AlmanacResult<Vector3> {
+        let gm_km3_s2 = match self.frame_from_uid(SUN_J2000).map(|sun| sun.mu_km3_s2) {
+            Ok(Some(mu_km3_s2)) => mu_km3_s2,
+            _ => self.constant(GM_SUN.name).unwrap_or(GM_SUN.value),
+        };
+
+        gravitational_deflection(direction, source_from_sun, observer_from_sun_km, gm_km3_s2)
+            .context(EphemerisPhysicsSnafu {
+                action: "computing light deflection by the Sun",
+            })
+            .context(EphemerisSnafu {
+                action: "computing apparent direction",
+            })
+    }
+
+    fn aberrate(
+        &self,
+        direction: Vector3,
+        obs_ssb_vel_km_s: Vector3,
+        ab_corr: Aberration,
+    ) -> AlmanacResult<Vector3> {
+        stellar_aberration(direction, obs_ssb_vel_km_s, ab_corr)
+            .context(EphemerisPhysicsSnafu {
+                action: "computing stellar aberration",
+            })
+            .context(EphemerisSnafu {
+                action: "computing apparent direction",
+            })
+            .map(|direction| direction.normalize())
+    }
+
+    /// Rotates a direction in J2000 into the orientation of the observer.
+    fn direction_in(&self, direction: Vector3, observer: Orbit) -> AlmanacResult<Vector3> {
+        if observer.frame.orientation_id == J2000 {
+            return Ok(direction);
+        }
+
+        let dcm = self
+            .rotate(
+                observer.frame.with_orient(J2000),
+                observer.frame,
+                observer.epoch,
+            )
+            .context(OrientationSnafu {
+                action: "rotating apparent direction",
+            })?;
+
+        Ok(dcm.rot_mat * direction)
+    }
+}
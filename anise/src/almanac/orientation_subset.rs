@@ -0,0 +1,127 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::Epoch;
+
+use super::memory::LoadedData;
+use super::Almanac;
+use crate::math::cartesian::CartesianState;
+use crate::math::rotation::DCM;
+use crate::naif::BPC;
+use crate::orientations::{AttitudeSample, DerivativePolicy, OrientationError};
+use crate::prelude::Frame;
+
+/// A subset of an Almanac with only the orientation data: the BPC kernels, the planetary constants (for the IAU frames),
+/// the Euler parameters (for the constant frames), and the orientation providers.
+///
+/// This is meant for software which never needs ephemerides, like attitude determination and control: it does not hold
+/// the SPK kernels (nor the spacecraft data, the clocks, or the query cache), and only the orientation queries are available.
+/// Build it with [Almanac::orientation_subset], and convert it back into a full Almanac with `Almanac::from`.
+#[derive(Clone, Default)]
+pub struct OrientationAlmanac {
+    almanac: Almanac,
+}
+
+impl OrientationAlmanac {
+    /// Loads a Binary Planetary Constants kernel.
+    pub fn with_bpc(&self, bpc: BPC) -> Result<Self, OrientationError> {
+        Ok(Self {
+            almanac: self.almanac.with_bpc(bpc)?,
+        })
+    }
+
+    /// Returns the number of loaded BPC kernels
+    pub fn num_loaded_bpc(&self) -> usize {
+        self.almanac.num_loaded_bpc()
+    }
+
+    /// Returns the DCM to rotate from the `from_frame` to the `to_frame`, cf. [Almanac::rotate].
+    pub fn rotate(
+        &self,
+        from_frame: Frame,
+        to_frame: Frame,
+        epoch: Epoch,
+    ) -> Result<DCM, OrientationError> {
+        self.almanac.rotate(from_frame, to_frame, epoch)
+    }
+
+    /// Returns the DCM to rotate from the `from_frame` to the `to_frame`, where the time derivative follows the provided policy, cf. [Almanac::rotate_with_policy].
+    pub fn rotate_with_policy(
+        &self,
+        from_frame: Frame,
+        to_frame: Frame,
+        epoch: Epoch,
+        policy: DerivativePolicy,
+    ) -> Result<DCM, OrientationError> {
+        self.almanac
+            .rotate_with_policy(from_frame, to_frame, epoch, policy)
+    }
+
+    /// Rotates the provided Cartesian state into the requested observer frame, cf. [Almanac::rotate_to].
+    pub fn rotate_to(
+        &self,
+        state: CartesianState,
+        observer_frame: Frame,
+    ) -> Result<CartesianState, OrientationError> {
+        self.almanac.rotate_to(state, observer_frame)
+    }
+
+    /// Returns the attitude of the `to` frame with respect to the `from` frame at each of the provided epochs, cf. [Almanac::rotate_batch].
+    pub fn rotate_batch(
+        &self,
+        from_frame: Frame,
+        to_frame: Frame,
+        epochs: &[Epoch],
+    ) -> Result<Vec<AttitudeSample>, OrientationError> {
+        self.almanac.rotate_batch(from_frame, to_frame, epochs)
+    }
+
+    /// Returns the memory held by each of the kernels and data sets of this subset, cf. [Almanac::memory_usage].
+    pub fn memory_usage(&self) -> Vec<LoadedData> {
+        self.almanac.memory_usage()
+    }
+}
+
+impl Almanac {
+    /// Returns the subset of this Almanac needed for orientation queries only, cf. [OrientationAlmanac].
+    ///
+    /// The kernels are shared and not copied, so the memory is only freed once this Almanac is dropped.
+    pub fn orientation_subset(&self) -> OrientationAlmanac {
+        OrientationAlmanac {
+            almanac: Almanac {
+                bpc_data: self.bpc_data.clone(),
+                planetary_data: self.planetary_data.clone(),
+                euler_param_data: self.euler_param_data.clone(),
+                spice_compat: self.spice_compat,
+                frame_bias: self.frame_bias,
+                constant_overrides: self.constant_overrides.clone(),
+                nutation_truncation: self.nutation_truncation,
+                planetary_overrides: self.planetary_overrides.clone(),
+                body_names: self.body_names.clone(),
+                load_checks: self.load_checks,
+                orientation_providers: self.orientation_providers.clone(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl From<Almanac> for OrientationAlmanac {
+    fn from(almanac: Almanac) -> Self {
+        almanac.orientation_subset()
+    }
+}
+
+impl From<OrientationAlmanac> for Almanac {
+    /// Converts the subset into a full Almanac, to which ephemerides may be loaded again.
+    fn from(subset: OrientationAlmanac) -> Self {
+        subset.almanac
+    }
+}
@@ -0,0 +1,217 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use super::Almanac;
+use crate::constants::registry::default_gm;
+use crate::prelude::Frame;
+use crate::structure::planetocentric::ellipsoid::Ellipsoid;
+use crate::NaifId;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// A planetary constant overridden in memory, cf. [PlanetaryOverride].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum OverriddenValue {
+    /// Gravitational parameter, in km^3/s^2
+    Gm(f64),
+    Shape(Ellipsoid),
+}
+
+impl fmt::Display for OverriddenValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Gm(mu_km3_s2) => write!(f, "GM = {mu_km3_s2} km^3/s^2"),
+            Self::Shape(shape) => write!(f, "shape = {shape}"),
+        }
+    }
+}
+
+/// A planetary constant of a body overridden in an Almanac, which takes precedence over the loaded planetary data, e.g. for a
+/// sensitivity study, cf. [Almanac::override_gm] and [Almanac::override_shape].
+///
+/// This is the only override of the planetary constants: overriding a GM of the constants registry with `with_constant`
+/// (e.g. `GM_EARTH`) adds a planetary override of its bodies.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PlanetaryOverride {
+    pub id: NaifId,
+    pub value: OverriddenValue,
+    /// Value replaced when this override was set, if any, to trace where the original value came from: that of the loaded
+    /// planetary data, or else the default GM of the constants registry
+    pub replaced: Option<OverriddenValue>,
+    /// Set if the replaced value is the default GM of the constants registry, because the body is not in the planetary data
+    pub replaced_from_registry: bool,
+}
+
+impl fmt::Display for PlanetaryOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.id, self.value)?;
+        match self.replaced {
+            Some(replaced) if self.replaced_from_registry => {
+                write!(f, " (overrides {replaced} of the constants registry)")
+            }
+            Some(replaced) => write!(f, " (overrides {replaced} of the planetary data)"),
+            None => write!(f, " (not in the planetary data)"),
+        }
+    }
+}
+
+impl Almanac {
+    /// Returns the planetary constants overridden in this Almanac, in the order in which they were set.
+    pub fn planetary_overrides(&self) -> &[PlanetaryOverride] {
+        &self.planetary_overrides
+    }
+
+    /// Returns true if any planetary constant of the body with the provided ephemeris ID is overridden.
+    pub(crate) fn is_overridden(&self, id: NaifId) -> bool {
+        self.planetary_overrides
+            .iter()
+            .any(|overlay| overlay.id == id)
+    }
+
+    /// Returns the provided frame with the overridden planetary constants of its ephemeris ID, if any.
+    pub(crate) fn apply_planetary_overrides(&self, mut frame: Frame) -> Frame {
+        for overlay in &self.planetary_overrides {
+            if overlay.id == frame.ephemeris_id {
+                match overlay.value {
+                    OverriddenValue::Gm(mu_km3_s2) => frame.mu_km3_s2 = Some(mu_km3_s2),
+                    OverriddenValue::Shape(shape) => frame.shape = Some(shape),
+                }
+            }
+        }
+        frame
+    }
+
+    fn with_planetary_override(&self, id: NaifId, value: OverriddenValue) -> Self {
+        let replaced = self
+            .planetary_data
+            .get_by_id(id)
+            .ok()
+            .and_then(|data| match value {
+                OverriddenValue::Gm(_) => Some(OverriddenValue::Gm(data.mu_km3_s2)),
+                OverriddenValue::Shape(_) => data.shape.map(OverriddenValue::Shape),
+            });
+        let registry_gm = match value {
+            OverriddenValue::Gm(_) if replaced.is_none() => {
+                default_gm(id).map(|gm| OverriddenValue::Gm(gm.value))
+            }
+            _ => None,
+        };
+
+        let mut me = self.clone();
+        // Only the last override of each constant of a body is kept.
+        me.planetary_overrides.retain(|other| {
+            other.id != id
+                || core::mem::discriminant(&other.value) != core::mem::discriminant(&value)
+        });
+        me.planetary_overrides.push(PlanetaryOverride {
+            id,
+            value,
+            replaced: replaced.or(registry_gm),
+            replaced_from_registry: registry_gm.is_some(),
+        });
+        me
+    }
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Returns a copy of this Almanac where the gravitational parameter (km^3/s^2) of the body with the provided ephemeris ID
+    /// is overridden, taking precedence over the loaded planetary data (at all epochs) without modifying it.
+    ///
+    /// :type id: int
+    /// :type mu_km3_s2: float
+    /// :rtype: Almanac
+    pub fn override_gm(&self, id: NaifId, mu_km3_s2: f64) -> Self {
+        self.with_planetary_override(id, OverriddenValue::Gm(mu_km3_s2))
+    }
+
+    /// Returns a copy of this Almanac where the shape of the body with the provided ephemeris ID is overridden, taking
+    /// precedence over the loaded planetary data (at all epochs) without modifying it.
+    ///
+    /// :type id: int
+    /// :type shape: Ellipsoid
+    /// :rtype: Almanac
+    pub fn override_shape(&self, id: NaifId, shape: Ellipsoid) -> Self {
+        self.with_planetary_override(id, OverriddenValue::Shape(shape))
+    }
+
+    /// Returns a copy of this Almanac without any overridden planetary constant.
+    ///
+    /// :rtype: Almanac
+    pub fn clear_planetary_overrides(&self) -> Self {
+        let mut me = self.clone();
+        me.planetary_overrides.clear();
+        me
+    }
+}
+
+#[cfg(test)]
+mod ut_overlay {
+    use super::{Almanac, OverriddenValue};
+    use crate::constants::frames::{EARTH_J2000, MOON_J2000};
+    use crate::constants::registry::GM_EARTH;
+    use crate::prelude::Frame;
+    use crate::structure::planetocentric::ellipsoid::Ellipsoid;
+
+    #[test]
+    fn overrides_without_planetary_data() {
+        let almanac = Almanac::default();
+        assert!(almanac.frame_from_uid(EARTH_J2000).is_err());
+
+        let almanac = almanac
+            .override_gm(399, 398_600.0)
+            .override_gm(399, 398_600.5)
+            .override_shape(399, Ellipsoid::from_sphere(6_378.0));
+
+        // Only the last override of each constant is kept, and only the registry had a GM of the Earth.
+        assert_eq!(almanac.planetary_overrides().len(), 2);
+        assert_eq!(
+            almanac.planetary_overrides()[0].replaced,
+            Some(OverriddenValue::Gm(GM_EARTH.value))
+        );
+        assert!(almanac.planetary_overrides()[0].replaced_from_registry);
+        assert!(almanac.planetary_overrides()[1].replaced.is_none());
+        assert_eq!(
+            almanac.planetary_overrides()[0].value,
+            OverriddenValue::Gm(398_600.5)
+        );
+
+        let earth = almanac.frame_from_uid(EARTH_J2000).unwrap();
+        assert_eq!(earth.mu_km3_s2, Some(398_600.5));
+        assert_eq!(earth.mean_equatorial_radius_km().unwrap(), 6_378.0);
+        assert!(almanac.frame_from_uid(MOON_J2000).is_err());
+
+        assert!(almanac
+            .clear_planetary_overrides()
+            .frame_from_uid(EARTH_J2000)
+            .is_err());
+    }
+
+    #[test]
+    fn registry_gm_is_a_planetary_override() {
+        // Overriding a GM of the registry or of the body is the same override, read by all of the consumers.
+        let almanac = Almanac::default()
+            .with_constant("GM_MERCURY", 22_031.8)
+            .unwrap();
+        assert_eq!(almanac.planetary_overrides().len(), 2);
+        assert_eq!(almanac.constant("GM_MERCURY").unwrap(), 22_031.8);
+        for id in [1, 199] {
+            let mercury = almanac.frame_from_uid(Frame::from_ephem_j2000(id)).unwrap();
+            assert_eq!(mercury.mu_km3_s2, Some(22_031.8));
+        }
+
+        let almanac = almanac.override_gm(199, 22_031.9);
+        assert_eq!(almanac.constant("GM_MERCURY").unwrap(), 22_031.9);
+        assert!(almanac.constant_overrides.is_empty());
+    }
+}
@@ -0,0 +1,154 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Instant;
+
+use hifitime::{Duration, Unit as TimeUnit};
+use snafu::ResultExt;
+
+use super::{Almanac, ParsedKernel};
+use crate::errors::{AlmanacError, AlmanacResult, LoadingSnafu};
+use crate::file2heap;
+
+/// Time spent loading a file in an Almanac, cf. [Almanac::load_timings].
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileLoadTiming {
+    pub path: String,
+    pub size_bytes: usize,
+    /// CRC32 of the SPK or BPC kernel of this file, used to find which file answered a query, cf. `Almanac::query_provenance`
+    pub crc32: Option<u32>,
+    /// Time spent reading the file into memory
+    pub read: Duration,
+    /// Time spent parsing the file, including the computation or verification of its checksum, and the integrity checks
+    /// enabled with `Almanac::with_load_checks`
+    pub parse: Duration,
+}
+
+impl fmt::Display for FileLoadTiming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({} bytes): read in {}, parsed in {}",
+            self.path, self.size_bytes, self.read, self.parse
+        )
+    }
+}
+
+impl Almanac {
+    /// Loads all of the files at the provided paths like `load`, e.g. the files of a large meta-kernel, where the files are
+    /// read, parsed, checksummed, and checked (cf. `with_load_checks`) concurrently on all of the available cores.
+    ///
+    /// The files are added to the Almanac in the order of the paths, so the precedence of the kernels is the same as if they
+    /// were loaded one after the other, and the error of the first file which fails, if any, is returned.
+    pub fn load_parallel(&self, paths: &[&str]) -> AlmanacResult<Self> {
+        let num_threads = thread::available_parallelism()
+            .map(|num| num.get())
+            .unwrap_or(1)
+            .min(paths.len())
+            .max(1);
+
+        // Each thread takes the next file to parse, so that one large file does not hold up the others.
+        let next_idx = AtomicUsize::new(0);
+        let mut parsed = thread::scope(|scope| {
+            let handles = (0..num_threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut parsed = Vec::new();
+                        loop {
+                            let idx = next_idx.fetch_add(1, Ordering::Relaxed);
+                            match paths.get(idx) {
+                                Some(path) => {
+                                    parsed.push((idx, read_and_parse(path, self.load_checks)))
+                                }
+                                None => return parsed,
+                            }
+                        }
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                // Propagate the panic of a thread, if any.
+                .flat_map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|e| std::panic::resume_unwind(e))
+                })
+                .collect::<Vec<_>>()
+        });
+        parsed.sort_by_key(|(idx, _)| *idx);
+
+        let mut me = self.clone();
+        for (_, result) in parsed {
+            let (kernel, timing) = result?;
+            me = me.with_parsed_kernel(kernel)?;
+            me.load_timings.push(timing);
+        }
+        Ok(me)
+    }
+
+    /// Returns the time spent loading each of the files loaded with `load` and `load_parallel`, in the order in which they
+    /// were loaded, e.g. to find which files slow down the start up of an application.
+    pub fn load_timings(&self) -> &[FileLoadTiming] {
+        &self.load_timings
+    }
+
+    /// Loads the file at the provided path, recording the time spent loading it.
+    pub(crate) fn load_timed(&self, path: &str) -> AlmanacResult<Self> {
+        let (kernel, timing) = read_and_parse(path, self.load_checks)?;
+        let mut me = self.with_parsed_kernel(kernel)?;
+        me.load_timings.push(timing);
+        Ok(me)
+    }
+}
+
+/// Reads and parses the file at the provided path, and checks its integrity if `load_checks` is set, without adding it to
+/// an Almanac.
+fn read_and_parse(path: &str, load_checks: bool) -> AlmanacResult<(ParsedKernel, FileLoadTiming)> {
+    let start = Instant::now();
+    let bytes = file2heap!(path).context(LoadingSnafu {
+        path: path.to_string(),
+    })?;
+    let read = start.elapsed();
+    let size_bytes = bytes.len();
+
+    let kernel = Almanac::parse_kernel(bytes, Some(path)).map_err(|e| match e {
+        AlmanacError::GenericError { err } => {
+            // Add the path to the error
+            AlmanacError::GenericError {
+                err: format!("with {path}: {err}"),
+            }
+        }
+        _ => e,
+    })?;
+    kernel.check_integrity(load_checks);
+    let parse = start.elapsed() - read;
+
+    let crc32 = match &kernel {
+        ParsedKernel::Spk(spk) => Some(spk.checksum()),
+        ParsedKernel::Bpc(bpc) => Some(bpc.checksum()),
+        _ => None,
+    };
+
+    Ok((
+        kernel,
+        FileLoadTiming {
+            path: path.to_string(),
+            size_bytes,
+            crc32,
+            read: read.as_secs_f64() * TimeUnit::Second,
+            parse: parse.as_secs_f64() * TimeUnit::Second,
+        },
+    ))
+}
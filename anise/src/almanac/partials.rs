@@ -0,0 +1,124 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::Epoch;
+use nalgebra::Matrix6x3;
+use snafu::ResultExt;
+
+use crate::astro::Aberration;
+use crate::constants::orientations::{ITRF93, J2000};
+use crate::constants::usual_planetary_constants::MEAN_EARTH_ANGULAR_VELOCITY_DEG_S;
+use crate::ephemerides::EphemerisPhysicsSnafu;
+use crate::errors::{AlmanacError, AlmanacResult, EphemerisSnafu, OrientationSnafu};
+use crate::math::cartesian::CartesianState;
+use crate::math::{Vector3, Vector6};
+use crate::prelude::Frame;
+
+use super::Almanac;
+
+/// Partial derivatives of a state with respect to the parameters usually considered (but not estimated) in orbit determination.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StatePartials {
+    pub state: CartesianState,
+    /// Partial of the state with respect to the gravitational parameter of the central body of the observer frame,
+    /// holding the osculating Keplerian elements constant, in km/(km^3/s^2) and (km/s)/(km^3/s^2).
+    pub dstate_dgm: Vector6,
+    /// Partials of the state with respect to the Earth orientation parameters, set only if the observer frame is ITRF93.
+    /// The columns are the polar motion x_p and y_p (per radian), and UT1-UTC (per second).
+    pub dstate_deop: Option<Matrix6x3<f64>>,
+}
+
+impl Almanac {
+    /// Returns the state of the target with respect to the observer like `transform`, along with its partial derivatives
+    /// with respect to the gravitational parameter of the observer and, for Earth-fixed (ITRF93) observer frames, with
+    /// respect to the Earth orientation parameters. These are useful for consider covariance analyses.
+    ///
+    /// # Algorithm
+    /// All partials are analytical.
+    /// + GM: holding the osculating Keplerian elements constant, the position does not depend on the GM and the
+    ///   inertial velocity scales with its square root, so the partial of the velocity is `v / (2 GM)`.
+    /// + EOP: per the IERS conventions, the polar motion rotates the Earth-fixed frame by `-x_p` about its Y axis and by
+    ///   `-y_p` about its X axis, and UT1-UTC rotates it about its Z axis by the Earth rotation rate times the offset. The
+    ///   partial of any vector `u` expressed in that frame with respect to a small rotation about axis `a` is `u × a`.
+    pub fn transform_partials(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<StatePartials> {
+        let state = self.transform(target_frame, observer_frame, epoch, ab_corr)?;
+
+        let mu_km3_s2 = self
+            .frame_from_uid_at(observer_frame, epoch)
+            .map_err(|e| AlmanacError::GenericError {
+                err: format!("{e} when fetching {observer_frame:e} frame data"),
+            })?
+            .mu_km3_s2()
+            .context(EphemerisPhysicsSnafu {
+                action: "fetching gravitational parameter of observer",
+            })
+            .context(EphemerisSnafu {
+                action: "computing partials with respect to GM",
+            })?;
+
+        // The Keplerian elements are defined in an inertial frame, so compute the partial there and rotate it.
+        let inertial_observer = observer_frame.with_orient(J2000);
+        let inertial_vel_km_s = if observer_frame.orient_origin_match(inertial_observer) {
+            state.velocity_km_s
+        } else {
+            self.transform(target_frame, inertial_observer, epoch, ab_corr)?
+                .velocity_km_s
+        };
+        let dcm = self
+            .rotate(inertial_observer, observer_frame, epoch)
+            .context(OrientationSnafu {
+                action: "computing partials with respect to GM",
+            })?;
+
+        let mut dstate_dgm = Vector6::zeros();
+        dstate_dgm
+            .fixed_rows_mut::<3>(3)
+            .copy_from(&(dcm.rot_mat * inertial_vel_km_s / (2.0 * mu_km3_s2)));
+
+        let dstate_deop = if observer_frame.orientation_id == ITRF93 {
+            let ut1_rate_rad_s = MEAN_EARTH_ANGULAR_VELOCITY_DEG_S.to_radians();
+
+            let mut partials = Matrix6x3::zeros();
+            for (col, (axis, scale)) in [
+                (Vector3::y(), -1.0),
+                (Vector3::x(), -1.0),
+                (Vector3::z(), ut1_rate_rad_s),
+            ]
+            .iter()
+            .enumerate()
+            {
+                let mut column = Vector6::zeros();
+                column
+                    .fixed_rows_mut::<3>(0)
+                    .copy_from(&(state.radius_km.cross(axis) * *scale));
+                column
+                    .fixed_rows_mut::<3>(3)
+                    .copy_from(&(state.velocity_km_s.cross(axis) * *scale));
+                partials.set_column(col, &column);
+            }
+
+            Some(partials)
+        } else {
+            None
+        };
+
+        Ok(StatePartials {
+            state,
+            dstate_dgm,
+            dstate_deop,
+        })
+    }
+}
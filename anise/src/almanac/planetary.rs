@@ -7,15 +7,21 @@
  *
  * Documentation: https://nyxspace.com/
  */
-use super::Almanac;
+use super::{overlay::OverriddenValue, Almanac};
+use hifitime::Epoch;
 use snafu::prelude::*;
 use tabled::{settings::Style, Table, Tabled};
 
 use crate::{
+    constants::registry::{constant_from_name, gm_ids_from_name, SPEED_OF_LIGHT},
+    errors::{AlmanacError, AlmanacResult, ErrorCode},
     prelude::{Frame, FrameUid},
-    structure::{dataset::DataSetError, PlanetaryDataSet},
+    structure::{dataset::DataSetError, planetocentric::ellipsoid::Ellipsoid, PlanetaryDataSet},
 };
 
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
 #[derive(Debug, Snafu, PartialEq)]
 #[snafu(visibility(pub(crate)))]
 pub enum PlanetaryDataError {
@@ -24,19 +30,62 @@ pub enum PlanetaryDataError {
         action: &'static str,
         source: DataSetError,
     },
+    #[snafu(display("{frame} has no {data} defined"))]
+    MissingPlanetaryData { data: &'static str, frame: FrameUid },
+}
+
+impl ErrorCode for PlanetaryDataError {
+    fn code(&self) -> u16 {
+        match self {
+            Self::PlanetaryDataSet { source, .. } => source.code(),
+            Self::MissingPlanetaryData { .. } => 1401,
+        }
+    }
 }
 
 impl Almanac {
     /// Given the frame UID (or something that can be transformed into it), attempt to retrieve the full frame information, if that frame is loaded
+    ///
+    /// The planetary constants overridden with `override_gm` and `override_shape` take precedence over the loaded ones.
     pub fn frame_from_uid<U: Into<FrameUid>>(&self, uid: U) -> Result<Frame, PlanetaryDataError> {
         let uid = uid.into();
-        Ok(self
-            .planetary_data
-            .get_by_id(uid.ephemeris_id)
-            .context(PlanetaryDataSetSnafu {
-                action: "fetching frame by its UID via ephemeris_id",
-            })?
-            .to_frame(uid))
+        let frame = match self.planetary_data.get_ref_by_id(uid.ephemeris_id) {
+            Ok(data) => data.to_frame(uid),
+            Err(_) if self.is_overridden(uid.ephemeris_id) => Frame::from(uid),
+            Err(e) => {
+                return Err(e).context(PlanetaryDataSetSnafu {
+                    action: "fetching frame by its UID via ephemeris_id",
+                })
+            }
+        };
+        Ok(self.apply_planetary_overrides(frame))
+    }
+
+    /// Given the frame UID (or something that can be transformed into it), attempt to retrieve the full frame information
+    /// using the planetary constants valid at the provided epoch, if that frame is loaded.
+    pub fn frame_from_uid_at<U: Into<FrameUid>>(
+        &self,
+        uid: U,
+        epoch: Epoch,
+    ) -> Result<Frame, PlanetaryDataError> {
+        let uid = uid.into();
+        let frame = match self.planetary_data.get_ref_by_id(uid.ephemeris_id) {
+            Ok(data) => data.to_frame_at(uid, epoch),
+            Err(_) if self.is_overridden(uid.ephemeris_id) => Frame::from(uid),
+            Err(e) => {
+                return Err(e).context(PlanetaryDataSetSnafu {
+                    action: "fetching frame by its UID via ephemeris_id",
+                })
+            }
+        };
+        Ok(self.apply_planetary_overrides(frame))
+    }
+
+    /// Returns the speed of light in km/s of the constants registry, or its overridden value, used for all of the light time
+    /// computations of this Almanac.
+    pub(crate) fn speed_of_light_km_s(&self) -> f64 {
+        self.constant(SPEED_OF_LIGHT.name)
+            .unwrap_or(SPEED_OF_LIGHT.value)
     }
 
     /// Loads the provided planetary data into a clone of this original Almanac.
@@ -47,6 +96,107 @@ impl Almanac {
     }
 }
 
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Returns the value of the constant with the provided name (case insensitive) from the constants registry,
+    /// or its overridden value if it was overridden in this Almanac.
+    ///
+    /// The gravitational parameters (e.g. `GM_EARTH`) are also overridden by `override_gm`, cf. [Almanac::with_constant].
+    ///
+    /// :type name: str
+    /// :rtype: float
+    pub fn constant(&self, name: &str) -> AlmanacResult<f64> {
+        let gm_ids = gm_ids_from_name(name);
+        if let Some(mu_km3_s2) =
+            self.planetary_overrides
+                .iter()
+                .rev()
+                .find_map(|overlay| match overlay.value {
+                    OverriddenValue::Gm(mu_km3_s2) if gm_ids.contains(&overlay.id) => {
+                        Some(mu_km3_s2)
+                    }
+                    _ => None,
+                })
+        {
+            return Ok(mu_km3_s2);
+        }
+
+        if let Some(constant) = self
+            .constant_overrides
+            .iter()
+            .find(|constant| constant.name.eq_ignore_ascii_case(name))
+        {
+            return Ok(constant.value);
+        }
+
+        constant_from_name(name)
+            .map(|constant| constant.value)
+            .ok_or(AlmanacError::GenericError {
+                err: format!("no constant named {name} in the registry"),
+            })
+    }
+
+    /// Returns the radii of the tri-axial ellipsoid of the body of the provided frame, from the planetary data or its
+    /// override, such that algorithms need not know how the planetary constants are stored.
+    ///
+    /// :type frame: Frame
+    /// :rtype: Ellipsoid
+    pub fn body_radii(&self, frame: Frame) -> Result<Ellipsoid, PlanetaryDataError> {
+        let frame = self.frame_from_uid(frame)?;
+        frame.shape.ok_or(PlanetaryDataError::MissingPlanetaryData {
+            data: "shape",
+            frame: frame.into(),
+        })
+    }
+
+    /// Returns the gravitational parameter in km^3/s^2 of the body of the provided frame, from the planetary data or its
+    /// override.
+    ///
+    /// :type frame: Frame
+    /// :rtype: float
+    pub fn gm(&self, frame: Frame) -> Result<f64, PlanetaryDataError> {
+        let frame = self.frame_from_uid(frame)?;
+        frame
+            .mu_km3_s2
+            .ok_or(PlanetaryDataError::MissingPlanetaryData {
+                data: "gravitational parameter",
+                frame: frame.into(),
+            })
+    }
+
+    /// Returns a copy of this Almanac where the constant of the registry with the provided name (case insensitive) is
+    /// overridden with the provided value, in the same unit as the constant of the registry.
+    ///
+    /// The overridden speed of light is used in the light time corrections. Overriding a gravitational parameter (e.g.
+    /// `GM_EARTH`) is the same as calling `override_gm` for its bodies (e.g. both Mercury and its barycenter for `GM_MERCURY`),
+    /// so it takes precedence over that of the loaded planetary data and is listed in the `planetary_overrides`.
+    ///
+    /// :type name: str
+    /// :type value: float
+    /// :rtype: Almanac
+    pub fn with_constant(&self, name: &str, value: f64) -> AlmanacResult<Self> {
+        let mut constant = constant_from_name(name).ok_or(AlmanacError::GenericError {
+            err: format!("no constant named {name} in the registry"),
+        })?;
+
+        let gm_ids = gm_ids_from_name(name);
+        if !gm_ids.is_empty() {
+            return Ok(gm_ids
+                .into_iter()
+                .fold(self.clone(), |me, id| me.override_gm(id, value)));
+        }
+
+        constant.value = value;
+        constant.source = "override";
+
+        let mut me = self.clone();
+        me.constant_overrides
+            .retain(|other| other.name != constant.name);
+        me.constant_overrides.push(constant);
+        Ok(me)
+    }
+}
+
 #[derive(Tabled, Default)]
 struct PlanetaryRow {
     #[tabled(rename = "Name")]
@@ -134,3 +284,41 @@ impl PlanetaryDataSet {
         format!("{tbl}")
     }
 }
+
+#[cfg(test)]
+mod ut_planetary {
+    use super::{Almanac, PlanetaryDataError};
+    use crate::constants::frames::{EARTH_J2000, MOON_J2000};
+    use crate::errors::ErrorCode;
+    use crate::structure::planetocentric::ellipsoid::Ellipsoid;
+
+    #[test]
+    fn body_radii_and_gm() {
+        let almanac = Almanac::default().override_gm(399, 398_600.5);
+
+        assert_eq!(almanac.gm(EARTH_J2000).unwrap(), 398_600.5);
+        // The GMs of the constants registry may be overridden too
+        let registry_gm = Almanac::default()
+            .with_constant("GM_EARTH", 398_600.4)
+            .unwrap();
+        assert_eq!(registry_gm.gm(EARTH_J2000).unwrap(), 398_600.4);
+        assert!(registry_gm.gm(MOON_J2000).is_err());
+        // The GM of the Earth is overridden but not its shape
+        let err = almanac.body_radii(EARTH_J2000).unwrap_err();
+        assert!(matches!(
+            err,
+            PlanetaryDataError::MissingPlanetaryData { data: "shape", .. }
+        ));
+        assert_eq!(err.code(), 1401);
+        // The Moon is not defined at all
+        assert!(matches!(
+            almanac.gm(MOON_J2000),
+            Err(PlanetaryDataError::PlanetaryDataSet { .. })
+        ));
+
+        let almanac = almanac.override_shape(399, Ellipsoid::from_spheroid(6_378.137, 6_356.752));
+        let radii = almanac.body_radii(EARTH_J2000).unwrap();
+        assert_eq!(radii.semi_major_equatorial_radius_km, 6_378.137);
+        assert_eq!(radii.polar_radius_km, 6_356.752);
+    }
+}
@@ -0,0 +1,212 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::Epoch;
+
+use super::Almanac;
+use crate::errors::AlmanacResult;
+use crate::naif::daf::{DafDataType, NAIFSummaryRecord, DAF};
+use crate::prelude::Frame;
+use crate::NaifId;
+
+/// A segment of a loaded SPK or BPC kernel which answered one link of a query, e.g. to find out whether an unexpected kernel
+/// shadows the intended one, cf. [Almanac::query_provenance].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SegmentProvenance {
+    /// Kind of kernel, `SPK` or `BPC`
+    pub kind: String,
+    /// Path of the kernel, if it was loaded from a file
+    pub path: Option<String>,
+    pub crc32: u32,
+    /// Index of the segment in its kernel
+    pub index: usize,
+    /// Name of the segment
+    pub name: String,
+    /// Target ID of an SPK segment, or frame ID of a BPC segment
+    pub id: NaifId,
+    pub data_type: Option<DafDataType>,
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+}
+
+impl SegmentProvenance {
+    /// Builds the provenance of the segment number `idx` of this kernel of the Almanac.
+    pub(crate) fn new<R: NAIFSummaryRecord>(
+        almanac: &Almanac,
+        kind: &str,
+        daf: &DAF<R>,
+        idx: usize,
+        summary: &R,
+    ) -> Self {
+        let name = daf.nth_summary_name(idx).unwrap_or_default();
+        // Lazily loaded kernels compute their checksum here, on the first query of their provenance.
+        let crc32 = daf.checksum();
+
+        Self {
+            kind: kind.to_string(),
+            path: daf.source_path().map(str::to_string).or_else(|| {
+                almanac
+                    .load_timings
+                    .iter()
+                    .rev()
+                    .find(|timing| timing.crc32 == Some(crc32))
+                    .map(|timing| timing.path.clone())
+            }),
+            crc32,
+            index: idx,
+            name,
+            id: summary.id(),
+            data_type: summary.data_type().ok(),
+            start_epoch: summary.start_epoch(),
+            end_epoch: summary.end_epoch(),
+        }
+    }
+}
+
+impl fmt::Display for SegmentProvenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} from {} (0x{:08x}) segment #{} `{}` ({}) from {} to {}",
+            self.kind,
+            self.id,
+            self.path.as_deref().unwrap_or("memory"),
+            self.crc32,
+            self.index,
+            self.name,
+            self.data_type
+                .map(|data_type| data_type.to_string())
+                .unwrap_or_else(|| "unknown data type".to_string()),
+            self.start_epoch,
+            self.end_epoch
+        )
+    }
+}
+
+impl Almanac {
+    /// Returns the segments of the loaded SPK and BPC kernels which answer the transformation of the target frame into the
+    /// observer frame at this epoch, i.e. the translations from each frame up to the root of the ephemeris tree, followed by
+    /// the rotations from each frame up to J2000. The links computed from other sources (e.g. providers or planetary data) are
+    /// not listed, cf. [Almanac::accuracy_report].
+    ///
+    /// The query is computed first, so this returns its error if it fails.
+    pub fn query_provenance(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        epoch: Epoch,
+    ) -> AlmanacResult<Vec<SegmentProvenance>> {
+        let report = self.accuracy_report(target_frame, observer_frame, epoch)?;
+
+        Ok(report
+            .translation_sources
+            .into_iter()
+            .chain(report.rotation_sources)
+            .filter_map(|source| source.segment)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod ut_provenance {
+    use crate::constants::frames::{EARTH_J2000, MOON_J2000};
+    use crate::naif::daf::datatypes::HermiteSetType13;
+    use crate::naif::daf::{DafDataType, NAIFDataSet};
+    use crate::naif::spk::summary::SPKSummaryRecord;
+    use crate::naif::{SPKSegment, SPK};
+    use crate::prelude::{Almanac, Frame};
+    use hifitime::Epoch;
+
+    #[test]
+    fn moon_wrt_earth_provenance() {
+        let almanac = Almanac::new("../data/de440s.bsp").unwrap();
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+
+        let segments = almanac
+            .query_provenance(MOON_J2000, EARTH_J2000, epoch)
+            .unwrap();
+        // The Moon with respect to the Earth-Moon barycenter, the barycenter with respect to the SSB, and the Earth.
+        let ids = segments
+            .iter()
+            .map(|segment| segment.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![301, 3, 399]);
+
+        for segment in &segments {
+            assert_eq!(segment.kind, "SPK");
+            assert!(segment.path.as_ref().unwrap().ends_with("de440s.bsp"));
+            assert_eq!(segment.data_type, Some(DafDataType::Type2ChebyshevTriplet));
+            assert!(segment.start_epoch <= epoch && epoch <= segment.end_epoch);
+            assert!(format!("{segment}").contains("de440s.bsp"));
+        }
+
+        // Kernels loaded from memory have no path.
+        let from_bytes = Almanac::default()
+            .with_spk(almanac.spk_data[0].clone().unwrap())
+            .unwrap();
+        let segments = from_bytes
+            .query_provenance(MOON_J2000, EARTH_J2000, epoch)
+            .unwrap();
+        assert!(segments.iter().all(|segment| segment.path.is_none()));
+    }
+
+    #[test]
+    fn provenance_past_first_summary_record() {
+        // Thirty ten second segments, such that the last five are in the second summary record
+        let segments = (0..30)
+            .map(|sno| {
+                let start_et_s = 10.0 * sno as f64;
+                let end_et_s = start_et_s + 10.0;
+                let state_data = [
+                    start_et_s, 0.0, 0.0, 1.0, 0.0, 0.0, end_et_s, 0.0, 0.0, 1.0, 0.0, 0.0,
+                ];
+                let epoch_data = [start_et_s, end_et_s];
+                let hermite = HermiteSetType13 {
+                    samples: 2,
+                    num_records: 2,
+                    state_data: &state_data,
+                    epoch_data: &epoch_data,
+                    epoch_registry: &[],
+                };
+
+                SPKSegment {
+                    name: format!("segment {sno}"),
+                    summary: SPKSummaryRecord {
+                        start_epoch_et_s: start_et_s,
+                        end_epoch_et_s: end_et_s,
+                        target_id: -10,
+                        center_id: 399,
+                        frame_id: 1,
+                        data_type_i: DafDataType::Type13HermiteUnequalStep as i32,
+                        ..Default::default()
+                    },
+                    data: hermite.to_f64_daf_vec().unwrap(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let almanac = Almanac::default()
+            .with_spk(SPK::from_segments("ANISE TEST", &segments).unwrap())
+            .unwrap();
+
+        let segments = almanac
+            .query_provenance(
+                Frame::from_ephem_j2000(-10),
+                EARTH_J2000,
+                Epoch::from_et_seconds(275.0),
+            )
+            .unwrap();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].index, 27);
+        assert_eq!(segments[0].name, "segment 27");
+    }
+}
@@ -8,26 +8,826 @@
  * Documentation: https://nyxspace.com/
  */
 
+use core::str::FromStr;
+
 use super::{
-    planetary::{PlanetaryDataError, PlanetaryDataSetSnafu},
+    overlay::{OverriddenValue, PlanetaryOverride},
+    parallel::FileLoadTiming,
+    planetary::PlanetaryDataError,
     Almanac,
 };
+use crate::astro::Aberration;
+use crate::compare::Comparator;
+use crate::errors::{AlmanacError, AlmanacResult};
+use crate::math::Vector3;
+use crate::naif::daf::{DAFError, NAIFSummaryRecord, DAF};
+use crate::naif::kpl::lsk::LeapSecondsKernel;
+use crate::naif::kpl::pool::PoolValue;
+use crate::naif::kpl::sclk::SpacecraftClock;
+use crate::orientations::{DirectionVector, FrameAxis, NutationTruncation, TwoVectorFrame};
+use crate::prelude::Epoch;
 use crate::prelude::Frame;
+use crate::python_config::ConfiguredEpoch;
+use crate::structure::dataset::{DataSet, DataSetError, DataSetT};
+use crate::structure::planetocentric::ellipsoid::Ellipsoid;
+use crate::NaifId;
+use bytes::Bytes;
+use der::Encode;
+use hifitime::{Duration, TimeScale};
 use pyo3::prelude::*;
-use snafu::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyType};
+
+pyo3::import_exception!(pickle, PicklingError);
+pyo3::import_exception!(pickle, UnpicklingError);
+
+/// Duration as its centuries and nanoseconds, such that it is pickled exactly.
+type PickledDuration = (i16, u64);
+
+/// Spacecraft clock: ID, time scale, moduli, offsets, delimiter, partitions, and coefficients.
+type PickledClock = (
+    i32,
+    String,
+    Vec<f64>,
+    Vec<f64>,
+    char,
+    Vec<(f64, f64)>,
+    Vec<[f64; 3]>,
+);
+
+/// Kernel pool variable: name, and either its numbers or its strings.
+type PickledPoolValue = (String, Option<Vec<f64>>, Option<Vec<String>>);
+
+/// Leap seconds kernel: DELTA_AT with TAI epochs, DELTA_T_A, K, EB, and M.
+type PickledLsk = (Vec<(f64, PickledDuration)>, f64, f64, f64, [f64; 2]);
+
+/// Direction of a two-vector frame: kind, observer, target, and constant vector.
+type PickledDirection = (u8, NaifId, NaifId, [f64; 3]);
+
+/// Two-vector frame: ID, name, primary axis and direction, secondary axis and direction, and aberration correction.
+type PickledTwoVectorFrame = (
+    NaifId,
+    String,
+    i8,
+    PickledDirection,
+    i8,
+    PickledDirection,
+    Option<Aberration>,
+);
+
+/// Overridden planetary constant: either a GM or a shape.
+type PickledOverriddenValue = (Option<f64>, Option<Ellipsoid>);
+
+/// Planetary override: ID, value, replaced value, and whether the replaced value is that of the constants registry.
+type PickledOverride = (
+    NaifId,
+    PickledOverriddenValue,
+    Option<PickledOverriddenValue>,
+    bool,
+);
+
+/// Load timing: path, size, CRC32, and read and parse durations.
+type PickledTiming = (String, usize, Option<u32>, PickledDuration, PickledDuration);
+
+/// State of an Almanac which is not in the buffers of its kernels and data sets: load checks, frame bias, boundary smoothing,
+/// number of terms of the nutation truncation, spacecraft clocks, kernel pool, leap seconds, two-vector frames, planetary
+/// overrides, and load timings.
+type PickledState = (
+    bool,
+    bool,
+    Option<PickledDuration>,
+    Option<usize>,
+    Vec<PickledClock>,
+    Vec<PickledPoolValue>,
+    Option<PickledLsk>,
+    Vec<PickledTwoVectorFrame>,
+    Vec<PickledOverride>,
+    Vec<PickledTiming>,
+);
 
 #[pymethods]
 impl Almanac {
-    /// Returns the frame information (gravitational param, shape) as defined in this Almanac from an empty frame
+    /// Returns the frame information (gravitational param, shape) as defined in this Almanac from an empty frame, including
+    /// the overridden planetary constants, cf. [Almanac::frame_from_uid].
     /// :type uid: Frame
     /// :rtype: Frame
     pub fn frame_info(&self, uid: Frame) -> Result<Frame, PlanetaryDataError> {
-        Ok(self
-            .planetary_data
-            .get_by_id(uid.ephemeris_id)
-            .context(PlanetaryDataSetSnafu {
-                action: "fetching frame by its UID via ephemeris_id",
-            })?
-            .to_frame(uid.into()))
+        self.frame_from_uid(uid)
+    }
+
+    /// Rebuilds an Almanac from the buffers of its kernels and data sets, in the order they were loaded.
+    /// This is used to unpickle an Almanac, e.g. when it is shipped to multiprocessing or Dask workers.
+    ///
+    /// :type buffers: typing.List
+    /// :type spice_compat: bool
+    /// :type constant_overrides: typing.List
+    /// :type body_names: typing.List
+    /// :type state: typing.Tuple
+    /// :rtype: Almanac
+    #[staticmethod]
+    fn from_buffers(
+        buffers: Vec<Vec<u8>>,
+        spice_compat: bool,
+        constant_overrides: Vec<(String, f64)>,
+        body_names: Vec<(String, NaifId)>,
+        state: PickledState,
+    ) -> PyResult<Self> {
+        let mut me = Self::default().with_spice_compat(spice_compat);
+        for buffer in buffers {
+            me = me.load_from_bytes(Bytes::from(buffer))?;
+        }
+        for (name, value) in constant_overrides {
+            me = me.with_constant(&name, value)?;
+        }
+        for (name, id) in body_names {
+            me = me.register_body_name(&name, id);
+        }
+        me.set_pickled_state(state)?;
+        Ok(me)
+    }
+
+    /// Pickles this Almanac as the bytes of all of its loaded kernels and data sets, followed by the rest of its state, so the
+    /// unpickled Almanac does not need access to the original files. The query transcript and the query cache, if any, are not
+    /// pickled.
+    ///
+    /// Raises a `pickle.PicklingError` if this Almanac has custom ephemeris or orientation providers, which cannot be pickled.
+    #[allow(clippy::type_complexity)]
+    fn __reduce__<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<(
+        Bound<'py, PyAny>,
+        (
+            Vec<Bound<'py, PyBytes>>,
+            bool,
+            Vec<(String, f64)>,
+            Vec<(String, NaifId)>,
+            PickledState,
+        ),
+    )> {
+        if !self.ephemeris_providers.is_empty() || !self.orientation_providers.is_empty() {
+            return Err(PicklingError::new_err(
+                "cannot pickle an Almanac with custom ephemeris or orientation providers",
+            ));
+        }
+
+        let buffers = self
+            .buffers()?
+            .iter()
+            .map(|buffer| PyBytes::new(py, buffer))
+            .collect();
+
+        let constant_overrides = self
+            .constant_overrides
+            .iter()
+            .map(|constant| (constant.name.to_string(), constant.value))
+            .collect();
+
+        Ok((
+            py.get_type::<Self>().getattr("from_buffers")?,
+            (
+                buffers,
+                self.spice_compat,
+                constant_overrides,
+                self.body_names.clone(),
+                self.pickled_state(),
+            ),
+        ))
+    }
+
+    /// Returns one dictionary per frame defined in the planetary data and in the Euler parameter data of this Almanac, with
+    /// the keys `kind` (`planetary` or `euler_parameter`), `id`, `name`, `parent_id`, `mu_km3_s2`, `equatorial_radius_km`,
+    /// and `polar_radius_km`, where the data which does not apply to the frame is None.
+    /// Use `pandas.DataFrame(almanac.list_frames())` to explore them.
+    ///
+    /// :rtype: typing.List
+    fn list_frames<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let mut rows = Vec::new();
+
+        for (id, name, data) in dataset_entries(&self.planetary_data)? {
+            let row = PyDict::new(py);
+            row.set_item("kind", "planetary")?;
+            row.set_item("id", id)?;
+            row.set_item("name", name)?;
+            row.set_item("parent_id", data.parent_id)?;
+            row.set_item("mu_km3_s2", data.mu_km3_s2)?;
+            row.set_item(
+                "equatorial_radius_km",
+                data.shape
+                    .map(|shape| shape.semi_major_equatorial_radius_km),
+            )?;
+            row.set_item(
+                "polar_radius_km",
+                data.shape.map(|shape| shape.polar_radius_km),
+            )?;
+            rows.push(row);
+        }
+
+        for (id, name, data) in dataset_entries(&self.euler_param_data)? {
+            let row = PyDict::new(py);
+            row.set_item("kind", "euler_parameter")?;
+            row.set_item("id", id)?;
+            row.set_item("name", name)?;
+            row.set_item("parent_id", data.to)?;
+            row.set_item("mu_km3_s2", None::<f64>)?;
+            row.set_item("equatorial_radius_km", None::<f64>)?;
+            row.set_item("polar_radius_km", None::<f64>)?;
+            rows.push(row);
+        }
+
+        Ok(rows)
+    }
+
+    /// Returns one dictionary per segment of the loaded SPK and BPC kernels, in the order in which they are searched (i.e.
+    /// the last loaded kernel first), with the keys `kind` (`SPK` or `BPC`), `kernel` (index in that order), `segment`
+    /// (segment name), `id` (target or frame ID), `name` (body name, if known), `center_id` (center of an SPK segment or
+    /// inertial frame of a BPC segment), `frame_id` (orientation of an SPK segment, or the frame of a BPC segment),
+    /// `data_type`, `start_epoch`, and `end_epoch`.
+    /// Use `pandas.DataFrame(almanac.list_objects())` to explore them.
+    ///
+    /// :rtype: typing.List
+    fn list_objects<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let mut rows = Vec::new();
+
+        for (kernel, spk) in self
+            .spk_data
+            .iter()
+            .take(self.num_loaded_spk())
+            .rev()
+            .flatten()
+            .enumerate()
+        {
+            for (name, summary) in named_summaries(spk)? {
+                let row = self.segment_row(py, "SPK", kernel, name, summary)?;
+                row.set_item("center_id", summary.center_id)?;
+                row.set_item("frame_id", summary.frame_id)?;
+                rows.push(row);
+            }
+        }
+
+        for (kernel, bpc) in self
+            .bpc_data
+            .iter()
+            .take(self.num_loaded_bpc())
+            .rev()
+            .flatten()
+            .enumerate()
+        {
+            for (name, summary) in named_summaries(bpc)? {
+                let row = self.segment_row(py, "BPC", kernel, name, summary)?;
+                row.set_item("center_id", summary.inertial_frame_id)?;
+                row.set_item("frame_id", summary.frame_id)?;
+                rows.push(row);
+            }
+        }
+
+        Ok(rows)
+    }
+
+    /// Returns one dictionary per segment of the loaded SPK and BPC kernels which answers the transformation of the target
+    /// frame into the observer frame at this epoch, with the keys `kind` (`SPK` or `BPC`), `path` (path of the kernel, if it
+    /// was loaded from a file), `crc32` (of the kernel), `segment_index`, `segment` (segment name), `id` (target or frame ID),
+    /// `name` (body name, if known), `data_type`, `start_epoch`, and `end_epoch`.
+    /// Use it to find out whether an unexpected kernel shadows the intended one.
+    ///
+    /// :type target_frame: Frame
+    /// :type observer_frame: Frame
+    /// :type epoch: Epoch
+    /// :rtype: typing.List
+    #[pyo3(name = "query_provenance")]
+    fn py_query_provenance<'py>(
+        &self,
+        py: Python<'py>,
+        target_frame: Frame,
+        observer_frame: Frame,
+        epoch: ConfiguredEpoch,
+    ) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let mut rows = Vec::new();
+        for segment in self.query_provenance(target_frame, observer_frame, epoch.0)? {
+            let row = PyDict::new(py);
+            row.set_item("kind", &segment.kind)?;
+            row.set_item("path", &segment.path)?;
+            row.set_item("crc32", segment.crc32)?;
+            row.set_item("segment_index", segment.index)?;
+            row.set_item("segment", &segment.name)?;
+            row.set_item("id", segment.id)?;
+            row.set_item("name", self.body_name(segment.id).ok())?;
+            row.set_item(
+                "data_type",
+                segment.data_type.map(|data_type| data_type.to_string()),
+            )?;
+            row.set_item("start_epoch", ConfiguredEpoch(segment.start_epoch))?;
+            row.set_item("end_epoch", ConfiguredEpoch(segment.end_epoch))?;
+            rows.push(row);
+        }
+        Ok(rows)
+    }
+
+    /// Returns one dictionary per continuous interval of coverage of each ID in the loaded SPK and BPC kernels, such that
+    /// gaps in the coverage appear as separate intervals, with the keys `kind` (`SPK` or `BPC`), `id`, `name` (body name, if
+    /// known), `start_epoch`, `end_epoch`, and `duration`.
+    /// Use `pandas.DataFrame(almanac.coverage())` to explore them.
+    ///
+    /// :rtype: typing.List
+    fn coverage<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let mut rows = Vec::new();
+
+        if self.num_loaded_spk() > 0 {
+            let mut ids = self.spk_domains()?.into_keys().collect::<Vec<_>>();
+            ids.sort();
+            for id in ids {
+                for (start, end) in self.spk_coverage(id)?.intervals() {
+                    rows.push(self.coverage_row(py, "SPK", id, *start, *end)?);
+                }
+            }
+        }
+
+        if self.num_loaded_bpc() > 0 {
+            let mut ids = self.bpc_domains()?.into_keys().collect::<Vec<_>>();
+            ids.sort();
+            for id in ids {
+                for (start, end) in self.bpc_coverage(id)?.intervals() {
+                    rows.push(self.coverage_row(py, "BPC", id, *start, *end)?);
+                }
+            }
+        }
+
+        Ok(rows)
+    }
+
+    /// Returns one dictionary per planetary constant which differs between the planetary data of this Almanac (the
+    /// reference) and the other Almanac (the candidate), with the keys `id`, `name` (body name, if known), `constant` (e.g.
+    /// `mu_km3_s2`, `polar_radius_km`, or `pole_declination.rate_deg`), `reference`, `candidate`, and `difference` (candidate
+    /// minus reference), where the values which are not defined are None.
+    /// Use `pandas.DataFrame(almanac.diff_planetary_data(other))` to review them.
+    ///
+    /// :type other: Almanac
+    /// :rtype: typing.List
+    fn diff_planetary_data<'py>(
+        &self,
+        py: Python<'py>,
+        other: &Self,
+    ) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let mut rows = Vec::new();
+
+        for diff in Comparator::new(self.clone(), other.clone()).planetary_differences()? {
+            let row = PyDict::new(py);
+            row.set_item("id", diff.id)?;
+            row.set_item("name", self.body_name(diff.id).ok())?;
+            row.set_item("constant", diff.constant)?;
+            row.set_item("reference", diff.reference)?;
+            row.set_item("candidate", diff.candidate)?;
+            row.set_item("difference", diff.difference())?;
+            rows.push(row);
+        }
+
+        Ok(rows)
+    }
+
+    /// Returns one dictionary per pair of target and observer frames and per epoch where the states computed by `transform`
+    /// with this Almanac (the reference) and with the other Almanac (the candidate) can both be computed, with the keys
+    /// `target_frame`, `observer_frame`, `epoch`, `dx_km`, `dy_km`, `dz_km`, `dvx_km_s`, `dvy_km_s`, `dvz_km_s` (candidate
+    /// minus reference), `position_km` and `velocity_km_s` (norms of the differences).
+    /// Use `pandas.DataFrame(almanac.diff_states(other, pairs, epochs))` to review them.
+    ///
+    /// :type other: Almanac
+    /// :type pairs: typing.List
+    /// :type epochs: typing.List
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: typing.List
+    #[pyo3(signature = (other, pairs, epochs, ab_corr=None))]
+    fn diff_states<'py>(
+        &self,
+        py: Python<'py>,
+        other: &Self,
+        pairs: Vec<(Frame, Frame)>,
+        epochs: Vec<ConfiguredEpoch>,
+        ab_corr: Option<Aberration>,
+    ) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let mut comparator = Comparator::new(self.clone(), other.clone())
+            .with_epochs(epochs.into_iter().map(|epoch| epoch.0))
+            .with_aberration(ab_corr);
+        for (target_frame, observer_frame) in pairs {
+            comparator = comparator.with_pair(target_frame, observer_frame);
+        }
+
+        let mut rows = Vec::new();
+
+        for diff in comparator.state_differences()? {
+            let row = PyDict::new(py);
+            row.set_item("target_frame", format!("{}", diff.target_frame))?;
+            row.set_item("observer_frame", format!("{}", diff.observer_frame))?;
+            row.set_item("epoch", ConfiguredEpoch(diff.epoch))?;
+            row.set_item("dx_km", diff.radius_km.x)?;
+            row.set_item("dy_km", diff.radius_km.y)?;
+            row.set_item("dz_km", diff.radius_km.z)?;
+            row.set_item("dvx_km_s", diff.velocity_km_s.x)?;
+            row.set_item("dvy_km_s", diff.velocity_km_s.y)?;
+            row.set_item("dvz_km_s", diff.velocity_km_s.z)?;
+            row.set_item("position_km", diff.radius_km.norm())?;
+            row.set_item("velocity_km_s", diff.velocity_km_s.norm())?;
+            rows.push(row);
+        }
+
+        Ok(rows)
     }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Nothing to release: all of the data is freed when the last reference to this Almanac is dropped.
+    /// Exceptions raised in the `with` block are never suppressed.
+    fn __exit__(
+        &self,
+        _exc_type: Option<Bound<'_, PyType>>,
+        _exc_value: Option<Bound<'_, PyAny>>,
+        _traceback: Option<Bound<'_, PyAny>>,
+    ) -> bool {
+        false
+    }
+}
+
+impl Almanac {
+    /// Returns the bytes of the loaded SPK and BPC kernels, followed by the encoded data sets, such that loading them in
+    /// that order rebuilds this Almanac.
+    fn buffers(&self) -> AlmanacResult<Vec<Bytes>> {
+        let mut buffers = Vec::new();
+
+        for spk in self.spk_data.iter().take(self.num_loaded_spk()).flatten() {
+            buffers.push(spk.bytes.clone());
+        }
+        for bpc in self.bpc_data.iter().take(self.num_loaded_bpc()).flatten() {
+            buffers.push(bpc.bytes.clone());
+        }
+
+        for buffer in [
+            encode_dataset(&self.planetary_data)?,
+            encode_dataset(&self.spacecraft_data)?,
+            encode_dataset(&self.euler_param_data)?,
+            encode_dataset(&self.maneuver_data)?,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            buffers.push(buffer);
+        }
+
+        Ok(buffers)
+    }
+
+    /// Returns the state of this Almanac which is not in its buffers, cf. [Almanac::buffers].
+    fn pickled_state(&self) -> PickledState {
+        let pickle_duration = |duration: Duration| duration.to_parts();
+
+        let clocks = self
+            .sclk_data
+            .iter()
+            .map(|clock| {
+                (
+                    clock.clock_id,
+                    clock.time_scale.to_string(),
+                    clock.moduli.clone(),
+                    clock.offsets.clone(),
+                    clock.delimiter,
+                    clock.partitions.clone(),
+                    clock.coefficients.clone(),
+                )
+            })
+            .collect();
+
+        let pool = self
+            .kernel_pool
+            .names()
+            .into_iter()
+            .filter_map(|name| match self.kernel_pool.get(name)? {
+                PoolValue::Numeric(numbers) => {
+                    Some((name.to_string(), Some(numbers.clone()), None))
+                }
+                PoolValue::Text(strings) => Some((name.to_string(), None, Some(strings.clone()))),
+            })
+            .collect();
+
+        let lsk = self.leap_seconds.as_ref().map(|lsk| {
+            (
+                lsk.delta_at
+                    .iter()
+                    .map(|(delta_at, epoch)| (*delta_at, pickle_duration(epoch.to_tai_duration())))
+                    .collect(),
+                lsk.delta_t_a,
+                lsk.k,
+                lsk.eb,
+                lsk.m,
+            )
+        });
+
+        let pickle_axis = |axis: FrameAxis| ((axis.index() + 1) as f64 * axis.sign()) as i8;
+        let pickle_direction = |direction: DirectionVector| match direction {
+            DirectionVector::Position {
+                observer_id,
+                target_id,
+            } => (0, observer_id, target_id, [0.0; 3]),
+            DirectionVector::Velocity {
+                observer_id,
+                target_id,
+            } => (1, observer_id, target_id, [0.0; 3]),
+            DirectionVector::OrbitNormal {
+                observer_id,
+                target_id,
+            } => (2, observer_id, target_id, [0.0; 3]),
+            DirectionVector::Constant { vector } => (3, 0, 0, [vector.x, vector.y, vector.z]),
+        };
+        let two_vector_frames = self
+            .two_vector_frames
+            .iter()
+            .map(|frame| {
+                (
+                    frame.orientation_id,
+                    frame.name.clone(),
+                    pickle_axis(frame.primary_axis),
+                    pickle_direction(frame.primary),
+                    pickle_axis(frame.secondary_axis),
+                    pickle_direction(frame.secondary),
+                    frame.ab_corr,
+                )
+            })
+            .collect();
+
+        let pickle_value = |value: OverriddenValue| match value {
+            OverriddenValue::Gm(mu_km3_s2) => (Some(mu_km3_s2), None),
+            OverriddenValue::Shape(shape) => (None, Some(shape)),
+        };
+        let overrides = self
+            .planetary_overrides
+            .iter()
+            .map(|item| {
+                (
+                    item.id,
+                    pickle_value(item.value),
+                    item.replaced.map(pickle_value),
+                    item.replaced_from_registry,
+                )
+            })
+            .collect();
+
+        let timings = self
+            .load_timings
+            .iter()
+            .map(|timing| {
+                (
+                    timing.path.clone(),
+                    timing.size_bytes,
+                    timing.crc32,
+                    pickle_duration(timing.read),
+                    pickle_duration(timing.parse),
+                )
+            })
+            .collect();
+
+        (
+            self.load_checks,
+            self.frame_bias,
+            self.boundary_smoothing.map(pickle_duration),
+            self.nutation_truncation
+                .map(|truncation| truncation.num_terms()),
+            clocks,
+            pool,
+            lsk,
+            two_vector_frames,
+            overrides,
+            timings,
+        )
+    }
+
+    /// Restores the state of this Almanac which is not in its buffers, cf. [Almanac::pickled_state].
+    fn set_pickled_state(&mut self, state: PickledState) -> PyResult<()> {
+        let (
+            load_checks,
+            frame_bias,
+            boundary_smoothing,
+            nutation_terms,
+            clocks,
+            pool,
+            lsk,
+            two_vector_frames,
+            overrides,
+            timings,
+        ) = state;
+        let unpickle_err = |err: String| UnpicklingError::new_err(format!("invalid {err}"));
+        let unpickle_duration = |(centuries, nanoseconds): PickledDuration| {
+            Duration::from_parts(centuries, nanoseconds)
+        };
+
+        self.load_checks = load_checks;
+        self.frame_bias = frame_bias;
+        self.boundary_smoothing = boundary_smoothing.map(unpickle_duration);
+        self.nutation_truncation = nutation_terms
+            .map(|num_terms| {
+                [
+                    NutationTruncation::Eighteen,
+                    NutationTruncation::Ten,
+                    NutationTruncation::Four,
+                ]
+                .into_iter()
+                .find(|truncation| truncation.num_terms() == num_terms)
+                .ok_or_else(|| unpickle_err(format!("nutation truncation of {num_terms} terms")))
+            })
+            .transpose()?;
+
+        for (clock_id, time_scale, moduli, offsets, delimiter, partitions, coefficients) in clocks {
+            self.sclk_data.push(SpacecraftClock {
+                clock_id,
+                time_scale: TimeScale::from_str(&time_scale)
+                    .map_err(|e| unpickle_err(format!("time scale {time_scale}: {e}")))?,
+                moduli,
+                offsets,
+                delimiter,
+                partitions,
+                coefficients,
+            });
+        }
+
+        for (name, numbers, strings) in pool {
+            let value = match (numbers, strings) {
+                (Some(numbers), None) => PoolValue::Numeric(numbers),
+                (None, Some(strings)) => PoolValue::Text(strings),
+                _ => return Err(unpickle_err(format!("kernel pool variable {name}"))),
+            };
+            self.kernel_pool.set(&name, value);
+        }
+
+        self.leap_seconds = lsk.map(|(delta_at, delta_t_a, k, eb, m)| LeapSecondsKernel {
+            delta_at: delta_at
+                .into_iter()
+                .map(|(delta_at, tai)| (delta_at, Epoch::from_tai_duration(unpickle_duration(tai))))
+                .collect(),
+            delta_t_a,
+            k,
+            eb,
+            m,
+        });
+
+        let unpickle_axis = |axis: i8| match axis {
+            1 => Ok(FrameAxis::PlusX),
+            2 => Ok(FrameAxis::PlusY),
+            3 => Ok(FrameAxis::PlusZ),
+            -1 => Ok(FrameAxis::MinusX),
+            -2 => Ok(FrameAxis::MinusY),
+            -3 => Ok(FrameAxis::MinusZ),
+            _ => Err(unpickle_err(format!("frame axis {axis}"))),
+        };
+        let unpickle_direction =
+            |(kind, observer_id, target_id, vector): PickledDirection| match kind {
+                0 => Ok(DirectionVector::Position {
+                    observer_id,
+                    target_id,
+                }),
+                1 => Ok(DirectionVector::Velocity {
+                    observer_id,
+                    target_id,
+                }),
+                2 => Ok(DirectionVector::OrbitNormal {
+                    observer_id,
+                    target_id,
+                }),
+                3 => Ok(DirectionVector::Constant {
+                    vector: Vector3::from(vector),
+                }),
+                _ => Err(unpickle_err(format!("direction kind {kind}"))),
+            };
+        for (orientation_id, name, primary_axis, primary, secondary_axis, secondary, ab_corr) in
+            two_vector_frames
+        {
+            self.two_vector_frames.push(TwoVectorFrame {
+                orientation_id,
+                name,
+                primary_axis: unpickle_axis(primary_axis)?,
+                primary: unpickle_direction(primary)?,
+                secondary_axis: unpickle_axis(secondary_axis)?,
+                secondary: unpickle_direction(secondary)?,
+                ab_corr,
+            });
+        }
+
+        let unpickle_value = |value: PickledOverriddenValue| match value {
+            (Some(mu_km3_s2), None) => Ok(OverriddenValue::Gm(mu_km3_s2)),
+            (None, Some(shape)) => Ok(OverriddenValue::Shape(shape)),
+            _ => Err(unpickle_err("planetary override".to_string())),
+        };
+        for (id, value, replaced, replaced_from_registry) in overrides {
+            self.planetary_overrides.push(PlanetaryOverride {
+                id,
+                value: unpickle_value(value)?,
+                replaced: replaced.map(unpickle_value).transpose()?,
+                replaced_from_registry,
+            });
+        }
+
+        self.load_timings = timings
+            .into_iter()
+            .map(|(path, size_bytes, crc32, read, parse)| FileLoadTiming {
+                path,
+                size_bytes,
+                crc32,
+                read: unpickle_duration(read),
+                parse: unpickle_duration(parse),
+            })
+            .collect();
+
+        Ok(())
+    }
+
+    /// Returns the columns shared by the SPK and BPC rows of [Almanac::list_objects].
+    fn segment_row<'py, R: NAIFSummaryRecord>(
+        &self,
+        py: Python<'py>,
+        kind: &str,
+        kernel: usize,
+        segment: String,
+        summary: &R,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let row = PyDict::new(py);
+        row.set_item("kind", kind)?;
+        row.set_item("kernel", kernel)?;
+        row.set_item("segment", segment)?;
+        row.set_item("id", summary.id())?;
+        row.set_item("name", self.body_name(summary.id()).ok())?;
+        row.set_item(
+            "data_type",
+            summary
+                .data_type()
+                .map(|data_type| data_type.to_string())
+                .ok(),
+        )?;
+        row.set_item("start_epoch", ConfiguredEpoch(summary.start_epoch()))?;
+        row.set_item("end_epoch", ConfiguredEpoch(summary.end_epoch()))?;
+        Ok(row)
+    }
+
+    /// Returns a row of [Almanac::coverage].
+    fn coverage_row<'py>(
+        &self,
+        py: Python<'py>,
+        kind: &str,
+        id: NaifId,
+        start: Epoch,
+        end: Epoch,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let row = PyDict::new(py);
+        row.set_item("kind", kind)?;
+        row.set_item("id", id)?;
+        row.set_item("name", self.body_name(id).ok())?;
+        row.set_item("start_epoch", ConfiguredEpoch(start))?;
+        row.set_item("end_epoch", ConfiguredEpoch(end))?;
+        row.set_item("duration", end - start)?;
+        Ok(row)
+    }
+}
+
+/// Encodes the data set, unless it is empty.
+fn encode_dataset<T: DataSetT, const ENTRIES: usize>(
+    dataset: &DataSet<T, ENTRIES>,
+) -> AlmanacResult<Option<Bytes>> {
+    if dataset.data.is_empty() {
+        return Ok(None);
+    }
+
+    let mut buf = vec![];
+    dataset
+        .encode_to_vec(&mut buf)
+        .map_err(|err| AlmanacError::GenericError {
+            err: format!("encoding data set: {err}"),
+        })?;
+    Ok(Some(Bytes::from(buf)))
+}
+
+/// Returns the ID, name, and data of each entry of the data set, sorted by ID.
+fn dataset_entries<T: DataSetT, const ENTRIES: usize>(
+    dataset: &DataSet<T, ENTRIES>,
+) -> Result<Vec<(Option<NaifId>, Option<String>, T)>, DataSetError> {
+    let mut entries = Vec::new();
+    for (id, name) in dataset.lut.entries().values() {
+        let data = match (id, name) {
+            (Some(id), _) => dataset.get_by_id(*id)?,
+            (None, Some(name)) => dataset.get_by_name(name)?,
+            (None, None) => continue,
+        };
+        entries.push((*id, name.as_ref().map(|name| name.to_string()), data));
+    }
+    entries.sort_by_key(|(id, _, _)| id.unwrap_or(0));
+    Ok(entries)
+}
+
+/// Returns the non empty data summaries of the DAF with their segment names.
+fn named_summaries<R: NAIFSummaryRecord>(daf: &DAF<R>) -> AlmanacResult<Vec<(String, &R)>> {
+    let to_err = |err: DAFError| AlmanacError::GenericError {
+        err: format!("listing {} segments: {err}", R::NAME),
+    };
+
+    daf.data_summaries()
+        .map_err(to_err)?
+        .iter()
+        .enumerate()
+        .filter(|(_, summary)| !summary.is_empty())
+        .map(|(sno, summary)| Ok((daf.nth_summary_name(sno).map_err(to_err)?, summary)))
+        .collect()
 }
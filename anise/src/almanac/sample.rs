@@ -0,0 +1,190 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::{Duration, Epoch, Unit as TimeUnit};
+
+use crate::astro::Aberration;
+use crate::errors::{AlmanacError, AlmanacResult};
+use crate::math::cartesian::CartesianState;
+use crate::prelude::Frame;
+
+use super::Almanac;
+
+/// Smallest step between two samples, in seconds, to bound the number of samples if the tolerance cannot be met.
+pub const MIN_SAMPLE_STEP_S: f64 = 1e-3;
+/// Longest step, as a fraction of the sampling window, such that short features of the trajectory are not skipped.
+const MAX_STEP_FRACTION: f64 = 0.125;
+/// Fractions of the step where the linear interpolation is checked.
+const CHECK_FRACTIONS: [f64; 3] = [0.25, 0.5, 0.75];
+
+impl Almanac {
+    /// Returns an iterator of the states of the target with respect to the observer between `start` and `end` (both
+    /// included), at epochs chosen such that the linear interpolation of the position between two consecutive samples
+    /// remains within `tolerance_km` of the actual position.
+    ///
+    /// This is useful to plot a trajectory, or to send it to a visualization tool, without oversampling it: the samples
+    /// are sparse where the trajectory is nearly straight, and dense where it curves, e.g. near a periapsis.
+    ///
+    /// # Algorithm
+    /// Each step is accepted if the linear interpolation at a quarter, half, and three quarters of the step is within
+    /// the tolerance. Otherwise, the step is halved. The next step grows with the square root of the ratio of the
+    /// tolerance to the error (the interpolation error grows with the square of the step), by a factor of at most two,
+    /// and never exceeds an eighth of the sampling window. Steps are never shorter than [MIN_SAMPLE_STEP_S].
+    ///
+    /// If a query fails, the iterator returns that error and then stops.
+    pub fn sample(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        start: Epoch,
+        end: Epoch,
+        tolerance_km: f64,
+        ab_corr: Option<Aberration>,
+    ) -> StateSampler<'_> {
+        StateSampler {
+            almanac: self,
+            target_frame,
+            observer_frame,
+            ab_corr,
+            end,
+            tolerance_km,
+            step: (end - start) * MAX_STEP_FRACTION,
+            max_step: (end - start) * MAX_STEP_FRACTION,
+            next_epoch: Some(start),
+            prev: None,
+        }
+    }
+}
+
+/// Iterator of the states sampled adaptively by [Almanac::sample].
+pub struct StateSampler<'a> {
+    almanac: &'a Almanac,
+    target_frame: Frame,
+    observer_frame: Frame,
+    ab_corr: Option<Aberration>,
+    end: Epoch,
+    tolerance_km: f64,
+    step: Duration,
+    max_step: Duration,
+    /// Epoch of the next sample, only set for the first sample
+    next_epoch: Option<Epoch>,
+    /// Previously returned sample
+    prev: Option<CartesianState>,
+}
+
+impl StateSampler<'_> {
+    fn query(&self, epoch: Epoch) -> AlmanacResult<CartesianState> {
+        self.almanac
+            .transform(self.target_frame, self.observer_frame, epoch, self.ab_corr)
+    }
+
+    /// Returns the next accepted sample after the previous one, and updates the step.
+    fn next_sample(&mut self, prev: CartesianState) -> AlmanacResult<CartesianState> {
+        let min_step = MIN_SAMPLE_STEP_S * TimeUnit::Second;
+
+        loop {
+            let step = if prev.epoch + self.step > self.end {
+                self.end - prev.epoch
+            } else {
+                self.step
+            };
+
+            let next = self.query(prev.epoch + step)?;
+
+            let mut max_err_km: f64 = 0.0;
+            for fraction in CHECK_FRACTIONS {
+                let actual = self.query(prev.epoch + step * fraction)?;
+                let interpolated = prev.radius_km + (next.radius_km - prev.radius_km) * fraction;
+                max_err_km = max_err_km.max((actual.radius_km - interpolated).norm());
+            }
+
+            if max_err_km > self.tolerance_km && step > min_step {
+                // Reject this step
+                self.step = if step * 0.5 < min_step {
+                    min_step
+                } else {
+                    step * 0.5
+                };
+                continue;
+            }
+
+            // Accept this step, and grow the next one
+            let growth = if max_err_km > 0.0 {
+                (0.9 * (self.tolerance_km / max_err_km).sqrt()).clamp(0.5, 2.0)
+            } else {
+                2.0
+            };
+            let grown = step * growth;
+            self.step = if grown > self.max_step {
+                self.max_step
+            } else if grown < min_step {
+                min_step
+            } else {
+                grown
+            };
+
+            return Ok(next);
+        }
+    }
+}
+
+impl Iterator for StateSampler<'_> {
+    type Item = AlmanacResult<CartesianState>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = match (self.next_epoch.take(), self.prev) {
+            (Some(start), _) => {
+                if start > self.end || self.tolerance_km <= 0.0 {
+                    return Some(Err(AlmanacError::GenericError {
+                        err: "sampling requires start <= end and a positive tolerance".to_string(),
+                    }));
+                }
+                self.query(start)
+            }
+            (None, Some(prev)) if prev.epoch < self.end => self.next_sample(prev),
+            _ => return None,
+        };
+
+        match result {
+            Ok(state) => {
+                self.prev = Some(state);
+                Some(Ok(state))
+            }
+            Err(e) => {
+                // Stop after an error
+                self.prev = None;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod ut_sample {
+    use crate::almanac::Almanac;
+    use crate::constants::frames::{EARTH_J2000, MOON_J2000};
+    use hifitime::{Epoch, TimeUnits};
+
+    #[test]
+    fn sample_nothing_loaded() {
+        let almanac = Almanac::default();
+        let start = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+
+        let mut sampler =
+            almanac.sample(MOON_J2000, EARTH_J2000, start, start + 1.days(), 1.0, None);
+        assert!(sampler.next().unwrap().is_err());
+        assert!(sampler.next().is_none());
+
+        let mut sampler =
+            almanac.sample(MOON_J2000, EARTH_J2000, start, start - 1.days(), 1.0, None);
+        assert!(sampler.next().unwrap().is_err());
+        assert!(sampler.next().is_none());
+    }
+}
@@ -0,0 +1,53 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::Epoch;
+use snafu::ResultExt;
+
+use crate::errors::{AlmanacError, AlmanacResult, SpacecraftClockSnafu};
+use crate::naif::kpl::sclk::SpacecraftClock;
+use crate::NaifId;
+
+use super::Almanac;
+
+impl Almanac {
+    /// Returns a copy of this Almanac with the provided spacecraft clock, replacing any clock with the same ID.
+    pub fn with_sclk(&self, clock: SpacecraftClock) -> Self {
+        let mut me = self.clone();
+        me.sclk_data
+            .retain(|loaded| loaded.clock_id != clock.clock_id);
+        me.sclk_data.push(clock);
+        me
+    }
+
+    /// Returns the clock of the provided spacecraft, as loaded from an SCLK kernel.
+    pub fn sclk(&self, sc_id: NaifId) -> AlmanacResult<&SpacecraftClock> {
+        self.sclk_data
+            .iter()
+            .find(|clock| clock.spacecraft_id() == sc_id)
+            .ok_or(AlmanacError::GenericError {
+                err: format!("no spacecraft clock loaded for spacecraft {sc_id}"),
+            })
+    }
+
+    /// Converts the clock string of the provided spacecraft (e.g. `1/0123456789.128`) to an epoch.
+    pub fn sclk_to_epoch(&self, sc_id: NaifId, sclk: &str) -> AlmanacResult<Epoch> {
+        self.sclk(sc_id)?
+            .to_epoch(sclk)
+            .context(SpacecraftClockSnafu { sc_id })
+    }
+
+    /// Converts the provided epoch to the clock string of the provided spacecraft, including its partition.
+    pub fn epoch_to_sclk(&self, sc_id: NaifId, epoch: Epoch) -> AlmanacResult<String> {
+        self.sclk(sc_id)?
+            .from_epoch(epoch)
+            .context(SpacecraftClockSnafu { sc_id })
+    }
+}
@@ -0,0 +1,112 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::Epoch;
+use snafu::ResultExt;
+
+use crate::ephemerides::EphemerisPhysicsSnafu;
+use crate::errors::{AlmanacError, AlmanacResult, EphemerisSnafu, OrientationSnafu};
+use crate::math::cartesian::CartesianState;
+use crate::math::Vector3;
+use crate::prelude::{Frame, Orbit};
+
+use super::Almanac;
+
+/// Position, velocity, and acceleration of a site fixed on a body, e.g. a ground station, in an inertial orientation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SiteKinematics {
+    /// Position and velocity of the site with respect to the center of its body, in the requested orientation
+    pub state: CartesianState,
+    /// Acceleration of the site with respect to the center of its body, in the requested orientation
+    pub acceleration_km_s2: Vector3,
+}
+
+impl Almanac {
+    /// Returns the inertial position, velocity, and acceleration of a site from its geodetic coordinates (degrees,
+    /// degrees, and kilometers) with respect to the ellipsoid of the body fixed frame, e.g. for precise Doppler modeling.
+    ///
+    /// The returned state is centered on the body of the body fixed frame and is expressed in the orientation of the
+    /// inertial frame: use `translate_to` to change its origin.
+    ///
+    /// # Earth orientation parameters
+    /// When the body fixed frame is `EARTH_ITRF93` and the high precision Earth BPC is loaded, the rotation includes the
+    /// polar motion and UT1-UTC of that BPC, so the site kinematics account for them.
+    ///
+    /// # Algorithm
+    /// The site is fixed in the body fixed frame, so its inertial position is `C r` and its inertial velocity is
+    /// `dC/dt r`, where `C` is the rotation from the body fixed frame to the inertial frame. The angular velocity `ω`
+    /// of the body is extracted from `dC/dt C^T`, and the acceleration is the centripetal term `ω × (ω × C r)`: the
+    /// angular acceleration of the body (precession, nutation, and length of day variations) is neglected.
+    #[allow(clippy::too_many_arguments)]
+    pub fn site_kinematics(
+        &self,
+        latitude_deg: f64,
+        longitude_deg: f64,
+        height_km: f64,
+        body_fixed_frame: Frame,
+        inertial_frame: Frame,
+        epoch: Epoch,
+    ) -> AlmanacResult<SiteKinematics> {
+        let body_fixed_frame =
+            self.frame_from_uid(body_fixed_frame)
+                .map_err(|e| AlmanacError::GenericError {
+                    err: format!("{e} when fetching {body_fixed_frame:e} frame data"),
+                })?;
+
+        let site_bf = Orbit::try_latlongalt(
+            latitude_deg,
+            longitude_deg,
+            height_km,
+            0.0,
+            epoch,
+            body_fixed_frame,
+        )
+        .context(EphemerisPhysicsSnafu {
+            action: "computing body fixed position of site",
+        })
+        .context(EphemerisSnafu {
+            action: "computing site kinematics",
+        })?;
+
+        let dcm = self
+            .rotate(body_fixed_frame, inertial_frame, epoch)
+            .context(OrientationSnafu {
+                action: "computing site kinematics",
+            })?;
+
+        let radius_km = dcm.rot_mat * site_bf.radius_km;
+
+        let (velocity_km_s, acceleration_km_s2) = match dcm.rot_mat_dt {
+            Some(rot_mat_dt) => {
+                let omega_skew = rot_mat_dt * dcm.rot_mat.transpose();
+                let omega_rad_s =
+                    Vector3::new(omega_skew[(2, 1)], omega_skew[(0, 2)], omega_skew[(1, 0)]);
+
+                (
+                    rot_mat_dt * site_bf.radius_km,
+                    omega_rad_s.cross(&omega_rad_s.cross(&radius_km)),
+                )
+            }
+            // Constant rotation, so the site is inertially fixed.
+            None => (Vector3::zeros(), Vector3::zeros()),
+        };
+
+        Ok(SiteKinematics {
+            state: CartesianState {
+                radius_km,
+                velocity_km_s,
+                acceleration_km_s2: None,
+                epoch,
+                frame: body_fixed_frame.with_orient(inertial_frame.orientation_id),
+            },
+            acceleration_km_s2,
+        })
+    }
+}
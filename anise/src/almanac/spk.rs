@@ -10,12 +10,17 @@
 
 use std::collections::HashMap;
 
+#[cfg(feature = "http")]
+use hifitime::Duration;
 use hifitime::Epoch;
 
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
 use snafu::ensure;
 
+#[cfg(feature = "http")]
+use crate::ephemerides::horizons::fetch_horizons_spk;
+use crate::ephemerides::stk::convert_stk_ephemeris;
 use crate::ephemerides::NoEphemerisLoadedSnafu;
 use crate::naif::daf::DAFError;
 use crate::naif::daf::NAIFSummaryRecord;
@@ -24,7 +29,7 @@ use crate::naif::SPK;
 use crate::{ephemerides::EphemerisError, NaifId};
 use log::error;
 
-use super::{Almanac, MAX_LOADED_SPKS};
+use super::{log_integrity, Almanac, MAX_LOADED_SPKS};
 
 impl Almanac {
     pub fn from_spk(spk: SPK) -> Result<Almanac, EphemerisError> {
@@ -35,6 +40,14 @@ impl Almanac {
     /// Loads a new SPK file into a new context.
     /// This new context is needed to satisfy the unloading of files. In fact, to unload a file, simply let the newly loaded context drop out of scope and Rust will clean it up.
     pub fn with_spk(&self, spk: SPK) -> Result<Self, EphemerisError> {
+        if self.load_checks {
+            log_integrity("SPK", spk.integrity_report());
+        }
+        self.add_spk(spk)
+    }
+
+    /// Adds the SPK to a new context without checking its integrity, e.g. because it was checked when it was parsed.
+    pub(crate) fn add_spk(&self, spk: SPK) -> Result<Self, EphemerisError> {
         // This is just a bunch of pointers so it doesn't use much memory.
         let mut me = self.clone();
         // Parse as SPK and place into the SPK list if there is room
@@ -53,6 +66,36 @@ impl Almanac {
         me.spk_data[data_idx] = Some(spk);
         Ok(me)
     }
+
+    /// Loads an STK ephemeris file (`.e`), e.g. as written by GMAT, as an SPK where the states are those of `target_id`.
+    ///
+    /// Refer to [convert_stk_ephemeris] for the supported subset of the format.
+    pub fn load_stk_ephemeris(
+        &self,
+        path: &str,
+        target_id: NaifId,
+    ) -> Result<Self, EphemerisError> {
+        self.with_spk(convert_stk_ephemeris(path, target_id)?)
+    }
+
+    /// Queries JPL Horizons for the ephemeris of `command` with respect to `center_id` and loads it as an SPK where the states are those of `target_id`.
+    ///
+    /// This is useful for asteroids and comets which are not in the DE kernels. Refer to [fetch_horizons_spk] for details.
+    #[cfg(feature = "http")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+    pub fn load_from_horizons(
+        &self,
+        command: &str,
+        target_id: NaifId,
+        center_id: NaifId,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+    ) -> Result<Self, EphemerisError> {
+        self.with_spk(fetch_horizons_spk(
+            command, target_id, center_id, start, end, step,
+        )?)
+    }
 }
 
 impl Almanac {
@@ -114,7 +157,12 @@ impl Almanac {
             .enumerate()
         {
             let spk = maybe_spk.as_ref().unwrap();
-            if let Ok((summary, idx_in_spk)) = spk.summary_from_id_at_epoch(id, epoch) {
+            let found = if self.spice_compat {
+                spk.last_summary_from_id_at_epoch(id, epoch)
+            } else {
+                spk.summary_from_id_at_epoch(id, epoch)
+            };
+            if let Ok((summary, idx_in_spk)) = found {
                 // NOTE: We're iterating backward, so the correct SPK number is "total loaded" minus "current iteration".
                 return Ok((summary, self.num_loaded_spk() - spk_no - 1, idx_in_spk));
             }
@@ -337,4 +385,54 @@ mod ut_almanac_spk {
             "empty Almanac should report an error"
         );
     }
+
+    #[test]
+    fn spice_compat_segment_selection() {
+        use crate::naif::daf::datatypes::HermiteSetType13;
+        use crate::naif::daf::{DafDataType, NAIFDataSet};
+        use crate::naif::spk::summary::SPKSummaryRecord;
+        use crate::naif::{SPKSegment, SPK};
+        use hifitime::Unit;
+
+        let state_data = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 10.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+        let epoch_data = [0.0, 10.0];
+        let data = HermiteSetType13 {
+            samples: 2,
+            num_records: 2,
+            state_data: &state_data,
+            epoch_data: &epoch_data,
+            epoch_registry: &[],
+        }
+        .to_f64_daf_vec()
+        .unwrap();
+
+        // Two segments of the same object covering the same time span
+        let segments = ["first", "second"].map(|name| SPKSegment {
+            name: name.to_string(),
+            summary: SPKSummaryRecord {
+                start_epoch_et_s: 0.0,
+                end_epoch_et_s: 10.0,
+                target_id: -10,
+                center_id: 399,
+                frame_id: 1,
+                data_type_i: DafDataType::Type13HermiteUnequalStep as i32,
+                ..Default::default()
+            },
+            data: data.clone(),
+        });
+
+        let almanac =
+            Almanac::from_spk(SPK::from_segments("ANISE TEST", &segments).unwrap()).unwrap();
+        let spice_almanac = almanac.with_spice_compat(true);
+
+        let epoch = Epoch::from_et_seconds(5.0);
+        // ANISE uses the first segment, SPICE the last one
+        assert_eq!(almanac.spk_summary_at_epoch(-10, epoch).unwrap().2, 0);
+        assert_eq!(spice_almanac.spk_summary_at_epoch(-10, epoch).unwrap().2, 1);
+
+        // SPICE does not tolerate epochs slightly past the end of the segment
+        let epoch = Epoch::from_et_seconds(10.0) + Unit::Nanosecond * 50;
+        assert!(almanac.spk_summary_at_epoch(-10, epoch).is_ok());
+        assert!(spice_almanac.spk_summary_at_epoch(-10, epoch).is_err());
+    }
 }
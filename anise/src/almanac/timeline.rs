@@ -0,0 +1,309 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use hifitime::Epoch;
+use log::info;
+
+use super::events::{CrossingDirection, EventCrossing};
+use super::window::Window;
+use crate::errors::{AlmanacError, AlmanacResult};
+
+/// Column names of the CSV timeline.
+pub const TIMELINE_COLUMNS: [&str; 4] = ["name", "start (UTC)", "end (UTC)", "duration (s)"];
+
+/// File format of an exported timeline.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TimelineFormat {
+    #[default]
+    Csv,
+    Json,
+    /// iCalendar (RFC 5545), which most scheduling tools import
+    Ics,
+}
+
+/// An event of a timeline, which is instantaneous if it ends when it starts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimelineEvent {
+    pub name: String,
+    pub start: Epoch,
+    pub end: Epoch,
+}
+
+/// A timeline of computed events (e.g. eclipses, station passes, or node crossings), sorted by start epoch, which can be
+/// exported to CSV, JSON, or iCalendar files.
+///
+/// The name of each event is that provided when adding the events, preceded by the `prefix` of the timeline if any,
+/// e.g. the name of the spacecraft.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Timeline {
+    pub prefix: Option<String>,
+    pub events: Vec<TimelineEvent>,
+}
+
+impl Timeline {
+    /// Returns a copy of this timeline where the names of the events are preceded by the provided prefix.
+    pub fn with_prefix(&self, prefix: &str) -> Self {
+        let mut me = self.clone();
+        me.prefix = Some(prefix.to_string());
+        me
+    }
+
+    /// Adds an event to the timeline, keeping it sorted.
+    pub fn add_event(&mut self, name: &str, start: Epoch, end: Epoch) {
+        let event = TimelineEvent {
+            name: name.to_string(),
+            start,
+            end,
+        };
+        let idx = self.events.partition_point(|other| other.start <= start);
+        self.events.insert(idx, event);
+    }
+
+    /// Adds the crossings as instantaneous events, named after their direction, e.g. "ascending node" and "descending node".
+    pub fn add_crossings(
+        &mut self,
+        crossings: &[EventCrossing],
+        rising_name: &str,
+        falling_name: &str,
+    ) {
+        for crossing in crossings {
+            let name = match crossing.direction {
+                CrossingDirection::Rising => rising_name,
+                CrossingDirection::Falling => falling_name,
+            };
+            self.add_event(name, crossing.epoch, crossing.epoch);
+        }
+    }
+
+    /// Adds the windows where the event function of the crossings, found between `start` and `end`, is positive, e.g. a
+    /// station pass from the rise to the set of the spacecraft. A window which is open at `start` or `end` is clipped there.
+    ///
+    /// Without any crossing, the sign of the event function is unknown, so no window is added.
+    pub fn add_windows(
+        &mut self,
+        crossings: &[EventCrossing],
+        start: Epoch,
+        end: Epoch,
+        name: &str,
+    ) {
+        self.add_window(&Window::from_crossings(crossings, start, end), name);
+    }
+
+    /// Adds each interval of the window as an event, e.g. the intersection of the sunlit and station visibility windows.
+    pub fn add_window(&mut self, window: &Window, name: &str) {
+        for (start, end) in window.intervals() {
+            self.add_event(name, *start, *end);
+        }
+    }
+
+    /// Returns the full name of the event, with the prefix of this timeline.
+    fn event_name(&self, event: &TimelineEvent) -> String {
+        match &self.prefix {
+            Some(prefix) => format!("{prefix} {}", event.name),
+            None => event.name.clone(),
+        }
+    }
+
+    /// Returns the timeline as a CSV table whose columns are [TIMELINE_COLUMNS], with the epochs in UTC (ISO 8601).
+    pub fn to_csv(&self) -> String {
+        let mut csv = TIMELINE_COLUMNS.join(",");
+        csv.push('\n');
+        for event in &self.events {
+            writeln!(
+                csv,
+                "{},{},{},{}",
+                escape_csv(&self.event_name(event)),
+                iso_utc(event.start),
+                iso_utc(event.end),
+                (event.end - event.start).to_seconds()
+            )
+            .unwrap();
+        }
+        csv
+    }
+
+    /// Returns the timeline as a JSON array of objects with the `name`, `start`, `end` (UTC, ISO 8601), and `duration_s` of each event.
+    pub fn to_json(&self) -> String {
+        let events = self
+            .events
+            .iter()
+            .map(|event| {
+                format!(
+                    "  {{\"name\": \"{}\", \"start\": \"{}\", \"end\": \"{}\", \"duration_s\": {}}}",
+                    escape_json(&self.event_name(event)),
+                    iso_utc(event.start),
+                    iso_utc(event.end),
+                    (event.end - event.start).to_seconds()
+                )
+            })
+            .collect::<Vec<String>>();
+
+        if events.is_empty() {
+            "[]\n".to_string()
+        } else {
+            format!("[\n{}\n]\n", events.join(",\n"))
+        }
+    }
+
+    /// Returns the timeline as an iCalendar (RFC 5545), where each event is a `VEVENT` whose summary is its name.
+    ///
+    /// The time stamp of each event is its start, so that exporting the same timeline twice produces the same calendar.
+    pub fn to_ics(&self) -> String {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            "PRODID:-//Nyx Space//ANISE timeline//EN".to_string(),
+        ];
+        for (idx, event) in self.events.iter().enumerate() {
+            let start = ics_utc(event.start);
+            lines.push("BEGIN:VEVENT".to_string());
+            lines.push(format!("UID:{start}-{idx}@anise"));
+            lines.push(format!("DTSTAMP:{start}"));
+            lines.push(format!("DTSTART:{start}"));
+            lines.push(format!("DTEND:{}", ics_utc(event.end)));
+            lines.push(format!("SUMMARY:{}", escape_ics(&self.event_name(event))));
+            lines.push("END:VEVENT".to_string());
+        }
+        lines.push("END:VCALENDAR".to_string());
+
+        // Lines of an iCalendar end with CRLF.
+        let mut ics = lines.join("\r\n");
+        ics.push_str("\r\n");
+        ics
+    }
+
+    /// Writes the timeline to the provided file in the requested format and returns the number of events written.
+    pub fn export<P: AsRef<Path>>(&self, path: P, format: TimelineFormat) -> AlmanacResult<usize> {
+        let path = path.as_ref();
+        let contents = match format {
+            TimelineFormat::Csv => self.to_csv(),
+            TimelineFormat::Json => self.to_json(),
+            TimelineFormat::Ics => self.to_ics(),
+        };
+
+        fs::write(path, contents).map_err(|e| AlmanacError::Export {
+            path: path.display().to_string(),
+            err: e.to_string(),
+        })?;
+
+        info!(
+            "[export_timeline] {} events written to {}",
+            self.events.len(),
+            path.display()
+        );
+
+        Ok(self.events.len())
+    }
+}
+
+/// Formats the epoch in UTC as `YYYY-MM-DDTHH:MM:SS.sssZ`.
+fn iso_utc(epoch: Epoch) -> String {
+    let (y, mm, dd, hh, min, s, ns) = epoch.to_gregorian_utc();
+    format!(
+        "{y:04}-{mm:02}-{dd:02}T{hh:02}:{min:02}:{s:02}.{:03}Z",
+        ns / 1_000_000
+    )
+}
+
+/// Formats the epoch in UTC as `YYYYMMDDTHHMMSSZ`, since iCalendar does not support fractional seconds.
+fn ics_utc(epoch: Epoch) -> String {
+    let (y, mm, dd, hh, min, s, _) = epoch.to_gregorian_utc();
+    format!("{y:04}{mm:02}{dd:02}T{hh:02}{min:02}{s:02}Z")
+}
+
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Quotes a CSV field if it contains a separator, a quote, or a line break, doubling its quotes, as per RFC 4180.
+pub(crate) fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn escape_ics(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod ut_timeline {
+    use super::{Timeline, TimelineFormat};
+    use crate::almanac::events::{CrossingDirection, EventCrossing};
+    use hifitime::{Epoch, TimeUnits};
+
+    #[test]
+    fn timeline_formats() {
+        let start = Epoch::from_gregorian_utc_hms(2025, 3, 1, 12, 0, 0);
+        let crossings = [
+            EventCrossing {
+                epoch: start + 10.minutes(),
+                direction: CrossingDirection::Falling,
+            },
+            EventCrossing {
+                epoch: start + 40.minutes(),
+                direction: CrossingDirection::Rising,
+            },
+            EventCrossing {
+                epoch: start + 50.minutes(),
+                direction: CrossingDirection::Falling,
+            },
+        ];
+
+        let mut timeline = Timeline::default().with_prefix("LRO");
+        timeline.add_windows(&crossings, start, start + 1.hours(), "pass, DSS-65");
+        timeline.add_crossings(&crossings[1..2], "AOS", "LOS");
+
+        assert_eq!(timeline.events.len(), 3);
+        assert_eq!(timeline.events[0].end, start + 10.minutes());
+        assert_eq!(timeline.events[1].name, "pass, DSS-65");
+        assert_eq!(timeline.events[2].name, "AOS");
+
+        let csv = timeline.to_csv();
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            "\"LRO pass, DSS-65\",2025-03-01T12:00:00.000Z,2025-03-01T12:10:00.000Z,600"
+        );
+
+        let json = timeline.to_json();
+        assert!(json.contains(
+            "{\"name\": \"LRO AOS\", \"start\": \"2025-03-01T12:40:00.000Z\", \"end\": \"2025-03-01T12:40:00.000Z\", \"duration_s\": 0}"
+        ));
+
+        let ics = timeline.to_ics();
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 3);
+        assert!(ics.contains(
+            "DTSTART:20250301T124000Z\r\nDTEND:20250301T125000Z\r\nSUMMARY:LRO pass\\, DSS-65"
+        ));
+
+        assert_eq!(Timeline::default().to_json(), "[]\n");
+        assert_eq!(TimelineFormat::default(), TimelineFormat::Csv);
+    }
+}
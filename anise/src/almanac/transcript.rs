@@ -0,0 +1,273 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use core::str::FromStr;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use hifitime::Epoch;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::astro::Aberration;
+use crate::ephemerides::paths::MAX_TREE_DEPTH;
+use crate::errors::{AlmanacError, AlmanacResult};
+use crate::math::cartesian::CartesianState;
+use crate::naif::daf::{NAIFSummaryRecord, DAF};
+use crate::prelude::Frame;
+use crate::structure::dataset::{DataSet, DataSetT};
+use crate::NaifId;
+
+use super::timeline::escape_csv;
+use super::Almanac;
+
+/// A single query of the Almanac, as recorded in a [QueryTranscript].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QueryRecord {
+    /// Name of the Almanac function which was called, e.g. `transform`
+    pub function: String,
+    pub target_frame: Frame,
+    pub observer_frame: Frame,
+    /// Epoch of the query, formatted such that it can be parsed back exactly
+    pub epoch: String,
+    /// SPICE name of the aberration correction, if any
+    pub ab_corr: Option<String>,
+    /// SPK and BPC segments used to answer this query, identified by the CRC32 of their file and their name.
+    pub sources: Vec<String>,
+    /// ANISE data sets loaded when the query was made, identified by their kind and the digest of their canonical form,
+    /// cf. [DataSet::digest]
+    #[serde(default)]
+    pub datasets: Vec<String>,
+    /// CRC32 of the state returned by the query, if it succeeded
+    pub result_hash: Option<u32>,
+    /// Error returned by the query, if it failed
+    pub error: Option<String>,
+}
+
+impl fmt::Display for QueryRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{},{},{},{},{},{}",
+            escape_csv(&self.function),
+            escape_csv(&self.target_frame.to_string()),
+            escape_csv(&self.observer_frame.to_string()),
+            escape_csv(&self.epoch),
+            escape_csv(self.ab_corr.as_deref().unwrap_or("NONE")),
+            escape_csv(&self.sources.join(";")),
+            escape_csv(&self.datasets.join(";")),
+            self.result_hash
+                .map(|hash| format!("0x{hash:08x}"))
+                .unwrap_or_default(),
+            escape_csv(self.error.as_deref().unwrap_or_default())
+        )
+    }
+}
+
+/// A transcript of the queries of an Almanac, used to audit and replay an analysis.
+///
+/// Clones of a transcript share the same records, so all of the Almanacs derived from an Almanac with a transcript
+/// (e.g. via `load` or `with_spk`) record their queries into the same transcript.
+///
+/// # Limitation
+/// Only the `transform` queries (which include the `transform_to` and `state_of` queries) are recorded.
+#[derive(Clone, Debug, Default)]
+pub struct QueryTranscript {
+    records: Arc<Mutex<Vec<QueryRecord>>>,
+}
+
+impl QueryTranscript {
+    /// Returns a copy of all of the records in this transcript, in the order of the queries
+    pub fn records(&self) -> Vec<QueryRecord> {
+        self.lock_records().clone()
+    }
+
+    /// Returns the number of queries in this transcript
+    pub fn len(&self) -> usize {
+        self.lock_records().len()
+    }
+
+    /// Returns true if no query has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all of the records of this transcript
+    pub fn clear(&self) {
+        self.lock_records().clear();
+    }
+
+    /// Returns this transcript as CSV, one query per line
+    pub fn to_csv(&self) -> String {
+        let mut csv =
+            "function,target frame,observer frame,epoch,aberration,sources,datasets,result hash,error\n"
+                .to_string();
+        for record in self.lock_records().iter() {
+            csv.push_str(&format!("{record}\n"));
+        }
+        csv
+    }
+
+    /// Replays all of the queries of this transcript with the provided Almanac, and returns the indexes of the records whose result differs.
+    pub fn replay(&self, almanac: &Almanac) -> AlmanacResult<Vec<usize>> {
+        let mut mismatches = Vec::new();
+
+        for (rno, record) in self.records().iter().enumerate() {
+            let epoch = Epoch::from_str(&record.epoch).map_err(|e| AlmanacError::GenericError {
+                err: format!("record #{rno}: {e}"),
+            })?;
+
+            let ab_corr = match &record.ab_corr {
+                Some(name) => Aberration::new(name).map_err(|e| AlmanacError::GenericError {
+                    err: format!("record #{rno}: {e}"),
+                })?,
+                None => None,
+            };
+
+            let result = almanac.transform_unrecorded(
+                record.target_frame,
+                record.observer_frame,
+                epoch,
+                ab_corr,
+            );
+
+            if result.as_ref().ok().map(hash_state) != record.result_hash {
+                mismatches.push(rno);
+            }
+        }
+
+        Ok(mismatches)
+    }
+
+    /// Records a `transform` query and its result.
+    pub(crate) fn record_transform(
+        &self,
+        almanac: &Almanac,
+        target_frame: Frame,
+        observer_frame: Frame,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+        result: &AlmanacResult<CartesianState>,
+    ) {
+        let mut sources = almanac.query_sources(target_frame, epoch);
+        for source in almanac.query_sources(observer_frame, epoch) {
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+
+        let record = QueryRecord {
+            function: "transform".to_string(),
+            target_frame,
+            observer_frame,
+            epoch: format!("{epoch}"),
+            ab_corr: ab_corr.map(|ab_corr| format!("{ab_corr:?}")),
+            sources,
+            datasets: self.dataset_digests(almanac),
+            result_hash: result.as_ref().ok().map(hash_state),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        };
+
+        self.lock_records().push(record);
+    }
+
+    /// Locks the records of this transcript, recovering them if another thread panicked while recording a query: a record is
+    /// pushed at once, so the panic of another thread does not need to propagate to all of the threads sharing this transcript.
+    fn lock_records(&self) -> MutexGuard<'_, Vec<QueryRecord>> {
+        self.records.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the digests of the non empty data sets of the Almanac.
+    ///
+    /// The digests are computed at each query because the data sets may be modified in place without updating their
+    /// checksum, e.g. with `set_by_id`.
+    fn dataset_digests(&self, almanac: &Almanac) -> Vec<String> {
+        let mut datasets = Vec::new();
+        push_digest(&mut datasets, &almanac.planetary_data);
+        push_digest(&mut datasets, &almanac.spacecraft_data);
+        push_digest(&mut datasets, &almanac.euler_param_data);
+        push_digest(&mut datasets, &almanac.maneuver_data);
+        datasets
+    }
+}
+
+fn push_digest<T: DataSetT, const ENTRIES: usize>(
+    datasets: &mut Vec<String>,
+    dataset: &DataSet<T, ENTRIES>,
+) {
+    if dataset.is_empty() {
+        return;
+    }
+
+    datasets.push(match dataset.digest() {
+        Ok(digest) => format!(
+            "{} blake3:{}",
+            T::NAME,
+            digest
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<String>()
+        ),
+        Err(e) => format!("{} digest failed: {e}", T::NAME),
+    });
+}
+
+impl Almanac {
+    /// Returns a copy of this Almanac which records all of its queries in a new [QueryTranscript].
+    pub fn with_transcript(&self) -> Self {
+        let mut me = self.clone();
+        me.transcript = Some(QueryTranscript::default());
+        me
+    }
+
+    /// Returns the SPK segments (from the frame up to the root of the ephemeris tree) and the BPC segment used for this frame at this epoch.
+    fn query_sources(&self, frame: Frame, epoch: Epoch) -> Vec<String> {
+        let mut sources = Vec::new();
+
+        let root: Option<NaifId> = self.try_find_ephemeris_root().ok();
+        let mut id = frame.ephemeris_id;
+        for _ in 0..MAX_TREE_DEPTH {
+            if Some(id) == root {
+                break;
+            }
+            match self.spk_summary_at_epoch(id, epoch) {
+                Ok((summary, spk_no, idx_in_spk)) => {
+                    let spk = self.spk_data[spk_no].as_ref().unwrap();
+                    sources.push(segment_source("SPK", spk, idx_in_spk));
+                    id = summary.center_id;
+                }
+                Err(_) => break,
+            }
+        }
+
+        if let Ok((_, bpc_no, idx_in_bpc)) = self.bpc_summary_at_epoch(frame.orientation_id, epoch)
+        {
+            let bpc = self.bpc_data[bpc_no].as_ref().unwrap();
+            sources.push(segment_source("BPC", bpc, idx_in_bpc));
+        }
+
+        sources
+    }
+}
+
+/// Identifies a segment by the CRC32 of its DAF file, its index, and its name.
+pub(crate) fn segment_source<R: NAIFSummaryRecord>(kind: &str, daf: &DAF<R>, idx: usize) -> String {
+    let name = daf.nth_summary_name(idx).unwrap_or_default();
+    format!("{kind} 0x{:08x} #{idx} {name}", daf.checksum())
+}
+
+/// Hashes the epoch, position, and velocity of a state.
+fn hash_state(state: &CartesianState) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(format!("{}", state.epoch).as_bytes());
+    for val in state.to_cartesian_pos_vel().iter() {
+        hasher.update(&val.to_le_bytes());
+    }
+    hasher.finalize()
+}
@@ -8,13 +8,18 @@
  * Documentation: https://nyxspace.com/
  */
 
-use hifitime::{Epoch, Unit as TimeUnit};
+use hifitime::{Duration, Epoch, Unit as TimeUnit};
 use snafu::ResultExt;
 
 use crate::{
-    constants::orientations::J2000,
+    astro::LightTimeConvergence,
+    constants::{frames::SSB_J2000, orientations::J2000},
     errors::{AlmanacResult, EphemerisSnafu, OrientationSnafu},
-    math::{cartesian::CartesianState, units::LengthUnit, Vector3},
+    math::{
+        cartesian::{CartesianState, CovariantState},
+        units::LengthUnit,
+        Vector3,
+    },
     orientations::OrientationPhysicsSnafu,
     prelude::{Aberration, Frame},
     NaifId,
@@ -52,24 +57,35 @@ impl Almanac {
         epoch: Epoch,
         ab_corr: Option<Aberration>,
     ) -> AlmanacResult<CartesianState> {
-        // Translate
-        let state = self
-            .translate(target_frame, observer_frame, epoch, ab_corr)
-            .context(EphemerisSnafu {
-                action: "transform from/to",
-            })?;
-        // Rotate
-        let dcm = self
-            .rotate(target_frame, observer_frame, epoch)
-            .context(OrientationSnafu {
-                action: "transform from/to",
-            })?;
+        let cached = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.state(target_frame, observer_frame, epoch, ab_corr));
 
-        (dcm * state)
-            .context(OrientationPhysicsSnafu {})
-            .context(OrientationSnafu {
-                action: "transform from/to",
-            })
+        let result = match cached {
+            Some(state) => Ok(state),
+            None => {
+                let result =
+                    self.transform_unrecorded(target_frame, observer_frame, epoch, ab_corr);
+                if let (Some(cache), Ok(state)) = (&self.cache, &result) {
+                    cache.insert_state(target_frame, observer_frame, ab_corr, *state);
+                }
+                result
+            }
+        };
+
+        if let Some(transcript) = &self.transcript {
+            transcript.record_transform(
+                self,
+                target_frame,
+                observer_frame,
+                epoch,
+                ab_corr,
+                &result,
+            );
+        }
+
+        result
     }
 
     /// Translates a state with its origin (`to_frame`) and given its units (distance_unit, time_unit), returns that state with respect to the requested frame
@@ -111,6 +127,35 @@ impl Almanac {
             })
     }
 
+    /// Transforms a state and its covariance into the requested observer frame, like `transform_to`.
+    ///
+    /// The translations do not change the covariance, which is rotated with the 6x6 Jacobian of the rotation from the frame
+    /// of the state to that of the observer, i.e. `state_transformation_from_to`.
+    ///
+    /// :type state: CovariantState
+    /// :type observer_frame: Frame
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: CovariantState
+    pub fn transform_covariant_to(
+        &self,
+        state: CovariantState,
+        observer_frame: Frame,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<CovariantState> {
+        let transformed = self.transform_to(state.state, observer_frame, ab_corr)?;
+
+        let jacobian = self
+            .state_transformation_from_to(state.state.frame, transformed.frame, state.state.epoch)
+            .context(OrientationSnafu {
+                action: "transform covariance",
+            })?;
+
+        Ok(CovariantState {
+            state: transformed,
+            covariance: jacobian * state.covariance * jacobian.transpose(),
+        })
+    }
+
     /// Returns the Cartesian state of the object as seen from the provided observer frame (essentially `spkezr`).
     ///
     /// # Note
@@ -163,6 +208,111 @@ impl Almanac {
                 action: "spkerz from/to",
             })
     }
+
+    /// Returns the apparent state of the target as seen from the observer, expressed in the orientation of the provided frame,
+    /// where the rotation into that frame is evaluated at the epoch at which the light left (or reaches) the center of that frame.
+    ///
+    /// # SPICE Compatibility
+    /// This function is the SPICE equivalent of spkezr with a non-inertial frame: `spkezr(TARGET_ID, EPOCH_TDB_S, FRAME, ABERRATION, OBSERVER_ID)`,
+    /// where the center of the FRAME is the ephemeris ID of the `frame` argument, e.g. the Moon for `IAU_MOON_FRAME`.
+    /// As in SPICE, the state is corrected for aberration in J2000, then rotated into the frame at the epoch minus (or plus
+    /// in transmission mode) the one-way light time from the observer to the center of the frame. Hence, the rotation of a
+    /// frame centered on the target is evaluated at the light time corrected epoch of the target, whereas the rotation of a
+    /// frame centered on the observer is evaluated at the provided epoch.
+    ///
+    /// Without aberration correction, this is the same as rotating the geometric state into the frame at the provided epoch.
+    /// The returned state is centered on the observer.
+    ///
+    /// :type target: int
+    /// :type observer: int
+    /// :type frame: Frame
+    /// :type epoch: Epoch
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: Orbit
+    pub fn apparent_state_in(
+        &self,
+        target: NaifId,
+        observer: NaifId,
+        frame: Frame,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<CartesianState> {
+        let target_j2000 = Frame::from_ephem_j2000(target);
+        let observer_j2000 = Frame::from_ephem_j2000(observer);
+
+        let (state, frame_epoch) = match ab_corr {
+            None => (
+                self.translate(target_j2000, observer_j2000, epoch, None)
+                    .context(EphemerisSnafu {
+                        action: "apparent state translation",
+                    })?,
+                epoch,
+            ),
+            Some(ab_corr) => {
+                let convergence = if self.spice_compat {
+                    LightTimeConvergence::SPICE
+                } else {
+                    LightTimeConvergence::ANISE
+                };
+
+                let observer_ssb = self
+                    .translate(observer_j2000, SSB_J2000, epoch, None)
+                    .context(EphemerisSnafu {
+                        action: "apparent state of the observer",
+                    })?;
+
+                let solution = self
+                    .aberrated_state_converged(target_j2000, observer_ssb, ab_corr, convergence)
+                    .context(EphemerisSnafu {
+                        action: "apparent state translation",
+                    })?;
+
+                // One-way light time between the observer and the center of the frame
+                let light_time = if frame.ephemeris_id == observer {
+                    Duration::ZERO
+                } else if frame.ephemeris_id == target {
+                    solution.light_time
+                } else {
+                    self.aberrated_state_converged(
+                        Frame::from_ephem_j2000(frame.ephemeris_id),
+                        observer_ssb,
+                        Aberration {
+                            stellar: false,
+                            ..ab_corr
+                        },
+                        convergence,
+                    )
+                    .context(EphemerisSnafu {
+                        action: "light time to the center of the frame",
+                    })?
+                    .light_time
+                };
+
+                let frame_epoch = if ab_corr.transmit_mode {
+                    epoch + light_time
+                } else {
+                    epoch - light_time
+                };
+
+                let mut state = solution.state;
+                state.frame = observer_j2000;
+                (state, frame_epoch)
+            }
+        };
+
+        // Rotate into the frame as it was oriented when the light left (or reaches) its center.
+        let dcm = self
+            .rotate(observer_j2000, frame, frame_epoch)
+            .context(OrientationSnafu {
+                action: "apparent state rotation",
+            })?;
+
+        (dcm * state)
+            .context(OrientationPhysicsSnafu {})
+            .context(OrientationSnafu {
+                action: "apparent state rotation",
+            })
+    }
 }
 
 impl Almanac {
@@ -210,3 +360,33 @@ impl Almanac {
             })
     }
 }
+
+impl Almanac {
+    /// Computes the transformation of [Almanac::transform] without recording it in the query transcript, if any.
+    pub(crate) fn transform_unrecorded(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<CartesianState> {
+        // Translate
+        let state = self
+            .translate(target_frame, observer_frame, epoch, ab_corr)
+            .context(EphemerisSnafu {
+                action: "transform from/to",
+            })?;
+        // Rotate
+        let dcm = self
+            .rotate(target_frame, observer_frame, epoch)
+            .context(OrientationSnafu {
+                action: "transform from/to",
+            })?;
+
+        (dcm * state)
+            .context(OrientationPhysicsSnafu {})
+            .context(OrientationSnafu {
+                action: "transform from/to",
+            })
+    }
+}
@@ -0,0 +1,377 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::{Duration, Epoch, Unit};
+
+use super::events::{CrossingDirection, EventCrossing};
+use super::Almanac;
+use crate::ephemerides::EphemerisError;
+use crate::naif::daf::NAIFSummaryRecord;
+use crate::orientations::OrientationError;
+use crate::NaifId;
+
+/// A set of disjoint time intervals, sorted by epoch, e.g. when a spacecraft is in sunlight or visible from a station, like
+/// the windows of SPICE.
+///
+/// The set operations allow combining the windows of several events, e.g. the intersection of the sunlit window with the
+/// station visibility window. Intervals are closed: two intervals which touch are merged, and an interval may be a single epoch.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Window {
+    intervals: Vec<(Epoch, Epoch)>,
+}
+
+impl Window {
+    /// Returns the window spanning the provided interval, or an empty window if the interval ends before it starts.
+    pub fn new(start: Epoch, end: Epoch) -> Self {
+        let mut me = Self::default();
+        me.insert(start, end);
+        me
+    }
+
+    /// Returns the window covering all of the provided intervals, which may overlap and be in any order.
+    pub fn from_intervals<I: IntoIterator<Item = (Epoch, Epoch)>>(intervals: I) -> Self {
+        let mut me = Self::default();
+        for (start, end) in intervals {
+            me.insert(start, end);
+        }
+        me
+    }
+
+    /// Returns the window where the event function of the crossings, found between `start` and `end`, is positive, e.g. the
+    /// station passes from the rise to the set of the spacecraft. An interval which is open at `start` or `end` is clipped there.
+    ///
+    /// Without any crossing, the sign of the event function is unknown, so the window is empty.
+    pub fn from_crossings(crossings: &[EventCrossing], start: Epoch, end: Epoch) -> Self {
+        let mut me = Self::default();
+        let mut interval_start = match crossings.first() {
+            Some(first) if first.direction == CrossingDirection::Falling => Some(start),
+            _ => None,
+        };
+        for crossing in crossings {
+            match crossing.direction {
+                CrossingDirection::Rising => interval_start = Some(crossing.epoch),
+                CrossingDirection::Falling => {
+                    if let Some(interval_start) = interval_start.take() {
+                        me.insert(interval_start, crossing.epoch);
+                    }
+                }
+            }
+        }
+        if let Some(interval_start) = interval_start {
+            me.insert(interval_start, end);
+        }
+        me
+    }
+
+    /// Adds the interval to this window, merging it with the intervals it overlaps or touches. Intervals which end before
+    /// they start are ignored.
+    pub fn insert(&mut self, start: Epoch, end: Epoch) {
+        if end < start {
+            return;
+        }
+        // Intervals entirely before or after the new one are kept as is.
+        let first = self.intervals.partition_point(|(_, e)| *e < start);
+        let last = self.intervals.partition_point(|(s, _)| *s <= end);
+
+        let mut merged = (start, end);
+        if first < last {
+            merged.0 = merged.0.min(self.intervals[first].0);
+            merged.1 = merged.1.max(self.intervals[last - 1].1);
+        }
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Returns the intervals of this window, sorted and disjoint.
+    pub fn intervals(&self) -> &[(Epoch, Epoch)] {
+        &self.intervals
+    }
+
+    /// Returns the number of intervals in this window.
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Returns true if this window has no interval.
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns the first and last epochs of this window, if any.
+    pub fn bounds(&self) -> Option<(Epoch, Epoch)> {
+        Some((self.intervals.first()?.0, self.intervals.last()?.1))
+    }
+
+    /// Returns true if the epoch is in one of the intervals of this window.
+    pub fn contains(&self, epoch: Epoch) -> bool {
+        let idx = self.intervals.partition_point(|(_, end)| *end < epoch);
+        self.intervals
+            .get(idx)
+            .is_some_and(|(start, _)| *start <= epoch)
+    }
+
+    /// Returns the window covering the epochs of either window.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut me = self.clone();
+        for (start, end) in &other.intervals {
+            me.insert(*start, *end);
+        }
+        me
+    }
+
+    /// Returns the window covering the epochs in both windows.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (start_a, end_a) = self.intervals[i];
+            let (start_b, end_b) = other.intervals[j];
+
+            let start = start_a.max(start_b);
+            let end = end_a.min(end_b);
+            if start <= end {
+                intervals.push((start, end));
+            }
+            // Move past the interval which ends first.
+            if end_a < end_b {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { intervals }
+    }
+
+    /// Returns the window covering the epochs between `start` and `end` which are not in this window, i.e. the gaps of this
+    /// window and its margins within these bounds. The epochs at the edges of the intervals are in both windows.
+    pub fn complement(&self, start: Epoch, end: Epoch) -> Self {
+        let mut me = Self::default();
+        let mut gap_start = start;
+        for (interval_start, interval_end) in &self.intervals {
+            if *interval_start > end {
+                break;
+            }
+            me.insert(gap_start, interval_start.min(end));
+            gap_start = gap_start.max(*interval_end);
+        }
+        me.insert(gap_start, end);
+
+        // Drop the single epochs which only come from the edges of this window.
+        me.intervals
+            .retain(|(gap_start, gap_end)| gap_start < gap_end || !self.contains(*gap_start));
+        me
+    }
+
+    /// Returns the window covering the epochs of this window which are not in the other one.
+    pub fn difference(&self, other: &Self) -> Self {
+        match self.bounds() {
+            Some((start, end)) => self.intersection(&other.complement(start, end)),
+            None => Self::default(),
+        }
+    }
+
+    /// Returns a copy of this window where each interval starts earlier by `left` and ends later by `right`, merging
+    /// the intervals which then overlap. Negative durations shrink the intervals, removing those which would end before
+    /// they start, like SPICE's `wnexpd`.
+    pub fn expanded(&self, left: Duration, right: Duration) -> Self {
+        Self::from_intervals(
+            self.intervals
+                .iter()
+                .map(|(start, end)| (*start - left, *end + right)),
+        )
+    }
+
+    /// Returns a copy of this window without the intervals shorter than the provided duration, like SPICE's `wnfltd`.
+    pub fn filter_shorter_than(&self, min_duration: Duration) -> Self {
+        Self {
+            intervals: self
+                .intervals
+                .iter()
+                .copied()
+                .filter(|(start, end)| *end - *start >= min_duration)
+                .collect(),
+        }
+    }
+
+    /// Returns a copy of this window with only the gaps shorter than the provided duration filled, like SPICE's `wnfild`.
+    pub fn fill_gaps_shorter_than(&self, max_gap: Duration) -> Self {
+        let mut intervals: Vec<(Epoch, Epoch)> = Vec::with_capacity(self.intervals.len());
+        for (start, end) in &self.intervals {
+            match intervals.last_mut() {
+                Some(prev) if *start - prev.1 < max_gap => prev.1 = *end,
+                _ => intervals.push((*start, *end)),
+            }
+        }
+        Self { intervals }
+    }
+
+    /// Returns the total duration of the intervals of this window.
+    pub fn total_duration(&self) -> Duration {
+        self.intervals
+            .iter()
+            .fold(Duration::ZERO, |total, (start, end)| {
+                total + (*end - *start)
+            })
+    }
+
+    /// Returns the statistics of the durations of the intervals of this window, or None if it is empty, like SPICE's `wnsumd`.
+    pub fn summary(&self) -> Option<WindowSummary> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let durations = self
+            .intervals
+            .iter()
+            .map(|(start, end)| *end - *start)
+            .collect::<Vec<Duration>>();
+
+        let total = self.total_duration();
+        let mean = total / (durations.len() as f64);
+
+        let variance_s2 = durations
+            .iter()
+            .map(|duration| (*duration - mean).to_seconds().powi(2))
+            .sum::<f64>()
+            / (durations.len() as f64);
+
+        let shortest = (0..durations.len()).min_by_key(|idx| durations[*idx])?;
+        let longest = (0..durations.len()).max_by_key(|idx| durations[*idx])?;
+
+        Some(WindowSummary {
+            count: durations.len(),
+            total,
+            mean,
+            std_dev: variance_s2.sqrt() * Unit::Second,
+            shortest: self.intervals[shortest],
+            longest: self.intervals[longest],
+        })
+    }
+}
+
+impl fmt::Display for Window {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "window of {} intervals", self.len())?;
+        for (start, end) in &self.intervals {
+            write!(f, "\n\t{start} to {end} ({})", *end - *start)?;
+        }
+        Ok(())
+    }
+}
+
+/// Statistics of the durations of the intervals of a [Window].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct WindowSummary {
+    pub count: usize,
+    pub total: Duration,
+    pub mean: Duration,
+    /// Population standard deviation of the durations
+    pub std_dev: Duration,
+    pub shortest: (Epoch, Epoch),
+    pub longest: (Epoch, Epoch),
+}
+
+impl Almanac {
+    /// Returns the window covered by the loaded SPK segments of the provided ID, whose gaps are the epochs where it cannot be
+    /// translated, unlike `spk_domain` which only returns its bounds (this is SPICE's `spkcov`).
+    pub fn spk_coverage(&self, id: NaifId) -> Result<Window, EphemerisError> {
+        Ok(Window::from_intervals(self.spk_summaries(id)?.iter().map(
+            |summary| (summary.start_epoch(), summary.end_epoch()),
+        )))
+    }
+
+    /// Returns the window covered by the loaded BPC segments of the provided ID, unlike `bpc_domain` which only returns its
+    /// bounds (this is SPICE's `pckcov`).
+    pub fn bpc_coverage(&self, id: NaifId) -> Result<Window, OrientationError> {
+        Ok(Window::from_intervals(self.bpc_summaries(id)?.iter().map(
+            |summary| (summary.start_epoch(), summary.end_epoch()),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod ut_window {
+    use super::Window;
+    use crate::almanac::events::{CrossingDirection, EventCrossing};
+    use hifitime::{Epoch, TimeUnits};
+
+    fn window(bounds: &[(i64, i64)]) -> Window {
+        let t0 = Epoch::from_tdb_seconds(0.0);
+        Window::from_intervals(
+            bounds
+                .iter()
+                .map(|(s, e)| (t0 + (*s).hours(), t0 + (*e).hours())),
+        )
+    }
+
+    #[test]
+    fn set_operations() {
+        let t0 = Epoch::from_tdb_seconds(0.0);
+        // Overlapping and touching intervals are merged, in any order.
+        let sunlit = window(&[(5, 8), (0, 2), (1, 3), (8, 10)]);
+        assert_eq!(sunlit, window(&[(0, 3), (5, 10)]));
+        assert_eq!(sunlit.total_duration(), 8.hours());
+        assert!(sunlit.contains(t0 + 6.hours()));
+        assert!(sunlit.contains(t0 + 3.hours()));
+        assert!(!sunlit.contains(t0 + 4.hours()));
+
+        let visible = window(&[(2, 6), (9, 12)]);
+        assert_eq!(
+            sunlit.intersection(&visible),
+            window(&[(2, 3), (5, 6), (9, 10)])
+        );
+        assert_eq!(sunlit.union(&visible), window(&[(0, 12)]));
+        assert_eq!(sunlit.difference(&visible), window(&[(0, 2), (6, 9)]));
+        assert_eq!(
+            sunlit.complement(t0 - 1.hours(), t0 + 11.hours()),
+            window(&[(-1, 0), (3, 5), (10, 11)])
+        );
+        assert_eq!(
+            Window::default().complement(t0, t0 + 1.hours()),
+            window(&[(0, 1)])
+        );
+
+        assert_eq!(
+            sunlit.expanded(1.hours(), 0.hours()),
+            window(&[(-1, 3), (4, 10)])
+        );
+        assert_eq!(sunlit.expanded(-4.hours(), 0.hours()), window(&[(9, 10)]));
+        assert_eq!(sunlit.filter_shorter_than(4.hours()), window(&[(5, 10)]));
+        assert_eq!(sunlit.fill_gaps_shorter_than(3.hours()), window(&[(0, 10)]));
+
+        let summary = sunlit.summary().unwrap();
+        assert_eq!(summary.count, 2);
+        assert_eq!(summary.mean, 4.hours());
+        assert_eq!(summary.std_dev, 1.hours());
+        assert_eq!(summary.longest, (t0 + 5.hours(), t0 + 10.hours()));
+        assert!(Window::default().summary().is_none());
+    }
+
+    #[test]
+    fn windows_from_crossings() {
+        let t0 = Epoch::from_tdb_seconds(0.0);
+        let crossings = [
+            EventCrossing {
+                epoch: t0 + 1.hours(),
+                direction: CrossingDirection::Falling,
+            },
+            EventCrossing {
+                epoch: t0 + 2.hours(),
+                direction: CrossingDirection::Rising,
+            },
+        ];
+        assert_eq!(
+            Window::from_crossings(&crossings, t0, t0 + 3.hours()),
+            window(&[(0, 1), (2, 3)])
+        );
+        assert!(Window::from_crossings(&[], t0, t0 + 3.hours()).is_empty());
+    }
+}
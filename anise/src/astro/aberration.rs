@@ -10,9 +10,10 @@
 
 use crate::{
     constants::SPEED_OF_LIGHT_KM_S,
-    errors::{AberrationSnafu, VelocitySnafu},
-    math::{rotate_vector, Vector3},
+    errors::{AberrationSnafu, InfiniteValueSnafu, RadiusSnafu, VelocitySnafu},
+    math::{cartesian::CartesianState, rotate_vector, Vector3},
 };
+use hifitime::Duration;
 
 use core::fmt;
 
@@ -208,6 +209,51 @@ impl Aberration {
     }
 }
 
+/// Stopping criteria of the converged light time iterations, cf. [crate::almanac::Almanac::translate_light_time].
+///
+/// Unconverged light time corrections always perform a single iteration.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LightTimeConvergence {
+    /// Maximum number of light time iterations
+    pub max_iterations: usize,
+    /// Relative change of the one-way light time between two iterations below which the iterations stop, zero to
+    /// always perform the maximum number of iterations
+    pub tolerance: f64,
+}
+
+impl LightTimeConvergence {
+    /// Three iterations without early stop, as in ANISE and as in SPICE's `spkapo`.
+    pub const ANISE: Self = Self {
+        max_iterations: 3,
+        tolerance: 0.0,
+    };
+    /// Up to five iterations, stopping once the relative change of the light time is below 1e-17, as in SPICE's `spkltc`
+    /// (used in SPICE compatibility mode).
+    pub const SPICE: Self = Self {
+        max_iterations: 5,
+        tolerance: 1e-17,
+    };
+}
+
+impl Default for LightTimeConvergence {
+    fn default() -> Self {
+        Self::ANISE
+    }
+}
+
+/// State corrected for light time, with the convergence achieved by the light time iterations.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LightTimeSolution {
+    /// The corrected state, cf. [crate::almanac::Almanac::aberrated_state]
+    pub state: CartesianState,
+    /// Number of light time iterations performed
+    pub iterations: usize,
+    /// One-way light time computed from the corrected position
+    pub light_time: Duration,
+    /// Relative change of the one-way light time in the last iteration
+    pub achieved_tolerance: f64,
+}
+
 impl fmt::Debug for Aberration {
     /// Prints this configuration as the SPICE name.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -258,7 +304,11 @@ impl fmt::Display for Aberration {
 ///
 /// This function will return an error in the following cases:
 /// 1. the aberration is not set to include stellar corrections;
-/// 1. the `target` is moving faster than the speed of light.
+/// 1. the target position has a zero norm (the target is at the observer), so its direction is undefined;
+/// 1. either vector is not finite;
+/// 1. the observer is moving at or faster than the speed of light.
+///
+/// Observers moving close to the speed of light are supported: the aberration angle then approaches 90 degrees.
 ///
 /// # Algorithm
 /// Source: this algorithm and documentation were rewritten from NAIF's [`stelab`](https://github.com/nasa/kepler-pipeline/blob/f58b21df2c82969d8bd3e26a269bd7f5b9a770e1/source-code/matlab/fc/cspice-src-i686/cspice/stelab.c#L13) function:
@@ -287,6 +337,25 @@ pub fn stellar_aberration(
         }
     );
 
+    ensure!(
+        target_pos_km.iter().all(|x| x.is_finite()),
+        InfiniteValueSnafu {
+            action: "computing stellar aberration of non finite target position"
+        }
+    );
+    ensure!(
+        obs_wrt_ssb_vel_km_s.iter().all(|x| x.is_finite()),
+        InfiniteValueSnafu {
+            action: "computing stellar aberration with non finite observer velocity"
+        }
+    );
+    ensure!(
+        target_pos_km.norm() > f64::EPSILON,
+        RadiusSnafu {
+            action: "target at the observer has no direction, cannot compute stellar aberration"
+        }
+    );
+
     // Obtain the negative of the observer's velocity. This velocity, combined
     // with the target's position, will yield the inverse of the usual stellar
     // aberration correction, which is exactly what we seek.
@@ -315,7 +384,8 @@ pub fn stellar_aberration(
 
     // Correct for stellar aberration
     let mut app_target_pos_km = target_pos_km;
-    let sin_phi = h.norm();
+    // Clamp the sine to guard against rounding errors for observers moving near the speed of light.
+    let sin_phi = h.norm().min(1.0);
     if sin_phi > f64::EPSILON {
         let phi = sin_phi.asin();
         app_target_pos_km = rotate_vector(&target_pos_km, &h, phi);
@@ -324,12 +394,117 @@ pub fn stellar_aberration(
     Ok(app_target_pos_km)
 }
 
+/// Returns the direction of a light source deflected by the gravity of a body, i.e. the gravitational light bending of
+/// general relativity, e.g. of about 1.75 arcseconds at the limb of the Sun and 4 milliarcseconds at 90 degrees from it.
+///
+/// + `direction`: the unit direction from the observer to the source, without deflection
+/// + `source_from_body`: the unit direction from the deflecting body to the source, which is `direction` for a star
+/// + `observer_from_body_km`: the position of the observer with respect to the deflecting body in kilometers
+/// + `gm_km3_s2`: the gravitational parameter of the deflecting body
+///
+/// The returned direction is a unit vector. Lines of sight passing through the deflecting body (i.e. close to the direction of the
+/// source seen from the body) are not deflected to infinity, but clamped as in the SOFA library.
+///
+/// # Algorithm
+/// Source: IERS Conventions (2010), chapter 11, and the `iauLd` function of the IAU SOFA library, where `e` is the direction from
+/// the body to the observer at a distance E, `q` is `source_from_body`, and `p` is `direction`:
+///
+/// `p' = p + (2 GM / c^2 E) (p × (e × q)) / (1 + q · e)`
+pub fn gravitational_deflection(
+    direction: Vector3,
+    source_from_body: Vector3,
+    observer_from_body_km: Vector3,
+    gm_km3_s2: f64,
+) -> PhysicsResult<Vector3> {
+    ensure!(
+        observer_from_body_km.norm() > f64::EPSILON,
+        RadiusSnafu {
+            action: "observer at the deflecting body, cannot compute light deflection"
+        }
+    );
+
+    let p = direction.normalize();
+    let q = source_from_body.normalize();
+    let dist_km = observer_from_body_km.norm();
+    let e = observer_from_body_km / dist_km;
+
+    // Limit the deflection of the lines of sight which pass through the body, as in SOFA.
+    let w = 2.0 * gm_km3_s2 / SPEED_OF_LIGHT_KM_S.powi(2) / dist_km / (1.0 + q.dot(&e)).max(1e-9);
+
+    Ok((p + w * p.cross(&e.cross(&q))).normalize())
+}
+
 #[cfg(test)]
 mod ut_aberration {
+    use super::{gravitational_deflection, stellar_aberration, Aberration};
+    use crate::constants::SPEED_OF_LIGHT_KM_S;
+    use crate::errors::PhysicsError;
+    use crate::math::Vector3;
+
     #[test]
-    fn test_display() {
-        use super::Aberration;
+    fn test_stellar_aberration_edge_cases() {
+        let target_pos_km = Vector3::new(1e8, 0.0, 0.0);
+        let obs_vel_km_s = Vector3::new(0.0, 30.0, 0.0);
+        let ab_corr = Aberration::LT_S.unwrap();
+
+        // Correction not requested
+        assert!(matches!(
+            stellar_aberration(target_pos_km, obs_vel_km_s, Aberration::LT.unwrap()),
+            Err(PhysicsError::AberrationError { .. })
+        ));
+
+        // Target at the observer
+        assert!(matches!(
+            stellar_aberration(Vector3::zeros(), obs_vel_km_s, ab_corr),
+            Err(PhysicsError::RadiusError { .. })
+        ));
+
+        // Non finite inputs
+        assert!(matches!(
+            stellar_aberration(Vector3::new(f64::NAN, 0.0, 0.0), obs_vel_km_s, ab_corr),
+            Err(PhysicsError::InfiniteValue { .. })
+        ));
+        assert!(matches!(
+            stellar_aberration(
+                target_pos_km,
+                Vector3::new(0.0, f64::INFINITY, 0.0),
+                ab_corr
+            ),
+            Err(PhysicsError::InfiniteValue { .. })
+        ));
 
+        // At and faster than light
+        for speed_km_s in [SPEED_OF_LIGHT_KM_S, 2.0 * SPEED_OF_LIGHT_KM_S] {
+            assert!(matches!(
+                stellar_aberration(target_pos_km, Vector3::new(0.0, speed_km_s, 0.0), ab_corr),
+                Err(PhysicsError::VelocityError { .. })
+            ));
+        }
+
+        // Near the speed of light, the aberration angle approaches 90 degrees without any NaN
+        let near_c = Vector3::new(0.0, SPEED_OF_LIGHT_KM_S * (1.0 - 1e-15), 0.0);
+        let app_pos_km = stellar_aberration(target_pos_km, near_c, ab_corr).unwrap();
+        assert!(app_pos_km.iter().all(|x| x.is_finite()));
+        assert!((app_pos_km.norm() - target_pos_km.norm()).abs() < 1e-6);
+        assert!(app_pos_km.angle(&near_c) < 1e-6);
+
+        // Target along the velocity of the observer: no aberration
+        let along = Vector3::new(0.0, 1e8, 0.0);
+        assert_eq!(
+            stellar_aberration(along, obs_vel_km_s, ab_corr).unwrap(),
+            along
+        );
+
+        // Transmission mode aberrates in the opposite direction
+        let rx = stellar_aberration(target_pos_km, obs_vel_km_s, ab_corr).unwrap();
+        let tx =
+            stellar_aberration(target_pos_km, obs_vel_km_s, Aberration::XLT_S.unwrap()).unwrap();
+        assert!(rx.y > 0.0);
+        assert!((rx.y + tx.y).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_display() {
         assert_eq!(format!("{:?}", Aberration::LT.unwrap()), "LT");
         assert_eq!(format!("{:?}", Aberration::LT_S.unwrap()), "LT+S");
         assert_eq!(format!("{:?}", Aberration::CN.unwrap()), "CN");
@@ -340,4 +515,24 @@ mod ut_aberration {
         assert_eq!(format!("{:?}", Aberration::XCN.unwrap()), "XCN");
         assert_eq!(format!("{:?}", Aberration::XCN_S.unwrap()), "XCN+S");
     }
+
+    #[test]
+    fn test_gravitational_deflection() {
+        use crate::constants::registry::GM_SUN;
+
+        // Star grazing the limb of the Sun, seen from 1 AU: 1.75 arcseconds toward the Sun.
+        let au_km = 149_597_870.7;
+        let sun_radius_km = 696_000.0;
+        let observer_from_sun_km = Vector3::new(-au_km, 0.0, 0.0);
+        let star = Vector3::new(au_km, sun_radius_km, 0.0).normalize();
+
+        let deflected =
+            gravitational_deflection(star, star, observer_from_sun_km, GM_SUN.value).unwrap();
+        let angle_arcsec = deflected.angle(&star).to_degrees() * 3600.0;
+        assert!((angle_arcsec - 1.75).abs() < 0.01, "{angle_arcsec}");
+        // The star appears further away from the Sun.
+        assert!(deflected.y > star.y);
+
+        assert!(gravitational_deflection(star, star, Vector3::zeros(), GM_SUN.value).is_err());
+    }
 }
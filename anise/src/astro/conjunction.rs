@@ -0,0 +1,102 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::{Duration, Epoch, Unit as TimeUnit};
+
+use crate::almanac::events::{CrossingDirection, EVENT_PRECISION_S};
+use crate::almanac::Almanac;
+use crate::constants::orientations::J2000;
+use crate::errors::AlmanacResult;
+use crate::math::cartesian::CartesianState;
+use crate::prelude::Frame;
+
+/// A close approach between two objects, found by [screen].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CloseApproach {
+    /// Time of closest approach (TCA)
+    pub tca: Epoch,
+    /// Distance between the objects at the TCA, in kilometers
+    pub miss_distance_km: f64,
+    /// Norm of the relative velocity at the TCA, in kilometers per second
+    pub relative_speed_km_s: f64,
+    /// State of the first object with respect to the second one at the TCA, in J2000
+    pub relative_state: CartesianState,
+}
+
+impl fmt::Display for CloseApproach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TCA {}: miss distance = {:.6} km, relative speed = {:.6} km/s",
+            self.tca, self.miss_distance_km, self.relative_speed_km_s
+        )
+    }
+}
+
+/// Screens the ephemerides of the two objects (e.g. a spacecraft and a debris object, both loaded from SPK files) for close
+/// approaches within the window (start and end epochs), and returns those whose miss distance is at most the threshold,
+/// sorted by TCA.
+///
+/// # Algorithm
+/// 1. The relative state is sampled every `step` (the coarse filter), and each local minimum of the distance is bracketed by
+///    a change of sign of the dot product of the relative position and velocity, from negative to positive.
+/// 2. Each bracket is refined with Newton steps, cf. [Almanac::find_crossings_with_rate], to [EVENT_PRECISION_S].
+/// 3. The close approaches farther than the threshold are dropped.
+///
+/// # Limitation
+/// Two close approaches within the same step are not both reported, so the step must be shorter than half of the shortest
+/// relative period of the objects, e.g. about 20 minutes for two objects in low Earth orbit.
+pub fn screen(
+    almanac: &Almanac,
+    obj_a: Frame,
+    obj_b: Frame,
+    window: (Epoch, Epoch),
+    step: Duration,
+    threshold_km: f64,
+) -> AlmanacResult<Vec<CloseApproach>> {
+    // The relative velocity must be inertial, so the relative state is computed in J2000.
+    let (obj_a, obj_b) = (obj_a.with_orient(J2000), obj_b.with_orient(J2000));
+    let (start, end) = window;
+
+    let minima = almanac.find_crossings_with_rate(
+        start,
+        end,
+        step,
+        EVENT_PRECISION_S * TimeUnit::Second,
+        |almanac, epoch| {
+            let state = almanac.transform(obj_a, obj_b, epoch, None)?;
+            // The derivative neglects the relative acceleration, which is small near a close approach.
+            Ok((
+                state.radius_km.dot(&state.velocity_km_s),
+                state.velocity_km_s.norm_squared(),
+            ))
+        },
+    )?;
+
+    let mut approaches = Vec::new();
+    for minimum in minima
+        .iter()
+        .filter(|crossing| crossing.direction == CrossingDirection::Rising)
+    {
+        let relative_state = almanac.transform(obj_a, obj_b, minimum.epoch, None)?;
+        if relative_state.rmag_km() <= threshold_km {
+            approaches.push(CloseApproach {
+                tca: minimum.epoch,
+                miss_distance_km: relative_state.rmag_km(),
+                relative_speed_km_s: relative_state.vmag_km_s(),
+                relative_state,
+            });
+        }
+    }
+
+    Ok(approaches)
+}
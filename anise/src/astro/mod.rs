@@ -22,16 +22,24 @@ use pyo3::prelude::*;
 #[cfg(feature = "python")]
 use pyo3::pyclass::CompareOp;
 
+#[cfg(feature = "python")]
+use crate::python_config::{ConfiguredAngle, ConfiguredEpoch};
+
 pub mod utils;
 
 pub(crate) mod aberration;
-pub use aberration::Aberration;
+pub use aberration::{
+    gravitational_deflection, stellar_aberration, Aberration, LightTimeConvergence,
+    LightTimeSolution,
+};
 
 pub(crate) mod occultation;
 pub use occultation::Occultation;
 
+pub mod conjunction;
 pub mod orbit;
 pub mod orbit_geodetic;
+pub mod pointing;
 
 pub type PhysicsResult<T> = Result<T, PhysicsError>;
 
@@ -81,7 +89,7 @@ impl AzElRange {
     #[new]
     #[pyo3(signature=(epoch, azimuth_deg, elevation_deg, range_km, range_rate_km_s, obstructed_by=None))]
     pub fn py_new(
-        epoch: Epoch,
+        epoch: ConfiguredEpoch,
         azimuth_deg: f64,
         elevation_deg: f64,
         range_km: f64,
@@ -92,7 +100,7 @@ impl AzElRange {
         use hifitime::TimeUnits;
 
         Self {
-            epoch,
+            epoch: epoch.0,
             azimuth_deg,
             elevation_deg,
             range_km,
@@ -104,13 +112,13 @@ impl AzElRange {
 
     /// :rtype: Epoch
     #[getter]
-    fn get_epoch(&self) -> PyResult<Epoch> {
-        Ok(self.epoch)
+    fn get_epoch(&self) -> PyResult<ConfiguredEpoch> {
+        Ok(ConfiguredEpoch(self.epoch))
     }
     /// :type epoch: Epoch
     #[setter]
-    fn set_epoch(&mut self, epoch: Epoch) -> PyResult<()> {
-        self.epoch = epoch;
+    fn set_epoch(&mut self, epoch: ConfiguredEpoch) -> PyResult<()> {
+        self.epoch = epoch.0;
         Ok(())
     }
 
@@ -138,6 +146,34 @@ impl AzElRange {
         Ok(())
     }
 
+    /// Azimuth in the configured angle unit (degrees by default)
+    ///
+    /// :rtype: float
+    #[getter]
+    fn get_azimuth(&self) -> PyResult<ConfiguredAngle> {
+        Ok(ConfiguredAngle(self.azimuth_deg))
+    }
+    /// :type azimuth: float
+    #[setter]
+    fn set_azimuth(&mut self, azimuth: ConfiguredAngle) -> PyResult<()> {
+        self.azimuth_deg = azimuth.deg();
+        Ok(())
+    }
+
+    /// Elevation in the configured angle unit (degrees by default)
+    ///
+    /// :rtype: float
+    #[getter]
+    fn get_elevation(&self) -> PyResult<ConfiguredAngle> {
+        Ok(ConfiguredAngle(self.elevation_deg))
+    }
+    /// :type elevation: float
+    #[setter]
+    fn set_elevation(&mut self, elevation: ConfiguredAngle) -> PyResult<()> {
+        self.elevation_deg = elevation.deg();
+        Ok(())
+    }
+
     /// :rtype: float
     #[getter]
     fn get_range_km(&self) -> PyResult<f64> {
@@ -17,7 +17,7 @@ use crate::{
         ParabolicSemiParamSnafu, PhysicsError, RadiusSnafu, VelocitySnafu,
     },
     math::{
-        angles::{between_0_360, between_pm_180},
+        angles::{between_0_360, between_pm_180, ra_dec_deg},
         cartesian::CartesianState,
         rotation::DCM,
         Matrix3, Vector3, Vector6,
@@ -37,6 +37,9 @@ use pyo3::prelude::*;
 #[cfg(feature = "python")]
 use pyo3::types::PyType;
 
+#[cfg(feature = "python")]
+use crate::python_config::ConfiguredEpoch;
+
 /// If an orbit has an eccentricity below the following value, it is considered circular (only affects warning messages)
 pub const ECC_EPSILON: f64 = 1e-11;
 
@@ -141,6 +144,7 @@ impl Orbit {
         Ok(Self {
             radius_km: Vector3::new(x, y, z),
             velocity_km_s: Vector3::new(vx, vy, vz),
+            acceleration_km_s2: None,
             epoch,
             frame,
         })
@@ -606,11 +610,11 @@ impl Orbit {
         raan_deg: f64,
         aop_deg: f64,
         ta_deg: f64,
-        epoch: Epoch,
+        epoch: ConfiguredEpoch,
         frame: Frame,
     ) -> PhysicsResult<Self> {
         Self::try_keplerian(
-            sma_km, ecc, inc_deg, raan_deg, aop_deg, ta_deg, epoch, frame,
+            sma_km, ecc, inc_deg, raan_deg, aop_deg, ta_deg, epoch.0, frame,
         )
     }
 
@@ -635,11 +639,11 @@ impl Orbit {
         raan_deg: f64,
         aop_deg: f64,
         ta_deg: f64,
-        epoch: Epoch,
+        epoch: ConfiguredEpoch,
         frame: Frame,
     ) -> PhysicsResult<Self> {
         Self::try_keplerian_apsis_radii(
-            r_a_km, r_p_km, inc_deg, raan_deg, aop_deg, ta_deg, epoch, frame,
+            r_a_km, r_p_km, inc_deg, raan_deg, aop_deg, ta_deg, epoch.0, frame,
         )
     }
 
@@ -669,11 +673,11 @@ impl Orbit {
         raan_deg: f64,
         aop_deg: f64,
         ma_deg: f64,
-        epoch: Epoch,
+        epoch: ConfiguredEpoch,
         frame: Frame,
     ) -> PhysicsResult<Self> {
         Self::try_keplerian_mean_anomaly(
-            sma_km, ecc, inc_deg, raan_deg, aop_deg, ma_deg, epoch, frame,
+            sma_km, ecc, inc_deg, raan_deg, aop_deg, ma_deg, epoch.0, frame,
         )
     }
 
@@ -1245,14 +1249,14 @@ impl Orbit {
     ///
     /// :rtype: float
     pub fn right_ascension_deg(&self) -> f64 {
-        between_0_360((self.radius_km.y.atan2(self.radius_km.x)).to_degrees())
+        ra_dec_deg(&self.radius_km).0
     }
 
     /// Returns the declination of this orbit in degrees
     ///
     /// :rtype: float
     pub fn declination_deg(&self) -> f64 {
-        between_pm_180((self.radius_km.z / self.rmag_km()).asin().to_degrees())
+        ra_dec_deg(&self.radius_km).1
     }
 
     /// Returns the semi minor axis in km, includes code for a hyperbolic orbit
@@ -0,0 +1,205 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::Epoch;
+
+use crate::almanac::Almanac;
+use crate::constants::frames::SUN_J2000;
+use crate::constants::orientations::J2000;
+use crate::errors::{AlmanacError, AlmanacResult};
+use crate::math::rotation::{Quaternion, DCM};
+use crate::math::{Matrix3, Vector3};
+use crate::orientations::{AttitudeSample, OrientationError, OrientationProvider};
+use crate::prelude::{Frame, Orbit};
+use crate::NaifId;
+
+/// Ideal attitude law of a spacecraft, which defines its body frame by a primary axis (+Z) pointed exactly at a direction, and
+/// a secondary axis (+X) as close as possible to another direction. The +Y axis completes the right handed frame.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PointingProfile {
+    /// +Z points to the center of the central body (nadir) and +X is as close as possible to the velocity with respect to it,
+    /// i.e. the local vertical local horizontal (LVLH) frame, where +Y is along the negative orbit normal.
+    ///
+    /// With yaw steering, the spacecraft instead yaws about +Z such that +X is as close as possible to the Sun, i.e. the Sun stays
+    /// in the XZ plane, as needed by solar arrays rotating about +Y.
+    Nadir { yaw_steering: bool },
+    /// +Z points to the Sun and +X is as close as possible to the nadir, e.g. to keep an antenna on the +X face toward the Earth.
+    SunPointing,
+    /// +Z points to the target, whose state is provided in its body fixed frame (e.g. a ground station built with
+    /// `Orbit::try_latlongalt`), and +X is as close as possible to the velocity of the spacecraft with respect to the central body.
+    TargetTracking { target: Orbit },
+}
+
+/// Time series of the ideal attitude of a spacecraft computed by [Almanac::pointing_attitude], with respect to J2000.
+///
+/// It is an orientation provider, so it can be registered in an Almanac with [Almanac::with_orientation_provider] to use the
+/// body frame like any other frame, where the attitude between two samples is interpolated with a SLERP.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PointingAttitude {
+    /// Orientation ID of the body frame
+    pub orientation_id: NaifId,
+    /// Rotations from J2000 to the body frame, sorted by epoch
+    pub samples: Vec<AttitudeSample>,
+}
+
+impl OrientationProvider for PointingAttitude {
+    fn orientation_id(&self) -> NaifId {
+        self.orientation_id
+    }
+
+    fn parent_id(&self) -> NaifId {
+        J2000
+    }
+
+    fn covers(&self, epoch: Epoch) -> bool {
+        match (self.samples.first(), self.samples.last()) {
+            (Some(first), Some(last)) => first.epoch <= epoch && epoch <= last.epoch,
+            _ => false,
+        }
+    }
+
+    fn rotation(&self, epoch: Epoch) -> Result<DCM, OrientationError> {
+        let idx = self.samples.partition_point(|sample| sample.epoch < epoch);
+        let after = self.samples.get(idx).ok_or(OrientationError::Provider {
+            id: self.orientation_id,
+            reason: format!("{epoch} is after the last sample"),
+        })?;
+        if after.epoch == epoch || idx == 0 {
+            return Ok(after.q.into());
+        }
+
+        let before = &self.samples[idx - 1];
+        let t = (epoch - before.epoch).to_seconds() / (after.epoch - before.epoch).to_seconds();
+        let q = before
+            .q
+            .slerp(&after.q, t)
+            .map_err(|e| OrientationError::Provider {
+                id: self.orientation_id,
+                reason: e.to_string(),
+            })?;
+        Ok(q.into())
+    }
+}
+
+impl Almanac {
+    /// Computes the ideal attitude of the spacecraft following the pointing profile at each of the provided epochs, e.g. to
+    /// register its body frame (with the provided orientation ID) for sensor analyses.
+    ///
+    /// The ephemeris of the spacecraft, of the central body, and of the Sun (for the profiles using it) must be loaded.
+    /// The central body is the reference of the nadir direction and of the velocity of the spacecraft.
+    pub fn pointing_attitude(
+        &self,
+        profile: PointingProfile,
+        spacecraft: Frame,
+        central_body: Frame,
+        orientation_id: NaifId,
+        epochs: &[Epoch],
+    ) -> AlmanacResult<PointingAttitude> {
+        let spacecraft = spacecraft.with_orient(J2000);
+        let central_body = central_body.with_orient(J2000);
+
+        let mut samples = Vec::with_capacity(epochs.len());
+        for epoch in epochs.iter().copied() {
+            // State of the spacecraft with respect to the central body, in J2000
+            let state = self.transform(spacecraft, central_body, epoch, None)?;
+            let nadir = -state.radius_km;
+
+            let (primary, secondary) = match profile {
+                PointingProfile::Nadir {
+                    yaw_steering: false,
+                } => (nadir, state.velocity_km_s),
+                PointingProfile::Nadir { yaw_steering: true } => (
+                    nadir,
+                    self.transform(SUN_J2000, spacecraft, epoch, None)?
+                        .radius_km,
+                ),
+                PointingProfile::SunPointing => (
+                    self.transform(SUN_J2000, spacecraft, epoch, None)?
+                        .radius_km,
+                    nadir,
+                ),
+                PointingProfile::TargetTracking { mut target } => {
+                    target.epoch = epoch;
+                    (
+                        self.transform_to(target, spacecraft, None)?.radius_km,
+                        state.velocity_km_s,
+                    )
+                }
+            };
+
+            let rot_mat = align_constrain(&primary, &secondary).ok_or_else(|| {
+                AlmanacError::GenericError {
+                    err: format!(
+                        "pointing directions of {spacecraft} are undefined or collinear at {epoch}"
+                    ),
+                }
+            })?;
+
+            let q: Quaternion = DCM {
+                rot_mat,
+                rot_mat_dt: None,
+                from: J2000,
+                to: orientation_id,
+            }
+            .into();
+
+            samples.push(AttitudeSample {
+                epoch,
+                q,
+                angular_velocity_rad_s: None,
+            });
+        }
+
+        Ok(PointingAttitude {
+            orientation_id,
+            samples,
+        })
+    }
+}
+
+/// Returns the rotation from the frame of the provided vectors to the body frame whose +Z axis is along the primary vector and
+/// whose +X axis is as close as possible to the secondary vector, or None if they are zero or collinear.
+fn align_constrain(primary: &Vector3, secondary: &Vector3) -> Option<Matrix3> {
+    let z_axis = primary.try_normalize(f64::EPSILON)?;
+    let x_axis =
+        (secondary - secondary.dot(&z_axis) * z_axis).try_normalize(1e-12 * secondary.norm())?;
+    let y_axis = z_axis.cross(&x_axis);
+
+    // The rows of the rotation are the axes of the body frame.
+    Some(Matrix3::from_rows(&[
+        x_axis.transpose(),
+        y_axis.transpose(),
+        z_axis.transpose(),
+    ]))
+}
+
+#[cfg(test)]
+mod ut_pointing {
+    use super::align_constrain;
+    use crate::math::Vector3;
+
+    #[test]
+    fn lvlh_axes() {
+        let radius_km = Vector3::new(7000.0, 0.0, 0.0);
+        let velocity_km_s = Vector3::new(0.0, 7.5, 0.1);
+
+        let rot_mat = align_constrain(&-radius_km, &velocity_km_s).unwrap();
+        // Nadir is +Z, the velocity is mostly +X, and the orbit normal is -Y.
+        assert!((rot_mat * -radius_km.normalize() - Vector3::z()).norm() < 1e-14);
+        assert!((rot_mat * velocity_km_s.normalize()).x > 0.99);
+        assert!(
+            (rot_mat * radius_km.cross(&velocity_km_s).normalize() + Vector3::y()).norm() < 1e-14
+        );
+        assert!((rot_mat.determinant() - 1.0).abs() < 1e-15);
+
+        assert!(align_constrain(&radius_km, &(2.0 * radius_km)).is_none());
+        assert!(align_constrain(&Vector3::zeros(), &velocity_km_s).is_none());
+    }
+}
@@ -0,0 +1,486 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::Epoch;
+use log::info;
+use snafu::ensure;
+
+use crate::almanac::Almanac;
+use crate::astro::Aberration;
+use crate::errors::{AlmanacResult, GenericSnafu};
+use crate::math::Vector3;
+use crate::prelude::Frame;
+use crate::structure::planetocentric::PlanetaryData;
+use crate::NaifId;
+
+/// Compares the states computed by two Almanacs (e.g. an old and a new set of kernels) for a matrix of queries.
+///
+/// Each pair of target and observer frames is queried at each epoch with the `transform` function of both Almanacs,
+/// and the differences in position and velocity are summarized per pair.
+///
+/// # Example
+/// ```no_run
+/// use anise::compare::Comparator;
+/// use anise::constants::frames::{EARTH_J2000, MOON_J2000};
+/// use anise::prelude::*;
+///
+/// let reference = Almanac::new("../data/de440s.bsp").unwrap();
+/// let candidate = Almanac::new("../data/de440.bsp").unwrap();
+///
+/// let start = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+/// let report = Comparator::new(reference, candidate)
+///     .with_pair(MOON_J2000, EARTH_J2000)
+///     .with_epochs(TimeSeries::inclusive(start, start + 1.days(), 1.hours()))
+///     .compare()
+///     .unwrap();
+///
+/// println!("{report}");
+/// ```
+#[derive(Clone, Default)]
+pub struct Comparator {
+    /// Almanac used as the reference of the comparison
+    pub reference: Almanac,
+    /// Almanac compared to the reference
+    pub candidate: Almanac,
+    /// Pairs of target and observer frames to compare
+    pub pairs: Vec<(Frame, Frame)>,
+    /// Epochs at which each pair is compared
+    pub epochs: Vec<Epoch>,
+    /// Aberration correction of all of the queries
+    pub ab_corr: Option<Aberration>,
+}
+
+impl Comparator {
+    /// Initializes a new comparison of the candidate Almanac against the reference Almanac, without any query.
+    pub fn new(reference: Almanac, candidate: Almanac) -> Self {
+        Self {
+            reference,
+            candidate,
+            ..Default::default()
+        }
+    }
+
+    /// Adds a pair of target and observer frames to compare.
+    pub fn with_pair(mut self, target_frame: Frame, observer_frame: Frame) -> Self {
+        self.pairs.push((target_frame, observer_frame));
+        self
+    }
+
+    /// Adds the provided epochs to the epochs of the comparison.
+    pub fn with_epochs<I: IntoIterator<Item = Epoch>>(mut self, epochs: I) -> Self {
+        self.epochs.extend(epochs);
+        self
+    }
+
+    /// Sets the aberration correction of the queries.
+    pub fn with_aberration(mut self, ab_corr: Option<Aberration>) -> Self {
+        self.ab_corr = ab_corr;
+        self
+    }
+
+    /// Runs all of the queries and returns the statistics of the differences for each pair.
+    pub fn compare(&self) -> AlmanacResult<ComparisonReport> {
+        ensure!(
+            !self.pairs.is_empty() && !self.epochs.is_empty(),
+            GenericSnafu {
+                err: "comparison requires at least one pair of frames and one epoch"
+            }
+        );
+
+        let mut pair_stats = Vec::with_capacity(self.pairs.len());
+
+        for (target_frame, observer_frame) in &self.pairs {
+            let mut stats = PairStatistics::new(*target_frame, *observer_frame);
+
+            for epoch in &self.epochs {
+                let reference =
+                    self.reference
+                        .transform(*target_frame, *observer_frame, *epoch, self.ab_corr);
+                let candidate =
+                    self.candidate
+                        .transform(*target_frame, *observer_frame, *epoch, self.ab_corr);
+
+                match (reference, candidate) {
+                    (Ok(reference), Ok(candidate)) => stats.add(
+                        *epoch,
+                        (reference.radius_km - candidate.radius_km).norm(),
+                        (reference.velocity_km_s - candidate.velocity_km_s).norm(),
+                    ),
+                    (Err(_), Err(_)) => stats.num_both_failed += 1,
+                    _ => stats.num_one_failed += 1,
+                }
+            }
+
+            info!("{stats}");
+            pair_stats.push(stats);
+        }
+
+        Ok(ComparisonReport { pairs: pair_stats })
+    }
+}
+
+impl Comparator {
+    /// Returns the difference of the states of each pair at each epoch, skipping the queries which failed with either Almanac.
+    pub fn state_differences(&self) -> AlmanacResult<Vec<StateDifference>> {
+        ensure!(
+            !self.pairs.is_empty() && !self.epochs.is_empty(),
+            GenericSnafu {
+                err: "comparison requires at least one pair of frames and one epoch"
+            }
+        );
+
+        let mut differences = Vec::with_capacity(self.pairs.len() * self.epochs.len());
+
+        for (target_frame, observer_frame) in &self.pairs {
+            for epoch in &self.epochs {
+                if let (Ok(reference), Ok(candidate)) = (
+                    self.reference
+                        .transform(*target_frame, *observer_frame, *epoch, self.ab_corr),
+                    self.candidate
+                        .transform(*target_frame, *observer_frame, *epoch, self.ab_corr),
+                ) {
+                    differences.push(StateDifference {
+                        target_frame: *target_frame,
+                        observer_frame: *observer_frame,
+                        epoch: *epoch,
+                        radius_km: candidate.radius_km - reference.radius_km,
+                        velocity_km_s: candidate.velocity_km_s - reference.velocity_km_s,
+                    });
+                }
+            }
+        }
+
+        Ok(differences)
+    }
+
+    /// Returns the planetary constants (gravitational parameter, radii, and pole and prime meridian terms) which differ
+    /// between the planetary data of both Almanacs, for each body defined by ID in either of them, sorted by ID.
+    ///
+    /// The constants are compared exactly, and a constant defined in only one of the Almanacs is reported as a difference.
+    pub fn planetary_differences(&self) -> AlmanacResult<Vec<ConstantDifference>> {
+        let mut ids = self
+            .reference
+            .planetary_data
+            .lut
+            .by_id
+            .keys()
+            .chain(self.candidate.planetary_data.lut.by_id.keys())
+            .copied()
+            .collect::<Vec<NaifId>>();
+        ids.sort();
+        ids.dedup();
+
+        let mut differences = Vec::new();
+
+        for id in ids {
+            let reference = self.reference.planetary_data.get_by_id(id).ok();
+            let candidate = self.candidate.planetary_data.get_by_id(id).ok();
+
+            let reference_constants = planetary_constants(reference.as_ref());
+            let candidate_constants = planetary_constants(candidate.as_ref());
+
+            for ((constant, reference), (_, candidate)) in
+                reference_constants.into_iter().zip(candidate_constants)
+            {
+                if reference != candidate {
+                    differences.push(ConstantDifference {
+                        id,
+                        constant,
+                        reference,
+                        candidate,
+                    });
+                }
+            }
+        }
+
+        Ok(differences)
+    }
+}
+
+/// Returns the comparable planetary constants of a body, in a fixed order, where the constants which are not defined are None.
+fn planetary_constants(data: Option<&PlanetaryData>) -> Vec<(&'static str, Option<f64>)> {
+    let shape = data.and_then(|data| data.shape);
+    let mut constants = vec![
+        ("mu_km3_s2", data.map(|data| data.mu_km3_s2)),
+        (
+            "semi_major_equatorial_radius_km",
+            shape.map(|shape| shape.semi_major_equatorial_radius_km),
+        ),
+        (
+            "semi_minor_equatorial_radius_km",
+            shape.map(|shape| shape.semi_minor_equatorial_radius_km),
+        ),
+        ("polar_radius_km", shape.map(|shape| shape.polar_radius_km)),
+    ];
+
+    for (names, phase) in [
+        (
+            [
+                "pole_right_ascension.offset_deg",
+                "pole_right_ascension.rate_deg",
+                "pole_right_ascension.accel_deg",
+            ],
+            data.and_then(|data| data.pole_right_ascension),
+        ),
+        (
+            [
+                "pole_declination.offset_deg",
+                "pole_declination.rate_deg",
+                "pole_declination.accel_deg",
+            ],
+            data.and_then(|data| data.pole_declination),
+        ),
+        (
+            [
+                "prime_meridian.offset_deg",
+                "prime_meridian.rate_deg",
+                "prime_meridian.accel_deg",
+            ],
+            data.and_then(|data| data.prime_meridian),
+        ),
+    ] {
+        constants.push((names[0], phase.map(|phase| phase.offset_deg)));
+        constants.push((names[1], phase.map(|phase| phase.rate_deg)));
+        constants.push((names[2], phase.map(|phase| phase.accel_deg)));
+    }
+
+    constants.push(("long_axis", data.and_then(|data| data.long_axis)));
+    constants
+}
+
+/// Difference of the state of a pair of frames at an epoch, computed as the candidate minus the reference.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StateDifference {
+    pub target_frame: Frame,
+    pub observer_frame: Frame,
+    pub epoch: Epoch,
+    pub radius_km: Vector3,
+    pub velocity_km_s: Vector3,
+}
+
+/// Planetary constant of a body which differs between the reference and the candidate Almanacs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConstantDifference {
+    pub id: NaifId,
+    /// Name of the constant, e.g. `mu_km3_s2` or `pole_declination.rate_deg`
+    pub constant: &'static str,
+    /// Value in the reference Almanac, if defined
+    pub reference: Option<f64>,
+    /// Value in the candidate Almanac, if defined
+    pub candidate: Option<f64>,
+}
+
+impl ConstantDifference {
+    /// Returns the candidate value minus the reference value, if the constant is defined in both Almanacs.
+    pub fn difference(&self) -> Option<f64> {
+        Some(self.candidate? - self.reference?)
+    }
+}
+
+impl fmt::Display for ConstantDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fmt_val =
+            |val: Option<f64>| val.map_or("undefined".to_string(), |val| format!("{val}"));
+        write!(
+            f,
+            "{} {}: {} -> {}",
+            self.id,
+            self.constant,
+            fmt_val(self.reference),
+            fmt_val(self.candidate)
+        )
+    }
+}
+
+/// Statistics of the differences between two Almanacs for a given pair of frames.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PairStatistics {
+    pub target_frame: Frame,
+    pub observer_frame: Frame,
+    /// Number of queries which succeeded with both Almanacs
+    pub num_compared: usize,
+    /// Number of queries which failed with only one of the Almanacs, e.g. because of different time spans
+    pub num_one_failed: usize,
+    /// Number of queries which failed with both Almanacs
+    pub num_both_failed: usize,
+    pub max_pos_err_km: f64,
+    /// Epoch of the largest position difference
+    pub max_pos_err_epoch: Option<Epoch>,
+    pub mean_pos_err_km: f64,
+    pub rms_pos_err_km: f64,
+    pub max_vel_err_km_s: f64,
+    /// Epoch of the largest velocity difference
+    pub max_vel_err_epoch: Option<Epoch>,
+    pub mean_vel_err_km_s: f64,
+    pub rms_vel_err_km_s: f64,
+}
+
+impl PairStatistics {
+    fn new(target_frame: Frame, observer_frame: Frame) -> Self {
+        Self {
+            target_frame,
+            observer_frame,
+            num_compared: 0,
+            num_one_failed: 0,
+            num_both_failed: 0,
+            max_pos_err_km: 0.0,
+            max_pos_err_epoch: None,
+            mean_pos_err_km: 0.0,
+            rms_pos_err_km: 0.0,
+            max_vel_err_km_s: 0.0,
+            max_vel_err_epoch: None,
+            mean_vel_err_km_s: 0.0,
+            rms_vel_err_km_s: 0.0,
+        }
+    }
+
+    /// Adds a comparison to these statistics, updating the running mean and RMS.
+    fn add(&mut self, epoch: Epoch, pos_err_km: f64, vel_err_km_s: f64) {
+        let prev_n = self.num_compared as f64;
+        self.num_compared += 1;
+        let n = self.num_compared as f64;
+
+        if self.max_pos_err_epoch.is_none() || pos_err_km > self.max_pos_err_km {
+            self.max_pos_err_km = pos_err_km;
+            self.max_pos_err_epoch = Some(epoch);
+        }
+        if self.max_vel_err_epoch.is_none() || vel_err_km_s > self.max_vel_err_km_s {
+            self.max_vel_err_km_s = vel_err_km_s;
+            self.max_vel_err_epoch = Some(epoch);
+        }
+
+        self.mean_pos_err_km = (self.mean_pos_err_km * prev_n + pos_err_km) / n;
+        self.mean_vel_err_km_s = (self.mean_vel_err_km_s * prev_n + vel_err_km_s) / n;
+        self.rms_pos_err_km =
+            ((self.rms_pos_err_km.powi(2) * prev_n + pos_err_km.powi(2)) / n).sqrt();
+        self.rms_vel_err_km_s =
+            ((self.rms_vel_err_km_s.powi(2) * prev_n + vel_err_km_s.powi(2)) / n).sqrt();
+    }
+}
+
+impl fmt::Display for PairStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} wrt {}: {} compared ({} failed in one, {} failed in both)",
+            self.target_frame,
+            self.observer_frame,
+            self.num_compared,
+            self.num_one_failed,
+            self.num_both_failed
+        )?;
+        if let (Some(pos_epoch), Some(vel_epoch)) = (self.max_pos_err_epoch, self.max_vel_err_epoch)
+        {
+            write!(
+                f,
+                "\tposition: max {:.3e} km @ {pos_epoch}, mean {:.3e} km, RMS {:.3e} km\tvelocity: max {:.3e} km/s @ {vel_epoch}, mean {:.3e} km/s, RMS {:.3e} km/s",
+                self.max_pos_err_km,
+                self.mean_pos_err_km,
+                self.rms_pos_err_km,
+                self.max_vel_err_km_s,
+                self.mean_vel_err_km_s,
+                self.rms_vel_err_km_s
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Results of a comparison, one set of statistics per pair of frames.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComparisonReport {
+    pub pairs: Vec<PairStatistics>,
+}
+
+impl ComparisonReport {
+    /// Returns the statistics of the pair with the largest position difference, if any query succeeded.
+    pub fn worst_pair(&self) -> Option<&PairStatistics> {
+        self.pairs
+            .iter()
+            .filter(|stats| stats.num_compared > 0)
+            .max_by(|a, b| a.max_pos_err_km.total_cmp(&b.max_pos_err_km))
+    }
+
+    /// Returns true if all of the queries which succeeded with both Almanacs differ by at most the provided tolerances,
+    /// and no query failed with only one of them.
+    pub fn is_within(&self, pos_tol_km: f64, vel_tol_km_s: f64) -> bool {
+        self.pairs.iter().all(|stats| {
+            stats.num_one_failed == 0
+                && stats.max_pos_err_km <= pos_tol_km
+                && stats.max_vel_err_km_s <= vel_tol_km_s
+        })
+    }
+}
+
+impl fmt::Display for ComparisonReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for stats in &self.pairs {
+            writeln!(f, "{stats}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod ut_compare {
+    use super::Comparator;
+    use crate::almanac::Almanac;
+    use crate::constants::frames::{EARTH_J2000, MOON_J2000};
+    use hifitime::Epoch;
+
+    #[test]
+    fn empty_comparison() {
+        let comparator = Comparator::new(Almanac::default(), Almanac::default());
+        assert!(comparator.compare().is_err());
+
+        // Queries which fail with both Almanacs are counted as such
+        let report = comparator
+            .with_pair(MOON_J2000, EARTH_J2000)
+            .with_epochs([Epoch::from_gregorian_utc_at_midnight(2025, 1, 1)])
+            .compare()
+            .unwrap();
+
+        assert_eq!(report.pairs[0].num_both_failed, 1);
+        assert_eq!(report.pairs[0].num_compared, 0);
+        assert!(report.worst_pair().is_none());
+        assert!(report.is_within(0.0, 0.0));
+        assert!(comparator.state_differences().unwrap().is_empty());
+    }
+
+    #[test]
+    fn planetary_differences() {
+        let reference = Almanac::default().load("../data/pck08.pca").unwrap();
+        let mut earth = reference.planetary_data.get_by_id(399).unwrap();
+        let long_axis = earth.long_axis;
+        earth.mu_km3_s2 += 1.0;
+        earth.long_axis = Some(long_axis.unwrap_or_default() + 0.5);
+        let mut candidate = reference.clone();
+        candidate.planetary_data.set_by_id(399, earth).unwrap();
+
+        let comparator = Comparator::new(reference.clone(), reference.clone());
+        assert!(comparator.planetary_differences().unwrap().is_empty());
+
+        let differences = Comparator::new(reference, candidate)
+            .planetary_differences()
+            .unwrap();
+        assert_eq!(differences.len(), 2);
+        assert_eq!(differences[0].id, 399);
+        assert_eq!(differences[0].constant, "mu_km3_s2");
+        assert!((differences[0].difference().unwrap() - 1.0).abs() < 1e-9);
+        assert_eq!(differences[1].constant, "long_axis");
+        assert_eq!(differences[1].reference, long_axis);
+        assert_eq!(
+            differences[1].candidate,
+            Some(long_axis.unwrap_or_default() + 0.5)
+        );
+    }
+}
@@ -9,7 +9,10 @@
  */
 
 /// Speed of light in kilometers per second (km/s)
-pub const SPEED_OF_LIGHT_KM_S: f64 = 299_792.458;
+pub const SPEED_OF_LIGHT_KM_S: f64 = registry::SPEED_OF_LIGHT.value;
+
+/// Astronomical unit in kilometers (km)
+pub const AU_KM: f64 = registry::ASTRONOMICAL_UNIT.value;
 
 pub mod celestial_objects {
     use crate::{ephemerides::EphemerisError, NaifId};
@@ -34,6 +37,29 @@ pub mod celestial_objects {
     pub const NEPTUNE: NaifId = 899;
     pub const PLUTO: NaifId = 999;
 
+    /// Bodies of the compact planetary constants dataset embedded with the `embed-constants` feature: the Sun, the planets, the
+    /// Moon, and the planetary system barycenters, whose nutation precession angles are used by the IAU rotations of their bodies.
+    pub const MAJOR_BODIES: [NaifId; 18] = [
+        SUN,
+        199,
+        299,
+        EARTH,
+        MOON,
+        MARS,
+        JUPITER,
+        SATURN,
+        URANUS,
+        NEPTUNE,
+        PLUTO,
+        EARTH_MOON_BARYCENTER,
+        MARS_BARYCENTER,
+        JUPITER_BARYCENTER,
+        SATURN_BARYCENTER,
+        URANUS_BARYCENTER,
+        NEPTUNE_BARYCENTER,
+        PLUTO_BARYCENTER,
+    ];
+
     pub const fn celestial_name_from_id(id: NaifId) -> Option<&'static str> {
         match id {
             SOLAR_SYSTEM_BARYCENTER => Some("Solar System Barycenter"),
@@ -54,6 +80,8 @@ pub mod celestial_objects {
     }
 
     /// Converts the provided ID to its human name. Only works for the common celestial bodies. Should be compatible with CCSDS OEM names
+    ///
+    /// Note that `ICRF` is J2000, as in SPICE and in CCSDS OEM files: use the [ICRF] ID for the biased frame.
     pub fn id_to_celestial_name(name: &str) -> Result<NaifId, EphemerisError> {
         match name {
             "Mercury" => Ok(MERCURY),
@@ -220,11 +248,20 @@ pub mod orientations {
     pub const IAU_EARTH: NaifId = 399;
     /// High fidelity Earth frame orientation by the NAIF, requires the "Earth high prec" BPC kernel
     pub const ITRF93: NaifId = 3000;
+    /// Fast, low fidelity, Earth fixed orientation of ANISE (not a NAIF ID) computed from the IAU 1976/1980 models and
+    /// the UT1-UTC of the constants registry (`UT1_MINUS_UTC`), accurate to about 10 meters at LEO, cf. `earth_fast_rotation`
+    pub const ITRF93_FAST: NaifId = 3099;
+    /// International Celestial Reference Frame of ANISE (not a NAIF ID), which differs from [J2000] by the constant frame bias
+    /// of about 0.023 arcseconds only if enabled with `Almanac::with_frame_bias`, cf. `icrf_frame_bias`. Otherwise, and as in
+    /// SPICE, it is identical to J2000: the planetary ephemerides (e.g. DE440) are aligned with the ICRF although labeled J2000.
+    pub const ICRF: NaifId = 1099;
     /// Low fidelity Moon frame orientation by the International Astronomical Union (IAU)
     pub const IAU_MOON: NaifId = 301;
     /// High fidelity Moon Mean Earth equator orientation frame (used for cartography), requires the Moon PA BPC kernel
+    /// and either the lunar frames kernel or `Almanac::with_moon_frames_de440`
     pub const MOON_ME: NaifId = 31001;
     /// High fidelity Moon Principal Axes orientation frame (used for gravity field and mass concentrations), requires the Moon PA BPC kernel
+    /// and either the lunar frames kernel or `Almanac::with_moon_frames_de440`
     pub const MOON_PA: NaifId = 31000;
     /// High fidelity Moon Mean Earth orientation frame of the DE421 (used for cartography), requires the Moon PA BPC kernel
     pub const MOON_ME_DE421: NaifId = 31007;
@@ -241,7 +278,7 @@ pub mod orientations {
     pub const IAU_URANUS: NaifId = 899;
 
     /// Angle between J2000 to solar system ecliptic J2000 ([ECLIPJ2000]), in radians (about 23.43929 degrees). Apply this rotation about the X axis (R1)
-    pub const J2000_TO_ECLIPJ2000_ANGLE_RAD: f64 = 0.40909280422232897;
+    pub const J2000_TO_ECLIPJ2000_ANGLE_RAD: f64 = super::registry::OBLIQUITY_J2000_IAU1976.value;
 
     /// Given the frame ID, try to return a human name
     /// Source: <https://naif.jpl.nasa.gov/pub/naif/toolkit_docs/C/req/frames.html#Appendix.%20%60%60Built%20in''%20Inertial%20Reference%20Frames>
@@ -261,6 +298,8 @@ pub mod orientations {
             MOON_ME => Some("MOON_ME"),
             MOON_PA => Some("MOON_PA"),
             ITRF93 => Some("ITRF93"),
+            ITRF93_FAST => Some("ITRF93_FAST"),
+            ICRF => Some("ICRF"),
             IAU_MARS => Some("IAU_MARS"),
             IAU_JUPITER => Some("IAU_JUPITER"),
             IAU_SATURN => Some("IAU_SATURN"),
@@ -287,6 +326,7 @@ pub mod orientations {
             "MOON_ME" => Ok(MOON_ME),
             "MOON_PA" => Ok(MOON_PA),
             "ITRF93" => Ok(ITRF93),
+            "ITRF93_FAST" => Ok(ITRF93_FAST),
             "IAU_MARS" => Ok(IAU_MARS),
             "IAU_JUPITER" => Ok(IAU_JUPITER),
             "IAU_SATURN" => Ok(IAU_SATURN),
@@ -319,6 +359,9 @@ pub mod frames {
     pub const EARTH_J2000: Frame = Frame::new(EARTH, J2000);
     pub const EME2000: Frame = Frame::new(EARTH, J2000);
     pub const EARTH_ECLIPJ2000: Frame = Frame::new(EARTH, ECLIPJ2000);
+    /// Solar system barycentric ICRF, cf. [ICRF](super::orientations::ICRF) for its difference with J2000
+    pub const ICRF: Frame = Frame::new(SOLAR_SYSTEM_BARYCENTER, super::orientations::ICRF);
+    pub const EARTH_ICRF: Frame = Frame::new(EARTH, super::orientations::ICRF);
 
     /// Body fixed IAU rotation
     pub const IAU_MERCURY_FRAME: Frame = Frame::new(MERCURY, IAU_MERCURY);
@@ -347,13 +390,16 @@ pub mod frames {
 
     /// High fidelity Earth centered body fixed frame by the NAIF, requires the "Earth high prec" BPC kernel
     pub const EARTH_ITRF93: Frame = Frame::new(EARTH, ITRF93);
+    /// Fast, low fidelity, Earth centered body fixed frame which requires no kernel, cf. [ITRF93_FAST]
+    pub const EARTH_ITRF93_FAST: Frame = Frame::new(EARTH, ITRF93_FAST);
 }
 
 /// Typical planetary constants that aren't found in SPICE input files.
 pub mod usual_planetary_constants {
     /// Mean angular velocity of the Earth in deg/s
     /// Source: G. Xu and Y. Xu, "GPS", DOI 10.1007/978-3-662-50367-6_2, 2016 (confirmed by <https://hpiers.obspm.fr/eop-pc/models/constants.html>)
-    pub const MEAN_EARTH_ANGULAR_VELOCITY_DEG_S: f64 = 0.004178079012116429;
+    pub const MEAN_EARTH_ANGULAR_VELOCITY_DEG_S: f64 =
+        super::registry::MEAN_EARTH_ANGULAR_VELOCITY.value;
     /// Mean angular velocity of the Moon in deg/s, computed from hifitime:
     /// ```py
     /// >>> moon_period = Unit.Day*27+Unit.Hour*7+Unit.Minute*43+Unit.Second*12
@@ -361,7 +407,322 @@ pub mod usual_planetary_constants {
     /// 2.661698975163682e-06
     /// ```
     /// Source: <https://www.britannica.com/science/month#ref225844> via <https://en.wikipedia.org/w/index.php?title=Lunar_day&oldid=1180701337>
-    pub const MEAN_MOON_ANGULAR_VELOCITY_DEG_S: f64 = 2.661_698_975_163_682e-6;
+    pub const MEAN_MOON_ANGULAR_VELOCITY_DEG_S: f64 =
+        super::registry::MEAN_MOON_ANGULAR_VELOCITY.value;
+}
+
+/// Registry of the physical constants used by ANISE, with their units and sources.
+///
+/// Each constant is available as a `const` in Rust, and by its name at runtime with [constant_from_name](registry::constant_from_name),
+/// e.g. for scripting. An Almanac may override any of these constants, cf. `Almanac::with_constant`.
+pub mod registry {
+    use crate::{constants::celestial_objects::*, NaifId};
+
+    /// A physical constant, its unit, and its source.
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub struct Constant {
+        /// Name of this constant in the registry, e.g. `SPEED_OF_LIGHT`
+        pub name: &'static str,
+        pub value: f64,
+        /// Unit of the value, e.g. `km/s`
+        pub unit: &'static str,
+        pub source: &'static str,
+    }
+
+    pub const SPEED_OF_LIGHT: Constant = Constant {
+        name: "SPEED_OF_LIGHT",
+        value: 299_792.458,
+        unit: "km/s",
+        source: "exact, SI definition of the meter",
+    };
+
+    pub const ASTRONOMICAL_UNIT: Constant = Constant {
+        name: "ASTRONOMICAL_UNIT",
+        value: 149_597_870.7,
+        unit: "km",
+        source: "exact, IAU 2012 Resolution B2",
+    };
+
+    /// Obliquity of the ecliptic at J2000 of the IAU 1976 precession model (84381.448 arcseconds), used to define [ECLIPJ2000](super::orientations::ECLIPJ2000)
+    pub const OBLIQUITY_J2000_IAU1976: Constant = Constant {
+        name: "OBLIQUITY_J2000_IAU1976",
+        value: 0.40909280422232897,
+        unit: "rad",
+        source: "Explanatory Supplement to the Astronomical Almanac (1992), equation 3.222-1",
+    };
+
+    /// Obliquity of the ecliptic at J2000 of the IAU 2006 precession model (84381.406 arcseconds)
+    pub const OBLIQUITY_J2000_IAU2006: Constant = Constant {
+        name: "OBLIQUITY_J2000_IAU2006",
+        value: 0.40909260060058283,
+        unit: "rad",
+        source: "IAU 2006 Resolution B1, Capitaine et al. (2003)",
+    };
+
+    pub const MEAN_EARTH_ANGULAR_VELOCITY: Constant = Constant {
+        name: "MEAN_EARTH_ANGULAR_VELOCITY",
+        value: 0.004178079012116429,
+        unit: "deg/s",
+        source: "G. Xu and Y. Xu, \"GPS\", DOI 10.1007/978-3-662-50367-6_2, 2016",
+    };
+
+    pub const MEAN_MOON_ANGULAR_VELOCITY: Constant = Constant {
+        name: "MEAN_MOON_ANGULAR_VELOCITY",
+        value: 2.661_698_975_163_682e-6,
+        unit: "deg/s",
+        source: "sidereal month of 27 d 7 h 43 min 12 s",
+    };
+
+    /// Difference between UT1 and UTC, used by the fast Earth orientation ([ITRF93_FAST](super::orientations::ITRF93_FAST)),
+    /// which should be overridden with the value of the IERS bulletins for the epochs of interest.
+    pub const UT1_MINUS_UTC: Constant = Constant {
+        name: "UT1_MINUS_UTC",
+        value: 0.0,
+        unit: "s",
+        source: "approximation, |UT1-UTC| < 0.9 s by definition of UTC (IERS Bulletin A)",
+    };
+
+    /// Second zonal harmonic of the gravity field of the Earth (unnormalized), with respect to [J2_EARTH_REFERENCE_RADIUS]
+    pub const J2_EARTH: Constant = Constant {
+        name: "J2_EARTH",
+        value: 1.082_626_173_852_2e-3,
+        unit: "",
+        source: "EGM2008, unnormalized C20",
+    };
+
+    pub const J2_EARTH_REFERENCE_RADIUS: Constant = Constant {
+        name: "J2_EARTH_REFERENCE_RADIUS",
+        value: 6_378.136_3,
+        unit: "km",
+        source: "EGM2008",
+    };
+
+    const GM_SOURCE: &str = "JPL DE440 (gm_de440.tpc)";
+
+    pub const GM_SUN: Constant = Constant {
+        name: "GM_SUN",
+        value: 1.327_124_400_412_794_2e11,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_MERCURY: Constant = Constant {
+        name: "GM_MERCURY",
+        value: 2.203_186_855_140_000_3e4,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_VENUS: Constant = Constant {
+        name: "GM_VENUS",
+        value: 3.248_585_920_000_000_0e5,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_EARTH_MOON_BARYCENTER: Constant = Constant {
+        name: "GM_EARTH_MOON_BARYCENTER",
+        value: 4.035_032_356_254_801_9e5,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_EARTH: Constant = Constant {
+        name: "GM_EARTH",
+        value: 3.986_004_355_070_226_6e5,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_MOON: Constant = Constant {
+        name: "GM_MOON",
+        value: 4.902_800_118_457_549_6e3,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_MARS_BARYCENTER: Constant = Constant {
+        name: "GM_MARS_BARYCENTER",
+        value: 4.282_837_581_575_610_2e4,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_MARS: Constant = Constant {
+        name: "GM_MARS",
+        value: 4.282_837_362_069_909e4,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_JUPITER_BARYCENTER: Constant = Constant {
+        name: "GM_JUPITER_BARYCENTER",
+        value: 1.267_127_640_999_999_8e8,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_JUPITER: Constant = Constant {
+        name: "GM_JUPITER",
+        value: 1.266_865_319_003_704e8,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_SATURN_BARYCENTER: Constant = Constant {
+        name: "GM_SATURN_BARYCENTER",
+        value: 3.794_058_484_179_999_7e7,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_SATURN: Constant = Constant {
+        name: "GM_SATURN",
+        value: 3.793_120_623_436_167e7,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_URANUS_BARYCENTER: Constant = Constant {
+        name: "GM_URANUS_BARYCENTER",
+        value: 5.794_556_399_999_998_5e6,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_URANUS: Constant = Constant {
+        name: "GM_URANUS",
+        value: 5.793_951_256_527_211e6,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_NEPTUNE_BARYCENTER: Constant = Constant {
+        name: "GM_NEPTUNE_BARYCENTER",
+        value: 6.836_527_100_580_398_9e6,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_NEPTUNE: Constant = Constant {
+        name: "GM_NEPTUNE",
+        value: 6.835_103_145_462_294e6,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_PLUTO_BARYCENTER: Constant = Constant {
+        name: "GM_PLUTO_BARYCENTER",
+        value: 9.755e2,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    pub const GM_PLUTO: Constant = Constant {
+        name: "GM_PLUTO",
+        value: 8.696_138_177_608_748e2,
+        unit: "km^3/s^2",
+        source: GM_SOURCE,
+    };
+
+    /// All of the constants of the registry
+    pub const CONSTANTS: [Constant; 27] = [
+        SPEED_OF_LIGHT,
+        ASTRONOMICAL_UNIT,
+        OBLIQUITY_J2000_IAU1976,
+        OBLIQUITY_J2000_IAU2006,
+        MEAN_EARTH_ANGULAR_VELOCITY,
+        MEAN_MOON_ANGULAR_VELOCITY,
+        UT1_MINUS_UTC,
+        J2_EARTH,
+        J2_EARTH_REFERENCE_RADIUS,
+        GM_SUN,
+        GM_MERCURY,
+        GM_VENUS,
+        GM_EARTH_MOON_BARYCENTER,
+        GM_EARTH,
+        GM_MOON,
+        GM_MARS_BARYCENTER,
+        GM_MARS,
+        GM_JUPITER_BARYCENTER,
+        GM_JUPITER,
+        GM_SATURN_BARYCENTER,
+        GM_SATURN,
+        GM_URANUS_BARYCENTER,
+        GM_URANUS,
+        GM_NEPTUNE_BARYCENTER,
+        GM_NEPTUNE,
+        GM_PLUTO_BARYCENTER,
+        GM_PLUTO,
+    ];
+
+    /// Returns the constant with the provided name (case insensitive), if it is in the registry.
+    pub fn constant_from_name(name: &str) -> Option<Constant> {
+        CONSTANTS
+            .iter()
+            .find(|constant| constant.name.eq_ignore_ascii_case(name))
+            .copied()
+    }
+
+    /// IDs of the celestial objects which have a default gravitational parameter, cf. [default_gm].
+    const GM_IDS: [NaifId; 20] = [
+        SUN,
+        MERCURY,
+        199,
+        VENUS,
+        299,
+        EARTH_MOON_BARYCENTER,
+        EARTH,
+        MOON,
+        MARS_BARYCENTER,
+        MARS,
+        JUPITER_BARYCENTER,
+        JUPITER,
+        SATURN_BARYCENTER,
+        SATURN,
+        URANUS_BARYCENTER,
+        URANUS,
+        NEPTUNE_BARYCENTER,
+        NEPTUNE,
+        PLUTO_BARYCENTER,
+        PLUTO,
+    ];
+
+    /// Returns the IDs of the celestial objects whose default gravitational parameter is the constant with the provided name
+    /// (case insensitive), e.g. both Mercury and its barycenter for `GM_MERCURY`, or nothing if it is not a GM.
+    pub fn gm_ids_from_name(name: &str) -> Vec<NaifId> {
+        GM_IDS
+            .iter()
+            .copied()
+            .filter(|id| default_gm(*id).is_some_and(|gm| gm.name.eq_ignore_ascii_case(name)))
+            .collect()
+    }
+
+    /// Returns the default gravitational parameter of the provided celestial object, if it is in the registry.
+    /// The gravitational parameters of the planetary data loaded in an Almanac should be preferred.
+    pub const fn default_gm(id: NaifId) -> Option<Constant> {
+        match id {
+            SUN => Some(GM_SUN),
+            MERCURY | 199 => Some(GM_MERCURY),
+            VENUS | 299 => Some(GM_VENUS),
+            EARTH_MOON_BARYCENTER => Some(GM_EARTH_MOON_BARYCENTER),
+            EARTH => Some(GM_EARTH),
+            MOON => Some(GM_MOON),
+            MARS_BARYCENTER => Some(GM_MARS_BARYCENTER),
+            MARS => Some(GM_MARS),
+            JUPITER_BARYCENTER => Some(GM_JUPITER_BARYCENTER),
+            JUPITER => Some(GM_JUPITER),
+            SATURN_BARYCENTER => Some(GM_SATURN_BARYCENTER),
+            SATURN => Some(GM_SATURN),
+            URANUS_BARYCENTER => Some(GM_URANUS_BARYCENTER),
+            URANUS => Some(GM_URANUS),
+            NEPTUNE_BARYCENTER => Some(GM_NEPTUNE_BARYCENTER),
+            NEPTUNE => Some(GM_NEPTUNE),
+            PLUTO_BARYCENTER => Some(GM_PLUTO_BARYCENTER),
+            PLUTO => Some(GM_PLUTO),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -424,4 +785,26 @@ mod constants_ut {
         assert_eq!(celestial_name_from_id(EARTH).unwrap(), "Earth");
         assert!(celestial_name_from_id(-1).is_none());
     }
+
+    #[test]
+    fn registry() {
+        use crate::constants::registry::*;
+        use crate::constants::SPEED_OF_LIGHT_KM_S;
+
+        assert_eq!(
+            constant_from_name("speed_of_light").unwrap().value,
+            SPEED_OF_LIGHT_KM_S
+        );
+        assert_eq!(constant_from_name("GM_EARTH").unwrap().unit, "km^3/s^2");
+        assert!(constant_from_name("GM_KRYPTON").is_none());
+        assert_eq!(default_gm(EARTH), Some(GM_EARTH));
+        assert!(default_gm(-1).is_none());
+
+        // Names are unique
+        for (i, constant) in CONSTANTS.iter().enumerate() {
+            assert!(CONSTANTS[i + 1..]
+                .iter()
+                .all(|other| other.name != constant.name));
+        }
+    }
 }
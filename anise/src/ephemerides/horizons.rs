@@ -0,0 +1,273 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::time::Duration as StdDuration;
+
+use hifitime::{Duration, Epoch, Unit};
+use log::{info, warn};
+use snafu::ResultExt;
+
+use super::{EphemerisError, SPKSnafu};
+use crate::constants::orientations::J2000;
+use crate::math::interpolation::MAX_SAMPLES;
+use crate::naif::daf::datatypes::HermiteSetType13;
+use crate::naif::daf::{DafDataType, NAIFDataSet};
+use crate::naif::spk::summary::SPKSummaryRecord;
+use crate::naif::{SPKSegment, SPK};
+use crate::NaifId;
+
+/// URL of the JPL Horizons API.
+pub const HORIZONS_API_URL: &str = "https://ssd.jpl.nasa.gov/api/horizons.api";
+
+/// Number of states used in the Hermite interpolation of the vectors returned by Horizons.
+const HORIZONS_HERMITE_SAMPLES: usize = 8;
+
+/// Name given to the data source of Horizons errors.
+const HORIZONS_SOURCE: &str = "JPL Horizons response";
+
+/// Queries JPL Horizons for the state vectors of `command` (e.g. `99942` for Apophis, or `DES=1000041;` for a comet)
+/// with respect to `center_id`, in the ICRF, from `start` until `end` every `step`.
+///
+/// The returned SPK stores these states as a single Type 13 (Hermite) segment where the target is `target_id`.
+///
+/// # Limitations
+/// + Horizons only supports steps which are whole minutes, so the step is rounded to the nearest minute;
+/// + Horizons limits the number of states per query, so long spans should use coarse steps.
+pub fn fetch_horizons_spk(
+    command: &str,
+    target_id: NaifId,
+    center_id: NaifId,
+    start: Epoch,
+    end: Epoch,
+    step: Duration,
+) -> Result<SPK, EphemerisError> {
+    let step_min = step.to_unit(Unit::Minute).round().max(1.0) as u64;
+    if (step - (step_min as f64) * Unit::Minute).abs() > Unit::Microsecond * 1 {
+        warn!("[fetch_horizons_spk] Horizons step rounded to {step_min} min (requested {step})");
+    }
+
+    let params = [
+        ("format", "text".to_string()),
+        ("COMMAND", format!("'{command}'")),
+        ("OBJ_DATA", "'NO'".to_string()),
+        ("MAKE_EPHEM", "'YES'".to_string()),
+        ("EPHEM_TYPE", "'VECTORS'".to_string()),
+        ("CENTER", format!("'500@{center_id}'")),
+        ("START_TIME", format!("'JD{:.9}'", start.to_jde_tdb_days())),
+        ("STOP_TIME", format!("'JD{:.9}'", end.to_jde_tdb_days())),
+        ("STEP_SIZE", format!("'{step_min} m'")),
+        ("TIME_TYPE", "'TDB'".to_string()),
+        ("REF_SYSTEM", "'ICRF'".to_string()),
+        ("REF_PLANE", "'FRAME'".to_string()),
+        ("VEC_TABLE", "'2'".to_string()),
+        ("VEC_LABELS", "'NO'".to_string()),
+        ("CSV_FORMAT", "'YES'".to_string()),
+        ("OUT_UNITS", "'KM-S'".to_string()),
+    ];
+
+    let query = params
+        .iter()
+        .map(|(key, value)| format!("{key}={}", percent_encode(value)))
+        .collect::<Vec<String>>()
+        .join("&");
+
+    let url = format!("{HORIZONS_API_URL}?{query}");
+
+    let client: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(StdDuration::from_secs(60)))
+        .build()
+        .into();
+
+    let response = client
+        .get(&url)
+        .call()
+        .map_err(|e| EphemerisError::HorizonsQuery {
+            err: format!("{e}"),
+        })?
+        .body_mut()
+        .read_to_string()
+        .map_err(|e| EphemerisError::HorizonsQuery {
+            err: format!("{e}"),
+        })?;
+
+    info!("[fetch_horizons_spk] queried {command} wrt {center_id} from Horizons");
+
+    parse_horizons_vectors(&response, target_id, center_id)
+}
+
+/// Builds an SPK from the text response of a JPL Horizons vector table query, as requested by [fetch_horizons_spk],
+/// i.e. a CSV vector table in kilometers and kilometers per second, in the ICRF, and whose epochs are Julian dates in TDB.
+pub fn parse_horizons_vectors(
+    response: &str,
+    target_id: NaifId,
+    center_id: NaifId,
+) -> Result<SPK, EphemerisError> {
+    let parsing_err = |lineno: usize, reason: String| EphemerisError::EphemerisFileParsing {
+        path: HORIZONS_SOURCE.to_string(),
+        lineno,
+        reason,
+    };
+
+    let mut in_data = false;
+    let mut found_data = false;
+    let mut epoch_data = Vec::new();
+    let mut state_data = Vec::new();
+
+    for (lno, line) in response.lines().enumerate() {
+        let lineno = lno + 1;
+        let line = line.trim();
+
+        if line == "$$SOE" {
+            in_data = true;
+            found_data = true;
+            continue;
+        } else if line == "$$EOE" {
+            in_data = false;
+            continue;
+        } else if !in_data || line.is_empty() {
+            continue;
+        }
+
+        // JDTDB, Calendar Date (TDB), X, Y, Z, VX, VY, VZ,
+        let items = line
+            .split(',')
+            .map(|item| item.trim())
+            .filter(|item| !item.is_empty())
+            .collect::<Vec<&str>>();
+
+        if items.len() != 8 {
+            return Err(parsing_err(
+                lineno,
+                format!("expected a Julian date, a calendar date, and six state components, got {items:?}"),
+            ));
+        }
+
+        let jde_tdb_days = items[0]
+            .parse::<f64>()
+            .map_err(|e| parsing_err(lineno, format!("{e}")))?;
+        let epoch_et_s = Epoch::from_jde_tdb(jde_tdb_days).to_et_seconds();
+
+        if epoch_data
+            .last()
+            .is_some_and(|prev_epoch_et_s| epoch_et_s <= *prev_epoch_et_s)
+        {
+            return Err(parsing_err(lineno, "epochs are not increasing".to_string()));
+        }
+
+        for item in &items[2..] {
+            state_data.push(
+                item.parse::<f64>()
+                    .map_err(|e| parsing_err(lineno, format!("{e}")))?,
+            );
+        }
+        epoch_data.push(epoch_et_s);
+    }
+
+    if !found_data {
+        // Horizons reports errors in plain text instead of the data, so include the start of the response.
+        let excerpt = response.lines().take(5).collect::<Vec<&str>>().join(" ");
+        return Err(EphemerisError::HorizonsQuery {
+            err: format!("no ephemeris in response: {excerpt}"),
+        });
+    } else if epoch_data.len() < 2 {
+        return Err(parsing_err(0, "at least two states are needed".to_string()));
+    }
+
+    let num_records = epoch_data.len();
+    let mut samples = HORIZONS_HERMITE_SAMPLES.min(num_records).min(MAX_SAMPLES);
+    samples -= samples % 2;
+
+    let data = HermiteSetType13 {
+        samples,
+        num_records,
+        state_data: &state_data,
+        epoch_data: &epoch_data,
+        epoch_registry: &epoch_data
+            .iter()
+            .skip(99)
+            .step_by(100)
+            .copied()
+            .collect::<Vec<f64>>(),
+    }
+    .to_f64_daf_vec()
+    .map_err(|e| parsing_err(0, format!("{e}")))?;
+
+    let segment = SPKSegment {
+        name: format!("Horizons {target_id} wrt {center_id}"),
+        summary: SPKSummaryRecord {
+            start_epoch_et_s: epoch_data[0],
+            end_epoch_et_s: epoch_data[num_records - 1],
+            target_id,
+            center_id,
+            frame_id: J2000,
+            data_type_i: DafDataType::Type13HermiteUnequalStep as i32,
+            ..Default::default()
+        },
+        data,
+    };
+
+    SPK::from_segments("JPL HORIZONS", &[segment]).context(SPKSnafu {
+        action: "building SPK from Horizons vectors",
+    })
+}
+
+/// Percent encodes the characters of the Horizons parameters which are not allowed in a URL query.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod ut_horizons {
+    use super::{parse_horizons_vectors, percent_encode};
+    use crate::naif::daf::NAIFSummaryRecord;
+    use hifitime::Epoch;
+
+    #[test]
+    fn parse_vectors() {
+        let response = "API VERSION: 1.2
+*******************************************************************************
+Ephemeris / API_USER Fri Jan  3 00:00:00 2025 Pasadena, USA      / Horizons
+*******************************************************************************
+            JDTDB,            Calendar Date (TDB),                      X,                      Y,                      Z,                     VX,                     VY,                     VZ,
+**************************************************************************************************************************************************************************************************
+$$SOE
+2460310.500000000, A.D. 2024-Jan-01 00:00:00.0000,  1.000000000000000E+08,  0.000000000000000E+00,  0.000000000000000E+00,  0.000000000000000E+00,  1.000000000000000E+01,  0.000000000000000E+00,
+2460310.541666667, A.D. 2024-Jan-01 01:00:00.0000,  1.000000000000000E+08,  3.600000000000000E+04,  0.000000000000000E+00,  0.000000000000000E+00,  1.000000000000000E+01,  0.000000000000000E+00,
+$$EOE
+**************************************************************************************************************************************************************************************************
+";
+
+        let spk = parse_horizons_vectors(response, 2099942, 10).unwrap();
+        let summary = spk.data_summaries().unwrap()[0];
+        assert_eq!(summary.target_id, 2099942);
+        assert_eq!(summary.center_id, 10);
+        assert!(
+            (summary.start_epoch() - Epoch::from_jde_tdb(2460310.5)).abs()
+                < hifitime::Unit::Microsecond * 1
+        );
+
+        // Horizons errors are reported without data
+        assert!(parse_horizons_vectors("No matches found.", 2099942, 10).is_err());
+    }
+
+    #[test]
+    fn encoding() {
+        assert_eq!(percent_encode("'500@10'"), "%27500%4010%27");
+        assert_eq!(percent_encode("'60 m'"), "%2760%20m%27");
+    }
+}
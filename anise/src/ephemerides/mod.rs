@@ -12,11 +12,21 @@ use hifitime::Epoch;
 use snafu::prelude::*;
 
 use crate::{
-    errors::PhysicsError, math::interpolation::InterpolationError, naif::daf::DAFError,
-    prelude::FrameUid, NaifId,
+    errors::{ErrorCode, PhysicsError},
+    math::interpolation::InterpolationError,
+    naif::daf::DAFError,
+    prelude::FrameUid,
+    NaifId,
 };
 
+#[cfg(feature = "http")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+pub mod horizons;
 pub mod paths;
+pub mod propagator;
+pub mod provider;
+pub mod refit;
+pub mod stk;
 pub mod translate_to_parent;
 pub mod translations;
 
@@ -60,4 +70,39 @@ pub enum EphemerisError {
     IdToName { id: NaifId },
     #[snafu(display("unknown NAIF ID associated with `{name}`"))]
     NameToId { name: String },
+    #[snafu(display("{path}:{lineno}: {reason}"))]
+    EphemerisFileParsing {
+        path: String,
+        lineno: usize,
+        reason: String,
+    },
+    #[snafu(display("JPL Horizons query failed: {err}"))]
+    HorizonsQuery { err: String },
+    #[snafu(display("ephemeris provider of {id} failed: {reason}"))]
+    Provider { id: NaifId, reason: String },
+    #[snafu(display("cannot compile query: {reason}"))]
+    CompiledQuery { reason: String },
+    #[snafu(display("cannot refit segment #{idx}: {reason}"))]
+    SegmentRefit { idx: usize, reason: String },
+}
+
+impl ErrorCode for EphemerisError {
+    fn code(&self) -> u16 {
+        match self {
+            Self::Unreachable => 201,
+            Self::StructureIsFull { .. } => 202,
+            Self::TranslationOrigin { .. } => 203,
+            Self::NoEphemerisLoaded => 204,
+            Self::SPK { source, .. } => source.code(),
+            Self::EphemerisPhysics { source, .. } => source.code(),
+            Self::EphemInterpolation { source } => source.code(),
+            Self::IdToName { .. } => 205,
+            Self::NameToId { .. } => 206,
+            Self::EphemerisFileParsing { .. } => 207,
+            Self::HorizonsQuery { .. } => 208,
+            Self::Provider { .. } => 209,
+            Self::CompiledQuery { .. } => 210,
+            Self::SegmentRefit { .. } => 211,
+        }
+    }
 }
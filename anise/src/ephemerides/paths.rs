@@ -28,11 +28,20 @@ impl Almanac {
     /// 1. For each loaded SPK, iterated in reverse order (to mimic SPICE behavior)
     /// 2. For each summary record in each SPK, follow the ephemeris branch all the way up until the end of this SPK or until the SSB.
     pub fn try_find_ephemeris_root(&self) -> Result<NaifId, EphemerisError> {
-        ensure!(self.num_loaded_spk() > 0, NoEphemerisLoadedSnafu);
+        ensure!(
+            self.num_loaded_spk() > 0 || !self.ephemeris_providers.is_empty(),
+            NoEphemerisLoadedSnafu
+        );
 
         // The common center is the absolute minimum of all centers due to the NAIF numbering.
         let mut common_center = i32::MAX;
 
+        for provider in &self.ephemeris_providers {
+            if provider.center_id().abs() < common_center.abs() {
+                common_center = provider.center_id();
+            }
+        }
+
         for maybe_spk in self.spk_data.iter().take(self.num_loaded_spk()).rev() {
             let spk = maybe_spk.as_ref().unwrap();
 
@@ -68,22 +77,19 @@ impl Almanac {
             return Ok((of_path_len, of_path));
         }
 
-        // Grab the summary data, which we use to find the paths
-        let summary = self.spk_summary_at_epoch(source.ephemeris_id, epoch)?.0;
-
-        let mut center_id = summary.center_id;
+        // Grab the center from the summary data or the providers, which we use to find the paths
+        let mut center_id = self.ephemeris_center_at_epoch(source.ephemeris_id, epoch)?;
 
-        of_path[of_path_len] = Some(summary.center_id);
+        of_path[of_path_len] = Some(center_id);
         of_path_len += 1;
 
-        if summary.center_id == common_center {
+        if center_id == common_center {
             // Well that was quick!
             return Ok((of_path_len, of_path));
         }
 
         for _ in 0..MAX_TREE_DEPTH {
-            let summary = self.spk_summary_at_epoch(center_id, epoch)?.0;
-            center_id = summary.center_id;
+            center_id = self.ephemeris_center_at_epoch(center_id, epoch)?;
             of_path[of_path_len] = Some(center_id);
             of_path_len += 1;
             if center_id == common_center {
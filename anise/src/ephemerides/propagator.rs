@@ -0,0 +1,358 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::f64::consts::TAU;
+
+use hifitime::Epoch;
+use snafu::ResultExt;
+
+use super::provider::EphemerisProvider;
+use super::{EphemerisError, EphemerisPhysicsSnafu};
+use crate::constants::orientations::J2000;
+use crate::constants::registry::{J2_EARTH, J2_EARTH_REFERENCE_RADIUS};
+use crate::math::cartesian::CartesianState;
+use crate::math::{Vector3, Vector6};
+use crate::NaifId;
+
+/// Number of integration steps per orbital period.
+const STEPS_PER_PERIOD: f64 = 512.0;
+/// Bounds of the integration step, in seconds.
+const MIN_STEP_S: f64 = 1.0;
+const MAX_STEP_S: f64 = 600.0;
+/// Maximum number of states stored over the window, i.e. about 50 MB.
+const MAX_NODES: usize = 1 << 20;
+
+/// An approximate ephemeris computed by numerical integration of the two-body problem and the J2 perturbation of the center,
+/// for objects whose only data is a state at some epoch, e.g. right after a launch.
+///
+/// Once registered in an Almanac with [Almanac::with_ephemeris_provider](crate::almanac::Almanac::with_ephemeris_provider),
+/// this object can be queried like any other, and the accuracy report flags it as an analytic source.
+///
+/// # Algorithm
+/// The state is integrated with a fourth order Runge-Kutta method with a fixed step of 1/512th of the orbital period, both
+/// forward and backward from its epoch, to cover the whole window. For non-elliptical orbits, the period of a circular
+/// orbit at the initial radius is used instead. The states at each step are stored, and the state at any
+/// epoch is integrated from the closest previous step.
+///
+/// # Limitations
+/// + All other perturbations are neglected (third bodies, higher order harmonics, drag, solar radiation pressure), so the
+///   error quickly grows to kilometers in low Earth orbit.
+/// + The pole of the center is assumed to be the Z axis of J2000, which is off by a fraction of a degree for the Earth.
+/// + The fixed step is poorly suited to highly eccentric orbits.
+/// + The window may span at most about a million steps.
+#[derive(Clone, Debug, PartialEq)]
+pub struct J2Propagator {
+    target_id: NaifId,
+    center_id: NaifId,
+    mu_km3_s2: f64,
+    j2: f64,
+    reference_radius_km: f64,
+    start: Epoch,
+    end: Epoch,
+    /// Epoch of the initial state, which is the node at index `num_back`
+    epoch: Epoch,
+    num_back: usize,
+    step_s: f64,
+    nodes: Vec<Vector6>,
+}
+
+impl J2Propagator {
+    /// Propagates the state of the target, which must be expressed in J2000 with respect to a center whose gravitational
+    /// parameter is set in the frame, over the window (start and end epochs), which must include the epoch of the state.
+    ///
+    /// The J2 perturbation is that of the provided zonal harmonic and reference radius (km), or none if J2 is zero.
+    pub fn new(
+        target_id: NaifId,
+        state: CartesianState,
+        j2: f64,
+        reference_radius_km: f64,
+        window: (Epoch, Epoch),
+    ) -> Result<Self, EphemerisError> {
+        let (start, end) = window;
+        if !state.frame.orient_origin_id_match(J2000) {
+            return Err(EphemerisError::Provider {
+                id: target_id,
+                reason: format!("state must be in J2000 but is in {}", state.frame),
+            });
+        } else if state.epoch < start || state.epoch > end {
+            return Err(EphemerisError::Provider {
+                id: target_id,
+                reason: format!(
+                    "state epoch {} is outside of the window from {start} to {end}",
+                    state.epoch
+                ),
+            });
+        }
+
+        let mu_km3_s2 = state.frame.mu_km3_s2().context(EphemerisPhysicsSnafu {
+            action: "building J2 propagator",
+        })?;
+
+        // Choose the step from the orbital period, or from the period of a circular orbit at the initial radius for
+        // non-elliptical orbits.
+        let energy = state.vmag_km_s().powi(2) / 2.0 - mu_km3_s2 / state.rmag_km();
+        let period_s = if energy < 0.0 {
+            let sma_km = -mu_km3_s2 / (2.0 * energy);
+            TAU * (sma_km.powi(3) / mu_km3_s2).sqrt()
+        } else {
+            TAU * (state.rmag_km().powi(3) / mu_km3_s2).sqrt()
+        };
+        let step_s = (period_s / STEPS_PER_PERIOD).clamp(MIN_STEP_S, MAX_STEP_S);
+
+        let num_back = ((state.epoch - start).to_seconds() / step_s).ceil() as usize;
+        let num_fwd = ((end - state.epoch).to_seconds() / step_s).ceil() as usize;
+        if num_back.saturating_add(num_fwd) >= MAX_NODES {
+            return Err(EphemerisError::Provider {
+                id: target_id,
+                reason: format!(
+                    "window from {start} to {end} requires {} steps of {step_s} s but at most {MAX_NODES} are supported",
+                    num_back.saturating_add(num_fwd)
+                ),
+            });
+        }
+
+        let mut me = Self {
+            target_id,
+            center_id: state.frame.ephemeris_id,
+            mu_km3_s2,
+            j2,
+            reference_radius_km,
+            start,
+            end,
+            epoch: state.epoch,
+            num_back,
+            step_s,
+            nodes: Vec::new(),
+        };
+
+        let pos_vel = state.to_cartesian_pos_vel();
+
+        // Integrate backward to the start of the window, then forward to its end.
+        let mut backward = Vec::with_capacity(num_back + num_fwd + 1);
+        backward.push(pos_vel);
+        for _ in 0..me.num_back {
+            let prev = me.rk4(backward.last().unwrap(), -step_s);
+            backward.push(prev);
+        }
+        backward.reverse();
+        me.nodes = backward;
+
+        for _ in 0..num_fwd {
+            let next = me.rk4(me.nodes.last().unwrap(), step_s);
+            me.nodes.push(next);
+        }
+
+        if me
+            .nodes
+            .iter()
+            .any(|node| node.iter().any(|x| !x.is_finite()))
+        {
+            return Err(EphemerisError::Provider {
+                id: target_id,
+                reason: "propagation diverged, e.g. the state is inside the center".to_string(),
+            });
+        }
+
+        Ok(me)
+    }
+
+    /// Propagates the state of the target about the Earth with the J2 of EGM2008, cf. [J2Propagator::new].
+    pub fn earth(
+        target_id: NaifId,
+        state: CartesianState,
+        window: (Epoch, Epoch),
+    ) -> Result<Self, EphemerisError> {
+        Self::new(
+            target_id,
+            state,
+            J2_EARTH.value,
+            J2_EARTH_REFERENCE_RADIUS.value,
+            window,
+        )
+    }
+
+    /// Returns the integration step, in seconds
+    pub fn step_s(&self) -> f64 {
+        self.step_s
+    }
+
+    /// Returns the derivative of the position and velocity.
+    fn derivative(&self, pos_vel: &Vector6) -> Vector6 {
+        let radius = Vector3::new(pos_vel[0], pos_vel[1], pos_vel[2]);
+        let rmag = radius.norm();
+        let two_body = -self.mu_km3_s2 / rmag.powi(3);
+
+        let z2_r2 = (radius.z / rmag).powi(2);
+        let j2_factor = 1.5 * self.j2 * (self.reference_radius_km / rmag).powi(2);
+        let accel = Vector3::new(
+            two_body * radius.x * (1.0 + j2_factor * (1.0 - 5.0 * z2_r2)),
+            two_body * radius.y * (1.0 + j2_factor * (1.0 - 5.0 * z2_r2)),
+            two_body * radius.z * (1.0 + j2_factor * (3.0 - 5.0 * z2_r2)),
+        );
+
+        Vector6::new(
+            pos_vel[3], pos_vel[4], pos_vel[5], accel.x, accel.y, accel.z,
+        )
+    }
+
+    /// Integrates the position and velocity over the step (s), which may be negative.
+    fn rk4(&self, pos_vel: &Vector6, step_s: f64) -> Vector6 {
+        let k1 = self.derivative(pos_vel);
+        let k2 = self.derivative(&(pos_vel + k1 * (step_s / 2.0)));
+        let k3 = self.derivative(&(pos_vel + k2 * (step_s / 2.0)));
+        let k4 = self.derivative(&(pos_vel + k3 * step_s));
+        pos_vel + (k1 + 2.0 * k2 + 2.0 * k3 + k4) * (step_s / 6.0)
+    }
+}
+
+impl EphemerisProvider for J2Propagator {
+    fn target_id(&self) -> NaifId {
+        self.target_id
+    }
+
+    fn center_id(&self) -> NaifId {
+        self.center_id
+    }
+
+    fn covers(&self, epoch: Epoch) -> bool {
+        epoch >= self.start && epoch <= self.end
+    }
+
+    fn position_velocity(&self, epoch: Epoch) -> Result<(Vector3, Vector3), EphemerisError> {
+        if !self.covers(epoch) {
+            return Err(EphemerisError::Provider {
+                id: self.target_id,
+                reason: format!(
+                    "{epoch} is outside of the window from {} to {}",
+                    self.start, self.end
+                ),
+            });
+        }
+
+        let elapsed_s = (epoch - self.epoch).to_seconds();
+        let num_steps = ((elapsed_s / self.step_s).floor() as isize + self.num_back as isize)
+            .clamp(0, self.nodes.len() as isize - 1);
+        let idx = num_steps as usize;
+        let remainder_s = elapsed_s - (num_steps - self.num_back as isize) as f64 * self.step_s;
+
+        let pos_vel = if remainder_s != 0.0 {
+            self.rk4(&self.nodes[idx], remainder_s)
+        } else {
+            self.nodes[idx]
+        };
+
+        Ok((
+            Vector3::new(pos_vel[0], pos_vel[1], pos_vel[2]),
+            Vector3::new(pos_vel[3], pos_vel[4], pos_vel[5]),
+        ))
+    }
+
+    fn is_approximate(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod ut_propagator {
+    use super::{EphemerisProvider, J2Propagator};
+    use crate::constants::frames::EARTH_J2000;
+    use crate::constants::orientations::IAU_EARTH;
+    use crate::constants::registry::GM_EARTH;
+    use crate::math::cartesian::CartesianState;
+    use core::f64::consts::TAU;
+    use hifitime::{Epoch, Unit};
+
+    #[test]
+    fn two_body_period() {
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+        let frame = EARTH_J2000.with_mu_km3_s2(GM_EARTH.value);
+        let sma_km = 7_000.0;
+        let speed_km_s = (GM_EARTH.value / sma_km).sqrt();
+        let state = CartesianState::new(
+            sma_km,
+            0.0,
+            0.0,
+            0.0,
+            speed_km_s * 0.6,
+            speed_km_s * 0.8,
+            epoch,
+            frame,
+        );
+        let period = TAU * (sma_km.powi(3) / GM_EARTH.value).sqrt() * Unit::Second;
+
+        let prop =
+            J2Propagator::new(-1, state, 0.0, 0.0, (epoch - period, epoch + period * 2)).unwrap();
+        assert!(prop.covers(epoch - period));
+        assert!(!prop.covers(epoch + period * 3));
+        assert!(prop.position_velocity(epoch + period * 3).is_err());
+
+        // The state at its own epoch is unchanged, and the orbit closes after each period.
+        let (radius, velocity) = prop.position_velocity(epoch).unwrap();
+        assert_eq!(radius, state.radius_km);
+        assert_eq!(velocity, state.velocity_km_s);
+        for offset in [-period, period, period * 2] {
+            let (radius, velocity) = prop.position_velocity(epoch + offset).unwrap();
+            assert!((radius - state.radius_km).norm() < 1e-2);
+            assert!((velocity - state.velocity_km_s).norm() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn j2_nodal_regression() {
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+        let frame = EARTH_J2000.with_mu_km3_s2(GM_EARTH.value);
+        let sma_km = 7_000.0;
+        let inc_rad = 50.0_f64.to_radians();
+        let speed_km_s = (GM_EARTH.value / sma_km).sqrt();
+        let state = CartesianState::new(
+            sma_km,
+            0.0,
+            0.0,
+            0.0,
+            speed_km_s * inc_rad.cos(),
+            speed_km_s * inc_rad.sin(),
+            epoch,
+            frame,
+        );
+
+        let prop = J2Propagator::earth(-1, state, (epoch, epoch + Unit::Day)).unwrap();
+        let (radius, velocity) = prop.position_velocity(epoch + Unit::Day).unwrap();
+        let node = radius.cross(&velocity);
+        let raan_rad = node.x.atan2(-node.y);
+
+        // Secular rate of the right ascension of the ascending node of a circular orbit.
+        let mean_motion = (GM_EARTH.value / sma_km.powi(3)).sqrt();
+        let expected_rad = -1.5
+            * mean_motion
+            * super::J2_EARTH.value
+            * (super::J2_EARTH_REFERENCE_RADIUS.value / sma_km).powi(2)
+            * inc_rad.cos()
+            * 86_400.0;
+        // About -4.6 degrees per day, with short periodic variations of a few hundredths of a degree.
+        assert!(
+            (raan_rad - expected_rad).abs().to_degrees() < 0.1,
+            "{} deg",
+            raan_rad.to_degrees()
+        );
+
+        // The state must be in J2000 and within the window.
+        let mut iau_earth_state = state;
+        iau_earth_state.frame = frame.with_orient(IAU_EARTH);
+        assert!(J2Propagator::earth(-1, iau_earth_state, (epoch, epoch + Unit::Day)).is_err());
+        assert!(J2Propagator::earth(-1, state, (epoch + Unit::Hour, epoch + Unit::Day)).is_err());
+
+        // An escape trajectory is stepped from the circular period at its radius, and overly long windows are rejected.
+        let mut escape = state;
+        escape.velocity_km_s *= 1.5;
+        let prop = J2Propagator::earth(-1, escape, (epoch, epoch + Unit::Day)).unwrap();
+        assert!(prop.step_s() > 10.0);
+        assert!(J2Propagator::earth(-1, escape, (epoch, epoch + Unit::Day * 36_525)).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::sync::Arc;
+
+use hifitime::Epoch;
+
+use super::EphemerisError;
+use crate::almanac::Almanac;
+use crate::math::Vector3;
+use crate::NaifId;
+
+/// A source of ephemeris data other than the SPK files, e.g. a numerical propagator or a database, which is used like an SPK
+/// segment once registered in an Almanac with [Almanac::with_ephemeris_provider].
+///
+/// The states must be expressed in the J2000 orientation, like those of the SPK segments used by ANISE.
+pub trait EphemerisProvider: Send + Sync {
+    /// NAIF ID of the object whose state is provided
+    fn target_id(&self) -> NaifId;
+    /// NAIF ID of the center with respect to which the state is provided, which must be reachable in the Almanac
+    fn center_id(&self) -> NaifId;
+    /// Returns whether this provider can compute the state at the provided epoch
+    fn covers(&self, epoch: Epoch) -> bool;
+    /// Returns the position (km) and velocity (km/s) of the target with respect to the center at the provided epoch
+    fn position_velocity(&self, epoch: Epoch) -> Result<(Vector3, Vector3), EphemerisError>;
+    /// Returns whether this provider is an approximation, e.g. a simple propagator, such that its queries are flagged as
+    /// analytic in the accuracy reports.
+    fn is_approximate(&self) -> bool {
+        false
+    }
+}
+
+impl Almanac {
+    /// Returns a copy of this Almanac where the provided ephemeris source is registered.
+    ///
+    /// Providers have precedence over the loaded SPK files, and the providers registered last have precedence over the others,
+    /// but only at the epochs they cover: the loaded SPK files are used otherwise.
+    pub fn with_ephemeris_provider(&self, provider: Arc<dyn EphemerisProvider>) -> Self {
+        let mut me = self.clone();
+        me.ephemeris_providers.push(provider);
+        me
+    }
+
+    /// Returns the provider of the ephemeris of this object at this epoch, if any.
+    pub(crate) fn ephemeris_provider_at_epoch(
+        &self,
+        id: NaifId,
+        epoch: Epoch,
+    ) -> Option<&dyn EphemerisProvider> {
+        self.ephemeris_providers
+            .iter()
+            .rev()
+            .find(|provider| provider.target_id() == id && provider.covers(epoch))
+            .map(|provider| provider.as_ref())
+    }
+
+    /// Returns the center of the ephemeris of this object at this epoch, from the providers or the loaded SPK files.
+    pub(crate) fn ephemeris_center_at_epoch(
+        &self,
+        id: NaifId,
+        epoch: Epoch,
+    ) -> Result<NaifId, EphemerisError> {
+        match self.ephemeris_provider_at_epoch(id, epoch) {
+            Some(provider) => Ok(provider.center_id()),
+            None => Ok(self.spk_summary_at_epoch(id, epoch)?.0.center_id),
+        }
+    }
+}
@@ -0,0 +1,320 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::{Duration, Epoch, TimeUnits};
+use log::info;
+use snafu::ResultExt;
+
+use super::translate_to_parent::evaluate_spk_data;
+use super::{EphemInterpolationSnafu, EphemerisError, SPKSnafu};
+use crate::math::interpolation::{chebyshev_fit, chebyshev_nodes};
+use crate::math::Vector3;
+use crate::naif::daf::datatypes::Type2ChebyshevSet;
+use crate::naif::daf::{DafDataType, NAIFDataSet, NAIFSummaryRecord};
+use crate::naif::spk::summary::SPKSummaryRecord;
+use crate::naif::{SPKSegment, SPK};
+use crate::NaifId;
+
+/// Residuals of a re-fitted SPK segment with respect to the original segment, cf. [refit_spk_segment].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct RefitResiduals {
+    pub target_id: NaifId,
+    pub center_id: NaifId,
+    /// Data type of the original segment
+    pub original_data_type: Option<DafDataType>,
+    /// Number of Chebyshev records of the re-fitted segment
+    pub num_records: usize,
+    /// Number of epochs at which both segments were compared
+    pub num_checks: usize,
+    pub max_position_km: f64,
+    pub rms_position_km: f64,
+    pub max_velocity_km_s: f64,
+    pub rms_velocity_km_s: f64,
+}
+
+impl fmt::Display for RefitResiduals {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} wrt {} ({:?} -> {} Type 2 records): position max {:.6e} km (RMS {:.6e} km)\tvelocity max {:.6e} km/s (RMS {:.6e} km/s) over {} epochs",
+            self.target_id,
+            self.center_id,
+            self.original_data_type,
+            self.num_records,
+            self.max_position_km,
+            self.rms_position_km,
+            self.max_velocity_km_s,
+            self.rms_velocity_km_s,
+            self.num_checks
+        )
+    }
+}
+
+/// Re-fits the segment number `idx` of this SPK into a Chebyshev Type 2 segment of the same target, center, frame, and
+/// coverage, made of records of equal length of at most `interval` and of the provided polynomial degree.
+///
+/// The original segment may be of any data type supported by the translations, e.g. a dense Hermite Type 13 segment from an
+/// orbit determination, and the positions are fitted at the Chebyshev nodes of each record. The residuals are computed at
+/// `2 * (degree + 1)` evenly spaced epochs per record, i.e. between the fitting nodes.
+pub fn refit_spk_segment(
+    spk: &SPK,
+    idx: usize,
+    degree: usize,
+    interval: Duration,
+) -> Result<(SPKSegment, RefitResiduals), EphemerisError> {
+    let summary = spk.nth_summary(idx).context(SPKSnafu {
+        action: "fetching segment to refit",
+    })?;
+
+    let start_et_s = summary.start_epoch_et_s;
+    let end_et_s = summary.end_epoch_et_s;
+    let span_s = end_et_s - start_et_s;
+
+    if degree == 0 || interval <= Duration::ZERO || span_s <= 0.0 {
+        return Err(EphemerisError::SegmentRefit {
+            idx,
+            reason: format!(
+                "cannot fit {span_s} s of data with polynomials of degree {degree} every {interval}"
+            ),
+        });
+    }
+
+    let num_records = (span_s / interval.to_seconds()).ceil().max(1.0) as usize;
+    let interval_length_s = span_s / num_records as f64;
+    let radius_s = interval_length_s / 2.0;
+    let num_coeffs = degree + 1;
+    let nodes = chebyshev_nodes(num_coeffs);
+
+    let mut record_data = Vec::with_capacity(num_records * (2 + 3 * num_coeffs));
+    for rno in 0..num_records {
+        let midpoint_et_s = start_et_s + (rno as f64 + 0.5) * interval_length_s;
+
+        let positions_km = nodes
+            .iter()
+            .map(|node| {
+                let epoch = Epoch::from_et_seconds(midpoint_et_s + node * radius_s);
+                evaluate_spk_data(spk, summary, idx, epoch).map(|(pos_km, _)| pos_km)
+            })
+            .collect::<Result<Vec<Vector3>, _>>()?;
+
+        record_data.push(midpoint_et_s);
+        record_data.push(radius_s);
+        for axis in 0..3 {
+            record_data.extend(chebyshev_fit(
+                &positions_km
+                    .iter()
+                    .map(|pos_km| pos_km[axis])
+                    .collect::<Vec<f64>>(),
+            ));
+        }
+    }
+
+    let refit_summary = SPKSummaryRecord {
+        data_type_i: DafDataType::Type2ChebyshevTriplet as i32,
+        ..*summary
+    };
+
+    let refit = Type2ChebyshevSet {
+        init_epoch: Epoch::from_et_seconds(start_et_s),
+        interval_length: interval_length_s.seconds(),
+        rsize: 2 + 3 * num_coeffs,
+        num_records,
+        record_data: &record_data,
+    };
+
+    let num_checks = 2 * num_coeffs * num_records + 1;
+    let mut residuals = RefitResiduals {
+        target_id: summary.target_id,
+        center_id: summary.center_id,
+        original_data_type: summary.data_type().ok(),
+        num_records,
+        num_checks,
+        ..Default::default()
+    };
+
+    for cno in 0..num_checks {
+        let epoch = Epoch::from_et_seconds(
+            (start_et_s + span_s * cno as f64 / (num_checks - 1) as f64).min(end_et_s),
+        );
+
+        let (pos_km, vel_km_s) = evaluate_spk_data(spk, summary, idx, epoch)?;
+        let (refit_pos_km, refit_vel_km_s) = refit
+            .evaluate(epoch, &refit_summary)
+            .context(EphemInterpolationSnafu)?;
+
+        let pos_err_km = (refit_pos_km - pos_km).norm();
+        let vel_err_km_s = (refit_vel_km_s - vel_km_s).norm();
+
+        residuals.max_position_km = residuals.max_position_km.max(pos_err_km);
+        residuals.max_velocity_km_s = residuals.max_velocity_km_s.max(vel_err_km_s);
+        residuals.rms_position_km += pos_err_km.powi(2);
+        residuals.rms_velocity_km_s += vel_err_km_s.powi(2);
+    }
+
+    residuals.rms_position_km = (residuals.rms_position_km / num_checks as f64).sqrt();
+    residuals.rms_velocity_km_s = (residuals.rms_velocity_km_s / num_checks as f64).sqrt();
+
+    let segment = SPKSegment {
+        name: format!("{} refit into Type 2", summary.target_id),
+        summary: refit_summary,
+        data: refit.to_f64_daf_vec().context(EphemInterpolationSnafu)?,
+    };
+
+    Ok((segment, residuals))
+}
+
+/// Re-fits all of the segments of this SPK into Chebyshev Type 2 segments, cf. [refit_spk_segment], returning the new SPK
+/// and the residuals of each of its segments, in the order of the original SPK.
+pub fn refit_spk(
+    spk: &SPK,
+    degree: usize,
+    interval: Duration,
+) -> Result<(SPK, Vec<RefitResiduals>), EphemerisError> {
+    let per_record = SPK::summaries_per_record();
+
+    let mut segments = Vec::new();
+    let mut all_residuals = Vec::new();
+
+    for (rno, record) in spk
+        .iter_summary_records()
+        .context(SPKSnafu {
+            action: "listing segments to refit",
+        })?
+        .enumerate()
+    {
+        let (_, summaries) = record.context(SPKSnafu {
+            action: "listing segments to refit",
+        })?;
+
+        for (sno, summary) in summaries.iter().enumerate() {
+            if summary.is_empty() {
+                continue;
+            }
+
+            let (segment, residuals) =
+                refit_spk_segment(spk, rno * per_record + sno, degree, interval)?;
+            info!("[refit_spk] {residuals}");
+
+            segments.push(segment);
+            all_residuals.push(residuals);
+        }
+    }
+
+    let internal_filename = spk
+        .file_record()
+        .context(SPKSnafu {
+            action: "reading file record to refit",
+        })?
+        .internal_filename()
+        .map(|name| name.to_string())
+        .unwrap_or_default();
+
+    let refit = SPK::from_segments(&internal_filename, &segments).context(SPKSnafu {
+        action: "building refit SPK",
+    })?;
+
+    Ok((refit, all_residuals))
+}
+
+#[cfg(test)]
+mod ut_refit {
+    use super::{refit_spk, refit_spk_segment};
+    use crate::naif::daf::datatypes::HermiteSetType13;
+    use crate::naif::daf::{DafDataType, NAIFDataSet};
+    use crate::naif::spk::summary::SPKSummaryRecord;
+    use crate::naif::{SPKSegment, SPK};
+    use crate::prelude::{Almanac, Frame};
+    use hifitime::{Epoch, TimeUnits};
+
+    #[test]
+    fn refit_hermite_into_chebyshev() {
+        // One hour of a circular LEO, sampled every minute
+        let radius_km = 7000.0;
+        let mean_motion_rad_s = (398600.4418_f64 / radius_km.powi(3)).sqrt();
+        let epoch_data = (0..=60).map(|i| 60.0 * i as f64).collect::<Vec<f64>>();
+        let state_data = epoch_data
+            .iter()
+            .flat_map(|t| {
+                let (s, c) = (mean_motion_rad_s * t).sin_cos();
+                let speed_km_s = radius_km * mean_motion_rad_s;
+                [
+                    radius_km * c,
+                    radius_km * s,
+                    0.0,
+                    -speed_km_s * s,
+                    speed_km_s * c,
+                    0.0,
+                ]
+            })
+            .collect::<Vec<f64>>();
+
+        let hermite = HermiteSetType13 {
+            samples: 8,
+            num_records: epoch_data.len(),
+            state_data: &state_data,
+            epoch_data: &epoch_data,
+            epoch_registry: &[],
+        };
+
+        let segment = SPKSegment {
+            name: "dense OD".to_string(),
+            summary: SPKSummaryRecord {
+                start_epoch_et_s: 0.0,
+                end_epoch_et_s: 3600.0,
+                target_id: -10,
+                center_id: 399,
+                frame_id: 1,
+                data_type_i: DafDataType::Type13HermiteUnequalStep as i32,
+                ..Default::default()
+            },
+            data: hermite.to_f64_daf_vec().unwrap(),
+        };
+        let spk = SPK::from_segments("ANISE TEST", &[segment]).unwrap();
+
+        let (refit, residuals) = refit_spk(&spk, 12, 10.minutes()).unwrap();
+        assert_eq!(residuals.len(), 1);
+        assert_eq!(residuals[0].num_records, 6);
+        assert_eq!(
+            residuals[0].original_data_type,
+            Some(DafDataType::Type13HermiteUnequalStep)
+        );
+        assert!(residuals[0].max_position_km < 1e-5, "{}", residuals[0]);
+        assert!(residuals[0].max_velocity_km_s < 1e-8, "{}", residuals[0]);
+        assert!(residuals[0].rms_position_km <= residuals[0].max_position_km);
+
+        let summary = refit.nth_summary(0).unwrap();
+        assert_eq!(summary.target_id, -10);
+        assert_eq!(summary.center_id, 399);
+        assert_eq!(
+            summary.data_type().unwrap(),
+            DafDataType::Type2ChebyshevTriplet
+        );
+
+        // The re-fitted SPK is usable as any other
+        let almanac = Almanac::from_spk(refit).unwrap();
+        let state = almanac
+            .translate(
+                Frame::from_ephem_j2000(-10),
+                Frame::from_ephem_j2000(399),
+                Epoch::from_et_seconds(1234.5),
+                None,
+            )
+            .unwrap();
+        assert!((state.rmag_km() - radius_km).abs() < 1e-5);
+
+        // A low degree over long records is reported by the residuals
+        let (_, coarse) = refit_spk_segment(&spk, 0, 3, 1.hours()).unwrap();
+        assert!(coarse.max_position_km > residuals[0].max_position_km);
+
+        assert!(refit_spk_segment(&spk, 0, 0, 1.hours()).is_err());
+    }
+}
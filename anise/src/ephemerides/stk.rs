@@ -0,0 +1,326 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::fs::read_to_string;
+
+use hifitime::{Epoch, TimeUnits};
+use log::{info, warn};
+use snafu::ResultExt;
+
+use super::{EphemerisError, SPKSnafu};
+use crate::constants::celestial_objects::id_to_celestial_name;
+use crate::constants::orientations::id_to_orientation_name;
+use crate::errors::InputOutputError;
+use crate::math::interpolation::MAX_SAMPLES;
+use crate::naif::daf::datatypes::{HermiteSetType13, LagrangeSetType9};
+use crate::naif::daf::{DAFError, DafDataType, NAIFDataSet};
+use crate::naif::spk::summary::SPKSummaryRecord;
+use crate::naif::{SPKSegment, SPK};
+use crate::NaifId;
+
+/// Number of epochs between two entries of the epoch directory of Type 9 and Type 13 SPK segments.
+const EPOCH_DIRECTORY_STEP: usize = 100;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum STKInterpolation {
+    Hermite,
+    Lagrange,
+}
+
+/// Converts an STK ephemeris file (`.e`), like those written by GMAT, into an in-memory SPK where the states are
+/// those of the provided `target_id` with respect to the central body of the file.
+///
+/// Each discontinuity in the ephemeris (e.g. at an impulsive maneuver, where GMAT repeats the epoch) starts a new segment.
+/// Segments of a single state cannot be interpolated, so they are skipped with a warning.
+/// Hermite interpolated files are stored as Type 13 segments, and Lagrange interpolated files as Type 9 segments.
+///
+/// # Limitations
+/// + Only the `EphemerisTimePosVel` data format is supported;
+/// + The coordinate system must be known to ANISE (e.g. J2000 or ICRF), and the scenario epoch must be in UTC Gregorian (the STK default).
+pub fn convert_stk_ephemeris(path: &str, target_id: NaifId) -> Result<SPK, EphemerisError> {
+    let contents = read_to_string(path)
+        .map_err(|e| DAFError::IO {
+            action: format!("reading STK ephemeris {path}"),
+            source: InputOutputError::IOError { kind: e.kind() },
+        })
+        .context(SPKSnafu {
+            action: "converting STK ephemeris",
+        })?;
+
+    let parsing_err = |lineno: usize, reason: String| EphemerisError::EphemerisFileParsing {
+        path: path.to_string(),
+        lineno,
+        reason,
+    };
+
+    let mut scenario_epoch = None;
+    let mut center_id = None;
+    let mut frame_id = None;
+    let mut interpolation = STKInterpolation::Lagrange;
+    let mut samples = 8;
+    let mut distance_factor = 1e-3;
+    let mut in_data = false;
+
+    // Each segment is the line number of its first state, a list of epochs in ET seconds, and of states in km and km/s
+    let mut segments: Vec<(usize, Vec<f64>, Vec<f64>)> = vec![(0, Vec::new(), Vec::new())];
+
+    for (lno, line) in contents.lines().enumerate() {
+        let lineno = lno + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("stk.v.") {
+            continue;
+        }
+
+        let mut tokens = line.split_whitespace();
+        let keyword = tokens.next().unwrap();
+        let value = tokens.collect::<Vec<&str>>().join(" ");
+
+        if in_data {
+            if keyword.eq_ignore_ascii_case("END") {
+                in_data = false;
+                continue;
+            }
+
+            let values = line
+                .split_whitespace()
+                .map(|item| item.parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|e| parsing_err(lineno, format!("{e}")))?;
+
+            if values.len() != 7 {
+                return Err(parsing_err(
+                    lineno,
+                    format!("expected an epoch offset and six state components, got {values:?}"),
+                ));
+            }
+
+            let epoch: Epoch = scenario_epoch.unwrap() + values[0].seconds();
+            let epoch_et_s = epoch.to_et_seconds();
+
+            let (_, epochs, _) = segments.last().unwrap();
+            if epochs
+                .last()
+                .is_some_and(|prev_epoch_et_s| epoch_et_s <= *prev_epoch_et_s)
+            {
+                // Discontinuity, start a new segment
+                segments.push((lineno, Vec::new(), Vec::new()));
+            }
+
+            let (first_lineno, epochs, states) = segments.last_mut().unwrap();
+            if epochs.is_empty() {
+                *first_lineno = lineno;
+            }
+            epochs.push(epoch_et_s);
+            states.extend(values[1..].iter().map(|val| val * distance_factor));
+        } else if keyword.eq_ignore_ascii_case("ScenarioEpoch") {
+            scenario_epoch = Some(parse_utcg(&value).ok_or_else(|| {
+                parsing_err(lineno, format!("could not parse `{value}` as UTCG"))
+            })?);
+        } else if keyword.eq_ignore_ascii_case("CentralBody") {
+            center_id = Some(
+                id_to_celestial_name(&value).map_err(|e| parsing_err(lineno, format!("{e}")))?,
+            );
+        } else if keyword.eq_ignore_ascii_case("CoordinateSystem") {
+            frame_id = Some(
+                id_to_orientation_name(&value).map_err(|e| parsing_err(lineno, format!("{e}")))?,
+            );
+        } else if keyword.eq_ignore_ascii_case("InterpolationMethod") {
+            interpolation = if value.eq_ignore_ascii_case("Hermite") {
+                STKInterpolation::Hermite
+            } else if value.eq_ignore_ascii_case("Lagrange") {
+                STKInterpolation::Lagrange
+            } else {
+                return Err(parsing_err(
+                    lineno,
+                    format!("unsupported interpolation method `{value}`"),
+                ));
+            };
+        } else if keyword.eq_ignore_ascii_case("InterpolationSamplesM1")
+            || keyword.eq_ignore_ascii_case("InterpolationOrder")
+        {
+            samples = value
+                .parse::<usize>()
+                .map_err(|e| parsing_err(lineno, format!("{e}")))?
+                + 1;
+        } else if keyword.eq_ignore_ascii_case("DistanceUnit") {
+            distance_factor = if value.eq_ignore_ascii_case("Meters") {
+                1e-3
+            } else if value.eq_ignore_ascii_case("Kilometers") {
+                1.0
+            } else {
+                return Err(parsing_err(
+                    lineno,
+                    format!("unsupported distance unit `{value}`"),
+                ));
+            };
+        } else if keyword.eq_ignore_ascii_case("EphemerisTimePosVel") {
+            if scenario_epoch.is_none() || center_id.is_none() || frame_id.is_none() {
+                return Err(parsing_err(
+                    lineno,
+                    "ScenarioEpoch, CentralBody, and CoordinateSystem must precede the data"
+                        .to_string(),
+                ));
+            }
+            in_data = true;
+        } else if keyword.starts_with("EphemerisTime") {
+            return Err(parsing_err(
+                lineno,
+                format!("unsupported ephemeris format `{keyword}`"),
+            ));
+        }
+    }
+
+    let center_id = center_id.ok_or_else(|| parsing_err(0, "no CentralBody".to_string()))?;
+    let frame_id = frame_id.ok_or_else(|| parsing_err(0, "no CoordinateSystem".to_string()))?;
+
+    let mut spk_segments = Vec::with_capacity(segments.len());
+    for (first_lineno, epoch_data, state_data) in &segments {
+        let num_records = epoch_data.len();
+        if num_records < 2 {
+            if let Some(epoch_et_s) = epoch_data.first() {
+                warn!(
+                    "[convert_stk_ephemeris] skipping the single state at {} (line {first_lineno} of {path}): it cannot be interpolated",
+                    Epoch::from_et_seconds(*epoch_et_s)
+                );
+            }
+            continue;
+        }
+
+        // Windows larger than the number of records would extrapolate, and the interpolation windows are centered so they must be even.
+        let mut window = samples.min(num_records).min(MAX_SAMPLES);
+        window -= window % 2;
+        if window != samples {
+            warn!("[convert_stk_ephemeris] using {window} samples instead of {samples}");
+        }
+
+        let epoch_registry = epoch_directory(epoch_data);
+
+        let (data, data_type) = match interpolation {
+            STKInterpolation::Hermite => (
+                HermiteSetType13 {
+                    samples: window,
+                    num_records,
+                    state_data,
+                    epoch_data,
+                    epoch_registry: &epoch_registry,
+                }
+                .to_f64_daf_vec(),
+                DafDataType::Type13HermiteUnequalStep,
+            ),
+            STKInterpolation::Lagrange => (
+                LagrangeSetType9 {
+                    degree: window - 1,
+                    num_records,
+                    state_data,
+                    epoch_data,
+                    epoch_registry: &epoch_registry,
+                }
+                .to_f64_daf_vec(),
+                DafDataType::Type9LagrangeUnequalStep,
+            ),
+        };
+
+        spk_segments.push(SPKSegment {
+            name: format!("STK ephemeris #{}", spk_segments.len()),
+            summary: SPKSummaryRecord {
+                start_epoch_et_s: epoch_data[0],
+                end_epoch_et_s: epoch_data[num_records - 1],
+                target_id,
+                center_id,
+                frame_id,
+                data_type_i: data_type as i32,
+                ..Default::default()
+            },
+            data: data.map_err(|_| parsing_err(0, "could not build segment".to_string()))?,
+        });
+    }
+
+    info!(
+        "[convert_stk_ephemeris] {} segment(s) of {target_id} built from {path}",
+        spk_segments.len()
+    );
+
+    SPK::from_segments(path, &spk_segments).context(SPKSnafu {
+        action: "building SPK from STK ephemeris",
+    })
+}
+
+/// Parses an STK UTC Gregorian date, e.g. `01 Jan 2000 11:59:28.000`.
+pub(crate) fn parse_utcg(value: &str) -> Option<Epoch> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let tokens = value.split_whitespace().collect::<Vec<&str>>();
+    if tokens.len() != 4 {
+        return None;
+    }
+
+    let day = tokens[0].parse::<u8>().ok()?;
+    let month = MONTHS
+        .iter()
+        .position(|month| month.eq_ignore_ascii_case(tokens[1]))? as u8
+        + 1;
+    let year = tokens[2].parse::<i32>().ok()?;
+
+    let hms = tokens[3].split(':').collect::<Vec<&str>>();
+    if hms.len() != 3 {
+        return None;
+    }
+    let hour = hms[0].parse::<u8>().ok()?;
+    let minute = hms[1].parse::<u8>().ok()?;
+    let seconds = hms[2].parse::<f64>().ok()?;
+
+    Some(Epoch::from_gregorian_utc(year, month, day, hour, minute, 0, 0) + seconds.seconds())
+}
+
+/// Builds the epoch directory of a Type 9 or Type 13 segment: every 100th epoch, excluding the last epoch of the segment,
+/// so a segment of N epochs has (N - 1) / 100 directory entries.
+fn epoch_directory(epoch_data: &[f64]) -> Vec<f64> {
+    epoch_data
+        .iter()
+        .skip(EPOCH_DIRECTORY_STEP - 1)
+        .step_by(EPOCH_DIRECTORY_STEP)
+        .take(epoch_data.len().saturating_sub(1) / EPOCH_DIRECTORY_STEP)
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod ut_stk {
+    use super::{epoch_directory, parse_utcg};
+    use hifitime::Epoch;
+
+    #[test]
+    fn utcg() {
+        assert_eq!(
+            parse_utcg("01 Jan 2000 11:59:28.000").unwrap(),
+            Epoch::from_gregorian_utc_hms(2000, 1, 1, 11, 59, 28)
+        );
+        assert_eq!(
+            parse_utcg("15 jul 2024 00:00:01.500").unwrap(),
+            Epoch::from_gregorian_utc(2024, 7, 15, 0, 0, 1, 500_000_000)
+        );
+        assert!(parse_utcg("2024-07-15T00:00:00").is_none());
+    }
+
+    #[test]
+    fn epoch_directory_boundaries() {
+        let epochs = |n: usize| (0..n).map(|i| i as f64).collect::<Vec<f64>>();
+
+        assert!(epoch_directory(&[]).is_empty());
+        assert!(epoch_directory(&epochs(99)).is_empty());
+        // The last epoch is never a directory entry.
+        assert!(epoch_directory(&epochs(100)).is_empty());
+        assert_eq!(epoch_directory(&epochs(101)), vec![99.0]);
+        assert_eq!(epoch_directory(&epochs(200)), vec![99.0]);
+        assert_eq!(epoch_directory(&epochs(201)), vec![99.0, 199.0]);
+    }
+}
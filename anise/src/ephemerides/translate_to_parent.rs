@@ -21,6 +21,8 @@ use crate::naif::daf::datatypes::{
     HermiteSetType13, LagrangeSetType9, Type2ChebyshevSet, Type3ChebyshevSet,
 };
 use crate::naif::daf::{DAFError, DafDataType, NAIFDataSet, NAIFSummaryRecord};
+use crate::naif::spk::summary::SPKSummaryRecord;
+use crate::naif::SPK;
 use crate::prelude::Frame;
 
 #[cfg(feature = "python")]
@@ -39,6 +41,30 @@ impl Almanac {
         &self,
         source: Frame,
         epoch: Epoch,
+    ) -> Result<(Vector3, Vector3, Frame), EphemerisError> {
+        // Custom providers have precedence over the SPK files.
+        if let Some(provider) = self.ephemeris_provider_at_epoch(source.ephemeris_id, epoch) {
+            let new_frame = source.with_ephem(provider.center_id());
+            trace!("translate {source} wrt to {new_frame} @ {epoch:E} using a provider");
+            let (pos_km, vel_km_s) = provider.position_velocity(epoch)?;
+            return Ok((pos_km, vel_km_s, new_frame));
+        }
+
+        if let Some(half_width) = self.boundary_smoothing {
+            if let Some(parts) = self.smoothed_translation_parts(source, epoch, half_width)? {
+                return Ok(parts);
+            }
+        }
+
+        self.spk_translation_parts(source, epoch)
+    }
+
+    /// Returns the position vector and velocity vector of the `source` with respect to its parent from the SPK segment used at
+    /// the provided epoch, without smoothing across the boundaries of the segments.
+    pub(crate) fn spk_translation_parts(
+        &self,
+        source: Frame,
+        epoch: Epoch,
     ) -> Result<(Vector3, Vector3, Frame), EphemerisError> {
         // First, let's find the SPK summary for this frame.
         let (summary, spk_no, idx_in_spk) =
@@ -48,14 +74,50 @@ impl Almanac {
 
         trace!("translate {source} wrt to {new_frame} @ {epoch:E}");
 
-        // This should not fail because we've fetched the spk_no from above with the spk_summary_at_epoch call.
+        let (pos_km, vel_km_s) = self.evaluate_spk_segment(summary, spk_no, idx_in_spk, epoch)?;
+
+        Ok((pos_km, vel_km_s, new_frame))
+    }
+
+    /// Evaluates the position and velocity of the segment of that summary, which is the segment number `idx_in_spk` of the SPK
+    /// number `spk_no`, at the provided epoch.
+    pub(crate) fn evaluate_spk_segment(
+        &self,
+        summary: &SPKSummaryRecord,
+        spk_no: usize,
+        idx_in_spk: usize,
+        epoch: Epoch,
+    ) -> Result<(Vector3, Vector3), EphemerisError> {
+        // This should not fail because the spk_no comes from a summary search.
         let spk_data = self.spk_data[spk_no]
             .as_ref()
             .ok_or(EphemerisError::Unreachable)?;
 
-        // Now let's simply evaluate the data
+        evaluate_spk_data(spk_data, summary, idx_in_spk, epoch)
+    }
+
+    /// Returns the acceleration of the `source` with respect to its parent in the ephemeris at the provided epoch, if the segment
+    /// provides it: Chebyshev Type 2 segments (analytic derivative of the polynomials) and Hermite Type 13 segments (from the fit).
+    /// Units are those used in the SPK, typically kilometers per second squared.
+    pub(crate) fn acceleration_to_parent(
+        &self,
+        source: Frame,
+        epoch: Epoch,
+    ) -> Result<(Option<Vector3>, Frame), EphemerisError> {
+        if let Some(provider) = self.ephemeris_provider_at_epoch(source.ephemeris_id, epoch) {
+            return Ok((None, source.with_ephem(provider.center_id())));
+        }
+
+        let (summary, spk_no, idx_in_spk) =
+            self.spk_summary_at_epoch(source.ephemeris_id, epoch)?;
 
-        let (pos_km, vel_km_s) = match summary.data_type()? {
+        let new_frame = source.with_ephem(summary.center_id);
+
+        let spk_data = self.spk_data[spk_no]
+            .as_ref()
+            .ok_or(EphemerisError::Unreachable)?;
+
+        let acc_km_s2 = match summary.data_type()? {
             DafDataType::Type2ChebyshevTriplet => {
                 let data =
                     spk_data
@@ -63,27 +125,11 @@ impl Almanac {
                         .context(SPKSnafu {
                             action: "fetching data for interpolation",
                         })?;
-                data.evaluate(epoch, summary)
-                    .context(EphemInterpolationSnafu)?
-            }
-            DafDataType::Type3ChebyshevSextuplet => {
-                let data =
-                    spk_data
-                        .nth_data::<Type3ChebyshevSet>(idx_in_spk)
-                        .context(SPKSnafu {
-                            action: "fetching data for interpolation",
-                        })?;
-                data.evaluate(epoch, summary)
-                    .context(EphemInterpolationSnafu)?
-            }
-            DafDataType::Type9LagrangeUnequalStep => {
-                let data = spk_data
-                    .nth_data::<LagrangeSetType9>(idx_in_spk)
-                    .context(SPKSnafu {
-                        action: "fetching data for interpolation",
-                    })?;
-                data.evaluate(epoch, summary)
-                    .context(EphemInterpolationSnafu)?
+                Some(
+                    data.evaluate_with_acceleration(epoch, summary)
+                        .context(EphemInterpolationSnafu)?
+                        .2,
+                )
             }
             DafDataType::Type13HermiteUnequalStep => {
                 let data = spk_data
@@ -91,21 +137,16 @@ impl Almanac {
                     .context(SPKSnafu {
                         action: "fetching data for interpolation",
                     })?;
-                data.evaluate(epoch, summary)
-                    .context(EphemInterpolationSnafu)?
-            }
-            dtype => {
-                return Err(EphemerisError::SPK {
-                    action: "translation to parent",
-                    source: DAFError::UnsupportedDatatype {
-                        dtype,
-                        kind: "SPK computations",
-                    },
-                })
+                Some(
+                    data.evaluate_with_acceleration(epoch, summary)
+                        .context(EphemInterpolationSnafu)?
+                        .2,
+                )
             }
+            _ => None,
         };
 
-        Ok((pos_km, vel_km_s, new_frame))
+        Ok((acc_km_s2, new_frame))
     }
 }
 
@@ -126,8 +167,68 @@ impl Almanac {
         Ok(CartesianState {
             radius_km,
             velocity_km_s,
+            acceleration_km_s2: None,
             epoch,
             frame,
         })
     }
 }
+
+/// Evaluates the position and velocity of the segment number `idx_in_spk` of this SPK, whose summary is provided, at the
+/// provided epoch.
+pub(crate) fn evaluate_spk_data(
+    spk_data: &SPK,
+    summary: &SPKSummaryRecord,
+    idx_in_spk: usize,
+    epoch: Epoch,
+) -> Result<(Vector3, Vector3), EphemerisError> {
+    let (pos_km, vel_km_s) = match summary.data_type()? {
+        DafDataType::Type2ChebyshevTriplet => {
+            let data = spk_data
+                .nth_data::<Type2ChebyshevSet>(idx_in_spk)
+                .context(SPKSnafu {
+                    action: "fetching data for interpolation",
+                })?;
+            data.evaluate(epoch, summary)
+                .context(EphemInterpolationSnafu)?
+        }
+        DafDataType::Type3ChebyshevSextuplet => {
+            let data = spk_data
+                .nth_data::<Type3ChebyshevSet>(idx_in_spk)
+                .context(SPKSnafu {
+                    action: "fetching data for interpolation",
+                })?;
+            data.evaluate(epoch, summary)
+                .context(EphemInterpolationSnafu)?
+        }
+        DafDataType::Type9LagrangeUnequalStep => {
+            let data = spk_data
+                .nth_data::<LagrangeSetType9>(idx_in_spk)
+                .context(SPKSnafu {
+                    action: "fetching data for interpolation",
+                })?;
+            data.evaluate(epoch, summary)
+                .context(EphemInterpolationSnafu)?
+        }
+        DafDataType::Type13HermiteUnequalStep => {
+            let data = spk_data
+                .nth_data::<HermiteSetType13>(idx_in_spk)
+                .context(SPKSnafu {
+                    action: "fetching data for interpolation",
+                })?;
+            data.evaluate(epoch, summary)
+                .context(EphemInterpolationSnafu)?
+        }
+        dtype => {
+            return Err(EphemerisError::SPK {
+                action: "translation to parent",
+                source: DAFError::UnsupportedDatatype {
+                    dtype,
+                    kind: "SPK computations",
+                },
+            })
+        }
+    };
+
+    Ok((pos_km, vel_km_s))
+}
@@ -14,14 +14,15 @@ use super::EphemerisError;
 use super::EphemerisPhysicsSnafu;
 use crate::almanac::Almanac;
 use crate::astro::aberration::stellar_aberration;
-use crate::astro::Aberration;
+use crate::astro::{Aberration, LightTimeConvergence, LightTimeSolution};
 use crate::constants::frames::SSB_J2000;
-use crate::constants::SPEED_OF_LIGHT_KM_S;
 use crate::hifitime::Epoch;
 use crate::math::cartesian::CartesianState;
 use crate::math::units::*;
 use crate::math::Vector3;
+use crate::naif::daf::DAFError;
 use crate::prelude::Frame;
+use crate::NaifId;
 
 /// **Limitation:** no translation or rotation may have more than 8 nodes.
 pub const MAX_TREE_DEPTH: usize = 8;
@@ -114,63 +115,18 @@ impl Almanac {
                 Ok(CartesianState {
                     radius_km: pos_bwrd - pos_fwrd,
                     velocity_km_s: vel_bwrd - vel_fwrd,
+                    acceleration_km_s2: None,
                     epoch,
                     frame: observer_frame.with_orient(target_frame.orientation_id),
                 })
             }
             Some(ab_corr) => {
-                // This is a rewrite of NAIF SPICE's `spkapo`
-
-                // Find the geometric position of the observer body with respect to the solar system barycenter.
+                // Find the geometric state of the observer body with respect to the solar system barycenter.
                 let obs_ssb = self.translate(observer_frame, SSB_J2000, epoch, None)?;
-                let obs_ssb_pos_km = obs_ssb.radius_km;
-                let obs_ssb_vel_km_s = obs_ssb.velocity_km_s;
-
-                // Find the geometric position of the target body with respect to the solar system barycenter.
-                let tgt_ssb = self.translate(target_frame, SSB_J2000, epoch, None)?;
-                let tgt_ssb_pos_km = tgt_ssb.radius_km;
-                let tgt_ssb_vel_km_s = tgt_ssb.velocity_km_s;
-
-                // Subtract the position of the observer to get the relative position.
-                let mut rel_pos_km = tgt_ssb_pos_km - obs_ssb_pos_km;
-                // NOTE: We never correct the velocity, so the geometric velocity is what we're seeking.
-                let mut rel_vel_km_s = tgt_ssb_vel_km_s - obs_ssb_vel_km_s;
-
-                // Use this to compute the one-way light time in seconds.
-                let mut one_way_lt_s = rel_pos_km.norm() / SPEED_OF_LIGHT_KM_S;
-
-                // To correct for light time, find the position of the target body at the current epoch
-                // minus the one-way light time. Note that the observer remains where he is.
-
-                let num_it = if ab_corr.converged { 3 } else { 1 };
-                let lt_sign = if ab_corr.transmit_mode { 1.0 } else { -1.0 };
-
-                for _ in 0..num_it {
-                    let epoch_lt = epoch + lt_sign * one_way_lt_s * TimeUnit::Second;
-                    let tgt_ssb = self.translate(target_frame, SSB_J2000, epoch_lt, None)?;
-                    let tgt_ssb_pos_km = tgt_ssb.radius_km;
-                    let tgt_ssb_vel_km_s = tgt_ssb.velocity_km_s;
-
-                    rel_pos_km = tgt_ssb_pos_km - obs_ssb_pos_km;
-                    rel_vel_km_s = tgt_ssb_vel_km_s - obs_ssb_vel_km_s;
-                    one_way_lt_s = rel_pos_km.norm() / SPEED_OF_LIGHT_KM_S;
-                }
-
-                // If stellar aberration correction is requested, perform it now.
-                if ab_corr.stellar {
-                    // Modifications based on transmission versus reception case is done in the function directly.
-                    rel_pos_km = stellar_aberration(rel_pos_km, obs_ssb_vel_km_s, ab_corr)
-                        .context(EphemerisPhysicsSnafu {
-                            action: "computing stellar aberration",
-                        })?;
-                }
 
-                Ok(CartesianState {
-                    radius_km: rel_pos_km,
-                    velocity_km_s: rel_vel_km_s,
-                    epoch,
-                    frame: observer_frame.with_orient(target_frame.orientation_id),
-                })
+                let mut state = self.aberrated_state(target_frame, obs_ssb, ab_corr)?;
+                state.frame = observer_frame.with_orient(target_frame.orientation_id);
+                Ok(state)
             }
         }
     }
@@ -190,6 +146,55 @@ impl Almanac {
         self.translate(target_frame, observer_frame, epoch, Aberration::NONE)
     }
 
+    /// Returns the geometric translation from the observer to the target, like `translate_geometric`, along with the acceleration
+    /// (in km/s^2) if all of the segments between them provide it, i.e. Chebyshev Type 2 and Hermite Type 13 segments.
+    ///
+    /// The acceleration is that of the interpolation of the ephemeris, so it is consistent with its position and velocity, as
+    /// needed to initialize a propagator or to plan a maneuver.
+    ///
+    /// :type target_frame: Orbit
+    /// :type observer_frame: Frame
+    /// :type epoch: Epoch
+    /// :rtype: Orbit
+    pub fn translate_with_acceleration(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        epoch: Epoch,
+    ) -> Result<CartesianState, EphemerisError> {
+        let mut state = self.translate(target_frame, observer_frame, epoch, None)?;
+        if observer_frame == target_frame {
+            state.acceleration_km_s2 = Some(Vector3::zeros());
+            return Ok(state);
+        }
+
+        let (_, _, common_node) =
+            self.common_ephemeris_path(observer_frame, target_frame, epoch)?;
+
+        // Sum the accelerations of each frame with respect to its parent up to the common node.
+        let acceleration_to_node = |mut frame: Frame| -> Result<Option<Vector3>, EphemerisError> {
+            let mut acc_km_s2 = Some(Vector3::zeros());
+            for _ in 0..MAX_TREE_DEPTH {
+                if frame.ephem_origin_id_match(common_node) {
+                    return Ok(acc_km_s2);
+                }
+                let (cur_acc_km_s2, parent_frame) = self.acceleration_to_parent(frame, epoch)?;
+                acc_km_s2 = acc_km_s2.zip(cur_acc_km_s2).map(|(acc, cur)| acc + cur);
+                frame = parent_frame;
+            }
+            Err(EphemerisError::SPK {
+                action: "computing acceleration to common node",
+                source: DAFError::MaxRecursionDepth,
+            })
+        };
+
+        state.acceleration_km_s2 = acceleration_to_node(target_frame)?
+            .zip(acceleration_to_node(observer_frame)?)
+            .map(|(acc_bwrd, acc_fwrd)| acc_bwrd - acc_fwrd);
+
+        Ok(state)
+    }
+
     /// Translates the provided Cartesian state into the requested observer frame
     ///
     /// **WARNING:** This function only performs the translation and no rotation _whatsoever_. Use the `transform_to` function instead to include rotations.
@@ -219,6 +224,153 @@ impl Almanac {
 }
 
 impl Almanac {
+    /// Returns the state of the target frame relative to an observer, corrected for light time and, if requested, for
+    /// stellar aberration, given the geometric state of that observer with respect to the solar system barycenter.
+    ///
+    /// This is the aberration pipeline of [Almanac::translate], and it is useful for observers which are not in the
+    /// loaded ephemerides, e.g. a spacecraft state from an orbit determination filter.
+    ///
+    /// # Arguments
+    /// + `target_frame`: the frame of the target, whose ephemeris must be loaded
+    /// + `observer_ssb`: the state of the observer with respect to the solar system barycenter, in the J2000 orientation,
+    ///   at the reception (or transmission) epoch
+    /// + `ab_corr`: the aberration correction
+    ///
+    /// The returned state is at the epoch of the observer state, and it is expressed in its frame, with the orientation
+    /// of the target frame. Only the position is corrected: the velocity is the geometric relative velocity at the light
+    /// time corrected epoch. A target coincident with the observer has no direction, so stellar aberration is not applied.
+    ///
+    /// # Algorithm
+    /// This is a rewrite of NAIF SPICE's `spkapo`, where the light time is iterated once when unconverged, three times
+    /// when converged, and as in SPICE's `spkltc` in SPICE compatibility mode, cf. [LightTimeConvergence].
+    pub fn aberrated_state(
+        &self,
+        target_frame: Frame,
+        observer_ssb: CartesianState,
+        ab_corr: Aberration,
+    ) -> Result<CartesianState, EphemerisError> {
+        let convergence = if self.spice_compat {
+            LightTimeConvergence::SPICE
+        } else {
+            LightTimeConvergence::ANISE
+        };
+
+        self.aberrated_state_converged(target_frame, observer_ssb, ab_corr, convergence)
+            .map(|solution| solution.state)
+    }
+
+    /// Returns the state of the target frame relative to an observer like [Almanac::aberrated_state], with the provided
+    /// stopping criteria of the converged light time iterations, and reports the convergence achieved.
+    pub fn aberrated_state_converged(
+        &self,
+        target_frame: Frame,
+        observer_ssb: CartesianState,
+        ab_corr: Aberration,
+        convergence: LightTimeConvergence,
+    ) -> Result<LightTimeSolution, EphemerisError> {
+        let epoch = observer_ssb.epoch;
+        let obs_ssb_pos_km = observer_ssb.radius_km;
+        let obs_ssb_vel_km_s = observer_ssb.velocity_km_s;
+
+        // Find the geometric position of the target body with respect to the solar system barycenter.
+        let tgt_ssb = self.translate(target_frame, SSB_J2000, epoch, None)?;
+        let tgt_ssb_pos_km = tgt_ssb.radius_km;
+        let tgt_ssb_vel_km_s = tgt_ssb.velocity_km_s;
+
+        // Subtract the position of the observer to get the relative position.
+        let mut rel_pos_km = tgt_ssb_pos_km - obs_ssb_pos_km;
+        // NOTE: We never correct the velocity, so the geometric velocity is what we're seeking.
+        let mut rel_vel_km_s = tgt_ssb_vel_km_s - obs_ssb_vel_km_s;
+
+        // Use this to compute the one-way light time in seconds, with the speed of light of this Almanac.
+        let speed_of_light_km_s = self.speed_of_light_km_s();
+        let mut one_way_lt_s = rel_pos_km.norm() / speed_of_light_km_s;
+        let mut prev_lt_s = 0.0;
+
+        // To correct for light time, find the position of the target body at the current epoch
+        // minus the one-way light time. Note that the observer remains where he is.
+
+        let num_it = if ab_corr.converged {
+            convergence.max_iterations
+        } else {
+            1
+        };
+        let lt_sign = if ab_corr.transmit_mode { 1.0 } else { -1.0 };
+
+        let mut iterations = 0;
+        for _ in 0..num_it {
+            // Stop iterating once the light time has converged.
+            if iterations > 0
+                && (one_way_lt_s - prev_lt_s).abs() <= convergence.tolerance * one_way_lt_s
+            {
+                break;
+            }
+            prev_lt_s = one_way_lt_s;
+
+            let epoch_lt = epoch + lt_sign * one_way_lt_s * TimeUnit::Second;
+            let tgt_ssb = self.translate(target_frame, SSB_J2000, epoch_lt, None)?;
+            let tgt_ssb_pos_km = tgt_ssb.radius_km;
+            let tgt_ssb_vel_km_s = tgt_ssb.velocity_km_s;
+
+            rel_pos_km = tgt_ssb_pos_km - obs_ssb_pos_km;
+            rel_vel_km_s = tgt_ssb_vel_km_s - obs_ssb_vel_km_s;
+            one_way_lt_s = rel_pos_km.norm() / speed_of_light_km_s;
+            iterations += 1;
+        }
+
+        let achieved_tolerance = if one_way_lt_s > 0.0 {
+            (one_way_lt_s - prev_lt_s).abs() / one_way_lt_s
+        } else {
+            0.0
+        };
+
+        // If stellar aberration correction is requested, perform it now.
+        if ab_corr.stellar && rel_pos_km.norm() > f64::EPSILON {
+            // Modifications based on transmission versus reception case is done in the function directly.
+            rel_pos_km = stellar_aberration(rel_pos_km, obs_ssb_vel_km_s, ab_corr).context(
+                EphemerisPhysicsSnafu {
+                    action: "computing stellar aberration",
+                },
+            )?;
+        }
+
+        Ok(LightTimeSolution {
+            state: CartesianState {
+                radius_km: rel_pos_km,
+                velocity_km_s: rel_vel_km_s,
+                acceleration_km_s2: None,
+                epoch,
+                frame: observer_ssb.frame.with_orient(target_frame.orientation_id),
+            },
+            iterations,
+            light_time: one_way_lt_s * TimeUnit::Second,
+            achieved_tolerance,
+        })
+    }
+
+    /// Returns the light time corrected state of the target frame as seen from the observer frame like
+    /// [Almanac::translate], with the provided stopping criteria of the converged light time iterations, and reports the
+    /// number of iterations and the convergence achieved, e.g. to validate against SPICE's `spkapo` (three iterations).
+    pub fn translate_light_time(
+        &self,
+        target_frame: Frame,
+        mut observer_frame: Frame,
+        epoch: Epoch,
+        ab_corr: Aberration,
+        convergence: LightTimeConvergence,
+    ) -> Result<LightTimeSolution, EphemerisError> {
+        if let Ok(obs_frame_info) = self.frame_from_uid(observer_frame) {
+            observer_frame = obs_frame_info;
+        }
+
+        let obs_ssb = self.translate(observer_frame, SSB_J2000, epoch, None)?;
+
+        let mut solution =
+            self.aberrated_state_converged(target_frame, obs_ssb, ab_corr, convergence)?;
+        solution.state.frame = observer_frame.with_orient(target_frame.orientation_id);
+        Ok(solution)
+    }
+
     /// Translates a state with its origin (`to_frame`) and given its units (distance_unit, time_unit), returns that state with respect to the requested frame
     ///
     /// **WARNING:** This function only performs the translation and no rotation _whatsoever_. Use the `transform_state_to` function instead to include rotations.
@@ -243,6 +395,7 @@ impl Almanac {
         let input_state = CartesianState {
             radius_km: position * dist_unit_factor,
             velocity_km_s: velocity * dist_unit_factor / time_unit_factor,
+            acceleration_km_s2: None,
             epoch,
             frame: from_frame,
         };
@@ -252,3 +405,20 @@ impl Almanac {
         })
     }
 }
+
+impl CartesianState {
+    /// Returns this state expressed about the provided new center, i.e. with respect to another ephemeris origin, at
+    /// the epoch of this state.
+    ///
+    /// Only the center changes: the orientation of the state is kept as is. This is a translation, unlike
+    /// `rotate_to` which changes the orientation but not the center, and it is equivalent to `translate_to` without
+    /// aberration corrections. The gravitational parameter and shape of the new frame are set from the Almanac if loaded.
+    pub fn recenter(&self, new_center: NaifId, almanac: &Almanac) -> Result<Self, EphemerisError> {
+        // Build the frame from its IDs only, so the data of the previous center is not carried over.
+        almanac.translate_to(
+            *self,
+            Frame::new(new_center, self.frame.orientation_id),
+            None,
+        )
+    }
+}
@@ -12,6 +12,7 @@ use hifitime::Epoch;
 use snafu::prelude::*;
 
 use crate::ephemerides::EphemerisError;
+use crate::naif::kpl::sclk::SCLKError;
 use crate::orientations::OrientationError;
 use crate::prelude::FrameUid;
 use crate::structure::dataset::DataSetError;
@@ -21,6 +22,9 @@ use core::convert::From;
 use der::Error as DerError;
 use std::io::ErrorKind as IOErrorKind;
 
+#[cfg(feature = "signing")]
+use crate::structure::signature::SignatureError;
+
 #[cfg(feature = "metaload")]
 use crate::almanac::metaload::MetaAlmanacError;
 #[cfg(feature = "metaload")]
@@ -53,6 +57,16 @@ pub enum AlmanacError {
     },
     #[snafu(display("{err}"))]
     GenericError { err: String },
+    #[snafu(display("{err} encountered when exporting to {path}"))]
+    Export { path: String, err: String },
+    #[snafu(display("{source} encountered with the clock of spacecraft {sc_id}"))]
+    SpacecraftClock { sc_id: NaifId, source: SCLKError },
+    #[cfg(feature = "signing")]
+    #[snafu(display("{source} encountered when verifying {path}"))]
+    Signature {
+        path: String,
+        source: SignatureError,
+    },
     #[cfg(feature = "metaload")]
     #[snafu(display("processing file #{fno} ({file:?}) caused an error: {source}"))]
     Meta {
@@ -181,6 +195,12 @@ pub enum PhysicsError {
         to: NaifId,
         state_frame: FrameUid,
     },
+    #[snafu(display("rotation matrix {from}->{to} is not a valid rotation while {action}"))]
+    InvalidRotationMatrix {
+        action: &'static str,
+        from: NaifId,
+        to: NaifId,
+    },
     #[snafu(display("{action} requires the time derivative of the DCM but it is not set"))]
     DCMMissingDerivative { action: &'static str },
     #[snafu(display("{action} requires the frame {frame} to have {data} defined"))]
@@ -214,3 +234,195 @@ impl From<IOErrorKind> for InputOutputError {
         Self::IOError { kind }
     }
 }
+
+/// Class of an error, i.e. the hundreds of its [ErrorCode::code].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum ErrorClass {
+    Almanac = 1,
+    Ephemeris = 2,
+    Orientation = 3,
+    Physics = 4,
+    Math = 5,
+    DAF = 6,
+    FileRecord = 7,
+    DataSet = 8,
+    LookUpTable = 9,
+    Decoding = 10,
+    Integrity = 11,
+    InputOutput = 12,
+    Interpolation = 13,
+    PlanetaryData = 14,
+}
+
+impl ErrorClass {
+    /// Returns the class of the provided error code, if it is valid.
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code / 100 {
+            1 => Some(Self::Almanac),
+            2 => Some(Self::Ephemeris),
+            3 => Some(Self::Orientation),
+            4 => Some(Self::Physics),
+            5 => Some(Self::Math),
+            6 => Some(Self::DAF),
+            7 => Some(Self::FileRecord),
+            8 => Some(Self::DataSet),
+            9 => Some(Self::LookUpTable),
+            10 => Some(Self::Decoding),
+            11 => Some(Self::Integrity),
+            12 => Some(Self::InputOutput),
+            13 => Some(Self::Interpolation),
+            14 => Some(Self::PlanetaryData),
+            _ => None,
+        }
+    }
+}
+
+/// Stable numeric codes of the errors, such that FFI users and services can branch on errors without matching their message.
+///
+/// The code of an error is the code of its root cause: an error which only wraps another error (e.g. an [AlmanacError]
+/// caused by an [EphemerisError]) returns the code of the wrapped error. The hundreds of the code are its [ErrorClass].
+/// Codes are never reused: new variants get new codes.
+pub trait ErrorCode {
+    /// Returns the numeric code of the root cause of this error.
+    fn code(&self) -> u16;
+
+    /// Returns the class of the root cause of this error.
+    fn class(&self) -> Option<ErrorClass> {
+        ErrorClass::from_code(self.code())
+    }
+}
+
+impl ErrorCode for AlmanacError {
+    fn code(&self) -> u16 {
+        match self {
+            Self::Ephemeris { source, .. } => source.code(),
+            Self::Orientation { source, .. } => source.code(),
+            Self::Loading { source, .. } => source.code(),
+            Self::TLDataSet { source, .. } => source.code(),
+            Self::GenericError { .. } => 101,
+            Self::Export { .. } => 102,
+            Self::SpacecraftClock { .. } => 103,
+            #[cfg(feature = "signing")]
+            Self::Signature { .. } => 104,
+            #[cfg(feature = "metaload")]
+            Self::Meta { .. } => 105,
+        }
+    }
+}
+
+impl ErrorCode for PhysicsError {
+    fn code(&self) -> u16 {
+        match self {
+            Self::Unreachable => 401,
+            Self::EpochMismatch { .. } => 402,
+            Self::FrameMismatch { .. } => 403,
+            Self::InvalidRotation { .. } => 404,
+            Self::InvalidStateRotation { .. } => 405,
+            Self::InvalidRotationMatrix { .. } => 406,
+            Self::DCMMissingDerivative { .. } => 407,
+            Self::MissingFrameData { .. } => 408,
+            Self::ParabolicEccentricity { .. } => 409,
+            Self::ParabolicSemiParam { .. } => 410,
+            Self::HyperbolicTrueAnomaly { .. } => 411,
+            Self::NotHyperbolic { .. } => 412,
+            Self::InfiniteValue { .. } => 413,
+            Self::AppliedMath { source } => source.code(),
+            Self::RadiusError { .. } => 414,
+            Self::VelocityError { .. } => 415,
+            Self::AberrationError { .. } => 416,
+        }
+    }
+}
+
+impl ErrorCode for MathError {
+    fn code(&self) -> u16 {
+        match self {
+            Self::DivisionByZero { .. } => 501,
+            Self::DomainError { .. } => 502,
+            Self::MaxIterationsReached { .. } => 503,
+        }
+    }
+}
+
+impl ErrorCode for DecodingError {
+    fn code(&self) -> u16 {
+        match self {
+            Self::TooFewDoubles { .. } => 1001,
+            Self::InaccessibleBytes { .. } => 1002,
+            Self::Integrity { source } => source.code(),
+            Self::DecodingDer { .. } => 1003,
+            Self::Casting => 1004,
+            Self::AniseVersion { .. } => 1005,
+            Self::Obscure { .. } => 1006,
+        }
+    }
+}
+
+impl ErrorCode for IntegrityError {
+    fn code(&self) -> u16 {
+        match self {
+            Self::ChecksumInvalid { .. } => 1101,
+            Self::DataMismatchOnMerge => 1102,
+            Self::DataMissing => 1103,
+            Self::LookupTable => 1104,
+            Self::DisjointRoots { .. } => 1105,
+            Self::SubNormal { .. } => 1106,
+            Self::InvalidValue { .. } => 1107,
+        }
+    }
+}
+
+impl ErrorCode for InputOutputError {
+    fn code(&self) -> u16 {
+        match self {
+            Self::IOError { .. } => 1201,
+            Self::IOUnknownError => 1202,
+        }
+    }
+}
+
+#[cfg(test)]
+mod ut_error_codes {
+    use super::{
+        AlmanacError, DecodingError, ErrorClass, ErrorCode, IntegrityError, MathError, PhysicsError,
+    };
+    use crate::ephemerides::EphemerisError;
+    use crate::naif::daf::DAFError;
+
+    #[test]
+    fn root_cause_codes() {
+        let math = MathError::DivisionByZero { action: "testing" };
+        assert_eq!(math.code(), 501);
+        assert_eq!(math.class(), Some(ErrorClass::Math));
+
+        // Wrapping errors return the code of their root cause
+        let physics = PhysicsError::AppliedMath { source: math };
+        assert_eq!(physics.code(), 501);
+
+        let almanac = AlmanacError::Ephemeris {
+            action: "testing",
+            source: Box::new(EphemerisError::SPK {
+                action: "testing",
+                source: DAFError::DecodingData {
+                    kind: "SPK",
+                    idx: 0,
+                    source: DecodingError::Integrity {
+                        source: IntegrityError::DataMissing,
+                    },
+                },
+            }),
+        };
+        assert_eq!(almanac.code(), 1103);
+        assert_eq!(almanac.class(), Some(ErrorClass::Integrity));
+
+        let generic = AlmanacError::GenericError {
+            err: "testing".to_string(),
+        };
+        assert_eq!(generic.code(), 101);
+        assert_eq!(generic.class(), Some(ErrorClass::Almanac));
+
+        assert_eq!(ErrorClass::from_code(1302), Some(ErrorClass::Interpolation));
+        assert_eq!(ErrorClass::from_code(42), None);
+    }
+}
@@ -72,6 +72,30 @@ impl Frame {
         Self::new(SOLAR_SYSTEM_BARYCENTER, orientation_id)
     }
 
+    /// Returns the frame of the bus of the provided spacecraft, centered on that spacecraft, following the NAIF convention
+    /// where the bus frame of the spacecraft -N is -N000 (e.g. -82000 for Cassini, -82), or None if that frame ID overflows.
+    pub const fn spacecraft_bus(spacecraft_id: NaifId) -> Option<Self> {
+        match spacecraft_id.checked_mul(1000) {
+            Some(bus_id) => Some(Self::new(spacecraft_id, bus_id)),
+            None => None,
+        }
+    }
+
+    /// Returns the provided spacecraft frame (e.g. of the bus or of an instrument) centered on its spacecraft, or None if
+    /// this frame ID does not follow the NAIF convention where the frames of the spacecraft -N are numbered from -N000 to
+    /// -N999 (e.g. the frame -123456 is centered on the spacecraft -123).
+    pub const fn from_spacecraft_frame(frame_id: NaifId) -> Option<Self> {
+        match spacecraft_id_of_frame(frame_id) {
+            Some(spacecraft_id) => Some(Self::new(spacecraft_id, frame_id)),
+            None => None,
+        }
+    }
+
+    /// Returns the ID of the spacecraft of the orientation of this frame, if it is a spacecraft frame, cf. [Frame::from_spacecraft_frame].
+    pub const fn spacecraft_id(&self) -> Option<NaifId> {
+        spacecraft_id_of_frame(self.orientation_id)
+    }
+
     /// Attempts to create a new frame from its center and reference frame name.
     /// This function is compatible with the CCSDS OEM names.
     pub fn from_name(center: &str, ref_frame: &str) -> Result<Self, AlmanacError> {
@@ -405,6 +429,15 @@ impl fmt::LowerHex for Frame {
     }
 }
 
+/// Returns the ID of the spacecraft of the provided frame ID, which are numbered from -N000 to -N999 for the spacecraft -N.
+const fn spacecraft_id_of_frame(frame_id: NaifId) -> Option<NaifId> {
+    if frame_id <= -1000 {
+        Some(frame_id / 1000)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod frame_ut {
     use super::Frame;
@@ -436,4 +469,22 @@ mod frame_ut {
     fn ccsds_name_to_frame() {
         assert_eq!(Frame::from_name("Earth", "ICRF").unwrap(), EARTH_J2000);
     }
+
+    #[test]
+    fn spacecraft_frames() {
+        assert_eq!(Frame::spacecraft_bus(-82), Some(Frame::new(-82, -82000)));
+        assert_eq!(Frame::spacecraft_bus(-3_000_000), None);
+        assert_eq!(
+            Frame::from_spacecraft_frame(-123456),
+            Some(Frame::new(-123, -123456))
+        );
+        assert_eq!(
+            Frame::spacecraft_bus(-82).unwrap().spacecraft_id(),
+            Some(-82)
+        );
+        // Neither NAIF body frames nor spacecraft IDs themselves are spacecraft frames.
+        assert_eq!(Frame::from_spacecraft_frame(31006), None);
+        assert_eq!(Frame::from_spacecraft_frame(-82), None);
+        assert_eq!(EARTH_J2000.spacecraft_id(), None);
+    }
 }
@@ -0,0 +1,274 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::fs;
+use std::path::Path;
+
+use hifitime::Epoch;
+
+use super::{TopocentricAxes, TopocentricFrame};
+use crate::astro::{AzElRange, PhysicsResult};
+use crate::errors::{AlmanacError, AlmanacResult, InputOutputError, MathError, PhysicsError};
+use crate::math::angles::{azimuth_elevation_deg, between_0_360};
+use crate::prelude::Orbit;
+
+/// A point of a terrain profile, in geodetic coordinates with respect to the ellipsoid of the body.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct TerrainPoint {
+    pub latitude_deg: f64,
+    /// Longitude, positive to the East
+    pub longitude_deg: f64,
+    /// Height above the ellipsoid
+    pub height_km: f64,
+}
+
+impl TerrainPoint {
+    /// Parses a terrain profile from CSV, one `latitude_deg,longitude_deg,height_km` point per line.
+    ///
+    /// Empty lines, lines starting with `#`, and a header line whose first column is not a number are skipped.
+    pub fn parse_csv(csv: &str) -> AlmanacResult<Vec<Self>> {
+        let mut points = Vec::new();
+
+        for (lno, line) in csv.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let columns = line
+                .split(',')
+                .map(|column| column.trim().parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>();
+
+            match columns {
+                Ok(columns) if columns.len() == 3 => points.push(Self {
+                    latitude_deg: columns[0],
+                    longitude_deg: columns[1],
+                    height_km: columns[2],
+                }),
+                Err(_) if points.is_empty() && lno == 0 => continue,
+                _ => {
+                    return Err(AlmanacError::GenericError {
+                        err: format!(
+                            "terrain profile line {}: expected `latitude_deg,longitude_deg,height_km` but got `{line}`",
+                            lno + 1
+                        ),
+                    })
+                }
+            }
+        }
+
+        Ok(points)
+    }
+
+    /// Reads a terrain profile from a CSV file, cf. [TerrainPoint::parse_csv].
+    pub fn from_csv<P: AsRef<Path>>(path: P) -> AlmanacResult<Vec<Self>> {
+        let csv = fs::read_to_string(&path).map_err(|e| AlmanacError::Loading {
+            path: path.as_ref().display().to_string(),
+            source: InputOutputError::IOError { kind: e.kind() },
+        })?;
+        Self::parse_csv(&csv)
+    }
+}
+
+/// Minimum elevation of the visible sky as a function of the azimuth at a site, e.g. due to the surrounding terrain.
+///
+/// The mask is defined by points sorted by azimuth, and linearly interpolated between them, wrapping around North.
+/// An empty mask is the geometric horizon, i.e. a minimum elevation of zero degrees in all directions.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HorizonMask {
+    /// Azimuth (clockwise from the North) and minimum elevation in degrees, sorted by azimuth
+    points: Vec<(f64, f64)>,
+}
+
+impl HorizonMask {
+    /// Builds a mask from pairs of azimuth and minimum elevation in degrees, in any order.
+    pub fn new<I: IntoIterator<Item = (f64, f64)>>(points: I) -> Self {
+        let mut points = points
+            .into_iter()
+            .map(|(azimuth_deg, elevation_deg)| (between_0_360(azimuth_deg), elevation_deg))
+            .collect::<Vec<_>>();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { points }
+    }
+
+    /// Builds the mask of a site from a terrain profile, by computing the elevation of each terrain point above the local
+    /// horizon of the site, both being placed on the ellipsoid of the body fixed frame of the site, and keeping the highest
+    /// elevation in each azimuth bin of `bin_deg` degrees. Bins without any terrain point are interpolated from their
+    /// neighbors.
+    ///
+    /// The body fixed frame of the site must have its shape defined, e.g. by fetching it with `Almanac::frame_from_uid`, and
+    /// the bin width must be finite and within (0, 360] degrees.
+    pub fn from_terrain(
+        site: &TopocentricFrame,
+        terrain: &[TerrainPoint],
+        bin_deg: f64,
+    ) -> PhysicsResult<Self> {
+        if !(bin_deg.is_finite() && bin_deg > 0.0 && bin_deg <= 360.0) {
+            return Err(PhysicsError::AppliedMath {
+                source: MathError::DomainError {
+                    value: bin_deg,
+                    msg: "azimuth bin width of a horizon mask must be within (0, 360] degrees",
+                },
+            });
+        }
+
+        let num_bins = (360.0 / bin_deg).ceil().max(1.0) as usize;
+        let mut bins: Vec<Option<f64>> = vec![None; num_bins];
+
+        // The body fixed positions do not depend on the epoch.
+        let epoch = Epoch::from_tdb_seconds(0.0);
+        let site_km = site.site(epoch)?.radius_km;
+        let sez_to_body_fixed = site
+            .with_axes(TopocentricAxes::SEZ)
+            .dcm_to_body_fixed()
+            .rot_mat;
+
+        for point in terrain {
+            let point_km = Orbit::try_latlongalt(
+                point.latitude_deg,
+                point.longitude_deg,
+                point.height_km,
+                0.0,
+                epoch,
+                site.body_frame,
+            )?
+            .radius_km;
+
+            let rho_sez = sez_to_body_fixed.transpose() * (point_km - site_km);
+            if rho_sez.norm() < 1e-6 {
+                // The site itself has no direction.
+                continue;
+            }
+
+            let (azimuth_deg, elevation_deg) = azimuth_elevation_deg(&rho_sez);
+            let bin = bins
+                .get_mut((azimuth_deg / bin_deg) as usize % num_bins)
+                .unwrap();
+            *bin = Some(bin.map_or(elevation_deg, |max_deg: f64| max_deg.max(elevation_deg)));
+        }
+
+        Ok(Self::new(bins.iter().enumerate().filter_map(
+            |(bno, bin)| bin.map(|elevation_deg| ((bno as f64 + 0.5) * bin_deg, elevation_deg)),
+        )))
+    }
+
+    /// Returns the points of this mask as pairs of azimuth and minimum elevation in degrees, sorted by azimuth.
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    /// Returns the minimum elevation of the visible sky in the direction of the provided azimuth, in degrees.
+    pub fn elevation_deg(&self, azimuth_deg: f64) -> f64 {
+        let azimuth_deg = between_0_360(azimuth_deg);
+
+        match self.points.len() {
+            0 => 0.0,
+            1 => self.points[0].1,
+            num => {
+                let idx = self.points.partition_point(|(az, _)| *az <= azimuth_deg);
+                // Wrap around North on either side of the first and last points.
+                let (prev_az, prev_el) = match idx {
+                    0 => (self.points[num - 1].0 - 360.0, self.points[num - 1].1),
+                    _ => self.points[idx - 1],
+                };
+                let (next_az, next_el) = match idx {
+                    idx if idx == num => (self.points[0].0 + 360.0, self.points[0].1),
+                    _ => self.points[idx],
+                };
+
+                if next_az - prev_az < f64::EPSILON {
+                    prev_el.max(next_el)
+                } else {
+                    prev_el + (next_el - prev_el) * (azimuth_deg - prev_az) / (next_az - prev_az)
+                }
+            }
+        }
+    }
+
+    /// Returns true if the direction of this azimuth, elevation, and range is above the mask and not obstructed.
+    pub fn is_visible(&self, aer: &AzElRange) -> bool {
+        aer.is_valid()
+            && !aer.is_obstructed()
+            && aer.elevation_deg >= self.elevation_deg(aer.azimuth_deg)
+    }
+}
+
+#[cfg(test)]
+mod ut_horizon_mask {
+    use super::{HorizonMask, TerrainPoint};
+    use crate::astro::AzElRange;
+    use crate::constants::frames::EARTH_ITRF93;
+    use crate::prelude::Frame;
+    use crate::structure::planetocentric::ellipsoid::Ellipsoid;
+    use hifitime::{Duration, Epoch};
+
+    #[test]
+    fn interpolated_mask() {
+        let mask = HorizonMask::new([(90.0, 10.0), (-10.0, 2.0), (180.0, 4.0)]);
+        assert_eq!(mask.points()[0], (90.0, 10.0));
+        assert_eq!(mask.points()[2], (350.0, 2.0));
+
+        assert_eq!(mask.elevation_deg(90.0), 10.0);
+        assert_eq!(mask.elevation_deg(135.0), 7.0);
+        // Wraps around North: 350 -> 450 (i.e. 90) degrees
+        assert!((mask.elevation_deg(0.0) - 2.8).abs() < 1e-12);
+        assert!((mask.elevation_deg(355.0) - 2.4).abs() < 1e-12);
+        assert_eq!(HorizonMask::default().elevation_deg(12.0), 0.0);
+    }
+
+    #[test]
+    fn mask_from_terrain() {
+        let csv = "# A ridge to the East of the site
+latitude_deg,longitude_deg,height_km
+0.0,0.01,1.0
+
+0.0,-0.5,0.0
+";
+        let terrain = TerrainPoint::parse_csv(csv).unwrap();
+        assert_eq!(terrain.len(), 2);
+        assert!(TerrainPoint::parse_csv("0.0,1.0").is_err());
+
+        let mut body_frame = EARTH_ITRF93;
+        body_frame.shape = Some(Ellipsoid::from_spheroid(6378.1366, 6356.7519));
+        let site = Frame::topocentric("SITE_TOPO", 1_399_002, 0.0, 0.0, 0.0, body_frame);
+
+        for bin_deg in [0.0, -1.0, 361.0, f64::NAN, f64::INFINITY] {
+            assert!(HorizonMask::from_terrain(&site, &terrain, bin_deg).is_err());
+        }
+
+        let mask = HorizonMask::from_terrain(&site, &terrain, 1.0).unwrap();
+        assert_eq!(mask.points().len(), 2);
+
+        // The ridge is about 1.1 km away and 1 km high, so it is high above the horizon to the East.
+        let (ridge_az_deg, ridge_el_deg) = mask.points()[0];
+        assert!((ridge_az_deg - 90.0).abs() < 1.0);
+        assert!(ridge_el_deg > 40.0 && ridge_el_deg < 45.0);
+        // Far points on the ellipsoid are below the local horizon, due to the curvature of the Earth.
+        let (flat_az_deg, flat_el_deg) = mask.points()[1];
+        assert!((flat_az_deg - 270.0).abs() < 1.0);
+        assert!(flat_el_deg < 0.0);
+
+        // The mask is used by the site to decide whether a target is visible.
+        let site = site.with_horizon_mask(mask);
+        let mut aer = AzElRange {
+            epoch: Epoch::from_tdb_seconds(0.0),
+            azimuth_deg: 90.0,
+            elevation_deg: 30.0,
+            range_km: 1000.0,
+            range_rate_km_s: 0.0,
+            obstructed_by: None,
+            light_time: Duration::ZERO,
+        };
+        assert!(!site.is_visible(&aer));
+        aer.azimuth_deg = 180.0;
+        assert!(site.is_visible(&aer));
+    }
+}
@@ -10,6 +10,10 @@
 
 mod frame;
 mod frameuid;
+mod horizon_mask;
+mod topocentric;
 
 pub use frame::Frame;
 pub use frameuid::FrameUid;
+pub use horizon_mask::{HorizonMask, TerrainPoint};
+pub use topocentric::{TopocentricAxes, TopocentricFrame};
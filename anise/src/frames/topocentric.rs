@@ -0,0 +1,357 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::sync::Arc;
+
+use hifitime::{Duration, Epoch, Unit as TimeUnit};
+use snafu::ResultExt;
+
+use super::{Frame, HorizonMask};
+use crate::almanac::events::{EventCrossing, EVENT_PRECISION_S};
+use crate::almanac::orientation_subset::OrientationAlmanac;
+use crate::almanac::Almanac;
+use crate::astro::{Aberration, AzElRange, PhysicsResult};
+use crate::constants::orientations::J2000;
+use crate::ephemerides::provider::EphemerisProvider;
+use crate::ephemerides::{EphemerisError, EphemerisPhysicsSnafu};
+use crate::errors::{AlmanacError, AlmanacResult, EphemerisSnafu, OrientationSnafu};
+use crate::math::cartesian::CartesianState;
+use crate::math::rotation::DCM;
+use crate::math::{Matrix3, Vector3};
+use crate::orientations::ConstantOrientation;
+use crate::prelude::Orbit;
+use crate::NaifId;
+
+/// Axes of a topocentric frame, cf. [TopocentricFrame].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TopocentricAxes {
+    /// East, North, Up
+    #[default]
+    ENU,
+    /// South, East, Zenith, as used by the azimuth, elevation, and range computations
+    SEZ,
+}
+
+/// A frame whose axes follow the local geodetic horizon of a site on a body, like a SPICE topocentric TK frame,
+/// cf. [Frame::topocentric].
+///
+/// Once registered with [Almanac::with_topocentric_frame], the frame is centered on the site, which is at rest in the body
+/// fixed frame, and its orientation is a constant rotation from the body fixed frame, so states can be expressed in it with
+/// the usual `translate` and `transform` functions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TopocentricFrame {
+    pub name: String,
+    /// ID of this frame, used both as the ephemeris ID of the site and as the orientation ID of its axes, which must not be
+    /// used by any other object or frame
+    pub id: NaifId,
+    /// Geodetic latitude of the site
+    pub latitude_deg: f64,
+    /// Longitude of the site, positive to the East
+    pub longitude_deg: f64,
+    /// Height of the site above the ellipsoid of the body
+    pub height_km: f64,
+    /// Body fixed frame of the site, e.g. `EARTH_ITRF93`
+    pub body_frame: Frame,
+    pub axes: TopocentricAxes,
+    /// Terrain mask of the site, if any, cf. [HorizonMask::from_terrain]
+    pub horizon_mask: Option<HorizonMask>,
+}
+
+impl Frame {
+    /// Returns a topocentric frame with East North Up axes at the provided geodetic coordinates of a site on the body
+    /// of the provided body fixed frame, which can then be registered with [Almanac::with_topocentric_frame].
+    pub fn topocentric(
+        name: &str,
+        id: NaifId,
+        latitude_deg: f64,
+        longitude_deg: f64,
+        height_km: f64,
+        body_frame: Frame,
+    ) -> TopocentricFrame {
+        TopocentricFrame {
+            name: name.to_string(),
+            id,
+            latitude_deg,
+            longitude_deg,
+            height_km,
+            body_frame,
+            axes: TopocentricAxes::default(),
+            horizon_mask: None,
+        }
+    }
+}
+
+impl TopocentricFrame {
+    /// Returns a copy of this topocentric frame with the provided axes.
+    pub fn with_axes(&self, axes: TopocentricAxes) -> Self {
+        let mut me = self.clone();
+        me.axes = axes;
+        me
+    }
+
+    /// Returns a copy of this topocentric frame with the provided horizon mask, used to decide whether targets are visible.
+    pub fn with_horizon_mask(&self, horizon_mask: HorizonMask) -> Self {
+        let mut me = self.clone();
+        me.horizon_mask = Some(horizon_mask);
+        me
+    }
+
+    /// Returns the frame centered on the site with the orientation of this topocentric frame.
+    pub fn frame(&self) -> Frame {
+        Frame::new(self.id, self.id)
+    }
+
+    /// Returns the rotation from this frame to the body fixed frame, whose columns are the axes of this frame.
+    ///
+    /// The axes only depend on the geodetic latitude and longitude: the Up (or Zenith) axis is the normal to the ellipsoid.
+    pub fn dcm_to_body_fixed(&self) -> DCM {
+        let (sin_lat, cos_lat) = self.latitude_deg.to_radians().sin_cos();
+        let (sin_long, cos_long) = self.longitude_deg.to_radians().sin_cos();
+
+        let east = Vector3::new(-sin_long, cos_long, 0.0);
+        let north = Vector3::new(-sin_lat * cos_long, -sin_lat * sin_long, cos_lat);
+        let up = Vector3::new(cos_lat * cos_long, cos_lat * sin_long, sin_lat);
+
+        let rot_mat = match self.axes {
+            TopocentricAxes::ENU => Matrix3::from_columns(&[east, north, up]),
+            TopocentricAxes::SEZ => Matrix3::from_columns(&[-north, east, up]),
+        };
+
+        DCM {
+            rot_mat,
+            rot_mat_dt: None,
+            from: self.id,
+            to: self.body_frame.orientation_id,
+        }
+    }
+
+    /// Returns the constant orientation of this frame with respect to the body fixed frame.
+    pub fn orientation(&self) -> ConstantOrientation {
+        self.dcm_to_body_fixed().into()
+    }
+
+    /// Returns the state of the site at the provided epoch, at rest in the body fixed frame.
+    ///
+    /// The body fixed frame must have its shape defined, e.g. by fetching it with `Almanac::frame_from_uid`.
+    pub fn site(&self, epoch: Epoch) -> PhysicsResult<Orbit> {
+        Orbit::try_latlongalt(
+            self.latitude_deg,
+            self.longitude_deg,
+            self.height_km,
+            0.0,
+            epoch,
+            self.body_frame,
+        )
+    }
+
+    /// Computes the azimuth, elevation, and range of the receiver as seen from this site, cf.
+    /// [Almanac::azimuth_elevation_range_sez].
+    pub fn azimuth_elevation_range(
+        &self,
+        almanac: &Almanac,
+        rx: Orbit,
+        obstructing_body: Option<Frame>,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<AzElRange> {
+        let tx = self
+            .site(rx.epoch)
+            .map_err(|e| AlmanacError::GenericError {
+                err: format!("{}: {e}", self.name),
+            })?;
+        almanac.azimuth_elevation_range_sez(rx, tx, obstructing_body, ab_corr)
+    }
+
+    /// Returns true if the target of this azimuth, elevation, and range computation is visible from this site: it must not
+    /// be obstructed, and it must be above the horizon mask of this site if any, or above the local horizon otherwise.
+    pub fn is_visible(&self, aer: &AzElRange) -> bool {
+        match &self.horizon_mask {
+            Some(mask) => mask.is_visible(aer),
+            None => HorizonMask::default().is_visible(aer),
+        }
+    }
+}
+
+/// Ephemeris of the site of a topocentric frame, at rest in its body fixed frame, cf. [Almanac::with_topocentric_frame].
+struct SiteEphemeris {
+    id: NaifId,
+    /// Position of the site in the body fixed frame
+    site: CartesianState,
+    /// Orientation data used to rotate the site from the body fixed frame into J2000
+    orientations: OrientationAlmanac,
+}
+
+impl EphemerisProvider for SiteEphemeris {
+    fn target_id(&self) -> NaifId {
+        self.id
+    }
+
+    fn center_id(&self) -> NaifId {
+        self.site.frame.ephemeris_id
+    }
+
+    fn covers(&self, _epoch: Epoch) -> bool {
+        true
+    }
+
+    fn position_velocity(&self, epoch: Epoch) -> Result<(Vector3, Vector3), EphemerisError> {
+        let mut site = self.site;
+        site.epoch = epoch;
+        let state = self
+            .orientations
+            .rotate_to(site, self.site.frame.with_orient(J2000))
+            .map_err(|e| EphemerisError::Provider {
+                id: self.id,
+                reason: e.to_string(),
+            })?;
+        Ok((state.radius_km, state.velocity_km_s))
+    }
+}
+
+impl Almanac {
+    /// Returns a copy of this Almanac where the provided topocentric frame is registered by its name and ID: its orientation
+    /// as a constant rotation from its body fixed frame, and its site as an ephemeris provider centered on the body.
+    ///
+    /// The body fixed frame must have its shape defined in this Almanac, and the site is rotated into J2000 with the
+    /// orientation data loaded at the time of this call, so the orientation of the body fixed frame must be loaded first.
+    pub fn with_topocentric_frame(&self, topocentric: &TopocentricFrame) -> AlmanacResult<Self> {
+        let body_frame = self.frame_from_uid(topocentric.body_frame).map_err(|e| {
+            AlmanacError::GenericError {
+                err: format!("{e} when fetching frame data for {}", topocentric.name),
+            }
+        })?;
+        let site = Orbit::try_latlongalt(
+            topocentric.latitude_deg,
+            topocentric.longitude_deg,
+            topocentric.height_km,
+            0.0,
+            Epoch::from_tdb_seconds(0.0),
+            body_frame,
+        )
+        .context(EphemerisPhysicsSnafu {
+            action: "computing the site of a topocentric frame",
+        })
+        .context(EphemerisSnafu {
+            action: "registering topocentric frame",
+        })?;
+
+        let me = self
+            .with_constant_orientation(topocentric.orientation(), Some(&topocentric.name))
+            .context(OrientationSnafu {
+                action: "registering topocentric frame",
+            })?;
+
+        Ok(me.with_ephemeris_provider(Arc::new(SiteEphemeris {
+            id: topocentric.id,
+            site,
+            orientations: self.orientation_subset(),
+        })))
+    }
+
+    /// Returns the visibility event function of the target as seen from the site, i.e. the elevation of the target above
+    /// the horizon mask of the site (or above the local horizon if the site has no mask) in degrees, which is positive
+    /// when the target is visible. A target hidden by the obstructing body is reported at -90 degrees.
+    ///
+    /// The body fixed frame of the site must have its shape defined, e.g. by fetching it with `Almanac::frame_from_uid`.
+    pub fn visibility_margin_deg(
+        &self,
+        site: &TopocentricFrame,
+        target_frame: Frame,
+        epoch: Epoch,
+        obstructing_body: Option<Frame>,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<f64> {
+        let rx = self.transform(target_frame, site.body_frame, epoch, ab_corr)?;
+        let aer = site.azimuth_elevation_range(self, rx, obstructing_body, ab_corr)?;
+        if aer.is_obstructed() {
+            return Ok(-90.0);
+        }
+
+        let mask_deg = site
+            .horizon_mask
+            .as_ref()
+            .map_or(0.0, |mask| mask.elevation_deg(aer.azimuth_deg));
+        Ok(aer.elevation_deg - mask_deg)
+    }
+
+    /// Finds the epochs where the target rises above and sets below the horizon mask of the site between `start` and
+    /// `end`, cf. [Almanac::visibility_margin_deg].
+    ///
+    /// A rise is reported as [Rising](crate::almanac::events::CrossingDirection::Rising) and a set as
+    /// [Falling](crate::almanac::events::CrossingDirection::Falling). The visibility is searched every `step`, and each
+    /// crossing is refined to [EVENT_PRECISION_S].
+    #[allow(clippy::too_many_arguments)]
+    pub fn visibility_crossings(
+        &self,
+        site: &TopocentricFrame,
+        target_frame: Frame,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+        obstructing_body: Option<Frame>,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<Vec<EventCrossing>> {
+        self.find_crossings(
+            start,
+            end,
+            step,
+            EVENT_PRECISION_S * TimeUnit::Second,
+            |almanac, epoch| {
+                almanac.visibility_margin_deg(site, target_frame, epoch, obstructing_body, ab_corr)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod ut_topocentric {
+    use super::{TopocentricAxes, TopocentricFrame};
+    use crate::constants::frames::EARTH_ITRF93;
+    use crate::math::Vector3;
+    use crate::prelude::Frame;
+
+    fn station(latitude_deg: f64, longitude_deg: f64) -> TopocentricFrame {
+        Frame::topocentric(
+            "STATION_TOPO",
+            1_399_001,
+            latitude_deg,
+            longitude_deg,
+            0.0,
+            EARTH_ITRF93,
+        )
+    }
+
+    #[test]
+    fn topocentric_axes() {
+        // At the intersection of the equator and the prime meridian, East is +Y, North is +Z, and Up is +X.
+        let dcm = station(0.0, 0.0).dcm_to_body_fixed();
+        assert!((dcm.rot_mat * Vector3::x() - Vector3::y()).norm() < 1e-15);
+        assert!((dcm.rot_mat * Vector3::y() - Vector3::z()).norm() < 1e-15);
+        assert!((dcm.rot_mat * Vector3::z() - Vector3::x()).norm() < 1e-15);
+        assert_eq!(dcm.from, 1_399_001);
+        assert_eq!(dcm.to, EARTH_ITRF93.orientation_id);
+
+        // South is -North, so SEZ is ENU rotated about the Up axis.
+        let topo = station(40.4, -4.25);
+        let enu = topo.dcm_to_body_fixed().rot_mat;
+        let sez = topo
+            .with_axes(TopocentricAxes::SEZ)
+            .dcm_to_body_fixed()
+            .rot_mat;
+        assert!((sez.column(0) + enu.column(1)).norm() < 1e-15);
+        assert!((sez.column(1) - enu.column(0)).norm() < 1e-15);
+        assert!((sez.column(2) - enu.column(2)).norm() < 1e-15);
+        assert!((enu.determinant() - 1.0).abs() < 1e-12);
+        assert!((sez.determinant() - 1.0).abs() < 1e-12);
+
+        // The frame is centered on the site.
+        assert_eq!(topo.frame().ephemeris_id, 1_399_001);
+        assert_eq!(topo.frame().orientation_id, 1_399_001);
+    }
+}
@@ -16,6 +16,7 @@ extern crate log;
 
 pub mod almanac;
 pub mod astro;
+pub mod compare;
 pub mod constants;
 pub mod ephemerides;
 pub mod errors;
@@ -25,6 +26,9 @@ pub mod naif;
 pub mod orientations;
 pub mod structure;
 
+#[cfg(feature = "python")]
+pub mod python_config;
+
 /// Re-export of hifitime
 pub mod time {
     pub use core::str::FromStr;
@@ -48,7 +52,7 @@ pub mod prelude {
 
     pub use crate::almanac::Almanac;
     pub use crate::astro::{orbit::Orbit, Aberration};
-    pub use crate::errors::InputOutputError;
+    pub use crate::errors::{ErrorCode, InputOutputError};
     pub use crate::frames::*;
     pub use crate::math::units::*;
     pub use crate::naif::daf::NAIFSummaryRecord;
@@ -87,6 +91,27 @@ macro_rules! file2heap {
     };
 }
 
+/// Memory maps a file **without** copying it: the pages of the file are only read from disk when accessed, and the
+/// mapping is released when the last clone of the returned bytes is dropped.
+///
+/// The file must not be modified while it is mapped, or the bytes may change, as for any memory mapped file.
+#[macro_export]
+macro_rules! file2mmap {
+    ($filename:tt) => {
+        match std::fs::File::open($filename) {
+            Err(e) => Err($crate::errors::InputOutputError::IOError { kind: e.kind() }),
+            Ok(file) => unsafe {
+                use bytes::Bytes;
+                use memmap2::MmapOptions;
+                match MmapOptions::new().map(&file) {
+                    Err(_) => Err($crate::errors::InputOutputError::IOUnknownError),
+                    Ok(mmap) => Ok(Bytes::from_owner(mmap)),
+                }
+            },
+        }
+    };
+}
+
 /// Memory maps a file and **copies** the data on the heap prior to returning a pointer to this heap data.
 #[macro_export]
 macro_rules! file_mmap {
@@ -8,6 +8,8 @@
  * Documentation: https://nyxspace.com/
  */
 
+use super::Vector3;
+
 /// Returns the provided angle bounded between 0.0 and 360.0
 pub fn between_0_360(angle: f64) -> f64 {
     let mut bounded = angle;
@@ -36,3 +38,185 @@ pub fn between_pm_x(angle: f64, x: f64) -> f64 {
     }
     bounded
 }
+
+/// Spherical coordinates of a Cartesian state and their rates, e.g. the range, right ascension, and declination in an inertial frame.
+///
+/// The longitude is measured in the XY plane from the X axis, and the latitude from the XY plane towards the Z axis.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct SphericalState {
+    pub range_km: f64,
+    /// Longitude, bounded between 0.0 and 360.0
+    pub longitude_deg: f64,
+    /// Latitude, bounded between -90.0 and +90.0
+    pub latitude_deg: f64,
+    pub range_rate_km_s: f64,
+    pub longitude_rate_deg_s: f64,
+    pub latitude_rate_deg_s: f64,
+}
+
+/// Cylindrical coordinates of a Cartesian state about the Z axis and their rates.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct CylindricalState {
+    /// Distance to the Z axis
+    pub radius_km: f64,
+    /// Azimuth, bounded between 0.0 and 360.0, measured in the XY plane from the X axis
+    pub azimuth_deg: f64,
+    pub height_km: f64,
+    pub radius_rate_km_s: f64,
+    pub azimuth_rate_deg_s: f64,
+    pub height_rate_km_s: f64,
+}
+
+/// Returns the right ascension and the declination (in degrees) of the provided vector, i.e. its spherical longitude and latitude.
+pub fn ra_dec_deg(vector: &Vector3) -> (f64, f64) {
+    (
+        between_0_360(vector.y.atan2(vector.x).to_degrees()),
+        (vector.z / vector.norm()).asin().to_degrees(),
+    )
+}
+
+/// Returns the azimuth and the elevation (in degrees) of the provided vector expressed in a South East Zenith (SEZ) frame.
+///
+/// The azimuth is measured clockwise from the North and is bounded between 0.0 and 360.0.
+pub fn azimuth_elevation_deg(rho_sez: &Vector3) -> (f64, f64) {
+    (
+        between_0_360(rho_sez.y.atan2(-rho_sez.x).to_degrees()),
+        (rho_sez.z / rho_sez.norm()).asin().to_degrees(),
+    )
+}
+
+/// Converts the provided position and velocity into spherical coordinates and their rates.
+///
+/// The rates of the angles are not defined (NaN) along the Z axis.
+pub fn cartesian_to_spherical(position_km: &Vector3, velocity_km_s: &Vector3) -> SphericalState {
+    let (x, y, z) = (position_km.x, position_km.y, position_km.z);
+    let range_km = position_km.norm();
+    let rho_sq = x.powi(2) + y.powi(2);
+    let (longitude_deg, latitude_deg) = ra_dec_deg(position_km);
+
+    SphericalState {
+        range_km,
+        longitude_deg,
+        latitude_deg,
+        range_rate_km_s: position_km.dot(velocity_km_s) / range_km,
+        longitude_rate_deg_s: ((x * velocity_km_s.y - y * velocity_km_s.x) / rho_sq).to_degrees(),
+        latitude_rate_deg_s: ((velocity_km_s.z * rho_sq
+            - z * (x * velocity_km_s.x + y * velocity_km_s.y))
+            / (range_km.powi(2) * rho_sq.sqrt()))
+        .to_degrees(),
+    }
+}
+
+/// Converts the provided spherical coordinates and their rates into a position (km) and a velocity (km/s).
+pub fn spherical_to_cartesian(spherical: &SphericalState) -> (Vector3, Vector3) {
+    let (sin_lon, cos_lon) = spherical.longitude_deg.to_radians().sin_cos();
+    let (sin_lat, cos_lat) = spherical.latitude_deg.to_radians().sin_cos();
+    let range_km = spherical.range_km;
+    let lon_rate_rad_s = spherical.longitude_rate_deg_s.to_radians();
+    let lat_rate_rad_s = spherical.latitude_rate_deg_s.to_radians();
+
+    let position_km = range_km * Vector3::new(cos_lat * cos_lon, cos_lat * sin_lon, sin_lat);
+    let velocity_km_s = spherical.range_rate_km_s / range_km * position_km
+        + range_km * lat_rate_rad_s * Vector3::new(-sin_lat * cos_lon, -sin_lat * sin_lon, cos_lat)
+        + range_km * cos_lat * lon_rate_rad_s * Vector3::new(-sin_lon, cos_lon, 0.0);
+
+    (position_km, velocity_km_s)
+}
+
+/// Converts the provided position and velocity into cylindrical coordinates about the Z axis and their rates.
+///
+/// The rates are not defined (NaN) along the Z axis.
+pub fn cartesian_to_cylindrical(
+    position_km: &Vector3,
+    velocity_km_s: &Vector3,
+) -> CylindricalState {
+    let (x, y) = (position_km.x, position_km.y);
+    let rho_sq = x.powi(2) + y.powi(2);
+    let radius_km = rho_sq.sqrt();
+
+    CylindricalState {
+        radius_km,
+        azimuth_deg: between_0_360(y.atan2(x).to_degrees()),
+        height_km: position_km.z,
+        radius_rate_km_s: (x * velocity_km_s.x + y * velocity_km_s.y) / radius_km,
+        azimuth_rate_deg_s: ((x * velocity_km_s.y - y * velocity_km_s.x) / rho_sq).to_degrees(),
+        height_rate_km_s: velocity_km_s.z,
+    }
+}
+
+/// Converts the provided cylindrical coordinates and their rates into a position (km) and a velocity (km/s).
+pub fn cylindrical_to_cartesian(cylindrical: &CylindricalState) -> (Vector3, Vector3) {
+    let (sin_az, cos_az) = cylindrical.azimuth_deg.to_radians().sin_cos();
+    let radius_km = cylindrical.radius_km;
+    let az_rate_rad_s = cylindrical.azimuth_rate_deg_s.to_radians();
+
+    let position_km = Vector3::new(
+        radius_km * cos_az,
+        radius_km * sin_az,
+        cylindrical.height_km,
+    );
+    let velocity_km_s = Vector3::new(
+        cylindrical.radius_rate_km_s * cos_az - radius_km * sin_az * az_rate_rad_s,
+        cylindrical.radius_rate_km_s * sin_az + radius_km * cos_az * az_rate_rad_s,
+        cylindrical.height_rate_km_s,
+    );
+
+    (position_km, velocity_km_s)
+}
+
+#[cfg(test)]
+mod ut_angles {
+    use super::*;
+
+    #[test]
+    fn spherical_round_trip() {
+        let position_km = Vector3::new(-2436.45, -2436.45, 6891.037);
+        let velocity_km_s = Vector3::new(5.088611, -5.088611, 0.1);
+
+        let spherical = cartesian_to_spherical(&position_km, &velocity_km_s);
+        assert!((spherical.longitude_deg - 225.0).abs() < 1e-12);
+        assert_eq!(
+            (spherical.longitude_deg, spherical.latitude_deg),
+            ra_dec_deg(&position_km)
+        );
+
+        let (rt_position_km, rt_velocity_km_s) = spherical_to_cartesian(&spherical);
+        assert!((rt_position_km - position_km).norm() < 1e-9);
+        assert!((rt_velocity_km_s - velocity_km_s).norm() < 1e-12);
+
+        // The rates match the central differences of the angles.
+        let dt_s = 1e-2;
+        let before = cartesian_to_spherical(&(position_km - velocity_km_s * dt_s), &velocity_km_s);
+        let after = cartesian_to_spherical(&(position_km + velocity_km_s * dt_s), &velocity_km_s);
+        let lat_rate_deg_s = (after.latitude_deg - before.latitude_deg) / (2.0 * dt_s);
+        assert!((lat_rate_deg_s - spherical.latitude_rate_deg_s).abs() < 1e-9);
+        let lon_rate_deg_s = (after.longitude_deg - before.longitude_deg) / (2.0 * dt_s);
+        assert!((lon_rate_deg_s - spherical.longitude_rate_deg_s).abs() < 1e-9);
+        let range_rate_km_s = (after.range_km - before.range_km) / (2.0 * dt_s);
+        assert!((range_rate_km_s - spherical.range_rate_km_s).abs() < 1e-6);
+    }
+
+    #[test]
+    fn cylindrical_round_trip() {
+        let position_km = Vector3::new(7000.0, -1200.0, 300.0);
+        let velocity_km_s = Vector3::new(0.5, 7.4, -1.1);
+
+        let cylindrical = cartesian_to_cylindrical(&position_km, &velocity_km_s);
+        assert_eq!(cylindrical.height_km, 300.0);
+        assert!(cylindrical.azimuth_deg > 270.0);
+
+        let (rt_position_km, rt_velocity_km_s) = cylindrical_to_cartesian(&cylindrical);
+        assert!((rt_position_km - position_km).norm() < 1e-9);
+        assert!((rt_velocity_km_s - velocity_km_s).norm() < 1e-12);
+    }
+
+    #[test]
+    fn azimuth_elevation() {
+        // North is -X in SEZ, and East is +Y.
+        let (az_deg, el_deg) = azimuth_elevation_deg(&Vector3::new(-1.0, 1.0, 0.0));
+        assert!((az_deg - 45.0).abs() < 1e-12);
+        assert_eq!(el_deg, 0.0);
+        let (_, el_deg) = azimuth_elevation_deg(&Vector3::new(0.0, 0.0, 2.0));
+        assert_eq!(el_deg, 90.0);
+    }
+}
@@ -8,7 +8,8 @@
  * Documentation: https://nyxspace.com/
  */
 
-use super::{perp_vector, root_mean_squared, root_sum_squared, Vector3};
+use super::units::{Kilometers, KilometersPerSecond, Meters, MetersPerSecond};
+use super::{perp_vector, root_mean_squared, root_sum_squared, Matrix6, Vector3};
 use crate::{
     astro::PhysicsResult,
     constants::SPEED_OF_LIGHT_KM_S,
@@ -48,6 +49,9 @@ pub struct CartesianState {
     pub radius_km: Vector3,
     /// Velocity in kilometers per second
     pub velocity_km_s: Vector3,
+    /// Acceleration in kilometers per second squared, only set by the queries which compute it, e.g. `Almanac::translate_with_acceleration`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acceleration_km_s2: Option<Vector3>,
     /// Epoch with time scale at which this is valid.
     pub epoch: Epoch,
     /// Frame in which this Cartesian state lives.
@@ -60,6 +64,7 @@ impl CartesianState {
         Self {
             radius_km: Vector3::zeros(),
             velocity_km_s: Vector3::zeros(),
+            acceleration_km_s2: None,
             epoch: Epoch::from_tdb_seconds(0.0),
             frame,
         }
@@ -70,6 +75,7 @@ impl CartesianState {
         Self {
             radius_km: Vector3::zeros(),
             velocity_km_s: Vector3::zeros(),
+            acceleration_km_s2: None,
             epoch,
             frame,
         }
@@ -92,6 +98,7 @@ impl CartesianState {
         Self {
             radius_km: Vector3::new(x_km, y_km, z_km),
             velocity_km_s: Vector3::new(vx_km_s, vy_km_s, vz_km_s),
+            acceleration_km_s2: None,
             epoch,
             frame,
         }
@@ -189,6 +196,10 @@ impl CartesianState {
         Self {
             radius_km: self.radius_km + other.radius_km,
             velocity_km_s: self.velocity_km_s + other.velocity_km_s,
+            acceleration_km_s2: self
+                .acceleration_km_s2
+                .zip(other.acceleration_km_s2)
+                .map(|(a, b)| a + b),
             epoch: self.epoch,
             frame: self.frame,
         }
@@ -199,6 +210,10 @@ impl CartesianState {
         Self {
             radius_km: self.radius_km - other.radius_km,
             velocity_km_s: self.velocity_km_s - other.velocity_km_s,
+            acceleration_km_s2: self
+                .acceleration_km_s2
+                .zip(other.acceleration_km_s2)
+                .map(|(a, b)| a - b),
             epoch: self.epoch,
             frame: self.frame,
         }
@@ -222,6 +237,27 @@ impl CartesianState {
     pub fn has_velocity_dynamics(&self) -> bool {
         self.velocity_km_s.norm() > 0.0
     }
+
+    /// Returns the magnitude of the radius vector as a typed length, which converts into [Meters].
+    pub fn rmag(&self) -> Kilometers {
+        Kilometers(self.rmag_km())
+    }
+
+    /// Returns the magnitude of the velocity vector as a typed velocity, which converts into [MetersPerSecond].
+    pub fn vmag(&self) -> KilometersPerSecond {
+        KilometersPerSecond(self.vmag_km_s())
+    }
+
+    /// Returns this state in SI units, i.e. meters and meters per second.
+    pub fn to_si(&self) -> CartesianStateSI {
+        CartesianStateSI {
+            radius_m: self.radius_km * 1e3,
+            velocity_m_s: self.velocity_km_s * 1e3,
+            acceleration_m_s2: self.acceleration_km_s2.map(|acc_km_s2| acc_km_s2 * 1e3),
+            epoch: self.epoch,
+            frame: self.frame,
+        }
+    }
 }
 
 // Methods shared with Python
@@ -516,10 +552,103 @@ impl Neg for CartesianState {
         let mut me = self;
         me.radius_km = -me.radius_km;
         me.velocity_km_s = -me.velocity_km_s;
+        me.acceleration_km_s2 = me.acceleration_km_s2.map(|acc| -acc);
         me
     }
 }
 
+/// A Cartesian state with the covariance of its position and velocity, e.g. from an orbit determination filter.
+///
+/// The covariance is stored next to the state rather than in it, such that the [CartesianState] returned by every query
+/// remains small. It is rotated with the 6x6 Jacobian of the rotation when multiplied by a [DCM](crate::math::rotation::DCM),
+/// and carried through the translations by [Almanac::transform_covariant_to](crate::almanac::Almanac::transform_covariant_to).
+///
+/// :type state: Orbit
+/// :type covariance: numpy.array
+/// :rtype: CovariantState
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise.astro"))]
+pub struct CovariantState {
+    pub state: CartesianState,
+    /// Covariance of the position and velocity in km^2, km^2/s, and km^2/s^2
+    pub covariance: Matrix6,
+}
+
+impl CovariantState {
+    /// Creates a new state with the provided covariance of its position and velocity.
+    pub fn new(state: CartesianState, covariance: Matrix6) -> Self {
+        Self { state, covariance }
+    }
+}
+
+/// Defines a Cartesian state in SI units: radius data is expressed in meters and velocity data in meters per second.
+///
+/// This is a distinct type from [CartesianState] (whose data is in kilometers and kilometers per second) such that a state
+/// in meters cannot be passed where a state in kilometers is expected: convert between both with [CartesianState::to_si] and
+/// [CartesianStateSI::to_km], or with their `From` implementations.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CartesianStateSI {
+    /// Position radius in meters
+    pub radius_m: Vector3,
+    /// Velocity in meters per second
+    pub velocity_m_s: Vector3,
+    /// Acceleration in meters per second squared, if set
+    pub acceleration_m_s2: Option<Vector3>,
+    /// Epoch with time scale at which this is valid.
+    pub epoch: Epoch,
+    /// Frame in which this Cartesian state lives.
+    pub frame: Frame,
+}
+
+impl CartesianStateSI {
+    /// Creates a new Cartesian state in SI units in the provided frame at the provided epoch.
+    ///
+    /// **Units:** m, m/s
+    pub fn new(radius_m: Vector3, velocity_m_s: Vector3, epoch: Epoch, frame: Frame) -> Self {
+        Self {
+            radius_m,
+            velocity_m_s,
+            acceleration_m_s2: None,
+            epoch,
+            frame,
+        }
+    }
+
+    /// Returns the magnitude of the radius vector as a typed length, which converts into [Kilometers].
+    pub fn rmag(&self) -> Meters {
+        Meters(self.radius_m.norm())
+    }
+
+    /// Returns the magnitude of the velocity vector as a typed velocity, which converts into [KilometersPerSecond].
+    pub fn vmag(&self) -> MetersPerSecond {
+        MetersPerSecond(self.velocity_m_s.norm())
+    }
+
+    /// Returns this state in kilometers and kilometers per second, i.e. the units of ANISE.
+    pub fn to_km(&self) -> CartesianState {
+        CartesianState {
+            radius_km: self.radius_m / 1e3,
+            velocity_km_s: self.velocity_m_s / 1e3,
+            acceleration_km_s2: self.acceleration_m_s2.map(|acc_m_s2| acc_m_s2 / 1e3),
+            epoch: self.epoch,
+            frame: self.frame,
+        }
+    }
+}
+
+impl From<CartesianState> for CartesianStateSI {
+    fn from(state: CartesianState) -> Self {
+        state.to_si()
+    }
+}
+
+impl From<CartesianStateSI> for CartesianState {
+    fn from(state: CartesianStateSI) -> Self {
+        state.to_km()
+    }
+}
+
 #[allow(clippy::format_in_format_args)]
 impl fmt::Display for CartesianState {
     // Prints as Cartesian in floating point with units
@@ -567,9 +696,54 @@ mod cartesian_state_ut {
 
     use crate::constants::frames::{EARTH_J2000, VENUS_J2000};
     use crate::errors::PhysicsError;
-    use crate::math::Vector6;
+    use crate::math::units::{KilometersPerSecond, Meters};
+    use crate::math::{Vector3, Vector6};
+
+    use super::{CartesianState, CartesianStateSI};
+
+    #[test]
+    fn si_units() {
+        let e = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let state = CartesianState::new(7000.0, 0.0, 0.0, 0.0, 7.5, 0.0, e, EARTH_J2000);
 
-    use super::CartesianState;
+        let si = state.to_si();
+        assert_eq!(si.radius_m, Vector3::new(7e6, 0.0, 0.0));
+        assert_eq!(si.velocity_m_s, Vector3::new(0.0, 7.5e3, 0.0));
+        assert_eq!(si.epoch, e);
+        assert_eq!(si.frame, EARTH_J2000);
+
+        assert_eq!(Meters::from(state.rmag()), si.rmag());
+        assert_eq!(KilometersPerSecond::from(si.vmag()), state.vmag());
+
+        // Round trip
+        assert_eq!(CartesianState::from(si), state);
+        assert_eq!(
+            CartesianStateSI::new(si.radius_m, si.velocity_m_s, e, EARTH_J2000).to_km(),
+            state
+        );
+        // Arbitrary values round trip within one ulp
+        let state = CartesianState::new(
+            848.0262463668842,
+            -63094.78612713469,
+            7000.123,
+            -1.234567,
+            7.004,
+            0.003,
+            e,
+            EARTH_J2000,
+        );
+        let round_trip = state.to_si().to_km();
+        for i in 0..3 {
+            assert!(
+                (round_trip.radius_km[i] - state.radius_km[i]).abs()
+                    <= f64::EPSILON * state.radius_km[i].abs()
+            );
+            assert!(
+                (round_trip.velocity_km_s[i] - state.velocity_km_s[i]).abs()
+                    <= f64::EPSILON * state.velocity_km_s[i].abs()
+            );
+        }
+    }
 
     #[test]
     fn add_wrong_epoch() {
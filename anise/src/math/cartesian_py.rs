@@ -10,19 +10,24 @@
 
 // This file contains Python specific helper functions that don't fit anywhere else.
 
-use super::cartesian::CartesianState;
+use super::cartesian::{CartesianState, CovariantState};
+use super::Matrix6;
+use crate::errors::{FrameMismatchSnafu, PhysicsError};
 use crate::prelude::Frame;
+use crate::python_config::ConfiguredEpoch;
 use hifitime::Epoch;
-use ndarray::Array1;
-use numpy::PyArray1;
+use ndarray::{Array1, Array2};
+use numpy::{PyArray1, PyArray2, PyReadonlyArray2, PyUntypedArrayMethods};
 use pyo3::exceptions::PyTypeError;
 use pyo3::prelude::*;
 use pyo3::pyclass::CompareOp;
 use pyo3::types::PyType;
+use snafu::ensure;
 
 #[pymethods]
 impl CartesianState {
-    /// Creates a new Cartesian state in the provided frame at the provided Epoch.
+    /// Creates a new Cartesian state in the provided frame at the provided Epoch, or epoch string in the time scale of the
+    /// `Config` (UTC by default).
     ///
     /// **Units:** km, km, km, km/s, km/s, km/s
     ///
@@ -45,10 +50,10 @@ impl CartesianState {
         vx_km_s: f64,
         vy_km_s: f64,
         vz_km_s: f64,
-        epoch: Epoch,
+        epoch: ConfiguredEpoch,
         frame: Frame,
     ) -> Self {
-        Self::new(x_km, y_km, z_km, vx_km_s, vy_km_s, vz_km_s, epoch, frame)
+        Self::new(x_km, y_km, z_km, vx_km_s, vy_km_s, vz_km_s, epoch.0, frame)
     }
 
     /// Creates a new Cartesian state in the provided frame at the provided Epoch (calls from_cartesian).
@@ -63,10 +68,10 @@ impl CartesianState {
         vx_km_s: f64,
         vy_km_s: f64,
         vz_km_s: f64,
-        epoch: Epoch,
+        epoch: ConfiguredEpoch,
         frame: Frame,
     ) -> Self {
-        Self::new(x_km, y_km, z_km, vx_km_s, vy_km_s, vz_km_s, epoch, frame)
+        Self::new(x_km, y_km, z_km, vx_km_s, vy_km_s, vz_km_s, epoch.0, frame)
     }
 
     /// :rtype: float
@@ -147,10 +152,12 @@ impl CartesianState {
         Ok(())
     }
 
+    /// Epoch of this state, in the time scale of the `Config` (UTC by default)
+    ///
     /// :rtype: Epoch
     #[getter]
-    fn get_epoch(&self) -> PyResult<Epoch> {
-        Ok(self.epoch)
+    fn get_epoch(&self) -> PyResult<ConfiguredEpoch> {
+        Ok(ConfiguredEpoch(self.epoch))
     }
 
     /// :rtype: Frame
@@ -159,6 +166,19 @@ impl CartesianState {
         Ok(self.frame)
     }
 
+    /// Returns the acceleration in km/s^2, if it was computed, e.g. by `Almanac.translate_with_acceleration`
+    ///
+    /// :rtype: numpy.array, optional
+    #[getter]
+    fn get_acceleration_km_s2<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<Option<Bound<'py, PyArray1<f64>>>> {
+        Ok(self.acceleration_km_s2.map(|acc| {
+            PyArray1::<f64>::from_owned_array(py, Array1::from_iter(acc.iter().copied()))
+        }))
+    }
+
     /// Returns this state as a Cartesian vector of size 6 in [km, km, km, km/s, km/s, km/s]
     ///
     /// Note that the time is **not** returned in the vector.
@@ -188,6 +208,37 @@ impl CartesianState {
         }
     }
 
+    /// Adds the other state to this state, e.g. to offset a relative state by the state of its center.
+    ///
+    /// Raises a ValueError if the states are not at the same epoch or not in the same frame (center and orientation), since
+    /// the result would be meaningless: transform either state first, e.g. with `Almanac.transform_to`.
+    ///
+    /// :type other: Orbit
+    /// :rtype: Orbit
+    fn __add__(&self, other: Self) -> PyResult<Self> {
+        self.ensure_same_orientation(&other, "adding states")?;
+        Ok((*self + other)?)
+    }
+
+    /// Subtracts the other state from this state, e.g. to compute the state of this state relative to the other one.
+    ///
+    /// Raises a ValueError if the states are not at the same epoch or not in the same frame (center and orientation), since
+    /// the result would be meaningless: transform either state first, e.g. with `Almanac.transform_to`.
+    ///
+    /// :type other: Orbit
+    /// :rtype: Orbit
+    fn __sub__(&self, other: Self) -> PyResult<Self> {
+        self.ensure_same_orientation(&other, "subtracting states")?;
+        Ok((*self - other)?)
+    }
+
+    /// Returns this state with its position, velocity, and acceleration negated, at the same epoch and in the same frame.
+    ///
+    /// :rtype: Orbit
+    fn __neg__(&self) -> Self {
+        -*self
+    }
+
     #[allow(clippy::type_complexity)]
     /// :rtype: typing.Tuple
     fn __getnewargs__(&self) -> Result<(f64, f64, f64, f64, f64, f64, Epoch, Frame), PyErr> {
@@ -203,3 +254,74 @@ impl CartesianState {
         ))
     }
 }
+
+impl CartesianState {
+    /// Ensures that both states share the same orientation, which the Rust arithmetic leaves to the caller.
+    fn ensure_same_orientation(
+        &self,
+        other: &Self,
+        action: &'static str,
+    ) -> Result<(), PhysicsError> {
+        ensure!(
+            self.frame.orientation_id == other.frame.orientation_id,
+            FrameMismatchSnafu {
+                action,
+                frame1: self.frame,
+                frame2: other.frame
+            }
+        );
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl CovariantState {
+    /// Creates a new state with the provided 6x6 covariance of its position and velocity.
+    ///
+    /// :type state: Orbit
+    /// :type covariance: numpy.array
+    /// :rtype: CovariantState
+    #[new]
+    fn py_new<'py>(
+        state: CartesianState,
+        covariance: PyReadonlyArray2<'py, f64>,
+    ) -> PyResult<Self> {
+        if covariance.shape() != [6, 6] {
+            return Err(PyErr::new::<PyTypeError, _>("covariance must be 6x6"));
+        }
+
+        Ok(Self::new(
+            state,
+            Matrix6::from_row_iterator(covariance.as_array().iter().copied()),
+        ))
+    }
+
+    /// Returns the state
+    ///
+    /// :rtype: Orbit
+    #[getter]
+    fn get_state(&self) -> CartesianState {
+        self.state
+    }
+
+    /// Returns the 6x6 covariance of the position and velocity in km^2, km^2/s, and km^2/s^2
+    ///
+    /// :rtype: numpy.array
+    #[getter]
+    fn get_covariance<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        // Extract data from SMatrix (column-major order, hence the transpose)
+        let data: Vec<f64> = self.covariance.transpose().iter().copied().collect();
+        Ok(PyArray2::<f64>::from_owned_array(
+            py,
+            Array2::from_shape_vec((6, 6), data).unwrap(),
+        ))
+    }
+
+    fn __str__(&self) -> String {
+        format!("{}\n{}", self.state, self.covariance)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self:?} @ {self:p}")
+    }
+}
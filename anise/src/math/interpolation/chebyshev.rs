@@ -58,6 +58,52 @@ pub fn chebyshev_eval(
     Ok((val, deriv))
 }
 
+/// Attempts to evaluate a Chebyshev polynomial given the coefficients, returning the value, its derivative, and its second derivative
+///
+/// # Notes
+/// 1. At this point, the splines are expected to be in Chebyshev format and no verification is done.
+/// 2. The second derivative is that of the polynomial, so it is only as accurate as the fit of the underlying data.
+pub fn chebyshev_eval_second_deriv(
+    normalized_time: f64,
+    spline_coeffs: &[f64],
+    spline_radius_s: f64,
+    eval_epoch: Epoch,
+    degree: usize,
+) -> Result<(f64, f64, f64), InterpolationError> {
+    let (val, deriv) = chebyshev_eval(
+        normalized_time,
+        spline_coeffs,
+        spline_radius_s,
+        eval_epoch,
+        degree,
+    )?;
+
+    // Workspace arrays of the Clenshaw recurrence and of its first and second derivatives
+    let mut w = [0.0_f64; 3];
+    let mut dw = [0.0_f64; 3];
+    let mut ddw = [0.0_f64; 3];
+
+    for j in (2..=degree + 1).rev() {
+        w[2] = w[1];
+        w[1] = w[0];
+        w[0] = (spline_coeffs
+            .get(j - 1)
+            .ok_or(InterpolationError::MissingInterpolationData { epoch: eval_epoch })?)
+            + (2.0 * normalized_time * w[1] - w[2]);
+
+        dw[2] = dw[1];
+        dw[1] = dw[0];
+        dw[0] = w[1] * 2. + dw[1] * 2.0 * normalized_time - dw[2];
+
+        ddw[2] = ddw[1];
+        ddw[1] = ddw[0];
+        ddw[0] = dw[1] * 4. + ddw[1] * 2.0 * normalized_time - ddw[2];
+    }
+
+    let second_deriv = (2.0 * dw[0] + normalized_time * ddw[0] - ddw[1]) / spline_radius_s.powi(2);
+    Ok((val, deriv, second_deriv))
+}
+
 /// Attempts to evaluate a Chebyshev polynomial given the coefficients, returning only the value
 ///
 /// # Notes
@@ -91,3 +137,66 @@ pub fn chebyshev_eval_poly(
 
     Ok(val)
 }
+
+/// Returns the `num_nodes` Chebyshev nodes (of the first kind) in the normalized interval [-1; 1], in decreasing order.
+pub fn chebyshev_nodes(num_nodes: usize) -> Vec<f64> {
+    (0..num_nodes)
+        .map(|k| (core::f64::consts::PI * (k as f64 + 0.5) / num_nodes as f64).cos())
+        .collect()
+}
+
+/// Computes the coefficients of the Chebyshev polynomial interpolating the values of a function sampled at each of the [chebyshev_nodes].
+///
+/// The degree of the polynomial is one less than the number of samples, and the coefficients can be evaluated with [chebyshev_eval].
+pub fn chebyshev_fit(values_at_nodes: &[f64]) -> Vec<f64> {
+    let num_nodes = values_at_nodes.len() as f64;
+
+    let mut coeffs = (0..values_at_nodes.len())
+        .map(|j| {
+            2.0 / num_nodes
+                * values_at_nodes
+                    .iter()
+                    .enumerate()
+                    .map(|(k, val)| {
+                        val * (core::f64::consts::PI * j as f64 * (k as f64 + 0.5) / num_nodes)
+                            .cos()
+                    })
+                    .sum::<f64>()
+        })
+        .collect::<Vec<f64>>();
+
+    if let Some(c0) = coeffs.first_mut() {
+        *c0 *= 0.5;
+    }
+
+    coeffs
+}
+
+#[cfg(test)]
+mod ut_chebyshev {
+    use super::{chebyshev_eval, chebyshev_eval_second_deriv, chebyshev_fit, chebyshev_nodes};
+    use hifitime::Epoch;
+
+    #[test]
+    fn fit_polynomial() {
+        // A cubic is exactly represented by four coefficients
+        let poly = |x: f64| 2.0 * x.powi(3) - x + 0.5;
+        let nodes = chebyshev_nodes(4);
+        let coeffs = chebyshev_fit(&nodes.iter().map(|x| poly(*x)).collect::<Vec<f64>>());
+
+        for x in [-1.0, -0.3, 0.0, 0.7, 1.0] {
+            let (val, deriv) =
+                chebyshev_eval(x, &coeffs, 1.0, Epoch::from_tdb_seconds(0.0), 3).unwrap();
+            assert!((val - poly(x)).abs() < 1e-12);
+            assert!((deriv - (6.0 * x.powi(2) - 1.0)).abs() < 1e-12);
+
+            // With a radius of two seconds, each derivative is halved.
+            let (val, deriv, second_deriv) =
+                chebyshev_eval_second_deriv(x, &coeffs, 2.0, Epoch::from_tdb_seconds(0.0), 3)
+                    .unwrap();
+            assert!((val - poly(x)).abs() < 1e-12);
+            assert!((deriv - (6.0 * x.powi(2) - 1.0) / 2.0).abs() < 1e-12);
+            assert!((second_deriv - 12.0 * x / 4.0).abs() < 1e-12);
+        }
+    }
+}
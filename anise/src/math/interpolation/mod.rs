@@ -12,13 +12,16 @@ mod chebyshev;
 mod hermite;
 mod lagrange;
 
-pub use chebyshev::{chebyshev_eval, chebyshev_eval_poly};
+pub use chebyshev::{
+    chebyshev_eval, chebyshev_eval_poly, chebyshev_eval_second_deriv, chebyshev_fit,
+    chebyshev_nodes,
+};
 pub use hermite::hermite_eval;
 use hifitime::Epoch;
 pub use lagrange::lagrange_eval;
 use snafu::Snafu;
 
-use crate::errors::{DecodingError, MathError};
+use crate::errors::{DecodingError, ErrorCode, MathError};
 
 /// Defines the maximum degree for an interpolation.
 /// Until https://github.com/rust-lang/rust/issues/60551 , we cannot do operations on const generic, so we need some hack around it.
@@ -57,3 +60,17 @@ pub enum InterpolationError {
     ))]
     UnimplementedType { issue: u32, dataset: &'static str },
 }
+
+impl ErrorCode for InterpolationError {
+    fn code(&self) -> u16 {
+        match self {
+            Self::InterpDecoding { source } => source.code(),
+            Self::InterpMath { source } => source.code(),
+            Self::NoInterpolationData { .. } => 1301,
+            Self::MissingInterpolationData { .. } => 1302,
+            Self::CorruptedData { .. } => 1303,
+            Self::UnsupportedOperation { .. } => 1304,
+            Self::UnimplementedType { .. } => 1305,
+        }
+    }
+}
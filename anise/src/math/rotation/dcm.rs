@@ -9,12 +9,18 @@
  */
 use crate::{
     astro::PhysicsResult,
-    errors::{InvalidRotationSnafu, InvalidStateRotationSnafu, PhysicsError},
-    math::{cartesian::CartesianState, Matrix3, Matrix6, Vector3, Vector6},
+    errors::{
+        InvalidRotationMatrixSnafu, InvalidRotationSnafu, InvalidStateRotationSnafu, PhysicsError,
+    },
+    math::{
+        cartesian::{CartesianState, CovariantState},
+        Matrix3, Matrix6, Vector3, Vector6,
+    },
     prelude::Frame,
     NaifId,
 };
 use nalgebra::Vector4;
+use serde_derive::{Deserialize, Serialize};
 use snafu::ensure;
 
 use super::{r1, r2, r3, Quaternion, Rotation};
@@ -24,6 +30,9 @@ use core::ops::Mul;
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
 
+/// Tolerance on the norm of the columns and on the determinant of a DCM converted to a quaternion, as in SPICE's `m2q`.
+const DCM_CONVERSION_TOL: f64 = 0.1;
+
 /// Defines a direction cosine matrix from one frame ID to another frame ID, optionally with its time derivative.
 /// It provides a number of run-time checks that prevent invalid rotations.
 ///
@@ -32,7 +41,7 @@ use pyo3::prelude::*;
 /// :type to_id: int
 /// :type np_rot_mat_dt: numpy.array, optional
 /// :rtype: DCM
-#[derive(Copy, Clone, Debug, Default)]
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize)]
 #[cfg_attr(feature = "python", pyclass(name = "DCM"))]
 #[cfg_attr(feature = "python", pyo3(module = "anise.rotation"))]
 pub struct DCM {
@@ -128,6 +137,30 @@ impl DCM {
         }
     }
 
+    /// Returns the angular velocity vector (in rad/s) of the `to` frame with respect to the `from` frame, expressed in the `from` frame
+    /// (as SPICE's `xf2rav`), computed from the skew-symmetric matrix `-rot_mat^T * rot_mat_dt`.
+    pub fn angular_velocity_rad_s(&self) -> PhysicsResult<Vector3> {
+        let rot_mat_dt = self.rot_mat_dt.ok_or(PhysicsError::DCMMissingDerivative {
+            action: "computing the angular velocity",
+        })?;
+
+        let omega_skew = -self.rot_mat.transpose() * rot_mat_dt;
+        // Average the symmetric terms to reduce the numerical noise.
+        Ok(Vector3::new(
+            0.5 * (omega_skew[(2, 1)] - omega_skew[(1, 2)]),
+            0.5 * (omega_skew[(0, 2)] - omega_skew[(2, 0)]),
+            0.5 * (omega_skew[(1, 0)] - omega_skew[(0, 1)]),
+        ))
+    }
+
+    /// Returns a copy of this DCM whose time derivative corresponds to the provided angular velocity vector (in rad/s) of the `to` frame
+    /// with respect to the `from` frame, expressed in the `from` frame, i.e. `rot_mat_dt = -rot_mat * [omega x]`.
+    pub fn with_angular_velocity(&self, omega_rad_s: Vector3) -> Self {
+        let mut me = *self;
+        me.rot_mat_dt = Some(-self.rot_mat * omega_rad_s.cross_matrix());
+        me
+    }
+
     /// Multiplies this DCM with another one WITHOUT checking if the frames match.
     pub(crate) fn mul_unchecked(&self, other: Self) -> Self {
         let mut rslt = *self;
@@ -187,18 +220,48 @@ impl DCM {
         }
         (self.rot_mat.determinant() - 1.0).abs() < det_tol
     }
+
+    /// Returns the rotation matrix closest to the `rot_mat` of this DCM (in the Frobenius norm), computed from its singular value decomposition.
+    /// This is useful to correct the numerical drift of a rotation matrix, or nearly orthonormal data. The time derivative is unchanged.
+    ///
+    /// :rtype: DCM
+    pub fn orthonormalize(&self) -> PhysicsResult<Self> {
+        let invalid = InvalidRotationMatrixSnafu {
+            action: "orthonormalizing DCM",
+            from: self.from,
+            to: self.to,
+        };
+
+        ensure!(self.rot_mat.iter().all(|val| val.is_finite()), invalid);
+
+        let svd = self
+            .rot_mat
+            .try_svd(true, true, f64::EPSILON, 0)
+            .ok_or(invalid.build())?;
+        let (mut u, v_t) = (svd.u.unwrap(), svd.v_t.unwrap());
+
+        // Ensure that this is a proper rotation, i.e. not a reflection.
+        if (u * v_t).determinant() < 0.0 {
+            u.column_mut(2).neg_mut();
+        }
+
+        let mut me = *self;
+        me.rot_mat = u * v_t;
+        Ok(me)
+    }
 }
 
 impl Mul for DCM {
     type Output = Result<Self, PhysicsError>;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        if self.is_identity() {
+        // The identity shortcuts are only valid if that identity is constant, otherwise its derivative must be accounted for.
+        if self.is_identity() && self.rot_mat_dt.is_none() {
             let mut rslt = rhs;
             rslt.from = rhs.from;
             rslt.to = self.to;
             Ok(rslt)
-        } else if rhs.is_identity() {
+        } else if rhs.is_identity() && rhs.rot_mat_dt.is_none() {
             let mut rslt = self;
             rslt.from = rhs.from;
             rslt.to = self.to;
@@ -288,12 +351,57 @@ impl Mul<&CartesianState> for DCM {
         let mut rslt = *rhs;
         rslt.radius_km = new_state.fixed_rows::<3>(0).to_owned().into();
         rslt.velocity_km_s = new_state.fixed_rows::<3>(3).to_owned().into();
+        // The acceleration in a rotating frame would require the second derivative of the rotation, which is not available.
+        rslt.acceleration_km_s2 = match self.rot_mat_dt {
+            None => rhs.acceleration_km_s2.map(|acc| self.rot_mat * acc),
+            Some(_) => None,
+        };
         rslt.frame.orientation_id = self.to;
 
         Ok(rslt)
     }
 }
 
+impl Mul<CovariantState> for DCM {
+    type Output = PhysicsResult<CovariantState>;
+
+    fn mul(self, rhs: CovariantState) -> Self::Output {
+        let state = (self * rhs.state)?;
+        // The state DCM is the Jacobian of this transformation.
+        let state_dcm = self.state_dcm();
+        Ok(CovariantState {
+            state,
+            covariance: state_dcm * rhs.covariance * state_dcm.transpose(),
+        })
+    }
+}
+
+impl Quaternion {
+    /// Converts a DCM into its quaternion representation, like `Quaternion::from`, but returns an error if the DCM is not a
+    /// valid rotation matrix (checked with the tolerances of SPICE's `m2q`) or if the resulting quaternion is not finite.
+    ///
+    /// # Warning
+    /// If this DCM has a time derivative, it will be lost in the conversion.
+    pub fn try_from_dcm(dcm: DCM) -> PhysicsResult<Self> {
+        let invalid = InvalidRotationMatrixSnafu {
+            action: "converting DCM to quaternion",
+            from: dcm.from,
+            to: dcm.to,
+        };
+
+        ensure!(
+            dcm.rot_mat.iter().all(|val| val.is_finite())
+                && dcm.is_valid(DCM_CONVERSION_TOL, DCM_CONVERSION_TOL),
+            invalid
+        );
+
+        let q = Self::from(dcm);
+        ensure!(q.as_vector().iter().all(|val| val.is_finite()), invalid);
+
+        Ok(q)
+    }
+}
+
 impl From<DCM> for Quaternion {
     /// Convert from a DCM into its quaternion representation
     ///
@@ -434,7 +542,7 @@ impl fmt::Display for DCM {
 mod ut_dcm {
     use crate::math::Matrix3;
 
-    use super::{Vector3, DCM};
+    use super::{Quaternion, Vector3, DCM};
     use core::f64::consts::FRAC_PI_2;
 
     #[test]
@@ -497,4 +605,130 @@ mod ut_dcm {
                 < f64::EPSILON
         );
     }
+
+    #[test]
+    fn test_serde() {
+        let dcm = DCM::r3(FRAC_PI_2, 0, 1);
+
+        let serialized = serde_yml::to_string(&dcm).unwrap();
+        let rtn: DCM = serde_yml::from_str(&serialized).unwrap();
+
+        assert_eq!(rtn, dcm);
+        assert_eq!(rtn.rot_mat_dt, dcm.rot_mat_dt);
+    }
+
+    #[test]
+    fn test_orthonormalize() {
+        let dcm = DCM::r3(0.7, 0, 1);
+
+        // Perturb the rotation matrix
+        let mut perturbed = dcm;
+        perturbed.rot_mat[(0, 1)] += 1e-4;
+        perturbed.rot_mat[(2, 0)] -= 2e-5;
+        assert!(!perturbed.is_valid(1e-12, 1e-12));
+
+        let fixed = perturbed.orthonormalize().unwrap();
+        assert!(fixed.is_valid(1e-12, 1e-12));
+        assert!((fixed.rot_mat - dcm.rot_mat).norm() < 1e-4);
+        assert_eq!(fixed.from, 0);
+        assert_eq!(fixed.to, 1);
+
+        // A valid rotation matrix is unchanged
+        let same = dcm.orthonormalize().unwrap();
+        assert!((same.rot_mat - dcm.rot_mat).norm() < 1e-14);
+
+        // Corrupted data is an error
+        let mut corrupted = dcm;
+        corrupted.rot_mat[(1, 1)] = f64::NAN;
+        assert!(corrupted.orthonormalize().is_err());
+    }
+
+    #[test]
+    fn test_try_into_quaternion() {
+        let dcm = DCM::r1(0.3, 0, 1);
+        let q = Quaternion::try_from_dcm(dcm).unwrap();
+        assert_eq!(q, Quaternion::from(dcm));
+
+        // Singular matrices are not rotations
+        let mut singular = dcm;
+        singular.rot_mat = Matrix3::zeros();
+        assert!(Quaternion::try_from_dcm(singular).is_err());
+
+        // Nor are reflections
+        let mut reflection = dcm;
+        reflection.rot_mat = -Matrix3::identity();
+        assert!(Quaternion::try_from_dcm(reflection).is_err());
+
+        let mut corrupted = dcm;
+        corrupted.rot_mat[(0, 2)] = f64::INFINITY;
+        assert!(Quaternion::try_from_dcm(corrupted).is_err());
+    }
+
+    #[test]
+    fn test_angular_velocity() {
+        use crate::math::rotation::r3_dot;
+
+        // Frame rotating about Z, like the Earth fixed frame with respect to the inertial frame.
+        let rate_rad_s = 7.292115e-5;
+        let angle_rad = 1.2;
+        let mut dcm = DCM::r3(angle_rad, 0, 1);
+        dcm.rot_mat_dt = Some(rate_rad_s * r3_dot(angle_rad));
+
+        let omega = dcm.angular_velocity_rad_s().unwrap();
+        assert!((omega - Vector3::new(0.0, 0.0, rate_rad_s)).norm() < 1e-15);
+
+        // Round trip with an arbitrary angular velocity
+        let omega = Vector3::new(1e-3, -2e-4, 5e-5);
+        let rotated = DCM::r1(0.4, 0, 1).with_angular_velocity(omega);
+        assert!((rotated.angular_velocity_rad_s().unwrap() - omega).norm() < 1e-15);
+
+        // The synthesized derivative matches the one of the single axis rotation
+        let synthesized =
+            DCM::r3(angle_rad, 0, 1).with_angular_velocity(Vector3::new(0.0, 0.0, rate_rad_s));
+        assert!((synthesized.rot_mat_dt.unwrap() - dcm.rot_mat_dt.unwrap()).norm() < 1e-15);
+
+        // The derivative is required
+        assert!(DCM::r1(0.4, 0, 1).angular_velocity_rad_s().is_err());
+    }
+
+    #[test]
+    fn test_covariance_rotation() {
+        use crate::constants::frames::EARTH_J2000;
+        use crate::math::cartesian::{CartesianState, CovariantState};
+        use crate::math::rotation::r3_dot;
+        use crate::math::{Matrix6, Vector6};
+        use hifitime::Epoch;
+
+        let rate_rad_s = 7.292115e-5;
+        let mut dcm = DCM::r3(FRAC_PI_2, EARTH_J2000.orientation_id, 3000);
+        dcm.rot_mat_dt = Some(rate_rad_s * r3_dot(FRAC_PI_2));
+
+        // One kilometer along X and one meter per second along each axis
+        let cov = Matrix6::from_diagonal(&Vector6::new(1.0, 1e-2, 1e-2, 1e-6, 1e-6, 1e-6));
+        let state = CovariantState::new(
+            CartesianState::new(
+                7000.0,
+                0.0,
+                0.0,
+                0.0,
+                7.5,
+                0.0,
+                Epoch::from_tdb_seconds(0.0),
+                EARTH_J2000,
+            ),
+            cov,
+        );
+
+        let rotated = (dcm * state).unwrap();
+        assert_eq!(rotated.state, (dcm * state.state).unwrap());
+        let rotated_cov = rotated.covariance;
+        // A quarter turn about Z moves the X uncertainty onto the -Y axis
+        assert!((rotated_cov[(0, 0)] - 1e-2).abs() < 1e-15);
+        assert!((rotated_cov[(1, 1)] - 1.0).abs() < 1e-15);
+        assert!((rotated_cov[(2, 2)] - 1e-2).abs() < 1e-15);
+        // The rotation rate couples the position uncertainty into the velocity
+        assert!((rotated_cov[(3, 3)] - (1e-6 + rate_rad_s.powi(2))).abs() < 1e-15);
+        assert!(rotated_cov[(3, 1)].abs() > 0.0);
+        assert!((rotated_cov - rotated_cov.transpose()).norm() < 1e-15);
+    }
 }
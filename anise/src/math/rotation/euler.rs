@@ -0,0 +1,272 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use super::{r1, r1_dot, r2, r2_dot, r3, r3_dot, DCM};
+use crate::math::{Matrix3, Vector3};
+
+/// Below this value of the sine (or cosine) of the middle angle, the sequence is considered to be in gimbal lock.
+const GIMBAL_LOCK_TOL: f64 = 1e-12;
+
+/// Sequence of the three successive rotations of a set of Euler angles.
+///
+/// The name of the sequence lists the axes in the order of the rotations, e.g. `ZYX` (also known as 3-2-1) first
+/// rotates about the Z axis by the first angle, then about the new Y axis by the second angle, and finally about the
+/// new X axis by the third angle. The DCM is therefore `R_X(θ3) R_Y(θ2) R_Z(θ1)`, with the (passive) rotations `r1`,
+/// `r2`, and `r3`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum EulerSequence {
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    #[default]
+    ZYX,
+    XYX,
+    XZX,
+    YXY,
+    YZY,
+    ZXZ,
+    ZYZ,
+}
+
+impl EulerSequence {
+    /// Returns the axes of this sequence, in the order of the rotations (0 for X, 1 for Y, and 2 for Z).
+    pub const fn axes(&self) -> [usize; 3] {
+        match self {
+            Self::XYZ => [0, 1, 2],
+            Self::XZY => [0, 2, 1],
+            Self::YXZ => [1, 0, 2],
+            Self::YZX => [1, 2, 0],
+            Self::ZXY => [2, 0, 1],
+            Self::ZYX => [2, 1, 0],
+            Self::XYX => [0, 1, 0],
+            Self::XZX => [0, 2, 0],
+            Self::YXY => [1, 0, 1],
+            Self::YZY => [1, 2, 1],
+            Self::ZXZ => [2, 0, 2],
+            Self::ZYZ => [2, 1, 2],
+        }
+    }
+
+    /// Returns true if the first and last axes are identical (e.g. ZXZ), false for Tait-Bryan sequences (e.g. ZYX).
+    pub const fn is_symmetric(&self) -> bool {
+        let axes = self.axes();
+        axes[0] == axes[2]
+    }
+
+    /// Returns the rotation matrix of the provided angles (in radians) in this sequence.
+    pub fn rotation_matrix(&self, angles_rad: Vector3) -> Matrix3 {
+        let [i, j, k] = self.axes();
+        rotation(k, angles_rad[2]) * rotation(j, angles_rad[1]) * rotation(i, angles_rad[0])
+    }
+
+    /// Returns the angles (in radians) of the provided rotation matrix in this sequence.
+    ///
+    /// The middle angle is within [-π/2, π/2] for Tait-Bryan sequences and within [0, π] for symmetric sequences, and
+    /// the other angles are within (-π, π]. In gimbal lock, only the sum (or difference) of the first and last angles
+    /// is defined, so the last angle is set to zero.
+    pub fn angles_rad(&self, rot_mat: &Matrix3) -> Vector3 {
+        let [i, j, k] = self.axes();
+        // The transpose is the product of the active rotations in the order of the sequence.
+        let m = rot_mat.transpose();
+
+        let (first, second, third, locked) = if self.is_symmetric() {
+            let l = 3 - i - j;
+            let eps = parity(i, j, l);
+            let second = m[(i, i)].clamp(-1.0, 1.0).acos();
+            (
+                (m[(j, i)]).atan2(-eps * m[(l, i)]),
+                second,
+                (m[(i, j)]).atan2(eps * m[(i, l)]),
+                second.sin().abs() < GIMBAL_LOCK_TOL,
+            )
+        } else {
+            let eps = parity(i, j, k);
+            let second = (eps * m[(i, k)]).clamp(-1.0, 1.0).asin();
+            (
+                (-eps * m[(j, k)]).atan2(m[(k, k)]),
+                second,
+                (-eps * m[(i, j)]).atan2(m[(i, i)]),
+                second.cos().abs() < GIMBAL_LOCK_TOL,
+            )
+        };
+
+        if locked {
+            // Set the last angle to zero, so the first rotation is the product of the matrix with the inverse of the second.
+            let first_only = m * rotation(j, second);
+            let (p, q) = ((i + 1) % 3, (i + 2) % 3);
+            Vector3::new(first_only[(q, p)].atan2(first_only[(p, p)]), second, 0.0)
+        } else {
+            Vector3::new(first, second, third)
+        }
+    }
+
+    /// Returns the rates (in radians per second) of the provided angles given the time derivative of their rotation
+    /// matrix, or None in gimbal lock where the rates are not defined.
+    ///
+    /// The rates are the least squares solution of `dC/dt = Σ ∂C/∂θ_i dθ_i/dt`, which is exact for a consistent derivative.
+    pub fn rates_rad_s(&self, angles_rad: Vector3, rot_mat_dt: &Matrix3) -> Option<Vector3> {
+        let [i, j, k] = self.axes();
+        let (r_i, r_j, r_k) = (
+            rotation(i, angles_rad[0]),
+            rotation(j, angles_rad[1]),
+            rotation(k, angles_rad[2]),
+        );
+
+        let partials = [
+            r_k * r_j * rotation_dot(i, angles_rad[0]),
+            r_k * rotation_dot(j, angles_rad[1]) * r_i,
+            rotation_dot(k, angles_rad[2]) * r_j * r_i,
+        ];
+
+        // Normal equations of the 9x3 least squares problem
+        let mut normal = Matrix3::zeros();
+        let mut rhs = Vector3::zeros();
+        for (row, partial_row) in partials.iter().enumerate() {
+            for (col, partial_col) in partials.iter().enumerate() {
+                normal[(row, col)] = partial_row.dot(partial_col);
+            }
+            rhs[row] = partial_row.dot(rot_mat_dt);
+        }
+
+        let conditioning = if self.is_symmetric() {
+            angles_rad[1].sin()
+        } else {
+            angles_rad[1].cos()
+        };
+        if conditioning.abs() < GIMBAL_LOCK_TOL {
+            return None;
+        }
+
+        normal.try_inverse().map(|inv| inv * rhs)
+    }
+}
+
+impl fmt::Display for EulerSequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl DCM {
+    /// Returns the Euler angles (in radians) of this rotation in the provided sequence, cf. [EulerSequence::angles_rad].
+    pub fn euler_angles_rad(&self, sequence: EulerSequence) -> Vector3 {
+        sequence.angles_rad(&self.rot_mat)
+    }
+}
+
+/// Returns the passive rotation matrix about the provided axis.
+fn rotation(axis: usize, angle_rad: f64) -> Matrix3 {
+    match axis {
+        0 => r1(angle_rad),
+        1 => r2(angle_rad),
+        _ => r3(angle_rad),
+    }
+}
+
+/// Returns the derivative of the passive rotation matrix about the provided axis with respect to its angle.
+fn rotation_dot(axis: usize, angle_rad: f64) -> Matrix3 {
+    match axis {
+        0 => r1_dot(angle_rad),
+        1 => r2_dot(angle_rad),
+        _ => r3_dot(angle_rad),
+    }
+}
+
+/// Returns 1 for even permutations of the axes, and -1 for odd ones.
+fn parity(i: usize, j: usize, k: usize) -> f64 {
+    if (i, j, k) == (0, 1, 2) || (i, j, k) == (1, 2, 0) || (i, j, k) == (2, 0, 1) {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+#[cfg(test)]
+mod ut_euler {
+    use super::EulerSequence;
+    use crate::math::Vector3;
+
+    const SEQUENCES: [EulerSequence; 12] = [
+        EulerSequence::XYZ,
+        EulerSequence::XZY,
+        EulerSequence::YXZ,
+        EulerSequence::YZX,
+        EulerSequence::ZXY,
+        EulerSequence::ZYX,
+        EulerSequence::XYX,
+        EulerSequence::XZX,
+        EulerSequence::YXY,
+        EulerSequence::YZY,
+        EulerSequence::ZXZ,
+        EulerSequence::ZYZ,
+    ];
+
+    #[test]
+    fn euler_angles_roundtrip() {
+        for sequence in SEQUENCES {
+            for angles_rad in [
+                Vector3::new(0.3, -0.7, 2.9),
+                Vector3::new(-2.5, 1.2, -0.1),
+                Vector3::new(1.0, 0.4, 0.0),
+            ] {
+                // Symmetric sequences have a positive middle angle
+                let mut angles_rad = angles_rad;
+                if sequence.is_symmetric() {
+                    angles_rad[1] = angles_rad[1].abs();
+                }
+
+                let rot_mat = sequence.rotation_matrix(angles_rad);
+                let computed = sequence.angles_rad(&rot_mat);
+                assert!(
+                    (computed - angles_rad).norm() < 1e-12,
+                    "{sequence}: {computed} != {angles_rad}"
+                );
+
+                // Rates from a finite difference of the rotation matrix
+                let rates_rad_s = Vector3::new(0.01, -0.02, 0.03);
+                let h = 1e-6;
+                let rot_mat_dt = (sequence.rotation_matrix(angles_rad + rates_rad_s * h)
+                    - sequence.rotation_matrix(angles_rad - rates_rad_s * h))
+                    / (2.0 * h);
+                let computed = sequence.rates_rad_s(angles_rad, &rot_mat_dt).unwrap();
+                assert!(
+                    (computed - rates_rad_s).norm() < 1e-8,
+                    "{sequence}: {computed} != {rates_rad_s}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn euler_angles_gimbal_lock() {
+        for sequence in SEQUENCES {
+            let middle = if sequence.is_symmetric() {
+                0.0
+            } else {
+                core::f64::consts::FRAC_PI_2
+            };
+            let angles_rad = Vector3::new(0.5, middle, 0.25);
+            let rot_mat = sequence.rotation_matrix(angles_rad);
+
+            let computed = sequence.angles_rad(&rot_mat);
+            assert_eq!(computed[2], 0.0);
+            // The angles are not unique, but they represent the same rotation.
+            assert!(
+                (sequence.rotation_matrix(computed) - rot_mat).norm() < 1e-12,
+                "{sequence}: {computed}"
+            );
+            assert!(sequence.rates_rad_s(computed, &rot_mat).is_none());
+        }
+    }
+}
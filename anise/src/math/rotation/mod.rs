@@ -14,11 +14,15 @@ pub(crate) const EPSILON_RAD: f64 = 4.8e-6;
 pub(crate) const EPSILON: f64 = 1e-12;
 
 mod dcm;
+mod euler;
 mod mrp;
 mod quaternion;
+mod squad;
 pub use dcm::DCM;
+pub use euler::EulerSequence;
 pub use mrp::MRP;
 pub use quaternion::Quaternion;
+pub use squad::squad_eval;
 
 #[cfg(feature = "python")]
 mod dcm_py;
@@ -11,6 +11,7 @@
 use crate::errors::{InvalidRotationSnafu, PhysicsError};
 use crate::math::rotation::EPSILON;
 use crate::structure::dataset::DataSetT;
+use crate::structure::semver::Semver;
 use crate::{math::Vector3, math::Vector4, NaifId};
 use core::fmt;
 use core::ops::Mul;
@@ -369,6 +370,11 @@ impl Encode for EulerParameter {
 
 impl DataSetT for EulerParameter {
     const NAME: &'static str = "euler parameter";
+    const LAYOUT_VERSION: Semver = Semver {
+        major: 0,
+        minor: 4,
+        patch: 0,
+    };
 }
 
 #[cfg(test)]
@@ -0,0 +1,291 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::Epoch;
+use snafu::ensure;
+
+use super::{Quaternion, EPSILON};
+use crate::errors::{InvalidRotationSnafu, PhysicsError};
+use crate::math::interpolation::InterpolationError;
+use crate::math::{Vector3, Vector4};
+
+impl Quaternion {
+    /// Spherical linear interpolation (SLERP) from this quaternion (at `t = 0`) to `other` (at `t = 1`) along the shortest arc,
+    /// i.e. a rotation at constant angular velocity.
+    ///
+    /// Both quaternions must rotate between the same frames.
+    pub fn slerp(&self, other: &Self, t: f64) -> Result<Self, PhysicsError> {
+        self.ensure_same_frames(other, "interpolate quaternions")?;
+        Ok(self.with_vector(slerp_vec(self.as_vector(), other.as_vector(), t)))
+    }
+
+    /// Spherical quadrangle interpolation (SQUAD) from this quaternion (at `t = 0`) to `other` (at `t = 1`), given the
+    /// control quaternions `ctrl` and `other_ctrl` of each, as computed by [squad_eval].
+    pub fn squad(
+        &self,
+        ctrl: &Self,
+        other_ctrl: &Self,
+        other: &Self,
+        t: f64,
+    ) -> Result<Self, PhysicsError> {
+        self.ensure_same_frames(ctrl, "interpolate quaternions")?;
+        self.ensure_same_frames(other_ctrl, "interpolate quaternions")?;
+        self.ensure_same_frames(other, "interpolate quaternions")?;
+
+        Ok(self.with_vector(squad_vec(
+            self.as_vector(),
+            ctrl.as_vector(),
+            other_ctrl.as_vector(),
+            other.as_vector(),
+            t,
+        )))
+    }
+
+    fn ensure_same_frames(&self, other: &Self, action: &'static str) -> Result<(), PhysicsError> {
+        ensure!(
+            self.from == other.from && self.to == other.to,
+            InvalidRotationSnafu {
+                action,
+                from1: self.from,
+                to1: self.to,
+                from2: other.from,
+                to2: other.to
+            }
+        );
+        Ok(())
+    }
+
+    fn with_vector(&self, q: Vector4) -> Self {
+        Self::new(q[0], q[1], q[2], q[3], self.from, self.to)
+    }
+}
+
+/// Interpolates the attitude at the requested epoch from a time series of quaternions using SQUAD, which (unlike SLERP)
+/// ensures the continuity of the angular velocity at the provided quaternions when they are evenly spaced in time.
+///
+/// The quaternions must all rotate between the same frames, and the epochs must be strictly increasing.
+/// If only two quaternions are provided, this is a SLERP.
+pub fn squad_eval(
+    epochs: &[Epoch],
+    quaternions: &[Quaternion],
+    epoch: Epoch,
+) -> Result<Quaternion, InterpolationError> {
+    if epochs.len() != quaternions.len() {
+        return Err(InterpolationError::CorruptedData {
+            what: "lengths of epochs and quaternions differ",
+        });
+    } else if epochs.len() < 2 {
+        return Err(InterpolationError::CorruptedData {
+            what: "at least two quaternions are needed",
+        });
+    } else if epochs.windows(2).any(|pair| pair[1] <= pair[0]) {
+        return Err(InterpolationError::CorruptedData {
+            what: "epochs are not strictly increasing",
+        });
+    } else if quaternions
+        .iter()
+        .any(|q| q.from != quaternions[0].from || q.to != quaternions[0].to)
+    {
+        return Err(InterpolationError::CorruptedData {
+            what: "quaternions do not all rotate between the same frames",
+        });
+    }
+
+    let start = epochs[0];
+    let end = epochs[epochs.len() - 1];
+    if epoch < start || epoch > end {
+        return Err(InterpolationError::NoInterpolationData {
+            req: epoch,
+            start,
+            end,
+        });
+    }
+
+    // Index of the interval which contains the requested epoch
+    let idx = (epochs.partition_point(|e| *e <= epoch) - 1).min(epochs.len() - 2);
+    let t = (epoch - epochs[idx]).to_seconds() / (epochs[idx + 1] - epochs[idx]).to_seconds();
+
+    // Work on a sign consistent sequence around the interval, such that consecutive quaternions are on the same hemisphere.
+    let first = idx.saturating_sub(1);
+    let last = (idx + 2).min(epochs.len() - 1);
+    let mut qs = Vec::with_capacity(last - first + 1);
+    for q in &quaternions[first..=last] {
+        let q = q.as_vector();
+        match qs.last() {
+            Some(prev) if q.dot(prev) < 0.0 => qs.push(-q),
+            _ => qs.push(q),
+        }
+    }
+
+    let q0 = qs[idx - first];
+    let q1 = qs[idx + 1 - first];
+
+    let ctrl0 = if idx > 0 {
+        squad_ctrl(qs[idx - 1 - first], q0, q1)
+    } else {
+        q0
+    };
+    let ctrl1 = if idx + 2 <= last {
+        squad_ctrl(q0, q1, qs[idx + 2 - first])
+    } else {
+        q1
+    };
+
+    Ok(quaternions[idx].with_vector(squad_vec(q0, ctrl0, ctrl1, q1, t)))
+}
+
+/// SLERP between two unit quaternions as vectors (w, x, y, z), along the shortest arc.
+fn slerp_vec(q0: Vector4, q1: Vector4, t: f64) -> Vector4 {
+    let mut cos_omega = q0.dot(&q1);
+    let q1 = if cos_omega < 0.0 {
+        cos_omega = -cos_omega;
+        -q1
+    } else {
+        q1
+    };
+
+    if 1.0 - cos_omega < EPSILON {
+        // The quaternions are nearly identical, so the linear interpolation is accurate and avoids dividing by zero.
+        return (q0 + t * (q1 - q0)).normalize();
+    }
+
+    let omega = cos_omega.min(1.0).acos();
+    let sin_omega = omega.sin();
+    ((((1.0 - t) * omega).sin() / sin_omega) * q0 + ((t * omega).sin() / sin_omega) * q1)
+        .normalize()
+}
+
+/// SQUAD between q0 and q1 given their control quaternions.
+fn squad_vec(q0: Vector4, ctrl0: Vector4, ctrl1: Vector4, q1: Vector4, t: f64) -> Vector4 {
+    slerp_vec(
+        slerp_vec(q0, q1, t),
+        slerp_vec(ctrl0, ctrl1, t),
+        2.0 * t * (1.0 - t),
+    )
+}
+
+/// Control quaternion of `q` given its previous and next quaternions: `q exp(-(log(q* next) + log(q* prev)) / 4)`
+fn squad_ctrl(prev: Vector4, q: Vector4, next: Vector4) -> Vector4 {
+    let q_conj = Vector4::new(q[0], -q[1], -q[2], -q[3]);
+    let log_sum = quat_log(hamilton(q_conj, next)) + quat_log(hamilton(q_conj, prev));
+    hamilton(q, quat_exp(-0.25 * log_sum))
+}
+
+/// Hamilton product of two quaternions as vectors (w, x, y, z).
+fn hamilton(a: Vector4, b: Vector4) -> Vector4 {
+    Vector4::new(
+        a[0] * b[0] - a[1] * b[1] - a[2] * b[2] - a[3] * b[3],
+        a[0] * b[1] + a[1] * b[0] + a[2] * b[3] - a[3] * b[2],
+        a[0] * b[2] - a[1] * b[3] + a[2] * b[0] + a[3] * b[1],
+        a[0] * b[3] + a[1] * b[2] - a[2] * b[1] + a[3] * b[0],
+    )
+}
+
+/// Logarithm of a unit quaternion, i.e. the half angle times the axis of rotation.
+fn quat_log(q: Vector4) -> Vector3 {
+    let v = Vector3::new(q[1], q[2], q[3]);
+    let sin_half = v.norm();
+    if sin_half < EPSILON {
+        v
+    } else {
+        v * (sin_half.atan2(q[0]) / sin_half)
+    }
+}
+
+/// Exponential of a pure quaternion, i.e. the inverse of [quat_log].
+fn quat_exp(v: Vector3) -> Vector4 {
+    let half_angle = v.norm();
+    if half_angle < EPSILON {
+        Vector4::new(1.0, v[0], v[1], v[2]).normalize()
+    } else {
+        let axis = v / half_angle;
+        let (s, c) = half_angle.sin_cos();
+        Vector4::new(c, s * axis[0], s * axis[1], s * axis[2])
+    }
+}
+
+#[cfg(test)]
+mod ut_squad {
+    use super::{squad_eval, Quaternion};
+    use hifitime::{Epoch, TimeUnits};
+
+    #[test]
+    fn slerp() {
+        let q0 = Quaternion::about_z(0.0, 0, 1);
+        let q1 = Quaternion::about_z(1.0, 0, 1);
+
+        assert_eq!(q0.slerp(&q1, 0.0).unwrap(), q0);
+        assert_eq!(q0.slerp(&q1, 1.0).unwrap(), q1);
+        assert_eq!(
+            q0.slerp(&q1, 0.25).unwrap(),
+            Quaternion::about_z(0.25, 0, 1)
+        );
+
+        // Shortest arc, even if the other quaternion is on the other hemisphere
+        let q1_neg = Quaternion::new(-q1.w, -q1.x, -q1.y, -q1.z, 0, 1);
+        let mid = q0.slerp(&q1_neg, 0.5).unwrap();
+        assert!((mid.prv().z.abs() - 0.5).abs() < 1e-12);
+
+        // Frames must match
+        assert!(q0.slerp(&Quaternion::about_z(1.0, 0, 2), 0.5).is_err());
+    }
+
+    #[test]
+    fn squad_constant_rate() {
+        // Rotation about a fixed axis at a constant rate, which SQUAD reproduces exactly.
+        let start = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+        let rate_rad_s = 1e-3;
+        let epochs = (0..10)
+            .map(|i| start + (60.0 * i as f64).seconds())
+            .collect::<Vec<Epoch>>();
+        let quats = epochs
+            .iter()
+            .map(|e| Quaternion::about_y(rate_rad_s * (*e - start).to_seconds(), 0, 1))
+            .collect::<Vec<Quaternion>>();
+
+        for offset_s in [0.0, 17.0, 95.5, 301.0, 540.0] {
+            let q = squad_eval(&epochs, &quats, start + offset_s.seconds()).unwrap();
+            let expected = Quaternion::about_y(rate_rad_s * offset_s, 0, 1);
+            assert!(
+                (q.as_vector() - expected.as_vector()).norm() < 1e-12,
+                "{offset_s} s: {q} != {expected}"
+            );
+        }
+
+        assert!(squad_eval(&epochs, &quats, start - 1.seconds()).is_err());
+        assert!(squad_eval(&epochs[..1], &quats[..1], start).is_err());
+    }
+
+    #[test]
+    fn squad_smooth_tumble() {
+        // Rotation whose axis changes over time: SQUAD remains close to the truth and continuous at the samples.
+        let start = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+        let truth = |t_s: f64| {
+            (Quaternion::about_z(1e-3 * t_s, 0, 1) * Quaternion::about_x(5e-4 * t_s, 1, 1)).unwrap()
+        };
+        let epochs = (0..20)
+            .map(|i| start + (30.0 * i as f64).seconds())
+            .collect::<Vec<Epoch>>();
+        let quats = epochs
+            .iter()
+            .map(|e| truth((*e - start).to_seconds()))
+            .collect::<Vec<Quaternion>>();
+
+        for offset_s in [45.0, 200.0, 431.0] {
+            let q = squad_eval(&epochs, &quats, start + offset_s.seconds()).unwrap();
+            let expected = truth(offset_s);
+            assert!((q.as_vector() - expected.as_vector()).norm() < 1e-6);
+        }
+
+        // Matches the samples exactly
+        let q = squad_eval(&epochs, &quats, epochs[7]).unwrap();
+        assert!((q.as_vector() - quats[7].as_vector()).norm() < 1e-12);
+    }
+}
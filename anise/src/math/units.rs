@@ -8,6 +8,7 @@
  * Documentation: https://nyxspace.com/
  */
 use core::fmt::Display;
+use core::ops::{Add, Div, Mul, Neg, Sub};
 
 /// Re-export hifitime's units as DurationUnit.
 pub use hifitime::Unit as TimeUnit;
@@ -67,3 +68,142 @@ impl Default for LengthUnit {
         Self::Kilometer
     }
 }
+
+/// Defines a newtype of a scalar in the provided unit, such that mixing units is a compilation error instead of a silent
+/// factor of a thousand.
+macro_rules! scalar_unit {
+    ($(#[$doc:meta])* $name:ident, $symbol:literal) => {
+        $(#[$doc])*
+        #[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+        pub struct $name(pub f64);
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                Display::fmt(&self.0, f)?;
+                write!(f, " {}", $symbol)
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<f64> for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: f64) -> Self {
+                Self(self.0 * rhs)
+            }
+        }
+
+        impl Div<f64> for $name {
+            type Output = Self;
+
+            fn div(self, rhs: f64) -> Self {
+                Self(self.0 / rhs)
+            }
+        }
+    };
+}
+
+scalar_unit!(
+    /// A length in kilometers, the unit of all of the distances of ANISE.
+    Kilometers,
+    "km"
+);
+scalar_unit!(
+    /// A length in meters, the SI unit of length.
+    Meters,
+    "m"
+);
+scalar_unit!(
+    /// A velocity in kilometers per second, the unit of all of the velocities of ANISE.
+    KilometersPerSecond,
+    "km/s"
+);
+scalar_unit!(
+    /// A velocity in meters per second, the SI unit of velocity.
+    MetersPerSecond,
+    "m/s"
+);
+
+impl From<Kilometers> for Meters {
+    fn from(length: Kilometers) -> Self {
+        Self(length.0 * 1e3)
+    }
+}
+
+impl From<Meters> for Kilometers {
+    fn from(length: Meters) -> Self {
+        Self(length.0 / 1e3)
+    }
+}
+
+impl From<KilometersPerSecond> for MetersPerSecond {
+    fn from(velocity: KilometersPerSecond) -> Self {
+        Self(velocity.0 * 1e3)
+    }
+}
+
+impl From<MetersPerSecond> for KilometersPerSecond {
+    fn from(velocity: MetersPerSecond) -> Self {
+        Self(velocity.0 / 1e3)
+    }
+}
+
+#[cfg(test)]
+mod ut_units {
+    use super::*;
+
+    #[test]
+    fn length_velocity_newtypes() {
+        let length = Kilometers(1.5);
+        assert_eq!(Meters::from(length), Meters(1_500.0));
+        assert_eq!(Kilometers::from(Meters(250.0)), Kilometers(0.25));
+        assert_eq!(length + Kilometers(0.5) - Kilometers(1.0), Kilometers(1.0));
+        assert_eq!(-length * 2.0 / 3.0, Kilometers(-1.0));
+        assert_eq!(format!("{length}"), "1.5 km");
+
+        let velocity = MetersPerSecond(7_500.0);
+        assert_eq!(
+            KilometersPerSecond::from(velocity),
+            KilometersPerSecond(7.5)
+        );
+        assert_eq!(
+            MetersPerSecond::from(KilometersPerSecond::from(velocity)),
+            velocity
+        );
+        assert_eq!(format!("{velocity:.1}"), "7500.0 m/s");
+
+        // Conversions round trip within one ulp: the product by 1e3 and the division by 1e3 are each correctly rounded,
+        // but their combination is not exact for about 2% of the values.
+        for i in 1..10_000 {
+            let km = (i as f64 * 0.618_033_988_749_895).fract() * 10.0_f64.powi(i % 12 - 3);
+            let round_trip = Kilometers::from(Meters::from(Kilometers(km))).0;
+            assert!((round_trip - km).abs() <= f64::EPSILON * km, "{km} km");
+            let round_trip =
+                KilometersPerSecond::from(MetersPerSecond::from(KilometersPerSecond(km))).0;
+            assert!((round_trip - km).abs() <= f64::EPSILON * km, "{km} km/s");
+        }
+    }
+}
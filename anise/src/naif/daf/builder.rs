@@ -0,0 +1,298 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use zerocopy::IntoBytes;
+
+use super::{
+    DAFError, FileRecord, NAIFRecord, NAIFSummaryRecord, NameRecord, SummaryRecord, DAF, RCRD_LEN,
+};
+use crate::naif::Endian;
+use crate::DBL_SIZE;
+
+/// FTP validation string of DAF files, used to detect corruption by ASCII mode transfers.
+const FTP_STR: &[u8; 28] = b"FTPSTR:\r:\n:\r\n:\r\x00:\x81:\x10\xce:ENDFTP";
+
+/// A segment to be written in a new DAF file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DAFSegment<R: NAIFSummaryRecord> {
+    /// Name of this segment, truncated to 40 characters.
+    pub name: String,
+    /// Summary of this segment: its start and end indexes are set when building the DAF.
+    pub summary: R,
+    /// DAF array of this segment, e.g. built with `to_f64_daf_vec` of its data set.
+    pub data: Vec<f64>,
+}
+
+impl<R: NAIFSummaryRecord> DAF<R> {
+    /// Builds a new DAF in memory from the provided segments.
+    ///
+    /// Each summary record holds up to 25 segments (for SPK and BPC files), each followed by its name record, and
+    /// additional summary records are linked as needed.
+    pub fn from_segments(
+        internal_filename: &str,
+        segments: &[DAFSegment<R>],
+    ) -> Result<Self, DAFError> {
+        let nd: usize = 2;
+        let ni = R::NUM_INTEGERS;
+        let summary_size = nd + ni.div_ceil(2);
+        let max_segments = (RCRD_LEN - SummaryRecord::SIZE) / (summary_size * DBL_SIZE);
+
+        if segments.is_empty() || segments.iter().any(|segment| segment.data.is_empty()) {
+            return Err(DAFError::DataBuildError { kind: R::NAME });
+        }
+
+        // The file record, then each summary record followed by its name record, and finally the data.
+        let num_summary_rcrds = segments.len().div_ceil(max_segments);
+        let fwrd_idx = 2;
+        let bwrd_idx = fwrd_idx + 2 * (num_summary_rcrds - 1);
+        let first_data_addr = (1 + 2 * num_summary_rcrds) * RCRD_LEN / DBL_SIZE + 1;
+
+        let mut bytes = Vec::new();
+        let mut data = Vec::new();
+        let mut next_addr = first_data_addr;
+
+        for (rno, chunk) in segments.chunks(max_segments).enumerate() {
+            let rcrd_no = fwrd_idx + 2 * rno;
+            let mut summaries = Vec::with_capacity(chunk.len());
+            let mut name_record = NameRecord::default();
+
+            for (sno, segment) in chunk.iter().enumerate() {
+                let mut summary = segment.summary;
+                summary.update_indexes(next_addr, next_addr + segment.data.len() - 1);
+                summaries.push(summary);
+
+                name_record.set_nth_name(sno, summary_size, &segment.name);
+
+                data.extend_from_slice(&segment.data);
+                next_addr += segment.data.len();
+            }
+
+            let next_rcrd = if rcrd_no == bwrd_idx { 0 } else { rcrd_no + 2 };
+            let prev_rcrd = if rno == 0 { 0 } else { rcrd_no - 2 };
+            let daf_summary = [next_rcrd as f64, prev_rcrd as f64, chunk.len() as f64];
+
+            bytes.extend(daf_summary.as_bytes());
+            bytes.extend(summaries.as_bytes());
+            bytes.resize(bytes.len().div_ceil(RCRD_LEN) * RCRD_LEN, 0x0);
+
+            bytes.extend(name_record.as_bytes());
+        }
+
+        let mut file_record = FileRecord {
+            nd: nd as u32,
+            ni: ni as u32,
+            forward: fwrd_idx as u32,
+            backward: bwrd_idx as u32,
+            free_addr: next_addr as u32,
+            ..Default::default()
+        };
+        file_record
+            .id_str
+            .copy_from_slice(format!("DAF/{:<4}", R::IDWORD).as_bytes());
+        file_record.internal_filename.fill(b' ');
+        let ifn_len = internal_filename
+            .len()
+            .min(file_record.internal_filename.len());
+        file_record.internal_filename[..ifn_len]
+            .copy_from_slice(&internal_filename.as_bytes()[..ifn_len]);
+        file_record
+            .endian_str
+            .copy_from_slice(match Endian::f64_native() {
+                Endian::Little => b"LTL-IEEE",
+                Endian::Big => b"BIG-IEEE",
+            });
+        file_record.ftp_str.copy_from_slice(FTP_STR);
+
+        let mut file_bytes = Vec::from(file_record.as_bytes());
+        file_bytes.resize(RCRD_LEN, 0x0);
+        file_bytes.extend(bytes);
+
+        file_bytes.extend(data.as_bytes());
+        // Pad the last record
+        file_bytes.resize(file_bytes.len().div_ceil(RCRD_LEN) * RCRD_LEN, 0x0);
+
+        Self::parse(file_bytes)
+    }
+}
+
+#[cfg(test)]
+mod daf_builder_ut {
+    use crate::naif::daf::datatypes::HermiteSetType13;
+    use crate::naif::daf::{DAFError, DafDataType, NAIFDataSet, NAIFSummaryRecord};
+    use crate::naif::spk::summary::SPKSummaryRecord;
+    use crate::naif::{SPKSegment, SPK};
+    use crate::prelude::{Almanac, Frame};
+    use hifitime::{Epoch, Unit};
+
+    #[test]
+    fn build_hermite_spk() {
+        // Two states along the X axis, at 1 km/s
+        let state_data = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 10.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+        let epoch_data = [0.0, 10.0];
+        let hermite = HermiteSetType13 {
+            samples: 2,
+            num_records: 2,
+            state_data: &state_data,
+            epoch_data: &epoch_data,
+            epoch_registry: &[],
+        };
+
+        let segment = SPKSegment {
+            name: "test segment".to_string(),
+            summary: SPKSummaryRecord {
+                start_epoch_et_s: 0.0,
+                end_epoch_et_s: 10.0,
+                target_id: -10,
+                center_id: 399,
+                frame_id: 1,
+                data_type_i: DafDataType::Type13HermiteUnequalStep as i32,
+                ..Default::default()
+            },
+            data: hermite.to_f64_daf_vec().unwrap(),
+        };
+
+        let spk = SPK::from_segments("ANISE TEST", &[segment]).unwrap();
+
+        assert_eq!(
+            spk.file_record().unwrap().internal_filename().unwrap(),
+            "ANISE TEST"
+        );
+        assert_eq!(spk.file_record().unwrap().identification().unwrap(), "SPK");
+
+        let summaries = spk.data_summaries().unwrap();
+        assert_eq!(summaries[0].target_id, -10);
+        assert_eq!(summaries[0].center_id, 399);
+        assert!(summaries[1].is_empty());
+
+        let (summary, idx) = spk.summary_from_name("test segment").unwrap();
+        assert_eq!(idx, 0);
+        assert_eq!(summary.start_epoch(), Epoch::from_et_seconds(0.0));
+
+        let data = spk.nth_data::<HermiteSetType13>(0).unwrap();
+        assert!(data == hermite);
+
+        let (pos_km, vel_km_s) = data.evaluate(Epoch::from_et_seconds(5.0), summary).unwrap();
+        assert!((pos_km.x - 5.0).abs() < 1e-12);
+        assert!((vel_km_s.x - 1.0).abs() < 1e-12);
+
+        // Cannot build an empty SPK
+        assert!(SPK::from_segments("ANISE TEST", &[]).is_err());
+    }
+
+    #[test]
+    fn build_many_segments_spk() {
+        // Hundreds of ten second segments of the same spacecraft, moving along the X axis at 1 km/s
+        let num_segments = 300;
+        let segments = (0..num_segments)
+            .map(|sno| {
+                let start_et_s = 10.0 * sno as f64;
+                let end_et_s = start_et_s + 10.0;
+                let state_data = [
+                    start_et_s, 0.0, 0.0, 1.0, 0.0, 0.0, end_et_s, 0.0, 0.0, 1.0, 0.0, 0.0,
+                ];
+                let epoch_data = [start_et_s, end_et_s];
+                let hermite = HermiteSetType13 {
+                    samples: 2,
+                    num_records: 2,
+                    state_data: &state_data,
+                    epoch_data: &epoch_data,
+                    epoch_registry: &[],
+                };
+
+                SPKSegment {
+                    name: format!("segment {sno}"),
+                    summary: SPKSummaryRecord {
+                        start_epoch_et_s: start_et_s,
+                        end_epoch_et_s: end_et_s,
+                        target_id: -10,
+                        center_id: 399,
+                        frame_id: 1,
+                        data_type_i: DafDataType::Type13HermiteUnequalStep as i32,
+                        ..Default::default()
+                    },
+                    data: hermite.to_f64_daf_vec().unwrap(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let spk = SPK::from_segments("ANISE TEST", &segments).unwrap();
+
+        // 25 summaries per record
+        let records = spk.summary_records().unwrap();
+        assert_eq!(records.len(), 12);
+        assert!(records.iter().all(|(_, summaries)| summaries.len() == 25));
+        assert_eq!(spk.summary_index().unwrap().len(), num_segments);
+
+        // The data summaries span all of the summary records.
+        let summaries = spk.data_summaries().unwrap();
+        assert_eq!(summaries.len(), num_segments);
+        assert_eq!(summaries.iter().count(), num_segments);
+        assert_eq!(summaries[123].start_epoch(), Epoch::from_et_seconds(1230.0));
+        assert_eq!(summaries.get(299), Some(spk.nth_summary(299).unwrap()));
+        assert!(summaries.get(num_segments).is_none());
+        assert_eq!(spk.summary_from_id(-10).unwrap().1, 0);
+
+        // Each summary record is followed by the names of its segments.
+        assert_eq!(spk.nth_summary_name(123).unwrap(), "segment 123");
+        assert_eq!(spk.nth_summary_name(299).unwrap(), "segment 299");
+        assert!(spk.nth_summary_name(300).is_err());
+        assert_eq!(spk.summary_from_name("segment 299").unwrap().1, 299);
+        assert_eq!(spk.nth_segment(123).unwrap().name, "segment 123");
+        assert_eq!(spk.segment(-10).unwrap().name, "segment 299");
+
+        let epoch = Epoch::from_et_seconds(1234.5);
+        let (summary, idx) = spk.summary_from_id_at_epoch(-10, epoch).unwrap();
+        assert_eq!(idx, 123);
+        assert_eq!(summary.start_epoch(), Epoch::from_et_seconds(1230.0));
+        let (pos_km, _) = spk
+            .nth_data::<HermiteSetType13>(idx)
+            .unwrap()
+            .evaluate(epoch, summary)
+            .unwrap();
+        assert!((pos_km.x - 1234.5).abs() < 1e-9);
+
+        // At the boundary of two segments, the first one in the file has precedence unless in SPICE compatibility mode.
+        let boundary = Epoch::from_et_seconds(2990.0);
+        assert_eq!(spk.summary_from_id_at_epoch(-10, boundary).unwrap().1, 298);
+        assert_eq!(
+            spk.last_summary_from_id_at_epoch(-10, boundary).unwrap().1,
+            299
+        );
+        // Tolerance of 100 ns at the end of the file, but not in SPICE compatibility mode.
+        let after_end = Epoch::from_et_seconds(3000.0) + Unit::Nanosecond * 50;
+        assert_eq!(spk.summary_from_id_at_epoch(-10, after_end).unwrap().1, 299);
+        assert!(spk.last_summary_from_id_at_epoch(-10, after_end).is_err());
+        assert!(spk
+            .summary_from_id_at_epoch(-10, Epoch::from_et_seconds(3000.5))
+            .is_err());
+        assert!(spk.summary_from_id_at_epoch(-20, epoch).is_err());
+
+        // Files with several summary records cannot be edited.
+        let mut mut_spk = spk.to_mutable();
+        assert_eq!(
+            mut_spk.delete_nth_data(0),
+            Err(DAFError::MultipleSummaryRecords { kind: "SPK" })
+        );
+
+        // The Almanac uses the segment covering each epoch.
+        let almanac = Almanac::default().with_spk(spk).unwrap();
+        for et_s in [0.5, 555.0, 1234.5, 2999.0] {
+            let state = almanac
+                .translate(
+                    Frame::from_ephem_j2000(-10),
+                    Frame::from_ephem_j2000(399),
+                    Epoch::from_et_seconds(et_s),
+                    None,
+                )
+                .unwrap();
+            assert!((state.radius_km.x - et_s).abs() < 1e-9);
+        }
+    }
+}
@@ -11,7 +11,7 @@
 use super::file_record::FileRecordError;
 use super::{
     DAFError, DecodingNameSnafu, DecodingSummarySnafu, FileRecordSnafu, IOSnafu, NAIFDataSet,
-    NAIFRecord, NAIFSummaryRecord,
+    NAIFRecord, NAIFSummaryRecord, SummaryIndex,
 };
 pub use super::{FileRecord, NameRecord, SummaryRecord};
 use crate::errors::DecodingError;
@@ -23,9 +23,10 @@ use core::fmt::Debug;
 use core::hash::Hash;
 use core::marker::PhantomData;
 use core::ops::Deref;
-use hifitime::{Epoch, Unit};
+use hifitime::{Duration, Epoch, Unit};
 use log::{debug, error, trace};
 use snafu::ResultExt;
+use std::sync::OnceLock;
 
 use zerocopy::IntoBytes;
 use zerocopy::{FromBytes, Ref};
@@ -42,11 +43,21 @@ macro_rules! io_imports {
 io_imports!();
 
 pub(crate) const RCRD_LEN: usize = 1024;
-#[derive(Clone, Default, Debug, PartialEq)]
+/// Tolerance on the bounds of the summaries when searching for the summary covering an epoch.
+const SUMMARY_EPOCH_TOL: Duration = Duration::from_parts(0, 100);
+
+#[derive(Clone, Default, Debug)]
 pub struct GenericDAF<R: NAIFSummaryRecord, W: MutKind> {
     pub bytes: W,
+    /// CRC32 of the bytes, computed when parsed, or zero if parsed lazily: use [GenericDAF::checksum] instead.
     pub crc32_checksum: u32,
     pub _daf_type: PhantomData<R>,
+    /// Index of the coverage of the summaries, built on the first search by epoch
+    pub(crate) summary_index: OnceLock<SummaryIndex>,
+    /// CRC32 of the bytes of a lazily parsed DAF, computed on the first call to [GenericDAF::checksum]
+    pub(crate) lazy_crc32: Option<OnceLock<u32>>,
+    /// Path of the file this DAF was loaded from, if known, to identify it in query provenance
+    pub(crate) source_path: Option<String>,
 }
 
 pub type DAF<R> = GenericDAF<R, Bytes>;
@@ -62,14 +73,28 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
         crc32fast::hash(&self.bytes)
     }
 
+    /// Returns the CRC32 checksum of this DAF, which is computed on the first call if it was parsed lazily.
+    pub fn checksum(&self) -> u32 {
+        match &self.lazy_crc32 {
+            Some(lazy_crc32) => *lazy_crc32.get_or_init(|| self.crc32()),
+            None => self.crc32_checksum,
+        }
+    }
+
+    /// Returns the path of the file this DAF was loaded from, if it was loaded from a file.
+    pub fn source_path(&self) -> Option<&str> {
+        self.source_path.as_deref()
+    }
+
     /// Scrubs the data by computing the CRC32 of the bytes and making sure that it still matches the previously known hash
     pub fn scrub(&self) -> Result<(), IntegrityError> {
-        if self.crc32() == self.crc32_checksum {
+        let expected = self.checksum();
+        if self.crc32() == expected {
             Ok(())
         } else {
             // Compiler will optimize the double computation away
             Err(IntegrityError::ChecksumInvalid {
-                expected: self.crc32_checksum,
+                expected,
                 computed: self.crc32(),
             })
         }
@@ -98,7 +123,70 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
     }
 
     pub fn name_record(&self) -> Result<NameRecord, DAFError> {
-        let rcrd_idx = self.file_record()?.fwrd_idx() * RCRD_LEN;
+        self.name_record_after(self.file_record()?.fwrd_idx())
+    }
+
+    /// Returns the name record of the n-th summary record, i.e. the record that follows it in the file, cf. [DAF::nth_summary_name].
+    pub fn nth_name_record(&self, rcrd_no: usize) -> Result<NameRecord, DAFError> {
+        let mut summary_rcrd_idx = self.file_record()?.fwrd_idx();
+        for summary_rcrd in self.iter_summary_records()?.take(rcrd_no) {
+            summary_rcrd_idx = summary_rcrd?.0.next_record();
+        }
+
+        if summary_rcrd_idx == 0 {
+            return Err(DAFError::InvalidIndex {
+                kind: R::NAME,
+                idx: rcrd_no,
+            });
+        }
+
+        self.name_record_after(summary_rcrd_idx)
+    }
+
+    /// Returns the name of the n-th data summary of this DAF, indexed as in [DAF::nth_summary].
+    pub fn nth_summary_name(&self, idx: usize) -> Result<String, DAFError> {
+        let per_record = Self::summaries_per_record();
+        let summary_size = self.file_record()?.summary_size();
+        let name_rcrd = self.nth_name_record(idx / per_record)?;
+
+        if idx % per_record >= name_rcrd.num_entries(summary_size) {
+            return Err(DAFError::InvalidIndex { kind: R::NAME, idx });
+        }
+
+        Ok(name_rcrd
+            .nth_name(idx % per_record, summary_size)
+            .trim()
+            .to_string())
+    }
+
+    /// Returns the index of the data summary of the provided name, searching the name records of all of the summary records.
+    ///
+    /// **Warning:** this performs an O(N) search!
+    pub fn summary_index_from_name(&self, name: &str) -> Result<usize, DAFError> {
+        let per_record = Self::summaries_per_record();
+        let summary_size = self.file_record()?.summary_size();
+
+        let mut summary_rcrd_idx = self.file_record()?.fwrd_idx();
+        for (rcrd_no, summary_rcrd) in self.iter_summary_records()?.enumerate() {
+            let (summary_rcrd, summaries) = summary_rcrd?;
+            let name_rcrd = self.name_record_after(summary_rcrd_idx)?;
+            for sno in 0..summaries.len().min(name_rcrd.num_entries(summary_size)) {
+                if name_rcrd.nth_name(sno, summary_size) == name.trim() {
+                    return Ok(rcrd_no * per_record + sno);
+                }
+            }
+            summary_rcrd_idx = summary_rcrd.next_record();
+        }
+
+        Err(DAFError::NameError {
+            kind: R::NAME,
+            name: name.to_string(),
+        })
+    }
+
+    /// Returns the name record that follows the summary record of the provided (one-indexed) record number.
+    fn name_record_after(&self, summary_rcrd_idx: usize) -> Result<NameRecord, DAFError> {
+        let rcrd_idx = summary_rcrd_idx * RCRD_LEN;
         let rcrd_bytes = self
             .bytes
             .get(rcrd_idx..rcrd_idx + RCRD_LEN)
@@ -128,8 +216,36 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
             .context(DecodingSummarySnafu { kind: R::NAME })
     }
 
-    /// Parses the data summaries on the fly.
-    pub fn data_summaries(&self) -> Result<&[R], DAFError> {
+    /// Parses the data summaries of all of the summary records on the fly, cf. [DataSummaries] for their indexes.
+    pub fn data_summaries(&self) -> Result<DataSummaries<'_, R>, DAFError> {
+        if self.file_record()?.is_empty() {
+            return Err(DAFError::FileRecord {
+                kind: R::NAME,
+                source: FileRecordError::EmptyRecord,
+            });
+        }
+
+        let mut iter = SummaryRecordIter {
+            bytes: &self.bytes,
+            next_rcrd_no: self.file_record()?.fwrd_idx(),
+            num_read: 0,
+            all_slots: true,
+            _daf_type: PhantomData,
+        };
+
+        let mut records = Vec::new();
+        for record in &mut iter {
+            records.push(record?.1);
+        }
+
+        Ok(DataSummaries {
+            records,
+            per_record: Self::summaries_per_record(),
+        })
+    }
+
+    /// Parses the data summaries of the first summary record only, which is all of them in most files.
+    pub(crate) fn first_record_summaries(&self) -> Result<&[R], DAFError> {
         if self.file_record()?.is_empty() {
             return Err(DAFError::FileRecord {
                 kind: R::NAME,
@@ -170,11 +286,9 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
 
     /// Returns the summary given the name of the summary record
     pub fn summary_from_name(&self, name: &str) -> Result<(&R, usize), DAFError> {
-        let idx = self
-            .name_record()?
-            .index_from_name::<R>(name, self.file_record()?.summary_size())?;
+        let idx = self.summary_index_from_name(name)?;
 
-        Ok((&self.data_summaries()?[idx], idx))
+        Ok((self.nth_summary(idx)?, idx))
     }
 
     /// Returns the summary given the name of the summary record if that summary has data defined at the requested epoch
@@ -211,61 +325,148 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
     }
 
     /// Returns the summary given the name of the summary record if that summary has data defined at the requested epoch
+    ///
+    /// If several summaries of this ID cover the epoch (within 100 ns), the first one in the file is returned.
     pub fn summary_from_id_at_epoch(&self, id: i32, epoch: Epoch) -> Result<(&R, usize), DAFError> {
-        // NOTE: We iterate through the whole summary because a specific NAIF ID may be repeated in the summary for different valid epochs
-        // so we can't just call `summary_from_id`.
-        for (idx, summary) in self.data_summaries()?.iter().enumerate() {
-            if summary.id() == id {
-                if epoch >= summary.start_epoch() - Unit::Nanosecond * 100
-                    && epoch <= summary.end_epoch() + Unit::Nanosecond * 100
-                {
-                    trace!("Found {id} in position {idx}: {summary:?}");
-                    return Ok((summary, idx));
-                } else {
-                    debug!(
-                        "Summary {id} not valid at {epoch:?} (only from {:?} to {:?}, offset of {} - {})",
-                        summary.start_epoch(),
-                        summary.end_epoch(),
-                        epoch - summary.start_epoch(),
-                        summary.end_epoch() - epoch
-                    );
+        let index = self.summary_index()?;
+        // NOTE: A specific NAIF ID may be repeated in the summary for different valid epochs, so we can't just call `summary_from_id`.
+        match index.first_covering(id, epoch, SUMMARY_EPOCH_TOL) {
+            Some(idx) => {
+                let summary = self.nth_summary(idx)?;
+                trace!("Found {id} in position {idx}: {summary:?}");
+                Ok((summary, idx))
+            }
+            None => {
+                if index.contains_id(id) {
+                    debug!("No summary {id} valid at {epoch:?}");
                 }
+                Err(DAFError::InterpolationDataErrorFromId {
+                    kind: R::NAME,
+                    id,
+                    epoch,
+                })
             }
         }
-        Err(DAFError::InterpolationDataErrorFromId {
-            kind: R::NAME,
-            id,
-            epoch,
+    }
+
+    /// Returns the last summary of this ID which covers the requested epoch, as in SPICE where the segments later in a file have precedence.
+    ///
+    /// Unlike [DAF::summary_from_id_at_epoch], the epoch must be strictly within the bounds of the summary.
+    pub fn last_summary_from_id_at_epoch(
+        &self,
+        id: i32,
+        epoch: Epoch,
+    ) -> Result<(&R, usize), DAFError> {
+        match self
+            .summary_index()?
+            .last_covering(id, epoch, Duration::ZERO)
+        {
+            Some(idx) => {
+                let summary = self.nth_summary(idx)?;
+                trace!("Found {id} in position {idx}: {summary:?}");
+                Ok((summary, idx))
+            }
+            None => Err(DAFError::InterpolationDataErrorFromId {
+                kind: R::NAME,
+                id,
+                epoch,
+            }),
+        }
+    }
+
+    /// Returns the summary records of this DAF with their data summaries, following the forward links from the first
+    /// summary record. Only the first `num_summaries` data summaries of each record are returned.
+    pub fn summary_records(&self) -> Result<Vec<(SummaryRecord, &[R])>, DAFError> {
+        self.iter_summary_records()?.collect()
+    }
+
+    /// Returns an iterator over the summary records of this DAF with their data summaries, like [DAF::summary_records], where
+    /// each record is only read from the underlying bytes when the iterator reaches it.
+    pub fn iter_summary_records(&self) -> Result<SummaryRecordIter<'_, R>, DAFError> {
+        if self.file_record()?.is_empty() {
+            return Err(DAFError::FileRecord {
+                kind: R::NAME,
+                source: FileRecordError::EmptyRecord,
+            });
+        }
+
+        Ok(SummaryRecordIter {
+            bytes: &self.bytes,
+            next_rcrd_no: self.file_record()?.fwrd_idx(),
+            num_read: 0,
+            all_slots: false,
+            _daf_type: PhantomData,
         })
     }
 
+    /// Returns the number of data summaries which fit in one summary record.
+    pub(crate) fn summaries_per_record() -> usize {
+        (RCRD_LEN - SummaryRecord::SIZE) / R::SIZE
+    }
+
+    /// Returns the n-th data summary of this DAF, where the summaries of the n-th summary record start at index
+    /// `n * (1000 / R::SIZE)` (e.g. 25 for SPK files), such that the indexes of the first record match [DAF::data_summaries].
+    pub fn nth_summary(&self, idx: usize) -> Result<&R, DAFError> {
+        let per_record = Self::summaries_per_record();
+        if idx < per_record {
+            return self
+                .first_record_summaries()?
+                .get(idx)
+                .ok_or(DAFError::InvalidIndex { idx, kind: R::NAME });
+        }
+
+        match self.iter_summary_records()?.nth(idx / per_record) {
+            Some(record) => record?
+                .1
+                .get(idx % per_record)
+                .ok_or(DAFError::InvalidIndex { idx, kind: R::NAME }),
+            None => Err(DAFError::InvalidIndex { idx, kind: R::NAME }),
+        }
+    }
+
+    /// Returns the index of the coverage of all of the summaries of this DAF, building it on the first call.
+    pub fn summary_index(&self) -> Result<&SummaryIndex, DAFError> {
+        if let Some(index) = self.summary_index.get() {
+            return Ok(index);
+        }
+
+        let per_record = Self::summaries_per_record();
+        let records = self.summary_records()?;
+        let index = SummaryIndex::new(records.iter().enumerate().flat_map(
+            |(rcrd_no, (_, summaries))| {
+                summaries
+                    .iter()
+                    .enumerate()
+                    .map(move |(sno, summary)| (rcrd_no * per_record + sno, summary))
+            },
+        ));
+
+        Ok(self.summary_index.get_or_init(|| index))
+    }
+
     /// Provided a name that is in the summary, return its full data, if name is available.
     pub fn data_from_name<'a, S: NAIFDataSet<'a>>(&'a self, name: &str) -> Result<S, DAFError> {
         // O(N) search through the summaries
-        let name_rcrd = self.name_record()?;
-        for idx in 0..name_rcrd.num_entries(self.file_record()?.summary_size()) {
-            let this_name = name_rcrd.nth_name(idx, self.file_record()?.summary_size());
-
-            if name.trim() == this_name.trim() {
-                // Found it!
-                return self.nth_data(idx);
-            }
-        }
-        Err(DAFError::NameError {
-            kind: R::NAME,
-            name: name.to_string(),
-        })
+        self.nth_data(self.summary_index_from_name(name)?)
     }
 
     /// Provided a name that is in the summary, return its full data, if name is available.
     pub fn nth_data<'a, S: NAIFDataSet<'a>>(&'a self, idx: usize) -> Result<S, DAFError> {
+        let data = self.nth_data_slice(idx, S::DATASET_NAME)?;
+
+        // Convert it
+        S::from_f64_slice(data).context(DecodingDataSnafu { kind: R::NAME, idx })
+    }
+
+    /// Returns the raw data of the nth summary, in native endianness.
+    pub(crate) fn nth_data_slice(
+        &self,
+        idx: usize,
+        kind: &'static str,
+    ) -> Result<&[f64], DAFError> {
         let this_summary = self
-            .data_summaries()?
-            .get(idx)
-            .ok_or(DAFError::InvalidIndex {
-                idx,
-                kind: S::DATASET_NAME,
-            })?;
+            .nth_summary(idx)
+            .or(Err(DAFError::InvalidIndex { idx, kind }))?;
         // Grab the data in native endianness (TODO: How to support both big and little endian?)
         trace!("{idx} -> {this_summary:?}");
         if self.file_record()?.is_empty() {
@@ -300,8 +501,7 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
             .unwrap(),
         );
 
-        // Convert it
-        S::from_f64_slice(data).context(DecodingDataSnafu { kind: R::NAME, idx })
+        Ok(data)
     }
 
     pub fn comments(&self) -> Result<Option<String>, DAFError> {
@@ -361,7 +561,7 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
 
         let mut daf_summary = Vec::from(self.daf_summary().unwrap().as_bytes());
         // Extend with the data summaries
-        for data_summary in self.data_summaries().unwrap() {
+        for data_summary in self.first_record_summaries().unwrap() {
             daf_summary.extend(data_summary.as_bytes());
         }
         // And pad with NULL
@@ -376,6 +576,139 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
     }
 }
 
+/// Iterator over the summary records of a DAF and their data summaries, following the forward links, cf. [DAF::iter_summary_records].
+///
+/// Only the records reached by the iterator are read, so finding the n-th record does not read those after it.
+pub struct SummaryRecordIter<'a, R: NAIFSummaryRecord> {
+    bytes: &'a [u8],
+    next_rcrd_no: usize,
+    num_read: usize,
+    /// Set to return all of the slots of each record, and not only the first `num_summaries`
+    all_slots: bool,
+    _daf_type: PhantomData<R>,
+}
+
+impl<'a, R: NAIFSummaryRecord> Iterator for SummaryRecordIter<'a, R> {
+    type Item = Result<(SummaryRecord, &'a [R]), DAFError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Guard against circular links in corrupted files
+        if self.next_rcrd_no == 0 || self.num_read >= self.bytes.len() / RCRD_LEN {
+            return None;
+        }
+
+        let rcrd_idx = (self.next_rcrd_no - 1) * RCRD_LEN;
+        let rcrd_bytes = match self
+            .bytes
+            .get(rcrd_idx..rcrd_idx + RCRD_LEN)
+            .ok_or_else(|| DecodingError::InaccessibleBytes {
+                start: rcrd_idx,
+                end: rcrd_idx + RCRD_LEN,
+                size: self.bytes.len(),
+            })
+            .context(DecodingSummarySnafu { kind: R::NAME })
+        {
+            Ok(rcrd_bytes) => rcrd_bytes,
+            Err(e) => {
+                // Stop after an error
+                self.next_rcrd_no = 0;
+                return Some(Err(e));
+            }
+        };
+
+        let summary_rcrd = match SummaryRecord::read_from_bytes(&rcrd_bytes[..SummaryRecord::SIZE])
+            .or(Err(DecodingError::Casting))
+            .context(DecodingSummarySnafu { kind: R::NAME })
+        {
+            Ok(summary_rcrd) => summary_rcrd,
+            Err(e) => {
+                self.next_rcrd_no = 0;
+                return Some(Err(e));
+            }
+        };
+
+        let summaries: &[R] = match Ref::<_, [R]>::from_bytes(&rcrd_bytes[SummaryRecord::SIZE..]) {
+            Ok(r) => Ref::into_ref(r),
+            Err(_) => &[],
+        };
+
+        self.num_read += 1;
+        self.next_rcrd_no = summary_rcrd.next_record();
+
+        if self.all_slots {
+            return Some(Ok((summary_rcrd, summaries)));
+        }
+
+        Some(Ok((
+            summary_rcrd,
+            &summaries[..summary_rcrd.num_summaries().min(summaries.len())],
+        )))
+    }
+}
+
+/// The data summaries of all of the summary records of a DAF, in the order of the file, cf. [DAF::data_summaries].
+///
+/// Each summary record contributes all of its slots, including the empty ones, such that the summaries of the n-th summary
+/// record start at index `n * (1000 / R::SIZE)` (e.g. 25 for SPK files), as for [DAF::nth_summary].
+#[derive(Clone, Debug)]
+pub struct DataSummaries<'a, R: NAIFSummaryRecord> {
+    records: Vec<&'a [R]>,
+    per_record: usize,
+}
+
+impl<'a, R: NAIFSummaryRecord> DataSummaries<'a, R> {
+    /// Returns the number of slots of the summaries, empty or not
+    pub fn len(&self) -> usize {
+        self.records.iter().map(|summaries| summaries.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the summary at this index, if any
+    pub fn get(&self, idx: usize) -> Option<&'a R> {
+        self.records
+            .get(idx / self.per_record)?
+            .get(idx % self.per_record)
+    }
+
+    /// Returns an iterator over all of the summaries, in the order of their indexes
+    pub fn iter(&self) -> core::iter::Flatten<core::iter::Copied<core::slice::Iter<'_, &'a [R]>>> {
+        self.records.iter().copied().flatten()
+    }
+
+    /// Returns a copy of all of the summaries
+    pub fn to_vec(&self) -> Vec<R> {
+        self.iter().copied().collect()
+    }
+}
+
+impl<R: NAIFSummaryRecord> core::ops::Index<usize> for DataSummaries<'_, R> {
+    type Output = R;
+
+    fn index(&self, idx: usize) -> &Self::Output {
+        self.get(idx)
+            .unwrap_or_else(|| panic!("summary index {idx} out of bounds of {}", self.len()))
+    }
+}
+
+impl<'a, R: NAIFSummaryRecord> IntoIterator for DataSummaries<'a, R> {
+    type Item = &'a R;
+    type IntoIter = core::iter::Flatten<std::vec::IntoIter<&'a [R]>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.records.into_iter().flatten()
+    }
+}
+
+impl<R: NAIFSummaryRecord, W: MutKind + PartialEq> PartialEq for GenericDAF<R, W> {
+    /// Only compares the bytes and the checksum: the summary index is derived from the bytes.
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes && self.crc32_checksum == other.crc32_checksum
+    }
+}
+
 impl<R: NAIFSummaryRecord, W: MutKind> Hash for GenericDAF<R, W> {
     /// Hash will only hash the bytes, nothing else (since these are derived from the bytes anyway).
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -391,6 +724,9 @@ impl<R: NAIFSummaryRecord> DAF<R> {
             bytes: Bytes::copy_from_slice(&bytes),
             crc32_checksum,
             _daf_type: PhantomData,
+            summary_index: OnceLock::new(),
+            lazy_crc32: None,
+            source_path: None,
         };
         // Check that these calls will succeed.
         me.file_record()?;
@@ -398,6 +734,26 @@ impl<R: NAIFSummaryRecord> DAF<R> {
         Ok(me)
     }
 
+    /// Parses the provided bytes as a SPICE Double Array File without copying them nor reading the data, such that the load time
+    /// does not depend on the size of the file when the bytes are memory mapped, cf. [file2mmap](crate::file2mmap).
+    ///
+    /// Only the file record and the name record are read: the summary records are read when first searched, and the
+    /// segments when first evaluated. The CRC32 checksum is computed on the first call to [GenericDAF::checksum] (e.g. by
+    /// `scrub` or `Almanac::query_provenance`), and `crc32_checksum` is left to zero.
+    pub fn parse_lazy(bytes: Bytes) -> Result<Self, DAFError> {
+        let me = Self {
+            bytes,
+            crc32_checksum: 0,
+            _daf_type: PhantomData,
+            summary_index: OnceLock::new(),
+            lazy_crc32: Some(OnceLock::new()),
+            source_path: None,
+        };
+        me.file_record()?;
+        me.name_record()?;
+        Ok(me)
+    }
+
     /// Parse the DAF only if the CRC32 checksum of the data is valid
     pub fn check_then_parse<B: Deref<Target = [u8]>>(
         bytes: B,
@@ -418,7 +774,13 @@ impl<R: NAIFSummaryRecord> DAF<R> {
             action: format!("loading {path:?}"),
         })?;
 
-        Self::parse(bytes)
+        Ok(Self::parse(bytes)?.with_source_path(path))
+    }
+
+    /// Returns this DAF, recording the path of the file it was loaded from.
+    pub(crate) fn with_source_path(mut self, path: &str) -> Self {
+        self.source_path = Some(path.to_string());
+        self
     }
 
     /// Parse the provided static byte array as a SPICE Double Array File
@@ -430,8 +792,11 @@ impl<R: NAIFSummaryRecord> DAF<R> {
     pub fn to_mutable(&self) -> MutDAF<R> {
         MutDAF {
             bytes: BytesMut::from_iter(&self.bytes),
-            crc32_checksum: self.crc32_checksum,
+            crc32_checksum: self.checksum(),
             _daf_type: PhantomData,
+            summary_index: OnceLock::new(),
+            lazy_crc32: None,
+            source_path: self.source_path.clone(),
         }
     }
 }
@@ -15,7 +15,9 @@ use snafu::{ensure, ResultExt};
 use crate::{
     errors::{DecodingError, IntegrityError, TooFewDoublesSnafu},
     math::{
-        interpolation::{chebyshev_eval, InterpDecodingSnafu, InterpolationError},
+        interpolation::{
+            chebyshev_eval, chebyshev_eval_second_deriv, InterpDecodingSnafu, InterpolationError,
+        },
         Vector3,
     },
     naif::daf::{NAIFDataRecord, NAIFDataSet, NAIFSummaryRecord},
@@ -57,6 +59,46 @@ impl Type2ChebyshevSet<'_> {
 
         Ok(((ephem_start_delta_s / window_duration_s) as usize + 1).min(self.num_records))
     }
+
+    /// Evaluates the position, the velocity, and the acceleration at the provided epoch, where the acceleration is the
+    /// analytic second derivative of the Chebyshev polynomials.
+    pub fn evaluate_with_acceleration<S: NAIFSummaryRecord>(
+        &self,
+        epoch: Epoch,
+        summary: &S,
+    ) -> Result<(Vector3, Vector3, Vector3), InterpolationError> {
+        let spline_idx = self.spline_idx(epoch, summary)?;
+
+        let radius_s = self.interval_length.to_seconds() / 2.0;
+
+        let record = self
+            .nth_record(spline_idx - 1)
+            .context(InterpDecodingSnafu)?;
+
+        let normalized_time = (epoch.to_et_seconds() - record.midpoint_et_s) / radius_s;
+
+        let mut state = Vector3::zeros();
+        let mut rate = Vector3::zeros();
+        let mut accel = Vector3::zeros();
+
+        for (cno, coeffs) in [record.x_coeffs, record.y_coeffs, record.z_coeffs]
+            .iter()
+            .enumerate()
+        {
+            let (val, deriv, second_deriv) = chebyshev_eval_second_deriv(
+                normalized_time,
+                coeffs,
+                radius_s,
+                epoch,
+                self.degree(),
+            )?;
+            state[cno] = val;
+            rate[cno] = deriv;
+            accel[cno] = second_deriv;
+        }
+
+        Ok((state, rate, accel))
+    }
 }
 
 impl fmt::Display for Type2ChebyshevSet<'_> {
@@ -14,7 +14,7 @@ use snafu::{ensure, ResultExt};
 
 use crate::errors::{DecodingError, IntegrityError, TooFewDoublesSnafu};
 use crate::math::interpolation::{
-    hermite_eval, InterpDecodingSnafu, InterpolationError, MAX_SAMPLES,
+    hermite_eval, lagrange_eval, InterpDecodingSnafu, InterpolationError, MAX_SAMPLES,
 };
 use crate::naif::daf::NAIFSummaryRecord;
 use crate::{
@@ -153,6 +153,55 @@ impl HermiteSetType13<'_> {
     pub fn degree(&self) -> usize {
         2 * self.samples - 1
     }
+
+    /// Returns the indexes of the first and past-the-end records used to interpolate around the record at the provided index.
+    fn sample_window(&self, idx: usize) -> (usize, usize) {
+        let num_left = self.samples / 2;
+
+        // Ensure that we aren't fetching out of the window
+        let mut first_idx = idx.saturating_sub(num_left);
+        let last_idx = self.num_records.min(first_idx + self.samples);
+
+        // Check that we have enough samples
+        if last_idx == self.num_records {
+            first_idx = last_idx - 2 * num_left;
+        }
+
+        (first_idx, last_idx)
+    }
+
+    /// Evaluates the position, the velocity, and the acceleration at the provided epoch, where the acceleration is the
+    /// derivative of the Lagrange interpolation of the velocities of the same samples as the Hermite interpolation.
+    pub fn evaluate_with_acceleration<S: NAIFSummaryRecord>(
+        &self,
+        epoch: Epoch,
+        summary: &S,
+    ) -> Result<(Vector3, Vector3, Vector3), InterpolationError> {
+        let (pos_km, vel_km_s) = self.evaluate(epoch, summary)?;
+
+        let epoch_et_s = epoch.to_et_seconds();
+        let (first_idx, last_idx) =
+            self.sample_window(self.epoch_data.partition_point(|et_s| *et_s < epoch_et_s));
+
+        let mut epochs = [0.0; MAX_SAMPLES];
+        let mut vels = [[0.0; MAX_SAMPLES]; 3];
+        for (cno, idx) in (first_idx..last_idx).enumerate() {
+            let record = self.nth_record(idx).context(InterpDecodingSnafu)?;
+            vels[0][cno] = record.vx_km_s;
+            vels[1][cno] = record.vy_km_s;
+            vels[2][cno] = record.vz_km_s;
+            epochs[cno] = self.epoch_data[idx];
+        }
+
+        let num_samples = last_idx - first_idx;
+        let mut acc_km_s2 = Vector3::zeros();
+        for (axis, vel) in vels.iter().enumerate() {
+            acc_km_s2[axis] =
+                lagrange_eval(&epochs[..num_samples], &vel[..num_samples], epoch_et_s)?.1;
+        }
+
+        Ok((pos_km, vel_km_s, acc_km_s2))
+    }
 }
 
 impl fmt::Display for HermiteSetType13<'_> {
@@ -293,16 +342,7 @@ impl<'a> NAIFDataSet<'a> for HermiteSetType13<'a> {
             }
             Err(idx) => {
                 // We didn't find it, so let's build an interpolation here.
-                let num_left = self.samples / 2;
-
-                // Ensure that we aren't fetching out of the window
-                let mut first_idx = idx.saturating_sub(num_left);
-                let last_idx = self.num_records.min(first_idx + self.samples);
-
-                // Check that we have enough samples
-                if last_idx == self.num_records {
-                    first_idx = last_idx - 2 * num_left;
-                }
+                let (first_idx, last_idx) = self.sample_window(idx);
 
                 // Statically allocated arrays of the maximum number of samples
                 let mut epochs = [0.0; MAX_SAMPLES];
@@ -388,6 +428,18 @@ impl<'a> NAIFDataSet<'a> for HermiteSetType13<'a> {
 
         Ok(())
     }
+
+    /// Builds the DAF array representing a Hermite Type 13 interpolation set.
+    fn to_f64_daf_vec(&self) -> Result<Vec<f64>, InterpolationError> {
+        let mut data = self.state_data.to_vec();
+        data.extend_from_slice(self.epoch_data);
+        data.extend_from_slice(self.epoch_registry);
+        // NOTE: The Type 12 and 13 specify that the windows size minus one is stored!
+        data.push((self.samples - 1) as f64);
+        data.push(self.num_records as f64);
+
+        Ok(data)
+    }
 }
 
 #[cfg(test)]
@@ -351,4 +351,15 @@ impl<'a> NAIFDataSet<'a> for LagrangeSetType9<'a> {
 
         Ok(())
     }
+
+    /// Builds the DAF array representing a Lagrange Type 9 interpolation set.
+    fn to_f64_daf_vec(&self) -> Result<Vec<f64>, InterpolationError> {
+        let mut data = self.state_data.to_vec();
+        data.extend_from_slice(self.epoch_data);
+        data.extend_from_slice(self.epoch_registry);
+        data.push(self.degree as f64);
+        data.push(self.num_records as f64);
+
+        Ok(data)
+    }
 }
@@ -13,6 +13,7 @@ use std::str::Utf8Error;
 use snafu::prelude::*;
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
+use crate::errors::ErrorCode;
 use crate::naif::Endian;
 use log::error;
 
@@ -42,6 +43,20 @@ pub enum FileRecordError {
     EmptyRecord,
 }
 
+impl ErrorCode for FileRecordError {
+    fn code(&self) -> u16 {
+        match self {
+            Self::WrongEndian => 701,
+            Self::ParsingError { .. } => 702,
+            Self::InvalidEndian { .. } => 703,
+            Self::UnsupportedIdentifier { .. } => 704,
+            Self::NotDAF => 705,
+            Self::NoIdentifier => 706,
+            Self::EmptyRecord => 707,
+        }
+    }
+}
+
 #[derive(Debug, Clone, FromBytes, KnownLayout, Immutable, IntoBytes, PartialEq)]
 #[repr(C)]
 pub struct FileRecord {
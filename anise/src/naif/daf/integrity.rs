@@ -0,0 +1,323 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use super::daf::{GenericDAF, MutKind};
+use super::{DAFError, DafDataType, NAIFSummaryRecord};
+use crate::NaifId;
+
+/// Issue found in a segment of a DAF file by [GenericDAF::integrity_report], which would otherwise surface as an
+/// interpolation error when querying that segment.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum IntegrityWarning {
+    /// The segment ends at its start epoch
+    ZeroLengthInterval { idx: usize, id: NaifId },
+    /// The segment ends before its start epoch
+    InvertedInterval { idx: usize, id: NaifId },
+    /// The data of the segment includes NaN or infinite values
+    NonFiniteData {
+        idx: usize,
+        id: NaifId,
+        /// Number of non finite values in the segment
+        count: usize,
+    },
+    /// The epochs (or record midpoints) of the segment do not strictly increase
+    NonMonotonicEpochs {
+        idx: usize,
+        id: NaifId,
+        /// Index of the first epoch (or record) which is not after the previous one
+        position: usize,
+    },
+    /// A record or the step of the segment spans no time
+    ZeroLengthRecord {
+        idx: usize,
+        id: NaifId,
+        /// Index of the first record with a null radius, or zero for equal step segments
+        position: usize,
+    },
+    /// The metadata of the segment is inconsistent with its length, so its records cannot be read
+    MalformedDirectory { idx: usize, id: NaifId },
+    /// The data of the segment cannot be read, e.g. because it ends past the end of the file
+    UnreadableData { idx: usize, id: NaifId },
+}
+
+impl fmt::Display for IntegrityWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroLengthInterval { idx, id } => {
+                write!(f, "segment #{idx} (ID {id}) has a zero length interval")
+            }
+            Self::InvertedInterval { idx, id } => {
+                write!(f, "segment #{idx} (ID {id}) ends before it starts")
+            }
+            Self::NonFiniteData { idx, id, count } => {
+                write!(
+                    f,
+                    "segment #{idx} (ID {id}) has {count} NaN or infinite value(s)"
+                )
+            }
+            Self::NonMonotonicEpochs { idx, id, position } => write!(
+                f,
+                "segment #{idx} (ID {id}) has non monotonic epochs at position {position}"
+            ),
+            Self::ZeroLengthRecord { idx, id, position } => write!(
+                f,
+                "segment #{idx} (ID {id}) has a zero length record at position {position}"
+            ),
+            Self::MalformedDirectory { idx, id } => {
+                write!(
+                    f,
+                    "segment #{idx} (ID {id}) has a malformed record directory"
+                )
+            }
+            Self::UnreadableData { idx, id } => {
+                write!(f, "segment #{idx} (ID {id}) has unreadable data")
+            }
+        }
+    }
+}
+
+/// Report of the sanity checks of all of the segments of a DAF file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IntegrityReport {
+    /// Kind of DAF, e.g. `SPKSummaryRecord`
+    pub kind: &'static str,
+    /// CRC32 of the checked file
+    pub crc32: u32,
+    /// Number of checked segments
+    pub num_segments: usize,
+    pub warnings: Vec<IntegrityWarning>,
+}
+
+impl IntegrityReport {
+    /// Returns true if no issue was found
+    pub fn is_clean(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+impl fmt::Display for IntegrityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (CRC32 {:08x}): {} segment(s), {} warning(s)",
+            self.kind,
+            self.crc32,
+            self.num_segments,
+            self.warnings.len()
+        )?;
+        for warning in &self.warnings {
+            write!(f, "\n\t{warning}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
+    /// Scans all of the segments of this DAF for zero length or inverted intervals, unreadable, NaN or infinite data, and
+    /// non monotonic epoch directories, and reports the issues found instead of failing at the first one.
+    ///
+    /// An error is only returned if the summaries of the segments cannot be read.
+    ///
+    /// Epoch directories are only checked for the Chebyshev (types 2 and 3), Lagrange, and Hermite data types.
+    pub fn integrity_report(&self) -> Result<IntegrityReport, DAFError> {
+        let mut report = IntegrityReport {
+            kind: R::NAME,
+            crc32: self.crc32(),
+            ..Default::default()
+        };
+
+        for (idx, summary) in self.data_summaries()?.iter().enumerate() {
+            if summary.is_empty() {
+                continue;
+            }
+            report.num_segments += 1;
+            let id = summary.id();
+
+            if summary.end_epoch_et_s() == summary.start_epoch_et_s() {
+                report
+                    .warnings
+                    .push(IntegrityWarning::ZeroLengthInterval { idx, id });
+            } else if summary.end_epoch_et_s() < summary.start_epoch_et_s() {
+                report
+                    .warnings
+                    .push(IntegrityWarning::InvertedInterval { idx, id });
+            }
+
+            // Keep checking the other segments if the data of this one cannot be read.
+            let Ok(data) = self.nth_data_slice(idx, R::NAME) else {
+                report
+                    .warnings
+                    .push(IntegrityWarning::UnreadableData { idx, id });
+                continue;
+            };
+
+            let count = data.iter().filter(|val| !val.is_finite()).count();
+            if count > 0 {
+                report
+                    .warnings
+                    .push(IntegrityWarning::NonFiniteData { idx, id, count });
+            }
+
+            if let Ok(data_type) = summary.data_type() {
+                if let Some(warning) = check_directory(data_type, data, idx, id) {
+                    report.warnings.push(warning);
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Checks the epoch directory of the provided segment data, per its data type.
+fn check_directory(
+    data_type: DafDataType,
+    data: &[f64],
+    idx: usize,
+    id: NaifId,
+) -> Option<IntegrityWarning> {
+    let malformed = Some(IntegrityWarning::MalformedDirectory { idx, id });
+    let len = data.len();
+    let num_records =
+        |val: f64| (val.is_finite() && val >= 0.0 && val.fract() == 0.0).then_some(val as usize);
+
+    match data_type {
+        DafDataType::Type2ChebyshevTriplet | DafDataType::Type3ChebyshevSextuplet => {
+            // Records start with their midpoint and radius, followed by the init epoch, interval length, record size, and number of records.
+            if len < 4 {
+                return malformed;
+            }
+            let (Some(rsize), Some(num_records)) =
+                (num_records(data[len - 2]), num_records(data[len - 1]))
+            else {
+                return malformed;
+            };
+            match rsize.checked_mul(num_records) {
+                Some(size) if rsize >= 2 && size <= len - 4 => {}
+                _ => return malformed,
+            }
+
+            let mut prev_midpoint = f64::NEG_INFINITY;
+            for (position, record) in data[..rsize * num_records].chunks_exact(rsize).enumerate() {
+                if record[1] <= 0.0 {
+                    return Some(IntegrityWarning::ZeroLengthRecord { idx, id, position });
+                } else if record[0] <= prev_midpoint {
+                    return Some(IntegrityWarning::NonMonotonicEpochs { idx, id, position });
+                }
+                prev_midpoint = record[0];
+            }
+            None
+        }
+        DafDataType::Type8LagrangeEqualStep | DafDataType::Type12HermiteEqualStep => {
+            // Ends with the first epoch, the step size, the window size (or degree), and the number of records.
+            if len < 4 {
+                return malformed;
+            }
+            (data[len - 3] <= 0.0).then_some(IntegrityWarning::ZeroLengthRecord {
+                idx,
+                id,
+                position: 0,
+            })
+        }
+        DafDataType::Type9LagrangeUnequalStep | DafDataType::Type13HermiteUnequalStep => {
+            // States, then epochs, then the epoch directory, and finally the degree (or window size) and number of records.
+            if len < 2 {
+                return malformed;
+            }
+            let Some(num_records) = num_records(data[len - 1]) else {
+                return malformed;
+            };
+            // Each state is a position and a velocity.
+            let Some(epochs) = num_records
+                .checked_mul(7)
+                .and_then(|end| data.get(6 * num_records..end))
+            else {
+                return malformed;
+            };
+
+            epochs
+                .windows(2)
+                .position(|pair| pair[1] <= pair[0])
+                .map(|position| IntegrityWarning::NonMonotonicEpochs {
+                    idx,
+                    id,
+                    position: position + 1,
+                })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod ut_integrity {
+    use super::{check_directory, DafDataType, IntegrityWarning};
+
+    #[test]
+    fn chebyshev_directory() {
+        // Two records of size 5 (midpoint, radius, and three coefficients), then the metadata
+        let mut data = vec![
+            10.0, 10.0, 1.0, 2.0, 3.0, 30.0, 10.0, 1.0, 2.0, 3.0, 0.0, 20.0, 5.0, 2.0,
+        ];
+        assert_eq!(
+            check_directory(DafDataType::Type2ChebyshevTriplet, &data, 0, 301),
+            None
+        );
+
+        data[5] = 10.0;
+        assert_eq!(
+            check_directory(DafDataType::Type2ChebyshevTriplet, &data, 0, 301),
+            Some(IntegrityWarning::NonMonotonicEpochs {
+                idx: 0,
+                id: 301,
+                position: 1
+            })
+        );
+
+        data[1] = 0.0;
+        assert_eq!(
+            check_directory(DafDataType::Type2ChebyshevTriplet, &data, 0, 301),
+            Some(IntegrityWarning::ZeroLengthRecord {
+                idx: 0,
+                id: 301,
+                position: 0
+            })
+        );
+
+        // Too many records for the data
+        data[13] = 3.0;
+        assert_eq!(
+            check_directory(DafDataType::Type2ChebyshevTriplet, &data, 0, 301),
+            Some(IntegrityWarning::MalformedDirectory { idx: 0, id: 301 })
+        );
+    }
+
+    #[test]
+    fn lagrange_directory() {
+        // Three states, then their epochs, no directory, the degree, and the number of records
+        let mut data = vec![0.0; 18];
+        data.extend([0.0, 60.0, 120.0, 1.0, 3.0]);
+        assert_eq!(
+            check_directory(DafDataType::Type9LagrangeUnequalStep, &data, 2, -10),
+            None
+        );
+
+        data[20] = 60.0;
+        assert_eq!(
+            check_directory(DafDataType::Type9LagrangeUnequalStep, &data, 2, -10),
+            Some(IntegrityWarning::NonMonotonicEpochs {
+                idx: 2,
+                id: -10,
+                position: 2
+            })
+        );
+    }
+}
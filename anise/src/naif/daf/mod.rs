@@ -9,7 +9,9 @@
  */
 
 use crate::{
-    errors::IntegrityError, math::interpolation::InterpolationError, prelude::InputOutputError,
+    errors::{ErrorCode, IntegrityError},
+    math::interpolation::InterpolationError,
+    prelude::InputOutputError,
     NaifId,
 };
 use core::fmt::Display;
@@ -18,23 +20,31 @@ use snafu::prelude::*;
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
 pub(crate) const RCRD_LEN: usize = 1024;
+pub mod builder;
 #[allow(clippy::module_inception)]
 pub mod daf;
 mod data_types;
 pub mod mut_daf;
 pub use data_types::DataType as DafDataType;
 pub mod file_record;
+pub mod integrity;
 pub mod name_record;
+pub mod segment;
+pub mod summary_index;
 pub mod summary_record;
 // Defines the supported data types
 pub mod datatypes;
 
-pub use daf::DAF;
+pub use builder::DAFSegment;
+pub use daf::{DataSummaries, DAF};
 
 use crate::errors::DecodingError;
 use core::fmt::Debug;
 pub use file_record::FileRecord;
+pub use integrity::{IntegrityReport, IntegrityWarning};
 pub use name_record::NameRecord;
+pub use segment::{Segment, SegmentRecord};
+pub use summary_index::SummaryIndex;
 pub use summary_record::SummaryRecord;
 
 use self::file_record::FileRecordError;
@@ -70,6 +80,10 @@ pub trait NAIFSummaryRecord: NAIFRecord + Copy + Immutable + KnownLayout {
     fn update_epochs(&mut self, start_epoch: Epoch, end_epoch: Epoch);
     /// Name of this NAIF type
     const NAME: &'static str;
+    /// Identification word of DAF files of this type, e.g. `SPK`
+    const IDWORD: &'static str;
+    /// Number of integer components of this summary record
+    const NUM_INTEGERS: usize;
 }
 
 pub trait NAIFDataSet<'a>: Sized + Display + PartialEq {
@@ -219,6 +233,12 @@ pub enum DAFError {
     },
     #[snafu(display("data type {id}: {kind} (corrupted data?)"))]
     Datatype { id: i32, kind: &'static str },
+    #[snafu(display("DAF/{kind}: data type of segment {idx}: {err}"))]
+    SegmentDatatype {
+        kind: &'static str,
+        idx: usize,
+        err: String,
+    },
     #[snafu(display("{dtype:?} not supported for {kind}"))]
     UnsupportedDatatype {
         dtype: DafDataType,
@@ -228,6 +248,40 @@ pub enum DAFError {
     InvalidIndex { kind: &'static str, idx: usize },
     #[snafu(display("could not build data vector of type DAF/{kind}"))]
     DataBuildError { kind: &'static str },
+    #[snafu(display(
+        "DAF/{kind}: editing files with more than one summary record is not supported"
+    ))]
+    MultipleSummaryRecords { kind: &'static str },
+}
+
+impl ErrorCode for DAFError {
+    fn code(&self) -> u16 {
+        match self {
+            Self::NoDAFLoaded { .. } => 601,
+            Self::MaxRecursionDepth => 602,
+            Self::SummaryIdError { .. } => 603,
+            Self::SummaryIdAtEpochError { .. } => 604,
+            Self::SummaryNameError { .. } => 605,
+            Self::SummaryNameAtEpochError { .. } => 606,
+            Self::InterpolationDataErrorFromName { .. } => 607,
+            Self::InterpolationDataErrorFromId { .. } => 608,
+            Self::FileRecord { source, .. } => source.code(),
+            Self::EmptySummary { .. } => 609,
+            Self::NameError { .. } => 610,
+            Self::DecodingSummary { source, .. } => source.code(),
+            Self::DecodingComments { source, .. } => source.code(),
+            Self::DecodingName { source, .. } => source.code(),
+            Self::DecodingData { source, .. } => source.code(),
+            Self::DAFIntegrity { source } => source.code(),
+            Self::IO { source, .. } => source.code(),
+            Self::Datatype { .. } => 611,
+            Self::SegmentDatatype { .. } => 612,
+            Self::UnsupportedDatatype { .. } => 613,
+            Self::InvalidIndex { .. } => 614,
+            Self::DataBuildError { .. } => 615,
+            Self::MultipleSummaryRecords { .. } => 616,
+        }
+    }
 }
 
 // Manual implementation of PartialEq because IOError does not derive it, sadly.
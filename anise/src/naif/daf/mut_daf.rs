@@ -9,6 +9,7 @@
  */
 
 use core::{marker::PhantomData, ops::Deref};
+use std::sync::OnceLock;
 
 use super::{
     daf::MutDAF, DAFError, DecodingNameSnafu, IOSnafu, NAIFDataSet, NAIFSummaryRecord, NameRecord,
@@ -35,6 +36,9 @@ impl<R: NAIFSummaryRecord> MutDAF<R> {
             bytes: buf,
             crc32_checksum,
             _daf_type: PhantomData,
+            summary_index: OnceLock::new(),
+            lazy_crc32: None,
+            source_path: None,
         };
         // Check that these calls will succeed.
         me.file_record()?;
@@ -75,7 +79,11 @@ impl<R: NAIFSummaryRecord> MutDAF<R> {
         new_start_epoch: Epoch,
         new_end_epoch: Epoch,
     ) -> Result<(), DAFError> {
-        let summaries = self.data_summaries()?;
+        if self.summary_records()?.len() > 1 {
+            return Err(DAFError::MultipleSummaryRecords { kind: R::NAME });
+        }
+
+        let summaries = self.first_record_summaries()?;
         let this_summary = summaries
             .get(idx)
             .ok_or(DAFError::InvalidIndex { idx, kind: R::NAME })?;
@@ -141,13 +149,19 @@ impl<R: NAIFSummaryRecord> MutDAF<R> {
         orig_summary_bytes.copy_from_slice(&summary_bytes);
 
         self.bytes = BytesMut::from_iter(new_bytes);
+        // The coverage of the summaries may have changed
+        self.summary_index = OnceLock::new();
 
         Ok(())
     }
 
     /// Deletes the data for the n-th segment of this DAF file.
     pub fn delete_nth_data(&mut self, idx: usize) -> Result<(), DAFError> {
-        let summaries = self.data_summaries()?;
+        if self.summary_records()?.len() > 1 {
+            return Err(DAFError::MultipleSummaryRecords { kind: R::NAME });
+        }
+
+        let summaries = self.first_record_summaries()?;
         let this_summary = summaries
             .get(idx)
             .ok_or(DAFError::InvalidIndex { idx, kind: R::NAME })?;
@@ -209,6 +223,8 @@ impl<R: NAIFSummaryRecord> MutDAF<R> {
         orig_summary_bytes.copy_from_slice(&summary_bytes);
 
         self.bytes = BytesMut::from_iter(new_bytes);
+        // The coverage of the summaries may have changed
+        self.summary_index = OnceLock::new();
 
         Ok(())
     }
@@ -0,0 +1,331 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use hifitime::{Duration, Epoch};
+use snafu::ResultExt;
+
+use super::daf::{GenericDAF, MutKind};
+use super::datatypes::posvel::PositionVelocityRecord;
+use super::datatypes::{
+    HermiteSetType12, HermiteSetType13, LagrangeSetType8, LagrangeSetType9, Type2ChebyshevSet,
+    Type3ChebyshevSet,
+};
+use super::{DAFError, DafDataType, DecodingDataSnafu, NAIFDataSet, NAIFSummaryRecord};
+use crate::math::Vector3;
+
+/// A segment of a DAF file (e.g. of an SPK or a BPC), whose records can be decoded into owned values, e.g. to analyze the
+/// spectrum of the Chebyshev coefficients or to re-fit the data, without parsing the DAF bytes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segment<'a, R: NAIFSummaryRecord> {
+    /// Name of the segment, as stored in the name record
+    pub name: String,
+    /// Index of the segment in the DAF file
+    pub idx: usize,
+    pub summary: R,
+    pub data_type: DafDataType,
+    data: &'a [f64],
+}
+
+/// A decoded record of a segment, cf. [Segment::records].
+#[derive(Clone, Debug, PartialEq)]
+pub enum SegmentRecord {
+    /// Chebyshev polynomials valid over `midpoint ± radius` (types 2 and 3), with one set of coefficients per component,
+    /// in order: the position (or the angles of a BPC) and, for type 3, the velocity.
+    Chebyshev {
+        midpoint: Epoch,
+        radius: Duration,
+        coefficients: Vec<Vec<f64>>,
+    },
+    /// Interpolation node of a Lagrange or Hermite segment (types 8, 9, 12, and 13).
+    Node {
+        epoch: Epoch,
+        position_km: Vector3,
+        velocity_km_s: Vector3,
+    },
+}
+
+impl SegmentRecord {
+    /// Returns the epoch of this record, i.e. the midpoint of the Chebyshev interval or the epoch of the node.
+    pub fn epoch(&self) -> Epoch {
+        match self {
+            Self::Chebyshev { midpoint, .. } => *midpoint,
+            Self::Node { epoch, .. } => *epoch,
+        }
+    }
+}
+
+impl fmt::Display for SegmentRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Chebyshev {
+                midpoint,
+                radius,
+                coefficients,
+            } => write!(
+                f,
+                "Chebyshev record @ {midpoint} ± {radius}: {} components of {} coefficients",
+                coefficients.len(),
+                coefficients.first().map_or(0, |coeffs| coeffs.len())
+            ),
+            Self::Node {
+                epoch,
+                position_km,
+                velocity_km_s,
+            } => write!(
+                f,
+                "node @ {epoch}: position = {position_km} km, velocity = {velocity_km_s} km/s"
+            ),
+        }
+    }
+}
+
+impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
+    /// Returns the segment of the provided ID with the highest priority, i.e. the last one in the file, as in SPICE.
+    pub fn segment(&self, id: i32) -> Result<Segment<'_, R>, DAFError> {
+        let idx = self
+            .data_summaries()?
+            .iter()
+            .enumerate()
+            .filter(|(_, summary)| summary.id() == id && !summary.is_empty())
+            .last()
+            .map(|(idx, _)| idx)
+            .ok_or(DAFError::SummaryIdError { kind: R::NAME, id })?;
+
+        self.nth_segment(idx)
+    }
+
+    /// Returns the nth segment of this DAF file.
+    pub fn nth_segment(&self, idx: usize) -> Result<Segment<'_, R>, DAFError> {
+        let summary = *self.nth_summary(idx)?;
+        if summary.is_empty() {
+            return Err(DAFError::EmptySummary { kind: R::NAME, idx });
+        }
+
+        let data_type = summary.data_type().map_err(|e| DAFError::SegmentDatatype {
+            kind: R::NAME,
+            idx,
+            err: e.to_string(),
+        })?;
+
+        let name = self.nth_summary_name(idx)?;
+
+        Ok(Segment {
+            name,
+            idx,
+            summary,
+            data_type,
+            data: self.nth_data_slice(idx, R::NAME)?,
+        })
+    }
+}
+
+impl<'a, R: NAIFSummaryRecord> Segment<'a, R> {
+    /// Returns the number of records of this segment.
+    pub fn num_records(&self) -> Result<usize, DAFError> {
+        match self.data_type {
+            DafDataType::Type2ChebyshevTriplet => {
+                Ok(self.dataset::<Type2ChebyshevSet>()?.num_records)
+            }
+            DafDataType::Type3ChebyshevSextuplet => {
+                Ok(self.dataset::<Type3ChebyshevSet>()?.num_records)
+            }
+            DafDataType::Type8LagrangeEqualStep => {
+                Ok(self.dataset::<LagrangeSetType8>()?.num_records)
+            }
+            DafDataType::Type9LagrangeUnequalStep => {
+                Ok(self.dataset::<LagrangeSetType9>()?.num_records)
+            }
+            DafDataType::Type12HermiteEqualStep => {
+                Ok(self.dataset::<HermiteSetType12>()?.num_records)
+            }
+            DafDataType::Type13HermiteUnequalStep => {
+                Ok(self.dataset::<HermiteSetType13>()?.num_records)
+            }
+            dtype => Err(DAFError::UnsupportedDatatype {
+                dtype,
+                kind: "segment records",
+            }),
+        }
+    }
+
+    /// Decodes all of the records of this segment, in chronological order.
+    ///
+    /// # Limitations
+    /// Only the Chebyshev (2 and 3), Lagrange (8 and 9), and Hermite (12 and 13) types are supported.
+    pub fn records(&self) -> Result<Vec<SegmentRecord>, DAFError> {
+        match self.data_type {
+            DafDataType::Type2ChebyshevTriplet => {
+                let set = self.dataset::<Type2ChebyshevSet>()?;
+                (0..set.num_records)
+                    .map(|n| {
+                        let record = set.nth_record(n).context(DecodingDataSnafu {
+                            kind: R::NAME,
+                            idx: self.idx,
+                        })?;
+                        Ok(SegmentRecord::Chebyshev {
+                            midpoint: record.midpoint_epoch(),
+                            radius: record.radius,
+                            coefficients: vec![
+                                record.x_coeffs.to_vec(),
+                                record.y_coeffs.to_vec(),
+                                record.z_coeffs.to_vec(),
+                            ],
+                        })
+                    })
+                    .collect()
+            }
+            DafDataType::Type3ChebyshevSextuplet => {
+                let set = self.dataset::<Type3ChebyshevSet>()?;
+                (0..set.num_records)
+                    .map(|n| {
+                        let record = set.nth_record(n).context(DecodingDataSnafu {
+                            kind: R::NAME,
+                            idx: self.idx,
+                        })?;
+                        Ok(SegmentRecord::Chebyshev {
+                            midpoint: record.midpoint_epoch(),
+                            radius: record.radius,
+                            coefficients: vec![
+                                record.x_coeffs.to_vec(),
+                                record.y_coeffs.to_vec(),
+                                record.z_coeffs.to_vec(),
+                                record.vx_coeffs.to_vec(),
+                                record.vy_coeffs.to_vec(),
+                                record.vz_coeffs.to_vec(),
+                            ],
+                        })
+                    })
+                    .collect()
+            }
+            DafDataType::Type8LagrangeEqualStep => {
+                let set = self.dataset::<LagrangeSetType8>()?;
+                self.nodes(&set, set.num_records, |n| {
+                    set.first_state_epoch + set.step_size * (n as i64)
+                })
+            }
+            DafDataType::Type9LagrangeUnequalStep => {
+                let set = self.dataset::<LagrangeSetType9>()?;
+                self.nodes(&set, set.num_records, |n| {
+                    Epoch::from_et_seconds(set.epoch_data[n])
+                })
+            }
+            DafDataType::Type12HermiteEqualStep => {
+                let set = self.dataset::<HermiteSetType12>()?;
+                self.nodes(&set, set.num_records, |n| {
+                    set.first_state_epoch + set.step_size * (n as i64)
+                })
+            }
+            DafDataType::Type13HermiteUnequalStep => {
+                let set = self.dataset::<HermiteSetType13>()?;
+                self.nodes(&set, set.num_records, |n| {
+                    Epoch::from_et_seconds(set.epoch_data[n])
+                })
+            }
+            dtype => Err(DAFError::UnsupportedDatatype {
+                dtype,
+                kind: "segment records",
+            }),
+        }
+    }
+
+    fn dataset<S: NAIFDataSet<'a>>(&self) -> Result<S, DAFError> {
+        S::from_f64_slice(self.data).context(DecodingDataSnafu {
+            kind: R::NAME,
+            idx: self.idx,
+        })
+    }
+
+    fn nodes<S, F>(
+        &self,
+        set: &S,
+        num_records: usize,
+        epoch: F,
+    ) -> Result<Vec<SegmentRecord>, DAFError>
+    where
+        S: NAIFDataSet<'a, RecordKind = PositionVelocityRecord>,
+        F: Fn(usize) -> Epoch,
+    {
+        (0..num_records)
+            .map(|n| {
+                let (position_km, velocity_km_s) = set
+                    .nth_record(n)
+                    .context(DecodingDataSnafu {
+                        kind: R::NAME,
+                        idx: self.idx,
+                    })?
+                    .to_pos_vel();
+                Ok(SegmentRecord::Node {
+                    epoch: epoch(n),
+                    position_km,
+                    velocity_km_s,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod ut_segment {
+    use super::SegmentRecord;
+    use crate::naif::daf::{DafDataType, NAIFSummaryRecord};
+    use crate::prelude::SPK;
+    use hifitime::TimeUnits;
+
+    #[test]
+    fn chebyshev_records() {
+        let spk = SPK::load("../data/de440s.bsp").unwrap();
+
+        let segment = spk.segment(301).unwrap();
+        assert_eq!(segment.data_type, DafDataType::Type2ChebyshevTriplet);
+        assert_eq!(segment.summary.center_id, 3);
+
+        let records = segment.records().unwrap();
+        assert_eq!(records.len(), segment.num_records().unwrap());
+        assert!(!records.is_empty());
+
+        for (n, record) in records.iter().enumerate() {
+            match record {
+                SegmentRecord::Chebyshev { coefficients, .. } => {
+                    assert_eq!(coefficients.len(), 3);
+                    assert!(coefficients
+                        .iter()
+                        .all(|c| c.len() == coefficients[0].len()));
+                }
+                _ => panic!("expected Chebyshev records"),
+            }
+            if n > 0 {
+                assert!(records[n - 1].epoch() < record.epoch());
+            }
+        }
+
+        assert!(spk.segment(-1).is_err());
+    }
+
+    #[test]
+    fn hermite_nodes() {
+        let spk = SPK::load("../data/gmat-hermite.bsp").unwrap();
+
+        let segment = spk.nth_segment(0).unwrap();
+        assert_eq!(segment.data_type, DafDataType::Type13HermiteUnequalStep);
+
+        let records = segment.records().unwrap();
+        assert!(!records.is_empty());
+        assert_eq!(records.len(), segment.num_records().unwrap());
+        // The nodes span the coverage of the segment.
+        assert!((records[0].epoch() - segment.summary.start_epoch()).abs() < 1_i64.seconds());
+        assert!(
+            (records.last().unwrap().epoch() - segment.summary.end_epoch()).abs() < 1_i64.seconds()
+        );
+        assert!(records
+            .iter()
+            .all(|record| matches!(record, SegmentRecord::Node { .. })));
+    }
+}
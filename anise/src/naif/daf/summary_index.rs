@@ -0,0 +1,177 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::collections::HashMap;
+
+use hifitime::{Duration, Epoch};
+
+use super::NAIFSummaryRecord;
+use crate::NaifId;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Interval {
+    start: Epoch,
+    end: Epoch,
+    /// Latest end epoch of this interval and of all of the intervals which start before it
+    max_end: Epoch,
+    /// Index of the summary in the DAF
+    idx: usize,
+}
+
+/// Index of the coverage of the segments of a DAF, used to find the segments covering an epoch without scanning all of
+/// the summaries, e.g. for files with hundreds of short segments of the same body.
+///
+/// For each ID, the intervals are sorted by start epoch and augmented with the running maximum of their end epochs, so
+/// a query is a binary search followed by a scan of the intervals which may still cover the epoch: for disjoint
+/// segments, this is a single interval.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SummaryIndex {
+    intervals: HashMap<NaifId, Vec<Interval>>,
+}
+
+impl SummaryIndex {
+    /// Builds the index of the provided summaries and their indexes in the DAF, skipping empty summaries.
+    pub fn new<'a, R: NAIFSummaryRecord + 'a>(
+        summaries: impl Iterator<Item = (usize, &'a R)>,
+    ) -> Self {
+        let mut intervals: HashMap<NaifId, Vec<Interval>> = HashMap::new();
+        for (idx, summary) in summaries.filter(|(_, summary)| !summary.is_empty()) {
+            let (start, end) = (summary.start_epoch(), summary.end_epoch());
+            intervals.entry(summary.id()).or_default().push(Interval {
+                start,
+                end,
+                max_end: end,
+                idx,
+            });
+        }
+
+        for list in intervals.values_mut() {
+            list.sort_by(|a, b| a.start.cmp(&b.start).then(a.idx.cmp(&b.idx)));
+            let mut max_end = list[0].end;
+            for interval in list.iter_mut() {
+                max_end = max_end.max(interval.end);
+                interval.max_end = max_end;
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// Returns the number of indexed summaries
+    pub fn len(&self) -> usize {
+        self.intervals.values().map(|list| list.len()).sum()
+    }
+
+    /// Returns true if no summary is indexed
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns true if at least one summary of this ID is indexed, regardless of its coverage
+    pub fn contains_id(&self, id: NaifId) -> bool {
+        self.intervals.contains_key(&id)
+    }
+
+    /// Returns the lowest index of the summaries of this ID which cover the epoch, within the tolerance.
+    pub fn first_covering(&self, id: NaifId, epoch: Epoch, tolerance: Duration) -> Option<usize> {
+        self.covering(id, epoch, tolerance).min()
+    }
+
+    /// Returns the highest index of the summaries of this ID which cover the epoch, within the tolerance.
+    pub fn last_covering(&self, id: NaifId, epoch: Epoch, tolerance: Duration) -> Option<usize> {
+        self.covering(id, epoch, tolerance).max()
+    }
+
+    /// Returns the indexes of all of the summaries of this ID which cover the epoch, within the tolerance.
+    fn covering(
+        &self,
+        id: NaifId,
+        epoch: Epoch,
+        tolerance: Duration,
+    ) -> impl Iterator<Item = usize> + '_ {
+        let list = self
+            .intervals
+            .get(&id)
+            .map(|list| list.as_slice())
+            .unwrap_or_default();
+
+        // All of the intervals after this one start after the epoch.
+        let num_started = list.partition_point(|interval| interval.start - tolerance <= epoch);
+
+        list[..num_started]
+            .iter()
+            .rev()
+            // The running maximum decreases when going backward, so no earlier interval covers the epoch either.
+            .take_while(move |interval| interval.max_end + tolerance >= epoch)
+            .filter(move |interval| interval.end + tolerance >= epoch)
+            .map(|interval| interval.idx)
+    }
+}
+
+#[cfg(test)]
+mod ut_summary_index {
+    use super::SummaryIndex;
+    use crate::naif::spk::summary::SPKSummaryRecord;
+    use hifitime::{Duration, Epoch, Unit};
+
+    fn summary(target_id: i32, start: f64, end: f64) -> SPKSummaryRecord {
+        SPKSummaryRecord {
+            start_epoch_et_s: start,
+            end_epoch_et_s: end,
+            target_id,
+            start_idx: 1,
+            end_idx: 2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn overlapping_intervals() {
+        let summaries = [
+            summary(-10, 0.0, 100.0),
+            summary(-10, 10.0, 20.0),
+            summary(-20, 0.0, 100.0),
+            summary(-10, 30.0, 40.0),
+            summary(-10, 35.0, 50.0),
+            // Empty summaries are not indexed
+            SPKSummaryRecord::default(),
+        ];
+
+        let index = SummaryIndex::new(summaries.iter().enumerate());
+        assert_eq!(index.len(), 5);
+        assert!(index.contains_id(-20));
+        assert!(!index.contains_id(0));
+
+        let at = |et_s: f64| Epoch::from_et_seconds(et_s);
+        let zero = Duration::ZERO;
+
+        assert_eq!(index.first_covering(-10, at(15.0), zero), Some(0));
+        assert_eq!(index.last_covering(-10, at(15.0), zero), Some(1));
+        assert_eq!(index.last_covering(-10, at(25.0), zero), Some(0));
+        assert_eq!(index.last_covering(-10, at(37.0), zero), Some(4));
+        assert_eq!(index.last_covering(-20, at(37.0), zero), Some(2));
+        assert_eq!(index.first_covering(-10, at(100.5), zero), None);
+        assert_eq!(index.first_covering(-30, at(10.0), zero), None);
+
+        // Tolerance on both bounds
+        assert_eq!(
+            index.first_covering(-10, at(100.0) + Unit::Nanosecond * 50, zero),
+            None
+        );
+        assert_eq!(
+            index.first_covering(
+                -10,
+                at(100.0) + Unit::Nanosecond * 50,
+                Unit::Nanosecond * 100
+            ),
+            Some(0)
+        );
+    }
+}
@@ -0,0 +1,227 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::io::BufRead;
+
+use hifitime::{Epoch, TimeScale, Unit as TimeUnit};
+use snafu::{ResultExt, Snafu};
+
+use super::parser::KPLError;
+use super::pool::KernelPool;
+
+#[derive(Debug, Snafu, PartialEq)]
+#[snafu(visibility(pub(crate)))]
+pub enum LSKError {
+    #[snafu(display("leap second kernel is missing `{keyword}`"))]
+    MissingVariable { keyword: &'static str },
+    #[snafu(display("invalid leap second kernel: {reason}"))]
+    InvalidKernel { reason: String },
+    #[snafu(display("leap second kernel has a syntax error at {source}"))]
+    Syntax { source: KPLError },
+}
+
+/// A leap second kernel (LSK) as published by NAIF, e.g. `naif0012.tls`.
+///
+/// Hifitime has its own table of leap seconds, which may transiently differ from that of NAIF when a leap second is announced.
+/// This kernel converts between UTC and the other time scales exactly as SPICE does with the same LSK.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LeapSecondsKernel {
+    /// TAI - UTC (s) and the UTC date (as a TAI epoch at midnight of that calendar date) from which it applies, sorted by date
+    pub delta_at: Vec<(f64, Epoch)>,
+    /// `DELTET/DELTA_T_A`, i.e. TT - TAI (s)
+    pub delta_t_a: f64,
+    /// `DELTET/K`, amplitude (s) of the periodic term of ET - TT
+    pub k: f64,
+    /// `DELTET/EB`, eccentricity of the heliocentric orbit of the Earth-Moon barycenter
+    pub eb: f64,
+    /// `DELTET/M`, mean anomaly (rad) of the Earth-Moon barycenter at J2000 and its rate (rad/s)
+    pub m: [f64; 2],
+}
+
+impl LeapSecondsKernel {
+    /// Parses a leap second kernel.
+    pub fn parse<R: BufRead>(reader: &mut R) -> Result<Self, LSKError> {
+        Self::from_pool(&KernelPool::parse(reader).context(SyntaxSnafu)?)
+    }
+
+    /// Builds the leap second kernel from the `DELTET/` variables of the kernel pool.
+    pub fn from_pool(pool: &KernelPool) -> Result<Self, LSKError> {
+        let number = |keyword: &'static str| {
+            pool.get_f64(keyword)
+                .ok_or(LSKError::MissingVariable { keyword })
+        };
+
+        let m = match pool.get_f64s("DELTET/M") {
+            Some([m0, m1]) => [*m0, *m1],
+            _ => {
+                return Err(LSKError::MissingVariable {
+                    keyword: "DELTET/M",
+                })
+            }
+        };
+
+        // The table alternates the offsets and the dates, e.g. `10, @1972-JAN-1`, where the dates are parsed as seconds past
+        // J2000 in the formal calendar.
+        let items = pool
+            .get_f64s("DELTET/DELTA_AT")
+            .ok_or(LSKError::MissingVariable {
+                keyword: "DELTET/DELTA_AT",
+            })?;
+        if items.is_empty() || items.len() % 2 != 0 {
+            return Err(LSKError::InvalidKernel {
+                reason: format!(
+                    "`DELTET/DELTA_AT` must be pairs of offsets and dates but has {} items",
+                    items.len()
+                ),
+            });
+        }
+
+        // The dates are calendar dates, so they are counted from the calendar date of J2000 in TAI.
+        let j2000 = Epoch::from_gregorian_tai_at_noon(2000, 1, 1);
+        let mut delta_at = items
+            .chunks_exact(2)
+            .map(|pair| (pair[0], j2000 + pair[1] * TimeUnit::Second))
+            .collect::<Vec<(f64, Epoch)>>();
+        delta_at.sort_by(|a, b| a.1.cmp(&b.1));
+
+        Ok(Self {
+            delta_at,
+            delta_t_a: number("DELTET/DELTA_T_A")?,
+            k: number("DELTET/K")?,
+            eb: number("DELTET/EB")?,
+            m,
+        })
+    }
+
+    /// Returns TAI - UTC (s) at the provided epoch. Before the first date of the table, its first offset is used, as in SPICE.
+    pub fn delta_at_s(&self, epoch: Epoch) -> f64 {
+        // A leap second applies from the midnight UTC of its date, i.e. that calendar date in TAI plus the new offset.
+        self.delta_at
+            .iter()
+            .rev()
+            .find(|(offset, date)| epoch >= *date + *offset * TimeUnit::Second)
+            .or(self.delta_at.first())
+            .map(|(offset, _)| *offset)
+            .unwrap_or(0.0)
+    }
+
+    /// Returns ET - UTC (s) at the provided epoch, i.e. the `DELTET` routine of SPICE.
+    pub fn delta_et_s(&self, epoch: Epoch) -> f64 {
+        let mean_anomaly = self.m[0] + self.m[1] * epoch.to_et_seconds();
+        let eccentric_anomaly = mean_anomaly + self.eb * mean_anomaly.sin();
+        self.delta_at_s(epoch) + self.delta_t_a + self.k * eccentric_anomaly.sin()
+    }
+
+    /// Returns the epoch of the provided UTC calendar date, using the leap seconds of this kernel.
+    #[allow(clippy::too_many_arguments)]
+    pub fn epoch_from_gregorian_utc(
+        &self,
+        year: i32,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        nanos: u32,
+    ) -> Epoch {
+        let calendar = Epoch::from_gregorian(
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            nanos,
+            TimeScale::TAI,
+        );
+        // The calendar date is compared to the dates of the table, which are calendar dates too.
+        let offset = self
+            .delta_at
+            .iter()
+            .rev()
+            .find(|(_, date)| calendar >= *date)
+            .or(self.delta_at.first())
+            .map(|(offset, _)| *offset)
+            .unwrap_or(0.0);
+        calendar + offset * TimeUnit::Second
+    }
+
+    /// Returns the UTC calendar date of the provided epoch, using the leap seconds of this kernel.
+    pub fn to_gregorian_utc(&self, epoch: Epoch) -> (i32, u8, u8, u8, u8, u8, u32) {
+        (epoch - self.delta_at_s(epoch) * TimeUnit::Second).to_gregorian_tai()
+    }
+}
+
+#[cfg(test)]
+mod ut_lsk {
+    use super::{LSKError, LeapSecondsKernel};
+    use hifitime::{Epoch, Unit};
+
+    /// Excerpt of naif0012.tls, without the last leap second
+    const KERNEL: &str = "KPL/LSK
+
+\\begindata
+
+DELTET/DELTA_T_A       =   32.184
+DELTET/K               =    1.657D-3
+DELTET/EB              =    1.671D-2
+DELTET/M               = (  6.239996D0   1.99096871D-7 )
+
+DELTET/DELTA_AT        = ( 10,   @1972-JAN-1
+                           11,   @1972-JUL-1
+                           35,   @2012-JUL-1
+                           36,   @2015-JUL-1 )
+
+\\begintext
+";
+
+    #[test]
+    fn parse_lsk() {
+        let lsk = LeapSecondsKernel::parse(&mut KERNEL.as_bytes()).unwrap();
+        assert_eq!(lsk.delta_at.len(), 4);
+        assert_eq!(lsk.delta_t_a, 32.184);
+        assert_eq!(lsk.m, [6.239996, 1.99096871e-7]);
+
+        // Without the leap second of 2017, this LSK disagrees with hifitime by one second since then.
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2020, 1, 1);
+        assert_eq!(lsk.delta_at_s(epoch), 36.0);
+        assert_eq!(epoch.leap_seconds(true), Some(37.0));
+        assert_eq!(
+            lsk.epoch_from_gregorian_utc(2020, 1, 1, 0, 0, 0, 0),
+            epoch - Unit::Second
+        );
+        assert_eq!(
+            lsk.to_gregorian_utc(epoch - Unit::Second),
+            (2020, 1, 1, 0, 0, 0, 0)
+        );
+
+        // But they agree before then.
+        let epoch = Epoch::from_gregorian_utc_hms(2016, 3, 14, 15, 9, 26);
+        assert_eq!(
+            lsk.epoch_from_gregorian_utc(2016, 3, 14, 15, 9, 26, 0),
+            epoch
+        );
+        assert_eq!(lsk.to_gregorian_utc(epoch), (2016, 3, 14, 15, 9, 26, 0));
+        // ET - UTC is the leap seconds, TT - TAI, and a periodic term of at most K.
+        assert!((lsk.delta_et_s(epoch) - (36.0 + 32.184)).abs() <= 1.657e-3);
+
+        // The leap second applies from midnight UTC.
+        let leap = lsk.epoch_from_gregorian_utc(2015, 7, 1, 0, 0, 0, 0);
+        assert_eq!(lsk.delta_at_s(leap), 36.0);
+        assert_eq!(lsk.delta_at_s(leap - Unit::Second * 1.5), 35.0);
+
+        assert_eq!(
+            LeapSecondsKernel::parse(&mut "KPL/LSK\n\\begindata\nDELTET/K = 1.657D-3\n".as_bytes()),
+            Err(LSKError::MissingVariable {
+                keyword: "DELTET/M"
+            })
+        );
+    }
+}
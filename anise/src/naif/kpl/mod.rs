@@ -18,7 +18,10 @@ use self::parser::Assignment;
 
 pub mod fk;
 
+pub mod lsk;
 pub mod parser;
+pub mod pool;
+pub mod sclk;
 pub mod tpc;
 
 pub trait KPLItem: Debug + Default {
@@ -34,6 +37,8 @@ pub enum KPLValue {
     Float(f64),
     Matrix(Vec<f64>),
     String(String),
+    /// Vector of strings, e.g. `( 'EARTH_FIXED', 'ITRF93' )`
+    Strings(Vec<String>),
     Integer(i32),
 }
 
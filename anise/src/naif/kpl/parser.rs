@@ -16,20 +16,24 @@ use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
+use hifitime::{Epoch, TimeScale, Unit as TimeUnit};
 use log::{error, info, warn};
+use snafu::Snafu;
 
-use crate::constants::orientations::J2000;
+use crate::constants::orientations::{id_to_orientation_name, GALACTIC, J2000, MARSIAU};
 use crate::math::rotation::{r1, r2, r3, Quaternion, DCM};
 use crate::math::Matrix3;
 use crate::naif::kpl::fk::FKItem;
 use crate::naif::kpl::tpc::TPCItem;
 use crate::naif::kpl::Parameter;
 use crate::structure::dataset::{DataSetError, DataSetType};
+use crate::structure::lookuptable::KEY_NAME_LEN;
 use crate::structure::metadata::Metadata;
 use crate::structure::planetocentric::ellipsoid::Ellipsoid;
 use crate::structure::planetocentric::phaseangle::PhaseAngle;
 use crate::structure::planetocentric::{PlanetaryData, MAX_NUT_PREC_ANGLES};
 use crate::structure::{EulerParameterDataSet, PlanetaryDataSet};
+use crate::NaifId;
 
 use super::{KPLItem, KPLValue};
 
@@ -47,104 +51,670 @@ pub struct Assignment {
 
 impl Assignment {
     pub fn to_value(&self) -> KPLValue {
-        let value = &self.value;
-        // Sanitize the input
-        let value = value.
-            // Remove parentheses
-            // Convert remove the extra single quotes
-            // there usually aren't commas, only sometimes
-            replace(['(', ')', ',', '\''], "");
-
-        let vec: Vec<&str> = value.split_whitespace().filter(|s| !s.is_empty()).collect();
-        // If there are multiple items, we assume this is a vector
-        if vec.len() > 1 {
+        let items = match tokenize_line(&self.value, 0) {
+            Ok(tokens) => tokens
+                .into_iter()
+                .filter_map(|(token, _)| match token {
+                    Token::Quoted(text) => Some(Ok(text)),
+                    Token::Word(word) => Some(Err(word)),
+                    _ => None,
+                })
+                .collect::<Vec<Result<String, String>>>(),
+            Err(_) => return KPLValue::String(self.value.clone()),
+        };
+
+        if items.is_empty() {
+            // Return the original value as a string
+            KPLValue::String(self.value.clone())
+        } else if items.iter().all(|item| item.is_ok()) {
+            let mut strings = items.into_iter().flatten().collect::<Vec<String>>();
+            if strings.len() == 1 {
+                KPLValue::String(strings.remove(0))
+            } else {
+                KPLValue::Strings(strings)
+            }
+        } else if items.len() > 1 {
+            // If there are multiple items, we assume this is a vector
             KPLValue::Matrix(
-                vec.iter()
-                    .map(|s| s.parse::<f64>().unwrap_or(0.0))
+                items
+                    .iter()
+                    .map(|item| match item {
+                        Err(word) => parse_kpl_number(word).unwrap_or(0.0),
+                        Ok(_) => 0.0,
+                    })
                     .collect(),
             )
-        } else if vec.is_empty() {
-            // Return the original value as a string
-            KPLValue::String(self.value.clone())
         } else {
+            let word = items[0].as_ref().unwrap_err();
             // We have exactly one item, let's try to convert it as an integer first
-            if let Ok(as_int) = vec[0].parse::<i32>() {
+            if let Ok(as_int) = word.parse::<i32>() {
                 KPLValue::Integer(as_int)
-            } else if let Ok(as_f64) = vec[0].trim().replace("D", "E").parse::<f64>() {
+            } else if let Some(as_f64) = parse_kpl_number(word) {
                 KPLValue::Float(as_f64)
             } else {
                 // Darn, let's default to string
-                KPLValue::String(value.clone())
+                KPLValue::String(word.clone())
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Snafu, PartialEq)]
+#[snafu(visibility(pub(crate)))]
+pub enum KPLError {
+    /// Syntax error in the data blocks of a text kernel, where the line and column start at one.
+    #[snafu(display("line {line}, column {column}: {reason}"))]
+    Syntax {
+        line: usize,
+        column: usize,
+        reason: String,
+    },
+}
+
+/// Parses a KPL double, which may use Fortran's `D` exponent (e.g. `-1.4D-12`).
+pub(crate) fn parse_kpl_f64(value: &str) -> Option<f64> {
+    value
+        .parse::<f64>()
+        .or_else(|_| value.replace(['D', 'd'], "E").parse::<f64>())
+        .ok()
+}
+
+/// Parses a KPL number, i.e. a double or an `@` date.
+fn parse_kpl_number(value: &str) -> Option<f64> {
+    if value.starts_with('@') {
+        parse_kpl_date(value)
+    } else {
+        parse_kpl_f64(value)
+    }
+}
+
+/// Parses an `@` date of a text kernel (e.g. `@1972-JAN-1` or `@2025-01-01/00:00:00.00`) into the number of seconds past
+/// J2000 in the formal calendar, i.e. without leap seconds or any time system, as SPICE does.
+///
+/// The calendar date may be written as `YYYY-MON-DD`, `YYYY-MM-DD`, `DD-MON-YYYY`, `MON-DD-YYYY`, or `YYYY-DOY`, and may be
+/// followed by a time of day `HH:MM[:SS.S]` after a `/` or a `T`.
+pub(crate) fn parse_kpl_date(date: &str) -> Option<f64> {
+    let date = date.strip_prefix('@')?;
+
+    let time_sep = date.find('/').or_else(|| {
+        date.char_indices()
+            .skip(1)
+            .find(|(i, c)| *c == 'T' && date.as_bytes()[i - 1].is_ascii_digit())
+            .map(|(i, _)| i)
+    });
+    let (day_part, time_part) = match time_sep {
+        Some(i) => (&date[..i], Some(&date[i + 1..])),
+        None => (date, None),
+    };
+
+    let month = |month: &str| -> Option<u8> {
+        match month.parse::<u8>() {
+            Ok(month) => Some(month),
+            Err(_) => MONTHS
+                .iter()
+                .position(|name| {
+                    month
+                        .get(..3)
+                        .is_some_and(|month| name.eq_ignore_ascii_case(month))
+                })
+                .map(|idx| idx as u8 + 1),
+        }
+    };
+    let is_year = |item: &str| item.len() >= 3 && item.chars().all(|c| c.is_ascii_digit());
+
+    let parts = day_part.split('-').collect::<Vec<&str>>();
+    let start_of_day = match parts[..] {
+        [year, day_of_year] if is_year(year) => {
+            let day_of_year = day_of_year.parse::<u16>().ok()?;
+            if !(1..=366).contains(&day_of_year) {
+                return None;
             }
+            Epoch::maybe_from_gregorian(year.parse().ok()?, 1, 1, 0, 0, 0, 0, TimeScale::TAI)
+                .ok()?
+                + f64::from(day_of_year - 1) * TimeUnit::Day
         }
+        [first, second, third] => {
+            let (year, month, day) = if is_year(first) {
+                (first, month(second)?, third)
+            } else if first.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                (third, month(first)?, second)
+            } else {
+                (third, month(second)?, first)
+            };
+            Epoch::maybe_from_gregorian(
+                year.parse().ok()?,
+                month,
+                day.parse().ok()?,
+                0,
+                0,
+                0,
+                0,
+                TimeScale::TAI,
+            )
+            .ok()?
+        }
+        _ => return None,
+    };
+
+    let mut seconds_of_day = 0.0;
+    if let Some(time) = time_part {
+        let items = time.split(':').collect::<Vec<&str>>();
+        if !(2..=3).contains(&items.len()) {
+            return None;
+        }
+        let hours = items[0].parse::<u8>().ok().filter(|h| *h < 24)?;
+        let minutes = items[1].parse::<u8>().ok().filter(|m| *m < 60)?;
+        let seconds = match items.get(2) {
+            Some(seconds) => seconds
+                .parse::<f64>()
+                .ok()
+                .filter(|s| (0.0..61.0).contains(s))?,
+            None => 0.0,
+        };
+        seconds_of_day = f64::from(hours) * 3600.0 + f64::from(minutes) * 60.0 + seconds;
     }
+
+    Some(
+        (start_of_day - Epoch::from_gregorian_tai_at_noon(2000, 1, 1)).to_seconds()
+            + seconds_of_day,
+    )
 }
 
+/// Abbreviations of the months in the `@` dates
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
 pub fn parse_file<P: AsRef<Path> + fmt::Debug, I: KPLItem>(
     file_path: P,
     show_comments: bool,
 ) -> Result<HashMap<i32, I>, DataSetError> {
-    let file =
-        File::open(&file_path).unwrap_or_else(|_| panic!("Failed to open file {file_path:?}"));
+    let file = File::open(&file_path).map_err(|e| DataSetError::Conversion {
+        action: format!("opening {file_path:?}: {e}"),
+    })?;
     let mut reader = BufReader::new(file);
-    parse_bytes(&mut reader, show_comments)
+    let assignments =
+        parse_assignments(&mut reader, show_comments).map_err(|e| DataSetError::Conversion {
+            action: format!("parsing {file_path:?} at {e}"),
+        })?;
+    Ok(collect_items(assignments))
 }
 
 pub fn parse_bytes<R: BufRead, I: KPLItem>(
     reader: &mut R,
     show_comments: bool,
 ) -> Result<HashMap<i32, I>, DataSetError> {
+    let assignments =
+        parse_assignments(reader, show_comments).map_err(|e| DataSetError::Conversion {
+            action: format!("parsing text kernel at {e}"),
+        })?;
+    Ok(collect_items(assignments))
+}
+
+/// Parses all of the assignments and puts them into a pretty hash map.
+fn collect_items<I: KPLItem>(assignments: Vec<Assignment>) -> HashMap<i32, I> {
+    let mut map = HashMap::new();
+    for item in assignments {
+        let key = I::extract_key(&item);
+        if key == -1 {
+            // This is metadata
+            continue;
+        }
+        map.entry(key).or_insert_with(|| I::default());
+        let body_map = map.get_mut(&key).unwrap();
+        body_map.parse(item);
+    }
+    map
+}
+
+/// Token of the data blocks of a text kernel
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// `=`
+    Assign,
+    /// `+=`
+    Append,
+    Open,
+    Close,
+    Comma,
+    /// A string, without its quotes and where the doubled quotes are unescaped
+    Quoted(String),
+    /// A variable name, a number, or an `@` date
+    Word(String),
+}
+
+/// Splits a line of a data block into its tokens and their columns.
+fn tokenize_line(line: &str, line_no: usize) -> Result<Vec<(Token, usize)>, KPLError> {
+    let chars = line.chars().collect::<Vec<char>>();
+    let is_append = |i: usize| chars[i] == '+' && chars.get(i + 1) == Some(&'=');
+
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let column = i + 1;
+        let token = match chars[i] {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '=' => Token::Assign,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            ',' => Token::Comma,
+            _ if is_append(i) => {
+                i += 1;
+                Token::Append
+            }
+            '\'' => {
+                let mut text = String::new();
+                loop {
+                    i += 1;
+                    match chars.get(i) {
+                        Some('\'') if chars.get(i + 1) == Some(&'\'') => {
+                            text.push('\'');
+                            i += 1;
+                        }
+                        Some('\'') => break,
+                        Some(c) => text.push(*c),
+                        None => {
+                            return Err(KPLError::Syntax {
+                                line: line_no,
+                                column,
+                                reason: "unterminated string: strings cannot span several lines, use `//` to continue them in the next string".to_string(),
+                            })
+                        }
+                    }
+                }
+                Token::Quoted(text)
+            }
+            _ => {
+                let start = i;
+                while i + 1 < chars.len()
+                    && !chars[i + 1].is_whitespace()
+                    && !matches!(chars[i + 1], '=' | '(' | ')' | ',' | '\'')
+                    && !is_append(i + 1)
+                {
+                    i += 1;
+                }
+                Token::Word(chars[start..=i].iter().collect())
+            }
+        };
+        tokens.push((token, column));
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+/// Item of the value of an assignment
+#[derive(Clone, Debug, PartialEq)]
+enum ValueItem {
+    /// A number, as written in the kernel unless it is a date, which is converted to seconds past J2000
+    Number(String),
+    Text(String),
+}
+
+/// State of the parser of the assignments
+enum ParserState {
+    Keyword,
+    Operator {
+        keyword: String,
+    },
+    Value {
+        keyword: String,
+        append: bool,
+    },
+    Vector {
+        keyword: String,
+        append: bool,
+        items: Vec<ValueItem>,
+        line: usize,
+        column: usize,
+    },
+}
+
+/// Returns all of the assignments of the data blocks of a text kernel, in order.
+///
+/// This supports the full grammar of the text kernels: values may be scalars or vectors spanning several lines, numbers may
+/// use Fortran's `D` exponent, `@` dates are converted to seconds past J2000, strings ending with `//` are continued by the
+/// next string of the same value, and `+=` appends the values to the previous assignment of that variable. The
+/// `\begindata` and `\begintext` markers must be alone on their line, such that they may be quoted in the comments.
+pub fn parse_assignments<R: BufRead>(
+    reader: &mut R,
+    show_comments: bool,
+) -> Result<Vec<Assignment>, KPLError> {
     let mut block_type = BlockType::Comment;
-    let mut assignments = vec![];
+    // Each variable is assigned as a vector (or a scalar) of items.
+    let mut assignments: Vec<(String, Vec<ValueItem>, bool)> = vec![];
+    let mut state = ParserState::Keyword;
+    let mut line_no = 0;
 
     for line in reader.lines() {
+        line_no += 1;
         let line = match line {
             Ok(l) => l,
             Err(_) => continue, // skip lines that can't be read (invalid UTF-8)
         };
         let tline = line.trim();
 
-        if tline.starts_with("\\begintext") {
+        if tline == "\\begintext" || tline == "\\begindata" {
+            if let ParserState::Operator { keyword }
+            | ParserState::Value { keyword, .. }
+            | ParserState::Vector { keyword, .. } = &state
+            {
+                return Err(KPLError::Syntax {
+                    line: line_no,
+                    column: line.find('\\').unwrap() + 1,
+                    reason: format!("assignment of `{keyword}` is not complete"),
+                });
+            }
+            block_type = if tline == "\\begintext" {
+                BlockType::Comment
+            } else {
+                BlockType::Data
+            };
+            continue;
+        }
+
+        if block_type == BlockType::Comment {
+            if show_comments {
+                println!("{line}");
+            }
+            continue;
+        }
+
+        for (token, column) in tokenize_line(&line, line_no)? {
+            let error = |reason: String| KPLError::Syntax {
+                line: line_no,
+                column,
+                reason,
+            };
+
+            state = match (state, token) {
+                (ParserState::Keyword, Token::Word(keyword)) => ParserState::Operator { keyword },
+                (ParserState::Keyword, token) => {
+                    return Err(error(format!("expected a variable name, found {token}")))
+                }
+                (ParserState::Operator { keyword }, Token::Assign) => ParserState::Value {
+                    keyword,
+                    append: false,
+                },
+                (ParserState::Operator { keyword }, Token::Append) => ParserState::Value {
+                    keyword,
+                    append: true,
+                },
+                (ParserState::Operator { keyword }, token) => {
+                    return Err(error(format!(
+                        "expected `=` or `+=` after `{keyword}`, found {token}"
+                    )))
+                }
+                (ParserState::Value { keyword, append }, Token::Open) => ParserState::Vector {
+                    keyword,
+                    append,
+                    items: vec![],
+                    line: line_no,
+                    column,
+                },
+                (ParserState::Value { keyword, append }, token) => {
+                    let item = value_item(token).map_err(error)?;
+                    add_assignment(&mut assignments, keyword, vec![item], append).map_err(error)?;
+                    ParserState::Keyword
+                }
+                (
+                    ParserState::Vector {
+                        keyword,
+                        append,
+                        items,
+                        line,
+                        column,
+                    },
+                    Token::Comma,
+                ) => ParserState::Vector {
+                    keyword,
+                    append,
+                    items,
+                    line,
+                    column,
+                },
+                (
+                    ParserState::Vector {
+                        keyword,
+                        append,
+                        items,
+                        line,
+                        column,
+                    },
+                    Token::Close,
+                ) => {
+                    add_assignment(&mut assignments, keyword, items, append).map_err(|reason| {
+                        KPLError::Syntax {
+                            line,
+                            column,
+                            reason,
+                        }
+                    })?;
+                    ParserState::Keyword
+                }
+                (
+                    ParserState::Vector {
+                        keyword,
+                        append,
+                        mut items,
+                        line,
+                        column: vector_column,
+                    },
+                    token,
+                ) => {
+                    items.push(value_item(token).map_err(error)?);
+                    ParserState::Vector {
+                        keyword,
+                        append,
+                        items,
+                        line,
+                        column: vector_column,
+                    }
+                }
+            };
+        }
+    }
+
+    match state {
+        ParserState::Keyword => Ok(assignments
+            .into_iter()
+            .map(|(keyword, items, _)| Assignment {
+                keyword,
+                value: format_value(&items),
+            })
+            .collect()),
+        ParserState::Vector {
+            keyword,
+            line,
+            column,
+            ..
+        } => Err(KPLError::Syntax {
+            line,
+            column,
+            reason: format!("vector of `{keyword}` is not closed"),
+        }),
+        ParserState::Operator { keyword } | ParserState::Value { keyword, .. } => {
+            Err(KPLError::Syntax {
+                line: line_no,
+                column: 1,
+                reason: format!("assignment of `{keyword}` is not complete"),
+            })
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Assign => write!(f, "`=`"),
+            Self::Append => write!(f, "`+=`"),
+            Self::Open => write!(f, "`(`"),
+            Self::Close => write!(f, "`)`"),
+            Self::Comma => write!(f, "`,`"),
+            Self::Quoted(text) => write!(f, "string '{text}'"),
+            Self::Word(word) => write!(f, "`{word}`"),
+        }
+    }
+}
+
+/// Converts a token of a value into its item, or returns the reason why it is not a value.
+fn value_item(token: Token) -> Result<ValueItem, String> {
+    match token {
+        Token::Quoted(text) => Ok(ValueItem::Text(text)),
+        Token::Word(word) if word.starts_with('@') => match parse_kpl_date(&word) {
+            Some(seconds) => Ok(ValueItem::Number(format!("{seconds:?}"))),
+            None => Err(format!("invalid date `{word}`")),
+        },
+        Token::Word(word) if parse_kpl_f64(&word).is_some() => Ok(ValueItem::Number(word)),
+        token => Err(format!(
+            "expected a number, a quoted string, or an @ date, found {token}"
+        )),
+    }
+}
+
+/// Adds the assignment of this variable, or appends its items to the previous assignment of this variable.
+///
+/// Strings ending with `//` are joined with the next string, as in SPICE's `STPOOL`.
+fn add_assignment(
+    assignments: &mut Vec<(String, Vec<ValueItem>, bool)>,
+    keyword: String,
+    items: Vec<ValueItem>,
+    append: bool,
+) -> Result<(), String> {
+    let is_text = items.first().map(|item| matches!(item, ValueItem::Text(_)));
+    if items
+        .iter()
+        .any(|item| Some(matches!(item, ValueItem::Text(_))) != is_text)
+    {
+        return Err(format!("`{keyword}` mixes strings and numbers"));
+    }
+    let is_text = is_text.unwrap_or(false);
+
+    let mut joined: Vec<ValueItem> = Vec::with_capacity(items.len());
+    for item in items {
+        match (joined.last_mut(), item) {
+            (Some(ValueItem::Text(prev)), ValueItem::Text(text)) if prev.ends_with("//") => {
+                prev.truncate(prev.len() - 2);
+                prev.push_str(&text);
+            }
+            (_, item) => joined.push(item),
+        }
+    }
+
+    if append {
+        if let Some((_, prev_items, prev_is_text)) = assignments
+            .iter_mut()
+            .rev()
+            .find(|(prev_keyword, _, _)| *prev_keyword == keyword)
+        {
+            if *prev_is_text != is_text && !prev_items.is_empty() && !joined.is_empty() {
+                return Err(format!(
+                    "cannot append {} to the {} of `{keyword}`",
+                    if is_text { "strings" } else { "numbers" },
+                    if *prev_is_text { "strings" } else { "numbers" }
+                ));
+            }
+            prev_items.extend(joined);
+            return Ok(());
+        }
+    }
+
+    assignments.push((keyword, joined, is_text));
+    Ok(())
+}
+
+/// Formats the items of a value such that [Assignment::to_value] parses them back: a single item is a scalar.
+fn format_value(items: &[ValueItem]) -> String {
+    let formatted = items
+        .iter()
+        .map(|item| match item {
+            ValueItem::Number(number) => number.clone(),
+            ValueItem::Text(text) => format!("'{}'", text.replace('\'', "''")),
+        })
+        .collect::<Vec<String>>();
+
+    match formatted.len() {
+        1 => formatted[0].clone(),
+        _ => format!("( {} )", formatted.join(" ")),
+    }
+}
+
+/// Returns whether this NAIF ID is that of an asteroid or a comet, or of one of their satellites.
+fn is_small_body(id: NaifId) -> bool {
+    id >= 1_000_000
+}
+
+/// Parses the `NAIF_BODY_NAME` and `NAIF_BODY_CODE` assignments of a text kernel, e.g. NAIF's `codes_300ast` frame kernel.
+///
+/// The names are paired with the codes in order of assignment, as in SPICE.
+pub fn parse_body_names<R: BufRead>(reader: &mut R) -> HashMap<NaifId, String> {
+    let mut block_type = BlockType::Comment;
+    let mut names = Vec::new();
+    let mut codes = Vec::new();
+    // Assignments may span several lines, so track which list is being assigned.
+    let mut current: Option<bool> = None;
+
+    for line in reader.lines() {
+        let line = match line {
+            Ok(l) => l,
+            Err(_) => continue, // skip lines that can't be read (invalid UTF-8)
+        };
+        let tline = line.trim();
+
+        if tline == "\\begintext" {
             block_type = BlockType::Comment;
             continue;
-        } else if tline.starts_with("\\begindata") {
+        } else if tline == "\\begindata" {
             block_type = BlockType::Data;
             continue;
+        } else if block_type == BlockType::Comment {
+            continue;
         }
 
-        if block_type == BlockType::Comment && show_comments {
-            println!("{line}");
-        } else if block_type == BlockType::Data {
-            let parts: Vec<&str> = line.split('=').map(|s| s.trim()).collect();
-            if parts.len() == 2 {
-                let keyword = parts[0];
-                let value = parts[1];
-                let assignment = Assignment {
-                    keyword: keyword.to_string(),
-                    value: value.to_string(),
+        let value = match tline.split_once('=') {
+            Some((keyword, value)) => {
+                let keyword = keyword.trim_end_matches('+').trim();
+                current = match keyword {
+                    "NAIF_BODY_NAME" => Some(true),
+                    "NAIF_BODY_CODE" => Some(false),
+                    _ => None,
                 };
-                assignments.push(assignment);
-            } else if let Some(mut assignment) = assignments.pop() {
-                // This is a continuation of the previous line, so let's grab the data and append the value we're reding now.
-                // We're adding the full line with all of the extra spaces because the parsing needs those delimiters to not bunch together all of the floats.
-                assignment.value += &line;
-                assignments.push(assignment);
+                value
             }
+            None => tline,
+        };
+
+        match current {
+            Some(true) => names.extend(
+                value
+                    .split('\'')
+                    .skip(1)
+                    .step_by(2)
+                    .map(|name| name.trim().to_string()),
+            ),
+            Some(false) => codes.extend(
+                value
+                    .replace(['(', ')', ','], " ")
+                    .split_whitespace()
+                    .filter_map(|code| code.parse::<NaifId>().ok()),
+            ),
+            None => {}
         }
     }
-    // Now let's parse all of the assignments and put it into a pretty hash map.
-    let mut map = HashMap::new();
-    for item in assignments {
-        let key = I::extract_key(&item);
-        if key == -1 {
-            // This is metadata
-            continue;
-        }
-        map.entry(key).or_insert_with(|| I::default());
-        let body_map = map.get_mut(&key).unwrap();
-        body_map.parse(item);
+
+    if names.len() != codes.len() {
+        warn!(
+            "{} body names but {} body codes: only pairing the first ones",
+            names.len(),
+            codes.len()
+        );
     }
-    Ok(map)
+
+    // Like in SPICE, the latest assignment of a code has precedence.
+    codes.into_iter().zip(names).collect()
 }
 
 /// Converts two KPL/TPC files, one defining the planetary constants as text, and the other defining the gravity parameters, into the PlanetaryDataSet equivalent ANISE file.
@@ -158,9 +728,36 @@ pub fn convert_tpc<P: AsRef<Path> + fmt::Debug>(
     convert_tpc_items(planetary_data, gravity_data)
 }
 
+/// Converts two KPL/TPC files, like [convert_tpc], and names each body from a text kernel of body names and codes,
+/// like NAIF's `codes_300ast` frame kernel for asteroids.
+pub fn convert_tpc_with_names<P: AsRef<Path> + fmt::Debug>(
+    pck: P,
+    gm: P,
+    names: P,
+) -> Result<PlanetaryDataSet, DataSetError> {
+    let gravity_data = parse_file::<_, TPCItem>(gm, false)?;
+    let planetary_data = parse_file::<_, TPCItem>(pck, false)?;
+    let file = File::open(&names).map_err(|e| DataSetError::Conversion {
+        action: format!("opening {names:?}: {e}"),
+    })?;
+    let body_names = parse_body_names(&mut BufReader::new(file));
+    convert_named_tpc_items(planetary_data, gravity_data, &body_names)
+}
+
 pub fn convert_tpc_items(
+    planetary_data: HashMap<i32, TPCItem>,
+    gravity_data: HashMap<i32, TPCItem>,
+) -> Result<PlanetaryDataSet, DataSetError> {
+    convert_named_tpc_items(planetary_data, gravity_data, &HashMap::new())
+}
+
+/// Converts the planetary and gravity data into a planetary dataset, where each body is also named if it is in `body_names`.
+///
+/// Small bodies (asteroids and comets) are added even if only their gravity data is known.
+pub fn convert_named_tpc_items(
     mut planetary_data: HashMap<i32, TPCItem>,
     gravity_data: HashMap<i32, TPCItem>,
+    body_names: &HashMap<NaifId, String>,
 ) -> Result<PlanetaryDataSet, DataSetError> {
     let mut dataset = PlanetaryDataSet::default();
 
@@ -169,6 +766,8 @@ pub fn convert_tpc_items(
             for (gk, gv) in value.data {
                 planet_data.data.insert(gk, gv);
             }
+        } else if is_small_body(key) {
+            planetary_data.insert(key, value);
         }
     }
 
@@ -212,6 +811,7 @@ pub fn convert_tpc_items(
                                     if let Some(coeffs) =
                                         planetary_data.data.get(&Parameter::NutPrecRa)
                                     {
+                                        pole_ra_data.resize(3, 0.0);
                                         pole_ra_data.extend(coeffs.to_vec_f64().unwrap());
                                     }
                                     let pola_ra = PhaseAngle::maybe_new(&pole_ra_data);
@@ -223,6 +823,7 @@ pub fn convert_tpc_items(
                                     if let Some(coeffs) =
                                         planetary_data.data.get(&Parameter::NutPrecDec)
                                     {
+                                        pola_dec_data.resize(3, 0.0);
                                         pola_dec_data.extend(coeffs.to_vec_f64().unwrap());
                                     }
                                     let pola_dec = PhaseAngle::maybe_new(&pola_dec_data);
@@ -234,6 +835,7 @@ pub fn convert_tpc_items(
                                     if let Some(coeffs) =
                                         planetary_data.data.get(&Parameter::NutPrecPm)
                                     {
+                                        prime_mer_data.resize(3, 0.0);
                                         prime_mer_data.extend(coeffs.to_vec_f64().unwrap());
                                     }
                                     let prime_mer = PhaseAngle::maybe_new(&prime_mer_data);
@@ -252,7 +854,9 @@ pub fn convert_tpc_items(
 
                                     PlanetaryData {
                                         object_id,
-                                        parent_id: if [199, 299].contains(&object_id) {
+                                        parent_id: if [199, 299].contains(&object_id)
+                                            || is_small_body(object_id)
+                                        {
                                             J2000
                                         } else if object_id > 100 {
                                             object_id / 100
@@ -288,16 +892,32 @@ pub fn convert_tpc_items(
                         {
                             let phase_deg =
                                 match planetary_data.data.get(&Parameter::MaxPhaseDegree) {
-                                    Some(val) => (val.to_i32().unwrap() + 1) as usize,
+                                    Some(val) => match val.to_i32() {
+                                        Ok(degree @ 1..=3) => (degree + 1) as usize,
+                                        _ => {
+                                            return Err(DataSetError::Conversion {
+                                                action: format!(
+                                                    "MAX_PHASE_DEGREE of {object_id} should be 1, 2, or 3, got {val:?}"
+                                                ),
+                                            })
+                                        }
+                                    },
                                     None => 2,
                                 };
                             let nut_prec_data = nut_prec_val.to_vec_f64().unwrap();
                             let mut coeffs = [PhaseAngle::<0>::default(); MAX_NUT_PREC_ANGLES];
                             let mut num = 0;
-                            for (i, nut_prec) in nut_prec_data.chunks(phase_deg).enumerate() {
+                            // The angles are polynomials of degree MAX_PHASE_DEGREE, e.g. quadratic for the Martian system, up to cubic.
+                            for (i, nut_prec) in nut_prec_data
+                                .chunks(phase_deg)
+                                .take(MAX_NUT_PREC_ANGLES)
+                                .enumerate()
+                            {
                                 coeffs[i] = PhaseAngle::<0> {
                                     offset_deg: nut_prec[0],
-                                    rate_deg: nut_prec[1],
+                                    rate_deg: nut_prec.get(1).copied().unwrap_or(0.0),
+                                    accel_deg: nut_prec.get(2).copied().unwrap_or(0.0),
+                                    jerk_deg: nut_prec.get(3).copied().unwrap_or(0.0),
                                     ..Default::default()
                                 };
                                 num += 1;
@@ -308,8 +928,12 @@ pub fn convert_tpc_items(
                         };
 
                         // Skip the DER serialization in full.
-                        dataset.push(constant, Some(object_id), None)?;
-                        info!("Added {object_id}");
+                        let name = body_names
+                            .get(&object_id)
+                            .filter(|name| name.len() <= KEY_NAME_LEN)
+                            .map(|name| name.as_str());
+                        dataset.push(constant, Some(object_id), name)?;
+                        info!("Added {object_id} ({name:?})");
                     }
                     _ => error!(
                         "skipping {object_id}: gravity data is {mu_km3_s2_value:?} (want float)"
@@ -354,9 +978,11 @@ pub fn convert_fk_items(
         {
             let mut warn = false;
             if let Some(class) = item.data.get(&Parameter::Class) {
-                if class.to_i32().unwrap() == 2 {
-                    // BPC based frame, insert as-is.
-                    // Class 2 need a BPC for the full rotation.
+                let class = class.to_i32().unwrap();
+                if class == 2 || class == 3 {
+                    // BPC or CK based frame (e.g. a spacecraft bus), inserted only so that its name resolves to its ID: its
+                    // parent is itself, so it adds no edge to the orientation tree (cf. `NamedFrameOnly`). Class 2 need a BPC
+                    // for the full rotation, and class 3 need an orientation provider of the attitude.
                     dataset.push(Quaternion::identity(id, id), Some(id), item.name.as_deref())?;
                 } else {
                     warn = true;
@@ -368,23 +994,80 @@ pub fn convert_fk_items(
                 warn!("{id} contains neither angles nor matrix, cannot convert to Euler Parameter");
                 continue;
             }
-        } else if let Some(angles) = item.data.get(&Parameter::Angles) {
-            let unit = item
-                .data
-                .get(&Parameter::Units)
-                .ok_or(DataSetError::Conversion {
-                    action: format!("no unit data for FK ID {id}"),
+        } else {
+            let rot_mat = if let Some(angles) = item.data.get(&Parameter::Angles) {
+                let unit = item
+                    .data
+                    .get(&Parameter::Units)
+                    .ok_or(DataSetError::Conversion {
+                        action: format!("no unit data for FK ID {id}"),
+                    })?;
+                let unit = unit.to_string().unwrap_or_default();
+                let unit_deg = tk_angle_unit_deg(&unit).ok_or(DataSetError::Conversion {
+                    action: format!("unknown angle unit `{unit}` for FK ID {id}"),
                 })?;
-            let mut angle_data = angles.to_vec_f64().unwrap();
-            if unit == &KPLValue::String("ARCSECONDS".to_string()) {
-                // Convert the angles data into degrees
-                for item in &mut angle_data {
-                    *item /= 3600.0;
+
+                let angle_data = tk_values(id, angles)?;
+                let axes = tk_values(
+                    id,
+                    item.data
+                        .get(&Parameter::Axes)
+                        .ok_or(DataSetError::Conversion {
+                            action: format!("no axes data for FK ID {id}"),
+                        })?,
+                )?;
+                if axes.len() != angle_data.len() {
+                    return Err(DataSetError::Conversion {
+                        action: format!(
+                            "FK ID {id} has {} angles but {} axes",
+                            angle_data.len(),
+                            axes.len()
+                        ),
+                    });
                 }
-            }
-            // Build the quaternion from the Euler matrices
-            let from = id;
-            let to = item.data[&Parameter::Center].to_i32().unwrap();
+
+                // Build the rotation matrix from the Euler matrices
+                let mut dcm = Matrix3::identity();
+
+                for (angle, rot) in angle_data.iter().zip(axes.iter()) {
+                    let angle_rad = (angle * unit_deg).to_radians();
+                    let this_dcm = if rot == &1.0 {
+                        r1(angle_rad)
+                    } else if rot == &2.0 {
+                        r2(angle_rad)
+                    } else if rot == &3.0 {
+                        r3(angle_rad)
+                    } else {
+                        return Err(DataSetError::Conversion {
+                            action: format!("FK ID {id} has an invalid rotation axis {rot}"),
+                        });
+                    };
+                    dcm *= this_dcm;
+                }
+                dcm
+            } else {
+                let mat_data = tk_values(id, &item.data[&Parameter::Matrix])?;
+                if mat_data.len() != 9 {
+                    return Err(DataSetError::Conversion {
+                        action: format!(
+                            "FK ID {id} has a matrix of {} items instead of 9",
+                            mat_data.len()
+                        ),
+                    });
+                }
+                Matrix3::new(
+                    mat_data[0],
+                    mat_data[1],
+                    mat_data[2],
+                    mat_data[3],
+                    mat_data[4],
+                    mat_data[5],
+                    mat_data[6],
+                    mat_data[7],
+                    mat_data[8],
+                )
+            };
+
             if let Some(class) = item.data.get(&Parameter::Class) {
                 if class.to_i32().unwrap() == 4 {
                     // This is a relative frame.
@@ -397,70 +1080,44 @@ pub fn convert_fk_items(
                 }
             }
 
-            let mut dcm = Matrix3::identity();
+            let to = item
+                .data
+                .get(&Parameter::Center)
+                .and_then(|center| center.to_i32().ok())
+                .ok_or(DataSetError::Conversion {
+                    action: format!("no integer center for FK ID {id}"),
+                })?;
 
-            for (i, rot) in item.data[&Parameter::Axes]
-                .to_vec_f64()
-                .unwrap()
-                .iter()
-                .enumerate()
-            {
-                let this_dcm = if rot == &1.0 {
-                    r1(angle_data[i].to_radians())
-                } else if rot == &2.0 {
-                    r2(angle_data[i].to_radians())
-                } else {
-                    r3(angle_data[i].to_radians())
-                };
-                dcm *= this_dcm;
-            }
             // Convert to quaternion
             let q = DCM {
-                rot_mat: dcm,
+                rot_mat,
                 to,
-                from,
+                from: id,
                 rot_mat_dt: None,
             }
             .into();
 
             dataset.push(q, Some(id), item.name.as_deref())?;
-        } else if let Some(matrix) = item.data.get(&Parameter::Matrix) {
-            let mat_data = matrix.to_vec_f64().unwrap();
-            let rot_mat = Matrix3::new(
-                mat_data[0],
-                mat_data[1],
-                mat_data[2],
-                mat_data[3],
-                mat_data[4],
-                mat_data[5],
-                mat_data[6],
-                mat_data[7],
-                mat_data[8],
-            );
-            let dcm = DCM {
-                from: id,
-                to: item.data[&Parameter::Center].to_i32().unwrap(),
-                rot_mat,
-                rot_mat_dt: None,
-            };
-
-            dataset.push(dcm.into(), Some(id), item.name.as_deref())?;
         }
     }
 
     // Finally, let's update the frames of the IDs defined as relative.
     for (id, relative_to) in ids_to_update {
-        let parent_idx = dataset
-            .lut
-            .by_name
-            .get(&(relative_to.as_str().try_into().unwrap()))
-            .ok_or(DataSetError::Conversion {
+        // The parent is the relative frame itself, whose own parent may not have been updated yet.
+        let parent_id = match relative_to
+            .as_str()
+            .try_into()
+            .ok()
+            .and_then(|name| dataset.lut.by_name.get(&name))
+        {
+            Some(parent_idx) => dataset.data[(*parent_idx) as usize].from,
+            // Frames defined outside of this FK, like J2000 or IAU_EARTH, are resolved by their standard names.
+            None => standard_frame_id(&relative_to).ok_or(DataSetError::Conversion {
                 action: format!(
-                    "frame {id} is class 4 relative to `{relative_to}`, but that frame is not found"
+                    "frame {id} is class 4 relative to `{relative_to}`, but that frame is neither in the FK nor a standard frame"
                 ),
-            })?;
-
-        let parent_id = dataset.data[(*parent_idx) as usize].to;
+            })?,
+        };
 
         // Modify this EP.
         let index = dataset.lut.by_id.get(&id).unwrap();
@@ -475,3 +1132,434 @@ pub fn convert_fk_items(
 
     Ok(dataset)
 }
+
+/// Returns the ID of the standard frame with the provided name, e.g. `J2000`, `ECLIPJ2000` or `IAU_EARTH`, ignoring its case.
+fn standard_frame_id(name: &str) -> Option<NaifId> {
+    let name = name.trim().to_uppercase();
+    match name.as_str() {
+        "EME2000" => Some(J2000),
+        "GALACTIC" => Some(GALACTIC),
+        "MARSIAU" => Some(MARSIAU),
+        _ => id_to_orientation_name(&name).ok(),
+    }
+}
+
+/// Returns the values of a TK frame vector, where the vectors of one item are parsed as scalars (e.g. `( 90.0 )`).
+fn tk_values(id: i32, value: &KPLValue) -> Result<Vec<f64>, DataSetError> {
+    match value {
+        KPLValue::Matrix(data) => Ok(data.clone()),
+        KPLValue::Float(data) => Ok(vec![*data]),
+        KPLValue::Integer(data) => Ok(vec![f64::from(*data)]),
+        _ => Err(DataSetError::Conversion {
+            action: format!("expected numbers for FK ID {id} but got {value:?}"),
+        }),
+    }
+}
+
+/// Returns the value in degrees of one unit of the TK frame angles, for the angle units of SPICE.
+fn tk_angle_unit_deg(unit: &str) -> Option<f64> {
+    match unit {
+        "DEGREES" => Some(1.0),
+        "RADIANS" => Some(1.0_f64.to_degrees()),
+        "ARCMINUTES" => Some(1.0 / 60.0),
+        "ARCSECONDS" => Some(1.0 / 3600.0),
+        "HOURANGLE" => Some(15.0),
+        "MINUTEANGLE" => Some(15.0 / 60.0),
+        "SECONDANGLE" => Some(15.0 / 3600.0),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_parse_body_names() {
+    use std::io::Cursor;
+
+    let kernel = "
+KPL/FK
+
+Asteroid names and codes, in the style of codes_300ast.
+
+\\begindata
+
+   NAIF_BODY_NAME += ( 'CERES' )
+   NAIF_BODY_CODE += ( 2000001 )
+
+   NAIF_BODY_NAME += ( 'JUNO', 'APOPHIS' )
+   NAIF_BODY_CODE += ( 2000003,
+                       2099942 )
+
+\\begintext
+   NAIF_BODY_NAME += ( 'IGNORED' )
+";
+
+    let names = parse_body_names(&mut Cursor::new(kernel));
+    assert_eq!(names.len(), 3);
+    assert_eq!(names[&2000001], "CERES");
+    assert_eq!(names[&2000003], "JUNO");
+    assert_eq!(names[&2099942], "APOPHIS");
+}
+
+#[test]
+fn test_convert_small_bodies() {
+    use std::io::Cursor;
+
+    let planetary_data = parse_file::<_, TPCItem>("../data/pck00011.tpc", false).unwrap();
+    let gravity_data = parse_file::<_, TPCItem>("../data/gm_de440.tpc", false).unwrap();
+    let names = parse_body_names(&mut Cursor::new(
+        "\\begindata\nNAIF_BODY_NAME += ( 'CERES', 'JUNO' )\nNAIF_BODY_CODE += ( 2000001, 2000003 )\n",
+    ));
+
+    let dataset = convert_named_tpc_items(planetary_data, gravity_data, &names).unwrap();
+
+    // Ceres has both rotation and gravity data, and is not a satellite of some body 20000.
+    let ceres = dataset.get_by_name("CERES").unwrap();
+    assert_eq!(ceres.object_id, 2000001);
+    assert_eq!(ceres.parent_id, J2000);
+    assert!((ceres.mu_km3_s2 - 62.628888644409933).abs() < f64::EPSILON);
+    assert!(ceres.pole_right_ascension.is_some());
+
+    // Juno only has gravity data
+    let juno = dataset.get_by_id(2000003).unwrap();
+    assert_eq!(juno.parent_id, J2000);
+    assert!(juno.pole_right_ascension.is_none());
+    assert_eq!(dataset.get_by_name("JUNO").unwrap(), juno);
+}
+
+#[test]
+fn test_convert_nutation_precession() {
+    let planetary_data = parse_file::<_, TPCItem>("../data/pck00011.tpc", false).unwrap();
+    let gravity_data = parse_file::<_, TPCItem>("../data/gm_de440.tpc", false).unwrap();
+
+    let dataset = convert_tpc_items(planetary_data, gravity_data).unwrap();
+
+    // The acceleration of the lunar prime meridian uses a Fortran exponent: `-1.4D-12`
+    let moon = dataset.get_by_id(301).unwrap();
+    let moon_pm = moon.prime_meridian.unwrap();
+    assert_eq!(moon_pm.accel_deg, -1.4e-12);
+    assert_eq!(moon_pm.coeffs_count, 13);
+    assert_eq!(moon_pm.coeffs[0], 3.5610);
+
+    // The Earth system has 13 linear nutation precession angles
+    let earth_system = dataset.get_by_id(3).unwrap();
+    assert_eq!(earth_system.num_nut_prec_angles, 13);
+    assert_eq!(earth_system.nut_prec_angles[0].offset_deg, 125.045);
+
+    // The Martian system has quadratic angles
+    let mars_system = dataset.get_by_id(4).unwrap();
+    assert_eq!(mars_system.nut_prec_angles[4].offset_deg, 189.63271560);
+    assert_eq!(mars_system.nut_prec_angles[4].rate_deg, 41215158.18420050);
+    assert_eq!(mars_system.nut_prec_angles[4].accel_deg, 12.711923222);
+    assert_eq!(mars_system.nut_prec_angles[5].offset_deg, 121.46893664);
+}
+
+#[test]
+fn test_fortran_exponents() {
+    let assignment = Assignment {
+        keyword: "BODY301_PM".to_string(),
+        value: "(   38.3213       13.17635815   -1.4D-12 )".to_string(),
+    };
+    assert_eq!(
+        assignment.to_value(),
+        KPLValue::Matrix(vec![38.3213, 13.17635815, -1.4e-12])
+    );
+}
+
+#[test]
+fn test_convert_spacecraft_frames() {
+    use std::io::Cursor;
+
+    let kernel = "
+KPL/FK
+
+\\begindata
+
+   FRAME_SC_BUS                = -82000
+   FRAME_-82000_NAME           = 'SC_BUS'
+   FRAME_-82000_CLASS          = 3
+   FRAME_-82000_CLASS_ID       = -82000
+   FRAME_-82000_CENTER         = -82
+   CK_-82000_SCLK              = -82
+   CK_-82000_SPK               = -82
+
+   FRAME_SC_CAMERA             = -82100
+   FRAME_-82100_NAME           = 'SC_CAMERA'
+   FRAME_-82100_CLASS          = 4
+   FRAME_-82100_CLASS_ID       = -82100
+   FRAME_-82100_CENTER         = -82
+   TKFRAME_-82100_RELATIVE     = 'SC_BUS'
+   TKFRAME_-82100_SPEC         = 'ANGLES'
+   TKFRAME_-82100_UNITS        = 'DEGREES'
+   TKFRAME_-82100_AXES         = ( 3, 2, 1 )
+   TKFRAME_-82100_ANGLES       = ( 90.0, 0.0, 0.0 )
+
+\\begintext
+";
+
+    let assignments = parse_bytes::<_, FKItem>(&mut Cursor::new(kernel), false).unwrap();
+    let dataset = convert_fk_items(assignments).unwrap();
+    assert_eq!(dataset.len(), 2);
+
+    // The CK frame of the bus is kept so that its name resolves to its ID, and its attitude may be provided later.
+    let bus = dataset.get_by_name("SC_BUS").unwrap();
+    assert_eq!(bus, Quaternion::identity(-82000, -82000));
+
+    // The camera is relative to the bus frame, not to its center, the spacecraft itself.
+    let camera = dataset.get_by_name("SC_CAMERA").unwrap();
+    assert_eq!(camera.from, -82100);
+    assert_eq!(camera.to, -82000);
+    assert!((DCM::from(camera).rot_mat - r3(90.0_f64.to_radians())).norm() < 1e-12);
+}
+
+#[test]
+fn test_convert_tk_frames() {
+    use std::io::Cursor;
+
+    let kernel = "
+KPL/FK
+
+\\begindata
+
+   FRAME_SC_BUS                = -82000
+   FRAME_-82000_NAME           = 'SC_BUS'
+   FRAME_-82000_CLASS          = 3
+   FRAME_-82000_CLASS_ID       = -82000
+   FRAME_-82000_CENTER         = -82
+
+   FRAME_SC_BOOM               = -82200
+   FRAME_-82200_NAME           = 'SC_BOOM'
+   FRAME_-82200_CLASS          = 4
+   FRAME_-82200_CLASS_ID       = -82200
+   FRAME_-82200_CENTER         = -82
+   TKFRAME_-82200_RELATIVE     = 'SC_BUS'
+   TKFRAME_-82200_SPEC         = 'ANGLES'
+   TKFRAME_-82200_UNITS        = 'RADIANS'
+   TKFRAME_-82200_AXES         = ( 2 )
+   TKFRAME_-82200_ANGLES       = ( 0.5 )
+
+   FRAME_SC_MAGNETOMETER       = -82210
+   FRAME_-82210_NAME           = 'SC_MAGNETOMETER'
+   FRAME_-82210_CLASS          = 4
+   FRAME_-82210_CLASS_ID       = -82210
+   FRAME_-82210_CENTER         = -82
+   TKFRAME_-82210_RELATIVE     = 'SC_BOOM'
+   TKFRAME_-82210_SPEC         = 'MATRIX'
+   TKFRAME_-82210_MATRIX       = ( 0 1 0
+                                   -1 0 0
+                                   0 0 1 )
+
+\\begintext
+";
+
+    let assignments = parse_bytes::<_, FKItem>(&mut Cursor::new(kernel), false).unwrap();
+    let dataset = convert_fk_items(assignments).unwrap();
+    assert_eq!(dataset.len(), 3);
+
+    // A single angle is a scalar in the kernel, and its unit is converted.
+    let boom = dataset.get_by_name("SC_BOOM").unwrap();
+    assert_eq!(boom.to, -82000);
+    assert!((DCM::from(boom).rot_mat - r2(0.5)).norm() < 1e-12);
+
+    // The matrix frames are relative to their relative frame too, and not to the parent of that frame.
+    let magnetometer = dataset.get_by_name("SC_MAGNETOMETER").unwrap();
+    assert_eq!(magnetometer.from, -82210);
+    assert_eq!(magnetometer.to, -82200);
+    assert!((DCM::from(magnetometer).rot_mat - r3(core::f64::consts::FRAC_PI_2)).norm() < 1e-12);
+
+    // Frames relative to a frame outside of the FK are resolved by its standard name.
+    for (relative_to, parent_id) in [
+        ("'J2000'", J2000),
+        ("'eclipj2000'", crate::constants::orientations::ECLIPJ2000),
+        ("'IAU_EARTH'", crate::constants::orientations::IAU_EARTH),
+    ] {
+        let assignments = parse_bytes::<_, FKItem>(
+            &mut Cursor::new(kernel.replace("'SC_BUS'", relative_to)),
+            false,
+        )
+        .unwrap();
+        let dataset = convert_fk_items(assignments).unwrap();
+        assert_eq!(dataset.get_by_name("SC_BOOM").unwrap().to, parent_id);
+        assert_eq!(dataset.get_by_name("SC_MAGNETOMETER").unwrap().to, -82200);
+    }
+
+    // Invalid definitions are reported instead of being converted incorrectly.
+    for (valid, invalid) in [
+        ("'SC_BUS'", "'SC_UNKNOWN'"),
+        ("'RADIANS'", "'FURLONGS'"),
+        ("AXES         = ( 2 )", "AXES         = ( 2, 1 )"),
+        ("AXES         = ( 2 )", "AXES         = ( 4 )"),
+    ] {
+        let assignments =
+            parse_bytes::<_, FKItem>(&mut Cursor::new(kernel.replace(valid, invalid)), false)
+                .unwrap();
+        assert!(convert_fk_items(assignments).is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn test_kpl_grammar() {
+    use std::io::Cursor;
+
+    let kernel = "
+KPL/PCK
+
+The markers are only recognized alone on their line, so this is still a comment:
+   \\begindata BODY399_RADII = (
+
+\\begindata
+
+   BODY399_RADII    = ( 6378.1366, 6378.1366,
+                        6356.7519 )
+   BODY399_GM=398600.435436D0
+   BODY399_PM       = ( 190.147 ) BODY399_LONG_AXIS = 0.0
+   BODY399_NUT_PREC_RA =
+                      ( -0.641D0 0.0 )
+   BODY399_NUT_PREC_RA += 1.5
+   MISSION_NAME     = ( 'Lunar ''Gate//'
+                        'way''' )
+   MISSION_START    = @2000-JAN-01/12:00
+   MISSION_EPOCHS   = ( @2000-01-02T00:00:30.5, @1-FEB-2000, @2000-032 )
+   MISSION_FRAMES   = ( 'EARTH_FIXED' )
+   MISSION_FRAMES  += ( 'ITRF93', 'IAU EARTH' )
+
+\\begintext
+";
+
+    let assignments = parse_assignments(&mut Cursor::new(kernel), false).unwrap();
+    let value = |keyword: &str| {
+        assignments
+            .iter()
+            .rev()
+            .find(|assignment| assignment.keyword == keyword)
+            .unwrap()
+            .to_value()
+    };
+
+    assert_eq!(
+        value("BODY399_RADII"),
+        KPLValue::Matrix(vec![6378.1366, 6378.1366, 6356.7519])
+    );
+    assert_eq!(value("BODY399_GM"), KPLValue::Float(398600.435436));
+    // Vectors of one item are scalars
+    assert_eq!(value("BODY399_PM"), KPLValue::Float(190.147));
+    assert_eq!(value("BODY399_LONG_AXIS"), KPLValue::Float(0.0));
+    // Appended values
+    assert_eq!(
+        value("BODY399_NUT_PREC_RA"),
+        KPLValue::Matrix(vec![-0.641, 0.0, 1.5])
+    );
+    assert_eq!(
+        value("MISSION_FRAMES"),
+        KPLValue::Strings(vec![
+            "EARTH_FIXED".to_string(),
+            "ITRF93".to_string(),
+            "IAU EARTH".to_string()
+        ])
+    );
+    // Continued strings
+    assert_eq!(
+        value("MISSION_NAME"),
+        KPLValue::String("Lunar 'Gateway'".to_string())
+    );
+    // Dates are seconds past J2000 in the formal calendar
+    assert_eq!(value("MISSION_START"), KPLValue::Float(0.0));
+    assert_eq!(
+        value("MISSION_EPOCHS"),
+        KPLValue::Matrix(vec![
+            43_230.5,
+            31.0 * 86_400.0 - 43_200.0,
+            31.0 * 86_400.0 - 43_200.0
+        ])
+    );
+    assert_eq!(parse_kpl_date("@1972-JAN-1"), parse_kpl_date("@JAN-1-1972"));
+    assert_eq!(
+        parse_kpl_date("@2000-OCT-01"),
+        parse_kpl_date("@2000-10-01T00:00")
+    );
+    assert_eq!(parse_kpl_date("@2000-13-01"), None);
+    assert_eq!(parse_kpl_date("@2000-JAN-01/25:00"), None);
+
+    // The values of the body are still parsed into its item.
+    let items = parse_bytes::<_, TPCItem>(&mut Cursor::new(kernel), false).unwrap();
+    assert_eq!(
+        items[&399].data[&Parameter::GravitationalParameter],
+        KPLValue::Float(398600.435436)
+    );
+}
+
+#[test]
+fn test_kpl_syntax_errors() {
+    use std::io::Cursor;
+
+    let error = |data: &str| {
+        parse_assignments(&mut Cursor::new(format!("\\begindata\n{data}")), false).unwrap_err()
+    };
+
+    assert_eq!(
+        error("BODY399_RADII = ( 1.0 2.0\n\n  3.0"),
+        KPLError::Syntax {
+            line: 2,
+            column: 17,
+            reason: "vector of `BODY399_RADII` is not closed".to_string()
+        }
+    );
+    assert_eq!(
+        error("BODY399_GM = 3.0\n  BODY399_PM   ( 1.0 )"),
+        KPLError::Syntax {
+            line: 3,
+            column: 16,
+            reason: "expected `=` or `+=` after `BODY399_PM`, found `(`".to_string()
+        }
+    );
+    assert_eq!(
+        error("NAME = 'unterminated"),
+        KPLError::Syntax {
+            line: 2,
+            column: 8,
+            reason: "unterminated string: strings cannot span several lines, use `//` to continue them in the next string".to_string()
+        }
+    );
+    assert_eq!(
+        error("BODY399_GM = 3.0.0"),
+        KPLError::Syntax {
+            line: 2,
+            column: 14,
+            reason: "expected a number, a quoted string, or an @ date, found `3.0.0`".to_string()
+        }
+    );
+    assert_eq!(
+        error("NAMES = ( 'A' 1 )"),
+        KPLError::Syntax {
+            line: 2,
+            column: 9,
+            reason: "`NAMES` mixes strings and numbers".to_string()
+        }
+    );
+    assert_eq!(
+        error("START = @2000-FOO-01").to_string(),
+        "line 2, column 9: invalid date `@2000-FOO-01`"
+    );
+    assert!(matches!(
+        error("NAMES = 'A'\nNAMES += 1"),
+        KPLError::Syntax {
+            line: 3,
+            column: 10,
+            ..
+        }
+    ));
+    assert!(matches!(
+        error("NAMES = \n\\begintext"),
+        KPLError::Syntax {
+            line: 3,
+            column: 1,
+            ..
+        }
+    ));
+
+    // The errors are located in the files too.
+    assert!(
+        parse_bytes::<_, TPCItem>(&mut Cursor::new("\\begindata\nBODY399_GM = = 1.0"), false)
+            .unwrap_err()
+            .to_string()
+            .contains("line 2, column 14")
+    );
+}
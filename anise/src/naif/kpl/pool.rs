@@ -0,0 +1,198 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use super::parser::{parse_assignments, parse_kpl_f64, KPLError};
+
+/// Value of a kernel pool variable: either a list of numbers or a list of strings, as in SPICE.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PoolValue {
+    Numeric(Vec<f64>),
+    Text(Vec<String>),
+}
+
+impl PoolValue {
+    /// Parses the value of a text kernel assignment: quoted values are strings, and the others are numbers.
+    ///
+    /// A value which is neither is kept as the list of its unquoted items.
+    fn parse(value: &str) -> Self {
+        if value.contains('\'') {
+            // Strings are quoted, and a quote is escaped by doubling it.
+            let value = value.replace("''", "\u{0}");
+            return Self::Text(
+                value
+                    .split('\'')
+                    .skip(1)
+                    .step_by(2)
+                    .map(|item| item.replace('\u{0}', "'"))
+                    .collect(),
+            );
+        }
+
+        let items = value.replace(['(', ')', ','], " ");
+        let items = items.split_whitespace().collect::<Vec<&str>>();
+        match items
+            .iter()
+            .map(|item| parse_kpl_f64(item))
+            .collect::<Option<Vec<f64>>>()
+        {
+            Some(numbers) => Self::Numeric(numbers),
+            None => Self::Text(items.iter().map(|item| item.to_string()).collect()),
+        }
+    }
+}
+
+/// A pool of the variables assigned in text kernels (e.g. `BODY399_RADII` or mission specific parameters), like the kernel
+/// pool of SPICE, where a variable assigned again replaces the previous value.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KernelPool {
+    variables: HashMap<String, PoolValue>,
+}
+
+impl KernelPool {
+    /// Parses all of the assignments of the data blocks of a text kernel into a kernel pool, where the `@` dates are
+    /// converted to seconds past J2000, as in SPICE.
+    pub fn parse<R: BufRead>(reader: &mut R) -> Result<Self, KPLError> {
+        let mut pool = Self::default();
+        for assignment in parse_assignments(reader, false)? {
+            pool.set(&assignment.keyword, PoolValue::parse(&assignment.value));
+        }
+        Ok(pool)
+    }
+
+    /// Sets the value of this variable, replacing the previous one if any.
+    pub fn set(&mut self, name: &str, value: PoolValue) {
+        self.variables.insert(name.to_string(), value);
+    }
+
+    /// Adds all of the variables of the other pool to this one, where those of the other pool have precedence.
+    pub fn extend(&mut self, other: &Self) {
+        for (name, value) in &other.variables {
+            self.variables.insert(name.clone(), value.clone());
+        }
+    }
+
+    /// Returns the value of this variable, if it is defined.
+    pub fn get(&self, name: &str) -> Option<&PoolValue> {
+        self.variables.get(name)
+    }
+
+    /// Returns the numbers of this variable, if it is defined and numeric.
+    pub fn get_f64s(&self, name: &str) -> Option<&[f64]> {
+        match self.get(name)? {
+            PoolValue::Numeric(numbers) => Some(numbers),
+            PoolValue::Text(_) => None,
+        }
+    }
+
+    /// Returns the number of this variable, if it is defined and a single number.
+    pub fn get_f64(&self, name: &str) -> Option<f64> {
+        match self.get_f64s(name)? {
+            [number] => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// Returns the integers of this variable, if it is defined and all of its numbers are integers.
+    pub fn get_i32s(&self, name: &str) -> Option<Vec<i32>> {
+        self.get_f64s(name)?
+            .iter()
+            .map(|number| (number.fract() == 0.0).then_some(*number as i32))
+            .collect()
+    }
+
+    /// Returns the strings of this variable, if it is defined and a list of strings.
+    pub fn get_strings(&self, name: &str) -> Option<&[String]> {
+        match self.get(name)? {
+            PoolValue::Text(strings) => Some(strings),
+            PoolValue::Numeric(_) => None,
+        }
+    }
+
+    /// Returns the names of the variables of this pool, sorted alphabetically.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names = self
+            .variables
+            .keys()
+            .map(|name| name.as_str())
+            .collect::<Vec<&str>>();
+        names.sort_unstable();
+        names
+    }
+
+    /// Returns the number of variables in this pool
+    pub fn len(&self) -> usize {
+        self.variables.len()
+    }
+
+    /// Returns true if this pool has no variable
+    pub fn is_empty(&self) -> bool {
+        self.variables.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod ut_pool {
+    use super::{KernelPool, PoolValue};
+
+    const KERNEL: &str = "KPL/PCK
+
+\\begintext
+   BODY399_RADII = ( 1 2 3 ) is in a comment block.
+
+\\begindata
+
+BODY399_RADII     = ( 6378.1366   6378.1366   6356.7519 )
+BODY399_PM        = (  190.147  360.9856235  0.0 )
+BODY399_NUT_PREC_RA = ( -0.641D0
+                        0.0 )
+MISSION_NAME      = 'Lunar ''Gateway'''
+FRAME_ALIASES     = ( 'EARTH_FIXED', 'ITRF93' )
+TUNING_GAIN       = 0.25
+BODY399_RADII     = ( 6378.1   6378.1   6356.8 )
+
+\\begintext
+";
+
+    #[test]
+    fn parse_pool() {
+        let pool = KernelPool::parse(&mut KERNEL.as_bytes()).unwrap();
+        assert_eq!(pool.len(), 6);
+
+        // The last assignment has precedence.
+        assert_eq!(
+            pool.get_f64s("BODY399_RADII").unwrap(),
+            &[6378.1, 6378.1, 6356.8]
+        );
+        assert_eq!(
+            pool.get_f64s("BODY399_NUT_PREC_RA").unwrap(),
+            &[-0.641, 0.0]
+        );
+        assert_eq!(pool.get_f64("TUNING_GAIN"), Some(0.25));
+        assert_eq!(pool.get_f64("BODY399_PM"), None);
+        assert_eq!(pool.get_i32s("BODY399_PM"), None);
+        assert_eq!(
+            pool.get_strings("MISSION_NAME").unwrap(),
+            &["Lunar 'Gateway'".to_string()]
+        );
+        assert_eq!(
+            pool.get("FRAME_ALIASES"),
+            Some(&PoolValue::Text(vec![
+                "EARTH_FIXED".to_string(),
+                "ITRF93".to_string()
+            ]))
+        );
+        assert_eq!(pool.get_f64s("MISSION_NAME"), None);
+        assert_eq!(pool.get("UNDEFINED"), None);
+        assert_eq!(pool.names()[0], "BODY399_NUT_PREC_RA");
+    }
+}
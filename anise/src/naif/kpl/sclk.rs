@@ -0,0 +1,514 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use std::io::BufRead;
+use std::{collections::HashMap, str::FromStr};
+
+use hifitime::{Epoch, TimeScale, Unit as TimeUnit};
+use log::warn;
+use snafu::{ensure, whatever, Snafu, Whatever};
+
+use super::{
+    parser::{parse_bytes, Assignment},
+    KPLItem, KPLValue,
+};
+
+/// Delimiters accepted between the fields of a clock string, as in SPICE.
+const FIELD_DELIMITERS: [char; 5] = ['.', ':', '-', ',', ' '];
+
+#[derive(Debug, Snafu, PartialEq)]
+#[snafu(visibility(pub(crate)))]
+pub enum SCLKError {
+    #[snafu(display("spacecraft clock {clock_id} is missing `{keyword}`"))]
+    MissingKeyword {
+        clock_id: i32,
+        keyword: &'static str,
+    },
+    #[snafu(display(
+        "spacecraft clock {clock_id} is of type {data_type} but only type 1 is supported"
+    ))]
+    UnsupportedClockType { clock_id: i32, data_type: i32 },
+    #[snafu(display("invalid clock string `{sclk}`: {reason}"))]
+    InvalidClockString { sclk: String, reason: String },
+    #[snafu(display("{epoch} is outside of the partitions of spacecraft clock {clock_id}"))]
+    OutsidePartitions { clock_id: i32, epoch: Epoch },
+    #[snafu(display("invalid SCLK kernel: {reason}"))]
+    InvalidKernel { reason: String },
+}
+
+/// Known parameters of a type 1 SCLK kernel
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum SCLKParameter {
+    DataType,
+    TimeSystem,
+    NumFields,
+    Moduli,
+    Offsets,
+    OutputDelimiter,
+    PartitionStart,
+    PartitionEnd,
+    Coefficients,
+}
+
+impl FromStr for SCLKParameter {
+    type Err = Whatever;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "SCLK_DATA_TYPE" => Ok(Self::DataType),
+            "SCLK01_TIME_SYSTEM" => Ok(Self::TimeSystem),
+            "SCLK01_N_FIELDS" => Ok(Self::NumFields),
+            "SCLK01_MODULI" => Ok(Self::Moduli),
+            "SCLK01_OFFSETS" => Ok(Self::Offsets),
+            "SCLK01_OUTPUT_DELIM" => Ok(Self::OutputDelimiter),
+            "SCLK_PARTITION_START" => Ok(Self::PartitionStart),
+            "SCLK_PARTITION_END" => Ok(Self::PartitionEnd),
+            "SCLK01_COEFFICIENTS" => Ok(Self::Coefficients),
+            _ => whatever!("unknown SCLK parameter `{s}`"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SCLKItem {
+    pub data: HashMap<SCLKParameter, KPLValue>,
+}
+
+impl KPLItem for SCLKItem {
+    type Parameter = SCLKParameter;
+
+    /// Returns the clock ID at the end of the keyword (the opposite of the NAIF ID of the spacecraft), or -1 otherwise.
+    fn extract_key(data: &Assignment) -> i32 {
+        if data.keyword.starts_with("SCLK") {
+            match data.keyword.rsplit_once('_') {
+                Some((_, clock_id)) => clock_id.parse::<i32>().unwrap_or(-1),
+                None => -1,
+            }
+        } else {
+            -1
+        }
+    }
+
+    fn data(&self) -> &HashMap<Self::Parameter, KPLValue> {
+        &self.data
+    }
+
+    fn parse(&mut self, data: Assignment) {
+        if let Some((stem, _)) = data.keyword.rsplit_once('_') {
+            match SCLKParameter::from_str(stem) {
+                Ok(param) => {
+                    self.data.insert(param, data.to_value());
+                }
+                Err(_) => warn!("Unknown parameter `{stem}` -- ignoring"),
+            }
+        }
+    }
+}
+
+/// Returns the values of a KPL assignment as floats, whether it holds one or several values.
+fn to_values(value: &KPLValue) -> Vec<f64> {
+    match value {
+        KPLValue::Matrix(data) => data.clone(),
+        KPLValue::Float(data) => vec![*data],
+        KPLValue::Integer(data) => vec![*data as f64],
+        KPLValue::String(_) | KPLValue::Strings(_) => vec![],
+    }
+}
+
+/// A type 1 spacecraft clock, as defined in an SCLK kernel, which converts between clock strings (e.g. `1/1465644281.165`)
+/// and epochs with the piecewise linear correlation of the clock with a parallel time system (TDB or TT).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpacecraftClock {
+    /// ID of this clock in the kernel, which is the opposite of the NAIF ID of the spacecraft
+    pub clock_id: i32,
+    /// Parallel time system of the correlation
+    pub time_scale: TimeScale,
+    /// Modulus of each field of the clock, from the most significant field
+    pub moduli: Vec<f64>,
+    /// Offset of each field of the clock, from the most significant field
+    pub offsets: Vec<f64>,
+    /// Delimiter of the fields when formatting a clock string
+    pub delimiter: char,
+    /// Start and end of each partition, in ticks
+    pub partitions: Vec<(f64, f64)>,
+    /// Correlation records: encoded clock in ticks since the start of the first partition, parallel time in seconds past
+    /// J2000, and rate in parallel seconds per count of the most significant field
+    pub coefficients: Vec<[f64; 3]>,
+}
+
+impl SpacecraftClock {
+    /// Returns the NAIF ID of the spacecraft of this clock
+    pub fn spacecraft_id(&self) -> i32 {
+        -self.clock_id
+    }
+
+    /// Number of ticks (count of the least significant field) per count of the most significant field
+    fn ticks_per_count(&self) -> f64 {
+        self.moduli.iter().skip(1).product()
+    }
+
+    /// Reference epoch of the parallel time
+    fn j2000(&self) -> Epoch {
+        Epoch::from_gregorian_hms(2000, 1, 1, 12, 0, 0, self.time_scale)
+    }
+
+    /// Converts the clock string into the encoded clock, i.e. the number of ticks since the start of the first partition.
+    ///
+    /// If the partition is not specified (e.g. `1465644281.165` instead of `1/1465644281.165`), the first partition
+    /// containing this clock reading is used. Missing least significant fields are zero.
+    pub fn encode(&self, sclk: &str) -> Result<f64, SCLKError> {
+        let invalid = |reason: String| SCLKError::InvalidClockString {
+            sclk: sclk.to_string(),
+            reason,
+        };
+
+        let (partition, fields) = match sclk.split_once('/') {
+            Some((partition, fields)) => (
+                Some(
+                    partition
+                        .trim()
+                        .parse::<usize>()
+                        .map_err(|e| invalid(format!("partition: {e}")))?,
+                ),
+                fields,
+            ),
+            None => (None, sclk),
+        };
+
+        let fields = fields
+            .trim()
+            .split(FIELD_DELIMITERS)
+            .filter(|field| !field.is_empty())
+            .map(|field| field.parse::<f64>())
+            .collect::<Result<Vec<f64>, _>>()
+            .map_err(|e| invalid(format!("field: {e}")))?;
+
+        if fields.is_empty() || fields.len() > self.moduli.len() {
+            return Err(invalid(format!(
+                "expected between 1 and {} fields",
+                self.moduli.len()
+            )));
+        }
+
+        let mut ticks = 0.0;
+        for (i, modulus) in self.moduli.iter().enumerate() {
+            let offset = self.offsets.get(i).copied().unwrap_or(0.0);
+            let count = fields.get(i).map_or(0.0, |field| field - offset);
+            if count < 0.0 || (i > 0 && count >= *modulus) {
+                return Err(invalid(format!(
+                    "field #{} must be between {offset} and {}",
+                    i + 1,
+                    offset + modulus - 1.0
+                )));
+            }
+            ticks = ticks * modulus + count;
+        }
+
+        let mut encoded = 0.0;
+        for (idx, (start, end)) in self.partitions.iter().enumerate() {
+            let within = ticks >= *start && ticks <= *end;
+            match partition {
+                Some(p) if p == idx + 1 => {
+                    if !within {
+                        return Err(invalid(format!("not within partition {p}")));
+                    }
+                    return Ok(encoded + ticks - start);
+                }
+                None if within => return Ok(encoded + ticks - start),
+                _ => encoded += end - start,
+            }
+        }
+
+        Err(invalid("not within any partition".to_string()))
+    }
+
+    /// Converts the encoded clock into an epoch
+    pub fn encoded_to_epoch(&self, encoded: f64) -> Epoch {
+        // Correlation records are sorted by increasing encoded clock, and the first one is extrapolated before its start.
+        let record = self
+            .coefficients
+            .iter()
+            .rev()
+            .find(|record| record[0] <= encoded)
+            .unwrap_or(&self.coefficients[0]);
+
+        let parallel_s = record[1] + record[2] * (encoded - record[0]) / self.ticks_per_count();
+        self.j2000() + parallel_s * TimeUnit::Second
+    }
+
+    /// Converts the epoch into the encoded clock
+    pub fn epoch_to_encoded(&self, epoch: Epoch) -> f64 {
+        let parallel_s = (epoch - self.j2000()).to_seconds();
+
+        let record = self
+            .coefficients
+            .iter()
+            .rev()
+            .find(|record| record[1] <= parallel_s)
+            .unwrap_or(&self.coefficients[0]);
+
+        record[0] + (parallel_s - record[1]) * self.ticks_per_count() / record[2]
+    }
+
+    /// Converts the clock string into an epoch
+    pub fn to_epoch(&self, sclk: &str) -> Result<Epoch, SCLKError> {
+        Ok(self.encoded_to_epoch(self.encode(sclk)?))
+    }
+
+    /// Converts the epoch into a clock string, rounded to the nearest tick, e.g. `1/1465644281.165`.
+    pub fn from_epoch(&self, epoch: Epoch) -> Result<String, SCLKError> {
+        ensure!(
+            !self.moduli.is_empty(),
+            InvalidKernelSnafu {
+                reason: format!("clock {} has no fields", self.clock_id)
+            }
+        );
+
+        let mut encoded = self.epoch_to_encoded(epoch).round();
+
+        for (idx, (start, end)) in self.partitions.iter().enumerate() {
+            if encoded <= end - start {
+                // Found the partition, so decompose the ticks into each field.
+                let mut ticks = start + encoded;
+                let mut fields = vec![0.0; self.moduli.len()];
+                for i in (1..self.moduli.len()).rev() {
+                    fields[i] = ticks % self.moduli[i];
+                    ticks = (ticks / self.moduli[i]).floor();
+                }
+                fields[0] = ticks;
+
+                let mut sclk = format!("{}/", idx + 1);
+                for (i, field) in fields.iter().enumerate() {
+                    let offset = self.offsets.get(i).copied().unwrap_or(0.0);
+                    // Zero pad each field to the width of its largest value, as in SPICE
+                    let width = format!("{}", offset + self.moduli[i] - 1.0).len();
+                    if i > 0 {
+                        sclk.push(self.delimiter);
+                    }
+                    sclk += &format!("{:0width$}", field + offset);
+                }
+
+                return Ok(sclk);
+            }
+            encoded -= end - start;
+        }
+
+        Err(SCLKError::OutsidePartitions {
+            clock_id: self.clock_id,
+            epoch,
+        })
+    }
+
+    fn try_from_item(clock_id: i32, item: &SCLKItem) -> Result<Self, SCLKError> {
+        let get = |param: SCLKParameter, keyword: &'static str| {
+            item.data
+                .get(&param)
+                .map(to_values)
+                .ok_or(SCLKError::MissingKeyword { clock_id, keyword })
+        };
+
+        let data_type = get(SCLKParameter::DataType, "SCLK_DATA_TYPE")?;
+        if data_type.first() != Some(&1.0) {
+            return Err(SCLKError::UnsupportedClockType {
+                clock_id,
+                data_type: data_type.first().copied().unwrap_or(0.0) as i32,
+            });
+        }
+
+        let moduli = get(SCLKParameter::Moduli, "SCLK01_MODULI")?;
+        let offsets = get(SCLKParameter::Offsets, "SCLK01_OFFSETS")?;
+        let num_fields = get(SCLKParameter::NumFields, "SCLK01_N_FIELDS")?;
+        if num_fields.first() != Some(&(moduli.len() as f64)) || offsets.len() != moduli.len() {
+            return Err(SCLKError::InvalidKernel {
+                reason: format!("clock {clock_id} must have as many moduli and offsets as fields"),
+            });
+        }
+        if moduli.is_empty() || moduli.iter().any(|modulus| *modulus < 1.0) {
+            return Err(SCLKError::InvalidKernel {
+                reason: format!(
+                    "clock {clock_id} must have at least one field, of positive moduli"
+                ),
+            });
+        }
+
+        let starts = get(SCLKParameter::PartitionStart, "SCLK_PARTITION_START")?;
+        let ends = get(SCLKParameter::PartitionEnd, "SCLK_PARTITION_END")?;
+        if starts.len() != ends.len() || starts.is_empty() {
+            return Err(SCLKError::InvalidKernel {
+                reason: format!("clock {clock_id} must have as many partition starts as ends"),
+            });
+        }
+
+        let coefficients = get(SCLKParameter::Coefficients, "SCLK01_COEFFICIENTS")?;
+        if coefficients.len() % 3 != 0 || coefficients.is_empty() {
+            return Err(SCLKError::InvalidKernel {
+                reason: format!("clock {clock_id} coefficients must be triplets"),
+            });
+        }
+
+        // Defaults to TDB, as in SPICE
+        let time_scale = match item
+            .data
+            .get(&SCLKParameter::TimeSystem)
+            .map(to_values)
+            .and_then(|values| values.first().copied())
+        {
+            Some(system) if system == 2.0 => TimeScale::TT,
+            _ => TimeScale::TDB,
+        };
+
+        let delimiter = match item
+            .data
+            .get(&SCLKParameter::OutputDelimiter)
+            .map(to_values)
+            .and_then(|values| values.first().copied())
+        {
+            Some(delim) if delim == 2.0 => ':',
+            Some(delim) if delim == 3.0 => '-',
+            Some(delim) if delim == 4.0 => ',',
+            Some(delim) if delim == 5.0 => ' ',
+            _ => '.',
+        };
+
+        Ok(Self {
+            clock_id,
+            time_scale,
+            moduli,
+            offsets,
+            delimiter,
+            partitions: starts.into_iter().zip(ends).collect(),
+            coefficients: coefficients
+                .chunks_exact(3)
+                .map(|record| [record[0], record[1], record[2]])
+                .collect(),
+        })
+    }
+}
+
+impl fmt::Display for SpacecraftClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SCLK of spacecraft {} ({} fields, {} partitions, {} correlation records in {:?})",
+            self.spacecraft_id(),
+            self.moduli.len(),
+            self.partitions.len(),
+            self.coefficients.len(),
+            self.time_scale
+        )
+    }
+}
+
+/// Parses all of the type 1 spacecraft clocks of an SCLK kernel.
+pub fn parse_sclk<R: BufRead>(reader: &mut R) -> Result<Vec<SpacecraftClock>, SCLKError> {
+    let items =
+        parse_bytes::<_, SCLKItem>(reader, false).map_err(|e| SCLKError::InvalidKernel {
+            reason: e.to_string(),
+        })?;
+
+    let mut clocks = items
+        .iter()
+        .map(|(clock_id, item)| SpacecraftClock::try_from_item(*clock_id, item))
+        .collect::<Result<Vec<SpacecraftClock>, SCLKError>>()?;
+    clocks.sort_by_key(|clock| clock.clock_id);
+
+    Ok(clocks)
+}
+
+#[cfg(test)]
+mod ut_sclk {
+    use super::{parse_sclk, SCLKError};
+    use hifitime::{Epoch, TimeUnits};
+
+    const SCLK_KERNEL: &str = "KPL/SCLK
+
+\\begindata
+
+SCLK_KERNEL_ID           = ( @2025-01-01/00:00:00.00 )
+
+SCLK_DATA_TYPE_1234      = ( 1 )
+SCLK01_TIME_SYSTEM_1234  = ( 1 )
+SCLK01_N_FIELDS_1234     = ( 2 )
+SCLK01_MODULI_1234       = ( 4294967296 256 )
+SCLK01_OFFSETS_1234      = ( 0 0 )
+SCLK01_OUTPUT_DELIM_1234 = ( 1 )
+
+SCLK_PARTITION_START_1234 = ( 0.0000000000000E+00
+                              2.5600000000000E+04 )
+SCLK_PARTITION_END_1234   = ( 1.2800000000000E+04
+                              1.0995116277750E+12 )
+
+SCLK01_COEFFICIENTS_1234 = (
+    0.0000000000000E+00  7.8901920000000E+08  1.0000000000000E+00
+    1.2800000000000E+04  7.8902020000000E+08  1.0000100000000E+00 )
+
+\\begintext
+";
+
+    #[test]
+    fn sclk_conversions() {
+        let clocks = parse_sclk(&mut SCLK_KERNEL.as_bytes()).unwrap();
+        assert_eq!(clocks.len(), 1);
+        let clock = &clocks[0];
+        println!("{clock}");
+        assert_eq!(clock.spacecraft_id(), -1234);
+        assert_eq!(clock.partitions.len(), 2);
+        assert_eq!(clock.coefficients.len(), 2);
+
+        let start = Epoch::from_tdb_seconds(7.8901920000000E+08);
+
+        // Start of the first partition
+        assert_eq!(clock.to_epoch("1/0.000").unwrap(), start);
+        // 10 counts and 128 ticks later is 10.5 seconds later
+        assert_eq!(clock.to_epoch("1/10.128").unwrap(), start + 10.5.seconds());
+        // Without the partition, the first partition with this reading is used
+        assert_eq!(clock.to_epoch("10.128").unwrap(), start + 10.5.seconds());
+        assert_eq!(
+            clock.from_epoch(start + 10.5.seconds()).unwrap(),
+            "1/0000000010.128"
+        );
+
+        // The second partition restarts at count 100, which is 50 counts after the end of the first partition,
+        // and its correlation record has a slightly faster rate.
+        let second = clock.to_epoch("2/110.000").unwrap();
+        let expected = Epoch::from_tdb_seconds(7.8902020000000E+08) + (10.0 * 1.00001).seconds();
+        assert!((second - expected).abs() < 1.microseconds());
+        assert_eq!(clock.from_epoch(second).unwrap(), "2/0000000110.000");
+
+        // Invalid clock strings
+        assert!(matches!(
+            clock.encode("1/10.256"),
+            Err(SCLKError::InvalidClockString { .. })
+        ));
+        assert!(clock.encode("3/10.000").is_err());
+        assert!(clock.encode("2/10.000").is_err());
+        assert!(clock.encode("1/10.1.1").is_err());
+
+        // A clock without fields cannot format an epoch.
+        let mut no_fields = clock.clone();
+        no_fields.moduli.clear();
+        no_fields.offsets.clear();
+        assert!(matches!(
+            no_fields.from_epoch(start),
+            Err(SCLKError::InvalidKernel { .. })
+        ));
+
+        // Nor can it be parsed.
+        let kernel = SCLK_KERNEL
+            .replace("N_FIELDS_1234     = ( 2 )", "N_FIELDS_1234     = ( 0 )")
+            .replace("( 4294967296 256 )", "( )")
+            .replace("OFFSETS_1234      = ( 0 0 )", "OFFSETS_1234      = ( )");
+        assert!(matches!(
+            parse_sclk(&mut kernel.as_bytes()),
+            Err(SCLKError::InvalidKernel { .. })
+        ));
+    }
+}
@@ -11,13 +11,14 @@
 pub mod daf;
 
 pub mod kpl;
+pub mod names;
 pub mod pck;
 pub mod spk;
 
 pub mod pretty_print;
 
 use self::{
-    daf::{daf::MutDAF, DAF},
+    daf::{daf::MutDAF, DAFSegment, DAF},
     pck::BPCSummaryRecord,
     spk::summary::SPKSummaryRecord,
 };
@@ -26,10 +27,14 @@ use self::{
 pub type SPK = DAF<SPKSummaryRecord>;
 /// Spacecraft Planetary Kernel, mutable, for editing DAF/SPK files
 pub type MutSPK = MutDAF<SPKSummaryRecord>;
+/// Segment of an SPK, used to build new SPK files
+pub type SPKSegment = DAFSegment<SPKSummaryRecord>;
 /// Binary Planetary Constant
 pub type BPC = DAF<BPCSummaryRecord>;
 /// Binary Planetary Constant, mutable, for editing DAF/PCK files
 pub type MutBPC = MutDAF<BPCSummaryRecord>;
+/// Segment of a BPC, used to build new BPC files
+pub type BPCSegment = DAFSegment<BPCSummaryRecord>;
 
 #[macro_export]
 macro_rules! parse_bytes_as {
@@ -0,0 +1,198 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use crate::NaifId;
+
+/// Standard NAIF body names and their IDs, as built into SPICE (cf. NAIF's `naif_ids.req`).
+///
+/// Several names may map to the same ID: the first name of an ID in this table is its preferred name, returned by
+/// [body_id_to_name]. Names are stored in their normalized form, cf. [normalize_body_name].
+pub const NAIF_BODY_NAMES: &[(&str, NaifId)] = &[
+    // Barycenters
+    ("SOLAR SYSTEM BARYCENTER", 0),
+    ("SSB", 0),
+    ("SOLAR_SYSTEM_BARYCENTER", 0),
+    ("MERCURY BARYCENTER", 1),
+    ("VENUS BARYCENTER", 2),
+    ("EARTH MOON BARYCENTER", 3),
+    ("EARTH-MOON BARYCENTER", 3),
+    ("EMB", 3),
+    ("EARTH BARYCENTER", 3),
+    ("MARS BARYCENTER", 4),
+    ("JUPITER BARYCENTER", 5),
+    ("SATURN BARYCENTER", 6),
+    ("URANUS BARYCENTER", 7),
+    ("NEPTUNE BARYCENTER", 8),
+    ("PLUTO BARYCENTER", 9),
+    // Sun and planets
+    ("SUN", 10),
+    ("MERCURY", 199),
+    ("VENUS", 299),
+    ("EARTH", 399),
+    ("MARS", 499),
+    ("JUPITER", 599),
+    ("SATURN", 699),
+    ("URANUS", 799),
+    ("NEPTUNE", 899),
+    ("PLUTO", 999),
+    // Satellites
+    ("MOON", 301),
+    ("PHOBOS", 401),
+    ("DEIMOS", 402),
+    ("IO", 501),
+    ("EUROPA", 502),
+    ("GANYMEDE", 503),
+    ("CALLISTO", 504),
+    ("AMALTHEA", 505),
+    ("HIMALIA", 506),
+    ("ELARA", 507),
+    ("PASIPHAE", 508),
+    ("SINOPE", 509),
+    ("LYSITHEA", 510),
+    ("CARME", 511),
+    ("ANANKE", 512),
+    ("LEDA", 513),
+    ("THEBE", 514),
+    ("ADRASTEA", 515),
+    ("METIS", 516),
+    ("MIMAS", 601),
+    ("ENCELADUS", 602),
+    ("TETHYS", 603),
+    ("DIONE", 604),
+    ("RHEA", 605),
+    ("TITAN", 606),
+    ("HYPERION", 607),
+    ("IAPETUS", 608),
+    ("PHOEBE", 609),
+    ("JANUS", 610),
+    ("EPIMETHEUS", 611),
+    ("HELENE", 612),
+    ("TELESTO", 613),
+    ("CALYPSO", 614),
+    ("ATLAS", 615),
+    ("PROMETHEUS", 616),
+    ("PANDORA", 617),
+    ("PAN", 618),
+    ("ARIEL", 701),
+    ("UMBRIEL", 702),
+    ("TITANIA", 703),
+    ("OBERON", 704),
+    ("MIRANDA", 705),
+    ("CORDELIA", 706),
+    ("OPHELIA", 707),
+    ("BIANCA", 708),
+    ("CRESSIDA", 709),
+    ("DESDEMONA", 710),
+    ("JULIET", 711),
+    ("PORTIA", 712),
+    ("ROSALIND", 713),
+    ("BELINDA", 714),
+    ("PUCK", 715),
+    ("TRITON", 801),
+    ("NEREID", 802),
+    ("NAIAD", 803),
+    ("THALASSA", 804),
+    ("DESPINA", 805),
+    ("GALATEA", 806),
+    ("LARISSA", 807),
+    ("PROTEUS", 808),
+    ("CHARON", 901),
+    ("NIX", 902),
+    ("HYDRA", 903),
+    ("KERBEROS", 904),
+    ("STYX", 905),
+    // Dwarf planets and asteroids
+    ("CERES", 2_000_001),
+    ("PALLAS", 2_000_002),
+    ("JUNO", 2_000_003),
+    ("VESTA", 2_000_004),
+    ("PSYCHE", 2_000_016),
+    ("EROS", 2_000_433),
+    ("BENNU", 2_101_955),
+    ("RYUGU", 2_162_173),
+    ("DIDYMOS", 2_065_803),
+    ("ARROKOTH", 2_486_958),
+    // Spacecraft
+    ("HST", -48),
+    ("HUBBLE SPACE TELESCOPE", -48),
+    ("CASSINI", -82),
+    ("NEW HORIZONS", -98),
+    ("JNO", -61),
+    ("MRO", -74),
+    ("MARS RECONNAISSANCE ORBITER", -74),
+    ("LRO", -85),
+    ("LUNAR RECONNAISSANCE ORBITER", -85),
+    ("JWST", -170),
+    ("JAMES WEBB SPACE TELESCOPE", -170),
+    ("OSIRIS-REX", -64),
+    ("MAVEN", -202),
+    ("PARKER SOLAR PROBE", -96),
+    ("SPP", -96),
+    ("VOYAGER 1", -31),
+    ("VOYAGER 2", -32),
+    ("CAPSTONE", -1176),
+];
+
+/// Normalizes a body name as SPICE does: leading and trailing blanks are removed, consecutive blanks are compressed
+/// to a single space, and the name is converted to uppercase.
+pub fn normalize_body_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_uppercase()
+}
+
+/// Returns the NAIF ID of the provided body name, from the standard NAIF names, case insensitively. Integer strings
+/// (e.g. `-85`) are returned as is, like SPICE's `bods2c`.
+pub fn body_name_to_id(name: &str) -> Option<NaifId> {
+    let normalized = normalize_body_name(name);
+    if let Ok(id) = normalized.parse::<NaifId>() {
+        return Some(id);
+    }
+
+    NAIF_BODY_NAMES
+        .iter()
+        .find(|(known, _)| *known == normalized)
+        .map(|(_, id)| *id)
+}
+
+/// Returns the preferred standard NAIF name of the provided body ID, if it is a known body.
+pub fn body_id_to_name(id: NaifId) -> Option<&'static str> {
+    NAIF_BODY_NAMES
+        .iter()
+        .find(|(_, known)| *known == id)
+        .map(|(name, _)| *name)
+}
+
+#[cfg(test)]
+mod ut_names {
+    use super::{body_id_to_name, body_name_to_id, normalize_body_name, NAIF_BODY_NAMES};
+
+    #[test]
+    fn standard_names() {
+        assert_eq!(body_name_to_id("Earth"), Some(399));
+        assert_eq!(body_name_to_id("  earth   moon  barycenter "), Some(3));
+        assert_eq!(body_name_to_id("EMB"), Some(3));
+        assert_eq!(body_name_to_id("-85"), Some(-85));
+        assert_eq!(body_name_to_id("Not a body"), None);
+
+        assert_eq!(body_id_to_name(301), Some("MOON"));
+        assert_eq!(body_id_to_name(3), Some("EARTH MOON BARYCENTER"));
+        assert_eq!(body_id_to_name(-123_456), None);
+
+        // All of the names are normalized, and unique
+        for (idx, (name, _)) in NAIF_BODY_NAMES.iter().enumerate() {
+            assert_eq!(normalize_body_name(name), *name);
+            assert!(NAIF_BODY_NAMES[idx + 1..]
+                .iter()
+                .all(|(other, _)| other != name));
+        }
+    }
+}
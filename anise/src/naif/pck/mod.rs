@@ -55,6 +55,8 @@ impl NAIFRecord for BPCSummaryRecord {}
 
 impl NAIFSummaryRecord for BPCSummaryRecord {
     const NAME: &'static str = "BPCSummaryRecord";
+    const IDWORD: &'static str = "PCK";
+    const NUM_INTEGERS: usize = 5;
 
     type Error = OrientationError;
 
@@ -63,13 +63,12 @@ impl NAIFPrettyPrint for BPC {
         };
 
         for (sno, summary) in self.data_summaries().unwrap().iter().enumerate() {
-            let name_rcrd = self.name_record().unwrap();
-            let name = name_rcrd.nth_name(sno, self.file_record().unwrap().summary_size());
             if summary.is_empty() {
                 continue;
             }
+            let name = self.nth_summary_name(sno).unwrap();
             rows.push(BpcRow {
-                name: name.to_string(),
+                name,
                 start_epoch: summary
                     .start_epoch()
                     .to_gregorian_str(time_scale)
@@ -102,14 +101,13 @@ impl NAIFPrettyPrint for SPK {
         };
 
         for (sno, summary) in self.data_summaries().unwrap().iter().enumerate() {
-            let name_rcrd = self.name_record().unwrap();
-            let name = name_rcrd.nth_name(sno, self.file_record().unwrap().summary_size());
             if summary.is_empty() {
                 continue;
             }
+            let name = self.nth_summary_name(sno).unwrap();
 
             rows.push(SpkRow {
-                name: name.to_string(),
+                name,
                 center: summary.center_frame_uid().to_string(),
                 start_epoch: summary
                     .start_epoch()
@@ -153,6 +153,8 @@ impl NAIFRecord for SPKSummaryRecord {}
 
 impl NAIFSummaryRecord for SPKSummaryRecord {
     const NAME: &'static str = "SPKSummaryRecord";
+    const IDWORD: &'static str = "SPK";
+    const NUM_INTEGERS: usize = 6;
 
     type Error = EphemerisError;
 
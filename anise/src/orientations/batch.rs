@@ -0,0 +1,95 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::thread;
+
+use hifitime::Epoch;
+
+use super::OrientationError;
+use crate::almanac::Almanac;
+use crate::math::rotation::Quaternion;
+use crate::math::Vector3;
+use crate::prelude::Frame;
+
+/// Batches smaller than this are not split across threads, since spawning them would cost more than the queries.
+const MIN_EPOCHS_PER_THREAD: usize = 512;
+
+/// Attitude of a frame with respect to another one at an epoch, as returned by [Almanac::rotate_batch].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AttitudeSample {
+    pub epoch: Epoch,
+    /// Rotation from the `from` frame to the `to` frame
+    pub q: Quaternion,
+    /// Angular velocity of the `to` frame with respect to the `from` frame, expressed in the `from` frame, if the
+    /// rotation provides its time derivative (constant rotations do not).
+    pub angular_velocity_rad_s: Option<Vector3>,
+}
+
+impl Almanac {
+    /// Returns the attitude of the `to` frame with respect to the `from` frame at each of the provided epochs, in the same
+    /// order, e.g. to render an attitude at a high frame rate over a long time span.
+    ///
+    /// Large batches are split into contiguous chunks of epochs which are queried in parallel, so that each thread keeps
+    /// hitting the same segments. The error of the first epoch which fails, if any, is returned.
+    pub fn rotate_batch(
+        &self,
+        from_frame: Frame,
+        to_frame: Frame,
+        epochs: &[Epoch],
+    ) -> Result<Vec<AttitudeSample>, OrientationError> {
+        let num_threads = thread::available_parallelism()
+            .map(|num| num.get())
+            .unwrap_or(1)
+            .min(epochs.len() / MIN_EPOCHS_PER_THREAD)
+            .max(1);
+
+        if num_threads == 1 {
+            return self.rotate_chunk(from_frame, to_frame, epochs);
+        }
+
+        let chunk_size = epochs.len().div_ceil(num_threads);
+        thread::scope(|scope| {
+            let handles = epochs
+                .chunks(chunk_size)
+                .map(|chunk| scope.spawn(move || self.rotate_chunk(from_frame, to_frame, chunk)))
+                .collect::<Vec<_>>();
+
+            let mut samples = Vec::with_capacity(epochs.len());
+            for handle in handles {
+                // Propagate the panic of a thread, if any.
+                samples.extend(
+                    handle
+                        .join()
+                        .unwrap_or_else(|e| std::panic::resume_unwind(e))?,
+                );
+            }
+            Ok(samples)
+        })
+    }
+
+    fn rotate_chunk(
+        &self,
+        from_frame: Frame,
+        to_frame: Frame,
+        epochs: &[Epoch],
+    ) -> Result<Vec<AttitudeSample>, OrientationError> {
+        epochs
+            .iter()
+            .map(|epoch| {
+                let dcm = self.rotate(from_frame, to_frame, *epoch)?;
+                Ok(AttitudeSample {
+                    epoch: *epoch,
+                    q: dcm.into(),
+                    angular_velocity_rad_s: dcm.angular_velocity_rad_s().ok(),
+                })
+            })
+            .collect()
+    }
+}
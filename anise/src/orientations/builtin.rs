@@ -0,0 +1,243 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use crate::constants::orientations::{
+    B1950, DE096, DE102, DE108, DE111, DE114, DE118, DE122, DE125, DE130, DE200, DE202, ECLIPB1950,
+    ECLIPJ2000, FK4, GALACTIC, J2000,
+};
+use crate::math::rotation::{r1, r2, r3, DCM};
+use crate::math::Matrix3;
+use crate::NaifId;
+
+/// Arcseconds to radians
+const ARCSEC_TO_RAD: f64 = core::f64::consts::PI / (180.0 * 3600.0);
+
+/// A built-in inertial frame, defined by a constant rotation from its parent frame, like the built-in inertial frames of
+/// SPICE (`chgirf`): these frames are available without loading any kernel, cf. [BUILTIN_ROTATIONS].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BuiltinRotation {
+    pub orientation_id: NaifId,
+    pub parent_id: NaifId,
+    /// Rotations about the X (1), Y (2), or Z (3) axis, with their angle in arcseconds: the rotation from the parent frame to
+    /// this frame is the product of these rotations in this order, e.g. `[(a, 3), (b, 1)]` is `R3(a) * R1(b)`.
+    pub rotations_arcsec: &'static [(f64, u8)],
+}
+
+impl BuiltinRotation {
+    /// Returns the rotation matrix from the parent frame to this frame.
+    pub fn rot_mat(&self) -> Matrix3 {
+        self.rotations_arcsec
+            .iter()
+            .fold(Matrix3::identity(), |rot_mat, (angle_arcsec, axis)| {
+                let angle_rad = angle_arcsec * ARCSEC_TO_RAD;
+                rot_mat
+                    * match axis {
+                        1 => r1(angle_rad),
+                        2 => r2(angle_rad),
+                        _ => r3(angle_rad),
+                    }
+            })
+    }
+
+    /// Returns the DCM from the parent frame to this frame, as returned by `Almanac::rotation_to_parent`.
+    pub fn dcm(&self) -> DCM {
+        DCM {
+            rot_mat: self.rot_mat(),
+            rot_mat_dt: None,
+            from: self.parent_id,
+            to: self.orientation_id,
+        }
+    }
+}
+
+/// Registry of the built-in inertial frames, all of which lead to [J2000], with the definitions of SPICE.
+///
+/// # Caveats
+/// + [B1950] is the mean equator and equinox of B1950 obtained with the IAU 1976 precession model, and [FK4] only adds the
+///   equinox offset of Fricke to it. Neither the elliptic terms of aberration of the FK4 catalog positions nor the spin of
+///   the FK4 system are modeled, so converting FK4 catalog positions to J2000 requires more than this constant rotation.
+/// + [GALACTIC] is the Galactic System II defined with respect to [FK4] (IAU 1958), not with respect to the ICRS.
+/// + The historical DE frames are the offsets of their equinox from [B1950] (or their identity with [J2000]), as
+///   documented in their constants.
+pub const BUILTIN_ROTATIONS: [BuiltinRotation; 16] = [
+    BuiltinRotation {
+        orientation_id: ECLIPJ2000,
+        parent_id: J2000,
+        rotations_arcsec: &[(84381.448, 1)],
+    },
+    BuiltinRotation {
+        orientation_id: B1950,
+        parent_id: J2000,
+        rotations_arcsec: &[
+            (-1152.84248596724, 3),
+            (1002.26108439117, 2),
+            (-1153.04066200330, 3),
+        ],
+    },
+    BuiltinRotation {
+        orientation_id: FK4,
+        parent_id: B1950,
+        rotations_arcsec: &[(0.525, 3)],
+    },
+    BuiltinRotation {
+        orientation_id: ECLIPB1950,
+        parent_id: B1950,
+        rotations_arcsec: &[(84404.836, 1)],
+    },
+    BuiltinRotation {
+        orientation_id: GALACTIC,
+        parent_id: FK4,
+        rotations_arcsec: &[(1177200.0, 3), (225360.0, 1), (1016100.0, 3)],
+    },
+    BuiltinRotation {
+        orientation_id: DE118,
+        parent_id: B1950,
+        rotations_arcsec: &[(0.53155, 3)],
+    },
+    BuiltinRotation {
+        orientation_id: DE096,
+        parent_id: B1950,
+        rotations_arcsec: &[(0.4107, 3)],
+    },
+    BuiltinRotation {
+        orientation_id: DE102,
+        parent_id: B1950,
+        rotations_arcsec: &[(0.1359, 3)],
+    },
+    BuiltinRotation {
+        orientation_id: DE108,
+        parent_id: B1950,
+        rotations_arcsec: &[(0.4775, 3)],
+    },
+    BuiltinRotation {
+        orientation_id: DE111,
+        parent_id: B1950,
+        rotations_arcsec: &[(0.5880, 3)],
+    },
+    BuiltinRotation {
+        orientation_id: DE114,
+        parent_id: B1950,
+        rotations_arcsec: &[(0.5529, 3)],
+    },
+    BuiltinRotation {
+        orientation_id: DE122,
+        parent_id: B1950,
+        rotations_arcsec: &[(0.5316, 3)],
+    },
+    BuiltinRotation {
+        orientation_id: DE125,
+        parent_id: B1950,
+        rotations_arcsec: &[(0.5754, 3)],
+    },
+    BuiltinRotation {
+        orientation_id: DE130,
+        parent_id: B1950,
+        rotations_arcsec: &[(0.5247, 3)],
+    },
+    BuiltinRotation {
+        orientation_id: DE200,
+        parent_id: J2000,
+        rotations_arcsec: &[],
+    },
+    BuiltinRotation {
+        orientation_id: DE202,
+        parent_id: J2000,
+        rotations_arcsec: &[],
+    },
+];
+
+/// Returns the definition of this built-in inertial frame, if it is one, cf. [BUILTIN_ROTATIONS].
+pub fn builtin_rotation(orientation_id: NaifId) -> Option<&'static BuiltinRotation> {
+    BUILTIN_ROTATIONS
+        .iter()
+        .find(|builtin| builtin.orientation_id == orientation_id)
+}
+
+#[cfg(test)]
+mod ut_builtin {
+    use super::{builtin_rotation, BUILTIN_ROTATIONS};
+    use crate::constants::orientations::{
+        B1950, ECLIPJ2000, FK4, GALACTIC, J2000, J2000_TO_ECLIPJ2000_ANGLE_RAD,
+    };
+    use crate::math::rotation::r1;
+    use crate::math::{Matrix3, Vector3};
+    use crate::prelude::{Almanac, Frame};
+    use hifitime::Epoch;
+
+    #[test]
+    fn builtin_rotations() {
+        // All of the built-in frames are valid rotations which lead to J2000.
+        for builtin in &BUILTIN_ROTATIONS {
+            let rot_mat = builtin.rot_mat();
+            assert!((rot_mat.determinant() - 1.0).abs() < 1e-12);
+
+            let mut parent_id = builtin.parent_id;
+            while parent_id != J2000 {
+                parent_id = builtin_rotation(parent_id).unwrap().parent_id;
+            }
+        }
+
+        let ecliptic = builtin_rotation(ECLIPJ2000).unwrap().dcm();
+        assert_eq!(ecliptic.from, J2000);
+        assert_eq!(ecliptic.to, ECLIPJ2000);
+        assert!((ecliptic.rot_mat - r1(J2000_TO_ECLIPJ2000_ANGLE_RAD)).norm() < 1e-15);
+
+        // The J2000 vernal equinox has precessed by about 0.64 degrees in right ascension since B1950.
+        let j2000_x_in_b1950 = builtin_rotation(B1950).unwrap().rot_mat() * Vector3::x();
+        assert!((j2000_x_in_b1950.y.atan2(j2000_x_in_b1950.x).to_degrees() - 0.6406).abs() < 1e-3);
+        // First element of the B1950 to J2000 precession matrix of the Explanatory Supplement
+        assert!(
+            (builtin_rotation(B1950).unwrap().rot_mat()[(0, 0)] - 0.9999257079523629).abs() < 1e-15
+        );
+
+        // The North Galactic pole is at 192.25 degrees of right ascension and 27.4 degrees of declination in FK4.
+        let galactic = builtin_rotation(GALACTIC).unwrap();
+        assert_eq!(galactic.parent_id, FK4);
+        let pole = galactic.rot_mat().transpose() * Vector3::z();
+        assert!((pole.y.atan2(pole.x).to_degrees() - 192.25).abs() < 1e-12);
+        assert!((pole.z.asin().to_degrees() - 27.4).abs() < 1e-12);
+
+        assert!(builtin_rotation(J2000).is_none());
+    }
+
+    #[test]
+    fn rotate_builtin_frames() {
+        // The built-in frames do not need any loaded data.
+        let almanac = Almanac::default();
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+
+        let gal_to_j2000 = almanac
+            .rotate(
+                Frame::from_orient_ssb(GALACTIC),
+                Frame::from_orient_ssb(J2000),
+                epoch,
+            )
+            .unwrap();
+        assert_eq!(gal_to_j2000.from, GALACTIC);
+        assert_eq!(gal_to_j2000.to, J2000);
+
+        let j2000_to_gal = [GALACTIC, FK4, B1950]
+            .iter()
+            .map(|id| builtin_rotation(*id).unwrap().rot_mat())
+            .fold(Matrix3::identity(), |rot_mat, builtin| rot_mat * builtin);
+        assert!((gal_to_j2000.rot_mat - j2000_to_gal.transpose()).norm() < 1e-15);
+
+        // Between two branches of the built-in frames
+        let gal_to_ecliptic = almanac
+            .rotate(
+                Frame::from_orient_ssb(GALACTIC),
+                Frame::from_orient_ssb(ECLIPJ2000),
+                epoch,
+            )
+            .unwrap();
+        let expected = builtin_rotation(ECLIPJ2000).unwrap().rot_mat() * j2000_to_gal.transpose();
+        assert!((gal_to_ecliptic.rot_mat - expected).norm() < 1e-15);
+    }
+}
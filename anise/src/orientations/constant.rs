@@ -0,0 +1,141 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use snafu::{ensure, ResultExt};
+
+use super::{NamedFrameOnlySnafu, OrientationDataSetSnafu, OrientationError};
+use crate::almanac::Almanac;
+use crate::math::rotation::{Quaternion, DCM};
+use crate::math::Matrix3;
+use crate::NaifId;
+
+/// A constant orientation of a frame with respect to its parent frame, e.g. the alignment of an instrument on a spacecraft,
+/// like a SPICE TK frame.
+///
+/// It is stored as Euler parameters in the Euler parameter data set of the Almanac, so it is honored by the orientation
+/// paths, and may be saved to an EPA file like the frames converted from a KPL/FK file.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ConstantOrientation {
+    /// Rotation from this frame (`from`) to its parent frame (`to`)
+    pub rotation: Quaternion,
+}
+
+impl ConstantOrientation {
+    /// Initializes a new constant orientation from the matrix which rotates vectors from this frame to its parent frame.
+    pub fn from_matrix(rot_mat: Matrix3, orientation_id: NaifId, parent_id: NaifId) -> Self {
+        DCM {
+            rot_mat,
+            rot_mat_dt: None,
+            from: orientation_id,
+            to: parent_id,
+        }
+        .into()
+    }
+
+    /// Orientation ID of this frame
+    pub fn orientation_id(&self) -> NaifId {
+        self.rotation.from
+    }
+
+    /// Orientation ID of the parent frame
+    pub fn parent_id(&self) -> NaifId {
+        self.rotation.to
+    }
+}
+
+impl From<Quaternion> for ConstantOrientation {
+    /// The quaternion must rotate from this frame (`from`) to its parent frame (`to`).
+    fn from(rotation: Quaternion) -> Self {
+        Self { rotation }
+    }
+}
+
+impl From<DCM> for ConstantOrientation {
+    /// The DCM must rotate from this frame (`from`) to its parent frame (`to`). Its time derivative, if any, is ignored.
+    fn from(dcm: DCM) -> Self {
+        Self {
+            rotation: dcm.into(),
+        }
+    }
+}
+
+impl From<ConstantOrientation> for DCM {
+    fn from(orientation: ConstantOrientation) -> Self {
+        orientation.rotation.into()
+    }
+}
+
+impl Almanac {
+    /// Returns a copy of this Almanac where the provided constant orientation is added to the Euler parameter data set,
+    /// optionally with a name.
+    ///
+    /// # Note
+    /// The BPC and planetary data have precedence over the Euler parameter data, so the orientation ID should not be
+    /// one of theirs.
+    pub fn with_constant_orientation(
+        &self,
+        orientation: ConstantOrientation,
+        name: Option<&str>,
+    ) -> Result<Self, OrientationError> {
+        let mut me = self.clone();
+        me.euler_param_data
+            .push(
+                orientation.rotation,
+                Some(orientation.orientation_id()),
+                name,
+            )
+            .context(OrientationDataSetSnafu)?;
+        Ok(me)
+    }
+
+    /// Returns the constant orientation of the provided orientation ID with respect to its parent, as stored in the
+    /// Euler parameter data set.
+    pub fn constant_orientation(
+        &self,
+        orientation_id: NaifId,
+    ) -> Result<ConstantOrientation, OrientationError> {
+        Ok(self.euler_parameters_to_parent(orientation_id)?.into())
+    }
+
+    /// Returns the Euler parameters of the provided orientation ID with respect to its parent.
+    ///
+    /// Entries whose parent is the orientation itself only name a frame rotated by other data, e.g. the BPC and CK frames of a
+    /// converted FK, so they are not an edge of the orientation tree and this returns an error for them.
+    pub(crate) fn euler_parameters_to_parent(
+        &self,
+        orientation_id: NaifId,
+    ) -> Result<Quaternion, OrientationError> {
+        let q = self
+            .euler_param_data
+            .get_by_id(orientation_id)
+            .context(OrientationDataSetSnafu)?;
+        ensure!(q.from != q.to, NamedFrameOnlySnafu { id: orientation_id });
+        Ok(q)
+    }
+}
+
+#[cfg(test)]
+mod ut_constant {
+    use super::ConstantOrientation;
+    use crate::math::rotation::{r3, DCM};
+
+    #[test]
+    fn constant_orientation_roundtrip() {
+        let rot_mat = r3(30.0_f64.to_radians());
+        let orientation = ConstantOrientation::from_matrix(rot_mat, -1001, 1);
+        assert_eq!(orientation.orientation_id(), -1001);
+        assert_eq!(orientation.parent_id(), 1);
+
+        let dcm = DCM::from(orientation);
+        assert_eq!(dcm.from, -1001);
+        assert_eq!(dcm.to, 1);
+        assert!((dcm.rot_mat - rot_mat).norm() < 1e-12);
+    }
+}
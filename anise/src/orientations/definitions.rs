@@ -0,0 +1,134 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::path::PathBuf;
+
+use super::ConstantOrientation;
+use crate::math::rotation::{EulerSequence, DCM};
+use crate::math::Vector3;
+use crate::structure::dataset::{DataSetError, DataSetType};
+use crate::structure::metadata::Metadata;
+use crate::structure::planetocentric::phaseangle::PhaseAngle;
+use crate::structure::planetocentric::PlanetaryData;
+use crate::structure::{EulerParameterDataSet, PlanetaryDataSet};
+use crate::NaifId;
+
+/// Reference frame definitions authored programmatically, e.g. the frames of a spacecraft and its instruments, which can be
+/// saved to ANISE files, shared, and loaded in an Almanac like the frames converted from KPL/FK and KPL/TPC files.
+///
+/// + The constant rotations are stored in an Euler parameter data set, saved to an EPA file.
+/// + The rotation models (right ascension and declination of the pole, and prime meridian, as polynomials of time) are
+///   stored in a planetary data set, saved to a PCA file.
+///
+/// Each frame may have a name, which is added to the look up table of its data set, cf. `Almanac::orientation_id`.
+/// The data sets may also be used directly, cf. `Almanac::with_euler_parameters` and `Almanac::with_planetary_data`.
+#[derive(Clone, Default)]
+pub struct FrameDefinitions {
+    pub constant_data: EulerParameterDataSet,
+    pub model_data: PlanetaryDataSet,
+}
+
+impl FrameDefinitions {
+    /// Adds the constant orientation of a frame with respect to its parent.
+    pub fn add_constant(
+        &mut self,
+        orientation: ConstantOrientation,
+        name: Option<&str>,
+    ) -> Result<(), DataSetError> {
+        self.constant_data.push(
+            orientation.rotation,
+            Some(orientation.orientation_id()),
+            name,
+        )
+    }
+
+    /// Adds a frame whose constant orientation with respect to its parent is defined by Euler angles (in degrees) in the
+    /// provided sequence.
+    pub fn add_euler_angles(
+        &mut self,
+        orientation_id: NaifId,
+        parent_id: NaifId,
+        sequence: EulerSequence,
+        angles_deg: [f64; 3],
+        name: Option<&str>,
+    ) -> Result<(), DataSetError> {
+        let angles_rad = Vector3::from(angles_deg.map(f64::to_radians));
+        // The rotation matrix of the sequence rotates vectors from the parent frame into this frame.
+        let dcm = DCM {
+            rot_mat: sequence.rotation_matrix(angles_rad).transpose(),
+            rot_mat_dt: None,
+            from: orientation_id,
+            to: parent_id,
+        };
+        self.add_constant(dcm.into(), name)
+    }
+
+    /// Adds a frame whose orientation with respect to its parent is defined by the right ascension and declination of its
+    /// pole, and by its prime meridian, like the IAU rotation models of a KPL/TPC file.
+    ///
+    /// Each angle is a polynomial of time in degrees: its offset, rate, and acceleration, where time is in centuries since
+    /// J2000 TDB for the pole, and in days since J2000 TDB for the prime meridian.
+    pub fn add_rotation_model(
+        &mut self,
+        orientation_id: NaifId,
+        parent_id: NaifId,
+        pole_right_ascension_deg: [f64; 3],
+        pole_declination_deg: [f64; 3],
+        prime_meridian_deg: [f64; 3],
+        name: Option<&str>,
+    ) -> Result<(), DataSetError> {
+        let model = PlanetaryData {
+            object_id: orientation_id,
+            parent_id,
+            pole_right_ascension: PhaseAngle::maybe_new(&pole_right_ascension_deg),
+            pole_declination: PhaseAngle::maybe_new(&pole_declination_deg),
+            prime_meridian: PhaseAngle::maybe_new(&prime_meridian_deg),
+            ..Default::default()
+        };
+        self.model_data.push(model, Some(orientation_id), name)
+    }
+
+    /// Returns the number of frames defined
+    pub fn len(&self) -> usize {
+        self.constant_data.len() + self.model_data.len()
+    }
+
+    /// Returns true if no frame is defined
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Saves the constant rotations to the EPA file and the rotation models to the PCA file, skipping either if there is no
+    /// such frame. If overwrite is set to false and a file already exists, this function will return an error.
+    pub fn save_as(
+        &self,
+        epa_filename: &PathBuf,
+        pca_filename: &PathBuf,
+        overwrite: bool,
+    ) -> Result<(), DataSetError> {
+        if !self.constant_data.is_empty() {
+            let mut dataset = self.constant_data.clone();
+            dataset.set_crc32();
+            dataset.metadata = Metadata::default();
+            dataset.metadata.dataset_type = DataSetType::EulerParameterData;
+            dataset.save_as(epa_filename, overwrite)?;
+        }
+
+        if !self.model_data.is_empty() {
+            let mut dataset = self.model_data.clone();
+            dataset.set_crc32();
+            dataset.metadata = Metadata::default();
+            dataset.metadata.dataset_type = DataSetType::PlanetaryData;
+            dataset.save_as(pca_filename, overwrite)?;
+        }
+
+        Ok(())
+    }
+}
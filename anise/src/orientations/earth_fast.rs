@@ -0,0 +1,258 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::Epoch;
+
+use crate::constants::orientations::{ITRF93_FAST, J2000};
+use crate::math::rotation::{r1, r2, r3, r3_dot, DCM};
+use crate::math::Matrix3;
+
+/// Arcseconds to radians
+const ARCSEC_TO_RAD: f64 = core::f64::consts::PI / (180.0 * 3600.0);
+
+/// Julian date of the J2000 reference epoch
+const JD_J2000: f64 = 2_451_545.0;
+
+/// Rotation rate of the Greenwich sidereal time, in radians per second of UT1
+const SIDEREAL_RATE_RAD_S: f64 = 360.985_647_366_29 * core::f64::consts::PI / 180.0 / 86_400.0;
+
+/// Largest 18 of the 106 terms of the IAU 1980 nutation series: the multipliers of the Delaunay arguments (l, l', F, D, Ω),
+/// then the coefficients of the nutation in longitude (constant and rate per century) and of the nutation in obliquity
+/// (likewise), in units of 0.1 milliarcseconds. The 88 dropped terms are each smaller than 5 milliarcseconds.
+#[rustfmt::skip]
+const NUTATION_TERMS: [([f64; 5], [f64; 4]); 18] = [
+    ([ 0.0,  0.0, 0.0,  0.0, 1.0], [-171_996.0, -174.2, 92_025.0,  8.9]),
+    ([ 0.0,  0.0, 2.0, -2.0, 2.0], [ -13_187.0,   -1.6,  5_736.0, -3.1]),
+    ([ 0.0,  0.0, 2.0,  0.0, 2.0], [  -2_274.0,   -0.2,    977.0, -0.5]),
+    ([ 0.0,  0.0, 0.0,  0.0, 2.0], [   2_062.0,    0.2,   -895.0,  0.5]),
+    ([ 0.0,  1.0, 0.0,  0.0, 0.0], [   1_426.0,   -3.4,     54.0, -0.1]),
+    ([ 1.0,  0.0, 0.0,  0.0, 0.0], [     712.0,    0.1,     -7.0,  0.0]),
+    ([ 0.0,  1.0, 2.0, -2.0, 2.0], [    -517.0,    1.2,    224.0, -0.6]),
+    ([ 0.0,  0.0, 2.0,  0.0, 1.0], [    -386.0,   -0.4,    200.0,  0.0]),
+    ([ 1.0,  0.0, 2.0,  0.0, 2.0], [    -301.0,    0.0,    129.0, -0.1]),
+    ([ 0.0, -1.0, 2.0, -2.0, 2.0], [     217.0,   -0.5,    -95.0,  0.3]),
+    ([ 1.0,  0.0, 0.0, -2.0, 0.0], [    -158.0,    0.0,     -1.0,  0.0]),
+    ([ 0.0,  0.0, 2.0, -2.0, 1.0], [     129.0,    0.1,    -70.0,  0.0]),
+    ([-1.0,  0.0, 2.0,  0.0, 2.0], [     123.0,    0.0,    -53.0,  0.0]),
+    ([ 1.0,  0.0, 0.0,  0.0, 1.0], [      63.0,    0.1,    -33.0,  0.0]),
+    ([ 0.0,  0.0, 0.0,  2.0, 0.0], [      63.0,    0.0,     -2.0,  0.0]),
+    ([-1.0,  0.0, 2.0,  2.0, 2.0], [     -59.0,    0.0,     26.0,  0.0]),
+    ([-1.0,  0.0, 0.0,  0.0, 1.0], [     -58.0,   -0.1,     32.0,  0.0]),
+    ([ 1.0,  0.0, 2.0,  0.0, 1.0], [     -51.0,    0.0,     27.0,  0.0]),
+];
+
+/// Truncation of the nutation series of the analytic orientation models, trading accuracy for speed, cf.
+/// [earth_fast_rotation_truncated] and `Almanac::with_nutation_truncation`.
+///
+/// # Fast Earth orientation
+/// Only the 18 largest of the 106 terms of the IAU 1980 nutation series are implemented, so `Eighteen` is the most accurate
+/// truncation: each of the dropped terms is smaller than 5 milliarcseconds (0.17 m at 7000 km).
+///
+/// The errors of the other truncations are the upper bounds with respect to `Eighteen`, i.e. the sums of the amplitudes of
+/// the dropped terms. The direction of the pole is off by at most `sqrt((Δψ sin ε)² + Δε²)`, i.e. about 0.04 arcseconds
+/// (1.3 m at 7000 km) with ten terms, and 0.2 arcseconds (6.6 m at 7000 km) with four terms.
+///
+/// # Planetary data
+/// The right ascension, declination, and prime meridian of the IAU rotation models, e.g. that of the Moon, only keep their
+/// largest nutation and precession terms, so each angle is off by at most the sum of the amplitudes of its dropped terms.
+/// The Moon has 13 terms, so `Eighteen` evaluates all of them.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum NutationTruncation {
+    /// The 18 largest terms, i.e. all of the terms implemented for the IAU 1980 nutation
+    #[default]
+    Eighteen,
+    /// The 10 largest terms: errors below 0.071 arcseconds in longitude (Δψ) and 0.025 arcseconds in obliquity (Δε)
+    Ten,
+    /// The 4 largest terms: errors below 0.429 arcseconds in longitude (Δψ) and 0.096 arcseconds in obliquity (Δε)
+    Four,
+}
+
+impl NutationTruncation {
+    /// Number of terms of the series evaluated with this truncation
+    pub const fn num_terms(&self) -> usize {
+        match self {
+            Self::Eighteen => NUTATION_TERMS.len(),
+            Self::Ten => 10,
+            Self::Four => 4,
+        }
+    }
+}
+
+/// Returns the Delaunay arguments (l, l', F, D, Ω) of the IAU 1980 nutation, in radians, at the provided TDB centuries
+/// past J2000.
+fn delaunay_arguments(t: f64) -> [f64; 5] {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    [
+        134.962_981_39 + (1325.0 * 360.0 + 198.867_398_1) * t + 0.008_697_2 * t2 + 1.78e-5 * t3,
+        357.527_723_33 + (99.0 * 360.0 + 359.050_340_0) * t - 0.000_160_3 * t2 - 3.3e-6 * t3,
+        93.271_910_28 + (1342.0 * 360.0 + 82.017_538_1) * t - 0.003_682_5 * t2 + 3.1e-6 * t3,
+        297.850_363_06 + (1236.0 * 360.0 + 307.111_480_0) * t - 0.001_914_2 * t2 + 5.3e-6 * t3,
+        125.044_522_22 - (5.0 * 360.0 + 134.136_260_8) * t + 0.002_070_8 * t2 + 2.2e-6 * t3,
+    ]
+    .map(|arg_deg: f64| (arg_deg % 360.0).to_radians())
+}
+
+/// Returns the nutation in longitude (Δψ) and in obliquity (Δε) of the IAU 1980 model at the provided epoch, in radians,
+/// evaluated with the provided truncation of its series.
+pub fn nutation_iau1980(epoch: Epoch, truncation: NutationTruncation) -> (f64, f64) {
+    let t = epoch.to_et_seconds() / (86_400.0 * 36_525.0);
+    nutation_series(t, &delaunay_arguments(t), truncation)
+}
+
+fn nutation_series(t: f64, delaunay: &[f64; 5], truncation: NutationTruncation) -> (f64, f64) {
+    let (mut dpsi, mut deps) = (0.0, 0.0);
+    for (multipliers, [psi, psi_dt, eps, eps_dt]) in
+        NUTATION_TERMS.iter().take(truncation.num_terms())
+    {
+        let arg: f64 = multipliers
+            .iter()
+            .zip(delaunay.iter())
+            .map(|(multiplier, angle)| multiplier * angle)
+            .sum();
+        dpsi += (psi + psi_dt * t) * arg.sin();
+        deps += (eps + eps_dt * t) * arg.cos();
+    }
+    (dpsi * 1e-4 * ARCSEC_TO_RAD, deps * 1e-4 * ARCSEC_TO_RAD)
+}
+
+/// Returns the rotation from J2000 to the fast Earth fixed frame ([ITRF93_FAST]) at the provided epoch, where UT1 is
+/// UTC plus the provided offset, in seconds.
+///
+/// # Model
+/// This chains the IAU 1976 precession, the largest 18 of the 106 terms of the IAU 1980 nutation, and the rotation by the Greenwich
+/// apparent sidereal time of the IAU 1982 model. Polar motion is ignored.
+///
+/// # Accuracy
+/// Compared to the ITRF93 high precision BPC, the error of the Earth pole (mostly polar motion) is below 0.6 arcseconds,
+/// i.e. about 10 meters and always under 20 meters at a low Earth orbit radius (7000 km). Every 0.1 second of error on
+/// UT1-UTC adds about 50 meters along the equator of such an orbit, so UT1-UTC should be set from the IERS bulletins.
+/// The time derivative only accounts for the rotation of the Earth.
+pub fn earth_fast_rotation(epoch: Epoch, ut1_minus_utc_s: f64) -> DCM {
+    earth_fast_rotation_truncated(epoch, ut1_minus_utc_s, NutationTruncation::Eighteen)
+}
+
+/// Returns the rotation of [earth_fast_rotation] where the nutation series is truncated as requested, e.g. to reduce the
+/// computation time on embedded systems at the cost of the accuracy documented in [NutationTruncation].
+pub fn earth_fast_rotation_truncated(
+    epoch: Epoch,
+    ut1_minus_utc_s: f64,
+    truncation: NutationTruncation,
+) -> DCM {
+    // Precession and nutation use TT centuries, approximated by TDB centuries.
+    let t = epoch.to_et_seconds() / (86_400.0 * 36_525.0);
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    // IAU 1976 precession angles
+    let zeta = (2306.2181 * t + 0.30188 * t2 + 0.017998 * t3) * ARCSEC_TO_RAD;
+    let z = (2306.2181 * t + 1.09468 * t2 + 0.018203 * t3) * ARCSEC_TO_RAD;
+    let theta = (2004.3109 * t - 0.42665 * t2 - 0.041833 * t3) * ARCSEC_TO_RAD;
+    let precession = r3(-z) * r2(theta) * r3(-zeta);
+
+    let delaunay = delaunay_arguments(t);
+    let (dpsi, deps) = nutation_series(t, &delaunay, truncation);
+
+    let mean_obliquity = (84_381.448 - 46.8150 * t - 0.00059 * t2 + 0.001813 * t3) * ARCSEC_TO_RAD;
+    let nutation = r1(-(mean_obliquity + deps)) * r3(-dpsi) * r1(mean_obliquity);
+
+    // Greenwich apparent sidereal time, from the UT1 days since J2000
+    let ut1_days = epoch.to_jde_utc_days() - JD_J2000 + ut1_minus_utc_s / 86_400.0;
+    let tu = ut1_days / 36_525.0;
+    let gmst_deg = 280.460_618_37 + 360.985_647_366_29 * ut1_days + 0.000_387_933 * tu * tu
+        - tu * tu * tu / 38_710_000.0;
+    let omega = delaunay[4];
+    let equation_of_equinoxes = dpsi * mean_obliquity.cos()
+        + (0.00264 * omega.sin() + 0.000063 * (2.0 * omega).sin()) * ARCSEC_TO_RAD;
+    let gast = (gmst_deg % 360.0).to_radians() + equation_of_equinoxes;
+
+    let precession_nutation: Matrix3 = nutation * precession;
+
+    DCM {
+        rot_mat: r3(gast) * precession_nutation,
+        rot_mat_dt: Some(SIDEREAL_RATE_RAD_S * r3_dot(gast) * precession_nutation),
+        from: J2000,
+        to: ITRF93_FAST,
+    }
+}
+
+#[cfg(test)]
+mod ut_earth_fast {
+    use super::{
+        earth_fast_rotation, earth_fast_rotation_truncated, nutation_iau1980, NutationTruncation,
+        ARCSEC_TO_RAD,
+    };
+    use hifitime::{Epoch, TimeSeries, Unit};
+
+    #[test]
+    fn earth_fast_is_a_rotation() {
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 20);
+        let dcm = earth_fast_rotation(epoch, 0.0);
+        assert!(
+            (dcm.rot_mat * dcm.rot_mat.transpose() - crate::math::Matrix3::identity()).norm()
+                < 1e-12
+        );
+        assert!((dcm.rot_mat.determinant() - 1.0).abs() < 1e-12);
+
+        // Half a sidereal day later, the Earth fixed X axis points the other way.
+        let later = earth_fast_rotation(epoch + hifitime::Unit::Second * 43_082.045, 0.0);
+        let x_axis = dcm.rot_mat.row(0);
+        assert!((x_axis.dot(&later.rot_mat.row(0)) + 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn nutation_truncation() {
+        let start = Epoch::from_gregorian_utc_at_midnight(2000, 1, 1);
+        let end = Epoch::from_gregorian_utc_at_midnight(2030, 1, 1);
+
+        let obliquity = 84_381.448 * ARCSEC_TO_RAD;
+        let mut max_pole_err_arcsec = [0.0_f64; 2];
+
+        for epoch in TimeSeries::inclusive(start, end, Unit::Day * 7) {
+            let (dpsi, deps) = nutation_iau1980(epoch, NutationTruncation::Eighteen);
+            let full = earth_fast_rotation(epoch, 0.0);
+
+            for (i, (truncation, max_dpsi_arcsec, max_deps_arcsec)) in [
+                (NutationTruncation::Ten, 0.071, 0.025),
+                (NutationTruncation::Four, 0.429, 0.096),
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                let (trunc_dpsi, trunc_deps) = nutation_iau1980(epoch, truncation);
+                assert!((dpsi - trunc_dpsi).abs() / ARCSEC_TO_RAD < max_dpsi_arcsec);
+                assert!((deps - trunc_deps).abs() / ARCSEC_TO_RAD < max_deps_arcsec);
+
+                // The pole of the truncated model is within the documented bound of that of the full model.
+                let truncated = earth_fast_rotation_truncated(epoch, 0.0, truncation);
+                let pole_err_arcsec = full
+                    .rot_mat
+                    .row(2)
+                    .dot(&truncated.rot_mat.row(2))
+                    .clamp(-1.0, 1.0)
+                    .acos()
+                    / ARCSEC_TO_RAD;
+                let bound_arcsec =
+                    ((max_dpsi_arcsec * obliquity.sin()).powi(2) + max_deps_arcsec.powi(2)).sqrt();
+                assert!(
+                    pole_err_arcsec < bound_arcsec,
+                    "{truncation:?} @ {epoch}: {pole_err_arcsec} arcsec"
+                );
+                max_pole_err_arcsec[i] = max_pole_err_arcsec[i].max(pole_err_arcsec);
+            }
+        }
+
+        // Truncating more is less accurate, and the full series is the default.
+        assert!(max_pole_err_arcsec[0] > 0.0);
+        assert!(max_pole_err_arcsec[1] > max_pole_err_arcsec[0]);
+        assert_eq!(NutationTruncation::default(), NutationTruncation::Eighteen);
+        assert_eq!(NutationTruncation::Eighteen.num_terms(), 18);
+    }
+}
@@ -0,0 +1,65 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use crate::constants::orientations::{ICRF, J2000};
+use crate::math::rotation::{r1, r2, r3, DCM};
+
+/// Arcseconds to radians
+const ARCSEC_TO_RAD: f64 = core::f64::consts::PI / (180.0 * 3600.0);
+
+/// Frame bias between the ICRF and the mean equator and dynamical equinox of J2000 from the IERS Conventions (2010), section 5.5.6,
+/// in arcseconds: the offset of the ICRS right ascension origin (dα0), and the offsets of the J2000 pole in the ICRS (ξ0 and η0).
+pub const ICRF_FRAME_BIAS_ARCSEC: [f64; 3] = [-0.014_6, -0.016_617_0, -0.006_819_2];
+
+/// Returns the constant rotation from the J2000 frame (mean equator and dynamical equinox of J2000) to the ICRF ([ICRF]).
+///
+/// This is the transpose of the frame bias matrix `B = R1(-η0) R2(ξ0) R3(dα0)` of the IERS Conventions, which rotates the ICRF
+/// into J2000. The rotation is about 0.023 arcseconds, i.e. 0.11 microradians or about 17 kilometers at one astronomical unit.
+pub fn icrf_frame_bias() -> DCM {
+    let [d_alpha0, xi0, eta0] = ICRF_FRAME_BIAS_ARCSEC.map(|arcsec| arcsec * ARCSEC_TO_RAD);
+    let icrf_to_j2000 = r1(-eta0) * r2(xi0) * r3(d_alpha0);
+
+    DCM {
+        rot_mat: icrf_to_j2000.transpose(),
+        rot_mat_dt: None,
+        from: J2000,
+        to: ICRF,
+    }
+}
+
+#[cfg(test)]
+mod ut_frame_bias {
+    use super::{icrf_frame_bias, ARCSEC_TO_RAD};
+    use crate::math::{Matrix3, Vector3};
+
+    #[test]
+    fn frame_bias_is_small() {
+        let dcm = icrf_frame_bias();
+        assert!((dcm.rot_mat * dcm.rot_mat.transpose() - Matrix3::identity()).norm() < 1e-15);
+
+        // Rotation angle from the skew symmetric part of the matrix, which is precise for small angles
+        let m = dcm.rot_mat;
+        let axis_sin = Vector3::new(
+            m[(1, 2)] - m[(2, 1)],
+            m[(2, 0)] - m[(0, 2)],
+            m[(0, 1)] - m[(1, 0)],
+        ) / 2.0;
+        let angle_arcsec = axis_sin.norm().asin() / ARCSEC_TO_RAD;
+        assert!(
+            (0.02..0.03).contains(&angle_arcsec),
+            "frame bias is {angle_arcsec} arcsec"
+        );
+
+        // The first element of the bias matrix of the IERS Conventions (2010), eq. (5.A.3) or SOFA's iauBp00, is
+        // 0.9999999999999942, and the (0, 1) element of its transpose is 0.7078279744e-7.
+        assert!((dcm.rot_mat[(0, 0)] - 0.999_999_999_999_994_2).abs() < 1e-15);
+        assert!((dcm.rot_mat[(1, 0)] + 0.707_827_974_4e-7).abs() < 1e-16);
+    }
+}
@@ -12,13 +12,51 @@ use hifitime::Epoch;
 use snafu::prelude::*;
 
 use crate::{
-    errors::PhysicsError, math::interpolation::InterpolationError, naif::daf::DAFError,
-    prelude::FrameUid, structure::dataset::DataSetError,
+    errors::{ErrorCode, PhysicsError},
+    math::interpolation::InterpolationError,
+    naif::daf::DAFError,
+    prelude::FrameUid,
+    structure::dataset::DataSetError,
+    NaifId,
 };
 
+mod batch;
+mod builtin;
+mod constant;
+mod definitions;
+mod earth_fast;
+mod frame_bias;
+mod moon;
 mod paths;
+mod provider;
 mod rotate_to_parent;
 mod rotations;
+pub mod stk;
+mod two_vector;
+
+pub use batch::AttitudeSample;
+pub use builtin::{builtin_rotation, BuiltinRotation, BUILTIN_ROTATIONS};
+pub use constant::ConstantOrientation;
+pub use definitions::FrameDefinitions;
+pub use earth_fast::{
+    earth_fast_rotation, earth_fast_rotation_truncated, nutation_iau1980, NutationTruncation,
+};
+pub use frame_bias::{icrf_frame_bias, ICRF_FRAME_BIAS_ARCSEC};
+pub use moon::{MOON_PA_TO_ME_DE421_ARCSEC, MOON_PA_TO_ME_DE440_ARCSEC};
+pub use provider::OrientationProvider;
+pub(crate) use rotate_to_parent::RotationSource;
+pub use two_vector::{DirectionVector, FrameAxis, TwoVectorFrame};
+
+/// Policy on the time derivative of the DCM returned by a rotation query, cf. `Almanac::rotate_with_policy`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DerivativePolicy {
+    /// The query fails if none of the rotations of the chain provides its time derivative.
+    RequireDerivative,
+    /// Rotations without a time derivative (e.g. constant frames) are considered constant, so the derivative of the chain is the
+    /// best available one, and is zero if none of the rotations provides a derivative.
+    #[default]
+    BestEffort,
+}
 
 #[derive(Debug, Snafu, PartialEq)]
 #[snafu(visibility(pub(crate)))]
@@ -64,4 +102,43 @@ pub enum OrientationError {
     },
     #[snafu(display("unknown orientation ID associated with `{name}`"))]
     OrientationNameToId { name: String },
+    #[snafu(display(
+        "{id} is not a spacecraft frame (the frames of the spacecraft -N are numbered from -N000 to -N999)"
+    ))]
+    NotSpacecraftFrame { id: NaifId },
+    #[snafu(display("{path}:{lineno}: {reason}"))]
+    OrientationFileParsing {
+        path: String,
+        lineno: usize,
+        reason: String,
+    },
+    #[snafu(display("orientation provider of {id} failed: {reason}"))]
+    Provider { id: NaifId, reason: String },
+    #[snafu(display("two-vector frame {id} is undefined: {reason}"))]
+    TwoVectorFrame { id: NaifId, reason: String },
+    #[snafu(display(
+        "frame {id} is only named in the Euler parameters: load the BPC or the attitude which rotates it"
+    ))]
+    NamedFrameOnly { id: NaifId },
+}
+
+impl ErrorCode for OrientationError {
+    fn code(&self) -> u16 {
+        match self {
+            Self::Unreachable => 301,
+            Self::StructureIsFull { .. } => 302,
+            Self::RotationOrigin { .. } => 303,
+            Self::NoOrientationsLoaded => 304,
+            Self::BPC { source, .. } => source.code(),
+            Self::OrientationPhysics { source } => source.code(),
+            Self::OrientationInterpolation { source } => source.code(),
+            Self::OrientationDataSet { source } => source.code(),
+            Self::OrientationNameToId { .. } => 305,
+            Self::NotSpacecraftFrame { .. } => 306,
+            Self::OrientationFileParsing { .. } => 307,
+            Self::Provider { .. } => 308,
+            Self::TwoVectorFrame { .. } => 309,
+            Self::NamedFrameOnly { .. } => 310,
+        }
+    }
 }
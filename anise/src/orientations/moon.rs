@@ -0,0 +1,99 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use super::{ConstantOrientation, OrientationError};
+use crate::almanac::Almanac;
+use crate::constants::orientations::{
+    MOON_ME, MOON_ME_DE421, MOON_ME_DE440_ME421, MOON_PA, MOON_PA_DE421, MOON_PA_DE440,
+};
+use crate::math::rotation::{r1, r2, r3};
+use crate::math::Matrix3;
+
+/// Euler angles (in arcseconds, about the Z, Y, then X axes) of the fixed rotation from the Moon Principal Axes frame of the DE440 to
+/// its Mean Earth frame, as defined by `TKFRAME_31009_ANGLES` of the lunar frames kernel `moon_de440_220930.tf`.
+pub const MOON_PA_TO_ME_DE440_ARCSEC: [f64; 3] = [67.8526, 78.6944, 0.2785];
+
+/// Euler angles (in arcseconds, about the Z, Y, then X axes) of the fixed rotation from the Moon Principal Axes frame of the DE421 to
+/// its Mean Earth frame, as defined by `TKFRAME_31007_ANGLES` of the lunar frames kernel `moon_080317.tf`.
+pub const MOON_PA_TO_ME_DE421_ARCSEC: [f64; 3] = [67.92, 78.56, 0.30];
+
+/// Builds the matrix of a 3-2-1 TK frame rotation from its angles in arcseconds, like the FK conversion does.
+fn tk_321_arcsec(angles: [f64; 3]) -> Matrix3 {
+    let [z, y, x] = angles.map(|angle| (angle / 3600.0_f64).to_radians());
+    r3(z) * r2(y) * r1(x)
+}
+
+impl ConstantOrientation {
+    /// Returns the fixed orientation of the DE440 Moon Mean Earth frame ([MOON_ME_DE440_ME421]) with respect to the DE440 Moon
+    /// Principal Axes frame ([MOON_PA_DE440]), whose lunar librations are provided by the `moon_pa_de440_200625.bpc` kernel.
+    pub fn moon_me_de440() -> Self {
+        Self::from_matrix(
+            tk_321_arcsec(MOON_PA_TO_ME_DE440_ARCSEC),
+            MOON_ME_DE440_ME421,
+            MOON_PA_DE440,
+        )
+    }
+
+    /// Returns the fixed orientation of the DE421 Moon Mean Earth frame ([MOON_ME_DE421]) with respect to the DE421 Moon
+    /// Principal Axes frame ([MOON_PA_DE421]).
+    pub fn moon_me_de421() -> Self {
+        Self::from_matrix(
+            tk_321_arcsec(MOON_PA_TO_ME_DE421_ARCSEC),
+            MOON_ME_DE421,
+            MOON_PA_DE421,
+        )
+    }
+}
+
+impl Almanac {
+    /// Returns a copy of this Almanac with the lunar Mean Earth (ME) and Principal Axes (PA) frames of the DE440, without loading
+    /// the lunar frames kernel:
+    /// + [MOON_ME_DE440_ME421] as the fixed rotation from [MOON_PA_DE440], cf. [ConstantOrientation::moon_me_de440];
+    /// + the generic [MOON_PA] and [MOON_ME] frames as aliases of [MOON_PA_DE440] and [MOON_ME_DE440_ME421] respectively.
+    ///
+    /// The librations of the PA frame must be loaded from the `moon_pa_de440_200625.bpc` kernel: this is consistent with the DE440
+    /// ephemeris to the centimeter level on the lunar surface, unlike the IAU Moon frame which is only accurate to about 150 meters.
+    pub fn with_moon_frames_de440(&self) -> Result<Self, OrientationError> {
+        self.with_constant_orientation(
+            ConstantOrientation::moon_me_de440(),
+            Some("MOON_ME_DE440_ME421"),
+        )?
+        .with_constant_orientation(
+            ConstantOrientation::from_matrix(Matrix3::identity(), MOON_PA, MOON_PA_DE440),
+            Some("MOON_PA"),
+        )?
+        .with_constant_orientation(
+            ConstantOrientation::from_matrix(Matrix3::identity(), MOON_ME, MOON_ME_DE440_ME421),
+            Some("MOON_ME"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod ut_moon {
+    use super::ConstantOrientation;
+    use crate::constants::orientations::{MOON_ME_DE440_ME421, MOON_PA_DE440};
+    use crate::math::rotation::DCM;
+
+    #[test]
+    fn moon_pa_to_me_angle() {
+        let dcm = DCM::from(ConstantOrientation::moon_me_de440());
+        assert_eq!(dcm.from, MOON_ME_DE440_ME421);
+        assert_eq!(dcm.to, MOON_PA_DE440);
+
+        // The lunar frames kernel states that the angle between the ME and PA frames is approximately 0.02886 degrees.
+        let angle_deg = ((dcm.rot_mat.trace() - 1.0) / 2.0).acos().to_degrees();
+        assert!((angle_deg - 0.02886).abs() < 1e-5, "{angle_deg}");
+
+        let dcm = DCM::from(ConstantOrientation::moon_me_de421());
+        let angle_deg = ((dcm.rot_mat.trace() - 1.0) / 2.0).acos().to_degrees();
+        assert!((angle_deg - 0.0288).abs() < 1e-4, "{angle_deg}");
+    }
+}
@@ -11,9 +11,9 @@
 use hifitime::Epoch;
 use snafu::{ensure, ResultExt};
 
-use super::{BPCSnafu, NoOrientationsLoadedSnafu, OrientationDataSetSnafu, OrientationError};
+use super::{builtin_rotation, BPCSnafu, NoOrientationsLoadedSnafu, OrientationError};
 use crate::almanac::Almanac;
-use crate::constants::orientations::{ECLIPJ2000, J2000};
+use crate::constants::orientations::{ICRF, ITRF93_FAST, J2000};
 use crate::frames::Frame;
 use crate::naif::daf::{DAFError, NAIFSummaryRecord};
 use crate::NaifId;
@@ -22,7 +22,7 @@ use crate::NaifId;
 pub const MAX_TREE_DEPTH: usize = 8;
 
 impl Almanac {
-    /// Returns the root of all of the loaded orientations (BPC or planetary), typically this should be J2000.
+    /// Returns the root of all of the loaded orientations (BPC, planetary, or Euler parameters), typically this should be J2000.
     ///
     /// # Algorithm
     ///
@@ -30,13 +30,22 @@ impl Almanac {
     /// 2. For each summary record in each BPC, follow the orientation branch all the way up until the end of this BPC or until the J2000.
     pub fn try_find_orientation_root(&self) -> Result<NaifId, OrientationError> {
         ensure!(
-            self.num_loaded_bpc() > 0 || !self.planetary_data.is_empty(),
+            self.num_loaded_bpc() > 0
+                || !self.planetary_data.is_empty()
+                || !self.euler_param_data.is_empty()
+                || !self.orientation_providers.is_empty(),
             NoOrientationsLoadedSnafu
         );
 
         // The common center is the absolute minimum of all centers due to the NAIF numbering.
         let mut common_center = i32::MAX;
 
+        for provider in &self.orientation_providers {
+            if provider.parent_id().abs() < common_center.abs() {
+                common_center = provider.parent_id();
+            }
+        }
+
         for maybe_bpc in self.bpc_data.iter().take(self.num_loaded_bpc()).rev() {
             let bpc = maybe_bpc.as_ref().unwrap();
 
@@ -57,7 +66,7 @@ impl Almanac {
         // If we reached this point, it means that we didn't find J2000 in the loaded BPCs, so let's iterate through the planetary data
         if !self.planetary_data.is_empty() {
             for id in self.planetary_data.lut.by_id.keys() {
-                if let Ok(pc) = self.planetary_data.get_by_id(*id) {
+                if let Ok(pc) = self.planetary_data.get_ref_by_id(*id) {
                     if pc.parent_id < common_center {
                         common_center = pc.parent_id;
                         if common_center == J2000 {
@@ -69,8 +78,23 @@ impl Almanac {
             }
         }
 
-        if common_center == ECLIPJ2000 {
-            // Rotation from ecliptic J2000 to J2000 is embedded.
+        // Finally, iterate through the Euler parameter data, e.g. constant orientations of instruments.
+        if !self.euler_param_data.is_empty() {
+            for id in self.euler_param_data.lut.by_id.keys() {
+                if let Ok(ep) = self.euler_parameters_to_parent(*id) {
+                    if ep.to.abs() < common_center.abs() {
+                        common_center = ep.to;
+                        if common_center == J2000 {
+                            // there is nothing higher up
+                            return Ok(common_center);
+                        }
+                    }
+                }
+            }
+        }
+
+        if builtin_rotation(common_center).is_some() {
+            // The built-in inertial frames, like ecliptic J2000, all lead to J2000.
             common_center = J2000;
         }
 
@@ -83,67 +107,63 @@ impl Almanac {
         source: Frame,
         epoch: Epoch,
     ) -> Result<(usize, [Option<NaifId>; MAX_TREE_DEPTH]), OrientationError> {
-        let common_center = self.try_find_orientation_root()?;
         // Build a tree, set a fixed depth to avoid allocations
         let mut of_path = [None; MAX_TREE_DEPTH];
         let mut of_path_len = 0;
 
-        if common_center == source.orientation_id {
-            // We're querying the source, no need to check that this summary even exists.
+        if source.orientation_id == J2000 {
+            // J2000 is the root of the built-in frames, so it needs no loaded data.
             return Ok((of_path_len, of_path));
         }
 
-        // Grab the summary data, which we use to find the paths
-        // Let's see if this orientation is defined in the loaded BPC files
-        let mut inertial_frame_id = match self.bpc_summary_at_epoch(source.orientation_id, epoch) {
-            Ok((summary, _, _)) => summary.inertial_frame_id,
-            Err(_) => {
-                // Not available as a BPC, so let's see if there's planetary data for it.
-                match self.planetary_data.get_by_id(source.orientation_id) {
-                    Ok(planetary_data) => planetary_data.parent_id,
-                    Err(_) => {
-                        // Finally, let's see if it's in the loaded Euler Parameters.
-                        self.euler_param_data
-                            .get_by_id(source.orientation_id)
-                            .context(OrientationDataSetSnafu)?
-                            .to
-                    }
-                }
+        if let Some(builtin) = builtin_rotation(source.orientation_id) {
+            // The built-in inertial frames lead to J2000 without any loaded data.
+            let mut parent_id = builtin.parent_id;
+            of_path[of_path_len] = Some(parent_id);
+            of_path_len += 1;
+            while let Some(parent) = builtin_rotation(parent_id) {
+                parent_id = parent.parent_id;
+                of_path[of_path_len] = Some(parent_id);
+                of_path_len += 1;
             }
-        };
+            return Ok((of_path_len, of_path));
+        }
 
-        of_path[of_path_len] = Some(inertial_frame_id);
-        of_path_len += 1;
+        if self.two_vector_frame(source.orientation_id).is_some() {
+            // The two-vector frames are computed in J2000.
+            of_path[of_path_len] = Some(J2000);
+            of_path_len += 1;
+            return Ok((of_path_len, of_path));
+        }
 
-        if inertial_frame_id == ECLIPJ2000 {
-            // Add the hop to J2000
-            inertial_frame_id = J2000;
-            of_path[of_path_len] = Some(inertial_frame_id);
+        let common_center = self.try_find_orientation_root()?;
+
+        if common_center == source.orientation_id {
+            // We're querying the source, no need to check that this summary even exists.
+            return Ok((of_path_len, of_path));
+        }
+
+        if source.orientation_id == ITRF93_FAST || source.orientation_id == ICRF {
+            // The fast Earth orientation and the ICRF are built in and defined with respect to J2000.
+            of_path[of_path_len] = Some(J2000);
             of_path_len += 1;
+            return Ok((of_path_len, of_path));
         }
 
+        // Grab the parent, which we use to find the paths
+        let mut inertial_frame_id =
+            self.orientation_parent_at_epoch(source.orientation_id, epoch)?;
+
+        of_path[of_path_len] = Some(inertial_frame_id);
+        of_path_len += 1;
+
         if inertial_frame_id == common_center {
             // Well that was quick!
             return Ok((of_path_len, of_path));
         }
 
         for _ in 0..MAX_TREE_DEPTH - 1 {
-            inertial_frame_id = match self.bpc_summary_at_epoch(inertial_frame_id, epoch) {
-                Ok((summary, _, _)) => summary.inertial_frame_id,
-                Err(_) => {
-                    // Not available as a BPC, so let's see if there's planetary data for it.
-                    match self.planetary_data.get_by_id(inertial_frame_id) {
-                        Ok(planetary_data) => planetary_data.parent_id,
-                        Err(_) => {
-                            // Finally, let's see if it's in the loaded Euler Parameters.
-                            self.euler_param_data
-                                .get_by_id(inertial_frame_id)
-                                .context(OrientationDataSetSnafu)?
-                                .to
-                        }
-                    }
-                }
-            };
+            inertial_frame_id = self.orientation_parent_at_epoch(inertial_frame_id, epoch)?;
 
             of_path[of_path_len] = Some(inertial_frame_id);
             of_path_len += 1;
@@ -159,6 +179,15 @@ impl Almanac {
         })
     }
 
+    /// Returns the parent of this orientation at this epoch, from the same source as [Almanac::rotation_to_parent].
+    fn orientation_parent_at_epoch(
+        &self,
+        id: NaifId,
+        epoch: Epoch,
+    ) -> Result<NaifId, OrientationError> {
+        Ok(self.rotation_source(id, epoch)?.parent_id())
+    }
+
     /// Returns the orientation path between two frames and the common node. This may return a `DisjointRoots` error if the frames do not share a common root, which is considered a file integrity error.
     pub fn common_orientation_path(
         &self,
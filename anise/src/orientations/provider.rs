@@ -0,0 +1,57 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::sync::Arc;
+
+use hifitime::Epoch;
+
+use super::OrientationError;
+use crate::almanac::Almanac;
+use crate::math::rotation::DCM;
+use crate::NaifId;
+
+/// A source of orientation data other than the BPC files and datasets, e.g. an attitude simulation or a database, which is
+/// used like a BPC segment once registered in an Almanac with [Almanac::with_orientation_provider].
+pub trait OrientationProvider: Send + Sync {
+    /// ID of the orientation which is provided
+    fn orientation_id(&self) -> NaifId;
+    /// ID of the orientation with respect to which it is provided, which must be reachable in the Almanac
+    fn parent_id(&self) -> NaifId;
+    /// Returns whether this provider can compute the orientation at the provided epoch
+    fn covers(&self, epoch: Epoch) -> bool;
+    /// Returns the rotation from the parent orientation to the provided one at the provided epoch, i.e. a DCM `from` the parent
+    /// `to` the orientation ID, ideally with its time derivative.
+    fn rotation(&self, epoch: Epoch) -> Result<DCM, OrientationError>;
+}
+
+impl Almanac {
+    /// Returns a copy of this Almanac where the provided orientation source is registered.
+    ///
+    /// Providers have precedence over the loaded BPC files and datasets, and the providers registered last have precedence over
+    /// the others, but only at the epochs they cover.
+    pub fn with_orientation_provider(&self, provider: Arc<dyn OrientationProvider>) -> Self {
+        let mut me = self.clone();
+        me.orientation_providers.push(provider);
+        me
+    }
+
+    /// Returns the provider of this orientation at this epoch, if any.
+    pub(crate) fn orientation_provider_at_epoch(
+        &self,
+        id: NaifId,
+        epoch: Epoch,
+    ) -> Option<&dyn OrientationProvider> {
+        self.orientation_providers
+            .iter()
+            .rev()
+            .find(|provider| provider.orientation_id() == id && provider.covers(epoch))
+            .map(|provider| provider.as_ref())
+    }
+}
@@ -13,15 +13,94 @@ use snafu::ResultExt;
 
 use super::{OrientationError, OrientationPhysicsSnafu};
 use crate::almanac::Almanac;
-use crate::constants::orientations::{ECLIPJ2000, J2000, J2000_TO_ECLIPJ2000_ANGLE_RAD};
+use crate::constants::orientations::{ICRF, ITRF93_FAST, J2000};
+use crate::constants::registry::UT1_MINUS_UTC;
 use crate::hifitime::Epoch;
+use crate::math::rotation::Quaternion;
 use crate::math::rotation::{r1, r1_dot, r3, r3_dot, DCM};
 use crate::naif::daf::datatypes::Type2ChebyshevSet;
 use crate::naif::daf::{DAFError, DafDataType, NAIFDataSet, NAIFSummaryRecord};
-use crate::orientations::{BPCSnafu, OrientationDataSetSnafu, OrientationInterpolationSnafu};
+use crate::naif::pck::BPCSummaryRecord;
+use crate::orientations::{
+    builtin_rotation, earth_fast_rotation_truncated, icrf_frame_bias, BPCSnafu, BuiltinRotation,
+    OrientationInterpolationSnafu, OrientationProvider, TwoVectorFrame,
+};
 use crate::prelude::Frame;
+use crate::structure::planetocentric::PlanetaryData;
+use crate::NaifId;
+
+/// Source of the rotation of an orientation to its parent, i.e. the data used by [Almanac::rotation_to_parent].
+pub(crate) enum RotationSource<'a> {
+    /// J2000 is the root of the orientations
+    Root,
+    Builtin(&'static BuiltinRotation),
+    EarthFast,
+    Icrf,
+    TwoVector(&'a TwoVectorFrame),
+    Provider(&'a dyn OrientationProvider),
+    Bpc {
+        summary: &'a BPCSummaryRecord,
+        bpc_no: usize,
+        idx_in_bpc: usize,
+    },
+    Planetary(&'a PlanetaryData),
+    EulerParameters(Quaternion),
+}
+
+impl RotationSource<'_> {
+    /// Returns the ID of the parent orientation of the rotation from this source.
+    pub(crate) fn parent_id(&self) -> NaifId {
+        match self {
+            Self::Root | Self::EarthFast | Self::Icrf | Self::TwoVector(_) => J2000,
+            Self::Builtin(builtin) => builtin.parent_id,
+            Self::Provider(provider) => provider.parent_id(),
+            Self::Bpc { summary, .. } => summary.inertial_frame_id,
+            Self::Planetary(planetary_data) => planetary_data.parent_id,
+            Self::EulerParameters(q) => q.to,
+        }
+    }
+}
 
 impl Almanac {
+    /// Returns the source of the rotation of this orientation to its parent at the provided epoch, in order of precedence:
+    /// the built-in frames, the two-vector frames, the providers, the loaded BPC files, the planetary data, and the Euler
+    /// parameter data.
+    pub(crate) fn rotation_source(
+        &self,
+        orientation_id: NaifId,
+        epoch: Epoch,
+    ) -> Result<RotationSource<'_>, OrientationError> {
+        if orientation_id == J2000 {
+            Ok(RotationSource::Root)
+        } else if let Some(builtin) = builtin_rotation(orientation_id) {
+            Ok(RotationSource::Builtin(builtin))
+        } else if orientation_id == ITRF93_FAST {
+            Ok(RotationSource::EarthFast)
+        } else if orientation_id == ICRF {
+            Ok(RotationSource::Icrf)
+        } else if let Some(two_vector) = self.two_vector_frame(orientation_id) {
+            Ok(RotationSource::TwoVector(two_vector))
+        } else if let Some(provider) = self.orientation_provider_at_epoch(orientation_id, epoch) {
+            // Custom providers have precedence over the BPC files and datasets.
+            Ok(RotationSource::Provider(provider))
+        } else if let Ok((summary, bpc_no, idx_in_bpc)) =
+            self.bpc_summary_at_epoch(orientation_id, epoch)
+        {
+            Ok(RotationSource::Bpc {
+                summary,
+                bpc_no,
+                idx_in_bpc,
+            })
+        } else if let Ok(planetary_data) = self.planetary_data.get_ref_by_id(orientation_id) {
+            Ok(RotationSource::Planetary(planetary_data))
+        } else {
+            // Finally, let's see if it's in the loaded Euler Parameters.
+            Ok(RotationSource::EulerParameters(
+                self.euler_parameters_to_parent(orientation_id)?,
+            ))
+        }
+    }
+
     /// Returns the direct cosine matrix (DCM) to rotate from the `source` to its parent in the orientation hierarchy at the provided epoch,
     ///
     /// # Example
@@ -33,21 +112,44 @@ impl Almanac {
     ///
     /// **WARNING:** This function only performs the rotation and no translation whatsoever. Use the `transform_to_parent_from` function instead to include rotations.
     pub fn rotation_to_parent(&self, source: Frame, epoch: Epoch) -> Result<DCM, OrientationError> {
-        if source.orient_origin_id_match(J2000) {
-            // The parent of Earth ecliptic J2000 is the J2000 inertial frame.
-            return Ok(DCM::identity(J2000, J2000));
-        } else if source.orient_origin_id_match(ECLIPJ2000) {
+        match self.rotation_source(source.orientation_id, epoch)? {
             // The parent of Earth ecliptic J2000 is the J2000 inertial frame.
-            return Ok(DCM {
-                rot_mat: r1(J2000_TO_ECLIPJ2000_ANGLE_RAD),
-                rot_mat_dt: None,
-                from: J2000,
-                to: ECLIPJ2000,
-            });
-        }
-        // Let's see if this orientation is defined in the loaded BPC files
-        match self.bpc_summary_at_epoch(source.orientation_id, epoch) {
-            Ok((summary, bpc_no, idx_in_bpc)) => {
+            RotationSource::Root => Ok(DCM::identity(J2000, J2000)),
+            // Built-in inertial frames, like Earth ecliptic J2000, are constant rotations from their parent.
+            RotationSource::Builtin(builtin) => Ok(builtin.dcm()),
+            RotationSource::EarthFast => {
+                // The parent of the fast Earth orientation is the J2000 inertial frame.
+                let ut1_minus_utc_s = self
+                    .constant(UT1_MINUS_UTC.name)
+                    .unwrap_or(UT1_MINUS_UTC.value);
+                Ok(earth_fast_rotation_truncated(
+                    epoch,
+                    ut1_minus_utc_s,
+                    self.nutation_truncation.unwrap_or_default(),
+                ))
+            }
+            RotationSource::Icrf => {
+                // The parent of the ICRF is the J2000 inertial frame, from which it only differs if the frame bias is enabled.
+                if self.frame_bias && !self.spice_compat {
+                    Ok(icrf_frame_bias())
+                } else {
+                    Ok(DCM::identity(J2000, ICRF))
+                }
+            }
+            RotationSource::TwoVector(two_vector) => {
+                // Two-vector frames are computed from the ephemerides, in J2000.
+                trace!("rotate {source} wrt to its parent @ {epoch:E} using a two-vector frame");
+                two_vector.rotation(self, epoch)
+            }
+            RotationSource::Provider(provider) => {
+                trace!("rotate {source} wrt to its parent @ {epoch:E} using a provider");
+                provider.rotation(epoch)
+            }
+            RotationSource::Bpc {
+                summary,
+                bpc_no,
+                idx_in_bpc,
+            } => {
                 let new_frame = source.with_orient(summary.inertial_frame_id);
 
                 trace!("rotate {source} wrt to {new_frame} @ {epoch:E}");
@@ -102,33 +204,27 @@ impl Almanac {
                     to: source.orientation_id,
                 })
             }
-            Err(_) => {
-                // Not available as a BPC, so let's see if there's planetary data for it.
-                match self.planetary_data.get_by_id(source.orientation_id) {
-                    Ok(planetary_data) => {
-                        trace!("query {source} wrt to its parent @ {epoch:E} using planetary data");
-                        // Fetch the parent info
-                        let system_data =
-                            match self.planetary_data.get_by_id(planetary_data.parent_id) {
-                                Ok(parent) => parent,
-                                Err(_) => planetary_data,
-                            };
+            RotationSource::Planetary(planetary_data) => {
+                trace!("query {source} wrt to its parent @ {epoch:E} using planetary data");
+                // Fetch the parent info
+                let system_data = match self.planetary_data.get_ref_by_id(planetary_data.parent_id)
+                {
+                    Ok(parent) => parent,
+                    Err(_) => planetary_data,
+                };
 
-                        planetary_data
-                            .rotation_to_parent(epoch, &system_data)
-                            .context(OrientationPhysicsSnafu)
-                    }
-                    Err(_) => {
-                        trace!("query {source} wrt to its parent @ {epoch:E} using Euler parameter data");
-                        // Finally, let's see if it's in the loaded Euler Parameters.
-                        // We can call `into` because EPs can be converted directly into DCMs.
-                        Ok(self
-                            .euler_param_data
-                            .get_by_id(source.orientation_id)
-                            .context(OrientationDataSetSnafu)?
-                            .into())
+                match self.nutation_truncation {
+                    Some(truncation) => {
+                        planetary_data.rotation_to_parent_truncated(epoch, system_data, truncation)
                     }
+                    None => planetary_data.rotation_to_parent(epoch, system_data),
                 }
+                .context(OrientationPhysicsSnafu)
+            }
+            RotationSource::EulerParameters(q) => {
+                trace!("query {source} wrt to its parent @ {epoch:E} using Euler parameter data");
+                // We can call `into` because EPs can be converted directly into DCMs.
+                Ok(q.into())
             }
         }
     }
@@ -10,15 +10,16 @@
 
 use snafu::ResultExt;
 
-use super::OrientationError;
 use super::OrientationPhysicsSnafu;
+use super::{DerivativePolicy, OrientationError};
 use crate::almanac::Almanac;
 use crate::constants::orientations::J2000;
+use crate::errors::PhysicsError;
 use crate::hifitime::Epoch;
 use crate::math::cartesian::CartesianState;
 use crate::math::rotation::DCM;
 use crate::math::units::*;
-use crate::math::Vector3;
+use crate::math::{Matrix3, Matrix6, Vector3};
 use crate::prelude::Frame;
 
 #[cfg(feature = "python")]
@@ -38,6 +39,43 @@ impl Almanac {
         from_frame: Frame,
         to_frame: Frame,
         epoch: Epoch,
+    ) -> Result<DCM, OrientationError> {
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return self.rotate_uncached(from_frame, to_frame, epoch),
+        };
+
+        if let Some(dcm) = cache.rotation(from_frame, to_frame, epoch) {
+            return Ok(dcm);
+        }
+
+        let dcm = self.rotate_uncached(from_frame, to_frame, epoch)?;
+        cache.insert_rotation(from_frame, to_frame, epoch, dcm);
+        Ok(dcm)
+    }
+
+    /// Rotates the provided Cartesian state into the requested observer frame
+    ///
+    /// **WARNING:** This function only performs the translation and no rotation _whatsoever_. Use the `transform_to` function instead to include rotations.
+    #[allow(clippy::too_many_arguments)]
+    pub fn rotate_to(
+        &self,
+        state: CartesianState,
+        observer_frame: Frame,
+    ) -> Result<CartesianState, OrientationError> {
+        let dcm = self.rotate(state.frame, observer_frame, state.epoch)?;
+
+        (dcm * state).context(OrientationPhysicsSnafu {})
+    }
+}
+
+impl Almanac {
+    /// Computes the rotation of [Almanac::rotate] without using the query cache, if any.
+    pub(crate) fn rotate_uncached(
+        &self,
+        from_frame: Frame,
+        to_frame: Frame,
+        epoch: Epoch,
     ) -> Result<DCM, OrientationError> {
         let mut to_frame: Frame = to_frame;
 
@@ -115,22 +153,70 @@ impl Almanac {
         }
     }
 
-    /// Rotates the provided Cartesian state into the requested observer frame
+    /// Returns the DCM to rotate from the `from_frame` to the `to_frame`, like `rotate`, where the time derivative of the DCM follows the provided policy.
     ///
-    /// **WARNING:** This function only performs the translation and no rotation _whatsoever_. Use the `transform_to` function instead to include rotations.
-    #[allow(clippy::too_many_arguments)]
-    pub fn rotate_to(
+    /// When only some of the rotations of the chain provide a time derivative (e.g. a constant frame composed with a BPC frame),
+    /// the others are considered constant and the derivative of the chain is still computed.
+    pub fn rotate_with_policy(
         &self,
-        state: CartesianState,
-        observer_frame: Frame,
-    ) -> Result<CartesianState, OrientationError> {
-        let dcm = self.rotate(state.frame, observer_frame, state.epoch)?;
+        from_frame: Frame,
+        to_frame: Frame,
+        epoch: Epoch,
+        policy: DerivativePolicy,
+    ) -> Result<DCM, OrientationError> {
+        let mut dcm = self.rotate(from_frame, to_frame, epoch)?;
+
+        if dcm.rot_mat_dt.is_none() {
+            match policy {
+                DerivativePolicy::RequireDerivative => {
+                    return Err(OrientationError::OrientationPhysics {
+                        source: PhysicsError::DCMMissingDerivative {
+                            action: "rotating with the RequireDerivative policy",
+                        },
+                    })
+                }
+                DerivativePolicy::BestEffort => dcm.rot_mat_dt = Some(Matrix3::zeros()),
+            }
+        }
 
-        (dcm * state).context(OrientationPhysicsSnafu {})
+        Ok(dcm)
+    }
+
+    /// Returns the 6x6 state transformation matrix from the `from_frame` to the `to_frame` at the provided epoch, i.e. the equivalent of SPICE's `sxform`.
+    ///
+    /// This matrix is `[[R, 0], [dR/dt, R]]` where `R` is the rotation matrix of the DCM from `from_frame` to `to_frame`: multiplying it
+    /// by a position and velocity vector (in km and km/s) yields the position and velocity in the `to_frame`, including the transport theorem.
+    /// If the time derivative of the rotation is not available (e.g. for constant rotations), its block is zero.
+    ///
+    /// # Warning
+    /// This function only performs the rotation and no translation whatsoever.
+    pub fn state_transformation_from_to(
+        &self,
+        from_frame: Frame,
+        to_frame: Frame,
+        epoch: Epoch,
+    ) -> Result<Matrix6, OrientationError> {
+        Ok(self.rotate(from_frame, to_frame, epoch)?.state_dcm())
+    }
+
+    /// Returns the velocity (in km/s) of the provided state as seen from the target frame, i.e. rotated into the orientation of the target
+    /// frame **and** including the rotation rate of the target frame with respect to the frame of the state (transport theorem):
+    ///
+    /// `v_target = R * v_state + dR/dt * r_state`, where `R` is the DCM from the frame of the state to the target frame.
+    ///
+    /// For example, the velocity of a spacecraft in the Earth fixed frame (ITRF93) is its inertial velocity (EME2000) _minus_ `omega x r`,
+    /// where `omega` is the rotation rate of the Earth, rotated into the ITRF93 orientation. The converse applies from a rotating frame to an inertial one.
+    ///
+    /// # Note
+    /// Only the orientation is changed, not the origin of the state: use `transform_to` to also change the origin.
+    pub fn velocity_in_frame(
+        &self,
+        state: CartesianState,
+        target_frame: Frame,
+    ) -> Result<Vector3, OrientationError> {
+        Ok(self.rotate_to(state, target_frame)?.velocity_km_s)
     }
-}
 
-impl Almanac {
     /// Rotates a state with its origin (`to_frame`) and given its units (distance_unit, time_unit), returns that state with respect to the requested frame
     ///
     /// **WARNING:** This function only performs the translation and no rotation _whatsoever_. Use the `transform_state_to` function instead to include rotations.
@@ -154,6 +240,7 @@ impl Almanac {
         let input_state = CartesianState {
             radius_km: position * dist_unit_factor,
             velocity_km_s: velocity * dist_unit_factor / time_unit_factor,
+            acceleration_km_s2: None,
             epoch,
             frame: from_frame,
         };
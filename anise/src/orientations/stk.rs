@@ -0,0 +1,302 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::f64::consts::{PI, TAU};
+use std::fs::read_to_string;
+
+use hifitime::{Epoch, TimeUnits};
+use log::{info, warn};
+use snafu::ResultExt;
+
+use super::{BPCSnafu, OrientationError, OrientationInterpolationSnafu};
+use crate::constants::orientations::id_to_orientation_name;
+use crate::ephemerides::stk::parse_utcg;
+use crate::errors::InputOutputError;
+use crate::math::interpolation::{chebyshev_fit, chebyshev_nodes, lagrange_eval, MAX_SAMPLES};
+use crate::math::rotation::{Quaternion, DCM};
+use crate::math::Matrix3;
+use crate::naif::daf::datatypes::Type2ChebyshevSet;
+use crate::naif::daf::{DAFError, DafDataType, NAIFDataSet};
+use crate::naif::pck::BPCSummaryRecord;
+use crate::naif::{BPCSegment, BPC};
+use crate::NaifId;
+
+/// Converts an STK attitude file (`.a`) into an in-memory BPC where the orientation is that of the provided `frame_id`
+/// with respect to the coordinate axes of the file.
+///
+/// The quaternions are converted into 3-1-3 Euler angles, which are resampled onto Chebyshev Type 2 segments (the only type
+/// supported for BPC computations), one record per interval between two attitude points. Each discontinuity in the attitude
+/// (where an epoch is repeated) starts a new segment.
+///
+/// # Limitations
+/// + Only the `AttitudeTimeQuaternions` and `AttitudeTimeQuatAngVels` data formats are supported, and the angular velocities are ignored;
+/// + The coordinate axes must be known to ANISE (e.g. J2000 or ICRF), and the scenario epoch must be in UTC Gregorian (the STK default).
+pub fn convert_stk_attitude(path: &str, frame_id: NaifId) -> Result<BPC, OrientationError> {
+    let contents = read_to_string(path)
+        .map_err(|e| DAFError::IO {
+            action: format!("reading STK attitude {path}"),
+            source: InputOutputError::IOError { kind: e.kind() },
+        })
+        .context(BPCSnafu {
+            action: "converting STK attitude",
+        })?;
+
+    let parsing_err = |lineno: usize, reason: String| OrientationError::OrientationFileParsing {
+        path: path.to_string(),
+        lineno,
+        reason,
+    };
+
+    let mut scenario_epoch = None;
+    let mut inertial_frame_id = None;
+    let mut samples = 2;
+    let mut num_columns = 0;
+
+    // Each segment is a list of epochs in ET seconds and of 3-1-3 Euler angles in radians
+    let mut segments: Vec<(Vec<f64>, Vec<[f64; 3]>)> = vec![(Vec::new(), Vec::new())];
+
+    for (lno, line) in contents.lines().enumerate() {
+        let lineno = lno + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("stk.v.") {
+            continue;
+        }
+
+        let mut tokens = line.split_whitespace();
+        let keyword = tokens.next().unwrap();
+        let value = tokens.collect::<Vec<&str>>().join(" ");
+
+        if num_columns > 0 {
+            if keyword.eq_ignore_ascii_case("END") {
+                num_columns = 0;
+                continue;
+            }
+
+            let values = line
+                .split_whitespace()
+                .map(|item| item.parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|e| parsing_err(lineno, format!("{e}")))?;
+
+            if values.len() != num_columns {
+                return Err(parsing_err(
+                    lineno,
+                    format!("expected {num_columns} columns, got {values:?}"),
+                ));
+            }
+
+            let epoch: Epoch = scenario_epoch.unwrap() + values[0].seconds();
+            let epoch_et_s = epoch.to_et_seconds();
+
+            // STK quaternions are scalar last, and rotate from the coordinate axes to the body axes.
+            let dcm = DCM::from(Quaternion::new(
+                values[4],
+                values[1],
+                values[2],
+                values[3],
+                inertial_frame_id.unwrap(),
+                frame_id,
+            ));
+
+            let (epochs, angles) = segments.last().unwrap();
+            let prev_angles = if epochs
+                .last()
+                .is_some_and(|prev_epoch_et_s| epoch_et_s <= *prev_epoch_et_s)
+            {
+                // Discontinuity, start a new segment
+                segments.push((Vec::new(), Vec::new()));
+                None
+            } else {
+                angles.last().copied()
+            };
+
+            let (epochs, angles) = segments.last_mut().unwrap();
+            epochs.push(epoch_et_s);
+            angles.push(euler_313_angles(&dcm.rot_mat, prev_angles));
+        } else if keyword.eq_ignore_ascii_case("ScenarioEpoch") {
+            scenario_epoch = Some(parse_utcg(&value).ok_or_else(|| {
+                parsing_err(lineno, format!("could not parse `{value}` as UTCG"))
+            })?);
+        } else if keyword.eq_ignore_ascii_case("CoordinateAxes") {
+            inertial_frame_id = Some(
+                id_to_orientation_name(&value).map_err(|e| parsing_err(lineno, format!("{e}")))?,
+            );
+        } else if keyword.eq_ignore_ascii_case("InterpolationOrder") {
+            samples = value
+                .parse::<usize>()
+                .map_err(|e| parsing_err(lineno, format!("{e}")))?
+                + 1;
+        } else if keyword.eq_ignore_ascii_case("AttitudeTimeQuaternions")
+            || keyword.eq_ignore_ascii_case("AttitudeTimeQuatAngVels")
+        {
+            if scenario_epoch.is_none() || inertial_frame_id.is_none() {
+                return Err(parsing_err(
+                    lineno,
+                    "ScenarioEpoch and CoordinateAxes must precede the data".to_string(),
+                ));
+            }
+            num_columns = if keyword.eq_ignore_ascii_case("AttitudeTimeQuaternions") {
+                5
+            } else {
+                8
+            };
+        } else if keyword.starts_with("AttitudeTime") {
+            return Err(parsing_err(
+                lineno,
+                format!("unsupported attitude format `{keyword}`"),
+            ));
+        }
+    }
+
+    let inertial_frame_id =
+        inertial_frame_id.ok_or_else(|| parsing_err(0, "no CoordinateAxes".to_string()))?;
+
+    let mut bpc_segments = Vec::with_capacity(segments.len());
+    for (epoch_data, angle_data) in segments.iter().filter(|(epochs, _)| epochs.len() > 1) {
+        let num_samples = epoch_data.len();
+        let window = samples.min(num_samples).min(MAX_SAMPLES).max(2);
+        if window != samples {
+            warn!("[convert_stk_attitude] using {window} samples instead of {samples}");
+        }
+
+        // Offsets from the start of the segment, to preserve the precision of the interpolation.
+        let start_et_s = epoch_data[0];
+        let offsets_s = epoch_data
+            .iter()
+            .map(|epoch_et_s| epoch_et_s - start_et_s)
+            .collect::<Vec<f64>>();
+
+        let num_records = num_samples - 1;
+        let interval_length_s = offsets_s[num_records] / num_records as f64;
+        let radius_s = interval_length_s / 2.0;
+        let nodes = chebyshev_nodes(window);
+
+        let mut record_data = Vec::with_capacity(num_records * (2 + 3 * window));
+        for rno in 0..num_records {
+            let midpoint_s = (rno as f64 + 0.5) * interval_length_s;
+
+            // Use the attitude points which surround the middle of this record.
+            let first = offsets_s
+                .partition_point(|offset_s| *offset_s < midpoint_s)
+                .saturating_sub(window / 2)
+                .min(num_samples - window);
+            let xs = &offsets_s[first..first + window];
+
+            record_data.push(start_et_s + midpoint_s);
+            record_data.push(radius_s);
+
+            for ano in 0..3 {
+                let ys = angle_data[first..first + window]
+                    .iter()
+                    .map(|angles| angles[ano])
+                    .collect::<Vec<f64>>();
+
+                let values_at_nodes = nodes
+                    .iter()
+                    .map(|node| {
+                        lagrange_eval(xs, &ys, midpoint_s + node * radius_s).map(|(val, _)| val)
+                    })
+                    .collect::<Result<Vec<f64>, _>>()
+                    .context(OrientationInterpolationSnafu)?;
+
+                record_data.extend(chebyshev_fit(&values_at_nodes));
+            }
+        }
+
+        let data = Type2ChebyshevSet {
+            init_epoch: Epoch::from_et_seconds(start_et_s),
+            interval_length: interval_length_s.seconds(),
+            rsize: 2 + 3 * window,
+            num_records,
+            record_data: &record_data,
+        }
+        .to_f64_daf_vec()
+        .context(OrientationInterpolationSnafu)?;
+
+        bpc_segments.push(BPCSegment {
+            name: format!("STK attitude #{}", bpc_segments.len()),
+            summary: BPCSummaryRecord {
+                start_epoch_et_s: start_et_s,
+                end_epoch_et_s: epoch_data[num_records],
+                frame_id,
+                inertial_frame_id,
+                data_type_i: DafDataType::Type2ChebyshevTriplet as i32,
+                ..Default::default()
+            },
+            data,
+        });
+    }
+
+    info!(
+        "[convert_stk_attitude] {} segment(s) of {frame_id} built from {path}",
+        bpc_segments.len()
+    );
+
+    BPC::from_segments(path, &bpc_segments).context(BPCSnafu {
+        action: "building BPC from STK attitude",
+    })
+}
+
+/// Returns the 3-1-3 Euler angles (as used in BPCs) of the provided rotation matrix, such that `rot_mat = R3(w) * R1(dec) * R3(ra)`.
+///
+/// The right ascension and twist angles are unwrapped to be continuous with the previous angles, if any, which are also used
+/// to resolve the gimbal lock when the declination is zero or pi.
+fn euler_313_angles(rot_mat: &Matrix3, prev: Option<[f64; 3]>) -> [f64; 3] {
+    let dec = rot_mat[(2, 2)].clamp(-1.0, 1.0).acos();
+
+    let (mut ra, mut w) = if rot_mat[(2, 0)].hypot(rot_mat[(2, 1)]) > 1e-12 {
+        (
+            rot_mat[(2, 0)].atan2(-rot_mat[(2, 1)]),
+            rot_mat[(0, 2)].atan2(rot_mat[(1, 2)]),
+        )
+    } else {
+        // Only the sum (or difference) of the right ascension and twist is defined, so keep the previous right ascension.
+        let ra = prev.map(|angles| angles[0]).unwrap_or(0.0);
+        let combined = rot_mat[(0, 1)].atan2(rot_mat[(0, 0)]);
+        if rot_mat[(2, 2)] > 0.0 {
+            (ra, combined - ra)
+        } else {
+            (ra, ra - combined)
+        }
+    };
+
+    if let Some(prev) = prev {
+        ra -= TAU * ((ra - prev[0] + PI) / TAU).floor();
+        w -= TAU * ((w - prev[2] + PI) / TAU).floor();
+    }
+
+    [ra, dec, w]
+}
+
+#[cfg(test)]
+mod ut_stk {
+    use super::euler_313_angles;
+    use crate::math::rotation::{r1, r3};
+
+    #[test]
+    fn euler_313() {
+        let rot_mat = r3(0.3) * r1(1.1) * r3(-2.0);
+        let angles = euler_313_angles(&rot_mat, None);
+        assert!((angles[0] + 2.0).abs() < 1e-14);
+        assert!((angles[1] - 1.1).abs() < 1e-14);
+        assert!((angles[2] - 0.3).abs() < 1e-14);
+
+        // Unwrapped with respect to the previous angles
+        let angles = euler_313_angles(&rot_mat, Some([4.0, 1.1, 0.3]));
+        assert!((angles[0] - (core::f64::consts::TAU - 2.0)).abs() < 1e-14);
+
+        // Gimbal lock keeps the previous right ascension
+        let rot_mat = r3(0.3) * r1(0.0) * r3(0.5);
+        let angles = euler_313_angles(&rot_mat, Some([0.5, 0.0, 0.3]));
+        assert!((angles[0] - 0.5).abs() < 1e-14);
+        assert!(angles[1].abs() < 1e-7);
+        assert!((angles[2] - 0.3).abs() < 1e-14);
+    }
+}
@@ -0,0 +1,376 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::{Epoch, TimeUnits};
+
+use super::OrientationError;
+use crate::almanac::Almanac;
+use crate::astro::Aberration;
+use crate::constants::orientations::J2000;
+use crate::ephemerides::EphemerisError;
+use crate::math::rotation::DCM;
+use crate::math::{Matrix3, Vector3};
+use crate::prelude::Frame;
+use crate::NaifId;
+
+/// Step of the central difference used to compute the time derivative of the rotation of a two-vector frame.
+const TWO_VECTOR_DT_STEP_S: f64 = 1.0;
+
+/// An axis of a two-vector frame, along or opposite to one of its X, Y, or Z axes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FrameAxis {
+    PlusX,
+    PlusY,
+    PlusZ,
+    MinusX,
+    MinusY,
+    MinusZ,
+}
+
+impl FrameAxis {
+    /// Index of this axis, 0 for X, 1 for Y, and 2 for Z.
+    pub fn index(&self) -> usize {
+        match self {
+            Self::PlusX | Self::MinusX => 0,
+            Self::PlusY | Self::MinusY => 1,
+            Self::PlusZ | Self::MinusZ => 2,
+        }
+    }
+
+    /// Sign of this axis, -1 if it is opposite to the axis of its index.
+    pub fn sign(&self) -> f64 {
+        match self {
+            Self::PlusX | Self::PlusY | Self::PlusZ => 1.0,
+            Self::MinusX | Self::MinusY | Self::MinusZ => -1.0,
+        }
+    }
+}
+
+/// A direction defining an axis of a two-vector frame, computed in the J2000 frame from the loaded ephemerides at the epoch of
+/// the rotation query.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DirectionVector {
+    /// Position of the target as seen from the observer, e.g. the direction of the Sun from a spacecraft
+    Position {
+        observer_id: NaifId,
+        target_id: NaifId,
+    },
+    /// Velocity of the target with respect to the observer, e.g. the velocity of a spacecraft with respect to the Earth
+    Velocity {
+        observer_id: NaifId,
+        target_id: NaifId,
+    },
+    /// Angular momentum of the target about the observer, i.e. the normal of its orbit
+    OrbitNormal {
+        observer_id: NaifId,
+        target_id: NaifId,
+    },
+    /// Constant direction in the J2000 frame
+    Constant { vector: Vector3 },
+}
+
+impl DirectionVector {
+    /// Computes this direction in the J2000 frame at the provided epoch.
+    pub fn evaluate(
+        &self,
+        almanac: &Almanac,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+    ) -> Result<Vector3, EphemerisError> {
+        let (observer_id, target_id) = match *self {
+            Self::Constant { vector } => return Ok(vector),
+            Self::Position {
+                observer_id,
+                target_id,
+            }
+            | Self::Velocity {
+                observer_id,
+                target_id,
+            }
+            | Self::OrbitNormal {
+                observer_id,
+                target_id,
+            } => (observer_id, target_id),
+        };
+
+        let state = almanac.translate(
+            Frame::from_ephem_j2000(target_id),
+            Frame::from_ephem_j2000(observer_id),
+            epoch,
+            ab_corr,
+        )?;
+
+        Ok(match self {
+            Self::Position { .. } => state.radius_km,
+            Self::Velocity { .. } => state.velocity_km_s,
+            _ => state.radius_km.cross(&state.velocity_km_s),
+        })
+    }
+}
+
+/// A dynamic frame defined by two time-varying directions, like the two-vector frames of SPICE: the primary axis is along the
+/// primary direction, and the secondary axis is along the component of the secondary direction which is orthogonal to the
+/// primary one, e.g. X toward the Sun and Z as close as possible to the orbit normal of a spacecraft.
+///
+/// The directions are computed from the loaded ephemerides at the epoch of each rotation query, so the parent of these frames
+/// is always J2000. Register it with [Almanac::with_two_vector_frame] to use it in any rotation like any other frame.
+///
+/// The time derivative of the rotation is computed by central differences over one second on either side of the epoch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TwoVectorFrame {
+    pub orientation_id: NaifId,
+    /// Name of this frame, used to fetch its ID with `Almanac::orientation_id`
+    pub name: String,
+    pub primary_axis: FrameAxis,
+    pub primary: DirectionVector,
+    pub secondary_axis: FrameAxis,
+    pub secondary: DirectionVector,
+    /// Aberration correction of the positions and velocities of the directions, if any
+    pub ab_corr: Option<Aberration>,
+}
+
+impl TwoVectorFrame {
+    /// Builds a two-vector frame, without aberration corrections.
+    pub fn new(
+        orientation_id: NaifId,
+        name: &str,
+        primary_axis: FrameAxis,
+        primary: DirectionVector,
+        secondary_axis: FrameAxis,
+        secondary: DirectionVector,
+    ) -> Self {
+        Self {
+            orientation_id,
+            name: name.to_string(),
+            primary_axis,
+            primary,
+            secondary_axis,
+            secondary,
+            ab_corr: None,
+        }
+    }
+
+    /// Returns a copy of this frame whose directions are computed with the provided aberration correction.
+    pub fn with_abcorr(&self, ab_corr: Option<Aberration>) -> Self {
+        let mut me = self.clone();
+        me.ab_corr = ab_corr;
+        me
+    }
+
+    /// Returns the rotation matrix from J2000 to this frame at the provided epoch, whose rows are the axes of this frame.
+    fn rot_mat(&self, almanac: &Almanac, epoch: Epoch) -> Result<Matrix3, OrientationError> {
+        let err = |reason: String| OrientationError::TwoVectorFrame {
+            id: self.orientation_id,
+            reason,
+        };
+
+        let primary_idx = self.primary_axis.index();
+        let secondary_idx = self.secondary_axis.index();
+        if primary_idx == secondary_idx {
+            return Err(err(format!(
+                "primary axis {:?} and secondary axis {:?} are not orthogonal",
+                self.primary_axis, self.secondary_axis
+            )));
+        }
+
+        let primary = self
+            .primary
+            .evaluate(almanac, epoch, self.ab_corr)
+            .map_err(|e| err(e.to_string()))?;
+        let secondary = self
+            .secondary
+            .evaluate(almanac, epoch, self.ab_corr)
+            .map_err(|e| err(e.to_string()))?;
+
+        let normal = primary.cross(&secondary);
+        if primary.norm() < f64::EPSILON
+            || normal.norm() <= f64::EPSILON * primary.norm() * secondary.norm()
+        {
+            return Err(err(format!(
+                "primary and secondary directions are parallel at {epoch}"
+            )));
+        }
+
+        let mut axes = [Vector3::zeros(); 3];
+        axes[primary_idx] = self.primary_axis.sign() * primary.normalize();
+        let secondary = self.secondary_axis.sign() * secondary;
+        // The third axis completes the right-handed triad, which depends on the order of the primary and secondary axes.
+        let third_idx = 3 - primary_idx - secondary_idx;
+        if secondary_idx == (primary_idx + 1) % 3 {
+            axes[third_idx] = axes[primary_idx].cross(&secondary).normalize();
+            axes[secondary_idx] = axes[third_idx].cross(&axes[primary_idx]);
+        } else {
+            axes[third_idx] = secondary.cross(&axes[primary_idx]).normalize();
+            axes[secondary_idx] = axes[primary_idx].cross(&axes[third_idx]);
+        }
+
+        Ok(Matrix3::from_rows(&[
+            axes[0].transpose(),
+            axes[1].transpose(),
+            axes[2].transpose(),
+        ]))
+    }
+
+    /// Returns the DCM from J2000 to this frame at the provided epoch, with its time derivative.
+    pub fn rotation(&self, almanac: &Almanac, epoch: Epoch) -> Result<DCM, OrientationError> {
+        let rot_mat = self.rot_mat(almanac, epoch)?;
+        let step = TWO_VECTOR_DT_STEP_S.seconds();
+        let rot_mat_dt = (self.rot_mat(almanac, epoch + step)?
+            - self.rot_mat(almanac, epoch - step)?)
+            / (2.0 * TWO_VECTOR_DT_STEP_S);
+
+        Ok(DCM {
+            rot_mat,
+            rot_mat_dt: Some(rot_mat_dt),
+            from: J2000,
+            to: self.orientation_id,
+        })
+    }
+}
+
+impl Almanac {
+    /// Returns a copy of this Almanac where the provided two-vector frame is registered, replacing any two-vector frame of the
+    /// same ID.
+    ///
+    /// Two-vector frames have precedence over the providers, the loaded BPC files, and the datasets.
+    pub fn with_two_vector_frame(&self, frame: TwoVectorFrame) -> Self {
+        let mut me = self.clone();
+        me.two_vector_frames
+            .retain(|existing| existing.orientation_id != frame.orientation_id);
+        me.two_vector_frames.push(frame);
+        me
+    }
+
+    /// Returns the two-vector frame of this orientation ID, if it is registered.
+    pub fn two_vector_frame(&self, id: NaifId) -> Option<&TwoVectorFrame> {
+        self.two_vector_frames
+            .iter()
+            .find(|frame| frame.orientation_id == id)
+    }
+}
+
+#[cfg(test)]
+mod ut_two_vector {
+    use std::sync::Arc;
+
+    use super::{DirectionVector, FrameAxis, TwoVectorFrame};
+    use crate::constants::frames::EARTH_J2000;
+    use crate::constants::orientations::J2000;
+    use crate::ephemerides::{EphemerisError, EphemerisProvider};
+    use crate::math::cartesian::CartesianState;
+    use crate::math::Vector3;
+    use crate::prelude::{Almanac, Frame};
+    use hifitime::{Epoch, TimeUnits};
+
+    const SC_ID: i32 = -10;
+    const RADIUS_KM: f64 = 7000.0;
+    const RATE_RAD_S: f64 = 1e-3;
+
+    /// Circular orbit of 30 degrees of inclination about the Earth.
+    struct CircularOrbit;
+
+    impl EphemerisProvider for CircularOrbit {
+        fn target_id(&self) -> i32 {
+            SC_ID
+        }
+        fn center_id(&self) -> i32 {
+            399
+        }
+        fn covers(&self, _epoch: Epoch) -> bool {
+            true
+        }
+        fn position_velocity(&self, epoch: Epoch) -> Result<(Vector3, Vector3), EphemerisError> {
+            let (s, c) = (epoch.to_tdb_seconds() * RATE_RAD_S).sin_cos();
+            let (sin_inc, cos_inc) = 30.0_f64.to_radians().sin_cos();
+            Ok((
+                RADIUS_KM * Vector3::new(c, s * cos_inc, s * sin_inc),
+                RADIUS_KM * RATE_RAD_S * Vector3::new(-s, c * cos_inc, c * sin_inc),
+            ))
+        }
+    }
+
+    #[test]
+    fn radial_normal_frame() {
+        // X along the radial direction, and Z along the orbit normal, so Y is along the velocity of the circular orbit.
+        let radial = TwoVectorFrame::new(
+            -10_001,
+            "SC_RIC",
+            FrameAxis::PlusX,
+            DirectionVector::Position {
+                observer_id: 399,
+                target_id: SC_ID,
+            },
+            FrameAxis::PlusZ,
+            DirectionVector::OrbitNormal {
+                observer_id: 399,
+                target_id: SC_ID,
+            },
+        );
+        let almanac = Almanac::default()
+            .with_ephemeris_provider(Arc::new(CircularOrbit))
+            .with_two_vector_frame(radial.clone());
+        assert_eq!(almanac.orientation_id("sc_ric"), Ok(-10_001));
+
+        let epoch = Epoch::from_tdb_seconds(1234.5);
+        let ric = Frame::new(399, -10_001);
+        let dcm = almanac.rotate(EARTH_J2000, ric, epoch).unwrap();
+        assert_eq!(dcm.from, J2000);
+        assert_eq!(dcm.to, -10_001);
+        assert!((dcm.rot_mat.determinant() - 1.0).abs() < 1e-12);
+
+        let state: CartesianState = almanac
+            .translate(Frame::from_ephem_j2000(SC_ID), EARTH_J2000, epoch, None)
+            .unwrap();
+        let in_ric = almanac.rotate_to(state, ric).unwrap();
+        assert!((in_ric.radius_km - Vector3::new(RADIUS_KM, 0.0, 0.0)).norm() < 1e-9);
+        // The frame rotates with the spacecraft, so its velocity in this frame is zero.
+        assert!(
+            in_ric.velocity_km_s.norm() < 1e-6,
+            "{}",
+            in_ric.velocity_km_s
+        );
+
+        // Swapping the roles of the axes leads to the same frame since both directions are orthogonal.
+        let swapped = TwoVectorFrame::new(
+            -10_002,
+            "SC_RIC_SWAPPED",
+            FrameAxis::PlusZ,
+            radial.secondary,
+            FrameAxis::MinusY,
+            DirectionVector::Velocity {
+                observer_id: SC_ID,
+                target_id: 399,
+            },
+        );
+        let almanac = almanac.with_two_vector_frame(swapped);
+        let swapped_dcm = almanac
+            .rotate(EARTH_J2000, Frame::new(399, -10_002), epoch)
+            .unwrap();
+        assert!((swapped_dcm.rot_mat - dcm.rot_mat).norm() < 1e-12);
+
+        // Both directions must define a plane.
+        let degenerate = TwoVectorFrame::new(
+            -10_003,
+            "DEGENERATE",
+            FrameAxis::PlusX,
+            DirectionVector::Constant {
+                vector: Vector3::x(),
+            },
+            FrameAxis::PlusY,
+            DirectionVector::Constant {
+                vector: -Vector3::x(),
+            },
+        );
+        let almanac = almanac.with_two_vector_frame(degenerate);
+        assert!(almanac
+            .rotate(EARTH_J2000, Frame::new(399, -10_003), epoch)
+            .is_err());
+    }
+}
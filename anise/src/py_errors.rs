@@ -16,11 +16,20 @@ use crate::orientations::OrientationError;
 use crate::structure::dataset::DataSetError;
 use core::convert::From;
 
-use pyo3::{exceptions::PyException, prelude::*};
+use pyo3::{
+    exceptions::{PyException, PyValueError},
+    prelude::*,
+};
 
 impl From<PhysicsError> for PyErr {
     fn from(err: PhysicsError) -> PyErr {
-        PyException::new_err(err.to_string())
+        match err {
+            // Combining states at different epochs or in different frames is a usage error, so it is a ValueError.
+            PhysicsError::EpochMismatch { .. } | PhysicsError::FrameMismatch { .. } => {
+                PyValueError::new_err(err.to_string())
+            }
+            _ => PyException::new_err(err.to_string()),
+        }
     }
 }
 
@@ -0,0 +1,313 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+// This file contains the conventions of the Python bindings, which only apply to Python users.
+
+use core::convert::Infallible;
+use core::fmt;
+use core::str::FromStr;
+use std::sync::RwLock;
+
+use hifitime::{Epoch, HifitimeError, TimeScale};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyFloat;
+
+use crate::astro::PhysicsResult;
+use crate::math::cartesian::CartesianState;
+
+static CONFIG: RwLock<Config> = RwLock::new(Config::new());
+
+/// Angular unit of the angles returned by the unit agnostic functions of the Python bindings.
+#[pyclass(eq, eq_int)]
+#[pyo3(module = "anise")]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum AngleUnit {
+    #[default]
+    Degree,
+    Radian,
+}
+
+/// Conventions of the Python bindings, set for the whole session with `set_config`:
+/// + `angle_unit`: unit of the angles of the unit agnostic functions, like `Orbit.inc`, `Orbit.with_inc`, or
+///   `AzElRange.azimuth` (functions suffixed by their unit, like `Orbit.inc_deg`, are not affected), defaults to degrees;
+/// + `time_scale`: time scale of the epoch strings which do not specify one, which all of the Python functions taking an
+///   epoch (like `Orbit(...)` or `Almanac.state_of_named`) accept instead of an Epoch, and of the epochs they return,
+///   defaults to UTC.
+///
+/// :type angle_unit: AngleUnit, optional
+/// :type time_scale: TimeScale, optional
+#[pyclass(eq)]
+#[pyo3(module = "anise")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Config {
+    #[pyo3(get, set)]
+    pub angle_unit: AngleUnit,
+    #[pyo3(get, set)]
+    pub time_scale: TimeScale,
+}
+
+impl Config {
+    pub const fn new() -> Self {
+        Self {
+            angle_unit: AngleUnit::Degree,
+            time_scale: TimeScale::UTC,
+        }
+    }
+
+    /// Returns the provided angle in degrees in the configured angle unit
+    pub fn angle_from_deg(&self, angle_deg: f64) -> f64 {
+        match self.angle_unit {
+            AngleUnit::Degree => angle_deg,
+            AngleUnit::Radian => angle_deg.to_radians(),
+        }
+    }
+
+    /// Returns the provided angle in the configured angle unit in degrees
+    pub fn angle_to_deg(&self, angle: f64) -> f64 {
+        match self.angle_unit {
+            AngleUnit::Degree => angle,
+            AngleUnit::Radian => angle.to_degrees(),
+        }
+    }
+
+    /// Parses the epoch in the configured time scale, unless the string specifies its own time scale.
+    pub fn parse_epoch(&self, epoch: &str) -> Result<Epoch, HifitimeError> {
+        Epoch::from_str(&format!("{epoch} {}", self.time_scale)).or_else(|_| Epoch::from_str(epoch))
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Config {{ angle_unit: {:?}, time_scale: {} }}",
+            self.angle_unit, self.time_scale
+        )
+    }
+}
+
+/// Returns the conventions currently used by the Python bindings
+pub fn current_config() -> Config {
+    // The configuration is always valid, even if a writer panicked.
+    *CONFIG.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Angle in degrees, which the Python bindings convert from and to the configured angle unit: use it as the argument or
+/// return type of the Python functions instead of converting the angle in each of them.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ConfiguredAngle(pub f64);
+
+impl ConfiguredAngle {
+    /// Returns this angle in degrees
+    pub fn deg(self) -> f64 {
+        self.0
+    }
+}
+
+impl<'py> FromPyObject<'py> for ConfiguredAngle {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        Ok(Self(current_config().angle_to_deg(ob.extract()?)))
+    }
+}
+
+impl<'py> IntoPyObject<'py> for ConfiguredAngle {
+    type Target = PyFloat;
+    type Output = Bound<'py, PyFloat>;
+    type Error = Infallible;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        current_config().angle_from_deg(self.0).into_pyobject(py)
+    }
+}
+
+/// Epoch which the Python bindings extract from either an Epoch or a string parsed in the configured time scale, and
+/// return in the configured time scale: use it as the argument or return type of the Python functions taking or returning
+/// an epoch.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ConfiguredEpoch(pub Epoch);
+
+impl<'py> FromPyObject<'py> for ConfiguredEpoch {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(epoch) = ob.extract::<Epoch>() {
+            return Ok(Self(epoch));
+        }
+        let epoch: String = ob.extract()?;
+        current_config()
+            .parse_epoch(&epoch)
+            .map(Self)
+            .map_err(|e| PyValueError::new_err(format!("{e} when parsing epoch `{epoch}`")))
+    }
+}
+
+impl<'py> IntoPyObject<'py> for ConfiguredEpoch {
+    type Target = Epoch;
+    type Output = Bound<'py, Epoch>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        self.0
+            .to_time_scale(current_config().time_scale)
+            .into_pyobject(py)
+    }
+}
+
+#[pymethods]
+impl Config {
+    #[new]
+    #[pyo3(signature = (angle_unit=None, time_scale=None))]
+    fn py_new(angle_unit: Option<AngleUnit>, time_scale: Option<TimeScale>) -> Self {
+        let default = Self::new();
+        Self {
+            angle_unit: angle_unit.unwrap_or(default.angle_unit),
+            time_scale: time_scale.unwrap_or(default.time_scale),
+        }
+    }
+
+    /// Parses the epoch in the time scale of this configuration, unless the string specifies its own time scale.
+    ///
+    /// :type epoch: str
+    /// :rtype: Epoch
+    #[pyo3(name = "parse_epoch")]
+    fn py_parse_epoch(&self, epoch: &str) -> Result<Epoch, HifitimeError> {
+        self.parse_epoch(epoch)
+    }
+
+    /// Formats the epoch in the time scale of this configuration.
+    ///
+    /// :type epoch: Epoch
+    /// :rtype: str
+    fn format_epoch(&self, epoch: Epoch) -> String {
+        format!("{}", epoch.to_time_scale(self.time_scale))
+    }
+
+    fn __str__(&self) -> String {
+        format!("{self}")
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self} (@{self:p})")
+    }
+}
+
+/// Returns the conventions currently used by the Python bindings.
+///
+/// :rtype: Config
+#[pyfunction]
+pub fn get_config() -> Config {
+    current_config()
+}
+
+/// Sets the conventions used by the Python bindings for the rest of the session, e.g.
+/// `set_config(Config(angle_unit=AngleUnit.Radian, time_scale=TimeScale.TDB))`.
+///
+/// :type config: Config
+/// :rtype: None
+#[pyfunction]
+pub fn set_config(config: Config) {
+    *CONFIG.write().unwrap_or_else(|e| e.into_inner()) = config;
+}
+
+#[pymethods]
+impl CartesianState {
+    /// Returns the inclination in the configured angle unit (degrees by default).
+    ///
+    /// :rtype: float
+    #[pyo3(name = "inc")]
+    fn py_inc(&self) -> PhysicsResult<ConfiguredAngle> {
+        Ok(ConfiguredAngle(self.inc_deg()?))
+    }
+
+    /// Returns the right ascension of the ascending node in the configured angle unit (degrees by default).
+    ///
+    /// :rtype: float
+    #[pyo3(name = "raan")]
+    fn py_raan(&self) -> PhysicsResult<ConfiguredAngle> {
+        Ok(ConfiguredAngle(self.raan_deg()?))
+    }
+
+    /// Returns the argument of periapsis in the configured angle unit (degrees by default).
+    ///
+    /// :rtype: float
+    #[pyo3(name = "aop")]
+    fn py_aop(&self) -> PhysicsResult<ConfiguredAngle> {
+        Ok(ConfiguredAngle(self.aop_deg()?))
+    }
+
+    /// Returns the true anomaly in the configured angle unit (degrees by default).
+    ///
+    /// :rtype: float
+    #[pyo3(name = "ta")]
+    fn py_ta(&self) -> PhysicsResult<ConfiguredAngle> {
+        Ok(ConfiguredAngle(self.ta_deg()?))
+    }
+
+    /// Returns the mean anomaly in the configured angle unit (degrees by default).
+    ///
+    /// :rtype: float
+    #[pyo3(name = "ma")]
+    fn py_ma(&self) -> PhysicsResult<ConfiguredAngle> {
+        Ok(ConfiguredAngle(self.ma_deg()?))
+    }
+
+    /// Returns the geodetic latitude, longitude (between 0 and 360 degrees), and height, where the angles are in the
+    /// configured angle unit (degrees by default) and the height is in kilometers.
+    ///
+    /// :rtype: typing.Tuple
+    #[pyo3(name = "geodetic")]
+    fn py_geodetic(&self) -> PhysicsResult<(ConfiguredAngle, ConfiguredAngle, f64)> {
+        let (lat_deg, long_deg, alt_km) = self.latlongalt()?;
+        Ok((ConfiguredAngle(lat_deg), ConfiguredAngle(long_deg), alt_km))
+    }
+
+    /// Returns a copy of this state with the provided inclination, in the configured angle unit (degrees by default).
+    ///
+    /// :type new_inc: float
+    /// :rtype: Orbit
+    #[pyo3(name = "with_inc")]
+    fn py_with_inc(&self, new_inc: ConfiguredAngle) -> PhysicsResult<Self> {
+        self.with_inc_deg(new_inc.deg())
+    }
+
+    /// Returns a copy of this state with the provided right ascension of the ascending node, in the configured angle unit
+    /// (degrees by default).
+    ///
+    /// :type new_raan: float
+    /// :rtype: Orbit
+    #[pyo3(name = "with_raan")]
+    fn py_with_raan(&self, new_raan: ConfiguredAngle) -> PhysicsResult<Self> {
+        self.with_raan_deg(new_raan.deg())
+    }
+
+    /// Returns a copy of this state with the provided argument of periapsis, in the configured angle unit (degrees by
+    /// default).
+    ///
+    /// :type new_aop: float
+    /// :rtype: Orbit
+    #[pyo3(name = "with_aop")]
+    fn py_with_aop(&self, new_aop: ConfiguredAngle) -> PhysicsResult<Self> {
+        self.with_aop_deg(new_aop.deg())
+    }
+
+    /// Returns a copy of this state with the provided true anomaly, in the configured angle unit (degrees by default).
+    ///
+    /// :type new_ta: float
+    /// :rtype: Orbit
+    #[pyo3(name = "with_ta")]
+    fn py_with_ta(&self, new_ta: ConfiguredAngle) -> PhysicsResult<Self> {
+        self.with_ta_deg(new_ta.deg())
+    }
+}
@@ -1,7 +1,7 @@
 use snafu::prelude::*;
 
 use crate::{
-    errors::{DecodingError, IntegrityError},
+    errors::{DecodingError, ErrorCode, IntegrityError},
     structure::lookuptable::LutError,
 };
 use std::io::Error as IOError;
@@ -33,6 +33,18 @@ pub enum DataSetError {
     Conversion { action: String },
 }
 
+impl ErrorCode for DataSetError {
+    fn code(&self) -> u16 {
+        match self {
+            Self::DataSetLut { source, .. } => source.code(),
+            Self::DataSetIntegrity { source, .. } => source.code(),
+            Self::DataDecoding { source, .. } => source.code(),
+            Self::IO { .. } => 801,
+            Self::Conversion { .. } => 802,
+        }
+    }
+}
+
 impl PartialEq for DataSetError {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
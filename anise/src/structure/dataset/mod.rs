@@ -11,7 +11,7 @@ use self::error::{DataDecodingSnafu, DataSetLutSnafu};
 use super::{
     lookuptable::{LookUpTable, LutError},
     metadata::Metadata,
-    semver::Semver,
+    semver::{SchemaCompatibility, Semver},
     ANISE_VERSION,
 };
 use crate::{
@@ -22,10 +22,10 @@ use crate::{
 use core::fmt;
 use core::ops::Deref;
 use der::{
-    asn1::{OctetString, SequenceOf},
+    asn1::{OctetString, OctetStringRef},
     Decode, Encode, Reader, Writer,
 };
-use log::{error, trace};
+use log::{error, info, trace};
 use snafu::prelude::*;
 
 macro_rules! io_imports {
@@ -46,9 +46,15 @@ mod pretty_print;
 pub use datatype::DataSetType;
 pub use error::DataSetError;
 
+/// Length of the ed25519 signature which may close a data set, cf. [DataSet::signature].
+pub const SIGNATURE_LEN: usize = 64;
+
 /// The kind of data that can be encoded in a dataset
 pub trait DataSetT: Clone + Default + Encode + for<'a> Decode<'a> {
     const NAME: &'static str;
+    /// First version of ANISE encoding this structure with its current layout: data sets of older versions cannot be
+    /// decoded and must be rebuilt from their source kernels.
+    const LAYOUT_VERSION: Semver;
 }
 
 /// A DataSet is the core structure shared by all ANISE binary data.
@@ -60,11 +66,41 @@ pub struct DataSet<T: DataSetT, const ENTRIES: usize> {
     pub data_checksum: u32,
     /// The actual data from the dataset
     pub data: Vec<T>,
+    /// Optional ed25519 signature of all of the other fields of this data set as encoded, stored after them such that
+    /// unsigned data sets are unchanged (cf. the `signing` feature). Boxed because most data sets are not signed.
+    pub signature: Option<Box<[u8; SIGNATURE_LEN]>>,
 }
 
 impl<T: DataSetT, const ENTRIES: usize> DataSet<T, ENTRIES> {
     /// Try to load an Anise file from a pointer of bytes
+    ///
+    /// Data sets of another major version of ANISE, or older than the layout of their structure, are rejected without
+    /// being decoded, cf. [SchemaCompatibility].
     pub fn try_from_bytes<B: Deref<Target = [u8]>>(bytes: B) -> Result<Self, DataSetError> {
+        if let Some(Ok(file_version)) = bytes.get(0..5).map(Semver::from_der) {
+            match Self::compatibility_of(file_version) {
+                SchemaCompatibility::Incompatible => {
+                    return Err(DataSetError::DataDecoding {
+                        action: "checking data set version",
+                        source: DecodingError::AniseVersion {
+                            got: file_version,
+                            exp: ANISE_VERSION,
+                        },
+                    })
+                }
+                SchemaCompatibility::OlderMinor => {
+                    info!(
+                        "{} data set of version {}.{}.{} can be migrated with `anise upgrade`",
+                        T::NAME,
+                        file_version.major,
+                        file_version.minor,
+                        file_version.patch
+                    );
+                }
+                _ => {}
+            }
+        }
+
         match Self::from_der(&bytes) {
             Ok(ctx) => {
                 trace!("[try_from_bytes] loaded context successfully");
@@ -121,6 +157,48 @@ impl<T: DataSetT, const ENTRIES: usize> DataSet<T, ENTRIES> {
         Self::try_from_bytes(buf).unwrap()
     }
 
+    /// Returns the compatibility of the version of this data set with this version of ANISE.
+    pub fn schema_compatibility(&self) -> SchemaCompatibility {
+        Self::compatibility_of(self.metadata.anise_version)
+    }
+
+    /// Returns the compatibility of a data set of the provided version, which is incompatible if it predates the
+    /// layout of the structures of this data set.
+    fn compatibility_of(file_version: Semver) -> SchemaCompatibility {
+        if file_version < T::LAYOUT_VERSION {
+            SchemaCompatibility::Incompatible
+        } else {
+            file_version.compatibility_with(ANISE_VERSION)
+        }
+    }
+
+    /// Migrates this data set of an older minor version to the current version of ANISE, such that it is encoded with
+    /// the current structures, and returns whether it was changed.
+    ///
+    /// Data sets of a newer minor version are not downgraded, and data sets predating the layout of their structures
+    /// cannot be migrated: this returns an error instead.
+    pub fn upgrade(&mut self) -> Result<bool, DataSetError> {
+        match self.schema_compatibility() {
+            SchemaCompatibility::Current => Ok(false),
+            SchemaCompatibility::OlderMinor => {
+                // The entries were decoded with the current structures because their layout is compatible, so they
+                // are encoded with the current layout when saved.
+                self.metadata.anise_version = ANISE_VERSION;
+                self.set_crc32();
+                Ok(true)
+            }
+            SchemaCompatibility::NewerMinor | SchemaCompatibility::Incompatible => {
+                Err(DataSetError::DataDecoding {
+                    action: "upgrading data set",
+                    source: DecodingError::AniseVersion {
+                        got: self.metadata.anise_version,
+                        exp: ANISE_VERSION,
+                    },
+                })
+            }
+        }
+    }
+
     /// Compute the CRC32 of the underlying bytes
     pub fn crc32(&self) -> u32 {
         let bytes = self.build_data_seq().1;
@@ -163,6 +241,55 @@ impl<T: DataSetT, const ENTRIES: usize> DataSet<T, ENTRIES> {
         }
     }
 
+    /// Returns the BLAKE3 digest of the canonical form of this data set, i.e. of its entries encoded in DER and sorted by
+    /// ID then by name, such that two data sets with the same entries have the same digest even if they were encoded in
+    /// another order, on another machine, or with other metadata (e.g. the creation date).
+    pub fn digest(&self) -> Result<[u8; 32], DataSetError> {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(T::NAME.as_bytes());
+
+        let mut by_id = self.lut.by_id.iter().collect::<Vec<_>>();
+        by_id.sort_by_key(|(id, _)| **id);
+        hasher.update(&(by_id.len() as u64).to_le_bytes());
+        for (id, index) in by_id {
+            hasher.update(&id.to_le_bytes());
+            self.hash_entry(&mut hasher, *index)?;
+        }
+
+        let mut by_name = self.lut.by_name.iter().collect::<Vec<_>>();
+        by_name.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+        hasher.update(&(by_name.len() as u64).to_le_bytes());
+        for (name, index) in by_name {
+            hasher.update(&(name.len() as u64).to_le_bytes());
+            hasher.update(name.as_bytes());
+            self.hash_entry(&mut hasher, *index)?;
+        }
+
+        Ok(hasher.finalize().into())
+    }
+
+    /// Hashes the length and the DER encoding of the entry at the provided index.
+    fn hash_entry(&self, hasher: &mut blake3::Hasher, index: u32) -> Result<(), DataSetError> {
+        match self.data.get(index as usize) {
+            Some(entry) => {
+                let mut buf = vec![];
+                entry
+                    .encode_to_vec(&mut buf)
+                    .map_err(|err| DecodingError::DecodingDer { err })
+                    .context(DataDecodingSnafu {
+                        action: "encoding entry for its digest",
+                    })?;
+                hasher.update(&(buf.len() as u64).to_le_bytes());
+                hasher.update(&buf);
+            }
+            // Dangling index, cannot happen in a data set which passed its integrity check.
+            None => {
+                hasher.update(&u64::MAX.to_le_bytes());
+            }
+        }
+        Ok(())
+    }
+
     pub fn push(
         &mut self,
         item: T,
@@ -228,11 +355,18 @@ impl<T: DataSetT, const ENTRIES: usize> DataSet<T, ENTRIES> {
 
     /// Get a copy of the data with that ID, if that ID is in the lookup table
     pub fn get_by_id(&self, id: NaifId) -> Result<T, DataSetError> {
+        self.get_ref_by_id(id).cloned()
+    }
+
+    /// Get a reference to the data with that ID, if that ID is in the lookup table.
+    ///
+    /// The entries are decoded once, when the data set is loaded, so this neither decodes nor copies the entry: prefer it over
+    /// [DataSet::get_by_id] in hot loops, e.g. to compute the IAU rotations from the planetary data.
+    pub fn get_ref_by_id(&self, id: NaifId) -> Result<&T, DataSetError> {
         if let Some(index) = self.lut.by_id.get(&id) {
             // Found the ID
             self.data
                 .get(*index as usize)
-                .cloned()
                 .ok_or_else(|| LutError::InvalidIndex { index: *index })
                 .context(DataSetLutSnafu {
                     action: "fetching by ID",
@@ -301,10 +435,14 @@ impl<T: DataSetT, const ENTRIES: usize> DataSet<T, ENTRIES> {
 
     /// Get a copy of the data with that name, if that name is in the lookup table
     pub fn get_by_name(&self, name: &str) -> Result<T, DataSetError> {
+        self.get_ref_by_name(name).cloned()
+    }
+
+    /// Get a reference to the data with that name, if that name is in the lookup table, cf. [DataSet::get_ref_by_id].
+    pub fn get_ref_by_name(&self, name: &str) -> Result<&T, DataSetError> {
         if let Some(index) = self.lut.by_name.get(&name.try_into().unwrap()) {
             self.data
                 .get(*index as usize)
-                .cloned()
                 .ok_or_else(|| LutError::InvalidIndex { index: *index })
                 .context(DataSetLutSnafu {
                     action: "fetching by name",
@@ -377,6 +515,60 @@ impl<T: DataSetT, const ENTRIES: usize> DataSet<T, ENTRIES> {
         }
     }
 
+    /// Mutates this dataset to add all of the entries of the other dataset, replacing the entries which have any of the
+    /// same IDs or names, e.g. to merge the planetary data of several Almanacs.
+    pub fn merge(&mut self, other: &Self) -> Result<(), DataSetError> {
+        for (index, item) in other.data.iter().enumerate() {
+            let index = index as u32;
+            let ids = other
+                .lut
+                .by_id
+                .iter()
+                .filter(|(_, entry)| **entry == index)
+                .map(|(id, _)| *id)
+                .collect::<Vec<NaifId>>();
+            let names = other
+                .lut
+                .by_name
+                .iter()
+                .filter(|(_, entry)| **entry == index)
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<_>>();
+            if ids.is_empty() && names.is_empty() {
+                // Removed entry
+                continue;
+            }
+
+            for id in &ids {
+                if self.lut.by_id.contains_key(id) {
+                    self.rm_by_id(*id)?;
+                }
+            }
+            for name in &names {
+                if self.lut.by_name.contains_key(name) {
+                    self.rm_by_name(name.as_str())?;
+                }
+            }
+
+            let new_index = self.data.len() as u32;
+            for id in ids {
+                self.lut.append_id(id, new_index).context(DataSetLutSnafu {
+                    action: "merging data with ID",
+                })?;
+            }
+            for name in names {
+                self.lut
+                    .append_name(name.as_str(), new_index)
+                    .context(DataSetLutSnafu {
+                        action: "merging data with name",
+                    })?;
+            }
+            self.data.push(item.clone());
+        }
+
+        Ok(())
+    }
+
     /// Saves this dataset to the provided file
     /// If overwrite is set to false, and the filename already exists, this function will return an error.
     pub fn save_as(&self, filename: &PathBuf, overwrite: bool) -> Result<(), DataSetError> {
@@ -433,6 +625,28 @@ impl<T: DataSetT, const ENTRIES: usize> DataSet<T, ENTRIES> {
         self.len() == 0
     }
 
+    /// Returns the encoding of this data set without its signature, i.e. the bytes covered by its signature.
+    pub fn signed_bytes(&self) -> Result<Vec<u8>, DataSetError> {
+        let mut buf = vec![];
+        self.encode_to_vec(&mut buf)
+            .map_err(|err| DataSetError::DataDecoding {
+                action: "encoding data set",
+                source: DecodingError::DecodingDer { err },
+            })?;
+        if let Some(signature) = &self.signature {
+            // The signature block is the last field of the encoding.
+            let block_len = OctetStringRef::new(&signature[..])
+                .and_then(|block| block.encoded_len())
+                .and_then(usize::try_from)
+                .map_err(|err| DataSetError::DataDecoding {
+                    action: "encoding data set signature",
+                    source: DecodingError::DecodingDer { err },
+                })?;
+            buf.truncate(buf.len() - block_len);
+        }
+        Ok(buf)
+    }
+
     /// Returns this data as a data sequence, cloning all of the entries into this sequence.
     fn build_data_seq(&self) -> (Vec<u32>, OctetString) {
         let mut buf = Vec::with_capacity(ENTRIES * 2);
@@ -452,11 +666,17 @@ impl<T: DataSetT, const ENTRIES: usize> DataSet<T, ENTRIES> {
 impl<T: DataSetT, const ENTRIES: usize> Encode for DataSet<T, ENTRIES> {
     fn encoded_len(&self) -> der::Result<der::Length> {
         let (bytes_meta, bytes) = self.build_data_seq();
+        let signature = self
+            .signature
+            .as_ref()
+            .map(|signature| OctetStringRef::new(&signature[..]))
+            .transpose()?;
         self.metadata.encoded_len()?
             + self.lut.encoded_len()?
             + self.data_checksum.encoded_len()?
             + bytes_meta.encoded_len()?
             + bytes.encoded_len()?
+            + signature.encoded_len()?
     }
 
     fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
@@ -465,7 +685,11 @@ impl<T: DataSetT, const ENTRIES: usize> Encode for DataSet<T, ENTRIES> {
         self.lut.encode(encoder)?;
         self.data_checksum.encode(encoder)?;
         bytes_meta.encode(encoder)?;
-        bytes.encode(encoder)
+        bytes.encode(encoder)?;
+        if let Some(signature) = &self.signature {
+            OctetStringRef::new(&signature[..])?.encode(encoder)?;
+        }
+        Ok(())
     }
 }
 
@@ -477,25 +701,47 @@ impl<'a, T: DataSetT, const ENTRIES: usize> Decode<'a> for DataSet<T, ENTRIES> {
         // Metadata of the bytes to decode.
         // The first integer contains the number of usable items in the data.
         // The other integers are the encoded lengths of each of the data.
-        let bytes_meta: SequenceOf<u32, ENTRIES> = decoder.decode()?;
+        let bytes_meta: Vec<u32> = decoder.decode()?;
         let der_octets: OctetString = decoder.decode()?;
         let bytes = der_octets.as_bytes();
 
         let mut data = vec![];
 
+        // A structure which does not match the encoded data is an error, not a panic.
+        let invalid = || {
+            der::Error::new(
+                der::ErrorKind::Value {
+                    tag: der::Tag::OctetString,
+                },
+                der::Length::ZERO,
+            )
+        };
+
         let mut idx = 0;
-        for meta_idx in 0..*bytes_meta.get(0).unwrap() as usize {
-            let next_len = *bytes_meta.get(meta_idx + 1).unwrap() as usize;
-            let this_data = T::from_der(&bytes[idx..idx + next_len]).unwrap();
+        for meta_idx in 0..*bytes_meta.get(0).ok_or_else(invalid)? as usize {
+            let next_len = *bytes_meta.get(meta_idx + 1).ok_or_else(invalid)? as usize;
+            let this_bytes = bytes.get(idx..idx + next_len).ok_or_else(invalid)?;
+            let this_data = T::from_der(this_bytes)?;
             data.push(this_data);
             idx += next_len;
         }
 
+        // Unsigned data sets end with their data.
+        let signature = if decoder.is_finished() {
+            None
+        } else {
+            let signature: OctetStringRef = decoder.decode()?;
+            Some(Box::new(
+                signature.as_bytes().try_into().map_err(|_| invalid())?,
+            ))
+        };
+
         Ok(Self {
             metadata,
             lut,
             data_checksum: crc32_checksum,
             data,
+            signature,
         })
     }
 }
@@ -537,8 +783,84 @@ mod dataset_ut {
         assert_eq!(repr, repr_dec);
 
         dbg!(repr);
-        assert_eq!(core::mem::size_of::<DataSet<SpacecraftData, 2>>(), 256);
-        assert_eq!(core::mem::size_of::<DataSet<SpacecraftData, 128>>(), 8824);
+        #[cfg(feature = "heapless-lut")]
+        {
+            assert_eq!(core::mem::size_of::<DataSet<SpacecraftData, 2>>(), 264);
+            assert_eq!(core::mem::size_of::<DataSet<SpacecraftData, 128>>(), 8832);
+        }
+    }
+
+    #[test]
+    fn constellation_dataset() {
+        let mut constellation = SpacecraftDataSet::default();
+        for sc_no in 0..150 {
+            let sc = SpacecraftData {
+                mass: Some(Mass::from_dry_mass(100.0 + sc_no as f64)),
+                ..Default::default()
+            };
+            constellation
+                .push(sc, Some(-1000 - sc_no), Some(&format!("SAT-{sc_no:03}")))
+                .unwrap();
+        }
+
+        let mut buf = vec![];
+        constellation.encode_to_vec(&mut buf).unwrap();
+        let constellation_dec = SpacecraftDataSet::from_der(&buf).unwrap();
+        assert_eq!(constellation_dec, constellation);
+        assert_eq!(
+            constellation_dec.get_by_name("SAT-149").unwrap().mass,
+            Some(Mass::from_dry_mass(249.0))
+        );
+
+        // A data set larger than the capacity of an inline LUT cannot be decoded, but a heap allocated LUT grows as needed.
+        #[cfg(feature = "heapless-lut")]
+        assert!(DataSet::<SpacecraftData, 16>::from_der(&buf).is_err());
+        #[cfg(not(feature = "heapless-lut"))]
+        assert_eq!(
+            DataSet::<SpacecraftData, 16>::from_der(&buf).unwrap().len(),
+            150
+        );
+
+        // Data sets built with a smaller capacity can be decoded in the larger one.
+        let mut small = DataSet::<SpacecraftData, 16>::default();
+        small
+            .push(SpacecraftData::default(), Some(-10), Some("SMALL"))
+            .unwrap();
+        let mut buf = vec![];
+        small.encode_to_vec(&mut buf).unwrap();
+        let small_dec = SpacecraftDataSet::from_der(&buf).unwrap();
+        assert_eq!(small_dec.get_by_id(-10).unwrap(), SpacecraftData::default());
+    }
+
+    #[test]
+    fn merge_datasets() {
+        let sc = |dry_mass_kg: f64| SpacecraftData {
+            mass: Some(Mass::from_dry_mass(dry_mass_kg)),
+            ..Default::default()
+        };
+
+        let mut base = SpacecraftDataSet::default();
+        base.push(sc(100.0), Some(-10), Some("SC-10")).unwrap();
+        base.push(sc(200.0), Some(-20), Some("SC-20")).unwrap();
+        base.push(sc(300.0), None, Some("SC-30")).unwrap();
+
+        let mut other = SpacecraftDataSet::default();
+        other.push(sc(250.0), Some(-20), Some("SC-20")).unwrap();
+        other.push(sc(400.0), Some(-40), None).unwrap();
+        other.push(sc(500.0), Some(-50), Some("SC-50")).unwrap();
+        other.rm_by_id(-50).unwrap();
+
+        base.merge(&other).unwrap();
+        assert_eq!(base.get_by_id(-10).unwrap(), sc(100.0));
+        assert_eq!(base.get_by_name("SC-10").unwrap(), sc(100.0));
+        // Replaced by the entry of the other data set, both by ID and by name
+        assert_eq!(base.get_by_id(-20).unwrap(), sc(250.0));
+        assert_eq!(base.get_by_name("SC-20").unwrap(), sc(250.0));
+        assert_eq!(base.get_by_name("SC-30").unwrap(), sc(300.0));
+        assert_eq!(base.get_by_id(-40).unwrap(), sc(400.0));
+        // Removed entries are not merged
+        assert!(base.get_by_id(-50).is_err());
+        assert!(base.get_by_name("SC-50").is_err());
     }
 
     #[test]
@@ -560,6 +882,8 @@ mod dataset_ut {
             }),
             mass: Some(Mass::from_dry_and_prop_masses(150.0, 50.6)),
             drag_data: Some(DragData::default()),
+
+            ..Default::default()
         };
         let srp_sc = SpacecraftData {
             srp_data: Some(SRPData::default()),
@@ -598,10 +922,10 @@ mod dataset_ut {
 
         // Build the lookup table
         dataset
-            .push(srp_sc, Some(-20), Some("SRP spacecraft"))
+            .push(srp_sc.clone(), Some(-20), Some("SRP spacecraft"))
             .unwrap();
         dataset
-            .push(full_sc, Some(-50), Some("Full spacecraft"))
+            .push(full_sc.clone(), Some(-50), Some("Full spacecraft"))
             .unwrap();
 
         dataset.set_crc32();
@@ -627,13 +951,22 @@ mod dataset_ut {
         // And check that we get an error if the data is wrong.
         assert!(repr_dec.get_by_id(0).is_err());
 
+        // The references point to the decoded entries themselves.
+        assert!(core::ptr::eq(
+            repr_dec.get_ref_by_id(-50).unwrap(),
+            repr_dec.get_ref_by_name("Full spacecraft").unwrap()
+        ));
+        assert_eq!(repr_dec.get_ref_by_id(-20).unwrap(), &srp_sc);
+        assert!(repr_dec.get_ref_by_id(0).is_err());
+        assert!(repr_dec.get_ref_by_name("Unavailable SC").is_err());
+
         // Check that we can modify it.
         let orig_dataset = dataset.clone();
 
         // Grab a copy of the original data
         let mut sc = dataset.get_by_name("SRP spacecraft").unwrap();
         sc.srp_data.as_mut().unwrap().coeff_reflectivity = 1.1;
-        dataset.set_by_name("SRP spacecraft", sc).unwrap();
+        dataset.set_by_name("SRP spacecraft", sc.clone()).unwrap();
         // Ensure that we've modified only that entry
         assert_eq!(
             dataset.get_by_name("Full spacecraft").unwrap(),
@@ -684,6 +1017,61 @@ mod dataset_ut {
         assert!(dataset.get_by_name("Renamed SRP spacecraft").is_err());
     }
 
+    #[test]
+    fn canonical_digest() {
+        let srp_sc = SpacecraftData {
+            srp_data: Some(SRPData::default()),
+            ..Default::default()
+        };
+        let drag_sc = SpacecraftData {
+            drag_data: Some(DragData::default()),
+            ..Default::default()
+        };
+
+        let mut dataset = DataSet::<SpacecraftData, 4>::default();
+        dataset
+            .push(srp_sc.clone(), Some(-20), Some("SRP"))
+            .unwrap();
+        dataset
+            .push(drag_sc.clone(), Some(-50), Some("Drag"))
+            .unwrap();
+
+        // Same entries in another order and with other metadata
+        let mut reordered = DataSet::<SpacecraftData, 4>::default();
+        reordered.metadata.creation_date += hifitime::Unit::Day * 1;
+        reordered.push(drag_sc, Some(-50), Some("Drag")).unwrap();
+        reordered.push(srp_sc, Some(-20), Some("SRP")).unwrap();
+        assert_ne!(dataset, reordered);
+        assert_eq!(dataset.digest().unwrap(), reordered.digest().unwrap());
+
+        // Round trip through DER
+        dataset.set_crc32();
+        let mut buf = vec![];
+        dataset.encode_to_vec(&mut buf).unwrap();
+        let decoded = DataSet::<SpacecraftData, 4>::from_der(&buf).unwrap();
+        assert_eq!(dataset.digest().unwrap(), decoded.digest().unwrap());
+
+        // Any change of a value, an ID, or a name changes the digest
+        let mut changed = dataset.clone();
+        changed
+            .set_by_id(
+                -20,
+                SpacecraftData {
+                    srp_data: Some(SRPData {
+                        area_m2: 2.0,
+                        coeff_reflectivity: 1.8,
+                    }),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_ne!(dataset.digest().unwrap(), changed.digest().unwrap());
+
+        let mut renamed = dataset.clone();
+        renamed.lut.rename("SRP", "Solar sail").unwrap();
+        assert_ne!(dataset.digest().unwrap(), renamed.digest().unwrap());
+    }
+
     #[test]
     fn spacecraft_constants_lookup_builder() {
         // Build some data first.
@@ -703,6 +1091,8 @@ mod dataset_ut {
             }),
             mass: Some(Mass::from_dry_and_prop_masses(150.0, 50.6)),
             drag_data: Some(DragData::default()),
+
+            ..Default::default()
         };
         let srp_sc = SpacecraftData {
             srp_data: Some(SRPData::default()),
@@ -713,19 +1103,19 @@ mod dataset_ut {
 
         let mut dataset = DataSet::<SpacecraftData, 16>::default();
         dataset
-            .push(srp_sc, Some(-20), Some("SRP spacecraft"))
+            .push(srp_sc.clone(), Some(-20), Some("SRP spacecraft"))
             .unwrap();
 
         dataset
-            .push(full_sc, Some(-50), Some("Full spacecraft"))
+            .push(full_sc.clone(), Some(-50), Some("Full spacecraft"))
             .unwrap();
 
         // Pushing without name as ID -51
-        dataset.push(full_sc, Some(-51), None).unwrap();
+        dataset.push(full_sc.clone(), Some(-51), None).unwrap();
 
         // Pushing without ID
         dataset
-            .push(srp_sc, None, Some("ID less SRP spacecraft"))
+            .push(srp_sc.clone(), None, Some("ID less SRP spacecraft"))
             .unwrap();
 
         // Make sure to set the CRC32.
@@ -757,7 +1147,7 @@ mod dataset_ut {
         // Check that we can set by ID
         let mut repr = dataset.get_by_id(-50).unwrap();
         repr.mass.as_mut().unwrap().dry_mass_kg = 100.5;
-        dataset.set_by_id(-50, repr).unwrap();
+        dataset.set_by_id(-50, repr.clone()).unwrap();
         assert_eq!(
             dataset.get_by_id(-50).unwrap().mass.unwrap().dry_mass_kg,
             100.5,
@@ -799,4 +1189,122 @@ mod dataset_ut {
         // Check that the associated name is no reachable
         assert!(dataset.get_by_id(-52).is_err(), "still reachable by id");
     }
+
+    #[test]
+    fn schema_versions() {
+        use crate::errors::DecodingError;
+        use crate::math::rotation::Quaternion;
+        use crate::structure::dataset::DataSetError;
+        use crate::structure::dataset::DataSetT;
+        use crate::structure::planetocentric::PlanetaryData;
+        use crate::structure::semver::{SchemaCompatibility, Semver};
+        use crate::structure::{EulerParameterDataSet, PlanetaryDataSet, ANISE_VERSION};
+
+        let encode_with_version = |anise_version: Semver| {
+            let mut dataset = SpacecraftDataSet::default();
+            dataset
+                .push(SpacecraftData::default(), Some(-20), Some("SC"))
+                .unwrap();
+            dataset.metadata.anise_version = anise_version;
+            dataset.set_crc32();
+            let mut buf = vec![];
+            dataset.encode_to_vec(&mut buf).unwrap();
+            buf
+        };
+
+        // A newer major version is rejected without being decoded
+        let newer_major = Semver {
+            major: ANISE_VERSION.major + 1,
+            ..ANISE_VERSION
+        };
+        assert_eq!(
+            SpacecraftDataSet::try_from_bytes(encode_with_version(newer_major)),
+            Err(DataSetError::DataDecoding {
+                action: "checking data set version",
+                source: DecodingError::AniseVersion {
+                    got: newer_major,
+                    exp: ANISE_VERSION
+                }
+            })
+        );
+
+        // A version older than the layout of the spacecraft data is rejected without being decoded
+        let older_minor = Semver {
+            minor: SpacecraftData::LAYOUT_VERSION.minor - 1,
+            ..ANISE_VERSION
+        };
+        assert_eq!(
+            SpacecraftDataSet::try_from_bytes(encode_with_version(older_minor)),
+            Err(DataSetError::DataDecoding {
+                action: "checking data set version",
+                source: DecodingError::AniseVersion {
+                    got: older_minor,
+                    exp: ANISE_VERSION
+                }
+            })
+        );
+
+        // An older minor version with the same layout is decoded and can be upgraded
+        let mut dataset = EulerParameterDataSet::default();
+        dataset
+            .push(Quaternion::identity(1, 2), Some(1), Some("EP"))
+            .unwrap();
+        dataset.metadata.anise_version = Quaternion::LAYOUT_VERSION;
+        dataset.set_crc32();
+        let mut buf = vec![];
+        dataset.encode_to_vec(&mut buf).unwrap();
+
+        let mut dataset = EulerParameterDataSet::try_from_bytes(buf).unwrap();
+        assert_eq!(
+            dataset.schema_compatibility(),
+            SchemaCompatibility::OlderMinor
+        );
+        assert!(dataset.get_by_id(1).is_ok());
+        assert!(dataset.upgrade().unwrap());
+        assert_eq!(dataset.metadata.anise_version, ANISE_VERSION);
+        assert!(dataset.check_integrity().is_ok());
+        assert!(!dataset.upgrade().unwrap());
+
+        // The planetary data of the previous minor version, e.g. the published planetary constants, is still decoded
+        let mut dataset = PlanetaryDataSet::default();
+        dataset
+            .push(
+                PlanetaryData {
+                    object_id: 399,
+                    mu_km3_s2: 398_600.435_436,
+                    ..Default::default()
+                },
+                Some(399),
+                Some("Earth"),
+            )
+            .unwrap();
+        dataset.metadata.anise_version = Semver {
+            major: 0,
+            minor: 4,
+            patch: 0,
+        };
+        dataset.set_crc32();
+        let mut buf = vec![];
+        dataset.encode_to_vec(&mut buf).unwrap();
+
+        let dataset = PlanetaryDataSet::try_from_bytes(buf).unwrap();
+        assert_eq!(
+            dataset.schema_compatibility(),
+            SchemaCompatibility::OlderMinor
+        );
+        assert!(dataset.get_by_id(399).is_ok());
+
+        // A newer minor version is decoded but not downgraded
+        let newer_minor = Semver {
+            minor: ANISE_VERSION.minor + 1,
+            ..ANISE_VERSION
+        };
+        let mut dataset =
+            SpacecraftDataSet::try_from_bytes(encode_with_version(newer_minor)).unwrap();
+        assert_eq!(
+            dataset.schema_compatibility(),
+            SchemaCompatibility::NewerMinor
+        );
+        assert!(dataset.upgrade().is_err());
+    }
 }
@@ -7,14 +7,17 @@
  *
  * Documentation: https://nyxspace.com/
  */
-use der::{
-    asn1::{OctetStringRef, SequenceOf},
-    Decode, Encode, Reader, Writer,
-};
-use heapless::{FnvIndexMap, String};
+use core::hash::Hash;
+use der::{asn1::OctetStringRef, Decode, Encode, Reader, Writer};
+#[cfg(feature = "heapless-lut")]
+use heapless::FnvIndexMap;
+use heapless::String;
 use log::warn;
 use snafu::prelude::*;
+#[cfg(not(feature = "heapless-lut"))]
+use std::collections::BTreeMap;
 
+use crate::errors::ErrorCode;
 use crate::NaifId;
 
 /// Maximum length of a look up table name string
@@ -41,6 +44,102 @@ pub enum LutError {
     InvalidIndex { index: u32 },
 }
 
+impl ErrorCode for LutError {
+    fn code(&self) -> u16 {
+        match self {
+            Self::IdLutFull { .. } => 901,
+            Self::NameLutFull { .. } => 902,
+            Self::NoKeyProvided => 903,
+            Self::UnknownId { .. } => 904,
+            Self::UnknownName { .. } => 905,
+            Self::InvalidIndex { .. } => 906,
+        }
+    }
+}
+
+/// A map of a look up table, stored on the heap such that its capacity is only bounded by the available memory.
+///
+/// With the `heapless-lut` feature, the map is instead stored inline with a capacity of `ENTRIES`, which must be a power
+/// of two, for targets where the size of the data sets must be known at build time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LutMap<K: Eq + Hash + Ord, V, const ENTRIES: usize> {
+    #[cfg(not(feature = "heapless-lut"))]
+    map: BTreeMap<K, V>,
+    #[cfg(feature = "heapless-lut")]
+    map: FnvIndexMap<K, V, ENTRIES>,
+}
+
+impl<K: Eq + Hash + Ord, V, const ENTRIES: usize> Default for LutMap<K, V, ENTRIES> {
+    fn default() -> Self {
+        Self {
+            map: Default::default(),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Ord, V, const ENTRIES: usize> LutMap<K, V, ENTRIES> {
+    /// Inserts this key and value, returning the previous value of this key, or the key and value back if the map is full.
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, (K, V)> {
+        #[cfg(not(feature = "heapless-lut"))]
+        {
+            Ok(self.map.insert(key, value))
+        }
+        #[cfg(feature = "heapless-lut")]
+        {
+            self.map.insert(key, value)
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.map.get(key)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.map.get_mut(key)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Removes this key and returns its value, if any.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.map.remove(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.map.iter()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.map.keys()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.map.values()
+    }
+}
+
+impl<'a, K: Eq + Hash + Ord, V, const ENTRIES: usize> IntoIterator for &'a LutMap<K, V, ENTRIES> {
+    type Item = (&'a K, &'a V);
+    #[cfg(not(feature = "heapless-lut"))]
+    type IntoIter = std::collections::btree_map::Iter<'a, K, V>;
+    #[cfg(feature = "heapless-lut")]
+    type IntoIter = heapless::IndexMapIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.iter()
+    }
+}
+
 /// A LookUpTable allows finding the [u32] ("NaifId") associated with either an ID or a name.
 ///
 /// # Note
@@ -48,9 +147,9 @@ pub enum LutError {
 #[derive(Clone, Default, Debug, PartialEq, Eq)]
 pub struct LookUpTable<const ENTRIES: usize> {
     /// Unique IDs of each item in the LUT
-    pub by_id: FnvIndexMap<NaifId, u32, ENTRIES>,
+    pub by_id: LutMap<NaifId, u32, ENTRIES>,
     /// Corresponding index for each hash
-    pub by_name: FnvIndexMap<String<32>, u32, ENTRIES>,
+    pub by_name: LutMap<String<32>, u32, ENTRIES>,
 }
 
 impl<const ENTRIES: usize> LookUpTable<ENTRIES> {
@@ -79,8 +178,8 @@ impl<const ENTRIES: usize> LookUpTable<ENTRIES> {
     }
 
     /// Returns the list of entries of this LUT
-    pub fn entries(&self) -> FnvIndexMap<u32, (Option<NaifId>, Option<String<32>>), ENTRIES> {
-        let mut rtn = FnvIndexMap::default();
+    pub fn entries(&self) -> LutMap<u32, (Option<NaifId>, Option<String<32>>), ENTRIES> {
+        let mut rtn = LutMap::default();
 
         for (id, entry) in &self.by_id {
             // IDs are unique, and this is the first iteration, so we can't be overwriting anything
@@ -104,7 +203,7 @@ impl<const ENTRIES: usize> LookUpTable<ENTRIES> {
     ///
     /// This will return an error if the current ID is not in the LUT, or if the new ID is already in the LUT.
     pub fn reid(&mut self, current_id: i32, new_id: i32) -> Result<(), LutError> {
-        if let Some(entry) = self.by_id.swap_remove(&current_id) {
+        if let Some(entry) = self.by_id.remove(&current_id) {
             // We can unwrap the insertion because we just removed something.
             self.by_id.insert(new_id, entry).unwrap();
             Ok(())
@@ -128,7 +227,7 @@ impl<const ENTRIES: usize> LookUpTable<ENTRIES> {
     ///
     /// This will return an error if the current ID is not in the LUT, or if the new ID is already in the LUT.
     pub fn rename(&mut self, current_name: &str, new_name: &str) -> Result<(), LutError> {
-        if let Some(entry) = self.by_name.swap_remove(&current_name.try_into().unwrap()) {
+        if let Some(entry) = self.by_name.remove(&current_name.try_into().unwrap()) {
             // We can unwrap the insertion because we just removed something.
             self.by_name
                 .insert(new_name.try_into().unwrap(), entry)
@@ -188,32 +287,22 @@ impl<const ENTRIES: usize> LookUpTable<ENTRIES> {
     ///
     /// # Note
     /// The list of entries might be duplicated if all items have both a name and an ID.
-    fn der_encoding(
-        &self,
-    ) -> (
-        SequenceOf<i32, ENTRIES>,
-        SequenceOf<u32, ENTRIES>,
-        SequenceOf<OctetStringRef, ENTRIES>,
-        SequenceOf<u32, ENTRIES>,
-    ) {
+    fn der_encoding(&self) -> (Vec<i32>, Vec<u32>, Vec<OctetStringRef>, Vec<u32>) {
         // Build the list of entries
-        let mut id_entries = SequenceOf::<u32, ENTRIES>::new();
-        let mut name_entries = SequenceOf::<u32, ENTRIES>::new();
+        let mut id_entries = Vec::with_capacity(self.by_id.len());
+        let mut name_entries = Vec::with_capacity(self.by_name.len());
 
         // Build the list of keys
-        let mut ids = SequenceOf::<i32, ENTRIES>::new();
+        let mut ids = Vec::with_capacity(self.by_id.len());
         for (id, index) in &self.by_id {
-            ids.add(*id).unwrap();
-            id_entries.add(*index).unwrap();
+            ids.push(*id);
+            id_entries.push(*index);
         }
         // Build the list of names
-        let mut names = SequenceOf::<OctetStringRef, ENTRIES>::new();
+        let mut names = Vec::with_capacity(self.by_name.len());
         for (name, index) in &self.by_name {
-            names
-                .add(OctetStringRef::new(name.as_bytes()).unwrap())
-                .unwrap();
-
-            name_entries.add(*index).unwrap();
+            names.push(OctetStringRef::new(name.as_bytes()).unwrap());
+            name_entries.push(*index);
         }
 
         (ids, id_entries, names, name_entries)
@@ -242,13 +331,16 @@ impl<'a, const ENTRIES: usize> Decode<'a> for LookUpTable<ENTRIES> {
     fn decode<R: Reader<'a>>(decoder: &mut R) -> der::Result<Self> {
         // Decode as sequences and use that to build the look up table.
         let mut lut = Self::default();
-        let ids: SequenceOf<i32, ENTRIES> = decoder.decode()?;
-        let id_entries: SequenceOf<u32, ENTRIES> = decoder.decode()?;
-        let names: SequenceOf<OctetStringRef, ENTRIES> = decoder.decode()?;
-        let name_entries: SequenceOf<u32, ENTRIES> = decoder.decode()?;
+        let ids: Vec<i32> = decoder.decode()?;
+        let id_entries: Vec<u32> = decoder.decode()?;
+        let names: Vec<OctetStringRef> = decoder.decode()?;
+        let name_entries: Vec<u32> = decoder.decode()?;
+
+        // A look up table larger than the capacity of this one is an error, not a panic.
+        let full = || der::Error::new(der::ErrorKind::Overlength, der::Length::new(ENTRIES as u16));
 
         for (id, index) in ids.iter().zip(id_entries.iter()) {
-            lut.by_id.insert(*id, *index).unwrap();
+            lut.by_id.insert(*id, *index).map_err(|_| full())?;
         }
 
         for (name, entry) in names.iter().zip(name_entries.iter()) {
@@ -258,7 +350,7 @@ impl<'a, const ENTRIES: usize> Decode<'a> for LookUpTable<ENTRIES> {
                     key[..KEY_NAME_LEN.min(key.len())].try_into().unwrap(),
                     *entry,
                 )
-                .unwrap();
+                .map_err(|_| full())?;
         }
 
         if !lut.check_integrity() {
@@ -287,6 +379,7 @@ mod lut_ut {
         assert_eq!(repr, repr_dec);
 
         dbg!(repr);
+        #[cfg(feature = "heapless-lut")]
         assert_eq!(core::mem::size_of::<LookUpTable<64>>(), 4368);
     }
 
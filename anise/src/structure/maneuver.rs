@@ -0,0 +1,339 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use der::{Decode, Encode, Reader, Writer};
+use hifitime::Epoch;
+use serde_derive::{Deserialize, Serialize};
+
+use super::dataset::DataSetT;
+use super::semver::Semver;
+use super::spacecraft::{Mass, MassRecord};
+use crate::math::Vector3;
+use crate::NaifId;
+
+/// Kind of a maneuver, which defines the meaning of its vector.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum ManeuverKind {
+    /// Instantaneous change of velocity at the start epoch, where the vector is the delta-v in km/s
+    #[default]
+    Impulsive = 0,
+    /// Constant thrust between the start and end epochs, where the vector is the thrust in Newtons
+    FiniteBurn = 1,
+}
+
+impl TryFrom<u8> for ManeuverKind {
+    type Error = &'static str;
+
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        match val {
+            0 => Ok(ManeuverKind::Impulsive),
+            1 => Ok(ManeuverKind::FiniteBurn),
+            _ => Err("Invalid value for ManeuverKind"),
+        }
+    }
+}
+
+/// A maneuver of a spacecraft, either impulsive or a finite burn, e.g. as planned by flight dynamics and exchanged with the
+/// ephemeris of the spacecraft.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Maneuver {
+    pub kind: ManeuverKind,
+    /// Start epoch of the maneuver, in seconds past J2000 ET
+    pub start_et_s: f64,
+    /// End epoch of the maneuver, in seconds past J2000 ET, equal to the start epoch for an impulsive maneuver
+    pub end_et_s: f64,
+    /// Orientation ID of the frame in which the vector is expressed, e.g. J2000 or the body frame of the spacecraft
+    pub frame_id: NaifId,
+    /// Delta-v (km/s) of an impulsive maneuver or thrust (N) of a finite burn, cf. [ManeuverKind]
+    pub vector: [f64; 3],
+    /// Mass flow rate of a finite burn, in kg/s (the propellant used is this rate times the duration)
+    pub mass_flow_kg_s: f64,
+}
+
+impl Maneuver {
+    /// Creates an impulsive maneuver with the delta-v (km/s) expressed in the frame of the provided orientation ID.
+    pub fn impulsive(epoch: Epoch, frame_id: NaifId, delta_v_km_s: Vector3) -> Self {
+        Self {
+            kind: ManeuverKind::Impulsive,
+            start_et_s: epoch.to_et_seconds(),
+            end_et_s: epoch.to_et_seconds(),
+            frame_id,
+            vector: delta_v_km_s.into(),
+            mass_flow_kg_s: 0.0,
+        }
+    }
+
+    /// Creates a finite burn with a constant thrust (N) expressed in the frame of the provided orientation ID.
+    pub fn finite_burn(
+        start: Epoch,
+        end: Epoch,
+        frame_id: NaifId,
+        thrust_n: Vector3,
+        mass_flow_kg_s: f64,
+    ) -> Self {
+        Self {
+            kind: ManeuverKind::FiniteBurn,
+            start_et_s: start.to_et_seconds(),
+            end_et_s: end.to_et_seconds(),
+            frame_id,
+            vector: thrust_n.into(),
+            mass_flow_kg_s,
+        }
+    }
+
+    /// Returns the start epoch of this maneuver
+    pub fn start(&self) -> Epoch {
+        Epoch::from_et_seconds(self.start_et_s)
+    }
+
+    /// Returns the end epoch of this maneuver
+    pub fn end(&self) -> Epoch {
+        Epoch::from_et_seconds(self.end_et_s)
+    }
+
+    /// Returns the delta-v (km/s) or thrust (N) of this maneuver, cf. [ManeuverKind]
+    pub fn vector(&self) -> Vector3 {
+        Vector3::from(self.vector)
+    }
+
+    /// Returns the propellant mass used by this maneuver, in kg, which is zero for impulsive maneuvers.
+    pub fn prop_used_kg(&self) -> f64 {
+        self.mass_flow_kg_s * (self.end_et_s - self.start_et_s)
+    }
+
+    /// Returns true if this maneuver happens at least partially between the start and end epochs, inclusive.
+    pub fn overlaps(&self, start: Epoch, end: Epoch) -> bool {
+        self.start() <= end && self.end() >= start
+    }
+}
+
+impl Encode for Maneuver {
+    fn encoded_len(&self) -> der::Result<der::Length> {
+        (self.kind as u8).encoded_len()?
+            + self.start_et_s.encoded_len()?
+            + self.end_et_s.encoded_len()?
+            + self.frame_id.encoded_len()?
+            + self.vector[0].encoded_len()?
+            + self.vector[1].encoded_len()?
+            + self.vector[2].encoded_len()?
+            + self.mass_flow_kg_s.encoded_len()?
+    }
+
+    fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
+        (self.kind as u8).encode(encoder)?;
+        self.start_et_s.encode(encoder)?;
+        self.end_et_s.encode(encoder)?;
+        self.frame_id.encode(encoder)?;
+        self.vector[0].encode(encoder)?;
+        self.vector[1].encode(encoder)?;
+        self.vector[2].encode(encoder)?;
+        self.mass_flow_kg_s.encode(encoder)
+    }
+}
+
+impl<'a> Decode<'a> for Maneuver {
+    fn decode<R: Reader<'a>>(decoder: &mut R) -> der::Result<Self> {
+        let kind: u8 = decoder.decode()?;
+        let kind = ManeuverKind::try_from(kind).map_err(|_| {
+            der::Error::new(
+                der::ErrorKind::Value {
+                    tag: der::Tag::Integer,
+                },
+                der::Length::ONE,
+            )
+        })?;
+
+        Ok(Self {
+            kind,
+            start_et_s: decoder.decode()?,
+            end_et_s: decoder.decode()?,
+            frame_id: decoder.decode()?,
+            vector: [decoder.decode()?, decoder.decode()?, decoder.decode()?],
+            mass_flow_kg_s: decoder.decode()?,
+        })
+    }
+}
+
+impl fmt::Display for Maneuver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ManeuverKind::Impulsive => write!(
+                f,
+                "impulsive maneuver at {}: Δv = {:?} km/s in frame {}",
+                self.start(),
+                self.vector,
+                self.frame_id
+            ),
+            ManeuverKind::FiniteBurn => write!(
+                f,
+                "finite burn from {} to {}: thrust = {:?} N in frame {}, mass flow = {} kg/s",
+                self.start(),
+                self.end(),
+                self.vector,
+                self.frame_id,
+                self.mass_flow_kg_s
+            ),
+        }
+    }
+}
+
+/// All of the maneuvers of a spacecraft, sorted by start epoch.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ManeuverPlan {
+    pub maneuvers: Vec<Maneuver>,
+}
+
+impl DataSetT for ManeuverPlan {
+    const NAME: &'static str = "maneuver data";
+    const LAYOUT_VERSION: Semver = Semver {
+        major: 0,
+        minor: 5,
+        patch: 0,
+    };
+}
+
+impl ManeuverPlan {
+    /// Adds the maneuver to this plan, keeping the maneuvers sorted by start epoch.
+    pub fn add(&mut self, maneuver: Maneuver) {
+        let idx = self
+            .maneuvers
+            .partition_point(|other| other.start_et_s <= maneuver.start_et_s);
+        self.maneuvers.insert(idx, maneuver);
+    }
+
+    /// Returns the maneuvers happening at least partially between the start and end epochs, inclusive.
+    pub fn between(&self, start: Epoch, end: Epoch) -> Vec<Maneuver> {
+        self.maneuvers
+            .iter()
+            .filter(|maneuver| maneuver.overlaps(start, end))
+            .copied()
+            .collect()
+    }
+
+    /// Returns the mass history of the spacecraft from its mass at the provided epoch, with a record at the start and at the
+    /// end of each of the finite burns starting after that epoch, where the propellant used by the burn is removed from the
+    /// propellant mass. This may be stored in the `mass_history` of its spacecraft data.
+    ///
+    /// # Note
+    /// The propellant mass is not clamped: if the burns use more propellant than available, the mass of the last records is
+    /// not valid, cf. [Mass::is_valid].
+    pub fn mass_history(&self, epoch: Epoch, mut mass: Mass) -> Vec<MassRecord> {
+        let mut history = vec![MassRecord::new(epoch, mass)];
+        for burn in self.maneuvers.iter().filter(|maneuver| {
+            maneuver.kind == ManeuverKind::FiniteBurn && maneuver.start() >= epoch
+        }) {
+            history.push(MassRecord::new(burn.start(), mass));
+            mass.prop_mass_kg -= burn.prop_used_kg();
+            history.push(MassRecord::new(burn.end(), mass));
+        }
+        history
+    }
+}
+
+impl Encode for ManeuverPlan {
+    fn encoded_len(&self) -> der::Result<der::Length> {
+        let mut len = (self.maneuvers.len() as u32).encoded_len()?;
+        for maneuver in &self.maneuvers {
+            len = (len + maneuver.encoded_len()?)?;
+        }
+        Ok(len)
+    }
+
+    fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
+        (self.maneuvers.len() as u32).encode(encoder)?;
+        for maneuver in &self.maneuvers {
+            maneuver.encode(encoder)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Decode<'a> for ManeuverPlan {
+    fn decode<R: Reader<'a>>(decoder: &mut R) -> der::Result<Self> {
+        let num: u32 = decoder.decode()?;
+        let mut maneuvers = Vec::new();
+        for _ in 0..num {
+            maneuvers.push(decoder.decode()?);
+        }
+        Ok(Self { maneuvers })
+    }
+}
+
+#[cfg(test)]
+mod ut_maneuver {
+    use super::{Decode, Encode, Maneuver, ManeuverKind, ManeuverPlan, Mass};
+    use crate::constants::orientations::J2000;
+    use crate::math::Vector3;
+    use hifitime::{Epoch, Unit};
+
+    #[test]
+    fn maneuver_plan_repr() {
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+
+        let mut plan = ManeuverPlan::default();
+        plan.add(Maneuver::finite_burn(
+            epoch + Unit::Day,
+            epoch + Unit::Day + Unit::Minute * 10,
+            -10_000,
+            Vector3::new(22.0, 0.0, 0.0),
+            0.01,
+        ));
+        plan.add(Maneuver::impulsive(
+            epoch,
+            J2000,
+            Vector3::new(0.001, -0.002, 0.0),
+        ));
+
+        // The plan is sorted by start epoch.
+        assert_eq!(plan.maneuvers[0].kind, ManeuverKind::Impulsive);
+        assert_eq!(plan.maneuvers[0].prop_used_kg(), 0.0);
+        assert!((plan.maneuvers[1].prop_used_kg() - 6.0).abs() < 1e-9);
+
+        let mut buf = vec![];
+        plan.encode_to_vec(&mut buf).unwrap();
+        let plan_dec = ManeuverPlan::from_der(&buf).unwrap();
+        assert_eq!(plan, plan_dec);
+
+        assert_eq!(
+            plan.between(epoch - Unit::Hour, epoch + Unit::Hour).len(),
+            1
+        );
+        // The finite burn is in progress at the start of this window.
+        assert_eq!(
+            plan.between(epoch + Unit::Day + Unit::Minute, epoch + Unit::Day * 2)
+                .len(),
+            1
+        );
+        assert!(plan
+            .between(epoch + Unit::Hour, epoch + Unit::Hour * 2)
+            .is_empty());
+
+        // The mass history removes the propellant used by the finite burn, and ignores the impulsive maneuver.
+        let history = plan.mass_history(epoch, Mass::from_dry_and_prop_masses(150.0, 50.0));
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[1].epoch(), epoch + Unit::Day);
+        assert_eq!(history[1].mass.prop_mass_kg, 50.0);
+        assert!((history[2].mass.prop_mass_kg - 44.0).abs() < 1e-9);
+        assert_eq!(history[2].mass.dry_mass_kg, 150.0);
+        // The burns before the epoch are already accounted for in the mass.
+        assert_eq!(
+            plan.mass_history(epoch + Unit::Day * 2, Mass::default())
+                .len(),
+            1
+        );
+
+        let empty = ManeuverPlan::default();
+        let mut buf = vec![];
+        empty.encode_to_vec(&mut buf).unwrap();
+        assert_eq!(ManeuverPlan::from_der(&buf).unwrap(), empty);
+    }
+}
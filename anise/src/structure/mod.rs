@@ -14,13 +14,21 @@
  */
 pub mod dataset;
 pub mod lookuptable;
+pub mod maneuver;
 pub mod metadata;
 pub mod planetocentric;
 pub mod semver;
+/// Ed25519 signatures of data sets: unlike the CRC32 of the data sets, which only detects accidental corruption,
+/// a signature verified against a trusted public key detects tampering. The signature is stored at the end of the
+/// data set, after the fields it covers.
+#[cfg(feature = "signing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "signing")))]
+pub mod signature;
 pub mod spacecraft;
 
 use self::{
-    dataset::DataSet, planetocentric::PlanetaryData, semver::Semver, spacecraft::SpacecraftData,
+    dataset::DataSet, maneuver::ManeuverPlan, planetocentric::PlanetaryData, semver::Semver,
+    spacecraft::SpacecraftData,
 };
 use crate::{
     almanac::{MAX_PLANETARY_DATA, MAX_SPACECRAFT_DATA},
@@ -28,9 +36,12 @@ use crate::{
 };
 
 /// The current version of ANISE
+///
+/// The minor version is bumped whenever the layout of a data set structure changes, along with the
+/// [DataSetT::LAYOUT_VERSION](dataset::DataSetT::LAYOUT_VERSION) of that structure.
 pub const ANISE_VERSION: Semver = Semver {
     major: 0,
-    minor: 4,
+    minor: 5,
     patch: 0,
 };
 
@@ -40,3 +51,5 @@ pub type SpacecraftDataSet = DataSet<SpacecraftData, MAX_SPACECRAFT_DATA>;
 pub type PlanetaryDataSet = DataSet<PlanetaryData, MAX_PLANETARY_DATA>;
 /// Euler Parameter Data Set allow mapping an ID and/or name to a time invariant Quaternion
 pub type EulerParameterDataSet = DataSet<Quaternion, MAX_PLANETARY_DATA>;
+/// Maneuver Data Set allow mapping a spacecraft ID and/or name to its impulsive maneuvers and finite burns
+pub type ManeuverDataSet = DataSet<ManeuverPlan, MAX_SPACECRAFT_DATA>;
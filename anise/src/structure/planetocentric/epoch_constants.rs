@@ -0,0 +1,89 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use der::{Decode, Encode, Reader, Writer};
+use hifitime::Epoch;
+use serde_derive::{Deserialize, Serialize};
+
+use super::ellipsoid::Ellipsoid;
+
+/// Maximum number of epoch-tagged constant sets of a given planetary object.
+pub const MAX_EPOCH_CONSTANTS: usize = 8;
+
+/// A set of planetary constants valid from a given epoch onward, e.g. an updated estimate of the gravitational parameter.
+///
+/// If the shape is not set, the shape of the planetary data is used.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EpochConstants {
+    /// Epoch from which these constants are valid, in seconds past J2000 ET
+    pub valid_from_et_s: f64,
+    /// Gravitational parameter (μ) of the planetary object from that epoch
+    pub mu_km3_s2: f64,
+    /// Shape of the planetary object from that epoch
+    pub shape: Option<Ellipsoid>,
+}
+
+impl EpochConstants {
+    /// Returns the epoch from which these constants are valid
+    pub fn valid_from(&self) -> Epoch {
+        Epoch::from_et_seconds(self.valid_from_et_s)
+    }
+}
+
+impl Encode for EpochConstants {
+    fn encoded_len(&self) -> der::Result<der::Length> {
+        let has_shape = self.shape.is_some();
+        self.valid_from_et_s.encoded_len()?
+            + self.mu_km3_s2.encoded_len()?
+            + has_shape.encoded_len()?
+            + self.shape.encoded_len()?
+    }
+
+    fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
+        self.valid_from_et_s.encode(encoder)?;
+        self.mu_km3_s2.encode(encoder)?;
+        self.shape.is_some().encode(encoder)?;
+        self.shape.encode(encoder)
+    }
+}
+
+impl<'a> Decode<'a> for EpochConstants {
+    fn decode<R: Reader<'a>>(decoder: &mut R) -> der::Result<Self> {
+        let valid_from_et_s = decoder.decode()?;
+        let mu_km3_s2 = decoder.decode()?;
+        let has_shape: bool = decoder.decode()?;
+
+        Ok(Self {
+            valid_from_et_s,
+            mu_km3_s2,
+            shape: if has_shape {
+                Some(decoder.decode()?)
+            } else {
+                None
+            },
+        })
+    }
+}
+
+impl fmt::Display for EpochConstants {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "from {}: μ = {} km^3/s^2",
+            self.valid_from(),
+            self.mu_km3_s2
+        )?;
+        if let Some(shape) = self.shape {
+            write!(f, ", {shape}")?;
+        }
+        Ok(())
+    }
+}
@@ -12,22 +12,27 @@ use crate::{
     astro::PhysicsResult,
     constants::orientations::orientation_name_from_id,
     math::{
-        rotation::{r1, r3, DCM},
+        rotation::{r1, r1_dot, r3, r3_dot, DCM},
         Matrix3,
     },
+    orientations::NutationTruncation,
     prelude::{Frame, FrameUid},
     NaifId,
 };
 use core::f64::consts::FRAC_PI_2;
 use core::fmt;
 pub mod ellipsoid;
+pub mod epoch_constants;
 pub mod phaseangle;
 use der::{Decode, Encode, Reader, Writer};
 use ellipsoid::Ellipsoid;
-use hifitime::{Epoch, TimeUnits, Unit};
+use epoch_constants::{EpochConstants, MAX_EPOCH_CONSTANTS};
+use hifitime::{Epoch, Unit};
 use phaseangle::PhaseAngle;
+use serde_derive::{Deserialize, Serialize};
 
-use super::dataset::DataSetT;
+use super::dataset::{DataSetError, DataSetT};
+use super::semver::Semver;
 
 pub const MAX_NUT_PREC_ANGLES: usize = 32;
 
@@ -64,7 +69,7 @@ pub const MAX_NUT_PREC_ANGLES: usize = 32;
 /// t = ephemeris time, expressed as seconds past the reference epoch
 /// for this body or planetary system
 ///
-#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct PlanetaryData {
     /// The NAIF ID of this object
     pub object_id: NaifId,
@@ -78,14 +83,32 @@ pub struct PlanetaryData {
     pub pole_declination: Option<PhaseAngle<MAX_NUT_PREC_ANGLES>>,
     pub prime_meridian: Option<PhaseAngle<MAX_NUT_PREC_ANGLES>>,
     pub long_axis: Option<f64>,
-    /// These are the nutation precession angles as a list of tuples to rebuild them.
+    /// These are the nutation precession angles of the system as a list of polynomials to rebuild them, in degrees and centuries.
     /// E.g. For `E1 = 125.045 -  0.052992 d`, this would be stored as a single entry `(125.045, -0.052992)`.
+    /// The angles are quadratic when the system has a `MAX_PHASE_DEGREE` of 2 (e.g. the Martian system in pck00011), and
+    /// cubic when it is 3.
+    ///
+    /// The trig polynomial terms of the right ascension and prime meridian of each body are its coefficients times the sine
+    /// of these angles, and those of the declination are its coefficients times their cosine.
     pub num_nut_prec_angles: u8,
     pub nut_prec_angles: [PhaseAngle<0>; MAX_NUT_PREC_ANGLES],
+    /// Number of epoch-tagged constant sets, which supersede the gravitational parameter and shape from their epoch onward.
+    #[serde(default)]
+    pub num_epoch_constants: u8,
+    /// Epoch-tagged constant sets, sorted by increasing epoch.
+    #[serde(default)]
+    pub epoch_constants: [EpochConstants; MAX_EPOCH_CONSTANTS],
 }
 
+/// The epoch constants and the cubic nutation and precession angles are only encoded when the data flags are set, so
+/// the planetary data of version 0.4.0 (e.g. the published `pck08.pca` and `pck11.pca`) keeps the current layout.
 impl DataSetT for PlanetaryData {
     const NAME: &'static str = "planetary data";
+    const LAYOUT_VERSION: Semver = Semver {
+        major: 0,
+        minor: 4,
+        patch: 0,
+    };
 }
 
 impl PlanetaryData {
@@ -98,6 +121,63 @@ impl PlanetaryData {
             shape: self.shape,
         }
     }
+
+    /// Converts this planetary data into a Frame like [PlanetaryData::to_frame], using the constants valid at the provided epoch.
+    pub fn to_frame_at(&self, uid: FrameUid, epoch: Epoch) -> Frame {
+        Frame {
+            ephemeris_id: uid.ephemeris_id,
+            orientation_id: uid.orientation_id,
+            mu_km3_s2: Some(self.mu_km3_s2_at(epoch)),
+            shape: self.shape_at(epoch),
+        }
+    }
+
+    /// Returns the epoch-tagged constant set valid at the provided epoch, if any.
+    pub fn epoch_constants_at(&self, epoch: Epoch) -> Option<&EpochConstants> {
+        self.epoch_constants[..self.num_epoch_constants as usize]
+            .iter()
+            .rev()
+            .find(|constants| constants.valid_from() <= epoch)
+    }
+
+    /// Returns the gravitational parameter valid at the provided epoch.
+    pub fn mu_km3_s2_at(&self, epoch: Epoch) -> f64 {
+        match self.epoch_constants_at(epoch) {
+            Some(constants) => constants.mu_km3_s2,
+            None => self.mu_km3_s2,
+        }
+    }
+
+    /// Returns the shape valid at the provided epoch.
+    pub fn shape_at(&self, epoch: Epoch) -> Option<Ellipsoid> {
+        self.epoch_constants_at(epoch)
+            .and_then(|constants| constants.shape)
+            .or(self.shape)
+    }
+
+    /// Adds an epoch-tagged constant set, keeping them sorted by epoch. A set valid from the same epoch as an existing one replaces it.
+    pub fn add_epoch_constants(&mut self, constants: EpochConstants) -> Result<(), DataSetError> {
+        let num = self.num_epoch_constants as usize;
+        let idx = self.epoch_constants[..num]
+            .partition_point(|other| other.valid_from_et_s < constants.valid_from_et_s);
+
+        if idx < num && self.epoch_constants[idx].valid_from_et_s == constants.valid_from_et_s {
+            self.epoch_constants[idx] = constants;
+            return Ok(());
+        } else if num == MAX_EPOCH_CONSTANTS {
+            return Err(DataSetError::Conversion {
+                action: format!(
+                    "{} already has {MAX_EPOCH_CONSTANTS} epoch constants",
+                    self.object_id
+                ),
+            });
+        }
+
+        self.epoch_constants.copy_within(idx..num, idx + 1);
+        self.epoch_constants[idx] = constants;
+        self.num_epoch_constants += 1;
+        Ok(())
+    }
     /// Specifies what data is available in this structure.
     ///
     /// Returns:
@@ -106,6 +186,7 @@ impl PlanetaryData {
     /// + Bit 2 is set if `pole_declination` is available
     /// + Bit 3 is set if `prime_meridian` is available
     /// + Bit 4 is set if `long_axis` is available
+    /// + Bit 5 is set if epoch-tagged constants are available
     fn available_data(&self) -> u8 {
         let mut bits: u8 = 0;
 
@@ -124,10 +205,25 @@ impl PlanetaryData {
         if self.long_axis.is_some() {
             bits |= 1 << 4;
         }
+        if self.num_epoch_constants > 0 {
+            bits |= 1 << 5;
+        }
+        if self.has_cubic_nut_prec_angles() {
+            bits |= 1 << 6;
+        }
 
         bits
     }
 
+    /// Returns true if any of the nutation and precession angles is cubic: their third order coefficients are then encoded
+    /// after the rest of the data, such that the planetary data without cubic angles keeps the layout of previous versions.
+    fn has_cubic_nut_prec_angles(&self) -> bool {
+        self.nut_prec_angles
+            .iter()
+            .take(self.num_nut_prec_angles as usize)
+            .any(|angle| angle.jerk_deg != 0.0)
+    }
+
     fn uses_trig_polynomial(&self) -> bool {
         if let Some(phase) = self.pole_right_ascension {
             if phase.coeffs_count > 0 {
@@ -150,114 +246,191 @@ impl PlanetaryData {
         false
     }
 
-    /// Computes the rotation to the parent frame, returning only the rotation matrix
-    fn dcm_to_parent(&self, epoch: Epoch, system: &Self) -> PhysicsResult<Matrix3> {
-        if self.pole_declination.is_none()
-            && self.prime_meridian.is_none()
-            && self.pole_right_ascension.is_none()
-        {
-            Ok(Matrix3::identity())
-        } else {
-            let mut variable_angles_rad = [0.0_f64; MAX_NUT_PREC_ANGLES];
-            // Skip the computation of the nutation and precession angles of the system if we won't be using them.
-            if self.uses_trig_polynomial() {
-                for (ii, nut_prec_angle) in system
-                    .nut_prec_angles
-                    .iter()
-                    .enumerate()
-                    .take(system.num_nut_prec_angles.into())
-                {
-                    variable_angles_rad[ii] = nut_prec_angle
-                        .evaluate_deg(epoch, Unit::Century)
-                        .to_radians();
-                }
+    /// Returns the Euler angles of the rotation to the parent frame in radians, i.e. the right ascension of the pole plus 90
+    /// degrees, 90 degrees minus the declination of the pole, and the prime meridian angle, and their time derivatives in
+    /// radians per second. The derivatives are analytic, including those of the quadratic terms and of the nutation and
+    /// precession terms.
+    pub fn euler_angles_rad(&self, epoch: Epoch, system: &Self) -> ([f64; 3], [f64; 3]) {
+        self.euler_angles_rad_with_terms(epoch, system, MAX_NUT_PREC_ANGLES)
+    }
+
+    /// Returns the Euler angles of [PlanetaryData::euler_angles_rad] where each angle only keeps its largest nutation and
+    /// precession terms, as requested by the truncation.
+    pub fn euler_angles_rad_truncated(
+        &self,
+        epoch: Epoch,
+        system: &Self,
+        truncation: NutationTruncation,
+    ) -> ([f64; 3], [f64; 3]) {
+        self.euler_angles_rad_with_terms(epoch, system, truncation.num_terms())
+    }
+
+    fn euler_angles_rad_with_terms(
+        &self,
+        epoch: Epoch,
+        system: &Self,
+        max_terms: usize,
+    ) -> ([f64; 3], [f64; 3]) {
+        let mut variable_angles_rad = [0.0_f64; MAX_NUT_PREC_ANGLES];
+        let mut variable_rates_rad_s = [0.0_f64; MAX_NUT_PREC_ANGLES];
+        // Skip the computation of the nutation and precession angles of the system if we won't be using them.
+        if self.uses_trig_polynomial() {
+            for (ii, nut_prec_angle) in system
+                .nut_prec_angles
+                .iter()
+                .enumerate()
+                .take(system.num_nut_prec_angles.into())
+            {
+                variable_angles_rad[ii] = nut_prec_angle
+                    .evaluate_deg(epoch, Unit::Century)
+                    .to_radians();
+                variable_rates_rad_s[ii] = nut_prec_angle
+                    .evaluate_rate_deg_s(epoch, Unit::Century)
+                    .to_radians();
             }
+        }
 
-            let right_asc_rad = match self.pole_right_ascension {
-                Some(right_asc_deg) => {
-                    let mut angle_deg = right_asc_deg.evaluate_deg(epoch, Unit::Century);
-                    // Add the nutation and precession angles for this phase angle
-                    for (ii, coeff) in right_asc_deg
-                        .coeffs
-                        .iter()
-                        .enumerate()
-                        .take(right_asc_deg.coeffs_count as usize)
-                    {
-                        angle_deg += coeff * variable_angles_rad[ii].sin();
-                    }
-                    angle_deg.to_radians() + FRAC_PI_2
-                }
-                None => 0.0,
-            };
-
-            let dec_rad = match self.pole_declination {
-                Some(decl_deg) => {
-                    let mut angle_deg = decl_deg.evaluate_deg(epoch, Unit::Century);
-                    // Add the nutation and precession angles for this phase angle
-                    for (ii, coeff) in decl_deg
-                        .coeffs
-                        .iter()
-                        .enumerate()
-                        .take(decl_deg.coeffs_count as usize)
-                    {
-                        angle_deg += coeff * variable_angles_rad[ii].cos();
-                    }
-                    FRAC_PI_2 - angle_deg.to_radians()
-                }
-                None => 0.0,
-            };
-
-            let twist_rad = match self.prime_meridian {
-                Some(twist_deg) => {
-                    let mut angle_deg = twist_deg.evaluate_deg(epoch, Unit::Day);
-                    // Add the nutation and precession angles for this phase angle
-                    for (ii, coeff) in twist_deg
-                        .coeffs
-                        .iter()
-                        .enumerate()
-                        .take(twist_deg.coeffs_count as usize)
-                    {
-                        angle_deg += coeff * variable_angles_rad[ii].sin();
-                    }
-                    angle_deg.to_radians()
-                }
-                None => 0.0,
-            };
+        let mut angles_rad = [0.0_f64; 3];
+        let mut rates_rad_s = [0.0_f64; 3];
+
+        if let Some(right_asc_deg) = self.pole_right_ascension {
+            let (angle_deg, rate_deg_s) = with_trig_terms(
+                &right_asc_deg,
+                epoch,
+                Unit::Century,
+                &variable_angles_rad,
+                &variable_rates_rad_s,
+                false,
+                max_terms,
+            );
+            angles_rad[0] = angle_deg.to_radians() + FRAC_PI_2;
+            rates_rad_s[0] = rate_deg_s.to_radians();
+        }
 
-            let ra_dcm = r3(right_asc_rad);
-            let dec_dcm = r1(dec_rad);
-            let w_dcm = r3(twist_rad);
-            // Perform a multiplication of the DCMs, regardless of frames.
-            Ok(w_dcm * dec_dcm * ra_dcm)
+        if let Some(decl_deg) = self.pole_declination {
+            let (angle_deg, rate_deg_s) = with_trig_terms(
+                &decl_deg,
+                epoch,
+                Unit::Century,
+                &variable_angles_rad,
+                &variable_rates_rad_s,
+                true,
+                max_terms,
+            );
+            angles_rad[1] = FRAC_PI_2 - angle_deg.to_radians();
+            rates_rad_s[1] = -rate_deg_s.to_radians();
+        }
+
+        if let Some(twist_deg) = self.prime_meridian {
+            let (angle_deg, rate_deg_s) = with_trig_terms(
+                &twist_deg,
+                epoch,
+                Unit::Day,
+                &variable_angles_rad,
+                &variable_rates_rad_s,
+                false,
+                max_terms,
+            );
+            angles_rad[2] = angle_deg.to_radians();
+            rates_rad_s[2] = rate_deg_s.to_radians();
         }
+
+        (angles_rad, rates_rad_s)
     }
 
-    /// Computes the rotation to the parent frame, including its time derivative.
+    /// Computes the rotation to the parent frame, including its analytic time derivative.
     ///
     /// Source: <https://naif.jpl.nasa.gov/pub/naif/toolkit_docs/C/req/rotation.html#Working%20with%20RA,%20Dec%20and%20Twist>
     pub fn rotation_to_parent(&self, epoch: Epoch, system: &Self) -> PhysicsResult<DCM> {
+        self.rotation_to_parent_with_terms(epoch, system, MAX_NUT_PREC_ANGLES)
+    }
+
+    /// Computes the rotation of [PlanetaryData::rotation_to_parent] where each angle only keeps its largest nutation and
+    /// precession terms, as requested by the truncation.
+    pub fn rotation_to_parent_truncated(
+        &self,
+        epoch: Epoch,
+        system: &Self,
+        truncation: NutationTruncation,
+    ) -> PhysicsResult<DCM> {
+        self.rotation_to_parent_with_terms(epoch, system, truncation.num_terms())
+    }
+
+    fn rotation_to_parent_with_terms(
+        &self,
+        epoch: Epoch,
+        system: &Self,
+        max_terms: usize,
+    ) -> PhysicsResult<DCM> {
         if self.pole_declination.is_none()
             && self.prime_meridian.is_none()
             && self.pole_right_ascension.is_none()
         {
             Ok(DCM::identity(self.object_id, self.parent_id))
         } else {
-            // For planetary constants data, we perform a finite differencing to compute the time derivative.
-            let mut dcm = DCM {
-                rot_mat: self.dcm_to_parent(epoch, system)?,
+            let ([right_asc_rad, dec_rad, twist_rad], [right_asc_rate, dec_rate, twist_rate]) =
+                self.euler_angles_rad_with_terms(epoch, system, max_terms);
+
+            let ra_dcm = r3(right_asc_rad);
+            let dec_dcm = r1(dec_rad);
+            let w_dcm = r3(twist_rad);
+
+            // Product rule on the three elementary rotations, regardless of frames.
+            let rot_mat_dt = twist_rate * r3_dot(twist_rad) * dec_dcm * ra_dcm
+                + dec_rate * w_dcm * r1_dot(dec_rad) * ra_dcm
+                + right_asc_rate * w_dcm * dec_dcm * r3_dot(right_asc_rad);
+
+            Ok(DCM {
+                rot_mat: w_dcm * dec_dcm * ra_dcm,
                 from: self.parent_id,
                 to: self.object_id,
-                rot_mat_dt: None,
-            };
-            // Compute rotation matrix one second before
-            let pre_rot_dcm = self.dcm_to_parent(epoch - 1.seconds(), system)?;
-            let post_rot_dcm = self.dcm_to_parent(epoch + 1.seconds(), system)?;
+                rot_mat_dt: Some(rot_mat_dt),
+            })
+        }
+    }
+}
 
-            dcm.rot_mat_dt = Some((post_rot_dcm - pre_rot_dcm) / 2.0);
+/// Evaluates the phase angle and its rate, in degrees and degrees per second, adding its nutation and precession terms: its
+/// coefficients times the sine (or the cosine if `use_cos` is set) of the angles of the system. Only the `max_terms` largest
+/// terms are evaluated.
+fn with_trig_terms(
+    phase: &PhaseAngle<MAX_NUT_PREC_ANGLES>,
+    epoch: Epoch,
+    rate_unit: Unit,
+    angles_rad: &[f64; MAX_NUT_PREC_ANGLES],
+    rates_rad_s: &[f64; MAX_NUT_PREC_ANGLES],
+    use_cos: bool,
+    max_terms: usize,
+) -> (f64, f64) {
+    let mut angle_deg = phase.evaluate_deg(epoch, rate_unit);
+    let mut rate_deg_s = phase.evaluate_rate_deg_s(epoch, rate_unit);
+
+    let num_terms = (phase.coeffs_count as usize).min(MAX_NUT_PREC_ANGLES);
+    let mut kept = [true; MAX_NUT_PREC_ANGLES];
+    if num_terms > max_terms {
+        let mut by_amplitude: [usize; MAX_NUT_PREC_ANGLES] = core::array::from_fn(|ii| ii);
+        by_amplitude[..num_terms]
+            .sort_by(|a, b| phase.coeffs[*b].abs().total_cmp(&phase.coeffs[*a].abs()));
+        for ii in &by_amplitude[max_terms..num_terms] {
+            kept[*ii] = false;
+        }
+    }
 
-            Ok(dcm)
+    for (ii, coeff) in phase.coeffs.iter().enumerate().take(num_terms) {
+        if !kept[ii] {
+            continue;
+        }
+        let (sin, cos) = angles_rad[ii].sin_cos();
+        if use_cos {
+            angle_deg += coeff * cos;
+            rate_deg_s -= coeff * sin * rates_rad_s[ii];
+        } else {
+            angle_deg += coeff * sin;
+            rate_deg_s += coeff * cos * rates_rad_s[ii];
         }
     }
+
+    (angle_deg, rate_deg_s)
 }
 
 impl Encode for PlanetaryData {
@@ -274,6 +447,8 @@ impl Encode for PlanetaryData {
             + self.long_axis.encoded_len()?
             + self.num_nut_prec_angles.encoded_len()?
             + self.nut_prec_angles.encoded_len()?
+            + self.encoded_epoch_constants_len()?
+            + self.encoded_cubic_nut_prec_len()?
     }
 
     fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
@@ -287,7 +462,51 @@ impl Encode for PlanetaryData {
         self.prime_meridian.encode(encoder)?;
         self.long_axis.encode(encoder)?;
         self.num_nut_prec_angles.encode(encoder)?;
-        self.nut_prec_angles.encode(encoder)
+        self.nut_prec_angles.encode(encoder)?;
+        // Only encode the epoch constants if there are any, for compatibility with previous versions.
+        if self.num_epoch_constants > 0 {
+            self.num_epoch_constants.encode(encoder)?;
+            for constants in &self.epoch_constants[..self.num_epoch_constants as usize] {
+                constants.encode(encoder)?;
+            }
+        }
+        if self.has_cubic_nut_prec_angles() {
+            for angle in self
+                .nut_prec_angles
+                .iter()
+                .take(self.num_nut_prec_angles as usize)
+            {
+                angle.jerk_deg.encode(encoder)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PlanetaryData {
+    fn encoded_epoch_constants_len(&self) -> der::Result<der::Length> {
+        if self.num_epoch_constants == 0 {
+            return Ok(der::Length::ZERO);
+        }
+        let mut len = self.num_epoch_constants.encoded_len()?;
+        for constants in &self.epoch_constants[..self.num_epoch_constants as usize] {
+            len = (len + constants.encoded_len()?)?;
+        }
+        Ok(len)
+    }
+
+    fn encoded_cubic_nut_prec_len(&self) -> der::Result<der::Length> {
+        let mut len = der::Length::ZERO;
+        if self.has_cubic_nut_prec_angles() {
+            for angle in self
+                .nut_prec_angles
+                .iter()
+                .take(self.num_nut_prec_angles as usize)
+            {
+                len = (len + angle.jerk_deg.encoded_len()?)?;
+            }
+        }
+        Ok(len)
     }
 }
 
@@ -329,6 +548,38 @@ impl<'a> Decode<'a> for PlanetaryData {
             None
         };
 
+        let num_nut_prec_angles: u8 = decoder.decode()?;
+        let mut nut_prec_angles: [PhaseAngle<0>; MAX_NUT_PREC_ANGLES] = decoder.decode()?;
+
+        let mut num_epoch_constants = 0;
+        let mut epoch_constants = [EpochConstants::default(); MAX_EPOCH_CONSTANTS];
+        if data_flags & (1 << 5) != 0 {
+            num_epoch_constants = decoder.decode()?;
+            if num_epoch_constants as usize > MAX_EPOCH_CONSTANTS {
+                return Err(der::Error::new(
+                    der::ErrorKind::Value {
+                        tag: der::Tag::Integer,
+                    },
+                    der::Length::ONE,
+                ));
+            }
+            for constants in epoch_constants
+                .iter_mut()
+                .take(num_epoch_constants as usize)
+            {
+                *constants = decoder.decode()?;
+            }
+        }
+
+        if data_flags & (1 << 6) != 0 {
+            for angle in nut_prec_angles
+                .iter_mut()
+                .take(num_nut_prec_angles as usize)
+            {
+                angle.jerk_deg = decoder.decode()?;
+            }
+        }
+
         Ok(Self {
             object_id,
             parent_id,
@@ -338,8 +589,10 @@ impl<'a> Decode<'a> for PlanetaryData {
             pole_declination,
             prime_meridian,
             long_axis,
-            num_nut_prec_angles: decoder.decode()?,
-            nut_prec_angles: decoder.decode()?,
+            num_nut_prec_angles,
+            nut_prec_angles,
+            num_epoch_constants,
+            epoch_constants,
         })
     }
 }
@@ -374,6 +627,9 @@ impl fmt::Display for PlanetaryData {
         if self.num_nut_prec_angles > 0 {
             write!(f, " + {} nut/prec angles", self.num_nut_prec_angles)?;
         }
+        if self.num_epoch_constants > 0 {
+            write!(f, " + {} epoch constants", self.num_epoch_constants)?;
+        }
 
         Ok(())
     }
@@ -381,8 +637,12 @@ impl fmt::Display for PlanetaryData {
 
 #[cfg(test)]
 mod planetary_constants_ut {
-    use super::{Ellipsoid, PhaseAngle, PlanetaryData};
+    use super::{
+        Ellipsoid, EpochConstants, PhaseAngle, PlanetaryData, MAX_EPOCH_CONSTANTS,
+        MAX_NUT_PREC_ANGLES,
+    };
     use der::{Decode, Encode};
+    use hifitime::Epoch;
 
     #[test]
     fn pc_encdec_min_repr() {
@@ -602,6 +862,8 @@ mod planetary_constants_ut {
             long_axis: None,
             num_nut_prec_angles: 0,
             nut_prec_angles: Default::default(),
+            num_epoch_constants: 0,
+            epoch_constants: Default::default(),
         };
 
         // Encode
@@ -613,6 +875,237 @@ mod planetary_constants_ut {
 
         assert_eq!(moon, moon_dec);
 
+        // Serde round trip, only the populated coefficients are serialized
+        let serialized = serde_yml::to_string(&moon).unwrap();
+        let moon_serde: PlanetaryData = serde_yml::from_str(&serialized).unwrap();
+        assert_eq!(moon, moon_serde);
+
         assert_eq!(format!("{moon}"), "IAU_MOON (μ = 4902.800066163796 km^3/s^2) RA = 269.9949 + 0.0031 t Dec = 66.5392 + 0.013 t PM = 38.3213 + 13.17635815 t + -0.0000000000014 t^2");
     }
+
+    #[test]
+    fn pc_encdec_with_epoch_constants() {
+        let mut repr = PlanetaryData {
+            object_id: 2099942,
+            mu_km3_s2: 2.0e-9,
+            shape: Some(Ellipsoid::from_sphere(0.17)),
+            ..Default::default()
+        };
+
+        let update_2021 = EpochConstants {
+            valid_from_et_s: Epoch::from_gregorian_utc_at_midnight(2021, 3, 1).to_et_seconds(),
+            mu_km3_s2: 2.6e-9,
+            shape: None,
+        };
+        let update_2029 = EpochConstants {
+            valid_from_et_s: Epoch::from_gregorian_utc_at_midnight(2029, 4, 13).to_et_seconds(),
+            mu_km3_s2: 2.7e-9,
+            shape: Some(Ellipsoid::from_spheroid(0.2, 0.15)),
+        };
+
+        // Added out of order, but sorted by epoch
+        repr.add_epoch_constants(update_2029).unwrap();
+        repr.add_epoch_constants(update_2021).unwrap();
+        assert_eq!(repr.num_epoch_constants, 2);
+        assert_eq!(repr.epoch_constants[0], update_2021);
+
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+
+        let repr_dec = PlanetaryData::from_der(&buf).unwrap();
+        assert_eq!(repr, repr_dec);
+
+        let before = Epoch::from_gregorian_utc_at_midnight(2020, 1, 1);
+        assert_eq!(repr.mu_km3_s2_at(before), 2.0e-9);
+        assert_eq!(repr.shape_at(before), Some(Ellipsoid::from_sphere(0.17)));
+
+        let during = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+        assert_eq!(repr.mu_km3_s2_at(during), 2.6e-9);
+        // The 2021 update does not change the shape
+        assert_eq!(repr.shape_at(during), Some(Ellipsoid::from_sphere(0.17)));
+
+        let after = Epoch::from_gregorian_utc_at_midnight(2030, 1, 1);
+        assert_eq!(repr.mu_km3_s2_at(after), 2.7e-9);
+        assert_eq!(
+            repr.shape_at(after),
+            Some(Ellipsoid::from_spheroid(0.2, 0.15))
+        );
+
+        // Replacing an update at the same epoch
+        repr.add_epoch_constants(EpochConstants {
+            mu_km3_s2: 2.65e-9,
+            ..update_2021
+        })
+        .unwrap();
+        assert_eq!(repr.num_epoch_constants, 2);
+        assert_eq!(repr.mu_km3_s2_at(during), 2.65e-9);
+
+        assert!(format!("{repr}").ends_with(" + 2 epoch constants"));
+
+        // More epoch constants than can be stored are rejected: the count is encoded right before the constants.
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+        let constants_len: u32 = repr.epoch_constants[..2]
+            .iter()
+            .map(|constants| u32::from(constants.encoded_len().unwrap()))
+            .sum();
+        let count_idx = buf.len() - constants_len as usize - 1;
+        assert_eq!(buf[count_idx], 2);
+        buf[count_idx] = MAX_EPOCH_CONSTANTS as u8 + 1;
+        assert!(PlanetaryData::from_der(&buf).is_err());
+    }
+
+    #[test]
+    fn pc_encdec_with_cubic_nut_prec_angles() {
+        let mut repr = PlanetaryData {
+            object_id: 599,
+            mu_km3_s2: 126_686_531.9,
+            num_nut_prec_angles: 2,
+            ..Default::default()
+        };
+        repr.nut_prec_angles[0] = PhaseAngle {
+            offset_deg: 73.32,
+            rate_deg: 91_472.9,
+            ..Default::default()
+        };
+        repr.nut_prec_angles[1] = PhaseAngle {
+            offset_deg: 24.62,
+            rate_deg: 45_137.2,
+            ..Default::default()
+        };
+
+        // Without cubic angles, the layout is that of previous versions
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+        let linear_len = buf.len();
+        assert_eq!(PlanetaryData::from_der(&buf).unwrap(), repr);
+
+        repr.nut_prec_angles[1].jerk_deg = 1.5e-6;
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+        assert!(buf.len() > linear_len);
+        assert_eq!(PlanetaryData::from_der(&buf).unwrap(), repr);
+    }
+
+    #[test]
+    fn analytic_rotation_rate() {
+        use crate::almanac::Almanac;
+        use hifitime::TimeUnits;
+
+        // Mars has quadratic nutation and precession angles in pck00011, and the Moon has many trig terms.
+        let almanac = Almanac::default().load("../data/pck11.pca").unwrap();
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2030, 6, 15);
+
+        for id in [399, 499, 301] {
+            let data = almanac.planetary_data.get_by_id(id).unwrap();
+            let system = almanac
+                .planetary_data
+                .get_by_id(data.parent_id)
+                .unwrap_or(data);
+
+            let dcm = data.rotation_to_parent(epoch, &system).unwrap();
+            let pre = data
+                .rotation_to_parent(epoch - 0.5.seconds(), &system)
+                .unwrap();
+            let post = data
+                .rotation_to_parent(epoch + 0.5.seconds(), &system)
+                .unwrap();
+            let finite_diff = post.rot_mat - pre.rot_mat;
+
+            let rot_mat_dt = dcm.rot_mat_dt.unwrap();
+            assert!(
+                (rot_mat_dt - finite_diff).norm() < 1e-12,
+                "{id}: {}",
+                (rot_mat_dt - finite_diff).norm()
+            );
+        }
+
+        // The rate of a quadratic phase angle is linear in time.
+        let phase = PhaseAngle::<0> {
+            offset_deg: 10.0,
+            rate_deg: 2.0,
+            accel_deg: 0.5,
+            ..Default::default()
+        };
+        let one_day = Epoch::from_tdb_duration(1.days());
+        assert_eq!(
+            phase.evaluate_rate_deg_s(Epoch::from_tdb_seconds(0.0), hifitime::Unit::Day),
+            2.0 / 86_400.0
+        );
+        assert!(
+            (phase.evaluate_rate_deg_s(one_day, hifitime::Unit::Day) - 3.0 / 86_400.0).abs()
+                < 1e-18
+        );
+    }
+
+    #[test]
+    fn truncated_moon_rotation() {
+        use crate::almanac::Almanac;
+        use crate::orientations::NutationTruncation;
+        use hifitime::{TimeSeries, Unit};
+
+        let almanac = Almanac::default().load("../data/pck11.pca").unwrap();
+        let moon = almanac.planetary_data.get_by_id(301).unwrap();
+        let system = almanac
+            .planetary_data
+            .get_by_id(moon.parent_id)
+            .unwrap_or(moon);
+
+        // Bound of the error of each angle: the sum of the amplitudes of its dropped terms, in radians.
+        let bound_rad = |phase: Option<PhaseAngle<MAX_NUT_PREC_ANGLES>>, max_terms: usize| {
+            let phase = phase.unwrap();
+            let mut amplitudes = phase.coeffs[..phase.coeffs_count as usize]
+                .iter()
+                .map(|coeff| coeff.abs())
+                .collect::<Vec<f64>>();
+            amplitudes.sort_by(|a, b| b.total_cmp(a));
+            amplitudes.iter().skip(max_terms).sum::<f64>().to_radians()
+        };
+
+        let mut max_err_rad = [0.0_f64; 2];
+        for epoch in TimeSeries::inclusive(
+            Epoch::from_gregorian_utc_at_midnight(2020, 1, 1),
+            Epoch::from_gregorian_utc_at_midnight(2040, 1, 1),
+            Unit::Day * 11,
+        ) {
+            // The Moon has fewer than 18 terms, so they are all evaluated.
+            let (angles, rates) = moon.euler_angles_rad(epoch, &system);
+            assert_eq!(
+                moon.euler_angles_rad_truncated(epoch, &system, NutationTruncation::Eighteen),
+                (angles, rates)
+            );
+
+            for (i, truncation) in [NutationTruncation::Ten, NutationTruncation::Four]
+                .into_iter()
+                .enumerate()
+            {
+                let (trunc_angles, _) = moon.euler_angles_rad_truncated(epoch, &system, truncation);
+                let max_terms = truncation.num_terms();
+                for (k, phase) in [
+                    moon.pole_right_ascension,
+                    moon.pole_declination,
+                    moon.prime_meridian,
+                ]
+                .into_iter()
+                .enumerate()
+                {
+                    let err_rad = (angles[k] - trunc_angles[k]).abs();
+                    assert!(
+                        err_rad <= bound_rad(phase, max_terms) + 1e-15,
+                        "{truncation:?} angle #{k} @ {epoch}"
+                    );
+                    max_err_rad[i] = max_err_rad[i].max(err_rad);
+                }
+
+                let dcm = moon
+                    .rotation_to_parent_truncated(epoch, &system, truncation)
+                    .unwrap();
+                assert!((dcm.rot_mat.determinant() - 1.0).abs() < 1e-12);
+            }
+        }
+
+        // Truncating more is less accurate.
+        assert!(max_err_rad[0] > 0.0);
+        assert!(max_err_rad[1] > max_err_rad[0]);
+    }
 }
@@ -10,6 +10,10 @@
 use core::fmt;
 use der::{Decode, Encode, Reader, Writer};
 use hifitime::{Epoch, Unit};
+use serde::{
+    de::Error as _, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_derive::Deserialize as DeriveDeserialize;
 
 /// Angle data is represented as a polynomial of an angle, exactly like in SPICE PCK.
 /// In fact, the following documentation is basically copied from [the required PCK reading](https://naif.jpl.nasa.gov/pub/naif/toolkit_docs/C/req/pck.html).
@@ -22,6 +26,12 @@ pub struct PhaseAngle<const N: usize> {
     pub rate_deg: f64,
     /// The acceleration of this angle per T (same definition as above).
     pub accel_deg: f64,
+    /// The third order coefficient of this angle per T (same definition as above), only used by the nutation and precession
+    /// angles of the systems with a `MAX_PHASE_DEGREE` of 3, which is the highest degree supported by SPICE.
+    ///
+    /// It is not part of the encoding of a phase angle, which keeps the layout of previous versions: the planetary data
+    /// encodes the third order coefficients of its nutation and precession angles separately, only if any is set.
+    pub jerk_deg: f64,
     /// Number of nutation / precession angle coefficients
     pub coeffs_count: u8,
     pub coeffs: [f64; N],
@@ -40,6 +50,7 @@ impl<const N: usize> PhaseAngle<N> {
                 offset_deg: data[0],
                 rate_deg: *data.get(1).unwrap_or(&0.0),
                 accel_deg: *data.get(2).unwrap_or(&0.0),
+                jerk_deg: 0.0,
                 coeffs_count: (data.len() as u8).saturating_sub(3),
                 coeffs,
             })
@@ -50,7 +61,18 @@ impl<const N: usize> PhaseAngle<N> {
     pub fn evaluate_deg(&self, epoch: Epoch, rate_unit: Unit) -> f64 {
         let factor = epoch.to_tdb_duration().to_unit(rate_unit);
 
-        self.offset_deg + self.rate_deg * factor + self.accel_deg * factor.powi(2)
+        self.offset_deg
+            + self.rate_deg * factor
+            + self.accel_deg * factor.powi(2)
+            + self.jerk_deg * factor.powi(3)
+    }
+
+    /// Evaluates the time derivative of this phase angle in degrees per second provided the epoch
+    pub fn evaluate_rate_deg_s(&self, epoch: Epoch, rate_unit: Unit) -> f64 {
+        let factor = epoch.to_tdb_duration().to_unit(rate_unit);
+
+        (self.rate_deg + 2.0 * self.accel_deg * factor + 3.0 * self.jerk_deg * factor.powi(2))
+            / rate_unit.in_seconds()
     }
 }
 
@@ -78,15 +100,74 @@ impl<'a, const N: usize> Decode<'a> for PhaseAngle<N> {
             offset_deg: decoder.decode()?,
             rate_deg: decoder.decode()?,
             accel_deg: decoder.decode()?,
+            jerk_deg: 0.0,
             coeffs_count: decoder.decode()?,
             coeffs: decoder.decode()?,
         })
     }
 }
 
+impl<const N: usize> Serialize for PhaseAngle<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Only the populated coefficients are serialized, the rest of the array is padding.
+        let count = (self.coeffs_count as usize).min(N);
+        let mut state = serializer.serialize_struct("PhaseAngle", 5)?;
+        state.serialize_field("offset_deg", &self.offset_deg)?;
+        state.serialize_field("rate_deg", &self.rate_deg)?;
+        state.serialize_field("accel_deg", &self.accel_deg)?;
+        state.serialize_field("jerk_deg", &self.jerk_deg)?;
+        state.serialize_field("coeffs", &self.coeffs[..count])?;
+        state.end()
+    }
+}
+
+/// Serialized representation of a phase angle, where the coefficients are not padded to the capacity of the phase angle.
+#[derive(DeriveDeserialize)]
+struct PhaseAngleRepr {
+    offset_deg: f64,
+    #[serde(default)]
+    rate_deg: f64,
+    #[serde(default)]
+    accel_deg: f64,
+    #[serde(default)]
+    jerk_deg: f64,
+    #[serde(default)]
+    coeffs: Vec<f64>,
+}
+
+impl<'de, const N: usize> Deserialize<'de> for PhaseAngle<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = PhaseAngleRepr::deserialize(deserializer)?;
+        if repr.coeffs.len() > N {
+            return Err(D::Error::custom(format!(
+                "phase angle supports at most {N} coefficients, got {}",
+                repr.coeffs.len()
+            )));
+        }
+
+        let mut coeffs = [0.0; N];
+        coeffs[..repr.coeffs.len()].copy_from_slice(&repr.coeffs);
+
+        Ok(Self {
+            offset_deg: repr.offset_deg,
+            rate_deg: repr.rate_deg,
+            accel_deg: repr.accel_deg,
+            jerk_deg: repr.jerk_deg,
+            coeffs_count: repr.coeffs.len() as u8,
+            coeffs,
+        })
+    }
+}
+
 impl<const N: usize> fmt::Display for PhaseAngle<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.accel_deg.abs() > 0.0 {
+        if self.jerk_deg.abs() > 0.0 {
+            write!(
+                f,
+                "{} + {} t + {} t^2 + {} t^3",
+                self.offset_deg, self.rate_deg, self.accel_deg, self.jerk_deg
+            )
+        } else if self.accel_deg.abs() > 0.0 {
             write!(
                 f,
                 "{} + {} t + {} t^2",
@@ -104,6 +185,7 @@ impl<const N: usize> Default for PhaseAngle<N> {
             offset_deg: Default::default(),
             rate_deg: Default::default(),
             accel_deg: Default::default(),
+            jerk_deg: Default::default(),
             coeffs_count: Default::default(),
             coeffs: [0.0; N],
         }
@@ -156,4 +238,39 @@ mod phase_angle_ut {
             125.04499854915811
         );
     }
+
+    #[test]
+    fn cubic_repr() {
+        // Nutation and precession angle of a system with a MAX_PHASE_DEGREE of 3
+        let repr = PhaseAngle::<0> {
+            offset_deg: 10.0,
+            rate_deg: 2.0,
+            accel_deg: 0.5,
+            jerk_deg: 0.25,
+            ..Default::default()
+        };
+
+        // The third order coefficient is encoded by the planetary data, not by the phase angle.
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+        assert_eq!(
+            PhaseAngle::from_der(&buf).unwrap(),
+            PhaseAngle {
+                jerk_deg: 0.0,
+                ..repr
+            }
+        );
+        assert_eq!(format!("{repr}"), "10 + 2 t + 0.5 t^2 + 0.25 t^3");
+
+        let two_days = Epoch::from_tdb_duration(2.days());
+        assert_eq!(
+            repr.evaluate_deg(two_days, Unit::Day),
+            10.0 + 4.0 + 2.0 + 2.0
+        );
+        // The rate is 2 + 2 * 0.5 * t + 3 * 0.25 * t^2 degrees per day
+        assert!(
+            (repr.evaluate_rate_deg_s(two_days, Unit::Day) - (2.0 + 2.0 + 3.0) / 86_400.0).abs()
+                < 1e-18
+        );
+    }
 }
@@ -19,6 +19,48 @@ pub struct Semver {
     pub patch: u8,
 }
 
+/// Compatibility of the version of a data set with the version of ANISE reading it.
+///
+/// # Policy
+/// Data sets of another major version are rejected without being decoded. Within a major version, a change to the
+/// layout of a data set structure bumps the minor version of ANISE and the `LAYOUT_VERSION` of that structure: data
+/// sets older than the layout of their structure are incompatible and must be rebuilt from their source kernels, while
+/// data sets of other structures remain decodable and can be migrated with `anise upgrade`. Optional data added behind
+/// a data flag, which older data sets leave unset, does not change the layout of a structure.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SchemaCompatibility {
+    /// Same major and minor versions
+    Current,
+    /// Older minor version of the same major version, which can be migrated with `anise upgrade`
+    OlderMinor,
+    /// Newer minor version of the same major version
+    NewerMinor,
+    /// Different major version, which cannot be decoded
+    Incompatible,
+}
+
+impl SchemaCompatibility {
+    /// Returns true if data sets of this compatibility can be decoded.
+    pub const fn is_decodable(&self) -> bool {
+        !matches!(self, Self::Incompatible)
+    }
+}
+
+impl Semver {
+    /// Returns the compatibility of data encoded with this version when read by the provided version of ANISE.
+    pub const fn compatibility_with(&self, reader: Semver) -> SchemaCompatibility {
+        if self.major != reader.major {
+            SchemaCompatibility::Incompatible
+        } else if self.minor < reader.minor {
+            SchemaCompatibility::OlderMinor
+        } else if self.minor > reader.minor {
+            SchemaCompatibility::NewerMinor
+        } else {
+            SchemaCompatibility::Current
+        }
+    }
+}
+
 impl Encode for Semver {
     fn encoded_len(&self) -> der::Result<der::Length> {
         let data: [u8; 3] = [self.major, self.minor, self.patch];
@@ -63,3 +105,41 @@ impl fmt::Display for Semver {
         )
     }
 }
+
+#[cfg(test)]
+mod semver_ut {
+    use super::{SchemaCompatibility, Semver};
+
+    #[test]
+    fn schema_compatibility() {
+        let reader = Semver {
+            major: 1,
+            minor: 4,
+            patch: 2,
+        };
+        let version = |major, minor, patch| Semver {
+            major,
+            minor,
+            patch,
+        };
+
+        assert_eq!(
+            version(1, 4, 0).compatibility_with(reader),
+            SchemaCompatibility::Current
+        );
+        assert_eq!(
+            version(1, 2, 7).compatibility_with(reader),
+            SchemaCompatibility::OlderMinor
+        );
+        assert_eq!(
+            version(1, 5, 0).compatibility_with(reader),
+            SchemaCompatibility::NewerMinor
+        );
+        assert_eq!(
+            version(2, 0, 0).compatibility_with(reader),
+            SchemaCompatibility::Incompatible
+        );
+        assert!(!version(0, 4, 0).compatibility_with(reader).is_decodable());
+        assert!(version(1, 0, 0).compatibility_with(reader).is_decodable());
+    }
+}
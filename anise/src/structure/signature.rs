@@ -0,0 +1,172 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use ed25519_dalek::{Signature, Signer};
+use snafu::prelude::*;
+use std::fs;
+use std::path::PathBuf;
+
+pub use ed25519_dalek::{SigningKey, VerifyingKey};
+
+use super::dataset::{DataSet, DataSetError, DataSetT, DataSetType};
+use super::metadata::Metadata;
+use super::{EulerParameterDataSet, ManeuverDataSet, PlanetaryDataSet, SpacecraftDataSet};
+use crate::errors::{DecodingError, InputOutputError};
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum SignatureError {
+    #[snafu(display("data set is not signed"))]
+    Unsigned,
+    #[snafu(display("signature does not match the data set and the trusted key"))]
+    Mismatch,
+    #[snafu(display("only ANISE data sets can be signed: {source}"))]
+    NotADataSet { source: DecodingError },
+    #[snafu(display("{source} encountered when {action}"))]
+    SignedDataSet {
+        action: &'static str,
+        source: DataSetError,
+    },
+    #[snafu(display("{source} encountered with {path}"))]
+    SignatureIO {
+        path: String,
+        source: InputOutputError,
+    },
+}
+
+/// Signs the data set with the private key, replacing its previous signature if any.
+pub fn sign<T: DataSetT, const ENTRIES: usize>(
+    dataset: &mut DataSet<T, ENTRIES>,
+    key: &SigningKey,
+) -> Result<(), SignatureError> {
+    let bytes = dataset.signed_bytes().context(SignedDataSetSnafu {
+        action: "signing data set",
+    })?;
+    dataset.signature = Some(Box::new(key.sign(&bytes).to_bytes()));
+    Ok(())
+}
+
+/// Verifies the signature of the data set against the trusted public key.
+///
+/// The verification is strict: weak public keys and malleable signatures are rejected.
+pub fn verify<T: DataSetT, const ENTRIES: usize>(
+    dataset: &DataSet<T, ENTRIES>,
+    key: &VerifyingKey,
+) -> Result<(), SignatureError> {
+    let signature = dataset.signature.as_deref().context(UnsignedSnafu)?;
+    let bytes = dataset.signed_bytes().context(SignedDataSetSnafu {
+        action: "verifying data set",
+    })?;
+
+    key.verify_strict(&bytes, &Signature::from_bytes(signature))
+        .map_err(|_| SignatureError::Mismatch)
+}
+
+/// Signs the ANISE data set file at the provided path with the private key, and stores the signature in the file.
+pub fn sign_file(path: &str, key: &SigningKey) -> Result<(), SignatureError> {
+    let bytes = fs::read(path).map_err(|e| SignatureError::SignatureIO {
+        path: path.to_string(),
+        source: e.kind().into(),
+    })?;
+
+    let metadata = Metadata::decode_header(&bytes).context(NotADataSetSnafu)?;
+
+    match metadata.dataset_type {
+        DataSetType::SpacecraftData => {
+            sign_and_save(SpacecraftDataSet::try_from_bytes(bytes), path, key)
+        }
+        DataSetType::PlanetaryData => {
+            sign_and_save(PlanetaryDataSet::try_from_bytes(bytes), path, key)
+        }
+        DataSetType::EulerParameterData => {
+            sign_and_save(EulerParameterDataSet::try_from_bytes(bytes), path, key)
+        }
+        DataSetType::ManeuverData => {
+            sign_and_save(ManeuverDataSet::try_from_bytes(bytes), path, key)
+        }
+        DataSetType::NotApplicable => Err(SignatureError::NotADataSet {
+            source: DecodingError::Obscure { kind: "data set" },
+        }),
+    }
+}
+
+/// Signs the decoded data set and overwrites the file it was read from.
+fn sign_and_save<T: DataSetT, const ENTRIES: usize>(
+    dataset: Result<DataSet<T, ENTRIES>, DataSetError>,
+    path: &str,
+    key: &SigningKey,
+) -> Result<(), SignatureError> {
+    let mut dataset = dataset.context(SignedDataSetSnafu {
+        action: "decoding data set to sign",
+    })?;
+    sign(&mut dataset, key)?;
+    dataset
+        .save_as(&PathBuf::from(path), true)
+        .context(SignedDataSetSnafu {
+            action: "saving signed data set",
+        })
+}
+
+#[cfg(test)]
+mod ut_signature {
+    use super::{sign, verify, SignatureError, SigningKey};
+    use crate::structure::spacecraft::{Mass, SpacecraftData};
+    use crate::structure::SpacecraftDataSet;
+    use der::{Decode, Encode};
+
+    #[test]
+    fn sign_and_verify() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let other_key = SigningKey::from_bytes(&[8; 32]);
+
+        let mut dataset = SpacecraftDataSet::default();
+        dataset
+            .push(
+                SpacecraftData {
+                    mass: Some(Mass::from_dry_mass(150.0)),
+                    ..Default::default()
+                },
+                Some(-20),
+                Some("SC"),
+            )
+            .unwrap();
+        dataset.set_crc32();
+
+        assert!(matches!(
+            verify(&dataset, &key.verifying_key()),
+            Err(SignatureError::Unsigned)
+        ));
+
+        sign(&mut dataset, &key).unwrap();
+        assert!(verify(&dataset, &key.verifying_key()).is_ok());
+
+        // The signature is stored in the data set, and unsigned data sets are encoded as before.
+        let mut buf = vec![];
+        dataset.encode_to_vec(&mut buf).unwrap();
+        let decoded = SpacecraftDataSet::from_der(&buf).unwrap();
+        assert_eq!(decoded, dataset);
+        assert!(verify(&decoded, &key.verifying_key()).is_ok());
+        assert_eq!(dataset.signed_bytes().unwrap(), buf[..buf.len() - 66]);
+
+        // Untrusted key
+        assert!(matches!(
+            verify(&dataset, &other_key.verifying_key()),
+            Err(SignatureError::Mismatch)
+        ));
+
+        // Tampered data
+        let mut tampered = dataset.clone();
+        tampered.metadata.originator = "Mallory".try_into().unwrap();
+        assert!(matches!(
+            verify(&tampered, &key.verifying_key()),
+            Err(SignatureError::Mismatch)
+        ));
+    }
+}
@@ -0,0 +1,162 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+use der::{Decode, Encode, Reader, Writer};
+use hifitime::Epoch;
+use serde_derive::{Deserialize, Serialize};
+
+use super::{Inertia, Mass};
+
+/// Mass properties of a spacecraft at an epoch, e.g. after a burn, as stored in the mass history of its spacecraft data.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MassRecord {
+    /// Epoch of this record, in seconds past J2000 ET
+    pub epoch_et_s: f64,
+    pub mass: Mass,
+    /// Inertia tensor at this epoch, if it is known
+    pub inertia: Option<Inertia>,
+}
+
+impl MassRecord {
+    /// Creates a record of the mass at the provided epoch, without inertia.
+    pub fn new(epoch: Epoch, mass: Mass) -> Self {
+        Self {
+            epoch_et_s: epoch.to_et_seconds(),
+            mass,
+            inertia: None,
+        }
+    }
+
+    /// Returns a copy of this record with the provided inertia tensor.
+    pub fn with_inertia(&self, inertia: Inertia) -> Self {
+        let mut me = *self;
+        me.inertia = Some(inertia);
+        me
+    }
+
+    /// Returns the epoch of this record
+    pub fn epoch(&self) -> Epoch {
+        Epoch::from_et_seconds(self.epoch_et_s)
+    }
+}
+
+impl Encode for MassRecord {
+    fn encoded_len(&self) -> der::Result<der::Length> {
+        let has_inertia = self.inertia.is_some() as u8;
+        self.epoch_et_s.encoded_len()?
+            + self.mass.encoded_len()?
+            + has_inertia.encoded_len()?
+            + self.inertia.encoded_len()?
+    }
+
+    fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
+        self.epoch_et_s.encode(encoder)?;
+        self.mass.encode(encoder)?;
+        (self.inertia.is_some() as u8).encode(encoder)?;
+        self.inertia.encode(encoder)
+    }
+}
+
+impl<'a> Decode<'a> for MassRecord {
+    fn decode<R: Reader<'a>>(decoder: &mut R) -> der::Result<Self> {
+        let epoch_et_s = decoder.decode()?;
+        let mass = decoder.decode()?;
+        let has_inertia: u8 = decoder.decode()?;
+        let inertia = if has_inertia != 0 {
+            Some(decoder.decode()?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            epoch_et_s,
+            mass,
+            inertia,
+        })
+    }
+}
+
+/// Linearly interpolates the values of the records, sorted by epoch, at the provided epoch, skipping the records without a
+/// value, and holds the first and last values outside of their span. Returns None if no record has a value.
+pub(super) fn interpolate<T: Copy>(
+    records: &[MassRecord],
+    epoch_et_s: f64,
+    value: impl Fn(&MassRecord) -> Option<T>,
+    lerp: impl Fn(T, T, f64) -> T,
+) -> Option<T> {
+    let tagged = |record: &MassRecord| value(record).map(|value| (record.epoch_et_s, value));
+    let idx = records.partition_point(|record| record.epoch_et_s <= epoch_et_s);
+    let prev = records[..idx].iter().rev().find_map(tagged);
+    let next = records[idx..].iter().find_map(tagged);
+
+    match (prev, next) {
+        (Some((start_et_s, start)), Some((end_et_s, end))) => Some(lerp(
+            start,
+            end,
+            (epoch_et_s - start_et_s) / (end_et_s - start_et_s),
+        )),
+        (Some((_, value)), None) | (None, Some((_, value))) => Some(value),
+        (None, None) => None,
+    }
+}
+
+pub(super) fn lerp_mass(start: Mass, end: Mass, fraction: f64) -> Mass {
+    Mass {
+        dry_mass_kg: start.dry_mass_kg + (end.dry_mass_kg - start.dry_mass_kg) * fraction,
+        prop_mass_kg: start.prop_mass_kg + (end.prop_mass_kg - start.prop_mass_kg) * fraction,
+        extra_mass_kg: start.extra_mass_kg + (end.extra_mass_kg - start.extra_mass_kg) * fraction,
+    }
+}
+
+/// Interpolates the components of the inertia tensors if they are expressed in the same frame, and holds the start tensor
+/// otherwise.
+pub(super) fn lerp_inertia(start: Inertia, end: Inertia, fraction: f64) -> Inertia {
+    if start.orientation_id != end.orientation_id {
+        return start;
+    }
+    let lerp = |start: f64, end: f64| start + (end - start) * fraction;
+    Inertia {
+        orientation_id: start.orientation_id,
+        i_xx_kgm2: lerp(start.i_xx_kgm2, end.i_xx_kgm2),
+        i_yy_kgm2: lerp(start.i_yy_kgm2, end.i_yy_kgm2),
+        i_zz_kgm2: lerp(start.i_zz_kgm2, end.i_zz_kgm2),
+        i_xy_kgm2: lerp(start.i_xy_kgm2, end.i_xy_kgm2),
+        i_xz_kgm2: lerp(start.i_xz_kgm2, end.i_xz_kgm2),
+        i_yz_kgm2: lerp(start.i_yz_kgm2, end.i_yz_kgm2),
+    }
+}
+
+#[cfg(test)]
+mod ut_history {
+    use super::{Decode, Encode, Inertia, Mass, MassRecord};
+    use hifitime::Epoch;
+
+    #[test]
+    fn mass_record_repr() {
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+        let repr = MassRecord::new(epoch, Mass::from_dry_and_prop_masses(150.0, 50.6));
+
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+        assert_eq!(MassRecord::from_der(&buf).unwrap(), repr);
+        assert_eq!(repr.epoch(), epoch);
+
+        let repr = repr.with_inertia(Inertia {
+            orientation_id: -20,
+            i_xx_kgm2: 120.0,
+            i_yy_kgm2: 180.0,
+            i_zz_kgm2: 220.0,
+            ..Default::default()
+        });
+
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+        assert_eq!(MassRecord::from_der(&buf).unwrap(), repr);
+    }
+}
@@ -8,21 +8,30 @@
  * Documentation: https://nyxspace.com/
  */
 use der::{Decode, Encode, Reader, Writer};
+use hifitime::Epoch;
 use serde::{Deserialize, Serialize};
 
 mod drag;
+mod history;
 mod inertia;
 mod mass;
 mod srp;
 
 use super::dataset::DataSetT;
+use super::semver::Semver;
 pub use drag::DragData;
+pub use history::MassRecord;
 pub use inertia::Inertia;
 pub use mass::Mass;
 pub use srp::SRPData;
 
 /// Spacecraft constants can store the some of the spacecraft constant data as the CCSDS Orbit Parameter Message (OPM) and CCSDS Attitude Parameter Messages (APM)
-#[derive(Copy, Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+///
+/// # Compatibility
+/// The mass history changed the layout of this structure in version 0.5.0 of the ANISE data sets, cf. [DataSetT::LAYOUT_VERSION]:
+/// spacecraft data sets of older versions are rejected and must be rebuilt. Since the history is stored on the heap, this
+/// structure is not `Copy` since that version.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SpacecraftData {
     /// Mass of the spacecraft in kg
     pub mass: Option<Mass>,
@@ -32,10 +41,17 @@ pub struct SpacecraftData {
     pub drag_data: Option<DragData>,
     // Inertia tensor
     pub inertia: Option<Inertia>,
+    /// Time history of the mass properties, e.g. the mass depletion after each burn, sorted by epoch, cf. `mass_at`
+    pub mass_history: Vec<MassRecord>,
 }
 
 impl DataSetT for SpacecraftData {
     const NAME: &'static str = "spacecraft data";
+    const LAYOUT_VERSION: Semver = Semver {
+        major: 0,
+        minor: 5,
+        patch: 0,
+    };
 }
 
 impl SpacecraftData {
@@ -46,6 +62,7 @@ impl SpacecraftData {
     /// + Bit 1 is set if `srp_data` is available
     /// + Bit 2 is set if `drag_data` is available
     /// + Bit 3 is set if `inertia` is available
+    /// + Bit 4 is set if `mass_history` is available
     fn available_data(&self) -> u8 {
         let mut bits: u8 = 0;
 
@@ -61,9 +78,45 @@ impl SpacecraftData {
         if self.inertia.is_some() {
             bits |= 1 << 3;
         }
+        if !self.mass_history.is_empty() {
+            bits |= 1 << 4;
+        }
 
         bits
     }
+
+    /// Adds the record to the mass history, keeping the history sorted by epoch.
+    pub fn add_mass_record(&mut self, record: MassRecord) {
+        let idx = self
+            .mass_history
+            .partition_point(|other| other.epoch_et_s <= record.epoch_et_s);
+        self.mass_history.insert(idx, record);
+    }
+
+    /// Returns the mass of the spacecraft at the provided epoch, linearly interpolated between the records of the mass history,
+    /// and equal to the first (resp. last) record before (resp. after) the history. Returns the constant `mass` if there is no
+    /// mass history.
+    pub fn mass_at(&self, epoch: Epoch) -> Option<Mass> {
+        history::interpolate(
+            &self.mass_history,
+            epoch.to_et_seconds(),
+            |record| Some(record.mass),
+            history::lerp_mass,
+        )
+        .or(self.mass)
+    }
+
+    /// Returns the inertia tensor of the spacecraft at the provided epoch, interpolated as the mass between the records of the
+    /// mass history which have an inertia tensor, cf. `mass_at`. Returns the constant `inertia` if no record has one.
+    pub fn inertia_at(&self, epoch: Epoch) -> Option<Inertia> {
+        history::interpolate(
+            &self.mass_history,
+            epoch.to_et_seconds(),
+            |record| record.inertia,
+            history::lerp_inertia,
+        )
+        .or(self.inertia)
+    }
 }
 
 impl Encode for SpacecraftData {
@@ -74,6 +127,7 @@ impl Encode for SpacecraftData {
             + self.srp_data.encoded_len()?
             + self.drag_data.encoded_len()?
             + self.inertia.encoded_len()?
+            + self.mass_history_encoded_len()?
     }
 
     fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
@@ -81,7 +135,27 @@ impl Encode for SpacecraftData {
         self.mass.encode(encoder)?;
         self.srp_data.encode(encoder)?;
         self.drag_data.encode(encoder)?;
-        self.inertia.encode(encoder)
+        self.inertia.encode(encoder)?;
+        if !self.mass_history.is_empty() {
+            (self.mass_history.len() as u32).encode(encoder)?;
+            for record in &self.mass_history {
+                record.encode(encoder)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SpacecraftData {
+    fn mass_history_encoded_len(&self) -> der::Result<der::Length> {
+        if self.mass_history.is_empty() {
+            return Ok(der::Length::ZERO);
+        }
+        let mut len = (self.mass_history.len() as u32).encoded_len()?;
+        for record in &self.mass_history {
+            len = (len + record.encoded_len()?)?;
+        }
+        Ok(len)
     }
 }
 
@@ -113,18 +187,27 @@ impl<'a> Decode<'a> for SpacecraftData {
             None
         };
 
+        let mut mass_history = Vec::new();
+        if data_flags & (1 << 4) != 0 {
+            let num: u32 = decoder.decode()?;
+            for _ in 0..num {
+                mass_history.push(decoder.decode()?);
+            }
+        }
+
         Ok(Self {
             mass: mass_kg,
             srp_data,
             drag_data,
             inertia,
+            mass_history,
         })
     }
 }
 
 #[cfg(test)]
 mod spacecraft_constants_ut {
-    use super::{Decode, DragData, Encode, Inertia, Mass, SRPData, SpacecraftData};
+    use super::{Decode, DragData, Encode, Inertia, Mass, MassRecord, SRPData, SpacecraftData};
 
     #[test]
     fn sc_min_repr() {
@@ -244,6 +327,7 @@ mod spacecraft_constants_ut {
             }),
             mass: Some(Mass::from_dry_and_prop_masses(150.0, 50.6)),
             drag_data: Some(DragData::default()),
+            ..Default::default()
         };
 
         let mut buf = vec![];
@@ -253,4 +337,67 @@ mod spacecraft_constants_ut {
 
         assert_eq!(repr, repr_dec);
     }
+
+    #[test]
+    fn sc_mass_history() {
+        use hifitime::{Epoch, Unit};
+
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+        let inertia = Inertia {
+            orientation_id: -20,
+            i_xx_kgm2: 120.0,
+            i_yy_kgm2: 180.0,
+            i_zz_kgm2: 220.0,
+            ..Default::default()
+        };
+
+        let mut repr = SpacecraftData {
+            mass: Some(Mass::from_dry_and_prop_masses(150.0, 50.0)),
+            ..Default::default()
+        };
+        // Without history, the mass is constant.
+        assert_eq!(repr.mass_at(epoch), repr.mass);
+        assert_eq!(repr.inertia_at(epoch), None);
+
+        // A burn of one hour uses 10 kg of propellant.
+        repr.add_mass_record(MassRecord::new(
+            epoch + Unit::Hour,
+            Mass::from_dry_and_prop_masses(150.0, 40.0),
+        ));
+        repr.add_mass_record(
+            MassRecord::new(epoch, Mass::from_dry_and_prop_masses(150.0, 50.0))
+                .with_inertia(inertia),
+        );
+        assert_eq!(repr.mass_history[0].epoch(), epoch);
+
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+        let repr_dec = SpacecraftData::from_der(&buf).unwrap();
+        assert_eq!(repr, repr_dec);
+
+        let mid_burn = repr.mass_at(epoch + Unit::Minute * 30).unwrap();
+        assert!((mid_burn.prop_mass_kg - 45.0).abs() < 1e-9);
+        assert_eq!(mid_burn.dry_mass_kg, 150.0);
+        // Outside of the history, the mass of the closest record is returned.
+        assert_eq!(repr.mass_at(epoch - Unit::Day).unwrap().prop_mass_kg, 50.0);
+        assert_eq!(repr.mass_at(epoch + Unit::Day).unwrap().prop_mass_kg, 40.0);
+        // Only one record has an inertia tensor, so it is held over the whole history.
+        assert_eq!(repr.inertia_at(epoch + Unit::Day), Some(inertia));
+
+        // Records without an inertia tensor are skipped when interpolating the inertia.
+        let heavier = Inertia {
+            i_xx_kgm2: 140.0,
+            ..inertia
+        };
+        repr.add_mass_record(
+            MassRecord::new(
+                epoch + Unit::Hour * 2,
+                Mass::from_dry_and_prop_masses(150.0, 40.0),
+            )
+            .with_inertia(heavier),
+        );
+        let mid_inertia = repr.inertia_at(epoch + Unit::Hour).unwrap();
+        assert!((mid_inertia.i_xx_kgm2 - 130.0).abs() < 1e-9);
+        assert_eq!(repr.inertia_at(epoch + Unit::Day), Some(heavier));
+    }
 }
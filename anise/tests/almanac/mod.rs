@@ -78,6 +78,62 @@ fn test_state_transformation() {
     assert_eq!(orig_state, from_state_itrf93_to_eme2k);
 }
 
+#[test]
+fn test_covariance_transformation() {
+    use anise::constants::frames::MOON_J2000;
+    use anise::math::cartesian::CovariantState;
+    use anise::math::{Matrix6, Vector6};
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/earth_latest_high_prec.bpc")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let epoch = Epoch::from_str("2021-10-29 12:34:56 TDB").unwrap();
+    let orbit = Orbit::keplerian(
+        8_191.93, 1e-6, 12.85, 306.614, 314.19, 99.887_7, epoch, eme2k,
+    );
+    // One kilometer along X, one hundred meters along Y and Z, and one meter per second along each axis
+    let cov = Matrix6::from_diagonal(&Vector6::new(1.0, 1e-2, 1e-2, 1e-6, 1e-6, 1e-6));
+    let state = CovariantState::new(orbit, cov);
+
+    // Rotate into the Earth fixed frame
+    let itrf93 = almanac
+        .transform_covariant_to(state, EARTH_ITRF93, None)
+        .unwrap();
+    assert_eq!(
+        itrf93.state,
+        almanac.transform_to(orbit, EARTH_ITRF93, None).unwrap()
+    );
+    let jacobian = almanac
+        .state_transformation_from_to(EARTH_J2000, EARTH_ITRF93, epoch)
+        .unwrap();
+    assert!((itrf93.covariance - jacobian * cov * jacobian.transpose()).norm() < 1e-15);
+    assert!((itrf93.covariance - itrf93.covariance.transpose()).norm() < 1e-15);
+    // The rotation preserves the position uncertainty, and its rate increases the velocity uncertainty
+    assert!((itrf93.covariance.fixed_view::<3, 3>(0, 0).trace() - 1.02).abs() < 1e-12);
+    assert!(itrf93.covariance.fixed_view::<3, 3>(3, 3).trace() > 3e-6);
+
+    // Translating to the Moon does not change the covariance
+    let moon = almanac
+        .transform_covariant_to(state, MOON_J2000, None)
+        .unwrap();
+    assert_eq!(
+        moon.state,
+        almanac.transform_to(orbit, MOON_J2000, None).unwrap()
+    );
+    assert_eq!(moon.covariance, cov);
+
+    // And converting back recovers the original covariance
+    let back = almanac
+        .transform_covariant_to(itrf93, EARTH_J2000, None)
+        .unwrap();
+    assert!((back.covariance - cov).norm() < 1e-12);
+}
+
 #[test]
 fn test_type3_state_transformation() {
     // Load BSP and BPC
@@ -105,3 +161,1879 @@ fn test_type3_state_transformation() {
 
     println!("{state:x}");
 }
+
+#[cfg(feature = "export")]
+#[test]
+fn test_export_trajectory() {
+    use anise::almanac::export::ExportFormat;
+    use anise::constants::frames::MOON_J2000;
+    use hifitime::TimeUnits;
+    use polars::prelude::*;
+
+    let almanac = Almanac::new("../data/de440s.bsp").unwrap();
+
+    let start = Epoch::from_str("2021-10-29 12:34:56 TDB").unwrap();
+    let end = start + 1.days();
+
+    let num_rows = almanac
+        .export_trajectory(
+            MOON_J2000,
+            EARTH_J2000,
+            start,
+            end,
+            1.hours(),
+            None,
+            "../target/moon-earth-traj.parquet",
+            ExportFormat::Parquet,
+        )
+        .unwrap();
+
+    assert_eq!(num_rows, 25);
+
+    let df = LazyFrame::scan_parquet(
+        "../target/moon-earth-traj.parquet",
+        ScanArgsParquet::default(),
+    )
+    .unwrap()
+    .collect()
+    .unwrap();
+
+    assert_eq!(df.height(), num_rows);
+    assert_eq!(df.width(), 9);
+
+    let csv_rows = almanac
+        .export_trajectory(
+            MOON_J2000,
+            EARTH_J2000,
+            start,
+            end,
+            1.hours(),
+            None,
+            "../target/moon-earth-traj.csv",
+            ExportFormat::Csv,
+        )
+        .unwrap();
+
+    assert_eq!(csv_rows, num_rows);
+
+    let csv = std::fs::read_to_string("../target/moon-earth-traj.csv").unwrap();
+    // Header and one line per state
+    assert_eq!(csv.lines().count(), num_rows + 1);
+    assert!(csv.starts_with("source frame,destination frame,ET Epoch (s),X,Y,Z,VX,VY,VZ"));
+
+    // Exporting an empty window is an error
+    assert!(almanac
+        .export_trajectory(
+            MOON_J2000,
+            EARTH_J2000,
+            end,
+            start,
+            1.hours(),
+            None,
+            "../target/moon-earth-empty.csv",
+            ExportFormat::Csv,
+        )
+        .is_err());
+}
+
+#[test]
+fn test_query_transcript() {
+    use anise::constants::frames::MOON_J2000;
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .with_transcript();
+
+    let epoch = Epoch::from_str("2021-10-29 12:34:56 TDB").unwrap();
+
+    almanac
+        .transform(MOON_J2000, EARTH_J2000, epoch, None)
+        .unwrap();
+    almanac
+        .transform(SUN_J2000, EARTH_J2000, epoch, Aberration::LT)
+        .unwrap();
+    // Failed queries are recorded too
+    assert!(almanac
+        .transform(EARTH_ITRF93, EARTH_J2000, epoch, None)
+        .is_err());
+
+    let transcript = almanac.transcript.as_ref().unwrap();
+    assert_eq!(transcript.len(), 3);
+
+    let records = transcript.records();
+    assert_eq!(records[0].function, "transform");
+    assert_eq!(records[1].ab_corr.as_deref(), Some("LT"));
+    assert!(records[0].result_hash.is_some());
+    assert!(!records[0].sources.is_empty());
+    assert!(records[0].sources.iter().all(|src| src.starts_with("SPK")));
+    // No ANISE data set is loaded
+    assert!(records[0].datasets.is_empty());
+    assert!(records[2].result_hash.is_none());
+    assert!(records[2].error.is_some());
+
+    // Header and one line per query
+    assert_eq!(transcript.to_csv().lines().count(), 4);
+
+    // Fields with separators, quotes, or line breaks are quoted as per RFC 4180
+    let mut record = records[2].clone();
+    record.error = Some("bad \"frame\",\nretry".to_string());
+    assert!(format!("{record}").ends_with(",\"bad \"\"frame\"\",\nretry\""));
+
+    // Replaying with the same data reproduces all of the results
+    assert!(transcript.replay(&almanac).unwrap().is_empty());
+
+    // Replaying without the data flags the queries whose results differ
+    assert_eq!(transcript.replay(&Almanac::default()).unwrap(), vec![0, 1]);
+
+    transcript.clear();
+    assert!(transcript.is_empty());
+
+    // The digests of the loaded data sets are recorded with each query
+    let almanac = almanac.load("../data/pck08.pca").unwrap();
+    almanac
+        .transform(MOON_J2000, EARTH_J2000, epoch, None)
+        .unwrap();
+    let records = almanac.transcript.as_ref().unwrap().records();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].datasets.len(), 1);
+    assert!(records[0].datasets[0].starts_with("planetary data blake3:"));
+    assert_eq!(
+        records[0].datasets[0],
+        format!(
+            "planetary data blake3:{}",
+            almanac
+                .planetary_data
+                .digest()
+                .unwrap()
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<String>()
+        )
+    );
+
+    // Modifying a data set in place, without updating its checksum, changes its recorded digest
+    let mut almanac = almanac;
+    let mut moon = almanac.planetary_data.get_by_id(301).unwrap();
+    moon.mu_km3_s2 *= 2.0;
+    almanac.planetary_data.set_by_id(301, moon).unwrap();
+    almanac
+        .transform(MOON_J2000, EARTH_J2000, epoch, None)
+        .unwrap();
+    let records = almanac.transcript.as_ref().unwrap().records();
+    assert_eq!(records.len(), 2);
+    assert_ne!(records[0].datasets, records[1].datasets);
+}
+
+#[test]
+fn test_spice_compat_light_time() {
+    use anise::constants::frames::{MARS_BARYCENTER_J2000, MOON_J2000};
+
+    let almanac = Almanac::new("../data/de440s.bsp").unwrap();
+    let spice_almanac = almanac.with_spice_compat(true);
+
+    let epoch = Epoch::from_str("2021-10-29 12:34:56 TDB").unwrap();
+
+    for target in [MOON_J2000, MARS_BARYCENTER_J2000] {
+        // A single light time iteration is identical in both modes
+        let anise_lt = almanac
+            .translate(target, EARTH_J2000, epoch, Aberration::LT)
+            .unwrap();
+        let spice_lt = spice_almanac
+            .translate(target, EARTH_J2000, epoch, Aberration::LT)
+            .unwrap();
+        assert_eq!(anise_lt, spice_lt);
+
+        // The converged light time only differs by the stopping criteria
+        let anise_cn = almanac
+            .translate(target, EARTH_J2000, epoch, Aberration::CN)
+            .unwrap();
+        let spice_cn = spice_almanac
+            .translate(target, EARTH_J2000, epoch, Aberration::CN)
+            .unwrap();
+        assert!((anise_cn.radius_km - spice_cn.radius_km).norm() < 1e-6);
+    }
+}
+
+#[test]
+fn test_comparator() {
+    use anise::compare::Comparator;
+    use anise::constants::frames::MOON_J2000;
+    use hifitime::{TimeSeries, TimeUnits};
+
+    let de440s = Almanac::new("../data/de440s.bsp").unwrap();
+    let start = Epoch::from_str("2021-10-29 12:34:56 TDB").unwrap();
+
+    let comparator = Comparator::new(de440s.clone(), de440s.clone())
+        .with_pair(MOON_J2000, EARTH_J2000)
+        .with_pair(SUN_J2000, EARTH_J2000)
+        .with_epochs(TimeSeries::inclusive(start, start + 1.days(), 1.hours()));
+
+    // Identical data yields identical results
+    let report = comparator.compare().unwrap();
+    println!("{report}");
+    assert_eq!(report.pairs.len(), 2);
+    assert_eq!(report.pairs[0].num_compared, 25);
+    assert!(report.is_within(0.0, 0.0));
+    assert!(report.worst_pair().is_some());
+
+    // Missing data is reported
+    let mut comparator = comparator;
+    comparator.candidate = Almanac::default();
+    let report = comparator.compare().unwrap();
+    assert_eq!(report.pairs[0].num_one_failed, 25);
+    assert!(!report.is_within(1.0, 1.0));
+}
+
+#[test]
+fn test_saturn_ring_plane_crossings() {
+    use anise::almanac::events::CrossingDirection;
+    use anise::constants::celestial_objects::SATURN_BARYCENTER;
+    use anise::constants::orientations::IAU_SATURN;
+    use anise::prelude::Frame;
+    use hifitime::TimeUnits;
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+
+    // DE440s only includes the Saturn barycenter, which is close enough to Saturn for this purpose.
+    let saturn_fixed = Frame::new(SATURN_BARYCENTER, IAU_SATURN);
+
+    // The Earth crossed the ring plane of Saturn on 2025-03-23, and the Sun on 2025-05-06 (Saturn equinox).
+    let start = Epoch::from_str("2025-01-01 00:00:00 TDB").unwrap();
+    let end = Epoch::from_str("2025-07-01 00:00:00 TDB").unwrap();
+
+    let earth_crossings = almanac
+        .ring_plane_crossings(EARTH_J2000, saturn_fixed, start, end, 1.days(), None)
+        .unwrap();
+    println!("{earth_crossings:?}");
+    assert_eq!(earth_crossings.len(), 1);
+    assert_eq!(earth_crossings[0].direction, CrossingDirection::Falling);
+    let expected = Epoch::from_str("2025-03-23 12:00:00 TDB").unwrap();
+    assert!((earth_crossings[0].epoch - expected).abs() < 3.days());
+
+    let sun_crossings = almanac
+        .ring_plane_crossings(SUN_J2000, saturn_fixed, start, end, 1.days(), None)
+        .unwrap();
+    println!("{sun_crossings:?}");
+    assert_eq!(sun_crossings.len(), 1);
+    assert_eq!(sun_crossings[0].direction, CrossingDirection::Falling);
+    let expected = Epoch::from_str("2025-05-06 12:00:00 TDB").unwrap();
+    assert!((sun_crossings[0].epoch - expected).abs() < 3.days());
+
+    // The elevation is zero at the crossing
+    let elevation_deg = almanac
+        .equator_plane_elevation_deg(SUN_J2000, saturn_fixed, sun_crossings[0].epoch, None)
+        .unwrap();
+    assert!(elevation_deg.abs() < 1e-6);
+}
+
+#[test]
+fn test_moon_apsis_and_node_crossings() {
+    use anise::almanac::events::CrossingDirection;
+    use anise::constants::frames::{EARTH_ECLIPJ2000, MOON_J2000};
+    use hifitime::TimeUnits;
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+
+    let start = Epoch::from_str("2025-01-01 00:00:00 TDB").unwrap();
+    let end = start + 60.days();
+
+    let apsides = almanac
+        .apsis_crossings(MOON_J2000, EARTH_J2000, start, end, 1.days(), None)
+        .unwrap();
+    // About two perigees and two apogees in 60 days (anomalistic month of 27.55 days)
+    assert!(apsides.len() >= 4 && apsides.len() <= 5, "{apsides:?}");
+
+    let distance_km = |epoch| {
+        almanac
+            .transform(MOON_J2000, EARTH_J2000, epoch, None)
+            .unwrap()
+            .rmag_km()
+    };
+
+    for apsis in &apsides {
+        let before = distance_km(apsis.epoch - 1.minutes());
+        let at = distance_km(apsis.epoch);
+        let after = distance_km(apsis.epoch + 1.minutes());
+        match apsis.direction {
+            CrossingDirection::Rising => assert!(at < before && at < after, "perigee {apsis}"),
+            CrossingDirection::Falling => assert!(at > before && at > after, "apogee {apsis}"),
+        }
+    }
+
+    let nodes = almanac
+        .node_crossings(MOON_J2000, EARTH_ECLIPJ2000, start, end, 1.days(), None)
+        .unwrap();
+    // About two ascending and two descending nodes in 60 days (draconic month of 27.21 days), alternating
+    assert!(nodes.len() >= 4 && nodes.len() <= 5, "{nodes:?}");
+    for pair in nodes.windows(2) {
+        assert_ne!(pair[0].direction, pair[1].direction);
+    }
+
+    for node in &nodes {
+        let state = almanac
+            .transform(MOON_J2000, EARTH_ECLIPJ2000, node.epoch, None)
+            .unwrap();
+        assert!(
+            state.radius_km.z.abs() < 1e-2,
+            "{node}: {}",
+            state.radius_km.z
+        );
+        match node.direction {
+            CrossingDirection::Rising => assert!(state.velocity_km_s.z > 0.0),
+            CrossingDirection::Falling => assert!(state.velocity_km_s.z < 0.0),
+        }
+    }
+}
+
+#[test]
+fn test_constant_overrides() {
+    use anise::constants::{registry::GM_EARTH, SPEED_OF_LIGHT_KM_S};
+
+    let almanac = Almanac::default();
+    assert_eq!(
+        almanac.constant("SPEED_OF_LIGHT").unwrap(),
+        SPEED_OF_LIGHT_KM_S
+    );
+    assert!(almanac.constant("NOT_A_CONSTANT").is_err());
+
+    let overridden = almanac.with_constant("gm_earth", 398_600.4418).unwrap();
+    assert_eq!(overridden.constant("GM_EARTH").unwrap(), 398_600.4418);
+    // Overriding twice keeps the last value, and the original Almanac is unchanged
+    let overridden = overridden.with_constant("GM_EARTH", 398_600.0).unwrap();
+    assert_eq!(overridden.constant("GM_EARTH").unwrap(), 398_600.0);
+    assert_eq!(overridden.planetary_overrides().len(), 1);
+    assert!(overridden.constant_overrides.is_empty());
+    assert_eq!(almanac.constant("GM_EARTH").unwrap(), GM_EARTH.value);
+
+    assert!(almanac.with_constant("NOT_A_CONSTANT", 1.0).is_err());
+}
+
+#[test]
+fn test_speed_of_light_override() {
+    use anise::constants::frames::MOON_J2000;
+
+    let almanac = Almanac::new("../data/de440s.bsp").unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+
+    let geometric = almanac
+        .translate(MOON_J2000, EARTH_J2000, epoch, None)
+        .unwrap();
+    let apparent = almanac
+        .translate(MOON_J2000, EARTH_J2000, epoch, Aberration::LT)
+        .unwrap();
+    assert!((apparent.radius_km - geometric.radius_km).norm() > 1e-3);
+
+    // The light time corrections use the speed of light of the Almanac, so they vanish as it goes to infinity.
+    let instantaneous = almanac
+        .with_constant("SPEED_OF_LIGHT", 1e15)
+        .unwrap()
+        .translate(MOON_J2000, EARTH_J2000, epoch, Aberration::LT)
+        .unwrap();
+    assert!((instantaneous.radius_km - geometric.radius_km).norm() < 1e-6);
+}
+
+#[test]
+fn test_unload_and_memory_usage() {
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/earth_latest_high_prec.bpc")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+
+    let usage = almanac.memory_usage();
+    for loaded in &usage {
+        println!("{loaded}");
+    }
+    assert_eq!(usage.len(), 3);
+    assert_eq!(usage[0].kind, "SPK");
+    assert_eq!(
+        usage[0].bytes as u64,
+        std::fs::metadata("../data/de440s.bsp").unwrap().len()
+    );
+    assert_eq!(usage[1].kind, "BPC");
+    assert_eq!(usage[2].kind, "planetary data");
+    assert_eq!(
+        almanac.total_memory_usage(),
+        usage.iter().map(|loaded| loaded.bytes).sum::<usize>()
+    );
+
+    // Unload the BPC by its path, and the SPK by its handle
+    let without_bpc = almanac
+        .unload("../data/earth_latest_high_prec.bpc")
+        .unwrap();
+    assert_eq!(without_bpc.num_loaded_bpc(), 0);
+    assert_eq!(without_bpc.num_loaded_spk(), 1);
+    assert!(without_bpc
+        .unload("../data/earth_latest_high_prec.bpc")
+        .is_err());
+
+    let without_spk = without_bpc.unload_by_crc32(usage[0].crc32).unwrap();
+    assert_eq!(without_spk.num_loaded_spk(), 0);
+    assert_eq!(without_spk.memory_usage().len(), 1);
+
+    // The original Almanac is unchanged
+    assert_eq!(almanac.memory_usage(), usage);
+
+    // Lazily loaded kernels are memory mapped, so they are listed but not counted as held in memory
+    let lazy = almanac.load_lazy("../data/gmat-hermite.bsp").unwrap();
+    let lazy_usage = lazy.memory_usage();
+    assert_eq!(lazy_usage.len(), 4);
+    assert!(lazy_usage[1].memory_mapped);
+    assert!(lazy_usage
+        .iter()
+        .enumerate()
+        .all(|(idx, loaded)| loaded.memory_mapped == (idx == 1)));
+    assert_eq!(lazy.total_memory_usage(), almanac.total_memory_usage());
+    // And they are unloaded by their path
+    let unloaded = lazy.unload("../data/gmat-hermite.bsp").unwrap();
+    assert_eq!(unloaded.memory_usage(), usage);
+}
+
+#[test]
+fn test_adaptive_sampling() {
+    use anise::constants::frames::MOON_J2000;
+    use hifitime::TimeUnits;
+
+    let almanac = Almanac::new("../data/de440s.bsp").unwrap();
+    let start = Epoch::from_str("2025-01-01 00:00:00 TDB").unwrap();
+    let end = start + 30.days();
+    let tolerance_km = 10.0;
+
+    let samples = almanac
+        .sample(MOON_J2000, EARTH_J2000, start, end, tolerance_km, None)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    println!("{} samples", samples.len());
+    assert_eq!(samples.first().unwrap().epoch, start);
+    assert_eq!(samples.last().unwrap().epoch, end);
+    // Fewer samples than an hourly sampling
+    assert!(samples.len() < 30 * 24);
+
+    for pair in samples.windows(2) {
+        assert!(pair[1].epoch > pair[0].epoch);
+        // The linear interpolation between two samples is within the tolerance (with margin between the checked points)
+        for fraction in [0.1, 0.5, 0.9] {
+            let epoch = pair[0].epoch + (pair[1].epoch - pair[0].epoch) * fraction;
+            let actual = almanac
+                .transform(MOON_J2000, EARTH_J2000, epoch, None)
+                .unwrap();
+            let interpolated =
+                pair[0].radius_km + (pair[1].radius_km - pair[0].radius_km) * fraction;
+            assert!((actual.radius_km - interpolated).norm() < 1.5 * tolerance_km);
+        }
+    }
+}
+
+#[test]
+fn test_extrapolation() {
+    use anise::prelude::Frame;
+    use hifitime::TimeUnits;
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/gmat-hermite.bsp")
+        .unwrap();
+
+    let sc_j2k = Frame::from_ephem_j2000(-10000001);
+    let (start, end) = almanac.spk_domain(-10000001).unwrap();
+
+    // Within the data, this is the usual transform
+    let mid = start + (end - start) * 0.5;
+    let within = almanac
+        .transform_extrapolated(sc_j2k, EARTH_J2000, mid, None, 1.minutes())
+        .unwrap();
+    assert!(!within.is_extrapolated());
+    assert_eq!(
+        within.state,
+        almanac.transform(sc_j2k, EARTH_J2000, mid, None).unwrap()
+    );
+
+    // Ten seconds past the end of the data, the state has moved along the velocity at the end of the data
+    let at_end = almanac.transform(sc_j2k, EARTH_J2000, end, None).unwrap();
+    let extrapolated = almanac
+        .transform_extrapolated(sc_j2k, EARTH_J2000, end + 10.seconds(), None, 1.minutes())
+        .unwrap();
+    println!("{extrapolated}");
+    assert!(extrapolated.is_extrapolated());
+    assert_eq!(extrapolated.extrapolation, 10.seconds());
+    assert_eq!(extrapolated.state.epoch, end + 10.seconds());
+    let displacement = extrapolated.state.radius_km - at_end.radius_km;
+    assert!((displacement - at_end.velocity_km_s * 10.0).norm() < 1e-2 * displacement.norm());
+
+    let before = almanac
+        .transform_extrapolated(sc_j2k, EARTH_J2000, start - 5.seconds(), None, 1.minutes())
+        .unwrap();
+    assert_eq!(before.extrapolation, -5.seconds());
+
+    // Beyond the maximum extrapolation
+    assert!(almanac
+        .transform_extrapolated(sc_j2k, EARTH_J2000, end + 2.minutes(), None, 1.minutes())
+        .is_err());
+}
+
+#[cfg(feature = "signing")]
+#[test]
+fn test_load_verified() {
+    use anise::structure::signature::{sign_file, SigningKey};
+
+    let path = std::env::temp_dir().join("anise-test-signed-pck08.pca");
+    let path = path.to_str().unwrap();
+    std::fs::copy("../data/pck08.pca", path).unwrap();
+
+    let key = SigningKey::from_bytes(&[42; 32]);
+    let untrusted_key = SigningKey::from_bytes(&[43; 32]);
+
+    // Not signed yet
+    assert!(Almanac::default()
+        .load_verified(path, &key.verifying_key())
+        .is_err());
+
+    // The signature is stored in the file, which still loads normally.
+    let unsigned_len = std::fs::metadata(path).unwrap().len();
+    sign_file(path, &key).unwrap();
+    assert!(std::fs::metadata(path).unwrap().len() > unsigned_len);
+    assert!(!Almanac::new(path).unwrap().planetary_data.data.is_empty());
+
+    let almanac = Almanac::default()
+        .load_verified(path, &key.verifying_key())
+        .unwrap();
+    assert!(!almanac.planetary_data.data.is_empty());
+
+    assert!(Almanac::default()
+        .load_verified(path, &untrusted_key.verifying_key())
+        .is_err());
+
+    // Tampering with the file invalidates the signature
+    let mut bytes = std::fs::read(path).unwrap();
+    bytes[100] ^= 1;
+    std::fs::write(path, bytes).unwrap();
+    assert!(Almanac::default()
+        .load_verified(path, &key.verifying_key())
+        .is_err());
+
+    // NAIF kernels cannot be signed
+    assert!(sign_file("../data/de440s.bsp", &key).is_err());
+}
+
+#[test]
+fn test_transform_partials() {
+    use anise::constants::frames::MOON_J2000;
+    use hifitime::TimeUnits;
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap()
+        .load("../data/earth_latest_high_prec.bpc")
+        .unwrap();
+
+    let epoch = Epoch::from_str("2021-10-29 12:34:56 TDB").unwrap();
+
+    let partials = almanac
+        .transform_partials(MOON_J2000, EARTH_J2000, epoch, None)
+        .unwrap();
+    let mu_km3_s2 = almanac
+        .frame_from_uid(EARTH_J2000)
+        .unwrap()
+        .mu_km3_s2()
+        .unwrap();
+    assert_eq!(partials.dstate_dgm.fixed_rows::<3>(0).norm(), 0.0);
+    assert!(
+        (partials.dstate_dgm.fixed_rows::<3>(3) - partials.state.velocity_km_s / (2.0 * mu_km3_s2))
+            .norm()
+            < 1e-15
+    );
+    // Not an Earth-fixed frame
+    assert!(partials.dstate_deop.is_none());
+
+    let partials = almanac
+        .transform_partials(MOON_J2000, EARTH_ITRF93, epoch, None)
+        .unwrap();
+    let deop = partials.dstate_deop.unwrap();
+
+    // Advancing UT1 by one second is the same as letting the Earth rotate for one second.
+    let moon_j2k = almanac
+        .transform(MOON_J2000, EARTH_J2000, epoch, None)
+        .unwrap();
+    let dcm = almanac.rotate(EARTH_J2000, EARTH_ITRF93, epoch).unwrap();
+    let dcm_later = almanac
+        .rotate(EARTH_J2000, EARTH_ITRF93, epoch + 1.seconds())
+        .unwrap();
+    let expected = (dcm_later.rot_mat - dcm.rot_mat) * moon_j2k.radius_km;
+    let ut1_partial = deop.fixed_view::<3, 1>(0, 2);
+    println!("expected: {expected}\ngot: {ut1_partial}");
+    assert!((ut1_partial - expected).norm() < 1e-3 * expected.norm());
+
+    // A small rotation does not change the component of a vector along its axis.
+    assert_eq!(deop[(2, 2)], 0.0);
+    assert_eq!(deop[(1, 0)], 0.0);
+    assert_eq!(deop[(0, 1)], 0.0);
+}
+
+#[test]
+fn test_federated_almanac() {
+    use anise::almanac::federated::FederatedAlmanac;
+    use anise::constants::frames::MOON_J2000;
+    use hifitime::TimeUnits;
+
+    let orientations = Almanac::new("../data/pck08.pca")
+        .unwrap()
+        .load("../data/earth_latest_high_prec.bpc")
+        .unwrap();
+    let ephemerides = Almanac::new("../data/de440s.bsp").unwrap();
+
+    let federated = FederatedAlmanac::new(vec![orientations.clone()]).with_fallback(ephemerides);
+
+    let epoch = Epoch::from_str("2021-10-29 12:34:56 TDB").unwrap();
+
+    // Served by the second Almanac only
+    federated
+        .translate(MOON_J2000, EARTH_J2000, epoch, None)
+        .unwrap();
+    assert!(federated.spk_domain(301).is_ok());
+    // Served by the first Almanac only
+    federated.rotate(EARTH_J2000, EARTH_ITRF93, epoch).unwrap();
+    federated.frame_from_uid(EARTH_J2000).unwrap();
+
+    // Requires the data of both Almanacs, so it is served by their merge
+    assert!(orientations
+        .transform(MOON_J2000, EARTH_ITRF93, epoch, None)
+        .is_err());
+    let merged = federated.merged().unwrap();
+    assert_eq!(federated.almanacs().len(), 2);
+    assert_eq!(merged.num_loaded_spk(), 1);
+    assert_eq!(merged.num_loaded_bpc(), 1);
+    assert_eq!(
+        merged.planetary_data.len(),
+        orientations.planetary_data.len()
+    );
+    assert_eq!(
+        federated
+            .transform(MOON_J2000, EARTH_ITRF93, epoch, None)
+            .unwrap(),
+        merged
+            .transform(MOON_J2000, EARTH_ITRF93, epoch, None)
+            .unwrap()
+    );
+    // The merge is cached, so the following queries served by the merge return the same results
+    assert_eq!(
+        federated
+            .transform(MOON_J2000, EARTH_ITRF93, epoch + 1.0_f64.seconds(), None)
+            .unwrap(),
+        merged
+            .transform(MOON_J2000, EARTH_ITRF93, epoch + 1.0_f64.seconds(), None)
+            .unwrap()
+    );
+
+    // Data sets are merged per entry, and the entries of the first Almanac have precedence
+    let mut moon = orientations.planetary_data.get_by_id(301).unwrap();
+    moon.mu_km3_s2 = 4902.0;
+    let mut overridden = orientations.planetary_data.clone();
+    overridden.set_by_id(301, moon).unwrap();
+    let federated = FederatedAlmanac::new(vec![Almanac::default().with_planetary_data(overridden)])
+        .with_fallback(orientations.clone());
+    let merged = federated.merged().unwrap();
+    assert_eq!(
+        merged.planetary_data.get_by_id(301).unwrap().mu_km3_s2,
+        4902.0
+    );
+    assert_eq!(
+        merged.planetary_data.get_by_id(399).unwrap(),
+        orientations.planetary_data.get_by_id(399).unwrap()
+    );
+
+    // Nothing loaded
+    assert!(FederatedAlmanac::default()
+        .translate(MOON_J2000, EARTH_J2000, epoch, None)
+        .is_err());
+}
+
+#[test]
+fn test_sclk_conversions() {
+    use bytes::Bytes;
+    use hifitime::{TimeScale, TimeUnits};
+
+    let kernel = "KPL/SCLK
+
+\\begindata
+
+SCLK_DATA_TYPE_77        = ( 1 )
+SCLK01_TIME_SYSTEM_77    = ( 2 )
+SCLK01_N_FIELDS_77       = ( 2 )
+SCLK01_MODULI_77         = ( 4294967296 65536 )
+SCLK01_OFFSETS_77        = ( 0 0 )
+SCLK01_OUTPUT_DELIM_77   = ( 2 )
+
+SCLK_PARTITION_START_77  = ( 0.0000000000000E+00 )
+SCLK_PARTITION_END_77    = ( 2.8147497671065E+14 )
+
+SCLK01_COEFFICIENTS_77   = ( 0.0000000000000E+00  7.8901920000000E+08  1.0000000000000E+00 )
+
+\\begintext
+";
+
+    let almanac = Almanac::default()
+        .load_from_bytes(Bytes::from(kernel))
+        .unwrap();
+    assert_eq!(almanac.sclk_data.len(), 1);
+
+    // SCLK kernels are keyed by the positive clock ID, which is the opposite of the spacecraft ID.
+    let start = almanac.sclk_to_epoch(-77, "1/0:0").unwrap();
+    assert_eq!(
+        start,
+        Epoch::from_gregorian_hms(2000, 1, 1, 12, 0, 0, TimeScale::TT)
+            + 7.8901920000000E+08.seconds()
+    );
+
+    let epoch = start + 1.hours() + 0.25.seconds();
+    let sclk = almanac.epoch_to_sclk(-77, epoch).unwrap();
+    assert_eq!(sclk, "1/0000003600:16384");
+    assert_eq!(almanac.sclk_to_epoch(-77, &sclk).unwrap(), epoch);
+
+    // Unknown spacecraft
+    assert!(almanac.sclk_to_epoch(-78, "1/0:0").is_err());
+    // Reloading the kernel replaces the clock
+    let reloaded = almanac.load_from_bytes(Bytes::from(kernel)).unwrap();
+    assert_eq!(reloaded.sclk_data.len(), 1);
+}
+
+#[test]
+fn test_query_cache() {
+    use anise::constants::frames::{IAU_EARTH_FRAME, MOON_J2000};
+    use hifitime::TimeUnits;
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap()
+        .with_cache(1.microseconds());
+
+    let epoch = Epoch::from_str("2021-10-29 12:34:56 TDB").unwrap();
+
+    let state = almanac
+        .transform(MOON_J2000, EARTH_J2000, epoch, None)
+        .unwrap();
+    let cache = almanac.cache.as_ref().unwrap();
+    assert_eq!(cache.hits(), 0);
+
+    // Within the tolerance, the cached state is returned as is
+    assert_eq!(
+        almanac
+            .transform(MOON_J2000, EARTH_J2000, epoch + 500.nanoseconds(), None)
+            .unwrap(),
+        state
+    );
+    assert_eq!(cache.hits(), 1);
+
+    // Outside of the tolerance, or with another aberration correction, the state is computed
+    let later = almanac
+        .transform(MOON_J2000, EARTH_J2000, epoch + 2.microseconds(), None)
+        .unwrap();
+    assert_ne!(later.epoch, state.epoch);
+    assert_ne!(
+        almanac
+            .transform(MOON_J2000, EARTH_J2000, epoch, Aberration::LT)
+            .unwrap(),
+        state
+    );
+
+    // Rotations are cached too
+    let hits = cache.hits();
+    let dcm = almanac.rotate(EARTH_J2000, IAU_EARTH_FRAME, epoch).unwrap();
+    assert_eq!(
+        almanac.rotate(EARTH_J2000, IAU_EARTH_FRAME, epoch).unwrap(),
+        dcm
+    );
+    assert_eq!(cache.hits(), hits + 1);
+
+    // Almanacs derived from this one start with an empty cache
+    let reloaded = almanac.load("../data/pck08.pca").unwrap();
+    assert!(reloaded.cache.as_ref().unwrap().is_empty());
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.hits(), 0);
+}
+
+#[test]
+fn test_aberrated_state() {
+    use anise::constants::frames::{MOON_J2000, SSB_J2000};
+
+    let almanac = Almanac::new("../data/de440s.bsp").unwrap();
+    let epoch = Epoch::from_str("2021-10-29 12:34:56 TDB").unwrap();
+
+    // The pipeline of translate, applied to the state of the observer with respect to the SSB
+    let earth_ssb = almanac
+        .translate(EARTH_J2000, SSB_J2000, epoch, None)
+        .unwrap();
+
+    for ab_corr in [Aberration::LT_S, Aberration::CN_S, Aberration::XCN_S] {
+        let expected = almanac
+            .translate(MOON_J2000, EARTH_J2000, epoch, ab_corr)
+            .unwrap();
+        let state = almanac
+            .aberrated_state(MOON_J2000, earth_ssb, ab_corr.unwrap())
+            .unwrap();
+        assert_eq!(state.radius_km, expected.radius_km);
+        assert_eq!(state.velocity_km_s, expected.velocity_km_s);
+        assert_eq!(state.epoch, epoch);
+    }
+
+    // An observer at the target has no direction to aberrate
+    let moon_ssb = almanac
+        .translate(MOON_J2000, SSB_J2000, epoch, None)
+        .unwrap();
+    let state = almanac
+        .aberrated_state(MOON_J2000, moon_ssb, Aberration::LT_S.unwrap())
+        .unwrap();
+    assert!(state.radius_km.norm() < 1e-9);
+}
+
+#[test]
+fn test_light_time_convergence() {
+    use anise::astro::LightTimeConvergence;
+    use anise::constants::frames::MARS_BARYCENTER_J2000;
+
+    let almanac = Almanac::new("../data/de440s.bsp").unwrap();
+    let epoch = Epoch::from_str("2021-10-29 12:34:56 TDB").unwrap();
+
+    // The default convergence matches translate
+    let expected = almanac
+        .translate(MARS_BARYCENTER_J2000, EARTH_J2000, epoch, Aberration::CN_S)
+        .unwrap();
+    let solution = almanac
+        .translate_light_time(
+            MARS_BARYCENTER_J2000,
+            EARTH_J2000,
+            epoch,
+            Aberration::CN_S.unwrap(),
+            LightTimeConvergence::default(),
+        )
+        .unwrap();
+    assert_eq!(solution.state, expected);
+    assert_eq!(solution.iterations, 3);
+    assert!((solution.light_time.to_seconds() - expected.light_time().to_seconds()).abs() < 1e-6);
+
+    // Unconverged light time is a single iteration regardless of the criteria
+    let solution = almanac
+        .translate_light_time(
+            MARS_BARYCENTER_J2000,
+            EARTH_J2000,
+            epoch,
+            Aberration::LT.unwrap(),
+            LightTimeConvergence::SPICE,
+        )
+        .unwrap();
+    assert_eq!(solution.iterations, 1);
+    assert_eq!(
+        solution.state,
+        almanac
+            .translate(MARS_BARYCENTER_J2000, EARTH_J2000, epoch, Aberration::LT)
+            .unwrap()
+    );
+    // Mars is several light minutes away, so a single iteration changes the light time significantly.
+    assert!(solution.achieved_tolerance > 1e-9);
+
+    // Iterations stop once the requested tolerance is reached
+    let convergence = LightTimeConvergence {
+        max_iterations: 10,
+        tolerance: 1e-14,
+    };
+    let solution = almanac
+        .translate_light_time(
+            MARS_BARYCENTER_J2000,
+            EARTH_J2000,
+            epoch,
+            Aberration::CN.unwrap(),
+            convergence,
+        )
+        .unwrap();
+    assert!(solution.iterations < convergence.max_iterations);
+    assert!(solution.achieved_tolerance <= convergence.tolerance);
+}
+
+#[test]
+fn test_state_of_named() {
+    use anise::constants::celestial_objects::MOON;
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+
+    let named = almanac
+        .state_of_named(
+            "MOON",
+            "earth",
+            "2023-11-05 12:00:00 UTC",
+            "EME2000",
+            Aberration::LT,
+        )
+        .unwrap();
+
+    let epoch = Epoch::from_str("2023-11-05 12:00:00 UTC").unwrap();
+    let expected = almanac
+        .state_of(MOON, EARTH_J2000, epoch, Aberration::LT)
+        .unwrap();
+    assert_eq!(named.state, expected);
+    assert_eq!(named.target, "MOON");
+    println!("{named}");
+
+    // Numeric IDs and frame names are accepted
+    let named = almanac
+        .state_of_named("301", "399", "2023-11-05 12:00:00 UTC", "J2000", None)
+        .unwrap();
+    assert_eq!(
+        named.state,
+        almanac.state_of(MOON, EARTH_J2000, epoch, None).unwrap()
+    );
+
+    assert!(almanac
+        .state_of_named("MOON", "EARTH", "not an epoch", "EME2000", None)
+        .is_err());
+    assert!(almanac
+        .state_of_named(
+            "MOON",
+            "EARTH",
+            "2023-11-05 12:00:00 UTC",
+            "NOT_A_FRAME",
+            None
+        )
+        .is_err());
+    assert!(almanac
+        .state_of_named("MY_SC", "EARTH", "2023-11-05 12:00:00 UTC", "EME2000", None)
+        .is_err());
+}
+
+#[test]
+fn test_recenter() {
+    use anise::constants::celestial_objects::SOLAR_SYSTEM_BARYCENTER;
+    use anise::constants::frames::{MOON_J2000, SSB_J2000};
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+    let epoch = Epoch::from_str("2021-10-29 12:34:56 TDB").unwrap();
+
+    let moon_earth = almanac
+        .translate(MOON_J2000, EARTH_J2000, epoch, None)
+        .unwrap();
+    let moon_ssb = moon_earth
+        .recenter(SOLAR_SYSTEM_BARYCENTER, &almanac)
+        .unwrap();
+
+    let expected = almanac
+        .translate(MOON_J2000, SSB_J2000, epoch, None)
+        .unwrap();
+    assert!((moon_ssb.radius_km - expected.radius_km).norm() < 1e-6);
+    assert!((moon_ssb.velocity_km_s - expected.velocity_km_s).norm() < 1e-9);
+    assert_eq!(moon_ssb.frame.ephemeris_id, SOLAR_SYSTEM_BARYCENTER);
+    assert_eq!(
+        moon_ssb.frame.orientation_id,
+        moon_earth.frame.orientation_id
+    );
+    assert_eq!(moon_ssb.epoch, epoch);
+
+    // Recentering back onto the Earth updates the frame data from the Almanac
+    let moon_earth_again = moon_ssb
+        .recenter(EARTH_J2000.ephemeris_id, &almanac)
+        .unwrap();
+    assert!((moon_earth_again.radius_km - moon_earth.radius_km).norm() < 1e-6);
+    assert_eq!(
+        moon_earth_again.frame.mu_km3_s2,
+        almanac.frame_from_uid(EARTH_J2000).unwrap().mu_km3_s2
+    );
+}
+
+#[test]
+fn test_site_kinematics() {
+    use hifitime::TimeUnits;
+
+    let almanac = Almanac::new("../data/pck08.pca")
+        .unwrap()
+        .load("../data/earth_latest_high_prec.bpc")
+        .unwrap();
+
+    let epoch = Epoch::from_str("2021-10-29 12:34:56 TDB").unwrap();
+    // Goldstone, approximately
+    let (lat_deg, long_deg, height_km) = (35.4, 243.1, 1.0);
+
+    let site = almanac
+        .site_kinematics(
+            lat_deg,
+            long_deg,
+            height_km,
+            EARTH_ITRF93,
+            EARTH_J2000,
+            epoch,
+        )
+        .unwrap();
+
+    // Matches the transformation of the body fixed state of the site
+    let itrf93 = almanac.frame_from_uid(EARTH_ITRF93).unwrap();
+    let site_bf = Orbit::try_latlongalt(lat_deg, long_deg, height_km, 0.0, epoch, itrf93).unwrap();
+    let expected = almanac.rotate_to(site_bf, EARTH_J2000).unwrap();
+    assert!((site.state.radius_km - expected.radius_km).norm() < 1e-9);
+    assert!((site.state.velocity_km_s - expected.velocity_km_s).norm() < 1e-12);
+    // Earth rotates at about 465 m/s at the equator
+    assert!((site.state.velocity_km_s.norm() - 0.465 * lat_deg.to_radians().cos()).abs() < 1e-3);
+
+    // The acceleration matches the finite difference of the velocity
+    let step = 1.seconds();
+    let before = almanac
+        .site_kinematics(
+            lat_deg,
+            long_deg,
+            height_km,
+            EARTH_ITRF93,
+            EARTH_J2000,
+            epoch - step,
+        )
+        .unwrap();
+    let after = almanac
+        .site_kinematics(
+            lat_deg,
+            long_deg,
+            height_km,
+            EARTH_ITRF93,
+            EARTH_J2000,
+            epoch + step,
+        )
+        .unwrap();
+    let fd_acc_km_s2 = (after.state.velocity_km_s - before.state.velocity_km_s) / 2.0;
+    assert!(
+        (site.acceleration_km_s2 - fd_acc_km_s2).norm() < 1e-4 * site.acceleration_km_s2.norm(),
+        "{} != {}",
+        site.acceleration_km_s2,
+        fd_acc_km_s2
+    );
+}
+
+#[test]
+fn test_euler_angles_series() {
+    use anise::math::rotation::EulerSequence;
+    use hifitime::TimeUnits;
+
+    let almanac = Almanac::new("../data/pck08.pca")
+        .unwrap()
+        .load("../data/earth_latest_high_prec.bpc")
+        .unwrap();
+
+    let start = Epoch::from_str("2021-10-29 12:34:56 TDB").unwrap();
+    let series = almanac
+        .euler_angles_series(
+            EARTH_J2000,
+            EARTH_ITRF93,
+            start,
+            start + 1.days(),
+            1.hours(),
+            EulerSequence::ZYX,
+        )
+        .unwrap();
+    assert_eq!(series.len(), 25);
+
+    for (prev, row) in series.iter().zip(series.iter().skip(1)) {
+        // The Earth rotates by about 15 degrees per hour, and the unwrapped angle keeps increasing.
+        let delta_deg = row.angles_deg[0] - prev.angles_deg[0];
+        assert!((delta_deg - 15.04).abs() < 0.01, "{delta_deg}");
+        // The polar motion and precession are small.
+        assert!(row.angles_deg[1].abs() < 0.5);
+        assert!(row.angles_deg[2].abs() < 0.5);
+
+        let rates_deg_s = row.rates_deg_s.unwrap();
+        assert!((rates_deg_s[0] - 0.004178).abs() < 1e-5, "{rates_deg_s}");
+    }
+
+    // The angles match the rotation
+    let dcm = almanac.rotate(EARTH_J2000, EARTH_ITRF93, start).unwrap();
+    let angles_rad = series[0].angles_deg.map(|angle| angle.to_radians());
+    assert!((EulerSequence::ZYX.rotation_matrix(angles_rad) - dcm.rot_mat).norm() < 1e-12);
+
+    #[cfg(feature = "export")]
+    {
+        use anise::almanac::export::export_euler_angles;
+        assert_eq!(
+            export_euler_angles(&series, "../target/euler-angles.parquet").unwrap(),
+            25
+        );
+    }
+}
+
+#[test]
+fn test_custom_providers() {
+    use anise::constants::frames::MOON_J2000;
+    use anise::constants::orientations::J2000;
+    use anise::ephemerides::{provider::EphemerisProvider, EphemerisError};
+    use anise::math::rotation::{r3, r3_dot, DCM};
+    use anise::math::Vector3;
+    use anise::orientations::{OrientationError, OrientationProvider};
+    use anise::prelude::Frame;
+    use hifitime::TimeUnits;
+    use std::sync::Arc;
+
+    const SC_ID: i32 = -1_001;
+    const SC_BODY_ID: i32 = -1_001_000;
+    const RADIUS_KM: f64 = 7_000.0;
+    const RATE_RAD_S: f64 = 1e-3;
+
+    /// Circular equatorial orbit about the Earth, like a propagator would provide.
+    struct CircularOrbit {
+        start: Epoch,
+    }
+
+    impl EphemerisProvider for CircularOrbit {
+        fn target_id(&self) -> i32 {
+            SC_ID
+        }
+        fn center_id(&self) -> i32 {
+            EARTH_J2000.ephemeris_id
+        }
+        fn covers(&self, epoch: Epoch) -> bool {
+            epoch >= self.start && epoch <= self.start + 1.days()
+        }
+        fn position_velocity(&self, epoch: Epoch) -> Result<(Vector3, Vector3), EphemerisError> {
+            let angle = (epoch - self.start).to_seconds() * RATE_RAD_S;
+            Ok((
+                RADIUS_KM * Vector3::new(angle.cos(), angle.sin(), 0.0),
+                RADIUS_KM * RATE_RAD_S * Vector3::new(-angle.sin(), angle.cos(), 0.0),
+            ))
+        }
+    }
+
+    /// Spacecraft body frame spinning about the J2000 Z axis.
+    struct Spinner {
+        start: Epoch,
+    }
+
+    impl OrientationProvider for Spinner {
+        fn orientation_id(&self) -> i32 {
+            SC_BODY_ID
+        }
+        fn parent_id(&self) -> i32 {
+            J2000
+        }
+        fn covers(&self, epoch: Epoch) -> bool {
+            epoch >= self.start
+        }
+        fn rotation(&self, epoch: Epoch) -> Result<DCM, OrientationError> {
+            let angle = (epoch - self.start).to_seconds() * RATE_RAD_S;
+            Ok(DCM {
+                rot_mat: r3(angle),
+                rot_mat_dt: Some(RATE_RAD_S * r3_dot(angle)),
+                from: J2000,
+                to: SC_BODY_ID,
+            })
+        }
+    }
+
+    let start = Epoch::from_str("2021-10-29 12:34:56 TDB").unwrap();
+    let sc_j2000 = Frame::new(SC_ID, J2000);
+    let sc_body = Frame::new(SC_ID, SC_BODY_ID);
+
+    // The providers work on their own.
+    let providers_only = Almanac::default()
+        .with_ephemeris_provider(Arc::new(CircularOrbit { start }))
+        .with_orientation_provider(Arc::new(Spinner { start }));
+
+    let epoch = start + 10.minutes();
+    let state = providers_only
+        .translate(sc_j2000, EARTH_J2000, epoch, None)
+        .unwrap();
+    assert!((state.rmag_km() - RADIUS_KM).abs() < 1e-9);
+    assert!((state.vmag_km_s() - RADIUS_KM * RATE_RAD_S).abs() < 1e-12);
+
+    // In the body frame, the spacecraft stays on the X axis.
+    let dcm = providers_only.rotate(EARTH_J2000, sc_body, epoch).unwrap();
+    let radius_body = dcm.rot_mat * state.radius_km;
+    assert!((radius_body - Vector3::new(RADIUS_KM, 0.0, 0.0)).norm() < 1e-9);
+
+    // And they are used like segments along with the SPK files.
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .with_ephemeris_provider(Arc::new(CircularOrbit { start }));
+
+    let sc_wrt_moon = almanac
+        .translate(sc_j2000, MOON_J2000, epoch, None)
+        .unwrap();
+    let earth_wrt_moon = almanac
+        .translate(EARTH_J2000, MOON_J2000, epoch, None)
+        .unwrap();
+    assert!((sc_wrt_moon.radius_km - earth_wrt_moon.radius_km - state.radius_km).norm() < 1e-6);
+    assert!(
+        (sc_wrt_moon.velocity_km_s - earth_wrt_moon.velocity_km_s - state.velocity_km_s).norm()
+            < 1e-9
+    );
+
+    // Outside of the coverage of the provider, there is no data.
+    assert!(almanac
+        .translate(sc_j2000, MOON_J2000, start + 2.days(), None)
+        .is_err());
+}
+
+#[test]
+fn test_kernel_pool() {
+    let almanac = Almanac::default()
+        .load("../data/pck00011.tpc")
+        .unwrap()
+        .load("../data/gm_de440.tpc")
+        .unwrap();
+
+    let pool = &almanac.kernel_pool;
+    assert_eq!(
+        pool.get_f64s("BODY399_RADII").unwrap(),
+        &[6378.1366, 6378.1366, 6356.7519]
+    );
+    assert_eq!(pool.get_f64("BODY399_GM"), Some(3.986_004_355_070_226_6e5));
+    assert_eq!(pool.get_f64s("BODY399_PM").unwrap().len(), 3);
+    assert_eq!(pool.get_i32s("BODY399_PM"), None);
+
+    // Text kernels only fill the kernel pool: they must be converted to be used as planetary constants.
+    assert!(almanac.frame_from_uid(EARTH_J2000).is_err());
+}
+
+#[test]
+fn test_strict_lsk() {
+    // An LSK published before the leap second of 2017 was announced.
+    let lsk = b"KPL/LSK
+
+\\begindata
+
+DELTET/DELTA_T_A       =   32.184
+DELTET/K               =    1.657D-3
+DELTET/EB              =    1.671D-2
+DELTET/M               = (  6.239996D0   1.99096871D-7 )
+
+DELTET/DELTA_AT        = ( 10,   @1972-JAN-1
+                           35,   @2012-JUL-1
+                           36,   @2015-JUL-1 )
+
+\\begintext
+";
+
+    let almanac = Almanac::default()
+        .load_from_bytes(bytes::Bytes::from_static(lsk))
+        .unwrap();
+    assert_eq!(almanac.leap_seconds.as_ref().unwrap().delta_at.len(), 3);
+    assert_eq!(almanac.kernel_pool.get_f64("DELTET/K"), Some(1.657e-3));
+
+    // Without SPICE compatibility, the leap seconds of hifitime are used.
+    let hifitime_epoch = Epoch::from_gregorian_utc_hms(2024, 2, 29, 12, 0, 0);
+    assert_eq!(
+        almanac.utc_to_epoch(2024, 2, 29, 12, 0, 0, 0),
+        hifitime_epoch
+    );
+    assert!(almanac.strict_lsk().is_none());
+
+    // In strict mode, the leap second of 2017 is missing, as it would be in SPICE with this LSK.
+    let strict = almanac.with_spice_compat(true);
+    let strict_epoch = strict.utc_to_epoch(2024, 2, 29, 12, 0, 0, 0);
+    assert_eq!(
+        (hifitime_epoch - strict_epoch).to_seconds(),
+        1.0,
+        "strict epoch should be one second earlier"
+    );
+    assert_eq!(
+        strict.epoch_to_utc(strict_epoch),
+        (2024, 2, 29, 12, 0, 0, 0)
+    );
+}
+
+#[test]
+fn test_pointing_profiles() {
+    use anise::astro::pointing::{PointingAttitude, PointingProfile};
+    use anise::constants::frames::{MOON_J2000, SUN_J2000};
+    use anise::math::Vector3;
+    use anise::prelude::Frame;
+    use hifitime::{TimeSeries, TimeUnits};
+    use std::sync::Arc;
+
+    // The Moon plays the spacecraft orbiting the Earth.
+    const BODY_ID: i32 = -301_000;
+    let body_frame = Frame::new(MOON_J2000.ephemeris_id, BODY_ID);
+
+    let almanac = Almanac::new("../data/de440s.bsp").unwrap();
+    let start = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+    let epochs = TimeSeries::inclusive(start, start + 1.days(), 1.hours()).collect::<Vec<Epoch>>();
+
+    for profile in [
+        PointingProfile::Nadir {
+            yaw_steering: false,
+        },
+        PointingProfile::Nadir { yaw_steering: true },
+        PointingProfile::SunPointing,
+    ] {
+        let attitude: PointingAttitude = almanac
+            .pointing_attitude(profile, MOON_J2000, EARTH_J2000, BODY_ID, &epochs)
+            .unwrap();
+        assert_eq!(attitude.samples.len(), 25);
+
+        let with_body = almanac.with_orientation_provider(Arc::new(attitude));
+        // At a sample and between two samples, where the attitude is interpolated.
+        for (epoch, tol) in [(start + 2.hours(), 1e-12), (start + 150.minutes(), 1e-5)] {
+            let dcm = with_body.rotate(MOON_J2000, body_frame, epoch).unwrap();
+            let to_earth = dcm.rot_mat
+                * almanac
+                    .translate(EARTH_J2000, MOON_J2000, epoch, None)
+                    .unwrap()
+                    .radius_km
+                    .normalize();
+            let to_sun = dcm.rot_mat
+                * almanac
+                    .translate(SUN_J2000, MOON_J2000, epoch, None)
+                    .unwrap()
+                    .radius_km
+                    .normalize();
+
+            match profile {
+                PointingProfile::Nadir { yaw_steering } => {
+                    assert!((to_earth - Vector3::z()).norm() < tol, "{profile:?}");
+                    if yaw_steering {
+                        // The Sun stays in the XZ plane, on the +X side.
+                        assert!(to_sun.y.abs() < tol, "{profile:?}");
+                        assert!(to_sun.x > 0.0, "{profile:?}");
+                    }
+                }
+                PointingProfile::SunPointing => {
+                    assert!((to_sun - Vector3::z()).norm() < tol, "{profile:?}");
+                    assert!(to_earth.y.abs() < tol, "{profile:?}");
+                }
+                PointingProfile::TargetTracking { .. } => unreachable!(),
+            }
+        }
+    }
+
+    // The attitude is only available within the epochs of the samples.
+    let attitude = almanac
+        .pointing_attitude(
+            PointingProfile::SunPointing,
+            MOON_J2000,
+            EARTH_J2000,
+            BODY_ID,
+            &epochs,
+        )
+        .unwrap();
+    let with_body = almanac.with_orientation_provider(Arc::new(attitude));
+    assert!(with_body
+        .rotate(MOON_J2000, body_frame, start + 2.days())
+        .is_err());
+}
+
+#[test]
+fn test_maneuver_data() {
+    use anise::constants::orientations::J2000;
+    use anise::math::Vector3;
+    use anise::structure::dataset::DataSetType;
+    use anise::structure::maneuver::{Maneuver, ManeuverKind, ManeuverPlan};
+    use anise::structure::ManeuverDataSet;
+    use hifitime::TimeUnits;
+    use std::path::PathBuf;
+
+    const SC_ID: i32 = -10_000;
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+
+    let mut plan = ManeuverPlan::default();
+    plan.add(Maneuver::impulsive(
+        epoch + 2.days(),
+        J2000,
+        Vector3::new(0.0, 0.0015, 0.0),
+    ));
+    plan.add(Maneuver::finite_burn(
+        epoch,
+        epoch + 20.minutes(),
+        J2000,
+        Vector3::new(0.0, 0.0, -1.1),
+        2e-4,
+    ));
+
+    let mut dataset = ManeuverDataSet::default();
+    dataset.metadata.dataset_type = DataSetType::ManeuverData;
+    dataset.push(plan, Some(SC_ID), Some("Demo SC")).unwrap();
+    dataset.set_crc32();
+
+    let path = PathBuf::from_str("../target/maneuvers.anise").unwrap();
+    dataset.save_as(&path, true).unwrap();
+
+    // The maneuvers are bundled with the ephemerides in the same Almanac.
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../target/maneuvers.anise")
+        .unwrap();
+
+    let maneuvers = almanac
+        .maneuvers(SC_ID, epoch + 10.minutes(), epoch + 3.days())
+        .unwrap();
+    assert_eq!(maneuvers.len(), 2);
+    assert_eq!(maneuvers[0].kind, ManeuverKind::FiniteBurn);
+    assert!((maneuvers[0].prop_used_kg() - 0.24).abs() < 1e-12);
+    assert_eq!(maneuvers[1].start(), epoch + 2.days());
+
+    assert!(almanac
+        .maneuvers(SC_ID, epoch + 1.days(), epoch + 1.5.days())
+        .unwrap()
+        .is_empty());
+    assert!(almanac.maneuvers(-20_000, epoch, epoch + 3.days()).is_err());
+}
+
+#[test]
+fn test_j2_propagator_provider() {
+    use anise::almanac::accuracy::AccuracyClass;
+    use anise::constants::frames::MOON_J2000;
+    use anise::ephemerides::propagator::J2Propagator;
+    use anise::prelude::Frame;
+    use hifitime::TimeUnits;
+    use std::sync::Arc;
+
+    const SC_ID: i32 = -1_002;
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    // Right after launch, only a state in low Earth orbit is known.
+    let epoch = Epoch::from_str("2025-03-04 05:06:07 UTC").unwrap();
+    let state = Orbit::keplerian(6_900.0, 0.001, 51.6, 30.0, 45.0, 0.0, epoch, eme2k);
+    let propagator =
+        J2Propagator::earth(SC_ID, state, (epoch - 1.hours(), epoch + 2.days())).unwrap();
+
+    let almanac = almanac.with_ephemeris_provider(Arc::new(propagator));
+    let sc_j2000 = Frame::from_ephem_j2000(SC_ID);
+
+    // The state at the epoch is the initial state.
+    let at_epoch = almanac
+        .translate(sc_j2000, EARTH_J2000, epoch, None)
+        .unwrap();
+    assert!((at_epoch.radius_km - state.radius_km).norm() < 1e-9);
+
+    // The spacecraft can be queried with respect to any other object, and stays in orbit.
+    let later = epoch + 1.days();
+    let wrt_earth = almanac
+        .translate(sc_j2000, EARTH_J2000, later, None)
+        .unwrap();
+    assert!((wrt_earth.rmag_km() - 6_900.0).abs() < 50.0);
+    let wrt_moon = almanac
+        .translate(sc_j2000, MOON_J2000, later, None)
+        .unwrap();
+    let earth_wrt_moon = almanac
+        .translate(EARTH_J2000, MOON_J2000, later, None)
+        .unwrap();
+    assert!((wrt_moon.radius_km - earth_wrt_moon.radius_km - wrt_earth.radius_km).norm() < 1e-6);
+
+    // The query is flagged as approximate, and is unavailable outside of the window.
+    let report = almanac
+        .accuracy_report(sc_j2000, MOON_J2000, later)
+        .unwrap();
+    assert_eq!(report.accuracy(), AccuracyClass::Analytic);
+    assert_eq!(report.analytic_sources().len(), 1);
+    assert!(almanac
+        .translate(sc_j2000, EARTH_J2000, epoch + 3.days(), None)
+        .is_err());
+}
+
+#[test]
+fn test_load_parallel() {
+    let paths = [
+        "../data/de440s.bsp",
+        "../data/de421.bsp",
+        "../data/earth_latest_high_prec.bpc",
+        "../data/pck08.pca",
+    ];
+
+    let parallel = Almanac::default().load_parallel(&paths).unwrap();
+    let mut sequential = Almanac::default();
+    for path in paths {
+        sequential = sequential.load(path).unwrap();
+    }
+
+    // The kernels are loaded in the same order, so they have the same precedence.
+    assert_eq!(parallel.num_loaded_spk(), 2);
+    assert_eq!(parallel.num_loaded_bpc(), 1);
+    for (spk_p, spk_s) in parallel.spk_data.iter().zip(sequential.spk_data.iter()) {
+        assert_eq!(
+            spk_p.as_ref().map(|spk| spk.crc32()),
+            spk_s.as_ref().map(|spk| spk.crc32())
+        );
+    }
+    assert_eq!(
+        parallel.planetary_data.len(),
+        sequential.planetary_data.len()
+    );
+
+    let epoch = Epoch::from_str("2025-01-01 00:00:00 TDB").unwrap();
+    assert_eq!(
+        parallel
+            .translate(SUN_J2000, EARTH_J2000, epoch, None)
+            .unwrap(),
+        sequential
+            .translate(SUN_J2000, EARTH_J2000, epoch, None)
+            .unwrap()
+    );
+
+    // Each file is timed, in the order in which it was loaded.
+    let timings = parallel.load_timings();
+    assert_eq!(timings.len(), paths.len());
+    for (timing, path) in timings.iter().zip(paths) {
+        assert_eq!(timing.path, path);
+        assert!(timing.size_bytes > 0);
+        println!("{timing}");
+    }
+    assert_eq!(sequential.load_timings().len(), paths.len());
+
+    // The first error is returned.
+    assert!(Almanac::default()
+        .load_parallel(&["../data/de440s.bsp", "../data/does_not_exist.bsp"])
+        .is_err());
+}
+
+#[test]
+fn test_planetary_overrides() {
+    use anise::almanac::overlay::OverriddenValue;
+
+    let almanac = Almanac::new("../data/pck08.pca").unwrap();
+    let nominal = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let epoch = Epoch::from_str("2025-01-01 00:00:00 TDB").unwrap();
+
+    // Increase the GM of the Earth by 1 ppm for a sensitivity study, without touching the planetary data.
+    let mu_km3_s2 = nominal.mu_km3_s2.unwrap() * (1.0 + 1e-6);
+    let perturbed = almanac.override_gm(399, mu_km3_s2);
+    assert_eq!(
+        perturbed.frame_from_uid(EARTH_J2000).unwrap().mu_km3_s2,
+        Some(mu_km3_s2)
+    );
+    assert_eq!(
+        perturbed
+            .frame_from_uid_at(EARTH_J2000, epoch)
+            .unwrap()
+            .mu_km3_s2,
+        Some(mu_km3_s2)
+    );
+    // The shape is unchanged, and so is the original Almanac.
+    assert_eq!(
+        perturbed.frame_from_uid(EARTH_J2000).unwrap().shape,
+        nominal.shape
+    );
+    assert_eq!(almanac.frame_from_uid(EARTH_J2000).unwrap(), nominal);
+
+    // The provenance of the override is recorded.
+    let overrides = perturbed.planetary_overrides();
+    assert_eq!(overrides.len(), 1);
+    assert_eq!(
+        overrides[0].replaced,
+        Some(OverriddenValue::Gm(nominal.mu_km3_s2.unwrap()))
+    );
+    println!("{}", overrides[0]);
+
+    // The period of an orbit changes accordingly.
+    let orbit = Orbit::keplerian(7_000.0, 0.01, 30.0, 0.0, 0.0, 0.0, epoch, nominal);
+    let perturbed_orbit = Orbit::keplerian(
+        7_000.0,
+        0.01,
+        30.0,
+        0.0,
+        0.0,
+        0.0,
+        epoch,
+        perturbed.frame_from_uid(EARTH_J2000).unwrap(),
+    );
+    let ratio =
+        perturbed_orbit.period().unwrap().to_seconds() / orbit.period().unwrap().to_seconds();
+    assert!((ratio - (1.0 + 1e-6_f64).powf(-0.5)).abs() < 1e-12);
+
+    assert_eq!(
+        perturbed
+            .clear_planetary_overrides()
+            .frame_from_uid(EARTH_J2000)
+            .unwrap(),
+        nominal
+    );
+}
+
+#[test]
+fn test_angular_diameter_limb_terminator() {
+    use anise::constants::frames::{IAU_MOON_FRAME, MOON_J2000};
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+
+    let epoch = Epoch::from_str("2024-01-01 00:00:00 TDB").unwrap();
+    let observer = Orbit::new(7000.0, 0.0, 0.0, 0.0, 7.5, 0.0, epoch, EARTH_J2000);
+
+    // The Moon and the Sun have an apparent diameter of about half a degree from the Earth.
+    let moon_deg = almanac
+        .angular_diameter_deg(IAU_MOON_FRAME, observer, None)
+        .unwrap();
+    assert!((0.48..0.57).contains(&moon_deg), "{moon_deg}");
+    let sun_deg = almanac
+        .angular_diameter_deg(SUN_J2000, observer, None)
+        .unwrap();
+    assert!((0.52..0.55).contains(&sun_deg), "{sun_deg}");
+
+    // The Moon is a sphere in these planetary constants, so its diameter is that of its mean radius.
+    let moon = almanac.frame_from_uid(MOON_J2000).unwrap();
+    let radius_km = moon.mean_equatorial_radius_km().unwrap();
+    let range_km = almanac
+        .transform_to(observer, MOON_J2000, None)
+        .unwrap()
+        .rmag_km();
+    let expected_deg = 2.0 * (radius_km / range_km).asin().to_degrees();
+    assert!((moon_deg - expected_deg).abs() < 1e-9);
+
+    // The limb is in the orientation of the observer, and perpendicular to the line of sight.
+    let limb = almanac.limb(IAU_MOON_FRAME, observer, None).unwrap();
+    assert_eq!(limb.frame.orientation_id, EARTH_J2000.orientation_id);
+    let moon_from_obs = almanac
+        .transform_to(observer, MOON_J2000, None)
+        .unwrap()
+        .radius_km;
+    assert!(limb.normal().cross(&moon_from_obs.normalize()).norm() < 1e-9);
+
+    // The terminator of a sphere is a great circle whose normal points to the Sun.
+    let terminator = almanac.terminator(IAU_MOON_FRAME, epoch, None).unwrap();
+    let sun_dir = almanac
+        .transform(SUN_J2000, IAU_MOON_FRAME, epoch, None)
+        .unwrap()
+        .r_hat();
+    assert!(terminator.normal().cross(&sun_dir).norm() < 1e-9);
+    assert!((terminator.semi_major_axis_km.norm() - radius_km).abs() < 1e-2);
+    assert!(terminator.center_km.norm() < 1e-2);
+}
+
+#[test]
+fn test_apparent_direction() {
+    use anise::constants::frames::MARS_BARYCENTER_J2000;
+    use anise::math::Vector3;
+
+    let almanac = Almanac::new("../data/de440s.bsp").unwrap();
+    let epoch = Epoch::from_str("2024-06-01 00:00:00 TDB").unwrap();
+    let earth = Orbit::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0, epoch, EARTH_J2000);
+
+    // Without corrections, this is the geometric direction.
+    let geometric = almanac
+        .transform(MARS_BARYCENTER_J2000, EARTH_J2000, epoch, None)
+        .unwrap()
+        .r_hat();
+    let direction = almanac
+        .apparent_direction(MARS_BARYCENTER_J2000, earth, None, false)
+        .unwrap();
+    assert!((direction - geometric).norm() < 1e-12);
+
+    // With light time and stellar aberration, this is the direction of the aberrated translation.
+    let aberrated = almanac
+        .transform(MARS_BARYCENTER_J2000, EARTH_J2000, epoch, Aberration::LT_S)
+        .unwrap()
+        .r_hat();
+    let apparent = almanac
+        .apparent_direction(MARS_BARYCENTER_J2000, earth, Aberration::LT_S, false)
+        .unwrap();
+    assert!(apparent.angle(&aberrated) < 1e-10);
+
+    // The deflection by the Sun is of a few milliarcseconds away from it.
+    let bent = almanac
+        .apparent_direction(MARS_BARYCENTER_J2000, earth, Aberration::LT_S, true)
+        .unwrap();
+    let bending_mas = bent.angle(&apparent).to_degrees() * 3.6e6;
+    assert!(
+        bending_mas > 0.1 && bending_mas < 100.0,
+        "{bending_mas} mas"
+    );
+
+    // Stars are only corrected for stellar aberration (at most 20.5 arcseconds from the Earth) and light bending.
+    let star = Vector3::new(0.0, 0.0, 1.0);
+    let apparent_star = almanac
+        .apparent_star_direction(star, earth, true, false)
+        .unwrap();
+    let aberration_arcsec = apparent_star.angle(&star).to_degrees() * 3600.0;
+    assert!(aberration_arcsec > 1.0 && aberration_arcsec < 21.0);
+    assert_eq!(
+        almanac
+            .apparent_star_direction(star, earth, false, false)
+            .unwrap(),
+        star
+    );
+    assert!(almanac
+        .apparent_star_direction(Vector3::zeros(), earth, true, true)
+        .is_err());
+}
+
+#[test]
+fn test_spk_coverage_window() {
+    use anise::almanac::window::Window;
+    use hifitime::TimeUnits;
+
+    let almanac = Almanac::new("../data/de440s.bsp").unwrap();
+
+    // The coverage of the Moon is a single interval, that of its domain.
+    let coverage = almanac.spk_coverage(301).unwrap();
+    let (start, end) = almanac.spk_domain(301).unwrap();
+    assert_eq!(coverage, Window::new(start, end));
+    assert!(almanac.spk_coverage(-1234).is_err());
+
+    // A mission window is restricted to the coverage of the ephemeris.
+    let mission = Window::new(end - 10.days(), end + 10.days());
+    let usable = mission.intersection(&coverage);
+    assert_eq!(usable.total_duration(), 10.days());
+    assert_eq!(
+        mission.difference(&coverage).bounds(),
+        Some((end, end + 10.days()))
+    );
+}
+
+#[test]
+fn test_concurrent_queries() {
+    use anise::constants::frames::{IAU_EARTH_FRAME, MOON_J2000};
+    use hifitime::{TimeSeries, TimeUnits};
+
+    const NUM_THREADS: usize = 8;
+
+    let reference = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+    // A single Almanac, with a cache and a transcript shared by all of the threads
+    let almanac = reference.with_cache(1.microseconds()).with_transcript();
+
+    let start = Epoch::from_str("2021-10-29 12:34:56 TDB").unwrap();
+    let epochs = TimeSeries::inclusive(start, start + 1.days(), 1.hours()).collect::<Vec<_>>();
+
+    // Sequential results, computed without the cache
+    let expected = epochs
+        .iter()
+        .map(|epoch| {
+            (
+                reference
+                    .transform(MOON_J2000, EARTH_J2000, *epoch, None)
+                    .unwrap(),
+                reference
+                    .translate(SUN_J2000, EARTH_J2000, *epoch, Aberration::LT)
+                    .unwrap(),
+                reference
+                    .rotate(IAU_EARTH_FRAME, EARTH_J2000, *epoch)
+                    .unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let compiled = almanac
+        .compile(
+            MOON_J2000,
+            EARTH_J2000,
+            EARTH_J2000,
+            start,
+            start + 1.days(),
+        )
+        .unwrap();
+
+    std::thread::scope(|scope| {
+        for thread_no in 0..NUM_THREADS {
+            let almanac = &almanac;
+            let compiled = &compiled;
+            let epochs = &epochs;
+            let expected = &expected;
+            scope.spawn(move || {
+                // Each thread goes through the epochs in a different order, so that the threads hit the cache at different times.
+                for i in 0..epochs.len() {
+                    let idx = (i + thread_no * 3) % epochs.len();
+                    let epoch = epochs[idx];
+                    let (moon, sun, dcm) = &expected[idx];
+
+                    assert_eq!(
+                        &almanac
+                            .transform(MOON_J2000, EARTH_J2000, epoch, None)
+                            .unwrap(),
+                        moon
+                    );
+                    assert_eq!(
+                        &almanac
+                            .translate(SUN_J2000, EARTH_J2000, epoch, Aberration::LT)
+                            .unwrap(),
+                        sun
+                    );
+                    assert_eq!(
+                        &almanac.rotate(IAU_EARTH_FRAME, EARTH_J2000, epoch).unwrap(),
+                        dcm
+                    );
+
+                    let evaluated = compiled.evaluate(epoch).unwrap();
+                    assert!((evaluated.radius_km - moon.radius_km).norm() < 1e-6);
+                }
+            });
+        }
+    });
+
+    // Every transform query of every thread was recorded, and the cache answered most of them.
+    let transcript = almanac.transcript.as_ref().unwrap();
+    assert_eq!(transcript.len(), NUM_THREADS * epochs.len());
+    let cache = almanac.cache.as_ref().unwrap();
+    assert!(cache.hits() > 0);
+    assert!(cache.hits() + cache.misses() >= NUM_THREADS * epochs.len());
+}
+
+#[test]
+fn test_spacecraft_mass_history() {
+    use anise::constants::orientations::J2000;
+    use anise::math::Vector3;
+    use anise::structure::dataset::DataSetType;
+    use anise::structure::maneuver::{Maneuver, ManeuverPlan};
+    use anise::structure::spacecraft::{Mass, SpacecraftData};
+    use anise::structure::SpacecraftDataSet;
+    use hifitime::TimeUnits;
+    use std::path::PathBuf;
+
+    const SC_ID: i32 = -10_001;
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+
+    let mut plan = ManeuverPlan::default();
+    plan.add(Maneuver::finite_burn(
+        epoch + 1.days(),
+        epoch + 1.days() + 20.minutes(),
+        J2000,
+        Vector3::new(0.0, 0.0, -1.1),
+        2e-4,
+    ));
+
+    // The mass depletion of the planned burns is stored with the spacecraft data.
+    let sc = SpacecraftData {
+        mass: Some(Mass::from_dry_and_prop_masses(150.0, 50.0)),
+        mass_history: plan.mass_history(epoch, Mass::from_dry_and_prop_masses(150.0, 50.0)),
+        ..Default::default()
+    };
+
+    let mut dataset = SpacecraftDataSet::default();
+    dataset.metadata.dataset_type = DataSetType::SpacecraftData;
+    dataset.push(sc, Some(SC_ID), Some("Demo SC")).unwrap();
+    dataset.set_crc32();
+
+    let path = PathBuf::from_str("../target/mass_history.anise").unwrap();
+    dataset.save_as(&path, true).unwrap();
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../target/mass_history.anise")
+        .unwrap();
+
+    assert_eq!(
+        almanac.spacecraft_mass(SC_ID, epoch).unwrap().prop_mass_kg,
+        50.0
+    );
+    // Halfway through the burn, which uses 0.24 kg of propellant
+    let mid_burn = almanac
+        .spacecraft_mass(SC_ID, epoch + 1.days() + 10.minutes())
+        .unwrap();
+    assert!((mid_burn.prop_mass_kg - 49.88).abs() < 1e-9);
+    let after_burn = almanac.spacecraft_mass(SC_ID, epoch + 2.days()).unwrap();
+    assert!((after_burn.total_mass_kg() - 199.76).abs() < 1e-9);
+
+    assert!(almanac.spacecraft_mass(-20_000, epoch).is_err());
+}
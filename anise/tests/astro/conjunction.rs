@@ -0,0 +1,70 @@
+use anise::astro::conjunction::screen;
+use anise::constants::frames::{EARTH_J2000, MOON_J2000};
+use anise::prelude::Almanac;
+use core::str::FromStr;
+use hifitime::{Epoch, TimeUnits};
+
+#[test]
+fn screen_moon_perigees() {
+    let almanac = Almanac::new("../data/de440s.bsp").unwrap();
+
+    let start = Epoch::from_str("2025-01-01 00:00:00 TDB").unwrap();
+    let end = start + 60.days();
+
+    // Every perigee of the Moon is a close approach with the Earth within 410,000 km.
+    let approaches = screen(
+        &almanac,
+        MOON_J2000,
+        EARTH_J2000,
+        (start, end),
+        1.days(),
+        410_000.0,
+    )
+    .unwrap();
+    assert!(
+        approaches.len() >= 2 && approaches.len() <= 3,
+        "{approaches:?}"
+    );
+
+    let perigees = almanac
+        .apsis_crossings(MOON_J2000, EARTH_J2000, start, end, 1.days(), None)
+        .unwrap();
+
+    for approach in &approaches {
+        println!("{approach}");
+        assert!(approach.tca >= start && approach.tca <= end);
+        assert!(approach.miss_distance_km > 350_000.0 && approach.miss_distance_km < 410_000.0);
+        // At the TCA, the relative velocity is perpendicular to the relative position.
+        let state = approach.relative_state;
+        assert!(
+            state.radius_km.dot(&state.velocity_km_s) / (state.rmag_km() * state.vmag_km_s())
+                < 1e-6
+        );
+        assert!(
+            perigees
+                .iter()
+                .any(|perigee| (perigee.epoch - approach.tca).abs() < 1.seconds()),
+            "{approach} is not a perigee"
+        );
+
+        // It is a minimum of the distance.
+        for offset in [-1.minutes(), 1.minutes()] {
+            let nearby = almanac
+                .transform(MOON_J2000, EARTH_J2000, approach.tca + offset, None)
+                .unwrap();
+            assert!(nearby.rmag_km() > approach.miss_distance_km);
+        }
+    }
+
+    // None of the perigees is within 300,000 km.
+    assert!(screen(
+        &almanac,
+        MOON_J2000,
+        EARTH_J2000,
+        (start, end),
+        1.days(),
+        300_000.0
+    )
+    .unwrap()
+    .is_empty());
+}
@@ -1,2 +1,5 @@
 mod aer;
+mod conjunction;
 mod orbit;
+#[cfg(feature = "spkezr_validation")]
+mod stellar_aberration;
@@ -0,0 +1,76 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use anise::{
+    astro::{stellar_aberration, Aberration},
+    constants::SPEED_OF_LIGHT_KM_S,
+    math::Vector3,
+};
+
+/// Deterministic xorshift generator, such that any failure can be reproduced.
+struct XorShift(u64);
+
+impl XorShift {
+    /// Returns a uniform number in [-1, 1)
+    fn next(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+    }
+
+    fn vector(&mut self, scale: f64) -> Vector3 {
+        Vector3::new(self.next(), self.next(), self.next()) * scale
+    }
+}
+
+/// Compares the stellar aberration of random targets and observers with CSPICE `stelab` (reception) and `stlabx`
+/// (transmission), from Earth-like velocities up to 90% of the speed of light.
+#[test]
+fn validate_stellar_aberration() {
+    let mut rng = XorShift(0x5EED_ABE2_2A71_0000);
+
+    let mut max_err = 0.0_f64;
+
+    for case in 0..10_000 {
+        let target_pos_km = rng.vector(10.0_f64.powf(3.0 + 7.0 * rng.next().abs()));
+        let speed_km_s = if case % 10 == 0 {
+            0.9 * SPEED_OF_LIGHT_KM_S * rng.next().abs()
+        } else {
+            50.0 * rng.next().abs()
+        };
+        let obs_vel_km_s = rng.vector(1.0).normalize() * speed_km_s;
+
+        for ab_corr in [Aberration::LT_S.unwrap(), Aberration::XLT_S.unwrap()] {
+            let anise_pos_km = stellar_aberration(target_pos_km, obs_vel_km_s, ab_corr).unwrap();
+
+            let mut pobj = [target_pos_km.x, target_pos_km.y, target_pos_km.z];
+            let mut vobs = [obs_vel_km_s.x, obs_vel_km_s.y, obs_vel_km_s.z];
+            let mut appobj = [0.0; 3];
+            unsafe {
+                if ab_corr.transmit_mode {
+                    spice::c::stlabx_c(pobj.as_mut_ptr(), vobs.as_mut_ptr(), appobj.as_mut_ptr());
+                } else {
+                    spice::c::stelab_c(pobj.as_mut_ptr(), vobs.as_mut_ptr(), appobj.as_mut_ptr());
+                }
+            }
+            let spice_pos_km = Vector3::new(appobj[0], appobj[1], appobj[2]);
+
+            let rel_err = (anise_pos_km - spice_pos_km).norm() / target_pos_km.norm();
+            assert!(
+                rel_err < 1e-12,
+                "case #{case} {ab_corr:?}: relative error {rel_err:e} for r = {target_pos_km} km and v = {obs_vel_km_s} km/s"
+            );
+            max_err = max_err.max(rel_err);
+        }
+    }
+
+    println!("maximum relative error: {max_err:e}");
+}
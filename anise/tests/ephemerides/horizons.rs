@@ -0,0 +1,169 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::fs;
+use std::path::Path;
+
+use anise::constants::orientations::{ECLIPJ2000, J2000};
+use anise::math::Vector6;
+use anise::prelude::*;
+
+/// Directory of the recorded JPL Horizons vector tables, cf. [validate_horizons_tables].
+const HORIZONS_TABLES_DIR: &str = "../data/horizons";
+
+// Horizons uses DE441, which differs from DE440 by up to a few hundred meters for the outer planets around the present epoch.
+const POSITION_EPSILON_KM: f64 = 1.0;
+const VELOCITY_EPSILON_KM_S: f64 = 1e-5;
+
+/// States of a target with respect to a center as recorded from a Horizons vector table.
+#[derive(Debug)]
+struct HorizonsTable {
+    target_id: i32,
+    center_id: i32,
+    orientation_id: i32,
+    states: Vec<(Epoch, Vector6)>,
+}
+
+/// Returns the NAIF ID in parentheses at the end of a Horizons body name, e.g. `Moon (301)`.
+fn body_id(line: &str) -> i32 {
+    let name = line.split_once(':').unwrap().1;
+    let name = name.split('{').next().unwrap().trim();
+    let start = name.rfind('(').unwrap();
+    name[start + 1..name.len() - 1].parse().unwrap()
+}
+
+/// Parses a Horizons vector table, whose header is used to find the target, center, and reference frame, and whose epochs
+/// may be in TDB or UTC, such that the table also validates the time conversions.
+fn parse_table(path: &Path) -> HorizonsTable {
+    let contents = fs::read_to_string(path).unwrap();
+
+    let mut target_id = None;
+    let mut center_id = None;
+    let mut orientation_id = None;
+    let mut in_utc = None;
+    let mut in_data = false;
+    let mut states = Vec::new();
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with("Target body name") {
+            target_id = Some(body_id(line));
+        } else if line.starts_with("Center body name") {
+            center_id = Some(body_id(line));
+        } else if line.starts_with("Reference frame") {
+            orientation_id = if line.contains("Ecliptic") {
+                Some(ECLIPJ2000)
+            } else if line.contains("ICRF") {
+                Some(J2000)
+            } else {
+                panic!("{path:?}: unsupported reference frame `{line}`");
+            };
+        } else if line.starts_with("Output units") {
+            assert!(line.contains("KM-S"), "{path:?}: units must be KM-S");
+        } else if line.starts_with("JDTDB") {
+            in_utc = Some(false);
+        } else if line.starts_with("JDUT") {
+            in_utc = Some(true);
+        } else if line == "$$SOE" {
+            in_data = true;
+        } else if line == "$$EOE" {
+            in_data = false;
+        } else if in_data && !line.is_empty() {
+            // JD, Calendar Date, X, Y, Z, VX, VY, VZ,
+            let items = line
+                .split(',')
+                .map(|item| item.trim())
+                .filter(|item| !item.is_empty())
+                .collect::<Vec<&str>>();
+            assert_eq!(items.len(), 8, "{path:?}: expected a CSV vector table");
+
+            let jde_days = items[0].parse::<f64>().unwrap();
+            let epoch = match in_utc {
+                Some(true) => Epoch::from_jde_utc(jde_days),
+                Some(false) => Epoch::from_jde_tdb(jde_days),
+                None => panic!("{path:?}: time scale of the table not found"),
+            };
+
+            let state =
+                Vector6::from_iterator(items[2..].iter().map(|x| x.parse::<f64>().unwrap()));
+            states.push((epoch, state));
+        }
+    }
+
+    assert!(!states.is_empty(), "{path:?}: no states found");
+
+    HorizonsTable {
+        target_id: target_id.expect("target body not found"),
+        center_id: center_id.expect("center body not found"),
+        orientation_id: orientation_id.expect("reference frame not found"),
+        states,
+    }
+}
+
+/// Compares the states of DE440s with the Horizons vector tables recorded in `data/horizons`, as an independent
+/// cross-check of the DE parsing and of the time conversions.
+///
+/// Each table is the text output of a Horizons vector query of geometric states, i.e. with the following parameters:
+/// `EPHEM_TYPE='VECTORS'`, `VEC_TABLE='2'`, `VEC_CORR='NONE'`, `CSV_FORMAT='YES'`, `OUT_UNITS='KM-S'`, `REF_PLANE='FRAME'`
+/// (or `'ECLIPTIC'`), `REF_SYSTEM='ICRF'`, and `TIME_TYPE='TDB'` (or `'UT'`), where the target and center must be in DE440s.
+#[test]
+fn validate_horizons_tables() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut paths = fs::read_dir(HORIZONS_TABLES_DIR)
+        .unwrap_or_else(|e| panic!("Horizons tables not found in {HORIZONS_TABLES_DIR}: {e}"))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect::<Vec<_>>();
+    assert!(
+        !paths.is_empty(),
+        "no Horizons table (*.txt) in {HORIZONS_TABLES_DIR}"
+    );
+    paths.sort();
+
+    // The planetary constants are only needed to rotate into the ecliptic frame.
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+
+    let mut num_states = 0;
+    for path in paths {
+        let table = parse_table(&path);
+        let target = Frame::new(table.target_id, table.orientation_id);
+        let observer = Frame::new(table.center_id, table.orientation_id);
+
+        for (epoch, expected) in &table.states {
+            let state = almanac.transform(target, observer, *epoch, None).unwrap();
+
+            let pos_err_km = (state.radius_km - expected.fixed_rows::<3>(0)).norm();
+            let vel_err_km_s = (state.velocity_km_s - expected.fixed_rows::<3>(3)).norm();
+
+            assert!(
+                pos_err_km < POSITION_EPSILON_KM,
+                "{path:?} @ {epoch}: position error of {pos_err_km:e} km"
+            );
+            assert!(
+                vel_err_km_s < VELOCITY_EPSILON_KM_S,
+                "{path:?} @ {epoch}: velocity error of {vel_err_km_s:e} km/s"
+            );
+            num_states += 1;
+        }
+
+        println!(
+            "{path:?}: {} states of {} wrt {} validated",
+            table.states.len(),
+            table.target_id,
+            table.center_id
+        );
+    }
+
+    println!("{num_states} Horizons states validated");
+}
@@ -8,8 +8,10 @@
  * Documentation: https://nyxspace.com/
  */
 
+mod horizons;
 mod parent_translation_verif;
 mod paths;
+mod stk;
 mod transform;
 mod translation;
 #[cfg(feature = "spkezr_validation")]
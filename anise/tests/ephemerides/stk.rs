@@ -0,0 +1,95 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use anise::constants::frames::EARTH_J2000;
+use anise::constants::orientations::J2000;
+use anise::math::Vector3;
+use anise::prelude::*;
+
+#[test]
+fn gmat_stk_hermite_ephemeris() {
+    let _ = pretty_env_logger::try_init();
+
+    let sc_id = -1000;
+    let almanac = Almanac::default()
+        .load_stk_ephemeris("../data/gmat-circular-hermite.e", sc_id)
+        .unwrap();
+
+    assert_eq!(almanac.num_loaded_spk(), 1);
+
+    let (start, end) = almanac.spk_domain(sc_id).unwrap();
+    let scenario_epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+    assert!((start - scenario_epoch).abs() < 1.microseconds());
+    assert!((end - (scenario_epoch + 1.hours())).abs() < 1.microseconds());
+
+    // The file is a circular orbit in the XY plane.
+    let mu_km3_s2 = 398600.4415_f64;
+    let radius_km = 7000.0_f64;
+    let mean_motion_rad_s = (mu_km3_s2 / radius_km.powi(3)).sqrt();
+    let speed_km_s = (mu_km3_s2 / radius_km).sqrt();
+
+    // Query between two states to exercise the interpolation.
+    for offset_s in [90.0, 1234.5, 3570.0] {
+        let epoch = scenario_epoch + offset_s.seconds();
+        let state = almanac
+            .translate(Frame::new(sc_id, J2000), EARTH_J2000, epoch, None)
+            .unwrap();
+
+        let theta = mean_motion_rad_s * (epoch - scenario_epoch).to_seconds();
+        let expected_pos_km = Vector3::new(radius_km * theta.cos(), radius_km * theta.sin(), 0.0);
+        let expected_vel_km_s =
+            Vector3::new(-speed_km_s * theta.sin(), speed_km_s * theta.cos(), 0.0);
+
+        assert!(
+            (state.radius_km - expected_pos_km).norm() < 1e-6,
+            "{offset_s} s: position error of {} km",
+            (state.radius_km - expected_pos_km).norm()
+        );
+        assert!(
+            (state.velocity_km_s - expected_vel_km_s).norm() < 1e-9,
+            "{offset_s} s: velocity error of {} km/s",
+            (state.velocity_km_s - expected_vel_km_s).norm()
+        );
+    }
+}
+
+#[test]
+fn stk_ephemeris_missing_file() {
+    assert!(Almanac::default()
+        .load_stk_ephemeris("../data/does-not-exist.e", -1000)
+        .is_err());
+}
+
+#[test]
+fn stk_ephemeris_single_state_segment() {
+    let _ = pretty_env_logger::try_init();
+
+    // Repeating the last state starts a segment of a single state, which cannot be interpolated and is skipped.
+    let contents = std::fs::read_to_string("../data/gmat-circular-hermite.e").unwrap();
+    let last_state = contents
+        .lines()
+        .filter(|line| line.starts_with(|c: char| c.is_ascii_digit()))
+        .last()
+        .unwrap();
+    let path = "../target/gmat-circular-hermite-single-state.e";
+    std::fs::write(
+        path,
+        contents.replace("END Ephemeris", &format!("{last_state}\n\nEND Ephemeris")),
+    )
+    .unwrap();
+
+    let sc_id = -1000;
+    let almanac = Almanac::default().load_stk_ephemeris(path, sc_id).unwrap();
+
+    assert_eq!(almanac.num_loaded_spk(), 1);
+    let (_, end) = almanac.spk_domain(sc_id).unwrap();
+    let scenario_epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+    assert!((end - (scenario_epoch + 1.hours())).abs() < 1.microseconds());
+}
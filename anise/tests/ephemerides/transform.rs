@@ -9,7 +9,7 @@
  */
 
 use anise::astro::{AzElRange, Occultation};
-use anise::constants::celestial_objects::{EARTH, VENUS};
+use anise::constants::celestial_objects::{EARTH, MOON, VENUS};
 use anise::constants::frames::{
     EARTH_ITRF93, EARTH_J2000, IAU_EARTH_FRAME, IAU_MOON_FRAME, MOON_J2000, SUN_J2000, VENUS_J2000,
 };
@@ -473,3 +473,64 @@ fn validate_gh_283_multi_barycenter_and_los(almanac: Almanac) {
     assert_eq!(obstructions, 2762);
     assert_eq!(no_obstructions, 4250);
 }
+
+#[rstest]
+fn apparent_state_in_body_fixed_frames(almanac: Almanac) {
+    let epoch = Epoch::from_str("2024-09-22T08:45:22 UTC").unwrap();
+
+    // Without aberration correction, this is the geometric state rotated at the query epoch.
+    let geometric = almanac
+        .apparent_state_in(MOON, EARTH, IAU_MOON_FRAME, epoch, None)
+        .unwrap();
+    let expected = almanac
+        .spk_ezr(MOON, epoch, IAU_MOON_FRAME.orientation_id, EARTH, None)
+        .unwrap();
+    assert!(geometric.rss_radius_km(&expected).unwrap() < 1e-9);
+    assert_eq!(geometric.frame.ephemeris_id, EARTH);
+    assert_eq!(
+        geometric.frame.orientation_id,
+        IAU_MOON_FRAME.orientation_id
+    );
+
+    // A frame centered on the observer is rotated at the query epoch.
+    let apparent = almanac
+        .apparent_state_in(MOON, EARTH, IAU_EARTH_FRAME, epoch, Aberration::LT_S)
+        .unwrap();
+    let expected = almanac
+        .spk_ezr(
+            MOON,
+            epoch,
+            IAU_EARTH_FRAME.orientation_id,
+            EARTH,
+            Aberration::LT_S,
+        )
+        .unwrap();
+    assert!(apparent.rss_radius_km(&expected).unwrap() < 1e-9);
+    assert!(apparent.rss_velocity_km_s(&expected).unwrap() < 1e-12);
+
+    // A frame centered on the target is rotated at the epoch at which the light left the target.
+    let apparent = almanac
+        .apparent_state_in(MOON, EARTH, IAU_MOON_FRAME, epoch, Aberration::LT)
+        .unwrap();
+    let translated = almanac
+        .translate(MOON_J2000, EARTH_J2000, epoch, Aberration::LT)
+        .unwrap();
+    let dcm = almanac
+        .rotate(EARTH_J2000, IAU_MOON_FRAME, epoch - translated.light_time())
+        .unwrap();
+    let expected = (dcm * translated).unwrap();
+    assert!(apparent.rss_radius_km(&expected).unwrap() < 1e-6);
+    assert!(apparent.rss_velocity_km_s(&expected).unwrap() < 1e-9);
+
+    // This differs from rotating at the query epoch by about a kilometer, due to the rotation of the Moon during the light time.
+    let naive = almanac
+        .spk_ezr(
+            MOON,
+            epoch,
+            IAU_MOON_FRAME.orientation_id,
+            EARTH,
+            Aberration::LT,
+        )
+        .unwrap();
+    assert!(apparent.rss_radius_km(&naive).unwrap() > 0.1);
+}
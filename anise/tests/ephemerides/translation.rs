@@ -701,3 +701,63 @@ fn type9_lagrange_query() {
         (state.velocity_km_s - expected_vel_km_s).norm()
     );
 }
+
+#[test]
+fn translation_acceleration() {
+    let ctx = Almanac::default()
+        .load("../data/de440s.bsp")
+        .and_then(|ctx| ctx.load("../data/gmat-hermite.bsp"))
+        .unwrap();
+
+    let epoch = Epoch::from_gregorian_hms(2000, 1, 1, 14, 0, 0, TimeScale::UTC);
+    let step = Unit::Second * 1;
+
+    for (target, center, mu_km3_s2) in [
+        // Chebyshev Type 2, where the Sun perturbs the lunar orbit by about one percent
+        (MOON_J2000, EARTH_J2000, 398_600.435_436 + 4_902.800_066),
+        // Hermite Type 13
+        (
+            Frame::from_ephem_j2000(-10000001),
+            EARTH_J2000,
+            398_600.435_436,
+        ),
+    ] {
+        let state = ctx
+            .translate_with_acceleration(target, center, epoch)
+            .unwrap();
+        assert_eq!(
+            state,
+            ctx.translate_geometric(target, center, epoch).unwrap()
+        );
+        let acc_km_s2 = state.acceleration_km_s2.unwrap();
+
+        // The acceleration is consistent with the velocity of the ephemeris.
+        let vel_after = ctx
+            .translate_geometric(target, center, epoch + step)
+            .unwrap()
+            .velocity_km_s;
+        let vel_before = ctx
+            .translate_geometric(target, center, epoch - step)
+            .unwrap()
+            .velocity_km_s;
+        let acc_diff_km_s2 = (vel_after - vel_before) / 2.0;
+        assert!(
+            (acc_km_s2 - acc_diff_km_s2).norm() < 1e-3 * acc_km_s2.norm(),
+            "{acc_km_s2} vs {acc_diff_km_s2}"
+        );
+
+        // And it is close to the two body acceleration.
+        let two_body_km_s2 = -mu_km3_s2 * state.radius_km / state.rmag_km().powi(3);
+        assert!(
+            (acc_km_s2 - two_body_km_s2).norm() < 0.02 * two_body_km_s2.norm(),
+            "{acc_km_s2} vs {two_body_km_s2}"
+        );
+    }
+
+    // The acceleration is only computed on request.
+    assert!(ctx
+        .translate_geometric(MOON_J2000, EARTH_J2000, epoch)
+        .unwrap()
+        .acceleration_km_s2
+        .is_none());
+}
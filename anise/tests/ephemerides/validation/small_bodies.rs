@@ -0,0 +1,37 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use super::{compare::*, validate::Validation};
+use std::env;
+
+/// Validates a small-body SPK, e.g. that of (99942) Apophis as generated by JPL Horizons, whose path is set in the `SMALL_BODY_BSP` environment variable.
+#[ignore = "Requires Rust SPICE -- must be executed serially"]
+#[test]
+fn validate_small_body_spk() {
+    let small_body_bsp = match env::var("SMALL_BODY_BSP") {
+        Ok(path) => path,
+        // Skip this test if the env var is not defined.
+        Err(_) => return,
+    };
+
+    let file_name = "spk-small-body-validation".to_string();
+    let comparator = CompareEphem::new(vec![small_body_bsp], file_name.clone(), 1_000, None);
+
+    let err_count = comparator.run();
+
+    assert_eq!(err_count, 0, "None of the queries should fail!");
+
+    let validator = Validation {
+        file_name,
+        ..Default::default()
+    };
+
+    validator.validate();
+}
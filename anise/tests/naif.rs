@@ -262,3 +262,135 @@ fn test_spk_truncate_cheby() {
         "summary 301 not removed"
     );
 }
+
+#[test]
+fn test_spk_integrity_report() {
+    use anise::naif::daf::IntegrityWarning;
+    use bytes::{Bytes, BytesMut};
+
+    let bytes = file2heap!("../data/de421.bsp").unwrap();
+    let de421 = DAF::<SPKSummaryRecord>::parse(bytes.clone()).unwrap();
+
+    let report = de421.integrity_report().unwrap();
+    assert!(report.is_clean(), "{report}");
+    assert_eq!(report.num_segments, 15);
+
+    // Corrupt the first coefficient of the second record of the first segment
+    let summary = de421.data_summaries().unwrap()[0];
+    let rsize = de421.nth_data::<Type2ChebyshevSet>(0).unwrap().rsize;
+    let mut corrupted = BytesMut::from(bytes.as_ref());
+    let offset = (summary.start_idx as usize - 1 + rsize + 2) * 8;
+    corrupted[offset..offset + 8].copy_from_slice(&f64::NAN.to_ne_bytes());
+    // And make the midpoint of the third record earlier than that of the second
+    let offset = (summary.start_idx as usize - 1 + 2 * rsize) * 8;
+    corrupted[offset..offset + 8].copy_from_slice(&f64::MIN.to_ne_bytes());
+
+    let corrupted = DAF::<SPKSummaryRecord>::parse(Bytes::from(corrupted)).unwrap();
+    let report = corrupted.integrity_report().unwrap();
+    assert_eq!(
+        report.warnings,
+        vec![
+            IntegrityWarning::NonFiniteData {
+                idx: 0,
+                id: summary.target_id,
+                count: 1
+            },
+            IntegrityWarning::NonMonotonicEpochs {
+                idx: 0,
+                id: summary.target_id,
+                position: 2
+            }
+        ]
+    );
+
+    // Segments whose data is cut off are reported, and the other segments are still checked
+    let summaries = de421.data_summaries().unwrap();
+    let (last_idx, last_summary) = summaries
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, summary)| summary.start_idx)
+        .unwrap();
+    let truncated =
+        DAF::<SPKSummaryRecord>::parse(bytes.slice(..(last_summary.start_idx as usize - 1) * 8))
+            .unwrap();
+    let truncated_report = truncated.integrity_report().unwrap();
+    assert_eq!(truncated_report.num_segments, 15);
+    assert_eq!(
+        truncated_report.warnings,
+        vec![IntegrityWarning::UnreadableData {
+            idx: last_idx,
+            id: last_summary.target_id
+        }]
+    );
+
+    // Checks on load only warn, and the reports are available from the Almanac
+    let almanac = Almanac::default()
+        .with_load_checks(true)
+        .with_spk(corrupted)
+        .unwrap();
+    let reports = almanac.integrity_reports().unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0], report);
+}
+
+#[test]
+fn test_spk_lazy_load() {
+    use anise::constants::frames::{EARTH_J2000, MOON_J2000};
+    use anise::file2mmap;
+
+    let bytes = file2mmap!("../data/de421.bsp").unwrap();
+    let lazy = DAF::<SPKSummaryRecord>::parse_lazy(bytes.clone()).unwrap();
+    let eager = DAF::<SPKSummaryRecord>::parse(bytes).unwrap();
+
+    // The checksum is not computed until requested.
+    assert_eq!(lazy.crc32_checksum, 0);
+    assert_eq!(lazy.checksum(), 0x5c78bc13);
+    assert_eq!(lazy.checksum(), eager.checksum());
+    assert!(lazy.scrub().is_ok());
+
+    // The summary records are iterated lazily, with the same summaries.
+    let records = lazy.iter_summary_records().unwrap();
+    let eager_records = eager.summary_records().unwrap();
+    assert_eq!(records.count(), eager_records.len());
+    assert_eq!(
+        lazy.iter_summary_records()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .1,
+        eager_records[0].1
+    );
+    assert_eq!(lazy.nth_summary(3).unwrap(), eager.nth_summary(3).unwrap());
+
+    // And the queries match those of an eagerly loaded file.
+    let almanac_lazy = Almanac::default().load_lazy("../data/de421.bsp").unwrap();
+    let almanac = Almanac::new("../data/de421.bsp").unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2002, 2, 7);
+    assert_eq!(
+        almanac_lazy
+            .translate(MOON_J2000, EARTH_J2000, epoch, None)
+            .unwrap(),
+        almanac
+            .translate(MOON_J2000, EARTH_J2000, epoch, None)
+            .unwrap()
+    );
+
+    // The provenance of the lazily loaded segments names the file and its checksum.
+    let provenance = almanac_lazy
+        .query_provenance(MOON_J2000, EARTH_J2000, epoch)
+        .unwrap();
+    assert!(!provenance.is_empty());
+    for segment in provenance {
+        assert_eq!(segment.path.as_deref(), Some("../data/de421.bsp"));
+        assert_eq!(segment.crc32, 0x5c78bc13);
+    }
+
+    // Files other than DAFs are loaded as usual.
+    assert!(Almanac::default()
+        .load_lazy("../data/pck08.pca")
+        .unwrap()
+        .planetary_data
+        .get_by_id(399)
+        .is_ok());
+}
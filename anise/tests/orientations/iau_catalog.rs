@@ -0,0 +1,208 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use anise::{
+    constants::orientations::J2000,
+    math::Matrix3,
+    naif::kpl::parser::convert_tpc,
+    prelude::{Almanac, Frame},
+};
+use arrow::{
+    array::{ArrayRef, Float64Array, Int32Array},
+    record_batch::RecordBatch,
+};
+use hifitime::{Epoch, TimeSeries, TimeUnits};
+use parquet::{arrow::ArrowWriter, file::properties::WriterProperties};
+use std::{fs::File, sync::Arc};
+
+// Quantiles of the angular error between the ANISE and SPICE rotations, across all bodies and epochs.
+// The maximum is looser because of the SPICE rounding of the centuries past J2000 for the fast rotating bodies, cf. IAU Moon.
+const MAX_Q75_ERR_DEG: f64 = 2e-6;
+const MAX_Q99_ERR_DEG: f64 = 7.2e-6;
+const MAX_ABS_ERR_DEG: f64 = 1e-3;
+
+/// Statistics of the errors of one body
+struct ErrorStats {
+    body_id: i32,
+    count: usize,
+    mean: f64,
+    rms: f64,
+    q99: f64,
+    max: f64,
+}
+
+impl ErrorStats {
+    fn new(body_id: i32, errors: &mut [f64]) -> Self {
+        errors.sort_by(|a, b| a.total_cmp(b));
+        let count = errors.len();
+        Self {
+            body_id,
+            count,
+            mean: errors.iter().sum::<f64>() / count as f64,
+            rms: (errors.iter().map(|e| e.powi(2)).sum::<f64>() / count as f64).sqrt(),
+            q99: quantile(errors, 0.99),
+            max: errors[count - 1],
+        }
+    }
+}
+
+/// Returns the higher quantile of the sorted errors
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    sorted[((sorted.len() - 1) as f64 * q).ceil() as usize]
+}
+
+/// Returns the angle in degrees of the rotation between both DCMs, accurate even for tiny angles.
+fn angle_between_deg(anise: &Matrix3, spice: &Matrix3) -> f64 {
+    let err = anise * spice.transpose();
+    let sin_angle = 0.5
+        * ((err[(2, 1)] - err[(1, 2)]).powi(2)
+            + (err[(0, 2)] - err[(2, 0)]).powi(2)
+            + (err[(1, 0)] - err[(0, 1)]).powi(2))
+        .sqrt();
+    let cos_angle = 0.5 * (err.trace() - 1.0);
+    sin_angle.atan2(cos_angle).to_degrees()
+}
+
+/// Sweeps every body of the PCK00011 catalog with orientation data across a century, compares the ANISE rotation
+/// from J2000 to the IAU frame of that body with the SPICE equivalent (`tisbod`), writes all of the errors to a
+/// Parquet file for further analysis, prints the statistics of each body, and validates the quantiles of all errors.
+#[ignore = "Requires Rust SPICE -- must be executed serially"]
+#[test]
+fn validate_iau_catalog_rotations() {
+    let pck = "../data/pck00011.tpc";
+    spice::furnsh(pck);
+    let planetary_data = convert_tpc(pck, "../data/gm_de440.tpc").unwrap();
+
+    let mut bodies = planetary_data
+        .data
+        .iter()
+        .filter(|body| body.pole_right_ascension.is_some())
+        .map(|body| body.object_id)
+        .collect::<Vec<i32>>();
+    bodies.sort_unstable();
+    bodies.dedup();
+
+    let almanac = Almanac {
+        planetary_data,
+        ..Default::default()
+    };
+
+    let mut batch_body_id = Vec::new();
+    let mut batch_epoch_et_s = Vec::new();
+    let mut batch_angle_err_deg = Vec::new();
+    let mut batch_rate_err = Vec::new();
+
+    let mut stats = Vec::with_capacity(bodies.len());
+
+    for body_id in bodies {
+        let frame = Frame::new(body_id, body_id);
+        let mut errors = Vec::new();
+
+        for epoch in TimeSeries::inclusive(
+            Epoch::from_tdb_duration(-0.5.centuries()),
+            Epoch::from_tdb_duration(0.5.centuries()),
+            10.days(),
+        ) {
+            let dcm = almanac
+                .rotate(frame.with_orient(J2000), frame, epoch)
+                .unwrap_or_else(|e| panic!("{body_id} @ {epoch}: {e}"));
+
+            let mut rot_data: [[f64; 6]; 6] = [[0.0; 6]; 6];
+            unsafe {
+                spice::c::tisbod_c(
+                    spice::cstr!("J2000"),
+                    body_id,
+                    epoch.to_et_seconds(),
+                    rot_data.as_mut_ptr(),
+                );
+            }
+
+            let spice_mat = Matrix3::from_fn(|i, j| rot_data[i][j]);
+            let spice_mat_dt = Matrix3::from_fn(|i, j| rot_data[i + 3][j]);
+
+            let angle_err_deg = angle_between_deg(&dcm.rot_mat, &spice_mat);
+            let rate_err = (dcm.rot_mat_dt.unwrap() - spice_mat_dt).norm();
+
+            errors.push(angle_err_deg);
+            batch_body_id.push(body_id);
+            batch_epoch_et_s.push(epoch.to_et_seconds());
+            batch_angle_err_deg.push(angle_err_deg);
+            batch_rate_err.push(rate_err);
+        }
+
+        stats.push(ErrorStats::new(body_id, &mut errors));
+    }
+
+    // Persist all of the errors
+    let file = File::create("../target/iau-catalog-validation.parquet").unwrap();
+    let batch = RecordBatch::try_from_iter(vec![
+        (
+            "body ID",
+            Arc::new(Int32Array::from(batch_body_id)) as ArrayRef,
+        ),
+        (
+            "ET Epoch (s)",
+            Arc::new(Float64Array::from(batch_epoch_et_s)) as ArrayRef,
+        ),
+        (
+            "Angular error (deg)",
+            Arc::new(Float64Array::from(batch_angle_err_deg.clone())) as ArrayRef,
+        ),
+        (
+            "Rate error",
+            Arc::new(Float64Array::from(batch_rate_err)) as ArrayRef,
+        ),
+    ])
+    .unwrap();
+    let props = WriterProperties::builder().build();
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(props)).unwrap();
+    writer.write(&batch).unwrap();
+    writer.close().unwrap();
+
+    // Report the statistics of each body, worst first
+    stats.sort_by(|a, b| b.max.total_cmp(&a.max));
+    println!("body ID\tcount\tmean (deg)\tRMS (deg)\tq99 (deg)\tmax (deg)");
+    for body in &stats {
+        println!(
+            "{}\t{}\t{:.3e}\t{:.3e}\t{:.3e}\t{:.3e}",
+            body.body_id, body.count, body.mean, body.rms, body.q99, body.max
+        );
+    }
+
+    // Validate the quantiles across all bodies
+    let mut all_errors = batch_angle_err_deg;
+    all_errors.sort_by(|a, b| a.total_cmp(b));
+
+    let q75 = quantile(&all_errors, 0.75);
+    assert!(
+        q75 <= MAX_Q75_ERR_DEG,
+        "q75 of angular error is {q75:e} deg > {MAX_Q75_ERR_DEG:e}"
+    );
+
+    let q99 = quantile(&all_errors, 0.99);
+    assert!(
+        q99 <= MAX_Q99_ERR_DEG,
+        "q99 of angular error is {q99:e} deg > {MAX_Q99_ERR_DEG:e}"
+    );
+
+    let max = all_errors[all_errors.len() - 1];
+    assert!(
+        max <= MAX_ABS_ERR_DEG,
+        "maximum angular error is {max:e} deg > {MAX_ABS_ERR_DEG:e} (body {})",
+        stats[0].body_id
+    );
+
+    // Ensure that the sweep did not skip the catalog
+    assert!(
+        stats.len() > 50,
+        "only {} bodies with orientation data in the catalog",
+        stats.len()
+    );
+}
@@ -13,6 +13,9 @@ use anise::naif::kpl::parser::convert_tpc;
 
 use anise::prelude::*;
 
+#[cfg(feature = "spkezr_validation")]
+mod iau_catalog;
+mod stk;
 mod validation;
 
 #[test]
@@ -38,6 +41,44 @@ fn test_find_root_from_pca() {
     assert!(Almanac::default().load("../data/pck08.pca").is_ok());
 }
 
+#[test]
+fn build_major_bodies_pca() {
+    use anise::constants::celestial_objects::MAJOR_BODIES;
+    use anise::structure::PlanetaryDataSet;
+
+    // Compact dataset of the major bodies, like the one converted by `Almanac::embedded_constants`
+    let planetary_data = convert_tpc("../data/pck00011.tpc", "../data/gm_de440.tpc").unwrap();
+    let entries = planetary_data.lut.entries();
+
+    let mut major_bodies = PlanetaryDataSet::default();
+    for id in MAJOR_BODIES {
+        let name = entries
+            .values()
+            .find_map(|(entry_id, name)| (*entry_id == Some(id)).then_some(name.clone()))
+            .flatten();
+        major_bodies
+            .push(
+                planetary_data.get_by_id(id).unwrap(),
+                Some(id),
+                name.as_deref(),
+            )
+            .unwrap();
+    }
+    major_bodies.set_crc32();
+
+    major_bodies
+        .save_as(
+            &PathBuf::from_str("../data/major_bodies.pca").unwrap(),
+            true,
+        )
+        .unwrap();
+    println!("Major bodies CRC32: {}", major_bodies.crc32());
+
+    let almanac = Almanac::default().load("../data/major_bodies.pca").unwrap();
+    assert_eq!(almanac.try_find_orientation_root(), Ok(J2000));
+    assert_eq!(almanac.planetary_data.len(), MAJOR_BODIES.len());
+}
+
 #[test]
 fn test_single_bpc() {
     use core::str::FromStr;
@@ -506,3 +547,905 @@ fn regression_test_issue_357_test_moon_me_j2k() {
     let (lat, long, alt) = orbit_moon_me.latlongalt().unwrap();
     dbg!(lat, long, alt);
 }
+
+#[test]
+fn test_moon_frames_de440_without_fk() {
+    use anise::constants::frames::{MOON_ME_FRAME, MOON_PA_FRAME};
+    use core::str::FromStr;
+
+    // Same setup as the regression test of issue 357, but with the built-in lunar frames instead of the FK.
+    let almanac = Almanac::new("../data/moon_pa_de440_200625.bpc")
+        .unwrap()
+        .with_moon_frames_de440()
+        .unwrap();
+
+    let fk_almanac = almanac.load("../data/moon_fk_de440.epa").unwrap();
+
+    let epoch = Epoch::from_str("2024-01-01 22:28:39").unwrap();
+
+    let moon_me_path = almanac
+        .orientation_path_to_root(MOON_ME_FRAME, epoch)
+        .unwrap();
+    assert_eq!(
+        moon_me_path.0, 3,
+        "Moon ME is an alias of the DE440 ME, defined wrt Moon PA: {:?}",
+        moon_me_path.1
+    );
+
+    let dcm = almanac.rotate(MOON_PA_FRAME, MOON_ME_FRAME, epoch).unwrap();
+
+    // sp.sxform("MOON_PA_DE440", "MOON_ME", my_et)
+    let spice_rot_mat = Matrix3::new(
+        9.99999873e-01,
+        -3.28958658e-04,
+        3.81521208e-04,
+        3.28959197e-04,
+        9.99999946e-01,
+        -1.35020600e-06,
+        -3.81520743e-04,
+        1.47571074e-06,
+        9.99999927e-01,
+    );
+
+    assert!(
+        (dcm.rot_mat - spice_rot_mat).norm() < 1e-9,
+        "err = {:.3e}",
+        (dcm.rot_mat - spice_rot_mat).norm()
+    );
+
+    // And the full rotation to J2000 matches the one using the lunar frames kernel.
+    for frame in [MOON_ME_DE440_ME421_FRAME, MOON_PA_DE440_FRAME] {
+        let builtin = almanac.rotate(frame, MOON_J2000, epoch).unwrap();
+        let from_fk = fk_almanac.rotate(frame, MOON_J2000, epoch).unwrap();
+        assert!((builtin.rot_mat - from_fk.rot_mat).norm() < 1e-12);
+    }
+}
+
+#[test]
+fn test_earth_fast_accuracy() {
+    use anise::constants::frames::EARTH_ITRF93_FAST;
+    use anise::constants::orientations::ITRF93_FAST;
+    use anise::constants::registry::MEAN_EARTH_ANGULAR_VELOCITY;
+
+    // Position error at a low Earth orbit radius for a small rotation
+    const LEO_RADIUS_KM: f64 = 7000.0;
+
+    let almanac = Almanac::new("../data/earth_latest_high_prec.bpc").unwrap();
+    let earth_rate_rad_s = MEAN_EARTH_ANGULAR_VELOCITY.value.to_radians();
+
+    let mut max_err_m: f64 = 0.0;
+    for year in [2001, 2005, 2010, 2015, 2019, 2020, 2022] {
+        let epoch = Epoch::from_gregorian_utc_at_midnight(year, 3, 1);
+        let itrf93 = almanac.rotate(EME2000, EARTH_ITRF93, epoch).unwrap();
+        let fast = almanac.rotate(EME2000, EARTH_ITRF93_FAST, epoch).unwrap();
+        assert_eq!(fast.to, ITRF93_FAST);
+
+        // Small rotation from the fast frame to ITRF93
+        let delta = itrf93.rot_mat * fast.rot_mat.transpose();
+        let pole_err_rad = (delta[(0, 2)].powi(2) + delta[(1, 2)].powi(2)).sqrt();
+        // The remaining rotation about the pole is mostly UT1-UTC, which is ignored by default.
+        let ut1_minus_utc_s = delta[(0, 1)] / earth_rate_rad_s;
+        assert!(
+            ut1_minus_utc_s.abs() < 0.9,
+            "{year}: UT1-UTC = {ut1_minus_utc_s} s"
+        );
+
+        // With UT1-UTC, the error is that of the pole.
+        let fast = almanac
+            .with_constant("UT1_MINUS_UTC", ut1_minus_utc_s)
+            .unwrap()
+            .rotate(EME2000, EARTH_ITRF93_FAST, epoch)
+            .unwrap();
+        let delta = itrf93.rot_mat * fast.rot_mat.transpose();
+        let err_rad = (delta - delta.transpose()).norm() / 2.0_f64.sqrt() / 2.0;
+        let err_m = err_rad * LEO_RADIUS_KM * 1e3;
+        println!(
+            "{year}: UT1-UTC = {ut1_minus_utc_s:.3} s\tpole error = {:.3} arcsec\terror at LEO = {err_m:.2} m",
+            pole_err_rad.to_degrees() * 3600.0
+        );
+        assert!(
+            (pole_err_rad * LEO_RADIUS_KM * 1e3 - err_m).abs() < 1.0,
+            "{year}: the error should be that of the pole"
+        );
+        max_err_m = max_err_m.max(err_m);
+    }
+
+    assert!(max_err_m < 20.0, "max error at LEO = {max_err_m:.2} m");
+}
+
+#[test]
+fn test_nutation_truncation() {
+    use anise::constants::frames::EARTH_ITRF93_FAST;
+    use anise::orientations::NutationTruncation;
+
+    let almanac = Almanac::new("../data/pck08.pca").unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 1);
+
+    for frame in [EARTH_ITRF93_FAST, IAU_MOON_FRAME] {
+        let full = almanac.rotate(EME2000, frame, epoch).unwrap();
+
+        // Neither model has more than 18 terms, so they are fully evaluated by default.
+        let eighteen = almanac
+            .with_nutation_truncation(Some(NutationTruncation::Eighteen))
+            .rotate(EME2000, frame, epoch)
+            .unwrap();
+        assert_eq!(eighteen.rot_mat, full.rot_mat);
+
+        // Truncating the series changes the orientation, by less than an arcsecond for the Earth and 0.1 degrees for the Moon.
+        let four = almanac
+            .with_nutation_truncation(Some(NutationTruncation::Four))
+            .rotate(EME2000, frame, epoch)
+            .unwrap();
+        let err_rad = (((four.rot_mat * full.rot_mat.transpose()).trace() - 1.0) / 2.0)
+            .clamp(-1.0, 1.0)
+            .acos();
+        let max_err_rad = if frame == IAU_MOON_FRAME {
+            0.1_f64.to_radians()
+        } else {
+            (1.0 / 3600.0_f64).to_radians()
+        };
+        assert!(four.rot_mat != full.rot_mat, "{frame}");
+        assert!(err_rad < max_err_rad, "{frame}: {err_rad} rad");
+    }
+}
+
+#[test]
+fn test_rotate_batch() {
+    let almanac = Almanac::new("../data/earth_latest_high_prec.bpc").unwrap();
+
+    let start = Epoch::from_gregorian_utc_at_midnight(2020, 1, 1);
+    let epochs = TimeSeries::inclusive(start, start + Unit::Hour * 2, Unit::Second * 1)
+        .collect::<Vec<Epoch>>();
+
+    let samples = almanac
+        .rotate_batch(EME2000, EARTH_ITRF93, &epochs)
+        .unwrap();
+    assert_eq!(samples.len(), epochs.len());
+
+    for (sample, epoch) in samples.iter().zip(epochs.iter()).step_by(97) {
+        assert_eq!(sample.epoch, *epoch);
+        let dcm = almanac.rotate(EME2000, EARTH_ITRF93, *epoch).unwrap();
+        assert!((DCM::from(sample.q).rot_mat - dcm.rot_mat).norm() < 1e-12);
+        assert_eq!(
+            sample.angular_velocity_rad_s,
+            Some(dcm.angular_velocity_rad_s().unwrap())
+        );
+    }
+
+    // Epochs out of the BPC coverage fail the whole batch
+    let mut epochs = epochs;
+    epochs.push(Epoch::from_gregorian_utc_at_midnight(1950, 1, 1));
+    assert!(almanac
+        .rotate_batch(EME2000, EARTH_ITRF93, &epochs)
+        .is_err());
+}
+
+#[test]
+fn test_velocity_in_frame() {
+    use anise::math::Matrix6;
+    use core::str::FromStr;
+
+    let bpc = BPC::load("../data/earth_latest_high_prec.bpc").unwrap();
+    let almanac = Almanac::from_bpc(bpc).unwrap();
+
+    let epoch = Epoch::from_str("2019-03-01T04:02:51.0 ET").unwrap();
+
+    let state = Orbit::new(
+        -2436.45, -2436.45, 6891.037, 5.088611, -5.088611, 0.0, epoch, EME2000,
+    );
+
+    let vel_itrf93 = almanac.velocity_in_frame(state, EARTH_ITRF93).unwrap();
+
+    // SPICE's sxform from J2000 to ITRF93 at that epoch.
+    let spice_sxform = Matrix6::from_row_slice(&[
+        -0.7787074378266214,
+        -0.6273856264104672,
+        0.0014149371165367297,
+        0.0,
+        0.0,
+        0.0,
+        0.6273845404742724,
+        -0.7787087230243394,
+        -0.0011675014726372779,
+        0.0,
+        0.0,
+        0.0,
+        0.0018342975179237739,
+        -0.000021432407757815408,
+        0.9999983174452183,
+        0.0,
+        0.0,
+        0.0,
+        0.000045749603091397784,
+        -0.000056784336444384685,
+        -0.0000000850112519852614,
+        -0.7787074378266214,
+        -0.6273856264104672,
+        0.0014149371165367297,
+        0.00005678424274353827,
+        0.00004574968205088016,
+        -0.00000010316798647710046,
+        0.6273845404742724,
+        -0.7787087230243394,
+        -0.0011675014726372779,
+        0.00000000008998156330541006,
+        0.00000000008643799681544929,
+        -0.00000000000016320065843054112,
+        0.0018342975179237739,
+        -0.000021432407757815408,
+        0.9999983174452183,
+    ]);
+
+    let spice_state = spice_sxform * state.to_cartesian_pos_vel();
+    let spice_vel = spice_state.fixed_rows::<3>(3).into_owned();
+
+    assert!(
+        (vel_itrf93 - spice_vel).norm() < 1e-7,
+        "got {vel_itrf93}\twant {spice_vel}"
+    );
+
+    // The frame rotation rate matters: the rotated inertial velocity differs by about omega x r, i.e. ~0.25 km/s here.
+    let dcm = almanac.rotate(EME2000, EARTH_ITRF93, epoch).unwrap();
+    assert!((vel_itrf93 - dcm.rot_mat * state.velocity_km_s).norm() > 0.2);
+
+    // And back to the inertial frame
+    let itrf93_state = almanac.rotate_to(state, EARTH_ITRF93).unwrap();
+    let vel_eme2k = almanac.velocity_in_frame(itrf93_state, EME2000).unwrap();
+    assert!((vel_eme2k - state.velocity_km_s).norm() < 1e-9);
+}
+
+#[test]
+fn test_state_transformation_matrix() {
+    use core::str::FromStr;
+
+    let bpc = BPC::load("../data/earth_latest_high_prec.bpc").unwrap();
+    let almanac = Almanac::from_bpc(bpc).unwrap();
+
+    let epoch = Epoch::from_str("2019-03-01T04:02:51.0 ET").unwrap();
+
+    let sxform = almanac
+        .state_transformation_from_to(EME2000, EARTH_ITRF93, epoch)
+        .unwrap();
+
+    // SPICE's sxform from J2000 to ITRF93 at that epoch, where the upper right block is zero.
+    let spice_rot_mat = Matrix3::new(
+        -0.7787074378266214,
+        -0.6273856264104672,
+        0.0014149371165367297,
+        0.6273845404742724,
+        -0.7787087230243394,
+        -0.0011675014726372779,
+        0.0018342975179237739,
+        -0.000021432407757815408,
+        0.9999983174452183,
+    );
+    let spice_rot_mat_dt = Matrix3::new(
+        0.000045749603091397784,
+        -0.000056784336444384685,
+        -0.0000000850112519852614,
+        0.00005678424274353827,
+        0.00004574968205088016,
+        -0.00000010316798647710046,
+        0.00000000008998156330541006,
+        0.00000000008643799681544929,
+        -0.00000000000016320065843054112,
+    );
+
+    assert!((sxform.fixed_view::<3, 3>(0, 0) - spice_rot_mat).norm() < 2.9e-9);
+    assert!((sxform.fixed_view::<3, 3>(3, 3) - spice_rot_mat).norm() < 2.9e-9);
+    assert!((sxform.fixed_view::<3, 3>(3, 0) - spice_rot_mat_dt).norm() < 2.1e-13);
+    assert_eq!(sxform.fixed_view::<3, 3>(0, 3).norm(), 0.0);
+
+    // Applying the matrix is the same as rotating the state
+    let state = Orbit::new(
+        -2436.45, -2436.45, 6891.037, 5.088611, -5.088611, 0.0, epoch, EME2000,
+    );
+    let rotated = almanac.rotate_to(state, EARTH_ITRF93).unwrap();
+    let applied = sxform * state.to_cartesian_pos_vel();
+    assert!((rotated.to_cartesian_pos_vel() - applied).norm() < 1e-12);
+
+    // The inverse transformation is the inverse matrix
+    let inverse = almanac
+        .state_transformation_from_to(EARTH_ITRF93, EME2000, epoch)
+        .unwrap();
+    assert!((inverse * sxform - anise::math::Matrix6::identity()).norm() < 1e-12);
+}
+
+#[test]
+fn test_rotation_derivative_policy() {
+    use anise::constants::frames::EARTH_ECLIPJ2000;
+    use anise::orientations::DerivativePolicy;
+    use core::str::FromStr;
+
+    let bpc = BPC::load("../data/earth_latest_high_prec.bpc").unwrap();
+    let almanac = Almanac::from_bpc(bpc).unwrap();
+
+    let epoch = Epoch::from_str("2019-03-01T04:02:51.0 ET").unwrap();
+
+    // The J2000 to ecliptic rotation is constant, so it has no derivative.
+    assert!(almanac
+        .rotate(EME2000, EARTH_ECLIPJ2000, epoch)
+        .unwrap()
+        .rot_mat_dt
+        .is_none());
+
+    assert!(almanac
+        .rotate_with_policy(
+            EME2000,
+            EARTH_ECLIPJ2000,
+            epoch,
+            DerivativePolicy::RequireDerivative
+        )
+        .is_err());
+
+    let dcm = almanac
+        .rotate_with_policy(
+            EME2000,
+            EARTH_ECLIPJ2000,
+            epoch,
+            DerivativePolicy::BestEffort,
+        )
+        .unwrap();
+    assert_eq!(dcm.rot_mat_dt, Some(Matrix3::zeros()));
+
+    // Composing the constant rotation with the BPC rotation keeps the derivative of the latter.
+    for policy in [
+        DerivativePolicy::RequireDerivative,
+        DerivativePolicy::BestEffort,
+    ] {
+        let dcm = almanac
+            .rotate_with_policy(EARTH_ECLIPJ2000, EARTH_ITRF93, epoch, policy)
+            .unwrap();
+        let ecl_to_j2k = almanac.rotate(EARTH_ECLIPJ2000, EME2000, epoch).unwrap();
+        let j2k_to_itrf = almanac.rotate(EME2000, EARTH_ITRF93, epoch).unwrap();
+
+        let expected_dt = j2k_to_itrf.rot_mat_dt.unwrap() * ecl_to_j2k.rot_mat;
+        assert!((dcm.rot_mat_dt.unwrap() - expected_dt).norm() < 1e-15);
+    }
+}
+
+#[test]
+fn test_constant_orientations() {
+    use anise::math::rotation::{r1, r3};
+    use anise::orientations::ConstantOrientation;
+
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+
+    // Spacecraft body frame rotated about Z from J2000, and an instrument rotated about X from the body frame
+    let sc_body = Frame::from_orient_ssb(-1000);
+    let instrument = Frame::from_orient_ssb(-1001);
+
+    let almanac = Almanac::default()
+        .with_constant_orientation(
+            ConstantOrientation::from_matrix(r3(30.0_f64.to_radians()), -1000, J2000),
+            Some("SC_BODY"),
+        )
+        .unwrap()
+        .with_constant_orientation(
+            ConstantOrientation::from_matrix(r1(-10.0_f64.to_radians()), -1001, -1000),
+            Some("SC_INSTRUMENT"),
+        )
+        .unwrap();
+
+    assert_eq!(almanac.try_find_orientation_root().unwrap(), J2000);
+
+    let (path_len, path) = almanac.orientation_path_to_root(instrument, epoch).unwrap();
+    assert_eq!(path_len, 2);
+    assert_eq!(path[0], Some(-1000));
+    assert_eq!(path[1], Some(J2000));
+
+    let orientation = almanac.constant_orientation(-1001).unwrap();
+    assert_eq!(orientation.parent_id(), -1000);
+
+    let dcm = almanac.rotate(instrument, EME2000, epoch).unwrap();
+    let expected = r3(30.0_f64.to_radians()) * r1(-10.0_f64.to_radians());
+    assert!(
+        (dcm.rot_mat - expected).norm() < 1e-12,
+        "got {}want {expected}",
+        dcm.rot_mat
+    );
+
+    let dcm = almanac.rotate(sc_body, instrument, epoch).unwrap();
+    assert!((dcm.rot_mat - r1(10.0_f64.to_radians())).norm() < 1e-12);
+
+    // A constant orientation may also be defined with respect to a BPC frame, e.g. an antenna on the Earth.
+    let almanac = almanac
+        .load("../data/earth_latest_high_prec.bpc")
+        .unwrap()
+        .with_constant_orientation(
+            ConstantOrientation::from_matrix(r3(45.0_f64.to_radians()), -399001, ITRF93),
+            None,
+        )
+        .unwrap();
+
+    let antenna = Frame::from_orient_ssb(-399001);
+    let dcm = almanac.rotate(antenna, EME2000, epoch).unwrap();
+    let itrf_to_j2k = almanac.rotate(EARTH_ITRF93, EME2000, epoch).unwrap();
+    assert!((dcm.rot_mat - itrf_to_j2k.rot_mat * r3(45.0_f64.to_radians())).norm() < 1e-12);
+}
+
+#[test]
+fn test_topocentric_frame() {
+    use anise::frames::TopocentricAxes;
+    use anise::math::Vector3;
+
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+
+    // Madrid Deep Space Communications Complex
+    let topo = Frame::topocentric("DSS65_TOPO", 1_399_065, 40.427, -4.251, 0.834, EARTH_ITRF93)
+        .with_axes(TopocentricAxes::SEZ);
+
+    let almanac = Almanac::default()
+        .load("../data/earth_latest_high_prec.bpc")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap()
+        .with_topocentric_frame(&topo)
+        .unwrap();
+
+    assert_eq!(
+        almanac.constant_orientation(1_399_065).unwrap().parent_id(),
+        ITRF93
+    );
+
+    let dcm = almanac.rotate(topo.frame(), EME2000, epoch).unwrap();
+    let itrf_to_j2k = almanac.rotate(EARTH_ITRF93, EME2000, epoch).unwrap();
+    assert!((dcm.rot_mat - itrf_to_j2k.rot_mat * topo.dcm_to_body_fixed().rot_mat).norm() < 1e-12);
+
+    // A point above the site is at the zenith of the topocentric frame.
+    let body_frame = almanac.frame_from_uid(EARTH_ITRF93).unwrap();
+    let topo = Frame::topocentric("DSS65_TOPO", 1_399_065, 40.427, -4.251, 0.834, body_frame)
+        .with_axes(TopocentricAxes::SEZ);
+    let site = topo.site(epoch).unwrap();
+    let above = Orbit::try_latlongalt(40.427, -4.251, 100.834, 0.0, epoch, body_frame).unwrap();
+    let rho_sez = almanac
+        .rotate(EARTH_ITRF93, topo.frame(), epoch)
+        .unwrap()
+        .rot_mat
+        * (above.radius_km - site.radius_km);
+    assert!((rho_sez - Vector3::new(0.0, 0.0, 100.0)).norm() < 1e-9);
+}
+
+#[test]
+fn test_topocentric_visibility() {
+    use anise::almanac::events::CrossingDirection;
+    use anise::frames::{HorizonMask, TopocentricAxes};
+    use anise::math::angles::azimuth_elevation_deg;
+    use hifitime::Unit;
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/earth_latest_high_prec.bpc")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+
+    let start = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+    let end = start + Unit::Day * 2;
+
+    let body_frame = almanac.frame_from_uid(EARTH_ITRF93).unwrap();
+    let site = Frame::topocentric("DSS65_TOPO", 1_399_065, 40.427, -4.251, 0.834, body_frame);
+
+    // Once registered, the frame is centered on the site, so the Moon in its SEZ axes gives the azimuth, elevation, and range.
+    let sez = site.with_axes(TopocentricAxes::SEZ);
+    let with_site = almanac.with_topocentric_frame(&sez).unwrap();
+    let moon_sez = with_site
+        .transform(MOON_J2000, sez.frame(), start, None)
+        .unwrap();
+    let rx = almanac
+        .transform(MOON_J2000, body_frame, start, None)
+        .unwrap();
+    let aer = site
+        .azimuth_elevation_range(&almanac, rx, None, None)
+        .unwrap();
+    let (azimuth_deg, elevation_deg) = azimuth_elevation_deg(&moon_sez.radius_km);
+    assert!((moon_sez.rmag_km() - aer.range_km).abs() < 1e-6);
+    assert!((azimuth_deg - aer.azimuth_deg).abs() < 1e-6);
+    assert!((elevation_deg - aer.elevation_deg).abs() < 1e-6);
+
+    let rises_and_sets = almanac
+        .visibility_crossings(&site, MOON_J2000, start, end, Unit::Minute * 10, None, None)
+        .unwrap();
+    assert!(rises_and_sets.len() >= 3);
+    for crossing in &rises_and_sets {
+        let margin_deg = almanac
+            .visibility_margin_deg(&site, MOON_J2000, crossing.epoch, None, None)
+            .unwrap();
+        assert!(margin_deg.abs() < 1e-3, "{crossing}: {margin_deg} deg");
+    }
+
+    // A mask of 10 degrees all around delays the rises and advances the sets.
+    let masked_site = site.with_horizon_mask(HorizonMask::new([(0.0, 10.0)]));
+    let masked = almanac
+        .visibility_crossings(
+            &masked_site,
+            MOON_J2000,
+            start,
+            end,
+            Unit::Minute * 10,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(masked.len(), rises_and_sets.len());
+    for (crossing, masked_crossing) in rises_and_sets.iter().zip(&masked) {
+        assert_eq!(crossing.direction, masked_crossing.direction);
+        match crossing.direction {
+            CrossingDirection::Rising => assert!(masked_crossing.epoch > crossing.epoch),
+            CrossingDirection::Falling => assert!(masked_crossing.epoch < crossing.epoch),
+        }
+    }
+}
+
+#[test]
+fn test_orientation_subset() {
+    use anise::almanac::orientation_subset::OrientationAlmanac;
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/earth_latest_high_prec.bpc")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+
+    let subset = almanac.orientation_subset();
+    assert_eq!(subset.num_loaded_bpc(), 1);
+    // The SPK is not part of the subset.
+    assert_eq!(
+        subset.memory_usage().len(),
+        almanac.memory_usage().len() - 1
+    );
+
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2020, 1, 1);
+    for to_frame in [EARTH_ITRF93, IAU_MOON_FRAME, IAU_JUPITER_FRAME] {
+        assert_eq!(
+            subset.rotate(EME2000, to_frame, epoch).unwrap(),
+            almanac.rotate(EME2000, to_frame, epoch).unwrap()
+        );
+    }
+
+    // Converting back into an Almanac keeps the orientation data only.
+    let almanac_again = Almanac::from(subset);
+    assert_eq!(almanac_again.num_loaded_spk(), 0);
+    assert!(almanac_again
+        .translate(EARTH_J2000, MOON_J2000, epoch, None)
+        .is_err());
+    assert_eq!(
+        almanac_again.rotate(EME2000, EARTH_ITRF93, epoch).unwrap(),
+        almanac.rotate(EME2000, EARTH_ITRF93, epoch).unwrap()
+    );
+
+    let from_almanac = OrientationAlmanac::from(almanac_again.load("../data/de440s.bsp").unwrap());
+    assert_eq!(from_almanac.num_loaded_bpc(), 1);
+}
+
+#[test]
+fn test_icrf_frame_bias() {
+    use anise::constants::frames::EARTH_ICRF;
+    use anise::constants::orientations::ICRF;
+
+    let almanac = Almanac::new("../data/pck08.pca").unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2025, 6, 1);
+
+    // By default, as in SPICE, the ICRF is identical to J2000.
+    let dcm = almanac.rotate(EARTH_J2000, EARTH_ICRF, epoch).unwrap();
+    assert_eq!(dcm.rot_mat, Matrix3::identity());
+    assert_eq!(dcm.from, J2000);
+    assert_eq!(dcm.to, ICRF);
+
+    // With the frame bias, the direction of a star differs by at most a few hundredths of an arcsecond.
+    let biased = almanac.with_frame_bias(true);
+    let dcm = biased.rotate(EARTH_J2000, EARTH_ICRF, epoch).unwrap();
+    let arcsec_per_rad = 180.0 / core::f64::consts::PI * 3600.0;
+    let mut max_sep_arcsec = 0.0_f64;
+    for (ra_deg, dec_deg) in [
+        (0.0_f64, 0.0_f64),
+        (90.0, 0.0),
+        (101.287, -16.716),
+        (279.234, 38.784),
+    ] {
+        let (ra, dec) = (ra_deg.to_radians(), dec_deg.to_radians());
+        let star_j2000 =
+            anise::math::Vector3::new(dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin());
+        let star_icrf = dcm.rot_mat * star_j2000;
+        let sep_arcsec = star_j2000.cross(&star_icrf).norm().asin() * arcsec_per_rad;
+        assert!(sep_arcsec < 0.1, "{sep_arcsec} arcsec");
+        max_sep_arcsec = max_sep_arcsec.max(sep_arcsec);
+    }
+    assert!(max_sep_arcsec > 0.01, "{max_sep_arcsec} arcsec");
+    println!("max J2000/ICRF separation: {max_sep_arcsec:.4} arcsec");
+
+    // The ICRF is reachable from the other frames, and the bias is undone on the way back.
+    let to_iau = biased.rotate(EARTH_ICRF, IAU_MOON_FRAME, epoch).unwrap();
+    let direct = biased.rotate(EARTH_J2000, IAU_MOON_FRAME, epoch).unwrap();
+    assert!((to_iau.rot_mat - direct.rot_mat * dcm.rot_mat.transpose()).norm() < 1e-14);
+
+    // The frame bias is never applied in SPICE compatibility mode.
+    let dcm = biased
+        .with_spice_compat(true)
+        .rotate(EARTH_J2000, EARTH_ICRF, epoch)
+        .unwrap();
+    assert_eq!(dcm.rot_mat, Matrix3::identity());
+}
+
+#[test]
+fn test_accuracy_report() {
+    use anise::almanac::accuracy::AccuracyClass;
+    use anise::constants::frames::{EARTH_ITRF93_FAST, IAU_EARTH_FRAME};
+    use anise::orientations::ConstantOrientation;
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/earth_latest_high_prec.bpc")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2020, 1, 1);
+
+    // Translations only: both objects are in the SPK.
+    let report = almanac
+        .accuracy_report(MOON_J2000, EARTH_J2000, epoch)
+        .unwrap();
+    assert!(report.rotation_sources.is_empty());
+    assert!(!report.translation_sources.is_empty());
+    assert!(report
+        .translation_sources
+        .iter()
+        .all(|source| source.description.starts_with("SPK")));
+    assert_eq!(report.accuracy(), AccuracyClass::HighPrecision);
+    println!("{report}");
+
+    // The ITRF93 frame comes from the high precision BPC.
+    let report = almanac
+        .accuracy_report(EARTH_ITRF93, EME2000, epoch)
+        .unwrap();
+    assert_eq!(report.rotation_sources.len(), 1);
+    assert!(report.rotation_sources[0].description.starts_with("BPC"));
+    assert_eq!(report.accuracy(), AccuracyClass::HighPrecision);
+    assert!(report.analytic_sources().is_empty());
+
+    // The IAU Earth frame and the fast Earth orientation are analytic models.
+    for frame in [IAU_EARTH_FRAME, EARTH_ITRF93_FAST] {
+        let report = almanac.accuracy_report(frame, EME2000, epoch).unwrap();
+        assert_eq!(report.accuracy(), AccuracyClass::Analytic);
+        assert_eq!(report.analytic_sources().len(), 1);
+        println!("{report}");
+    }
+    // The planetary data record of the IAU Earth frame is named.
+    let report = almanac
+        .accuracy_report(IAU_EARTH_FRAME, EME2000, epoch)
+        .unwrap();
+    assert!(report.rotation_sources[0].description.contains("399"));
+
+    // A constant instrument frame on the ITRF93 frame follows the actual rotation path, through the BPC.
+    let instrument = almanac
+        .with_constant_orientation(
+            ConstantOrientation::from_matrix(Matrix3::identity(), -399_100, ITRF93),
+            Some("STATION_ANTENNA"),
+        )
+        .unwrap();
+    let report = instrument
+        .accuracy_report(EARTH_ITRF93.with_orient(-399_100), EME2000, epoch)
+        .unwrap();
+    assert_eq!(report.rotation_sources.len(), 2);
+    assert_eq!(report.rotation_sources[0].id, -399_100);
+    assert_eq!(report.rotation_sources[0].accuracy, AccuracyClass::Constant);
+    assert!(report.rotation_sources[0]
+        .description
+        .contains("STATION_ANTENNA"));
+    assert_eq!(report.rotation_sources[1].id, ITRF93);
+    assert!(report.rotation_sources[1].description.starts_with("BPC"));
+    assert_eq!(report.accuracy(), AccuracyClass::Constant);
+
+    // Outside of the BPC, the query fails and so does the report.
+    assert!(almanac
+        .accuracy_report(
+            EARTH_ITRF93,
+            EME2000,
+            Epoch::from_gregorian_utc_at_midnight(1950, 1, 1)
+        )
+        .is_err());
+}
+
+#[test]
+fn test_frame_definitions() {
+    use core::str::FromStr;
+
+    use anise::math::rotation::{r3, EulerSequence};
+    use anise::orientations::FrameDefinitions;
+    use hifitime::TimeUnits;
+
+    let mut definitions = FrameDefinitions::default();
+    // Spacecraft body frame yawed by 30 degrees from J2000, and a slowly spinning frame defined by its rotation model.
+    definitions
+        .add_euler_angles(
+            -2000,
+            J2000,
+            EulerSequence::ZYX,
+            [30.0, 0.0, 0.0],
+            Some("SC_BODY"),
+        )
+        .unwrap();
+    definitions
+        .add_rotation_model(
+            -2001,
+            J2000,
+            [-90.0, 0.0, 0.0],
+            [90.0, 0.0, 0.0],
+            [0.0, 10.0, 0.0],
+            Some("SPINNER"),
+        )
+        .unwrap();
+    assert_eq!(definitions.len(), 2);
+
+    let epa = PathBuf::from_str("../target/frame_definitions.epa").unwrap();
+    let pca = PathBuf::from_str("../target/frame_definitions.pca").unwrap();
+    definitions.save_as(&epa, &pca, true).unwrap();
+    assert!(definitions.save_as(&epa, &pca, false).is_err());
+
+    // The definitions are loaded like any other kernel, and the frames are found by name.
+    let almanac = Almanac::new(epa.to_str().unwrap())
+        .unwrap()
+        .load(pca.to_str().unwrap())
+        .unwrap();
+    assert_eq!(almanac.orientation_id("sc_body").unwrap(), -2000);
+    assert_eq!(almanac.orientation_id("SPINNER").unwrap(), -2001);
+
+    let epoch = Epoch::from_tdb_duration(1.days());
+    let dcm = almanac
+        .rotate(EME2000, Frame::from_orient_ssb(-2000), epoch)
+        .unwrap();
+    assert!((dcm.rot_mat - r3(30.0_f64.to_radians())).norm() < 1e-12);
+
+    let dcm = almanac
+        .rotate(EME2000, Frame::from_orient_ssb(-2001), epoch)
+        .unwrap();
+    assert!((dcm.rot_mat - r3(10.0_f64.to_radians())).norm() < 1e-12);
+}
+
+#[test]
+fn test_spacecraft_frames() {
+    use std::io::Cursor;
+    use std::sync::Arc;
+
+    use anise::astro::pointing::PointingAttitude;
+    use anise::math::rotation::r3;
+    use anise::naif::kpl::fk::FKItem;
+    use anise::naif::kpl::parser::{convert_fk_items, parse_bytes};
+    use anise::orientations::AttitudeSample;
+    use hifitime::TimeUnits;
+
+    // Mission FK with a CK based bus frame, and a camera fixed with respect to the bus.
+    let kernel = "
+\\begindata
+   FRAME_SC_BUS                = -82000
+   FRAME_-82000_NAME           = 'SC_BUS'
+   FRAME_-82000_CLASS          = 3
+   FRAME_-82000_CLASS_ID       = -82000
+   FRAME_-82000_CENTER         = -82
+
+   FRAME_SC_CAMERA             = -82100
+   FRAME_-82100_NAME           = 'SC_CAMERA'
+   FRAME_-82100_CLASS          = 4
+   FRAME_-82100_CLASS_ID       = -82100
+   FRAME_-82100_CENTER         = -82
+   TKFRAME_-82100_RELATIVE     = 'SC_BUS'
+   TKFRAME_-82100_SPEC         = 'ANGLES'
+   TKFRAME_-82100_UNITS        = 'DEGREES'
+   TKFRAME_-82100_AXES         = ( 3, 2, 1 )
+   TKFRAME_-82100_ANGLES       = ( 90.0, 0.0, 0.0 )
+\\begintext
+";
+    let fk = convert_fk_items(parse_bytes::<_, FKItem>(&mut Cursor::new(kernel), false).unwrap())
+        .unwrap();
+
+    // The attitude of the bus is provided separately, like a CK would.
+    let epoch = Epoch::from_tdb_duration(1.days());
+    let q = DCM {
+        rot_mat: r3(45.0_f64.to_radians()),
+        rot_mat_dt: None,
+        from: J2000,
+        to: -82000,
+    }
+    .into();
+    let attitude = PointingAttitude {
+        orientation_id: -82000,
+        samples: vec![
+            AttitudeSample {
+                epoch: epoch - 1.hours(),
+                q,
+                angular_velocity_rad_s: None,
+            },
+            AttitudeSample {
+                epoch: epoch + 1.hours(),
+                q,
+                angular_velocity_rad_s: None,
+            },
+        ],
+    };
+
+    // Without its attitude, the bus frame is only named: it has no parent, but the camera is still fixed to it.
+    let unrotated = Almanac::default().with_euler_parameters(fk.clone());
+    assert!(unrotated
+        .rotate(EME2000, Frame::new(-82, -82000), epoch)
+        .is_err());
+    assert!(unrotated
+        .rotate(Frame::new(-82, -82100), Frame::new(-82, -82000), epoch)
+        .is_ok());
+
+    let almanac = Almanac::default()
+        .with_euler_parameters(fk)
+        .with_orientation_provider(Arc::new(attitude));
+
+    // The frames are resolved by name, and centered on the spacecraft without any ID arithmetic.
+    let bus = almanac.spacecraft_frame("sc_bus").unwrap();
+    assert_eq!(bus, Frame::new(-82, -82000));
+    assert_eq!(Some(bus), Frame::spacecraft_bus(-82));
+    let camera = almanac.spacecraft_frame("SC_CAMERA").unwrap();
+    assert_eq!(camera, Frame::new(-82, -82100));
+    assert!(almanac.spacecraft_frame("EME2000").is_err());
+
+    let bus_dcm = almanac.rotate(EME2000, bus, epoch).unwrap();
+    assert!((bus_dcm.rot_mat - r3(45.0_f64.to_radians())).norm() < 1e-12);
+
+    // The camera is rotated through the bus attitude.
+    let camera_dcm = almanac.rotate(EME2000, camera, epoch).unwrap();
+    let bus_to_camera = almanac.rotate(bus, camera, epoch).unwrap();
+    assert!((camera_dcm.rot_mat - bus_to_camera.rot_mat * bus_dcm.rot_mat).norm() < 1e-12);
+}
+
+#[test]
+fn test_two_vector_frame() {
+    use anise::constants::frames::SUN_J2000;
+    use anise::math::Vector3;
+    use anise::orientations::{DirectionVector, FrameAxis, TwoVectorFrame};
+    use core::str::FromStr;
+
+    let almanac = Almanac::new("../data/de440s.bsp").unwrap();
+    let epoch = Epoch::from_str("2025-06-21 12:00:00 TDB").unwrap();
+
+    // X toward the Sun and Z along the normal of the orbit of the Earth, i.e. the pole of the ecliptic.
+    let earth_sun = TwoVectorFrame::new(
+        1_000_001,
+        "EARTH_SUN",
+        FrameAxis::PlusX,
+        DirectionVector::Position {
+            observer_id: 399,
+            target_id: 10,
+        },
+        FrameAxis::PlusZ,
+        DirectionVector::OrbitNormal {
+            observer_id: 10,
+            target_id: 399,
+        },
+    );
+    let almanac = almanac.with_two_vector_frame(earth_sun);
+    let frame = Frame::new(399, almanac.orientation_id("earth_sun").unwrap());
+
+    let dcm = almanac.rotate(EARTH_J2000, frame, epoch).unwrap();
+    let sun = almanac
+        .translate(SUN_J2000, EARTH_J2000, epoch, None)
+        .unwrap();
+    let sun_in_frame = dcm.rot_mat * sun.radius_km;
+    assert!((sun_in_frame.normalize() - Vector3::x()).norm() < 1e-12);
+
+    // The frame is usable with the built-in frames: the Z axis is within a few arcseconds of the ecliptic pole.
+    let to_ecliptic = almanac
+        .rotate(frame, EARTH_J2000.with_orient(ECLIPJ2000), epoch)
+        .unwrap();
+    let z_in_ecliptic = to_ecliptic.rot_mat * Vector3::z();
+    assert!(
+        z_in_ecliptic.z.acos().to_degrees() < 0.01,
+        "{z_in_ecliptic}"
+    );
+
+    // The frame turns by about one degree per day about its Z axis.
+    let rate_deg_day = (to_ecliptic.rot_mat_dt.unwrap() * Vector3::x())
+        .norm()
+        .to_degrees()
+        * 86_400.0;
+    assert!(
+        (rate_deg_day - 0.9856).abs() < 0.05,
+        "{rate_deg_day} deg/day"
+    );
+}
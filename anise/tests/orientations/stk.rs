@@ -0,0 +1,68 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use anise::constants::celestial_objects::EARTH;
+use anise::constants::orientations::J2000;
+use anise::math::rotation::{r1, r1_dot, r3, r3_dot};
+use anise::prelude::*;
+
+#[test]
+fn stk_tumbling_attitude() {
+    let _ = pretty_env_logger::try_init();
+
+    let body_frame_id = -10_000;
+    let almanac = Almanac::default()
+        .load_stk_attitude("../data/stk-tumbling-attitude.a", body_frame_id)
+        .unwrap();
+
+    assert_eq!(almanac.num_loaded_bpc(), 1);
+
+    let (start, end) = almanac.bpc_domain(body_frame_id).unwrap();
+    let scenario_epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+    assert!((start - scenario_epoch).abs() < 1.microseconds());
+    assert!((end - (scenario_epoch + 20.minutes())).abs() < 1.microseconds());
+
+    let body_frame = Frame::new(EARTH, body_frame_id);
+
+    // The attitude file was generated from these 3-1-3 Euler angles.
+    for offset_s in [0.0, 45.0, 601.5, 1200.0] {
+        let epoch = scenario_epoch + offset_s.seconds();
+
+        let ra = 1.2;
+        let dec = 0.4 + 1e-4 * offset_s;
+        let w = 0.1 + 1e-3 * offset_s;
+
+        let dcm = almanac.rotation_to_parent(body_frame, epoch).unwrap();
+        assert_eq!(dcm.from, J2000);
+        assert_eq!(dcm.to, body_frame_id);
+
+        let expected = r3(w) * r1(dec) * r3(ra);
+        assert!(
+            (dcm.rot_mat - expected).norm() < 1e-12,
+            "{offset_s} s: {}",
+            (dcm.rot_mat - expected).norm()
+        );
+
+        let expected_dt = 1e-3 * r3_dot(w) * r1(dec) * r3(ra) + 1e-4 * r3(w) * r1_dot(dec) * r3(ra);
+        assert!((dcm.rot_mat_dt.unwrap() - expected_dt).norm() < 1e-12);
+    }
+
+    // Outside of the attitude file
+    assert!(almanac
+        .rotation_to_parent(body_frame, scenario_epoch - 1.minutes())
+        .is_err());
+}
+
+#[test]
+fn stk_attitude_missing_file() {
+    assert!(Almanac::default()
+        .load_stk_attitude("../data/does-not-exist.a", -10_000)
+        .is_err());
+}
@@ -681,6 +681,7 @@ fn validate_bpc_to_iau_rotations() {
             let state = CartesianState {
                 radius_km: Vector3::new(1234.0, 5678.9, 1234.0),
                 velocity_km_s: Vector3::new(1.2340, 5.6789, 1.2340),
+                acceleration_km_s2: None,
                 epoch,
                 frame: EARTH_ITRF93,
             };
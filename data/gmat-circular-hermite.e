@@ -0,0 +1,76 @@
+stk.v.11.0
+# WrittenBy    GMAT R2022a
+BEGIN Ephemeris
+NumberOfEphemerisPoints 61
+ScenarioEpoch           01 Jan 2024 00:00:00.000
+CentralBody             Earth
+CoordinateSystem        J2000
+InterpolationMethod     Hermite
+InterpolationSamplesM1  7
+DistanceUnit            Meters
+
+EphemerisTimePosVel
+
+0.0000000000000000e+00 7.0000000000000000e+06 0.0000000000000000e+00 0.0000000000000000e+00 -0.0000000000000000e+00 7.5460532872678359e+03 0.0000000000000000e+00
+6.0000000000000000e+01 6.9853626388946725e+06 4.5244756948673585e+05 0.0000000000000000e+00 -4.8774192414881799e+02 7.5302741005698663e+03 0.0000000000000000e+00
+1.2000000000000000e+02 6.9415117705330132e+06 9.0300295655752753e+05 0.0000000000000000e+00 -9.7344406124907221e+02 7.4830025306627176e+03 0.0000000000000000e+00
+1.8000000000000000e+02 6.8686307837707326e+06 1.3497818920985160e+06 0.0000000000000000e+00 -1.4550751548520866e+03 7.4044362721288844e+03 0.0000000000000000e+00
+2.4000000000000000e+02 6.7670244744138177e+06 1.7909159005057679e+06 0.0000000000000000e+00 -1.9306209740331124e+03 7.2949038971674699e+03 0.0000000000000000e+00
+3.0000000000000000e+02 6.6371177705310229e+06 2.2245601138430270e+06 0.0000000000000000e+00 -2.3980927371128696e+03 7.1548634814713423e+03 0.0000000000000000e+00
+3.6000000000000000e+02 6.4794539553608429e+06 2.6489009872697638e+06 0.0000000000000000e+00 -2.8555354289477168e+03 6.9849006885073240e+03 0.0000000000000000e+00
+4.2000000000000000e+02 6.2946923952449607e+06 3.0621638834728072e+06 0.0000000000000000e+00 -3.3010359770046884e+03 6.7857263202111535e+03 0.0000000000000000e+00
+4.8000000000000000e+02 6.0836057820901750e+06 3.4626204943826846e+06 0.0000000000000000e+00 -3.7327312520282053e+03 6.5581733443404537e+03 0.0000000000000000e+00
+5.4000000000000000e+02 5.8470769018911310e+06 3.8485960691362792e+06 0.0000000000000000e+00 -4.1488158598388418e+03 6.3031934109176282e+03 0.0000000000000000e+00
+6.0000000000000000e+02 5.5860949428281989e+06 4.2184764180577351e+06 0.0000000000000000e+00 -4.5475496916780585e+03 6.0218528723312811e+03 0.0000000000000000e+00
+6.6000000000000000e+02 5.3017513583804639e+06 4.5707146633662125e+06 0.0000000000000000e+00 -4.9272652015225576e+03 5.7153283237405167e+03 0.0000000000000000e+00
+7.2000000000000000e+02 4.9952353027546499e+06 4.9038377083783755e+06 0.0000000000000000e+00 -5.2863743799338008e+03 5.3849016824325809e+03 0.0000000000000000e+00
+7.8000000000000000e+02 4.6678286577195497e+06 5.2164523981506927e+06 0.0000000000000000e+00 -5.6233753952773168e+03 5.0319548267125165e+03 0.0000000000000000e+00
+8.4000000000000000e+02 4.3209006716442099e+06 5.5072513457971578e+06 0.0000000000000000e+00 -5.9368588745375519e+03 4.6579638167455123e+03 0.0000000000000000e+00
+9.0000000000000000e+02 3.9559022331599835e+06 5.7750184001161288e+06 0.0000000000000000e+00 -6.2255137974612217e+03 4.2644927215210100e+03 0.0000000000000000e+00
+9.6000000000000000e+02 3.5743598033945756e+06 6.0186337316601258e+06 0.0000000000000000e+00 -6.4881329793792820e+03 3.8531870777548070e+03 0.0000000000000000e+00
+1.0200000000000000e+03 3.1778690321541247e+06 6.2370785159781184e+06 0.0000000000000000e+00 -6.7236181197777541e+03 3.4257670080847556e+03 0.0000000000000000e+00
+1.0800000000000000e+03 2.7680880847510789e+06 6.4294391944444971e+06 0.0000000000000000e+00 -6.9309843955037950e+03 2.9840200273404007e+03 0.0000000000000000e+00
+1.1400000000000000e+03 2.3467307073857901e+06 6.5949112948554959e+06 0.0000000000000000e+00 -7.1093645793977275e+03 2.5297935669715594e+03 0.0000000000000000e+00
+1.2000000000000000e+03 1.9155590600830810e+06 6.7328027958149519e+06 0.0000000000000000e+00 -7.2580126671264979e+03 2.0649872488993742e+03 0.0000000000000000e+00
+1.2600000000000000e+03 1.4763763471572259e+06 6.8425370208391771e+06 0.0000000000000000e+00 -7.3763069970507622e+03 1.5915449411014661e+03 0.0000000000000000e+00
+1.3200000000000000e+03 1.0310192760254550e+06 6.9236550500775203e+06 0.0000000000000000e+00 -7.4637528500780036e+03 1.1114466281554839e+03 0.0000000000000000e+00
+1.3800000000000000e+03 5.8135037590804300e+05 6.9758176395625174e+06 0.0000000000000000e+00 -7.5199845186288139e+03 6.2670013073932569e+02 0.0000000000000000e+00
+1.4400000000000000e+03 1.2925020853895160e+05 6.9988066399631752e+06 0.0000000000000000e+00 -7.5447668360637253e+03 1.3933270871791558e+02 0.0000000000000000e+00
+1.5000000000000000e+03 -3.2339049653737660e+05 6.9925259089079751e+06 0.0000000000000000e+00 -7.5379961601743635e+03 -3.4861741706672115e+02 0.0000000000000000e+00
+1.5600000000000000e+03 -7.7467874919203226e+05 6.9570017130621914e+06 0.0000000000000000e+00 -7.4997008066258450e+03 -8.3510958884529578e+02 0.0000000000000000e+00
+1.6200000000000000e+03 -1.2227272153916743e+06 6.8923826182779875e+06 0.0000000000000000e+00 -7.4300409305377570e+03 -1.3181092461625988e+03 0.0000000000000000e+00
+1.6800000000000000e+03 -1.6656621102241755e+06 6.7989388682766920e+06 0.0000000000000000e+00 -7.3293078566989134e+03 -1.7955964346192313e+03 0.0000000000000000e+00
+1.7400000000000000e+03 -2.1016310342604429e+06 6.6770612544616796e+06 0.0000000000000000e+00 -7.1979228612170364e+03 -2.2655742535293020e+03 0.0000000000000000e+00
+1.8000000000000000e+03 -2.5288107204799596e+06 6.5272594815883981e+06 0.0000000000000000e+00 -7.0364354096986108e+03 -2.7260772071651345e+03 0.0000000000000000e+00
+1.8600000000000000e+03 -2.9454146593615636e+06 6.3501600361264916e+06 0.0000000000000000e+00 -6.8455208593270218e+03 -3.1751794246631712e+03 0.0000000000000000e+00
+1.9200000000000000e+03 -3.3497005702505982e+06 6.1465035662287567e+06 0.0000000000000000e+00 -6.6259776344491402e+03 -3.6110027142146382e+03 0.0000000000000000e+00
+1.9800000000000000e+03 -3.7399776877563521e+06 5.9171417842641436e+06 0.0000000000000000e+00 -6.3787238874823297e+03 -4.0317244178574542e+03 0.0000000000000000e+00
+2.0400000000000000e+03 -4.1146138327073730e+06 5.6630339048687620e+06 0.0000000000000000e+00 -6.1047936591063044e+03 -4.4355850340198831e+03 0.0000000000000000e+00
+2.1000000000000000e+03 -4.4720422380931657e+06 5.3852426334112994e+06 0.0000000000000000e+00 -5.8053325537983201e+03 -4.8208955759376486e+03 0.0000000000000000e+00
+2.1600000000000000e+03 -4.8107681014454579e+06 5.0849297216495387e+06 0.0000000000000000e+00 -5.4815929487970616e+03 -5.1860446351708206e+03 0.0000000000000000e+00
+2.2200000000000000e+03 -5.1293748362563634e+06 4.7633511091646384e+06 0.0000000000000000e+00 -5.1349287565318164e+03 -5.5295051206801791e+03 0.0000000000000000e+00
+2.2800000000000000e+03 -5.4265299962893110e+06 4.4218516708922219e+06 0.0000000000000000e+00 -4.7667897624210027e+03 -5.8498406452794961e+03 0.0000000000000000e+00
+2.3400000000000000e+03 -5.7009908480066648e+06 4.0618595927166473e+06 0.0000000000000000e+00 -4.3787155617199787e+03 -6.1457115327549336e+03 0.0000000000000000e+00
+2.4000000000000000e+03 -5.9516095678096060e+06 3.6848803986502942e+06 0.0000000000000000e+00 -3.9723291207748375e+03 -6.4158804205291981e+03 0.0000000000000000e+00
+2.4600000000000000e+03 -6.1773380423548473e+06 3.2924906545768036e+06 0.0000000000000000e+00 -3.5493299896097024e+03 -6.6592174344394934e+03 0.0000000000000000e+00
+2.5200000000000000e+03 -6.3772322518727677e+06 2.8863313748898944e+06 0.0000000000000000e+00 -3.1114871942331683e+03 -6.8747049139878518e+03 0.0000000000000000e+00
+2.5800000000000000e+03 -6.5504562181554884e+06 2.4681011596018709e+06 0.0000000000000000e+00 -2.6606318383890366e+03 -7.0614416683023228e+03 0.0000000000000000e+00
+2.6400000000000000e+03 -6.6962855007039839e+06 2.0395490906231166e+06 0.0000000000000000e+00 -2.1986494456915279e+03 -7.2186467450101782e+03 0.0000000000000000e+00
+2.7000000000000000e+03 -6.8141102264130060e+06 1.6024674169210757e+06 0.0000000000000000e+00 -1.7274720741709830e+03 -7.3456626962613200e+03 0.0000000000000000e+00
+2.7600000000000000e+03 -6.9034376401233077e+06 1.1586840591501789e+06 0.0000000000000000e+00 -1.2490702362078639e+03 -7.4419583282429994e+03 0.0000000000000000e+00
+2.8200000000000000e+03 -6.9638941653745845e+06 7.1005496509906300e+05 0.0000000000000000e+00 -7.6544465764666177e+02 -7.5071309226871681e+03 0.0000000000000000e+00
+2.8800000000000000e+03 -6.9952269667407535e+06 2.5845634792543991e+05 0.0000000000000000e+00 -2.7861791055400079e+02 -7.5409079210798109e+03 0.0000000000000000e+00
+2.9400000000000000e+03 -6.9973050072138077e+06 -1.9422316036091454e+05 0.0000000000000000e+00 2.0937404538643244e+02 -7.5431480645287775e+03 0.0000000000000000e+00
+3.0000000000000000e+03 -6.9701195962140886e+06 -6.4609040735206299e+05 0.0000000000000000e+00 6.9649037746732142e+02 -7.5138419845230401e+03 0.0000000000000000e+00
+3.0600000000000000e+03 -6.9137844259351976e+06 -1.0952556376006084e+06 0.0000000000000000e+00 1.1806939149306713e+03 -7.4531122421127802e+03 0.0000000000000000e+00
+3.1200000000000000e+03 -6.8285350958715053e+06 -1.5398403957719551e+06 0.0000000000000000e+00 1.6599596686261098e+03 -7.3612128153464228e+03 0.0000000000000000e+00
+3.1800000000000000e+03 -6.7147281275167633e+06 -1.9779853825383065e+06 0.0000000000000000e+00 2.1322832997244163e+03 -7.2385280371082399e+03 0.0000000000000000e+00
+3.2400000000000000e+03 -6.5728394733544346e+06 -2.4078582303602914e+06 0.0000000000000000e+00 2.5956895020693132e+03 -7.0855709877985755e+03 0.0000000000000000e+00
+3.3000000000000000e+03 -6.4034625263752928e+06 -2.8276611666370742e+06 0.0000000000000000e+00 3.0482402631116142e+03 -6.9029813495786693e+03 0.0000000000000000e+00
+3.3600000000000000e+03 -6.2073056384467278e+06 -3.2356385321768350e+06 0.0000000000000000e+00 3.4880429688776399e+03 -6.6915227311538729e+03 0.0000000000000000e+00
+3.4200000000000000e+03 -5.9851891579121388e+06 -3.6300841235446567e+06 0.0000000000000000e+00 3.9132583190761338e+03 -6.4520794742832431e+03 0.0000000000000000e+00
+3.4800000000000000e+03 -5.7380419988095379e+06 -4.0093483285813225e+06 0.0000000000000000e+00 4.3221080192418412e+03 -6.1856529553710880e+03 0.0000000000000000e+00
+3.5400000000000000e+03 -5.4668977560571898e+06 -4.3718450252515655e+06 0.0000000000000000e+00 4.7128822177464453e+03 -5.8933573976075004e+03 0.0000000000000000e+00
+3.6000000000000000e+03 -5.1728903828530377e+06 -4.7160582149700569e+06 0.0000000000000000e+00 5.0839466565744688e+03 -5.5764152111720477e+03 0.0000000000000000e+00
+
+END Ephemeris